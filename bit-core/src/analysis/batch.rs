@@ -0,0 +1,184 @@
+// Batch analysis - run one operation chain and pattern set over many capture files
+
+use super::frame_width::find_best_width;
+use super::framer::{split_frames, FrameCrc};
+use super::pattern_locator::Pattern;
+use crate::processing::BitOperation;
+use crate::storage::read_file_as_bits;
+use crate::utils::csv_field;
+use rayon::prelude::*;
+use std::path::{Path, PathBuf};
+
+/// Bits scanned for the best frame width, so large captures stay quick to triage
+const MAX_WIDTH_SCAN_BITS: usize = 8 * 1024 * 1024;
+
+/// Sync word and checksum used to measure the CRC pass rate
+#[derive(Debug, Clone)]
+pub struct BatchFramer {
+    pub sync: Pattern,
+    pub frame_length: usize,
+    pub crc: FrameCrc,
+}
+
+/// What to compute for every file
+#[derive(Debug, Clone)]
+pub struct BatchSettings {
+    /// Applied in order to each file's bits; source operations are skipped since
+    /// each file is its own source
+    pub operations: Vec<BitOperation>,
+    pub patterns: Vec<Pattern>,
+    pub min_width: usize,
+    pub max_width: usize,
+    pub framer: Option<BatchFramer>,
+}
+
+/// Summary of one file
+#[derive(Debug, Clone)]
+pub struct BatchRow {
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    /// Matches of each pattern, in the order of `BatchSettings::patterns`
+    pub match_counts: Vec<usize>,
+    pub best_width: Option<usize>,
+    pub frames: usize,
+    /// Fraction of checked frames whose CRC matched, None without a framer or checked frames
+    pub crc_pass_rate: Option<f64>,
+    pub error: Option<String>,
+}
+
+/// Regular files directly inside `folder`, sorted by name
+pub fn list_capture_files(folder: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = std::fs::read_dir(folder)
+        .map_err(|e| format!("Failed to read folder {}: {}", folder.display(), e))?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Analyze every file in parallel; rows come back in the order of `paths`
+pub fn run_batch(paths: &[PathBuf], settings: &BatchSettings) -> Vec<BatchRow> {
+    paths.par_iter().map(|path| analyze_file(path, settings)).collect()
+}
+
+pub fn analyze_file(path: &Path, settings: &BatchSettings) -> BatchRow {
+    let mut row = BatchRow {
+        path: path.to_path_buf(),
+        size_bytes: std::fs::metadata(path).map(|m| m.len()).unwrap_or(0),
+        match_counts: Vec::new(),
+        best_width: None,
+        frames: 0,
+        crc_pass_rate: None,
+        error: None,
+    };
+
+    let mut bits = match read_file_as_bits(path) {
+        Ok(bits) => bits,
+        Err(e) => {
+            row.error = Some(e.to_string());
+            return row;
+        }
+    };
+    for op in settings.operations.iter().filter(|op| op.is_enabled() && !op.is_source()) {
+        op.apply_in_place(&mut bits);
+    }
+
+    row.match_counts = settings.patterns
+        .iter()
+        .map(|pattern| {
+            let mut pattern = pattern.clone();
+            pattern.search(&bits);
+            pattern.matches.len()
+        })
+        .collect();
+
+    let scanned = &bits[..bits.len().min(MAX_WIDTH_SCAN_BITS)];
+    if settings.min_width <= settings.max_width && scanned.len() >= settings.min_width * 3 {
+        let analysis = find_best_width(scanned, settings.min_width, settings.max_width, 0);
+        row.best_width = (analysis.best_score > 0.0).then_some(analysis.best_width);
+    }
+
+    if let Some(framer) = &settings.framer {
+        let frames = split_frames(&bits, &mut framer.sync.clone(), framer.frame_length, framer.crc);
+        let checked: Vec<bool> = frames.iter().filter_map(|frame| frame.crc_ok).collect();
+        row.frames = frames.len();
+        if !checked.is_empty() {
+            let passed = checked.iter().filter(|ok| **ok).count();
+            row.crc_pass_rate = Some(passed as f64 / checked.len() as f64);
+        }
+    }
+
+    row
+}
+
+/// The rows as CSV, with one match-count column per pattern name
+pub fn batch_csv(rows: &[BatchRow], pattern_names: &[String]) -> String {
+    let mut header = vec!["file".to_string(), "size_bytes".to_string()];
+    header.extend(pattern_names.iter().map(|name| format!("matches: {}", name)));
+    header.extend(["best_width", "frames", "crc_pass_rate", "error"].map(String::from));
+
+    let mut csv = String::new();
+    csv.push_str(&header.iter().map(|field| csv_field(field)).collect::<Vec<_>>().join(","));
+    csv.push('\n');
+
+    for row in rows {
+        let mut fields = vec![csv_field(&row.path.display().to_string()), row.size_bytes.to_string()];
+        for i in 0..pattern_names.len() {
+            fields.push(row.match_counts.get(i).map(|count| count.to_string()).unwrap_or_default());
+        }
+        fields.push(row.best_width.map(|width| width.to_string()).unwrap_or_default());
+        fields.push(row.frames.to_string());
+        fields.push(row.crc_pass_rate.map(|rate| format!("{:.4}", rate)).unwrap_or_default());
+        fields.push(csv_field(row.error.as_deref().unwrap_or("")));
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::PatternFormat;
+
+    #[test]
+    fn test_batch_counts_matches_and_crc_passes() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut good = Vec::new();
+        for payload in [[0x01u8, 0x02], [0x03, 0x04], [0x05, 0x06]] {
+            good.extend([0x1A, 0xCF]);
+            good.extend(payload);
+            good.push(FrameCrc::Crc8.compute(&payload) as u8);
+        }
+        let mut bad = good.clone();
+        bad[2] ^= 0xFF;
+        std::fs::write(dir.path().join("a_good.bin"), &good).unwrap();
+        std::fs::write(dir.path().join("b_bad.bin"), &bad).unwrap();
+
+        let sync = Pattern::new("Sync".to_string(), PatternFormat::Hex, "0x1ACF".to_string(), 0).unwrap();
+        let settings = BatchSettings {
+            operations: Vec::new(),
+            patterns: vec![sync.clone()],
+            min_width: 4,
+            max_width: 16,
+            framer: Some(BatchFramer { sync, frame_length: 40, crc: FrameCrc::Crc8 }),
+        };
+
+        let files = list_capture_files(dir.path()).unwrap();
+        let rows = run_batch(&files, &settings);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].match_counts, vec![3]);
+        assert_eq!(rows[0].frames, 3);
+        assert_eq!(rows[0].crc_pass_rate, Some(1.0));
+        assert!((rows[1].crc_pass_rate.unwrap() - 2.0 / 3.0).abs() < 1e-9);
+
+        let csv = batch_csv(&rows, &["Sync".to_string()]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "file,size_bytes,matches: Sync,best_width,frames,crc_pass_rate,error");
+        let fields: Vec<&str> = lines[1].split(',').collect();
+        assert_eq!(fields[1..3], ["15", "3"]);
+        assert_eq!(fields[4..], ["3", "1.0000", ""]);
+    }
+}
@@ -0,0 +1,93 @@
+// Bit diff - how two versions of the same data differ
+
+use bitvec::prelude::*;
+
+/// Differences between two bit sequences compared position by position
+#[derive(Debug, Clone, PartialEq)]
+pub struct BitDiff {
+    pub a_len: usize,
+    pub b_len: usize,
+    /// Positions within the shorter length whose bits differ
+    pub differing_bits: usize,
+    /// First differing position, counting a length difference at the end of the shorter one
+    pub first_difference: Option<usize>,
+}
+
+impl BitDiff {
+    pub fn is_identical(&self) -> bool {
+        self.first_difference.is_none()
+    }
+
+    /// Share of the compared positions that differ, in percent
+    pub fn differing_percent(&self) -> f64 {
+        let compared = self.a_len.min(self.b_len);
+        if compared == 0 { 0.0 } else { self.differing_bits as f64 / compared as f64 * 100.0 }
+    }
+
+    pub fn summary(&self) -> String {
+        match self.first_difference {
+            None => format!("Identical, {} bits", self.a_len),
+            Some(first) => format!(
+                "A: {} bits, B: {} bits; {} of {} compared bits differ ({:.2}%), first at bit {}",
+                self.a_len,
+                self.b_len,
+                self.differing_bits,
+                self.a_len.min(self.b_len),
+                self.differing_percent(),
+                first
+            ),
+        }
+    }
+}
+
+pub fn bit_diff(a: &BitSlice<u8, Msb0>, b: &BitSlice<u8, Msb0>) -> BitDiff {
+    let compared = a.len().min(b.len());
+    let mut differing_bits = 0;
+    let mut first_difference = None;
+    // 64 bits at a time; the XOR of each pair of words marks the differing positions
+    let mut pos = 0;
+    while pos < compared {
+        let width = 64.min(compared - pos);
+        let diff = a[pos..pos + width].load_be::<u64>() ^ b[pos..pos + width].load_be::<u64>();
+        if diff != 0 {
+            differing_bits += diff.count_ones() as usize;
+            if first_difference.is_none() {
+                first_difference = Some(pos + (diff.leading_zeros() as usize - (64 - width)));
+            }
+        }
+        pos += width;
+    }
+    if first_difference.is_none() && a.len() != b.len() {
+        first_difference = Some(compared);
+    }
+    BitDiff { a_len: a.len(), b_len: b.len(), differing_bits, first_difference }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_counts_and_locates_differences() {
+        let a = bitvec![u8, Msb0; 1, 0, 1, 1, 0, 0, 1, 0];
+        assert!(bit_diff(&a, &a).is_identical());
+
+        let mut b = a.clone();
+        b.set(5, true);
+        b.set(7, true);
+        let diff = bit_diff(&a, &b);
+        assert_eq!(diff.differing_bits, 2);
+        assert_eq!(diff.first_difference, Some(5));
+        assert_eq!(diff.differing_percent(), 25.0);
+
+        // A prefix differs only in length
+        let diff = bit_diff(&a, &a[..6]);
+        assert_eq!((diff.differing_bits, diff.first_difference), (0, Some(6)));
+
+        // A difference past the first 64-bit word
+        let long = bitvec![u8, Msb0; 0; 100];
+        let mut other = long.clone();
+        other.set(70, true);
+        assert_eq!(bit_diff(&long, &other).first_difference, Some(70));
+    }
+}
@@ -0,0 +1,73 @@
+// Bit stream statistics - balance and run lengths of the displayed bits
+
+use bitvec::prelude::*;
+
+/// Quick statistics of a bit stream
+#[derive(Debug, Clone, PartialEq)]
+pub struct BitStats {
+    pub len: usize,
+    pub ones: usize,
+    pub longest_zeros: usize,
+    pub longest_ones: usize,
+    /// Runs of equal bits
+    pub runs: usize,
+    /// Bits the run statistics cover, from the start; less than `len` for long streams
+    pub run_bits: usize,
+}
+
+impl BitStats {
+    pub fn ones_percent(&self) -> f64 {
+        if self.len == 0 { 0.0 } else { self.ones as f64 / self.len as f64 * 100.0 }
+    }
+
+    /// Bits past the last whole byte
+    pub fn byte_remainder(&self) -> usize {
+        self.len % 8
+    }
+}
+
+/// Statistics of `bits`; run lengths are counted over the first `run_limit` bits only, since
+/// they take a step per run rather than per word
+pub fn bit_stats(bits: &BitSlice<u8, Msb0>, run_limit: usize) -> BitStats {
+    let run_bits = bits.len().min(run_limit);
+    let scanned = &bits[..run_bits];
+    let (mut longest_zeros, mut longest_ones, mut runs) = (0, 0, 0);
+    let mut pos = 0;
+    while pos < run_bits {
+        let rest = &scanned[pos..];
+        let value = rest[0];
+        // The run ends where the opposite bit first appears
+        let len = if value { rest.first_zero() } else { rest.first_one() }.unwrap_or(rest.len());
+        if value {
+            longest_ones = longest_ones.max(len);
+        } else {
+            longest_zeros = longest_zeros.max(len);
+        }
+        runs += 1;
+        pos += len;
+    }
+    BitStats { len: bits.len(), ones: bits.count_ones(), longest_zeros, longest_ones, runs, run_bits }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_runs_and_balance() {
+        let bits = bitvec![u8, Msb0; 1, 1, 0, 0, 0, 1, 0, 1, 1, 1, 1];
+        let stats = bit_stats(&bits, usize::MAX);
+        assert_eq!(stats.ones, 7);
+        assert_eq!((stats.longest_zeros, stats.longest_ones, stats.runs), (3, 4, 5));
+        assert_eq!(stats.byte_remainder(), 3);
+        assert_eq!(stats.run_bits, 11);
+
+        // Runs only over the first five bits
+        let limited = bit_stats(&bits, 5);
+        assert_eq!((limited.longest_zeros, limited.longest_ones, limited.runs, limited.run_bits), (3, 2, 2, 5));
+        assert_eq!(limited.ones, 7);
+
+        let empty = bit_stats(BitSlice::empty(), usize::MAX);
+        assert_eq!((empty.runs, empty.ones_percent()), (0, 0.0));
+    }
+}
@@ -0,0 +1,212 @@
+// Checksums - common sums, CRCs and digests over a byte range
+
+use super::framer::FrameCrc;
+use bitvec::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChecksumKind {
+    Sum8,
+    Sum16,
+    Xor8,
+    Crc8,
+    Crc16Ccitt,
+    Crc32,
+    Md5,
+    Sha1,
+}
+
+impl ChecksumKind {
+    pub const ALL: [ChecksumKind; 8] = [
+        ChecksumKind::Sum8,
+        ChecksumKind::Sum16,
+        ChecksumKind::Xor8,
+        ChecksumKind::Crc8,
+        ChecksumKind::Crc16Ccitt,
+        ChecksumKind::Crc32,
+        ChecksumKind::Md5,
+        ChecksumKind::Sha1,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChecksumKind::Sum8 => "Sum-8",
+            ChecksumKind::Sum16 => "Sum-16",
+            ChecksumKind::Xor8 => "XOR-8",
+            ChecksumKind::Crc8 => "CRC-8",
+            ChecksumKind::Crc16Ccitt => "CRC-16/CCITT",
+            ChecksumKind::Crc32 => "CRC-32",
+            ChecksumKind::Md5 => "MD5",
+            ChecksumKind::Sha1 => "SHA-1",
+        }
+    }
+
+    /// The checksum of `bytes`, most significant byte first
+    pub fn compute(&self, bytes: &[u8]) -> Vec<u8> {
+        match self {
+            ChecksumKind::Sum8 => vec![bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte))],
+            ChecksumKind::Sum16 => bytes
+                .iter()
+                .fold(0u16, |sum, byte| sum.wrapping_add(*byte as u16))
+                .to_be_bytes()
+                .to_vec(),
+            ChecksumKind::Xor8 => vec![bytes.iter().fold(0u8, |xor, byte| xor ^ byte)],
+            ChecksumKind::Crc8 => vec![FrameCrc::Crc8.compute(bytes) as u8],
+            ChecksumKind::Crc16Ccitt => (FrameCrc::Crc16Ccitt.compute(bytes) as u16).to_be_bytes().to_vec(),
+            ChecksumKind::Crc32 => FrameCrc::Crc32.compute(bytes).to_be_bytes().to_vec(),
+            ChecksumKind::Md5 => md5(bytes).to_vec(),
+            ChecksumKind::Sha1 => sha1(bytes).to_vec(),
+        }
+    }
+}
+
+/// Checksums of a bit range
+#[derive(Debug, Clone)]
+pub struct ChecksumReport {
+    pub start: usize,
+    pub bits: usize,
+    /// Zero bits appended to complete the last byte
+    pub padding_bits: usize,
+    pub results: Vec<(ChecksumKind, Vec<u8>)>,
+}
+
+/// Every checksum over `bits` read as bytes, the last one zero-padded; `start` is recorded
+/// for display
+pub fn checksum_report(bits: &BitSlice<u8, Msb0>, start: usize) -> ChecksumReport {
+    let bytes: Vec<u8> = bits
+        .chunks(8)
+        .map(|chunk| chunk.load_be::<u8>() << (8 - chunk.len()))
+        .collect();
+    ChecksumReport {
+        start,
+        bits: bits.len(),
+        padding_bits: bytes.len() * 8 - bits.len(),
+        results: ChecksumKind::ALL.iter().map(|kind| (*kind, kind.compute(&bytes))).collect(),
+    }
+}
+
+/// Message padded to whole 64-byte blocks with the bit length appended, as MD5 and SHA-1 do
+fn padded_blocks(bytes: &[u8], big_endian_length: bool) -> Vec<u8> {
+    let bit_len = (bytes.len() as u64).wrapping_mul(8);
+    let mut message = bytes.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    if big_endian_length {
+        message.extend(bit_len.to_be_bytes());
+    } else {
+        message.extend(bit_len.to_le_bytes());
+    }
+    message
+}
+
+fn md5(bytes: &[u8]) -> [u8; 16] {
+    const SHIFTS: [u32; 64] = [
+        7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22,
+        5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20,
+        4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23,
+        6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+    ];
+    let constants: Vec<u32> = (0..64).map(|i| ((i as f64 + 1.0).sin().abs() * 4_294_967_296.0) as u32).collect();
+
+    let mut state: [u32; 4] = [0x6745_2301, 0xEFCD_AB89, 0x98BA_DCFE, 0x1032_5476];
+    for block in padded_blocks(bytes, false).chunks(64) {
+        let words: Vec<u32> = block.chunks(4).map(|w| u32::from_le_bytes([w[0], w[1], w[2], w[3]])).collect();
+        let [mut a, mut b, mut c, mut d] = state;
+        for i in 0..64 {
+            let (f, g) = match i / 16 {
+                0 => ((b & c) | (!b & d), i),
+                1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+                2 => (b ^ c ^ d, (3 * i + 5) % 16),
+                _ => (c ^ (b | !d), (7 * i) % 16),
+            };
+            let rotated = a
+                .wrapping_add(f)
+                .wrapping_add(constants[i])
+                .wrapping_add(words[g])
+                .rotate_left(SHIFTS[i]);
+            (a, b, c, d) = (d, b.wrapping_add(rotated), b, c);
+        }
+        for (word, add) in state.iter_mut().zip([a, b, c, d]) {
+            *word = word.wrapping_add(add);
+        }
+    }
+
+    let mut digest = [0u8; 16];
+    for (out, word) in digest.chunks_mut(4).zip(state) {
+        out.copy_from_slice(&word.to_le_bytes());
+    }
+    digest
+}
+
+fn sha1(bytes: &[u8]) -> [u8; 20] {
+    let mut state: [u32; 5] = [0x6745_2301, 0xEFCD_AB89, 0x98BA_DCFE, 0x1032_5476, 0xC3D2_E1F0];
+    for block in padded_blocks(bytes, true).chunks(64) {
+        let mut words = [0u32; 80];
+        for (i, w) in block.chunks(4).enumerate() {
+            words[i] = u32::from_be_bytes([w[0], w[1], w[2], w[3]]);
+        }
+        for i in 16..80 {
+            words[i] = (words[i - 3] ^ words[i - 8] ^ words[i - 14] ^ words[i - 16]).rotate_left(1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e] = state;
+        for (i, word) in words.iter().enumerate() {
+            let (f, k) = match i / 20 {
+                0 => ((b & c) | (!b & d), 0x5A82_7999),
+                1 => (b ^ c ^ d, 0x6ED9_EBA1),
+                2 => ((b & c) | (b & d) | (c & d), 0x8F1B_BCDC),
+                _ => (b ^ c ^ d, 0xCA62_C1D6),
+            };
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(*word);
+            (a, b, c, d, e) = (temp, a, b.rotate_left(30), c, d);
+        }
+        for (word, add) in state.iter_mut().zip([a, b, c, d, e]) {
+            *word = word.wrapping_add(add);
+        }
+    }
+
+    let mut digest = [0u8; 20];
+    for (out, word) in digest.chunks_mut(4).zip(state) {
+        out.copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    #[test]
+    fn test_digests_match_reference_vectors() {
+        assert_eq!(hex(&md5(b"")), "d41d8cd98f00b204e9800998ecf8427e");
+        assert_eq!(hex(&md5(b"abc")), "900150983cd24fb0d6963f7d28e17f72");
+        let long = b"12345678901234567890123456789012345678901234567890123456789012345678901234567890";
+        assert_eq!(hex(&md5(long)), "57edf4a22be3c955ac49da2e2107b67a");
+
+        assert_eq!(hex(&sha1(b"abc")), "a9993e364706816aba3e25717850c26c9cd0d89d");
+        let two_blocks = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+        assert_eq!(hex(&sha1(two_blocks)), "84983e441c3bd26ebaae4aa1f95129e5e54670f1");
+    }
+
+    #[test]
+    fn test_report_pads_partial_byte() {
+        let bytes = [0x12u8, 0x34, 0xF0];
+        let report = checksum_report(&bytes.view_bits::<Msb0>()[..20], 8);
+        assert_eq!(report.padding_bits, 4);
+        let result = |kind| report.results.iter().find(|(k, _)| *k == kind).map(|(_, value)| value.clone()).unwrap();
+        assert_eq!(result(ChecksumKind::Sum8), vec![0x36]);
+        assert_eq!(result(ChecksumKind::Sum16), vec![0x01, 0x36]);
+        assert_eq!(result(ChecksumKind::Xor8), vec![0x12 ^ 0x34 ^ 0xF0]);
+        assert_eq!(result(ChecksumKind::Crc32).len(), 4);
+    }
+}
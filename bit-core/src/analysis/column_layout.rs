@@ -0,0 +1,80 @@
+// Column auto-layout - a first guess at a frame's fields, anchored on its sync pattern
+
+use crate::view::ByteColumn;
+
+/// Frame-relative columns for frames of `frame_bits` bits whose sync pattern of `sync_bits`
+/// bits starts at absolute bit `sync_position`: a sync field, then the rest of the frame cut
+/// into `field_bits`-bit fields (the last may be shorter). Columns are placed at offsets
+/// relative to bit 0, so a field that would cross the frame boundary is split in two.
+/// Colors cycle through `palette`.
+pub fn anchored_columns(
+    sync_label: &str,
+    sync_position: usize,
+    sync_bits: usize,
+    frame_bits: usize,
+    field_bits: usize,
+    palette: &[[u8; 3]],
+) -> Result<Vec<ByteColumn>, String> {
+    if frame_bits == 0 || field_bits == 0 {
+        return Err("Frame and field sizes must be at least 1 bit".to_string());
+    }
+    if sync_bits == 0 || sync_bits > frame_bits {
+        return Err(format!("A {}-bit sync pattern doesn't fit in a {}-bit frame", sync_bits, frame_bits));
+    }
+
+    let mut fields = vec![(sync_label.to_string(), 0, sync_bits)];
+    let mut start = sync_bits;
+    while start < frame_bits {
+        let len = field_bits.min(frame_bits - start);
+        fields.push((format!("Field {}", fields.len()), start, len));
+        start += len;
+    }
+
+    let shift = sync_position % frame_bits;
+    let mut columns = Vec::new();
+    for (index, (label, start, len)) in fields.into_iter().enumerate() {
+        let color = palette.get(index % palette.len().max(1)).copied().unwrap_or([100, 150, 200]);
+        let start = (start + shift) % frame_bits;
+        if start + len <= frame_bits {
+            columns.push(ByteColumn::new(label, start, start + len - 1, color));
+        } else {
+            let head = frame_bits - start;
+            columns.push(ByteColumn::new(label.clone(), start, frame_bits - 1, color));
+            columns.push(ByteColumn::new(format!("{} (cont.)", label), 0, len - head - 1, color));
+        }
+    }
+    columns.sort_by_key(|column| column.bit_start);
+    Ok(columns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(columns: &[ByteColumn]) -> Vec<(&str, usize, usize)> {
+        columns.iter().map(|column| (column.label.as_str(), column.bit_start, column.bit_end)).collect()
+    }
+
+    #[test]
+    fn test_fields_follow_the_sync() {
+        let columns = anchored_columns("Sync", 64, 16, 64, 16, &[[1, 2, 3]]).unwrap();
+        assert_eq!(ranges(&columns), vec![
+            ("Sync", 0, 15),
+            ("Field 1", 16, 31),
+            ("Field 2", 32, 47),
+            ("Field 3", 48, 63),
+        ]);
+
+        // A sync 8 bits into each frame, with a short last field that wraps past the boundary
+        let columns = anchored_columns("Sync", 8, 8, 36, 16, &[]).unwrap();
+        assert_eq!(ranges(&columns), vec![
+            ("Field 2 (cont.)", 0, 7),
+            ("Sync", 8, 15),
+            ("Field 1", 16, 31),
+            ("Field 2", 32, 35),
+        ]);
+
+        assert!(anchored_columns("Sync", 0, 32, 16, 8, &[]).is_err());
+        assert!(anchored_columns("Sync", 0, 8, 16, 0, &[]).is_err());
+    }
+}
@@ -0,0 +1,111 @@
+// Frame distance - how many bits change from each frame to the next, which shows super-frames
+// and mode changes as spikes repeating every so many frames
+
+use bitvec::prelude::*;
+
+/// Most distances the period search looks at, from the start of the data
+const PERIOD_SAMPLE: usize = 65_536;
+/// Longest super-frame period looked for, in frames
+const MAX_SUPER_FRAME_PERIOD: usize = 1024;
+/// Autocorrelation a period needs to be reported
+const MIN_PERIOD_CORRELATION: f64 = 0.3;
+/// Shorter periods within this share of the best one's correlation win, so the fundamental
+/// is reported instead of a multiple of it
+const HARMONIC_SHARE: f64 = 0.9;
+
+/// Hamming distances between consecutive frames
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameDistances {
+    pub frame_length: usize,
+    /// `distances[i]` is the number of bits that differ between frame `i + 1` and frame `i`
+    pub distances: Vec<usize>,
+    /// Frames after which the distances repeat, if they clearly do
+    pub period: Option<usize>,
+}
+
+impl FrameDistances {
+    pub fn mean(&self) -> f64 {
+        if self.distances.is_empty() {
+            return 0.0;
+        }
+        self.distances.iter().sum::<usize>() as f64 / self.distances.len() as f64
+    }
+
+    pub fn max(&self) -> usize {
+        self.distances.iter().copied().max().unwrap_or(0)
+    }
+}
+
+/// Distances between each `frame_length`-bit frame of `bits` and the one before it; a trailing
+/// partial frame is ignored
+pub fn frame_distances(bits: &BitSlice<u8, Msb0>, frame_length: usize) -> FrameDistances {
+    let frames: Vec<&BitSlice<u8, Msb0>> = if frame_length == 0 {
+        Vec::new()
+    } else {
+        bits.chunks_exact(frame_length).collect()
+    };
+    let distances: Vec<usize> = frames.windows(2).map(|pair| hamming_distance(pair[0], pair[1])).collect();
+    let period = distance_period(&distances);
+    FrameDistances { frame_length, distances, period }
+}
+
+/// Bits that differ between `a` and `b`, which have the same length
+fn hamming_distance(a: &BitSlice<u8, Msb0>, b: &BitSlice<u8, Msb0>) -> usize {
+    a.chunks(64)
+        .zip(b.chunks(64))
+        .map(|(a, b)| (a.load_be::<u64>() ^ b.load_be::<u64>()).count_ones() as usize)
+        .sum()
+}
+
+/// The lag with the highest autocorrelation of `distances`, when it repeats at least three times
+fn distance_period(distances: &[usize]) -> Option<usize> {
+    let sample = &distances[..distances.len().min(PERIOD_SAMPLE)];
+    let n = sample.len();
+    let mean = sample.iter().sum::<usize>() as f64 / n.max(1) as f64;
+    let centered: Vec<f64> = sample.iter().map(|&d| d as f64 - mean).collect();
+    let variance = centered.iter().map(|d| d * d).sum::<f64>() / n.max(1) as f64;
+    if variance == 0.0 {
+        return None;
+    }
+
+    let correlations: Vec<(usize, f64)> = (2..=MAX_SUPER_FRAME_PERIOD.min(n / 3))
+        .map(|lag| {
+            let sum: f64 = centered.iter().zip(&centered[lag..]).map(|(a, b)| a * b).sum();
+            (lag, sum / ((n - lag) as f64 * variance))
+        })
+        .collect();
+    let best = correlations.iter().map(|&(_, r)| r).fold(f64::MIN, f64::max);
+    if best < MIN_PERIOD_CORRELATION {
+        return None;
+    }
+    correlations.iter().find(|&&(_, r)| r >= best * HARMONIC_SHARE).map(|&(lag, _)| lag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_super_frame_spikes() {
+        // 4-byte frames with a counter; every 16th frame carries a different mode byte
+        let mut bytes = Vec::new();
+        for frame in 0..160u32 {
+            let mode = if frame % 16 == 0 { 0xFF } else { 0x00 };
+            bytes.extend([0x7E, mode, 0x00, (frame % 2) as u8]);
+        }
+        let result = frame_distances(bytes.view_bits::<Msb0>(), 32);
+        assert_eq!(result.distances.len(), 159);
+        // The counter bit always flips; the mode byte adds 8 bits entering and leaving frame 16
+        assert_eq!(result.distances[0], 9);
+        assert_eq!(result.distances[1], 1);
+        assert_eq!(result.distances[15], 9);
+        assert_eq!(result.max(), 9);
+        assert_eq!(result.period, Some(16));
+
+        // Identical frames have no period to find
+        let flat = frame_distances(&bitvec![u8, Msb0; 0; 320], 32);
+        assert!(flat.distances.iter().all(|&d| d == 0));
+        assert_eq!(flat.period, None);
+        assert!(frame_distances(&bitvec![u8, Msb0; 1; 64], 0).distances.is_empty());
+    }
+}
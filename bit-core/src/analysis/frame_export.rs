@@ -0,0 +1,150 @@
+// Frame export - one CSV row per frame with chosen bit ranges decoded, for spreadsheets and
+// pandas
+
+use crate::utils::csv_field;
+use bitvec::prelude::*;
+
+/// How a field's bits are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldFormat {
+    /// Unsigned big-endian integer, at most 64 bits
+    Int,
+    /// Hex digits, right-aligned so a partial nibble is padded on the left
+    Hex,
+    /// The bits as `0` and `1`
+    Bits,
+}
+
+impl FieldFormat {
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            FieldFormat::Int => "int",
+            FieldFormat::Hex => "hex",
+            FieldFormat::Bits => "bits",
+        }
+    }
+
+    fn parse(keyword: &str) -> Option<FieldFormat> {
+        [FieldFormat::Int, FieldFormat::Hex, FieldFormat::Bits]
+            .into_iter()
+            .find(|format| format.keyword().eq_ignore_ascii_case(keyword))
+    }
+}
+
+/// A bit range of every frame written as one CSV column
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameField {
+    pub name: String,
+    /// First bit within the frame
+    pub start: usize,
+    pub length: usize,
+    pub format: FieldFormat,
+}
+
+/// One field per line as `name start..end [int|hex|bits]`, the end exclusive and the format
+/// `int` if left out. The name may contain spaces; blank lines and lines starting with `#` are
+/// skipped.
+pub fn parse_frame_fields(spec: &str) -> Result<Vec<FrameField>, String> {
+    let mut fields = Vec::new();
+    for (number, line) in spec.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let range_index = words
+            .iter()
+            .rposition(|word| word.contains(".."))
+            .ok_or_else(|| format!("Line {}: expected `name start..end [int|hex|bits]`", number + 1))?;
+        let name = words[..range_index].join(" ");
+        if name.is_empty() {
+            return Err(format!("Line {}: the field has no name", number + 1));
+        }
+
+        let (start, end) = words[range_index].split_once("..").unwrap_or_default();
+        let parse_bit = |text: &str| text.parse::<usize>().map_err(|_| format!("Line {}: '{}' is not a bit offset", number + 1, text));
+        let (start, end) = (parse_bit(start)?, parse_bit(end)?);
+        if end <= start {
+            return Err(format!("Line {}: '{}' has no bits", number + 1, words[range_index]));
+        }
+
+        let format = match &words[range_index + 1..] {
+            [] => FieldFormat::Int,
+            [keyword] => FieldFormat::parse(keyword)
+                .ok_or_else(|| format!("Line {}: unknown format '{}', use int, hex or bits", number + 1, keyword))?,
+            _ => return Err(format!("Line {}: unexpected text after the format", number + 1)),
+        };
+        if format == FieldFormat::Int && end - start > 64 {
+            return Err(format!("Line {}: '{}' is wider than 64 bits; write it as hex or bits", number + 1, name));
+        }
+        fields.push(FrameField { name, start, length: end - start, format });
+    }
+    Ok(fields)
+}
+
+/// A CSV of the `frame_length`-bit frames in `bits`: the frame number, the frame's first bit
+/// counted from `first_bit`, then each field. A trailing partial frame is left out.
+pub fn frames_csv(bits: &BitSlice<u8, Msb0>, frame_length: usize, fields: &[FrameField], first_bit: usize) -> Result<String, String> {
+    if frame_length == 0 {
+        return Err("Frame length must be at least one bit".to_string());
+    }
+    if let Some(field) = fields.iter().find(|field| field.start + field.length > frame_length) {
+        return Err(format!(
+            "'{}' ends at bit {}, past the end of the {}-bit frame",
+            field.name,
+            field.start + field.length,
+            frame_length
+        ));
+    }
+
+    let mut header = vec!["frame".to_string(), "bit_offset".to_string()];
+    header.extend(fields.iter().map(|field| csv_field(&field.name)));
+    let mut csv = header.join(",");
+    csv.push('\n');
+    for (index, frame) in bits.chunks_exact(frame_length).enumerate() {
+        csv.push_str(&format!("{},{}", index, first_bit + index * frame_length));
+        for field in fields {
+            csv.push(',');
+            csv.push_str(&field_value(&frame[field.start..field.start + field.length], field.format));
+        }
+        csv.push('\n');
+    }
+    Ok(csv)
+}
+
+fn field_value(bits: &BitSlice<u8, Msb0>, format: FieldFormat) -> String {
+    match format {
+        FieldFormat::Int => bits.load_be::<u64>().to_string(),
+        FieldFormat::Hex => {
+            let mut padded: BitVec<u8, Msb0> = BitVec::repeat(false, (4 - bits.len() % 4) % 4);
+            padded.extend_from_bitslice(bits);
+            padded.chunks(4).map(|nibble| format!("{:X}", nibble.load_be::<u8>())).collect()
+        }
+        FieldFormat::Bits => bits.iter().map(|bit| if *bit { '1' } else { '0' }).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frames_become_rows() {
+        let spec = "# sync and counter\nsync word 0..8 hex\ncounter 8..16\n\nflags 16..19 bits\nlow 19..24 hex";
+        let fields = parse_frame_fields(spec).unwrap();
+        assert_eq!(fields.len(), 4);
+        assert_eq!(fields[0], FrameField { name: "sync word".to_string(), start: 0, length: 8, format: FieldFormat::Hex });
+        assert_eq!(fields[1].format, FieldFormat::Int);
+
+        let bytes = [0x7Eu8, 1, 0b1010_0101, 0x7E, 2, 0b0111_1111, 0x7E];
+        let csv = frames_csv(bytes.view_bits::<Msb0>(), 24, &fields, 8).unwrap();
+        assert_eq!(csv, "frame,bit_offset,sync word,counter,flags,low\n0,8,7E,1,101,05\n1,32,7E,2,011,1F\n");
+
+        assert!(frames_csv(bytes.view_bits::<Msb0>(), 16, &fields, 0).unwrap_err().contains("flags"));
+        assert!(parse_frame_fields("counter 8..8").is_err());
+        assert!(parse_frame_fields("0..8 hex").is_err());
+        assert!(parse_frame_fields("wide 0..72").is_err());
+        assert!(parse_frame_fields("wide 0..72 hex").is_ok());
+        assert!(parse_frame_fields("counter 8..16 float").unwrap_err().starts_with("Line 1"));
+    }
+}
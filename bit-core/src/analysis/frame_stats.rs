@@ -0,0 +1,200 @@
+// Frame statistics - how each bit or byte position behaves from frame to frame, with a guess
+// at what kind of field it belongs to
+
+use bitvec::prelude::*;
+use std::collections::HashMap;
+
+/// What a column's values look like across frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnKind {
+    /// The same value in (almost) every frame, like a sync word or version
+    Constant,
+    /// Steps by the same amount from frame to frame, like a sequence number
+    Counter,
+    /// A few values that change now and then, like status bits
+    Flags,
+    /// Anything else: measurements, text or encrypted data
+    Payload,
+}
+
+impl ColumnKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ColumnKind::Constant => "Constant",
+            ColumnKind::Counter => "Counter",
+            ColumnKind::Flags => "Flags",
+            ColumnKind::Payload => "Payload",
+        }
+    }
+}
+
+/// Statistics of one column of the frames
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnStats {
+    /// First bit of the column within the frame
+    pub offset: usize,
+    pub width: usize,
+    /// The most common value
+    pub common_value: u64,
+    /// Percentage of frames holding the most common value
+    pub constant_percent: f64,
+    pub distinct: usize,
+    /// Fraction of consecutive frame pairs in which the value changes
+    pub transition_rate: f64,
+    pub kind: ColumnKind,
+}
+
+/// Share of frames with the common value above which a column counts as constant
+const CONSTANT_SHARE: f64 = 0.99;
+/// Share of frame pairs stepping by the common step above which a column counts as a counter
+const COUNTER_SHARE: f64 = 0.8;
+/// Most distinct values a flags column may have
+const MAX_FLAG_VALUES: usize = 8;
+
+/// Statistics for every `column_bits`-bit column of the `frame_length`-bit frames in `bits`;
+/// the last column of a frame may be narrower. A trailing partial frame is ignored.
+pub fn frame_stats(bits: &BitSlice<u8, Msb0>, frame_length: usize, column_bits: usize) -> Vec<ColumnStats> {
+    if frame_length == 0 {
+        return Vec::new();
+    }
+    stats_of_frames(bits.chunks_exact(frame_length).collect(), frame_length, column_bits)
+}
+
+/// `frame_stats` over only the frames at `position` within super-frames of `super_frame`
+/// frames: frames `position`, `position + super_frame` and so on
+pub fn super_frame_stats(
+    bits: &BitSlice<u8, Msb0>,
+    frame_length: usize,
+    column_bits: usize,
+    super_frame: usize,
+    position: usize,
+) -> Vec<ColumnStats> {
+    if frame_length == 0 || super_frame == 0 {
+        return Vec::new();
+    }
+    let frames = bits.chunks_exact(frame_length).skip(position).step_by(super_frame).collect();
+    stats_of_frames(frames, frame_length, column_bits)
+}
+
+fn stats_of_frames(frames: Vec<&BitSlice<u8, Msb0>>, frame_length: usize, column_bits: usize) -> Vec<ColumnStats> {
+    if column_bits == 0 || frames.is_empty() {
+        return Vec::new();
+    }
+
+    let mut stats = Vec::new();
+    let mut offset = 0;
+    while offset < frame_length {
+        let width = column_bits.min(64).min(frame_length - offset);
+        let values: Vec<u64> = frames.iter().map(|frame| frame[offset..offset + width].load_be::<u64>()).collect();
+        stats.push(column_stats(offset, width, &values));
+        offset += width;
+    }
+    stats
+}
+
+fn column_stats(offset: usize, width: usize, values: &[u64]) -> ColumnStats {
+    let mut counts: HashMap<u64, usize> = HashMap::new();
+    for &value in values {
+        *counts.entry(value).or_default() += 1;
+    }
+    // Ties go to the smallest value so results don't depend on hash order
+    let (common_value, common_count) = counts
+        .iter()
+        .map(|(&value, &count)| (value, count))
+        .max_by_key(|&(value, count)| (count, std::cmp::Reverse(value)))
+        .unwrap_or((0, 0));
+    let constant_share = common_count as f64 / values.len() as f64;
+
+    let pairs = values.len().saturating_sub(1);
+    let mask = if width >= 64 { u64::MAX } else { (1u64 << width) - 1 };
+    let mut steps: HashMap<u64, usize> = HashMap::new();
+    let mut transitions = 0;
+    for pair in values.windows(2) {
+        let step = pair[1].wrapping_sub(pair[0]) & mask;
+        if step != 0 {
+            transitions += 1;
+            *steps.entry(step).or_default() += 1;
+        }
+    }
+    let transition_rate = if pairs > 0 { transitions as f64 / pairs as f64 } else { 0.0 };
+    let common_step_share = if pairs > 0 {
+        steps.values().copied().max().unwrap_or(0) as f64 / pairs as f64
+    } else {
+        0.0
+    };
+
+    // A single bit toggling every frame is better read as a flag than a counter
+    let kind = if constant_share >= CONSTANT_SHARE {
+        ColumnKind::Constant
+    } else if width > 1 && common_step_share >= COUNTER_SHARE {
+        ColumnKind::Counter
+    } else if counts.len() <= MAX_FLAG_VALUES && transition_rate < 0.5 {
+        ColumnKind::Flags
+    } else {
+        ColumnKind::Payload
+    };
+
+    ColumnStats {
+        offset,
+        width,
+        common_value,
+        constant_percent: constant_share * 100.0,
+        distinct: counts.len(),
+        transition_rate,
+        kind,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_columns_are_classified() {
+        // 4-byte frames: sync byte, counter, a status byte that changes once, pseudo-random payload
+        let mut bytes = Vec::new();
+        let mut noise = 0x5Au8;
+        for frame in 0..40u8 {
+            noise = noise.wrapping_mul(97).wrapping_add(31) ^ (noise >> 3);
+            bytes.extend([0x7E, frame, if frame < 20 { 0x01 } else { 0x03 }, noise]);
+        }
+        let stats = frame_stats(bytes.view_bits::<Msb0>(), 32, 8);
+        let kinds: Vec<ColumnKind> = stats.iter().map(|column| column.kind).collect();
+        assert_eq!(kinds, vec![ColumnKind::Constant, ColumnKind::Counter, ColumnKind::Flags, ColumnKind::Payload]);
+
+        assert_eq!(stats[0].common_value, 0x7E);
+        assert_eq!(stats[0].constant_percent, 100.0);
+        assert_eq!(stats[1].distinct, 40);
+        assert_eq!(stats[1].transition_rate, 1.0);
+        assert_eq!(stats[2].distinct, 2);
+        assert_eq!(stats[2].offset, 16);
+
+        // 12-bit columns, with a narrower one at the end of each frame
+        let stats = frame_stats(bytes.view_bits::<Msb0>(), 32, 12);
+        assert_eq!(stats.iter().map(|column| column.width).collect::<Vec<_>>(), vec![12, 12, 8]);
+        assert!(frame_stats(bytes.view_bits::<Msb0>(), 0, 8).is_empty());
+    }
+
+    #[test]
+    fn test_positions_in_super_frame() {
+        // 2-byte frames in super-frames of 4: the first byte names the position, the second
+        // is constant only in the first frame of each super-frame
+        let mut bytes = Vec::new();
+        for frame in 0..32u8 {
+            let position = frame % 4;
+            bytes.extend([position, if position == 0 { 0xAA } else { frame.wrapping_mul(37) }]);
+        }
+        let bits = bytes.view_bits::<Msb0>();
+        assert_ne!(frame_stats(bits, 16, 8)[1].kind, ColumnKind::Constant);
+
+        let first = super_frame_stats(bits, 16, 8, 4, 0);
+        assert_eq!(first[0].common_value, 0);
+        assert_eq!(first[1].kind, ColumnKind::Constant);
+        assert_eq!(first[1].common_value, 0xAA);
+        let third = super_frame_stats(bits, 16, 8, 4, 2);
+        assert_eq!((third[0].kind, third[0].common_value), (ColumnKind::Constant, 2));
+        assert_ne!(third[1].kind, ColumnKind::Constant);
+        assert!(super_frame_stats(bits, 16, 8, 4, 40).is_empty());
+        assert!(super_frame_stats(bits, 16, 8, 0, 0).is_empty());
+    }
+}
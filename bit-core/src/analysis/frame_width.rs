@@ -1,6 +1,9 @@
 // Frame width detection - automatic detection of bit framing patterns
 
 use bitvec::prelude::*;
+use rayon::prelude::*;
+use crate::processing::ProgressReporter;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 /// Result of frame width analysis
 #[derive(Debug, Clone)]
@@ -17,11 +20,56 @@ pub struct FrameWidthAnalysis {
     pub bit_position_patterns: Vec<Vec<f64>>,
 }
 
+/// Bit position consistency of the tested widths, shrunk to a size that can be drawn
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsistencyGrid {
+    /// Width shown in each row, smallest first
+    pub widths: Vec<usize>,
+    /// One row per entry of `widths`: the highest consistency among the bit positions each
+    /// cell covers, None past the end of the width
+    pub cells: Vec<Vec<Option<f64>>>,
+    /// Bit positions each cell covers
+    pub bits_per_column: usize,
+}
+
+impl FrameWidthAnalysis {
+    /// At most `rows` by `columns` cells of `bit_position_patterns`. When there are more
+    /// widths than rows, each row shows the best scoring width of the ones it stands for.
+    pub fn consistency_grid(&self, rows: usize, columns: usize) -> ConsistencyGrid {
+        let count = self.width_scores.len().min(self.bit_position_patterns.len());
+        let longest = self.bit_position_patterns.iter().map(Vec::len).max().unwrap_or(0);
+        let bits_per_column = longest.div_ceil(columns.max(1)).max(1);
+        let rows = rows.clamp(1, count.max(1));
+        
+        let mut grid = ConsistencyGrid { widths: Vec::new(), cells: Vec::new(), bits_per_column };
+        for row in 0..rows.min(count) {
+            let Some(index) = (row * count / rows..(row + 1) * count / rows)
+                .max_by(|&a, &b| self.width_scores[a].1.total_cmp(&self.width_scores[b].1).then(b.cmp(&a)))
+            else {
+                continue;
+            };
+            let patterns = &self.bit_position_patterns[index];
+            grid.widths.push(self.width_scores[index].0);
+            grid.cells.push(
+                (0..longest.div_ceil(bits_per_column))
+                    .map(|column| {
+                        let start = column * bits_per_column;
+                        patterns
+                            .get(start..(start + bits_per_column).min(patterns.len()))
+                            .and_then(|cell| cell.iter().copied().reduce(f64::max))
+                    })
+                    .collect(),
+            );
+        }
+        grid
+    }
+}
+
 /// Score a single width by measuring bit position consistency using Shannon entropy
 /// Lower entropy = more structure = better frame width
 /// Returns (score, bit_position_entropies) where score is INVERTED (1.0 - avg_entropy)
 /// so higher score still means better, for consistency with UI
-pub fn score_width(bits: &BitVec<u8, Msb0>, width: usize) -> (f64, Vec<f64>) {
+pub fn score_width(bits: &BitSlice<u8, Msb0>, width: usize) -> (f64, Vec<f64>) {
     if width == 0 || bits.len() < width * 2 {
         return (0.0, vec![]);
     }
@@ -123,7 +171,7 @@ pub fn score_width(bits: &BitVec<u8, Msb0>, width: usize) -> (f64, Vec<f64>) {
 
 /// Score a width using delta-based pattern matching
 /// Delta = number of frames to look ahead for repeating patterns
-pub fn score_width_with_delta(bits: &BitVec<u8, Msb0>, width: usize, delta: usize) -> (f64, Vec<f64>) {
+pub fn score_width_with_delta(bits: &BitSlice<u8, Msb0>, width: usize, delta: usize) -> (f64, Vec<f64>) {
     if width == 0 || bits.len() < width * (delta + 2) {
         return (0.0, vec![]);
     }
@@ -165,23 +213,108 @@ pub fn score_width_with_delta(bits: &BitVec<u8, Msb0>, width: usize, delta: usiz
 
 /// Find the best frame width in the given range
 pub fn find_best_width(
-    bits: &BitVec<u8, Msb0>,
+    bits: &BitSlice<u8, Msb0>,
     min_width: usize,
     max_width: usize,
     delta: usize,
 ) -> FrameWidthAnalysis {
-    let mut width_scores = Vec::new();
-    let mut bit_position_patterns = Vec::new();
-    let mut best_width = min_width;
+    find_best_width_with_progress(bits, min_width, max_width, delta, &ProgressReporter::new())
+        .expect("uncancelled analysis always finishes")
+}
+
+/// Same as `find_best_width`, publishing the fraction of widths scored to `progress`.
+/// Returns None if `progress` was cancelled.
+pub fn find_best_width_with_progress(
+    bits: &BitSlice<u8, Msb0>,
+    min_width: usize,
+    max_width: usize,
+    delta: usize,
+    progress: &ProgressReporter,
+) -> Option<FrameWidthAnalysis> {
+    let widths: Vec<usize> = (min_width..=max_width).collect();
+    find_best_width_among(bits, &widths, delta, progress)
+}
+
+/// Most widths a width list may expand to
+pub const MAX_LISTED_WIDTHS: usize = 100_000;
+
+/// Widths in `spec`, smallest first without repeats: comma-separated widths and inclusive
+/// ranges, each range optionally with a step, like `96..1200 step 8, 100, 150`
+pub fn parse_width_list(spec: &str) -> Result<Vec<usize>, String> {
+    let parse = |text: &str| -> Result<usize, String> {
+        text.trim().parse::<usize>().map_err(|_| format!("'{}' is not a width", text.trim()))
+    };
+    let mut widths = Vec::new();
+    for item in spec.split(',').map(str::trim).filter(|item| !item.is_empty()) {
+        let (range, step) = match item.split_once("step") {
+            Some((range, step)) => (range.trim(), parse(step)?),
+            None => (item, 1),
+        };
+        match range.split_once("..") {
+            Some((start, end)) => {
+                let (start, end) = (parse(start)?, parse(end.trim_start_matches('='))?);
+                if start > end || step == 0 {
+                    return Err(format!("'{}' is an empty range", item));
+                }
+                if (end - start) / step >= MAX_LISTED_WIDTHS {
+                    return Err(format!("'{}' has more than {} widths", item, MAX_LISTED_WIDTHS));
+                }
+                widths.extend((start..=end).step_by(step));
+            }
+            None if step != 1 => return Err(format!("'{}' has a step but no range", item)),
+            None => widths.push(parse(range)?),
+        }
+    }
+    widths.sort_unstable();
+    widths.dedup();
+    if widths.first() == Some(&0) {
+        return Err("Widths must be at least 1".to_string());
+    }
+    if widths.is_empty() {
+        return Err("Enter at least one width".to_string());
+    }
+    Ok(widths)
+}
+
+/// `find_best_width` over an explicit list of widths, such as one from `parse_width_list`
+pub fn find_best_width_among(
+    bits: &BitSlice<u8, Msb0>,
+    widths: &[usize],
+    delta: usize,
+    progress: &ProgressReporter,
+) -> Option<FrameWidthAnalysis> {
+    let total = widths.len().max(1);
+    let done = AtomicUsize::new(0);
+    
+    // Widths are scored independently, so score them in parallel (results stay in width order)
+    let scored: Vec<(usize, f64, Vec<f64>)> = widths
+        .par_iter()
+        .map(|&width| {
+            // Remaining widths are skipped once cancelled; the result is thrown away below
+            if progress.is_cancelled() {
+                return (width, 0.0, Vec::new());
+            }
+            let (score, bit_patterns) = if delta > 0 {
+                score_width_with_delta(bits, width, delta)
+            } else {
+                score_width(bits, width)
+            };
+            let finished = done.fetch_add(1, Ordering::Relaxed) + 1;
+            progress.set_fraction(finished as f32 / total as f32);
+            (width, score, bit_patterns)
+        })
+        .collect();
+    
+    if progress.is_cancelled() {
+        return None;
+    }
+    
+    let mut width_scores = Vec::with_capacity(scored.len());
+    let mut bit_position_patterns = Vec::with_capacity(scored.len());
+    let mut best_width = widths.first().copied().unwrap_or(0);
     let mut best_score = 0.0;
     
-    for width in min_width..=max_width {
-        let (score, bit_patterns) = if delta > 0 {
-            score_width_with_delta(bits, width, delta)
-        } else {
-            score_width(bits, width)
-        };
-        
+    for (width, score, bit_patterns) in scored {
         width_scores.push((width, score));
         bit_position_patterns.push(bit_patterns);
         
@@ -214,12 +347,12 @@ pub fn find_best_width(
         }
     }
     
-    FrameWidthAnalysis {
+    Some(FrameWidthAnalysis {
         width_scores,
         best_width,
         best_score,
         bit_position_patterns,
-    }
+    })
 }
 
 #[cfg(test)]
@@ -278,6 +411,21 @@ mod tests {
         assert_eq!(analysis.best_width, 8, "Best width should be 8 for ASCII");
     }
     
+    #[test]
+    fn test_find_best_width_keeps_width_order() {
+        let mut bits = BitVec::<u8, Msb0>::new();
+        for _ in 0..20 {
+            bits.extend_from_bitslice(&bitvec![u8, Msb0; 0,1,0,0,0,0,0,1]);
+        }
+        
+        let analysis = find_best_width(&bits, 4, 40, 0);
+        
+        let widths: Vec<usize> = analysis.width_scores.iter().map(|(w, _)| *w).collect();
+        assert_eq!(widths, (4..=40).collect::<Vec<_>>());
+        assert_eq!(analysis.bit_position_patterns.len(), widths.len());
+        assert_eq!(analysis.bit_position_patterns[4].len(), 8);
+    }
+    
     #[test]
     fn test_varied_ascii() {
         // Test with varied ASCII text "Hello"
@@ -486,4 +634,55 @@ mod tests {
         println!("Multiples of 8 (16, 24, 32...) will score similarly due to repetition.");
         println!("Algorithm prefers smallest width when scores are equal (within 0.0001%).");
     }
+    
+    #[test]
+    fn test_consistency_grid() {
+        let analysis = FrameWidthAnalysis {
+            width_scores: vec![(2, 0.1), (3, 0.9), (4, 0.5), (5, 0.2)],
+            best_width: 3,
+            best_score: 0.9,
+            bit_position_patterns: vec![
+                vec![0.1, 0.2],
+                vec![1.0, 0.0, 0.5],
+                vec![0.0, 0.3, 0.6, 0.2],
+                vec![0.4; 5],
+            ],
+        };
+        let full = analysis.consistency_grid(10, 10);
+        assert_eq!(full.widths, vec![2, 3, 4, 5]);
+        assert_eq!(full.bits_per_column, 1);
+        assert_eq!(full.cells[0], vec![Some(0.1), Some(0.2), None, None, None]);
+        
+        // Two widths per row, keeping the better one; two bits per cell, keeping the highest
+        let small = analysis.consistency_grid(2, 3);
+        assert_eq!(small.widths, vec![3, 4]);
+        assert_eq!(small.bits_per_column, 2);
+        assert_eq!(small.cells, vec![vec![Some(1.0), Some(0.5), None], vec![Some(0.3), Some(0.6), None]]);
+    }
+    
+    #[test]
+    fn test_width_lists() {
+        assert_eq!(parse_width_list("100, 150,204 ,255").unwrap(), vec![100, 150, 204, 255]);
+        assert_eq!(parse_width_list("8..32 step 8, 16, 12").unwrap(), vec![8, 12, 16, 24, 32]);
+        assert_eq!(parse_width_list("3..5").unwrap(), vec![3, 4, 5]);
+        assert!(parse_width_list("").is_err());
+        assert!(parse_width_list("10..5").is_err());
+        assert!(parse_width_list("8 step 2").is_err());
+        assert!(parse_width_list("0, 8").is_err());
+        assert!(parse_width_list("abc").is_err());
+        
+        // Only the listed widths are scored, and the true width is still found among them
+        let bits = bitvec![u8, Msb0; 0, 1, 1, 0, 1, 0, 0, 1, 1, 1, 0, 0].repeat(20);
+        let analysis = find_best_width_among(&bits, &[5, 8, 12, 20], 0, &ProgressReporter::new()).unwrap();
+        assert_eq!(analysis.width_scores.iter().map(|(width, _)| *width).collect::<Vec<_>>(), vec![5, 8, 12, 20]);
+        assert_eq!(analysis.best_width, 12);
+    }
+    
+    #[test]
+    fn test_cancelled_analysis_returns_none() {
+        let bits = bitvec![u8, Msb0; 0, 1, 0, 0, 0, 0, 0, 1].repeat(8);
+        let progress = ProgressReporter::new();
+        progress.token.cancel();
+        assert!(find_best_width_with_progress(&bits, 4, 16, 0, &progress).is_none());
+    }
 }
@@ -0,0 +1,277 @@
+// Framer - splits a bit stream into frames that start with a sync word
+
+use super::pattern_locator::Pattern;
+use bitvec::prelude::*;
+
+/// Checksum stored in the last bits of each frame, covering the bits after the sync word
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameCrc {
+    None,
+    /// CRC-8 with polynomial 0x07
+    Crc8,
+    /// CRC-16/CCITT-FALSE (polynomial 0x1021, initial value 0xFFFF)
+    Crc16Ccitt,
+    /// CRC-32 as used by Ethernet and zip
+    Crc32,
+}
+
+impl FrameCrc {
+    pub fn name(&self) -> &str {
+        match self {
+            FrameCrc::None => "None",
+            FrameCrc::Crc8 => "CRC-8",
+            FrameCrc::Crc16Ccitt => "CRC-16/CCITT",
+            FrameCrc::Crc32 => "CRC-32",
+        }
+    }
+
+    pub fn all() -> [FrameCrc; 4] {
+        [FrameCrc::None, FrameCrc::Crc8, FrameCrc::Crc16Ccitt, FrameCrc::Crc32]
+    }
+
+    /// Number of bits the checksum occupies at the end of a frame
+    pub fn width(&self) -> usize {
+        match self {
+            FrameCrc::None => 0,
+            FrameCrc::Crc8 => 8,
+            FrameCrc::Crc16Ccitt => 16,
+            FrameCrc::Crc32 => 32,
+        }
+    }
+
+    pub fn compute(&self, bytes: &[u8]) -> u32 {
+        match self {
+            FrameCrc::None => 0,
+            FrameCrc::Crc8 => {
+                let mut crc: u8 = 0;
+                for &byte in bytes {
+                    crc ^= byte;
+                    for _ in 0..8 {
+                        crc = if crc & 0x80 != 0 { (crc << 1) ^ 0x07 } else { crc << 1 };
+                    }
+                }
+                crc as u32
+            }
+            FrameCrc::Crc16Ccitt => {
+                let mut crc: u16 = 0xFFFF;
+                for &byte in bytes {
+                    crc ^= (byte as u16) << 8;
+                    for _ in 0..8 {
+                        crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x1021 } else { crc << 1 };
+                    }
+                }
+                crc as u32
+            }
+            FrameCrc::Crc32 => {
+                let mut crc: u32 = 0xFFFF_FFFF;
+                for &byte in bytes {
+                    crc ^= byte as u32;
+                    for _ in 0..8 {
+                        crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+                    }
+                }
+                !crc
+            }
+        }
+    }
+}
+
+/// One frame found by the framer
+#[derive(Debug, Clone)]
+pub struct Frame {
+    /// Bit offset of the sync word
+    pub position: usize,
+    /// Frame length in bits, including the sync word
+    pub length: usize,
+    /// Bit errors in the sync word (within the pattern's garble tolerance)
+    pub sync_errors: usize,
+    /// Whether the stored checksum matches, None when no checksum is configured
+    /// or the covered bits aren't a whole number of bytes
+    pub crc_ok: Option<bool>,
+}
+
+/// Split `bits` into frames starting at each match of `sync`.
+/// With a `frame_length` of 0 each frame runs up to the next sync word; otherwise
+/// frames are `frame_length` bits long and sync matches inside a frame are ignored.
+pub fn split_frames(
+    bits: &BitSlice<u8, Msb0>,
+    sync: &mut Pattern,
+    frame_length: usize,
+    crc: FrameCrc,
+) -> Vec<Frame> {
+    sync.search(bits);
+
+    let sync_len = sync.bits.len();
+    let mut frames: Vec<Frame> = Vec::new();
+    let mut next_free = 0;
+
+    for (i, found) in sync.matches.iter().enumerate() {
+        if found.position < next_free {
+            continue;
+        }
+
+        let end = if frame_length > 0 {
+            found.position + frame_length
+        } else {
+            sync.matches[i + 1..]
+                .iter()
+                .map(|m| m.position)
+                .find(|&p| p >= found.position + sync_len)
+                .unwrap_or(bits.len())
+        };
+        if end > bits.len() {
+            break;
+        }
+
+        let frame_bits = &bits[found.position..end];
+        frames.push(Frame {
+            position: found.position,
+            length: end - found.position,
+            sync_errors: found.mismatches,
+            crc_ok: check_crc(frame_bits, sync_len, crc),
+        });
+        next_free = end;
+    }
+
+    frames
+}
+
+/// Split `bits` into frames that run from a match of `start` through the next match of `end`
+/// after it, for protocols with start and end flags. Start markers inside a frame are ignored
+/// and a start marker with no end marker after it doesn't make a frame. The checksum, if any,
+/// sits just before the end marker.
+pub fn split_frames_between(
+    bits: &BitSlice<u8, Msb0>,
+    start: &mut Pattern,
+    end: &mut Pattern,
+    crc: FrameCrc,
+) -> Vec<Frame> {
+    start.search(bits);
+    end.search(bits);
+
+    let start_len = start.bits.len();
+    let end_len = end.bits.len();
+    let mut frames: Vec<Frame> = Vec::new();
+    let mut next_free = 0;
+
+    for found in &start.matches {
+        if found.position < next_free {
+            continue;
+        }
+        let Some(end_position) = end
+            .matches
+            .iter()
+            .map(|m| m.position)
+            .find(|&p| p >= found.position + start_len)
+        else {
+            break;
+        };
+
+        let frame_end = end_position + end_len;
+        frames.push(Frame {
+            position: found.position,
+            length: frame_end - found.position,
+            sync_errors: found.mismatches,
+            crc_ok: check_crc(&bits[found.position..end_position], start_len, crc),
+        });
+        next_free = frame_end;
+    }
+
+    frames
+}
+
+/// Compare the checksum at the end of `frame` with one computed over the bits between
+/// the sync word and the checksum
+fn check_crc(frame: &BitSlice<u8, Msb0>, sync_len: usize, crc: FrameCrc) -> Option<bool> {
+    let width = crc.width();
+    if width == 0 || frame.len() < sync_len + width {
+        return None;
+    }
+
+    let crc_start = frame.len() - width;
+    let covered = &frame[sync_len..crc_start];
+    if !covered.len().is_multiple_of(8) {
+        return None;
+    }
+
+    let bytes: Vec<u8> = covered.chunks(8).map(|chunk| chunk.load_be::<u8>()).collect();
+    let stored = frame[crc_start..].load_be::<u32>();
+    Some(crc.compute(&bytes) == stored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::PatternFormat;
+
+    fn push_bytes(bits: &mut BitVec<u8, Msb0>, bytes: &[u8]) {
+        bits.extend_from_bitslice(bytes.view_bits::<Msb0>());
+    }
+
+    fn sync_word() -> Pattern {
+        Pattern::new("Sync".to_string(), PatternFormat::Hex, "0x1ACF".to_string(), 0).unwrap()
+    }
+
+    #[test]
+    fn test_crc_check_values() {
+        assert_eq!(FrameCrc::Crc8.compute(b"123456789"), 0xF4);
+        assert_eq!(FrameCrc::Crc16Ccitt.compute(b"123456789"), 0x29B1);
+        assert_eq!(FrameCrc::Crc32.compute(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn test_fixed_length_frames_with_crc() {
+        let mut bits = BitVec::<u8, Msb0>::new();
+        push_bytes(&mut bits, &[0xFF, 0x00]); // Junk before the first frame
+        for payload in [[0x01, 0x02], [0x03, 0x04]] {
+            let crc = FrameCrc::Crc16Ccitt.compute(&payload) as u16;
+            push_bytes(&mut bits, &[0x1A, 0xCF]);
+            push_bytes(&mut bits, &payload);
+            push_bytes(&mut bits, &crc.to_be_bytes());
+        }
+        // Corrupt the second frame's payload
+        let flipped = !bits[16 + 48 + 16];
+        bits.set(16 + 48 + 16, flipped);
+
+        let frames = split_frames(&bits, &mut sync_word(), 48, FrameCrc::Crc16Ccitt);
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].position, 16);
+        assert_eq!(frames[1].position, 64);
+        assert_eq!(frames[0].crc_ok, Some(true));
+        assert_eq!(frames[1].crc_ok, Some(false));
+    }
+
+    #[test]
+    fn test_variable_length_frames_end_at_next_sync() {
+        let mut bits = BitVec::<u8, Msb0>::new();
+        push_bytes(&mut bits, &[0x1A, 0xCF, 0x55]);
+        push_bytes(&mut bits, &[0x1A, 0xCF, 0x55, 0x55]);
+
+        let frames = split_frames(&bits, &mut sync_word(), 0, FrameCrc::None);
+        let lengths: Vec<usize> = frames.iter().map(|f| f.length).collect();
+        assert_eq!(lengths, vec![24, 32]);
+        assert_eq!(frames[0].crc_ok, None);
+    }
+
+    #[test]
+    fn test_frames_between_start_and_end_markers() {
+        let mut end = Pattern::new("End".to_string(), PatternFormat::Hex, "0xE0E0".to_string(), 0).unwrap();
+        let payload = [0x01, 0x02];
+        let crc = FrameCrc::Crc8.compute(&payload) as u8;
+
+        let mut bits = BitVec::<u8, Msb0>::new();
+        push_bytes(&mut bits, &[0x00]);
+        push_bytes(&mut bits, &[0x1A, 0xCF, 0x01, 0x02, crc, 0xE0, 0xE0]);
+        push_bytes(&mut bits, &[0x33]); // Between frames
+        // A second start marker before the end marker belongs to the frame
+        push_bytes(&mut bits, &[0x1A, 0xCF, 0x1A, 0xCF, 0xE0, 0xE0]);
+        // No end marker follows
+        push_bytes(&mut bits, &[0x1A, 0xCF, 0x44]);
+
+        let frames = split_frames_between(&bits, &mut sync_word(), &mut end, FrameCrc::Crc8);
+        assert_eq!(frames.len(), 2);
+        assert_eq!((frames[0].position, frames[0].length), (8, 56));
+        assert_eq!(frames[0].crc_ok, Some(true));
+        assert_eq!((frames[1].position, frames[1].length), (72, 48));
+    }
+}
@@ -0,0 +1,246 @@
+// HDLC decoding - flag detection, bit-destuffing and FCS checks for HDLC/AX.25 frames
+
+use bitvec::prelude::*;
+
+/// Residue left by CRC-16/X.25 when run over a frame including its FCS
+const FCS_GOOD_RESIDUE: u16 = 0xF0B8;
+
+/// Shortest frame worth reporting: 2 address/control bytes plus the 2-byte FCS
+const MIN_FRAME_BYTES: usize = 4;
+
+/// A frame found between two HDLC flags
+#[derive(Debug, Clone)]
+pub struct HdlcFrame {
+    /// Bit offset of the first bit after the opening flag
+    pub start_bit: usize,
+    /// Number of (still stuffed) bits before the closing flag
+    pub bit_len: usize,
+    /// Destuffed frame contents, including the FCS
+    pub bytes: Vec<u8>,
+    /// Whether the FCS matches the frame contents
+    pub fcs_ok: bool,
+}
+
+impl HdlcFrame {
+    /// Frame contents without the trailing FCS
+    pub fn payload(&self) -> &[u8] {
+        &self.bytes[..self.bytes.len().saturating_sub(2)]
+    }
+}
+
+/// How the bit stream should be interpreted while decoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HdlcOptions {
+    /// Decode NRZI first (a 0 is a transition, a 1 is no change), as used by AX.25 on air
+    pub nrzi: bool,
+    /// Bytes are sent least significant bit first, as HDLC specifies
+    pub lsb_first: bool,
+}
+
+impl Default for HdlcOptions {
+    fn default() -> Self {
+        Self { nrzi: false, lsb_first: true }
+    }
+}
+
+/// CRC-16/X.25 (reflected polynomial 0x8408, initial value 0xFFFF, no final XOR)
+pub fn crc16_x25(bytes: &[u8]) -> u16 {
+    let mut crc: u16 = 0xFFFF;
+    for &byte in bytes {
+        crc ^= byte as u16;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0x8408 } else { crc >> 1 };
+        }
+    }
+    crc
+}
+
+/// Find every frame between 0x7E flags, destuffing zeros inserted after five ones.
+/// Aborted frames (seven or more ones) and frames that aren't a whole number of
+/// bytes are dropped.
+pub fn decode_hdlc(bits: &BitSlice<u8, Msb0>, options: HdlcOptions) -> Vec<HdlcFrame> {
+    let mut frames = Vec::new();
+
+    let mut ones = 0usize;
+    let mut in_frame = false;
+    let mut frame_start = 0usize;
+    let mut data: Vec<bool> = Vec::new();
+    let mut previous = false;
+
+    for (i, raw_bit) in bits.iter().by_vals().enumerate() {
+        let bit = if options.nrzi {
+            let decoded = raw_bit == previous;
+            previous = raw_bit;
+            decoded
+        } else {
+            raw_bit
+        };
+
+        if bit {
+            ones += 1;
+            if ones >= 7 {
+                // Abort sequence, no frame until the next flag
+                in_frame = false;
+                data.clear();
+            } else if in_frame {
+                data.push(true);
+            }
+            continue;
+        }
+
+        match ones {
+            5 => {
+                // Stuffed zero, not part of the data
+            }
+            6 => {
+                // Flag: its leading zero and six ones were buffered as data
+                if in_frame {
+                    data.truncate(data.len().saturating_sub(7));
+                    // Flag bits sit at i-7..=i; the frame ends where they begin
+                    let bit_len = (i + 1).saturating_sub(8).saturating_sub(frame_start);
+                    if let Some(frame) = build_frame(&data, frame_start, bit_len, options) {
+                        frames.push(frame);
+                    }
+                }
+                in_frame = true;
+                frame_start = i + 1;
+                data.clear();
+            }
+            _ => {
+                if in_frame {
+                    data.push(false);
+                }
+            }
+        }
+        ones = 0;
+    }
+
+    frames
+}
+
+fn build_frame(data: &[bool], start_bit: usize, bit_len: usize, options: HdlcOptions) -> Option<HdlcFrame> {
+    if !data.len().is_multiple_of(8) || data.len() / 8 < MIN_FRAME_BYTES {
+        return None;
+    }
+
+    let bytes: Vec<u8> = data
+        .chunks(8)
+        .map(|chunk| {
+            chunk.iter().enumerate().fold(0u8, |byte, (bit_index, &bit)| {
+                let shift = if options.lsb_first { bit_index } else { 7 - bit_index };
+                byte | ((bit as u8) << shift)
+            })
+        })
+        .collect();
+
+    let fcs_ok = crc16_x25(&bytes) == FCS_GOOD_RESIDUE;
+    Some(HdlcFrame { start_bit, bit_len, bytes, fcs_ok })
+}
+
+/// "SOURCE>DEST" from the AX.25 address field, or None if it doesn't look like AX.25
+pub fn ax25_summary(payload: &[u8]) -> Option<String> {
+    if payload.len() < 14 {
+        return None;
+    }
+    let destination = ax25_callsign(&payload[0..7])?;
+    let source = ax25_callsign(&payload[7..14])?;
+    Some(format!("{}>{}", source, destination))
+}
+
+fn ax25_callsign(field: &[u8]) -> Option<String> {
+    let mut callsign = String::new();
+    for &byte in &field[..6] {
+        let c = (byte >> 1) as char;
+        if c == ' ' {
+            continue;
+        }
+        if !c.is_ascii_alphanumeric() {
+            return None;
+        }
+        callsign.push(c);
+    }
+    if callsign.is_empty() {
+        return None;
+    }
+
+    let ssid = (field[6] >> 1) & 0x0F;
+    if ssid != 0 {
+        callsign.push_str(&format!("-{}", ssid));
+    }
+    Some(callsign)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FLAG: [bool; 8] = [false, true, true, true, true, true, true, false];
+
+    /// Encode `payload` plus its FCS as an LSB-first, bit-stuffed frame between flags
+    fn encode_frame(bits: &mut BitVec<u8, Msb0>, payload: &[u8]) {
+        let fcs = !crc16_x25(payload);
+        let mut bytes = payload.to_vec();
+        bytes.extend_from_slice(&fcs.to_le_bytes());
+
+        bits.extend(FLAG);
+        let mut ones = 0;
+        for byte in bytes {
+            for bit_index in 0..8 {
+                let bit = (byte >> bit_index) & 1 == 1;
+                bits.push(bit);
+                ones = if bit { ones + 1 } else { 0 };
+                if ones == 5 {
+                    bits.push(false);
+                    ones = 0;
+                }
+            }
+        }
+        bits.extend(FLAG);
+    }
+
+    #[test]
+    fn test_crc16_x25_check_value() {
+        assert_eq!(crc16_x25(b"123456789") ^ 0xFFFF, 0x906E);
+    }
+
+    #[test]
+    fn test_frames_are_destuffed_and_checked() {
+        let mut bits = BitVec::<u8, Msb0>::new();
+        let payload = [0x03, 0xF0, 0xFF, 0x7E, 0x41];
+        encode_frame(&mut bits, &payload);
+        encode_frame(&mut bits, b"second frame");
+
+        let frames = decode_hdlc(&bits, HdlcOptions::default());
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].payload(), &payload);
+        assert!(frames[0].fcs_ok);
+        assert_eq!(frames[0].start_bit, 8);
+        assert_eq!(frames[1].payload(), b"second frame");
+        assert!(frames[1].fcs_ok);
+    }
+
+    #[test]
+    fn test_corrupted_frame_fails_fcs() {
+        let mut bits = BitVec::<u8, Msb0>::new();
+        encode_frame(&mut bits, b"hello");
+        // Flip a data bit that can't form a flag or stuffing run
+        let flipped = !bits[12];
+        bits.set(12, flipped);
+
+        let frames = decode_hdlc(&bits, HdlcOptions::default());
+        assert_eq!(frames.len(), 1);
+        assert!(!frames[0].fcs_ok);
+    }
+
+    #[test]
+    fn test_ax25_addresses() {
+        let mut payload = Vec::new();
+        for (call, ssid) in [("APRS", 0u8), ("N0CALL", 7u8)] {
+            let padded = format!("{:<6}", call);
+            payload.extend(padded.bytes().map(|b| b << 1));
+            payload.push(0x60 | (ssid << 1));
+        }
+        assert_eq!(ax25_summary(&payload).as_deref(), Some("N0CALL-7>APRS"));
+        assert_eq!(ax25_summary(b"short"), None);
+    }
+}
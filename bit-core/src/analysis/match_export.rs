@@ -0,0 +1,103 @@
+// Match export - pattern match reports for downstream scripts
+
+use super::pattern_locator::Pattern;
+use crate::utils::csv_field;
+use bitvec::prelude::*;
+use serde::Serialize;
+
+/// One pattern match with the bytes around it
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchRecord {
+    pub pattern: String,
+    pub position: usize,
+    pub length: usize,
+    pub delta: Option<usize>,
+    pub mismatches: usize,
+    pub slips: usize,
+    /// Bit offset where `context_hex` starts
+    pub context_start: usize,
+    /// The match plus up to `context_bytes` bytes either side, as hex read from
+    /// `context_start` (the last byte is zero-padded)
+    pub context_hex: String,
+}
+
+/// Flatten the matches of every pattern into records, in pattern order
+pub fn match_records(patterns: &[Pattern], bits: &BitSlice<u8, Msb0>, context_bytes: usize) -> Vec<MatchRecord> {
+    let context_bits = context_bytes * 8;
+    patterns
+        .iter()
+        .flat_map(|pattern| {
+            pattern.matches.iter().map(move |m| {
+                let length = m.actual_bits.len();
+                let context_start = m.position.saturating_sub(context_bits).min(bits.len());
+                let context_end = (m.position + length + context_bits).min(bits.len());
+                MatchRecord {
+                    pattern: pattern.name.clone(),
+                    position: m.position,
+                    length,
+                    delta: m.delta,
+                    mismatches: m.mismatches,
+                    slips: m.slips,
+                    context_start,
+                    context_hex: bits_to_hex(&bits[context_start..context_end]),
+                }
+            })
+        })
+        .collect()
+}
+
+fn bits_to_hex(bits: &BitSlice<u8, Msb0>) -> String {
+    bits.chunks(8)
+        .map(|chunk| format!("{:02X}", chunk.load_be::<u8>() << (8 - chunk.len())))
+        .collect()
+}
+
+pub fn matches_csv(records: &[MatchRecord]) -> String {
+    let mut csv = String::from("pattern,position,length,delta,mismatches,slips,context_start,context_hex\n");
+    for record in records {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{},{}\n",
+            csv_field(&record.pattern),
+            record.position,
+            record.length,
+            record.delta.map(|delta| delta.to_string()).unwrap_or_default(),
+            record.mismatches,
+            record.slips,
+            record.context_start,
+            record.context_hex,
+        ));
+    }
+    csv
+}
+
+pub fn matches_json(records: &[MatchRecord]) -> Result<String, String> {
+    serde_json::to_string_pretty(records).map_err(|e| format!("Failed to serialize matches: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::PatternFormat;
+
+    #[test]
+    fn test_records_include_context_bytes() {
+        let bytes = [0x00u8, 0x11, 0x1A, 0xCF, 0x22, 0x1A, 0xCF];
+        let bits = bytes.view_bits::<Msb0>();
+        let mut sync = Pattern::new("Sync".to_string(), PatternFormat::Hex, "0x1ACF".to_string(), 0).unwrap();
+        sync.search(bits);
+
+        let records = match_records(&[sync], bits, 1);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].position, 16);
+        assert_eq!(records[0].context_start, 8);
+        assert_eq!(records[0].context_hex, "111ACF22");
+        assert_eq!(records[1].delta, Some(24));
+        // Context is cut off at the end of the data
+        assert_eq!(records[1].context_hex, "221ACF");
+
+        let csv = matches_csv(&records);
+        assert_eq!(csv.lines().nth(1), Some("Sync,16,16,,0,0,8,111ACF22"));
+        let json: serde_json::Value = serde_json::from_str(&matches_json(&records).unwrap()).unwrap();
+        assert_eq!(json[1]["position"], 40);
+    }
+}
@@ -0,0 +1,138 @@
+// Match index - pattern matches as bit intervals sorted by start, so views can look up what
+// covers the cells they draw without scanning every match of every pattern
+
+use super::Pattern;
+use std::hash::{Hash, Hasher};
+
+/// Bits `start..end` matched by pattern number `pattern`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatchInterval {
+    pub start: usize,
+    pub end: usize,
+    pub pattern: usize,
+}
+
+/// The matches of a list of patterns, rebuilt when their searches change
+#[derive(Debug, Clone, Default)]
+pub struct MatchIndex {
+    /// Sorted by start
+    intervals: Vec<MatchInterval>,
+    /// Largest end among `intervals[..=i]`, for skipping intervals that end too early
+    max_end: Vec<usize>,
+    /// `patterns_fingerprint` of the patterns the index was built from
+    source: Option<u64>,
+}
+
+impl MatchIndex {
+    pub fn build(patterns: &[Pattern]) -> Self {
+        let mut intervals: Vec<MatchInterval> = patterns
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.visible)
+            .flat_map(|(pattern, p)| {
+                p.matches.iter().map(move |m| MatchInterval { start: m.position, end: m.position + p.bits.len(), pattern })
+            })
+            .collect();
+        intervals.sort_unstable_by_key(|interval| (interval.start, interval.pattern));
+        let max_end = intervals
+            .iter()
+            .scan(0, |max, interval| {
+                *max = interval.end.max(*max);
+                Some(*max)
+            })
+            .collect();
+        MatchIndex { intervals, max_end, source: Some(patterns_fingerprint(patterns)) }
+    }
+
+    /// Rebuilds the index if `patterns` were searched, added, removed, hidden or recolored since
+    /// it was built
+    pub fn refresh(&mut self, patterns: &[Pattern]) {
+        if self.source != Some(patterns_fingerprint(patterns)) {
+            *self = Self::build(patterns);
+        }
+    }
+
+    /// Changes whenever the index is rebuilt, for caches drawn from it
+    pub fn fingerprint(&self) -> u64 {
+        self.source.unwrap_or(0)
+    }
+
+    #[allow(dead_code)]
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Matches overlapping bits `start..end`, by start
+    pub fn overlapping(&self, start: usize, end: usize) -> impl Iterator<Item = &MatchInterval> {
+        let first = self.max_end.partition_point(|&max_end| max_end <= start);
+        let last = self.intervals.partition_point(|interval| interval.start < end);
+        self.intervals[first..last.max(first)].iter().filter(move |interval| interval.end > start)
+    }
+
+    /// Lowest numbered pattern with a match overlapping bits `start..end`
+    pub fn first_pattern(&self, start: usize, end: usize) -> Option<usize> {
+        self.overlapping(start, end).map(|interval| interval.pattern).min()
+    }
+}
+
+/// Changes whenever a pattern is added, removed, resized, searched again, hidden or recolored
+fn patterns_fingerprint(patterns: &[Pattern]) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    for pattern in patterns {
+        (pattern.visible, pattern.color).hash(&mut hasher);
+        pattern.bits.len().hash(&mut hasher);
+        pattern.matches.len().hash(&mut hasher);
+        pattern.matches.first().map(|m| m.position).hash(&mut hasher);
+        pattern.matches.last().map(|m| m.position).hash(&mut hasher);
+    }
+    patterns.len().hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::PatternFormat;
+    use bitvec::prelude::*;
+
+    #[test]
+    fn test_overlap_lookup() {
+        let bits = bitvec![u8, Msb0; 1, 0, 1, 1, 0, 0, 1, 0, 1, 1, 0, 1, 0, 0, 0, 0, 1, 0, 1, 1, 1, 1, 1, 1];
+        let mut long = Pattern::new("Long".to_string(), PatternFormat::Bits, "1011001011".to_string(), 0).unwrap();
+        let mut short = Pattern::new("Short".to_string(), PatternFormat::Bits, "1011".to_string(), 0).unwrap();
+        long.search(&bits);
+        short.search(&bits);
+        let mut patterns = vec![long, short];
+        let index = MatchIndex::build(&patterns);
+        assert_eq!(index.len(), patterns[0].matches.len() + patterns[1].matches.len());
+
+        // Checking the index against a scan of every match
+        for start in 0..bits.len() {
+            for end in start + 1..=bits.len() {
+                let scanned = patterns.iter().position(|pattern| {
+                    pattern.matches.iter().any(|m| m.position < end && m.position + pattern.bits.len() > start)
+                });
+                assert_eq!(index.first_pattern(start, end), scanned, "{}..{}", start, end);
+            }
+        }
+
+        let mut refreshed = index.clone();
+        refreshed.refresh(&patterns);
+        assert_eq!(refreshed.len(), index.len());
+        patterns[1].matches.clear();
+        refreshed.refresh(&patterns);
+        assert_eq!(refreshed.len(), patterns[0].matches.len());
+
+        // Hidden patterns' matches are left out
+        patterns[0].visible = false;
+        refreshed.refresh(&patterns);
+        assert!(refreshed.is_empty());
+        assert!(refreshed.first_pattern(0, bits.len()).is_none());
+        assert!(MatchIndex::default().first_pattern(0, 8).is_none());
+    }
+}
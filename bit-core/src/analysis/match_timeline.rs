@@ -0,0 +1,70 @@
+// Match timeline - the matches of every pattern merged in stream order
+
+use super::pattern_locator::Pattern;
+use std::collections::HashMap;
+
+/// One match on the combined timeline
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimelineEvent {
+    /// Index of the pattern that matched
+    pub pattern: usize,
+    pub position: usize,
+    pub length: usize,
+}
+
+/// Every pattern's matches in position order; matches at the same position keep pattern order
+pub fn match_timeline(patterns: &[Pattern]) -> Vec<TimelineEvent> {
+    let mut events: Vec<TimelineEvent> = patterns
+        .iter()
+        .enumerate()
+        .flat_map(|(pattern, p)| {
+            p.matches.iter().map(move |m| TimelineEvent { pattern, position: m.position, length: m.actual_bits.len() })
+        })
+        .collect();
+    events.sort_by_key(|event| (event.position, event.pattern));
+    events
+}
+
+/// How often a match of one pattern is directly followed by a match of another, as
+/// `((from, to), count)` with the most common pairs first
+pub fn pattern_transitions(events: &[TimelineEvent]) -> Vec<((usize, usize), usize)> {
+    let mut counts: HashMap<(usize, usize), usize> = HashMap::new();
+    for pair in events.windows(2) {
+        *counts.entry((pair[0].pattern, pair[1].pattern)).or_default() += 1;
+    }
+    let mut transitions: Vec<((usize, usize), usize)> = counts.into_iter().collect();
+    transitions.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    transitions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::PatternFormat;
+    use bitvec::prelude::*;
+
+    #[test]
+    fn test_timeline_orders_matches_across_patterns() {
+        // Header A5 is followed by either a 3C or a C3 message
+        let bytes = [0xA5u8, 0x3C, 0x00, 0xA5, 0xC3, 0x00, 0xA5, 0x3C];
+        let bits = bytes.view_bits::<Msb0>();
+        let mut patterns: Vec<Pattern> = ["0xA5", "0x3C", "0xC3"]
+            .iter()
+            .map(|hex| Pattern::new(hex.to_string(), PatternFormat::Hex, hex.to_string(), 0).unwrap())
+            .collect();
+        for pattern in &mut patterns {
+            pattern.search(bits);
+        }
+
+        let events = match_timeline(&patterns);
+        let order: Vec<usize> = events.iter().map(|event| event.pattern).collect();
+        assert_eq!(order, vec![0, 1, 0, 2, 0, 1]);
+        assert_eq!(events[1].position, 8);
+        assert_eq!(events[1].length, 8);
+
+        let transitions = pattern_transitions(&events);
+        assert_eq!(transitions[0], ((0, 1), 2));
+        assert!(transitions.contains(&((1, 0), 1)));
+        assert!(transitions.contains(&((2, 0), 1)));
+    }
+}
@@ -0,0 +1,51 @@
+// Analysis module - pattern matching and bit analysis tools
+
+pub mod pattern_locator;
+pub mod frame_width;
+pub mod hdlc;
+pub mod framer;
+pub mod repeats;
+pub mod periodicity;
+pub mod batch;
+pub mod match_export;
+pub mod match_timeline;
+pub mod xor_key;
+pub mod checksum;
+pub mod structure;
+pub mod protocol_export;
+pub mod session_search;
+pub mod column_layout;
+pub mod frame_stats;
+pub mod timestamp;
+pub mod report;
+pub mod bit_diff;
+pub mod bit_stats;
+pub mod sync_suggestion;
+pub mod match_index;
+pub mod frame_distance;
+pub mod frame_export;
+
+pub use pattern_locator::{Pattern, PatternFormat};
+pub use frame_width::{ConsistencyGrid, FrameWidthAnalysis, find_best_width_among, parse_width_list, score_width};
+pub use framer::{Frame, FrameCrc, split_frames, split_frames_between};
+pub use repeats::{RepeatCandidate, find_repeats, MAX_REPEAT_LENGTH};
+pub use periodicity::{PeriodicitySpectrum, periodicity_spectrum};
+pub use match_export::{match_records, matches_csv, matches_json};
+pub use match_timeline::{match_timeline, pattern_transitions};
+pub use batch::{BatchFramer, BatchRow, BatchSettings, batch_csv, list_capture_files, run_batch};
+pub use hdlc::{HdlcFrame, HdlcOptions, ax25_summary, decode_hdlc};
+pub use checksum::{ChecksumReport, checksum_report};
+pub use xor_key::{XorKeyCandidate, recover_xor_keys, MAX_XOR_KEY_LENGTH};
+pub use protocol_export::{ProtocolFormat, export_protocol, protocol_documentation};
+pub use structure::{DecodedField, decode_structure, parse_template};
+pub use session_search::{WorksheetMatches, search_session};
+pub use column_layout::anchored_columns;
+pub use frame_stats::{ColumnKind, ColumnStats, frame_stats, super_frame_stats};
+pub use timestamp::{TimestampCandidate, TimestampFormat, detect_timestamps, format_utc_millis};
+pub use report::HtmlReport;
+pub use bit_diff::{BitDiff, bit_diff};
+pub use bit_stats::{BitStats, bit_stats};
+pub use match_index::MatchIndex;
+pub use frame_distance::{FrameDistances, frame_distances};
+pub use frame_export::{frames_csv, parse_frame_fields};
+pub use sync_suggestion::{suggest_sync_pattern, MIN_SYNC_BITS};
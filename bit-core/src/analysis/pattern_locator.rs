@@ -0,0 +1,451 @@
+use bitvec::prelude::*;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::processing::ProgressReporter;
+
+/// Minimum number of window positions handed to each parallel search task
+const SEARCH_MIN_POSITIONS_PER_TASK: usize = 4096;
+
+/// Window positions searched between progress reports and cancellation checks
+const SEARCH_BLOCK_POSITIONS: usize = 1 << 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PatternFormat {
+    Hex,    // 0x prefix required
+    Ascii,  // Plain ASCII text
+    Bits,   // Raw bit sequence (0s and 1s)
+}
+
+impl PatternFormat {
+    #[allow(dead_code)]
+    pub fn name(&self) -> &str {
+        match self {
+            PatternFormat::Hex => "Hex (0x...)",
+            PatternFormat::Ascii => "ASCII",
+            PatternFormat::Bits => "Bits (0/1)",
+        }
+    }
+}
+
+fn default_visible() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Pattern {
+    pub name: String,
+    pub format: PatternFormat,
+    pub input: String,
+    pub garbles: usize,
+    /// Inserted or dropped bits tolerated inside a match, for clock-slipped captures
+    #[serde(default)]
+    pub slips: usize,
+    /// Highlight color of the matches; None takes the palette color for the pattern's position
+    #[serde(default)]
+    pub color: Option<[u8; 3]>,
+    /// Whether the views highlight the matches
+    #[serde(default = "default_visible")]
+    pub visible: bool,
+    #[serde(skip)]
+    pub bits: BitVec<u8, Msb0>,
+    #[serde(skip)]
+    pub matches: Vec<PatternMatch>,
+}
+
+impl Pattern {
+    pub fn new(name: String, format: PatternFormat, input: String, garbles: usize) -> Result<Self, String> {
+        let bits = Self::parse_input(&input, format)?;
+        
+        Ok(Pattern {
+            name,
+            format,
+            input,
+            garbles,
+            slips: 0,
+            color: None,
+            visible: true,
+            bits,
+            matches: Vec::new(),
+        })
+    }
+    
+    /// Parse input string into a bit sequence based on format
+    fn parse_input(input: &str, format: PatternFormat) -> Result<BitVec<u8, Msb0>, String> {
+        match format {
+            PatternFormat::Hex => Self::parse_hex(input),
+            PatternFormat::Ascii => Self::parse_ascii(input),
+            PatternFormat::Bits => Self::parse_bits(input),
+        }
+    }
+    
+    /// Parse hex string (must start with 0x)
+    fn parse_hex(input: &str) -> Result<BitVec<u8, Msb0>, String> {
+        let input = input.trim();
+        
+        if !input.starts_with("0x") && !input.starts_with("0X") {
+            return Err("Hex pattern must start with 0x".to_string());
+        }
+        
+        let hex_str = &input[2..];
+        if hex_str.is_empty() {
+            return Err("Hex pattern is empty".to_string());
+        }
+        
+        let mut bits = BitVec::<u8, Msb0>::new();
+        
+        for c in hex_str.chars() {
+            let nibble = c.to_digit(16)
+                .ok_or_else(|| format!("Invalid hex character: {}", c))? as u8;
+            
+            // Add 4 bits for each hex digit
+            bits.push((nibble & 0b1000) != 0);
+            bits.push((nibble & 0b0100) != 0);
+            bits.push((nibble & 0b0010) != 0);
+            bits.push((nibble & 0b0001) != 0);
+        }
+        
+        Ok(bits)
+    }
+    
+    /// Parse ASCII string (each character becomes 8 bits)
+    fn parse_ascii(input: &str) -> Result<BitVec<u8, Msb0>, String> {
+        if input.is_empty() {
+            return Err("ASCII pattern is empty".to_string());
+        }
+        
+        let mut bits = BitVec::<u8, Msb0>::new();
+        
+        for byte in input.as_bytes() {
+            // Add 8 bits for each ASCII character
+            for i in (0..8).rev() {
+                bits.push((byte & (1 << i)) != 0);
+            }
+        }
+        
+        Ok(bits)
+    }
+    
+    /// Parse raw bit string (only 0s and 1s allowed)
+    fn parse_bits(input: &str) -> Result<BitVec<u8, Msb0>, String> {
+        let input = input.trim();
+        
+        if input.is_empty() {
+            return Err("Bit pattern is empty".to_string());
+        }
+        
+        let mut bits = BitVec::<u8, Msb0>::new();
+        
+        for c in input.chars() {
+            match c {
+                '0' => bits.push(false),
+                '1' => bits.push(true),
+                ' ' | '_' => {}, // Allow spaces and underscores for readability
+                _ => return Err(format!("Invalid bit character: {}. Use only 0 and 1", c)),
+            }
+        }
+        
+        if bits.is_empty() {
+            return Err("Bit pattern is empty".to_string());
+        }
+        
+        Ok(bits)
+    }
+    
+    /// Update the pattern bits after input changes
+    pub fn update_bits(&mut self) -> Result<(), String> {
+        self.bits = Self::parse_input(&self.input, self.format)?;
+        Ok(())
+    }
+    
+    /// Search only `range` of `haystack`, reporting positions relative to the whole haystack.
+    /// Returns false, with no matches, if `progress` was cancelled.
+    pub fn search_range(&mut self, haystack: &BitSlice<u8, Msb0>, range: std::ops::Range<usize>, progress: &ProgressReporter) -> bool {
+        let offset = range.start;
+        if !self.search_with_progress(&haystack[range], progress) {
+            return false;
+        }
+        for m in &mut self.matches {
+            m.position += offset;
+        }
+        true
+    }
+    
+    /// Search for this pattern in the given bit sequence with garble tolerance
+    pub fn search(&mut self, haystack: &BitSlice<u8, Msb0>) {
+        self.search_with_progress(haystack, &ProgressReporter::new());
+    }
+    
+    /// Same as `search`, publishing the fraction searched to `progress`.
+    /// Returns false, with no matches, if `progress` was cancelled.
+    pub fn search_with_progress(&mut self, haystack: &BitSlice<u8, Msb0>, progress: &ProgressReporter) -> bool {
+        self.matches.clear();
+        
+        if self.bits.is_empty() || haystack.is_empty() || self.bits.len() > haystack.len() + self.slips {
+            return true;
+        }
+        
+        // With slips a match may run past the end of a window the pattern's length
+        let positions = if self.slips > 0 {
+            haystack.len()
+        } else {
+            haystack.len() - self.bits.len() + 1
+        };
+        
+        // Slide the pattern across the haystack a block at a time, checking positions in parallel.
+        // Results come back in position order, so deltas can be filled in afterwards.
+        let mut hits: Vec<(usize, SlipAlignment)> = Vec::new();
+        for block_start in (0..positions).step_by(SEARCH_BLOCK_POSITIONS) {
+            if progress.is_cancelled() {
+                return false;
+            }
+            let block_end = (block_start + SEARCH_BLOCK_POSITIONS).min(positions);
+            hits.extend(self.hits_in(haystack, block_start..block_end));
+            progress.set_fraction(block_end as f32 / positions as f32);
+        }
+        
+        // A match shifted by a bit or two also aligns using a slip; keep the best of each cluster
+        if self.slips > 0 {
+            let mut kept: Vec<(usize, SlipAlignment)> = Vec::new();
+            for (start, alignment) in hits {
+                match kept.last_mut() {
+                    Some((last_start, last)) if start - *last_start <= self.slips => {
+                        if alignment.edits() < last.edits() {
+                            *last_start = start;
+                            *last = alignment;
+                        }
+                    }
+                    _ => kept.push((start, alignment)),
+                }
+            }
+            hits = kept;
+        }
+        
+        let mut last_position: Option<usize> = None;
+        
+        for (start, alignment) in hits {
+            let delta = last_position.map(|last| start - last);
+            last_position = Some(start);
+            
+            self.matches.push(PatternMatch {
+                position: start,
+                actual_bits: haystack[start..start + alignment.length].to_bitvec(),
+                delta,
+                mismatches: alignment.substitutions,
+                slips: alignment.slips,
+            });
+        }
+        true
+    }
+    
+    /// Matches starting within `starts`, in position order
+    fn hits_in(&self, haystack: &BitSlice<u8, Msb0>, starts: std::ops::Range<usize>) -> Vec<(usize, SlipAlignment)> {
+        let pattern = &self.bits;
+        let (garbles, slips) = (self.garbles, self.slips);
+        
+        starts
+            .into_par_iter()
+            .with_min_len(SEARCH_MIN_POSITIONS_PER_TASK)
+            .filter_map(|start| {
+                if slips > 0 {
+                    // Edit-distance search: up to `slips` bits may also be inserted or dropped
+                    return align_with_slips(pattern, &haystack[start..], garbles, slips).map(|a| (start, a));
+                }
+                
+                // Count mismatches (Hamming distance)
+                let window = &haystack[start..start + pattern.len()];
+                let mismatches = pattern.iter()
+                    .zip(window.iter())
+                    .filter(|(a, b)| a != b)
+                    .count();
+                
+                // Check if within garble tolerance
+                (mismatches <= garbles).then_some((start, SlipAlignment {
+                    length: pattern.len(),
+                    substitutions: mismatches,
+                    slips: 0,
+                }))
+            })
+            .collect()
+    }
+}
+
+/// How a pattern lines up with the bits at one start position
+#[derive(Debug, Clone, Copy)]
+struct SlipAlignment {
+    /// Haystack bits covered by the match
+    length: usize,
+    substitutions: usize,
+    slips: usize,
+}
+
+impl SlipAlignment {
+    fn edits(&self) -> usize {
+        self.substitutions + self.slips
+    }
+}
+
+/// Best alignment of `pattern` at the start of `haystack` using at most `garbles`
+/// flipped bits and `slips` inserted or dropped bits, or None if there is none.
+///
+/// Dynamic programming over pattern bits consumed, how far the haystack has drifted
+/// from the pattern (-slips..=slips) and slips used so far; each cell holds the
+/// fewest flipped bits needed to get there.
+fn align_with_slips(pattern: &BitSlice<u8, Msb0>, haystack: &BitSlice<u8, Msb0>, garbles: usize, slips: usize) -> Option<SlipAlignment> {
+    const UNREACHABLE: usize = usize::MAX;
+    let drifts = 2 * slips + 1;
+    let cell = |drift: usize, used: usize| drift * (slips + 1) + used;
+    
+    // Row i: i pattern bits consumed, haystack bits consumed = i + drift - slips
+    let mut row = vec![UNREACHABLE; drifts * (slips + 1)];
+    row[cell(slips, 0)] = 0;
+    
+    for i in 0..pattern.len() {
+        let mut next = vec![UNREACHABLE; row.len()];
+        for drift in 0..drifts {
+            for used in 0..=slips {
+                let flips = row[cell(drift, used)];
+                if flips == UNREACHABLE {
+                    continue;
+                }
+                let consumed = i + drift - slips;
+                
+                // Pattern bit lines up with the next haystack bit
+                if consumed < haystack.len() {
+                    let cost = flips + (pattern[i] != haystack[consumed]) as usize;
+                    let target = &mut next[cell(drift, used)];
+                    *target = (*target).min(cost);
+                }
+                // Pattern bit was dropped from the haystack
+                if drift > 0 && used < slips {
+                    let target = &mut next[cell(drift - 1, used + 1)];
+                    *target = (*target).min(flips);
+                }
+            }
+        }
+        
+        // Extra haystack bit inserted before pattern bit i+1 (not after the last one,
+        // which would only lengthen the match)
+        if i + 1 < pattern.len() {
+            for drift in 1..drifts {
+                for used in 1..=slips {
+                    let from = next[cell(drift - 1, used - 1)];
+                    let target = &mut next[cell(drift, used)];
+                    *target = (*target).min(from);
+                }
+            }
+        }
+        
+        if next.iter().all(|&flips| flips == UNREACHABLE || flips > garbles) {
+            return None;
+        }
+        row = next;
+    }
+    
+    let mut best: Option<SlipAlignment> = None;
+    for drift in 0..drifts {
+        for used in 0..=slips {
+            let flips = row[cell(drift, used)];
+            if flips > garbles {
+                continue;
+            }
+            let alignment = SlipAlignment {
+                length: pattern.len() + drift - slips,
+                substitutions: flips,
+                slips: used,
+            };
+            if best.is_none_or(|b| alignment.edits() < b.edits()) {
+                best = Some(alignment);
+            }
+        }
+    }
+    best.filter(|alignment| alignment.length > 0)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatternMatch {
+    pub position: usize,        // Bit position where pattern was found
+    #[serde(skip)]
+    #[allow(dead_code)]
+    pub actual_bits: BitVec<u8, Msb0>,  // The actual bits that matched
+    pub delta: Option<usize>,   // Difference from previous match
+    pub mismatches: usize,      // Number of bit differences (garbles used)
+    #[serde(default)]
+    pub slips: usize,           // Number of inserted or dropped bits
+}
+
+impl PatternMatch {
+    /// Get a formatted string of the matched bits
+    #[allow(dead_code)]
+    pub fn bits_string(&self) -> String {
+        self.actual_bits.iter()
+            .map(|b| if *b { '1' } else { '0' })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn bits(text: &str) -> BitVec<u8, Msb0> {
+        text.chars().map(|c| c == '1').collect()
+    }
+    
+    fn pattern(input: &str, garbles: usize, slips: usize) -> Pattern {
+        let mut pattern = Pattern::new("Sync".to_string(), PatternFormat::Bits, input.to_string(), garbles).unwrap();
+        pattern.slips = slips;
+        pattern
+    }
+    
+    #[test]
+    fn test_garbles_allow_flipped_bits() {
+        let haystack = bits("0000110101100000");
+        let mut exact = pattern("11010110", 0, 0);
+        exact.search(&haystack);
+        assert_eq!(exact.matches.len(), 1);
+        assert_eq!(exact.matches[0].position, 4);
+        
+        let mut garbled = pattern("11110110", 1, 0);
+        garbled.search(&haystack);
+        assert_eq!(garbled.matches.len(), 1);
+        assert_eq!(garbled.matches[0].mismatches, 1);
+    }
+    
+    #[test]
+    fn test_slips_find_dropped_and_inserted_bits() {
+        // Sync word 11100010 with its fourth bit dropped, then with an extra 1 inserted
+        let haystack = bits(&["0101010", "1110010", "01010101010", "111010010", "010101"].concat());
+        
+        let mut strict = pattern("11100010", 0, 0);
+        strict.search(&haystack);
+        assert!(strict.matches.is_empty());
+        
+        let mut tolerant = pattern("11100010", 0, 1);
+        tolerant.search(&haystack);
+        let found: Vec<(usize, usize, usize)> = tolerant.matches
+            .iter()
+            .map(|m| (m.position, m.actual_bits.len(), m.slips))
+            .collect();
+        assert_eq!(found, vec![(7, 7, 1), (25, 9, 1)]);
+        assert!(tolerant.matches.iter().all(|m| m.mismatches == 0));
+    }
+    
+    #[test]
+    fn test_range_search_reports_absolute_positions() {
+        let haystack = bits("1101000011010000");
+        let mut sync = pattern("1101", 0, 0);
+        assert!(sync.search_range(&haystack, 4..16, &ProgressReporter::new()));
+        let positions: Vec<usize> = sync.matches.iter().map(|m| m.position).collect();
+        assert_eq!(positions, vec![8]);
+    }
+    
+    #[test]
+    fn test_cancelled_search_finds_nothing() {
+        let haystack = bits("1101000011010000");
+        let mut sync = pattern("1101", 0, 0);
+        let progress = ProgressReporter::new();
+        progress.token.cancel();
+        assert!(!sync.search_with_progress(&haystack, &progress));
+        assert!(sync.matches.is_empty());
+    }
+}
@@ -0,0 +1,145 @@
+// Periodicity spectrum - autocorrelation of a bit stream against candidate periods
+
+use bitvec::prelude::*;
+use rayon::prelude::*;
+
+/// Bits compared at most, so huge streams stay responsive
+const MAX_SCANNED_BITS: usize = 8 * 1024 * 1024;
+
+/// Peaks reported at most
+const MAX_PEAKS: usize = 10;
+
+/// Match score for every candidate period
+#[derive(Debug, Clone)]
+pub struct PeriodicitySpectrum {
+    /// (period, score) pairs; the score is the autocorrelation at that lag,
+    /// 1.0 when every bit equals the bit one period later and 0.0 for random data
+    pub scores: Vec<(usize, f64)>,
+    /// Periods that stand out from the rest of the spectrum, strongest first
+    pub peaks: Vec<usize>,
+}
+
+/// Compute the spectrum for periods `min_period..=max_period`
+pub fn periodicity_spectrum(bits: &BitSlice<u8, Msb0>, min_period: usize, max_period: usize) -> PeriodicitySpectrum {
+    let bits = &bits[..bits.len().min(MAX_SCANNED_BITS)];
+    let min_period = min_period.max(1);
+    // Each lag needs at least as many compared bits as the lag itself to mean anything
+    let max_period = max_period.min(bits.len() / 2);
+    if min_period > max_period {
+        return PeriodicitySpectrum { scores: Vec::new(), peaks: Vec::new() };
+    }
+
+    let words = pack_words(bits);
+    let scores: Vec<(usize, f64)> = (min_period..=max_period)
+        .into_par_iter()
+        .map(|period| {
+            let compared = bits.len() - period;
+            let differing = count_differences(&words, period, compared);
+            let matching = compared - differing;
+            (period, 2.0 * matching as f64 / compared as f64 - 1.0)
+        })
+        .collect();
+
+    let peaks = find_peaks(&scores);
+    PeriodicitySpectrum { scores, peaks }
+}
+
+/// Bits packed MSB-first into u64 words, the last one padded with zeros
+fn pack_words(bits: &BitSlice<u8, Msb0>) -> Vec<u64> {
+    bits.chunks(64)
+        .map(|chunk| chunk.load_be::<u64>() << (64 - chunk.len()))
+        .collect()
+}
+
+/// 64 bits starting at bit `position`
+fn word_at(words: &[u64], position: usize) -> u64 {
+    let (index, shift) = (position / 64, position % 64);
+    let high = words.get(index).copied().unwrap_or(0);
+    if shift == 0 {
+        return high;
+    }
+    let low = words.get(index + 1).copied().unwrap_or(0);
+    (high << shift) | (low >> (64 - shift))
+}
+
+/// Number of the first `compared` bits that differ from the bit `lag` positions later
+fn count_differences(words: &[u64], lag: usize, compared: usize) -> usize {
+    let mut differing = 0;
+    let mut position = 0;
+    while position < compared {
+        let remaining = compared - position;
+        let mut diff = word_at(words, position) ^ word_at(words, position + lag);
+        if remaining < 64 {
+            diff &= !(u64::MAX >> remaining);
+        }
+        differing += diff.count_ones() as usize;
+        position += 64;
+    }
+    differing
+}
+
+/// Local maxima that rise well above the spectrum's typical level
+fn find_peaks(scores: &[(usize, f64)]) -> Vec<usize> {
+    if scores.len() < 3 {
+        return Vec::new();
+    }
+
+    let mean = scores.iter().map(|(_, s)| s).sum::<f64>() / scores.len() as f64;
+    let variance = scores.iter().map(|(_, s)| (s - mean).powi(2)).sum::<f64>() / scores.len() as f64;
+    let threshold = mean + 2.0 * variance.sqrt();
+
+    let mut peaks: Vec<(usize, f64)> = scores
+        .windows(3)
+        .filter(|w| w[1].1 > threshold && w[1].1 >= w[0].1 && w[1].1 > w[2].1)
+        .map(|w| w[1])
+        .collect();
+    peaks.sort_by(|a, b| b.1.total_cmp(&a.1));
+    peaks.truncate(MAX_PEAKS);
+    peaks.into_iter().map(|(period, _)| period).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random bits
+    fn noise(len: usize, seed: u64) -> BitVec<u8, Msb0> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state & 1 == 1
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_differences_match_bit_by_bit_count() {
+        let bits = noise(1000, 7);
+        let words = pack_words(&bits);
+        for lag in [1, 63, 64, 65, 300] {
+            let compared = bits.len() - lag;
+            let expected = (0..compared).filter(|&i| bits[i] != bits[i + lag]).count();
+            assert_eq!(count_differences(&words, lag, compared), expected, "lag {}", lag);
+        }
+    }
+
+    #[test]
+    fn test_repeating_block_peaks_at_its_period_and_harmonics() {
+        let block = noise(37, 3);
+        let mut bits = BitVec::<u8, Msb0>::new();
+        for _ in 0..100 {
+            bits.extend_from_bitslice(&block);
+        }
+
+        let spectrum = periodicity_spectrum(&bits, 1, 120);
+        let score_at = |period: usize| spectrum.scores.iter().find(|(p, _)| *p == period).unwrap().1;
+        assert_eq!(score_at(37), 1.0);
+        assert_eq!(score_at(74), 1.0);
+        assert!(score_at(50) < 0.5);
+        assert!(spectrum.peaks.contains(&37));
+        assert!(spectrum.peaks.contains(&111));
+    }
+}
@@ -0,0 +1,271 @@
+// Protocol export - byte view columns as Kaitai Struct or Wireshark dissector skeletons
+
+use crate::view::{ByteColumn, ByteViewConfig};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProtocolFormat {
+    /// Kaitai Struct `.ksy` file
+    Kaitai,
+    /// Lua Wireshark dissector
+    WiresharkLua,
+}
+
+impl ProtocolFormat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProtocolFormat::Kaitai => "Kaitai Struct",
+            ProtocolFormat::WiresharkLua => "Wireshark Lua",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ProtocolFormat::Kaitai => "ksy",
+            ProtocolFormat::WiresharkLua => "lua",
+        }
+    }
+}
+
+/// A column laid out inside the record, or a gap between columns
+struct LaidOutField {
+    id: String,
+    label: String,
+    bit_start: usize,
+    bits: usize,
+    reserved: bool,
+}
+
+/// Columns sorted by start bit with unnamed reserved fields filling the gaps up to
+/// `record_bits`. Columns overlapping an earlier one can't be expressed as a sequence and are
+/// returned separately by label.
+fn lay_out(columns: &[ByteColumn], record_bits: usize) -> (Vec<LaidOutField>, Vec<String>) {
+    let mut sorted: Vec<&ByteColumn> = columns.iter().collect();
+    sorted.sort_by_key(|column| column.bit_start);
+
+    let mut fields: Vec<LaidOutField> = Vec::new();
+    let mut skipped = Vec::new();
+    let mut position = 0;
+    for column in sorted {
+        if column.bit_start < position {
+            skipped.push(column.label.clone());
+            continue;
+        }
+        if column.bit_start > position {
+            fields.push(reserved_field(&fields, position, column.bit_start - position));
+        }
+        fields.push(LaidOutField {
+            id: unique_id(&fields, identifier(&column.label)),
+            label: column.label.clone(),
+            bit_start: column.bit_start,
+            bits: column.width(),
+            reserved: false,
+        });
+        position = column.bit_end + 1;
+    }
+    if record_bits > position {
+        fields.push(reserved_field(&fields, position, record_bits - position));
+    }
+    (fields, skipped)
+}
+
+/// A gap field following `fields`
+fn reserved_field(fields: &[LaidOutField], bit_start: usize, bits: usize) -> LaidOutField {
+    let id = unique_id(fields, format!("reserved_{}", fields.len()));
+    LaidOutField { id, label: "Reserved".to_string(), bit_start, bits, reserved: true }
+}
+
+/// `base`, with a numeric suffix if a field in `fields` already has that id
+fn unique_id(fields: &[LaidOutField], base: String) -> String {
+    let mut id = base.clone();
+    let mut suffix = 2;
+    while fields.iter().any(|field| field.id == id) {
+        id = format!("{}_{}", base, suffix);
+        suffix += 1;
+    }
+    id
+}
+
+/// `label` as a lower-case identifier both formats accept
+fn identifier(label: &str) -> String {
+    let mut id = String::new();
+    for c in label.trim().chars() {
+        if c.is_ascii_alphanumeric() {
+            id.push(c.to_ascii_lowercase());
+        } else if !id.is_empty() && !id.ends_with('_') {
+            id.push('_');
+        }
+    }
+    let id = id.trim_end_matches('_').to_string();
+    match id.chars().next() {
+        None => "field".to_string(),
+        Some(c) if c.is_ascii_digit() => format!("f_{}", id),
+        Some(_) => id,
+    }
+}
+
+/// Plain-text description of the byte view columns
+pub fn protocol_documentation(config: &ByteViewConfig) -> String {
+    let mut doc = String::new();
+    doc.push_str("Protocol Documentation\n");
+    doc.push_str("=====================\n\n");
+    doc.push_str(&format!("Bytes per row: {}\n", config.bytes_per_row));
+    match config.column_frame_length {
+        Some(frame_length) => doc.push_str(&format!("Bit ranges are relative to {}-bit frames\n\n", frame_length)),
+        None => doc.push_str("Bit ranges are relative to the display row\n\n"),
+    }
+    doc.push_str("Field Definitions:\n");
+    doc.push_str("------------------\n\n");
+
+    for (idx, column) in config.columns.iter().enumerate() {
+        doc.push_str(&format!("{}. {}\n", idx + 1, column.label));
+        doc.push_str(&format!("   Bit Range: {} - {}\n", column.bit_start, column.bit_end));
+        let (start_byte, end_byte) = column.byte_range(config.bytes_per_row);
+        doc.push_str(&format!("   Byte Range: {} - {}\n", start_byte, end_byte));
+        doc.push_str(&format!("   Color: RGB({}, {}, {})\n\n", column.color[0], column.color[1], column.color[2]));
+    }
+    doc
+}
+
+/// A skeleton definition of one record made of `columns`, `record_bits` long, named `name`
+pub fn export_protocol(format: ProtocolFormat, name: &str, columns: &[ByteColumn], record_bits: usize) -> String {
+    let (fields, skipped) = lay_out(columns, record_bits);
+    match format {
+        ProtocolFormat::Kaitai => kaitai_ksy(name, &fields, &skipped),
+        ProtocolFormat::WiresharkLua => wireshark_lua(name, &fields, &skipped),
+    }
+}
+
+fn kaitai_ksy(name: &str, fields: &[LaidOutField], skipped: &[String]) -> String {
+    let mut ksy = String::new();
+    ksy.push_str("# Generated by B.I.T. from the byte view columns\n");
+    for label in skipped {
+        ksy.push_str(&format!("# Left out '{}': it overlaps an earlier column\n", label));
+    }
+    ksy.push_str("meta:\n");
+    ksy.push_str(&format!("  id: {}\n", identifier(name)));
+    ksy.push_str("  endian: be\n");
+    ksy.push_str("  bit-endian: be\n");
+    ksy.push_str("seq:\n");
+    for field in fields {
+        ksy.push_str(&format!("  - id: {}\n", field.id));
+        // Whole bytes on a byte boundary read as integers, anything else as a bit field
+        let aligned = field.bit_start.is_multiple_of(8);
+        match field.bits {
+            8 | 16 | 32 | 64 if aligned => ksy.push_str(&format!("    type: u{}\n", field.bits / 8)),
+            bits if aligned && bits.is_multiple_of(8) => ksy.push_str(&format!("    size: {}\n", bits / 8)),
+            bits => ksy.push_str(&format!("    type: b{}\n", bits)),
+        }
+        if !field.reserved && field.label != field.id {
+            ksy.push_str(&format!("    doc: {}\n", yaml_string(&field.label)));
+        }
+    }
+    ksy
+}
+
+fn yaml_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn lua_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn wireshark_lua(name: &str, fields: &[LaidOutField], skipped: &[String]) -> String {
+    let proto = identifier(name);
+    let mut lua = String::new();
+    lua.push_str("-- Generated by B.I.T. from the byte view columns\n");
+    for label in skipped {
+        lua.push_str(&format!("-- Left out '{}': it overlaps an earlier column\n", label));
+    }
+    lua.push_str(&format!("local proto = Proto({}, {})\n\n", lua_string(&proto), lua_string(name)));
+
+    // Each field reads the bytes it touches, masked down to its bits
+    let mut reads = Vec::new();
+    for field in fields.iter().filter(|field| !field.reserved) {
+        let first_byte = field.bit_start / 8;
+        let last_byte = (field.bit_start + field.bits - 1) / 8;
+        let byte_count = last_byte - first_byte + 1;
+        let abbrev = lua_string(&format!("{}.{}", proto, field.id));
+        let label = lua_string(&field.label);
+        let fits_exactly = field.bit_start.is_multiple_of(8) && field.bits == byte_count * 8;
+        let declaration = match byte_count {
+            1..=4 | 8 if fits_exactly => format!("ProtoField.uint{}({}, {}, base.HEX)", byte_count * 8, abbrev, label),
+            1..=4 | 8 => {
+                let width = if byte_count <= 4 { byte_count * 8 } else { 64 };
+                let shift = (last_byte + 1) * 8 - (field.bit_start + field.bits);
+                let mask = ((1u128 << field.bits) - 1) << shift;
+                format!("ProtoField.uint{}({}, {}, base.DEC, nil, 0x{:X})", width, abbrev, label, mask)
+            }
+            _ => format!("ProtoField.bytes({}, {})", abbrev, label),
+        };
+        lua.push_str(&format!("local f_{} = {}\n", field.id, declaration));
+        reads.push((field.id.clone(), first_byte, byte_count));
+    }
+    let record_bytes = fields.last().map_or(0, |field| (field.bit_start + field.bits).div_ceil(8));
+
+    lua.push_str("\nproto.fields = {");
+    lua.push_str(&reads.iter().map(|(id, _, _)| format!("f_{}", id)).collect::<Vec<_>>().join(", "));
+    lua.push_str("}\n\n");
+    lua.push_str("function proto.dissector(buffer, pinfo, tree)\n");
+    lua.push_str(&format!("    if buffer:len() < {} then return 0 end\n", record_bytes));
+    lua.push_str("    pinfo.cols.protocol = proto.name\n");
+    lua.push_str(&format!("    local subtree = tree:add(proto, buffer(0, {}))\n", record_bytes));
+    for (id, first_byte, byte_count) in &reads {
+        lua.push_str(&format!("    subtree:add(f_{}, buffer({}, {}))\n", id, first_byte, byte_count));
+    }
+    lua.push_str(&format!("    return {}\n", record_bytes));
+    lua.push_str("end\n\n");
+    lua.push_str("-- Register the dissector where the protocol is carried, for example:\n");
+    lua.push_str("-- DissectorTable.get(\"udp.port\"):add(5000, proto)\n");
+    lua
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn columns() -> Vec<ByteColumn> {
+        vec![
+            ByteColumn::new("Sync Word".to_string(), 0, 15, [0, 0, 0]),
+            ByteColumn::new("Flags".to_string(), 20, 23, [0, 0, 0]),
+            ByteColumn::new("Payload".to_string(), 24, 47, [0, 0, 0]),
+            ByteColumn::new("Overlap".to_string(), 22, 25, [0, 0, 0]),
+        ]
+    }
+
+    #[test]
+    fn test_kaitai_fills_gaps_and_skips_overlaps() {
+        let ksy = export_protocol(ProtocolFormat::Kaitai, "My Proto", &columns(), 56);
+        assert!(ksy.contains("  id: my_proto\n"));
+        assert!(ksy.contains("  - id: sync_word\n    type: u2\n    doc: \"Sync Word\"\n"));
+        assert!(ksy.contains("  - id: reserved_1\n    type: b4\n"));
+        assert!(ksy.contains("  - id: flags\n    type: b4\n"));
+        assert!(ksy.contains("  - id: payload\n    size: 3\n"));
+        assert!(ksy.contains("  - id: reserved_4\n    type: u1\n"));
+        assert!(ksy.contains("# Left out 'Overlap'"));
+    }
+
+    #[test]
+    fn test_gap_ids_stay_unique() {
+        // The gap after "Sync" is the third field, so it would be reserved_2 too
+        let columns = vec![
+            ByteColumn::new("Reserved 2".to_string(), 0, 7, [0, 0, 0]),
+            ByteColumn::new("Sync".to_string(), 8, 15, [0, 0, 0]),
+        ];
+        let ksy = export_protocol(ProtocolFormat::Kaitai, "Proto", &columns, 32);
+        assert!(ksy.contains("  - id: reserved_2\n    type: u1\n    doc: \"Reserved 2\"\n"));
+        assert!(ksy.contains("  - id: reserved_2_2\n    type: u2\n"));
+        assert_eq!(ksy.matches("  - id: reserved_2\n").count(), 1);
+    }
+
+    #[test]
+    fn test_lua_masks_bit_fields() {
+        let lua = export_protocol(ProtocolFormat::WiresharkLua, "My Proto", &columns(), 56);
+        assert!(lua.contains("local f_sync_word = ProtoField.uint16(\"my_proto.sync_word\", \"Sync Word\", base.HEX)"));
+        assert!(lua.contains("local f_flags = ProtoField.uint8(\"my_proto.flags\", \"Flags\", base.DEC, nil, 0xF)"));
+        assert!(lua.contains("local f_payload = ProtoField.uint24(\"my_proto.payload\", \"Payload\", base.HEX)"));
+        assert!(lua.contains("    subtree:add(f_flags, buffer(2, 1))\n"));
+        assert!(lua.contains("if buffer:len() < 7 then return 0 end"));
+        assert!(lua.contains("proto.fields = {f_sync_word, f_flags, f_payload}"));
+    }
+}
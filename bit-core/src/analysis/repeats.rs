@@ -0,0 +1,166 @@
+// Repeating sequences - finds bit strings that occur far more often than chance, e.g. sync words
+
+use bitvec::prelude::*;
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+/// Longest sequence searched for; windows are hashed as u64 values
+pub const MAX_REPEAT_LENGTH: usize = 64;
+
+/// Bits scanned at most, so huge streams stay responsive
+const MAX_SCANNED_BITS: usize = 16 * 1024 * 1024;
+
+/// Positions remembered per candidate
+const MAX_POSITIONS: usize = 64;
+
+/// A sequence that repeats in the stream
+#[derive(Debug, Clone)]
+pub struct RepeatCandidate {
+    pub bits: BitVec<u8, Msb0>,
+    pub count: usize,
+    /// First few positions where the sequence starts
+    pub positions: Vec<usize>,
+    /// How many times more often it occurs than in random data, as log2
+    pub surprise: f64,
+}
+
+impl RepeatCandidate {
+    pub fn bits_string(&self) -> String {
+        self.bits.iter().map(|b| if *b { '1' } else { '0' }).collect()
+    }
+
+    /// Hex form when the length is a whole number of nibbles
+    pub fn hex_string(&self) -> Option<String> {
+        if !self.bits.len().is_multiple_of(4) {
+            return None;
+        }
+        let digits: String = self.bits
+            .chunks(4)
+            .map(|nibble| char::from_digit(nibble.load_be::<u32>(), 16).unwrap_or('?').to_ascii_uppercase())
+            .collect();
+        Some(format!("0x{}", digits))
+    }
+}
+
+/// Find the `limit` most surprising sequences of `min_len..=max_len` bits that occur at least twice.
+/// Shorter sequences that only occur as part of a better candidate are left out.
+pub fn find_repeats(bits: &BitSlice<u8, Msb0>, min_len: usize, max_len: usize, limit: usize) -> Vec<RepeatCandidate> {
+    let bits = &bits[..bits.len().min(MAX_SCANNED_BITS)];
+    let max_len = max_len.min(MAX_REPEAT_LENGTH);
+    let min_len = min_len.max(1);
+    if min_len > max_len || bits.len() < min_len * 2 {
+        return Vec::new();
+    }
+
+    let mut candidates: Vec<RepeatCandidate> = (min_len..=max_len)
+        .into_par_iter()
+        .flat_map_iter(|len| candidates_of_length(bits, len, limit))
+        .collect();
+
+    candidates.sort_by(|a, b| b.surprise.total_cmp(&a.surprise).then(b.bits.len().cmp(&a.bits.len())));
+
+    // Keep the best candidates, skipping parts of ones already kept that don't repeat on their own
+    let mut kept: Vec<RepeatCandidate> = Vec::new();
+    for candidate in candidates {
+        if kept.len() >= limit {
+            break;
+        }
+        let redundant = kept.iter().any(|better| {
+            better.bits.len() > candidate.bits.len()
+                && candidate.count <= better.count + better.count / 10
+                && better.bits.windows(candidate.bits.len()).any(|window| window == candidate.bits)
+        });
+        if !redundant {
+            kept.push(candidate);
+        }
+    }
+    kept
+}
+
+/// The most frequent windows of exactly `len` bits
+fn candidates_of_length(bits: &BitSlice<u8, Msb0>, len: usize, limit: usize) -> Vec<RepeatCandidate> {
+    let windows = bits.len() - len + 1;
+    let mask = if len == 64 { u64::MAX } else { (1u64 << len) - 1 };
+
+    let mut counts: HashMap<u64, (usize, Vec<usize>)> = HashMap::new();
+    let mut value = bits[..len - 1].iter().fold(0u64, |acc, bit| (acc << 1) | *bit as u64);
+    for start in 0..windows {
+        value = ((value << 1) | bits[start + len - 1] as u64) & mask;
+        let entry = counts.entry(value).or_insert_with(|| (0, Vec::new()));
+        entry.0 += 1;
+        if entry.1.len() < MAX_POSITIONS {
+            entry.1.push(start);
+        }
+    }
+
+    // log2 of the expected count of one particular value in random data
+    let expected_log2 = (windows as f64).log2() - len as f64;
+
+    let mut frequent: Vec<(u64, usize, Vec<usize>)> = counts
+        .into_iter()
+        .filter(|(_, (count, _))| *count >= 2)
+        .map(|(value, (count, positions))| (value, count, positions))
+        .collect();
+    frequent.sort_by_key(|entry| std::cmp::Reverse(entry.1));
+    frequent.truncate(limit);
+
+    frequent
+        .into_iter()
+        .map(|(value, count, positions)| {
+            let mut pattern = BitVec::<u8, Msb0>::with_capacity(len);
+            for i in (0..len).rev() {
+                pattern.push((value >> i) & 1 == 1);
+            }
+            RepeatCandidate {
+                bits: pattern,
+                count,
+                positions,
+                surprise: (count as f64).log2() - expected_log2,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random bits
+    fn noise(len: usize, seed: u64) -> BitVec<u8, Msb0> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state & 1 == 1
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sync_word_is_the_top_candidate() {
+        let sync = 0x1ACF_FC1Du32;
+        let mut bits = BitVec::<u8, Msb0>::new();
+        for frame in 0..40 {
+            bits.extend_from_bitslice(sync.to_be_bytes().view_bits::<Msb0>());
+            bits.extend_from_bitslice(&noise(200, frame + 1));
+        }
+
+        let candidates = find_repeats(&bits, 8, 32, 10);
+        let best = &candidates[0];
+        assert_eq!(best.bits.len(), 32);
+        assert_eq!(best.hex_string().as_deref(), Some("0x1ACFFC1D"));
+        assert_eq!(best.count, 40);
+        assert_eq!(best.positions[1], 232);
+
+        // Pieces of the sync word don't crowd the list
+        assert!(candidates.iter().skip(1).all(|c| c.count < 40 || c.bits.len() == 32));
+    }
+
+    #[test]
+    fn test_too_little_data_finds_nothing() {
+        let bits = bitvec![u8, Msb0; 1, 0, 1];
+        assert!(find_repeats(&bits, 8, 16, 10).is_empty());
+    }
+}
@@ -0,0 +1,226 @@
+// HTML report - a self-contained page documenting a protocol, for sharing read-only
+
+use super::pattern_locator::Pattern;
+use super::protocol_export::protocol_documentation;
+use crate::processing::BitOperation;
+use crate::view::ByteViewConfig;
+use bitvec::prelude::*;
+
+/// Bytes drawn per snapshot, so a large selection doesn't make a huge page
+const MAX_SNAPSHOT_BYTES: usize = 4096;
+/// Rows per pattern's match table
+const MAX_MATCH_ROWS: usize = 1000;
+
+/// What goes into the report
+pub struct HtmlReport<'a> {
+    pub title: &'a str,
+    pub config: &'a ByteViewConfig,
+    /// Data the snapshots and matches refer to
+    pub bits: &'a BitSlice<u8, Msb0>,
+    /// Regions drawn as byte view snapshots, as (start, end) bit ranges, end exclusive
+    pub regions: &'a [(usize, usize)],
+    pub patterns: &'a [Pattern],
+    pub operations: &'a [BitOperation],
+}
+
+impl HtmlReport<'_> {
+    pub fn render(&self) -> String {
+        let mut html = String::new();
+        html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        html.push_str(&format!("<title>{}</title>\n", escape(self.title)));
+        html.push_str(STYLE);
+        html.push_str("</head>\n<body>\n");
+        html.push_str(&format!("<h1>{}</h1>\n", escape(self.title)));
+
+        html.push_str("<h2>Protocol Documentation</h2>\n");
+        html.push_str(&format!("<pre>{}</pre>\n", escape(&protocol_documentation(self.config))));
+        self.push_legend(&mut html);
+
+        html.push_str("<h2>Operation Chain</h2>\n");
+        self.push_operations(&mut html);
+
+        html.push_str("<h2>Snapshots</h2>\n");
+        if self.regions.is_empty() {
+            html.push_str("<p>No regions selected.</p>\n");
+        }
+        for &(start, end) in self.regions {
+            self.push_snapshot(&mut html, start, end);
+        }
+
+        html.push_str("<h2>Pattern Matches</h2>\n");
+        self.push_matches(&mut html);
+
+        html.push_str("<p class=\"footer\">Generated by B.I.T.</p>\n</body>\n</html>\n");
+        html
+    }
+
+    fn push_legend(&self, html: &mut String) {
+        let columns: Vec<_> = self.config.columns.iter().filter(|column| column.visible).collect();
+        if columns.is_empty() {
+            return;
+        }
+        html.push_str("<p class=\"legend\">");
+        for column in columns {
+            html.push_str(&format!(
+                "<span style=\"background:{}\">{} ({}-{})</span> ",
+                css_color(column.color),
+                escape(&column.label),
+                column.bit_start,
+                column.bit_end
+            ));
+        }
+        html.push_str("</p>\n");
+    }
+
+    fn push_operations(&self, html: &mut String) {
+        if self.operations.is_empty() {
+            html.push_str("<p>No operations; the data is shown as loaded.</p>\n");
+            return;
+        }
+        html.push_str("<ol>\n");
+        for op in self.operations {
+            let class = if op.is_enabled() { "" } else { " class=\"disabled\"" };
+            html.push_str(&format!(
+                "<li{}><b>{}</b>: {}{}</li>\n",
+                class,
+                escape(op.name()),
+                escape(&op.description()),
+                if op.is_enabled() { "" } else { " (disabled)" }
+            ));
+        }
+        html.push_str("</ol>\n");
+    }
+
+    /// The display rows covering `start..end` as a hex table colored by column, with the
+    /// bytes outside the region dimmed
+    fn push_snapshot(&self, html: &mut String, start: usize, end: usize) {
+        let end = end.min(self.bits.len());
+        if start >= end {
+            return;
+        }
+        let row_bytes = self.config.bytes_per_row.max(1);
+        let first_row = start / 8 / row_bytes;
+        let last_byte = end.div_ceil(8);
+        let rows_end = (last_byte.div_ceil(row_bytes) * row_bytes).min(self.bits.len().div_ceil(8));
+        let shown_end = rows_end.min(first_row * row_bytes + MAX_SNAPSHOT_BYTES);
+
+        html.push_str(&format!("<h3>Bits {}..{} (bytes 0x{:X}..0x{:X})</h3>\n", start, end, start / 8, last_byte));
+        if shown_end < rows_end {
+            html.push_str(&format!("<p>Only the first {} bytes are shown.</p>\n", MAX_SNAPSHOT_BYTES));
+        }
+        html.push_str("<table class=\"bytes\">\n");
+        for row_start in (first_row * row_bytes..shown_end).step_by(row_bytes) {
+            html.push_str(&format!("<tr><th>{:08X}</th>", row_start));
+            for byte in row_start..(row_start + row_bytes).min(shown_end) {
+                let bit_start = byte * 8;
+                let bit_end = (bit_start + 8).min(self.bits.len());
+                let chunk = &self.bits[bit_start..bit_end];
+                let value = chunk.load_be::<u8>() << (8 - chunk.len());
+
+                let mut style = String::new();
+                if let Some(column) = (bit_start..bit_end)
+                    .find_map(|bit| self.config.column_at(self.config.column_bit_offset(bit)))
+                    .map(|index| &self.config.columns[index])
+                {
+                    style.push_str(&format!("background:{};", css_color(column.color)));
+                }
+                if bit_end <= start || bit_start >= end {
+                    style.push_str("opacity:0.35;");
+                }
+                html.push_str(&format!("<td style=\"{}\">{:02X}</td>", style, value));
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</table>\n");
+    }
+
+    fn push_matches(&self, html: &mut String) {
+        let searched: Vec<&Pattern> = self.patterns.iter().filter(|pattern| !pattern.matches.is_empty()).collect();
+        if searched.is_empty() {
+            html.push_str("<p>No pattern matches.</p>\n");
+            return;
+        }
+        for pattern in searched {
+            html.push_str(&format!(
+                "<h3>{} <code>{}</code>: {} matches</h3>\n",
+                escape(&pattern.name),
+                escape(&pattern.input),
+                pattern.matches.len()
+            ));
+            html.push_str("<table class=\"matches\">\n<tr><th>#</th><th>Bit</th><th>Byte</th><th>Delta</th><th>Mismatches</th></tr>\n");
+            for (index, m) in pattern.matches.iter().take(MAX_MATCH_ROWS).enumerate() {
+                html.push_str(&format!(
+                    "<tr><td>{}</td><td>{}</td><td>0x{:X}{}</td><td>{}</td><td>{}</td></tr>\n",
+                    index + 1,
+                    m.position,
+                    m.position / 8,
+                    if m.position.is_multiple_of(8) { String::new() } else { format!(" +{}", m.position % 8) },
+                    m.delta.map_or(String::new(), |delta| delta.to_string()),
+                    m.mismatches
+                ));
+            }
+            html.push_str("</table>\n");
+            if pattern.matches.len() > MAX_MATCH_ROWS {
+                html.push_str(&format!("<p>First {} matches shown.</p>\n", MAX_MATCH_ROWS));
+            }
+        }
+    }
+}
+
+const STYLE: &str = "<style>
+body { font-family: sans-serif; margin: 2em; color: #222; }
+pre, code, table.bytes { font-family: monospace; }
+table { border-collapse: collapse; margin-bottom: 1em; }
+td, th { border: 1px solid #ccc; padding: 2px 6px; }
+table.bytes th { color: #888; font-weight: normal; }
+.legend span { padding: 2px 6px; margin-right: 4px; border-radius: 3px; }
+li.disabled { color: #999; }
+.footer { color: #888; font-size: small; }
+</style>
+";
+
+fn css_color([r, g, b]: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::PatternFormat;
+    use crate::view::ByteColumn;
+
+    #[test]
+    fn test_report_contains_every_section() {
+        let bytes = [0x1Au8, 0xCF, 0x01, 0x02, 0x1A, 0xCF, 0x03, 0x04];
+        let bits = bytes.view_bits::<Msb0>();
+        let mut config = ByteViewConfig { bytes_per_row: 4, ..Default::default() };
+        config.columns.push(ByteColumn::new("Sync <word>".to_string(), 0, 15, [255, 0, 0]));
+        let mut sync = Pattern::new("Sync".to_string(), PatternFormat::Hex, "0x1ACF".to_string(), 0).unwrap();
+        sync.search(bits);
+        let operations = vec![BitOperation::InvertBits { name: "Invert".to_string(), enabled: false }];
+
+        let html = HtmlReport {
+            title: "Test & Report",
+            config: &config,
+            bits,
+            regions: &[(32, 48)],
+            patterns: &[sync],
+            operations: &operations,
+        }
+        .render();
+
+        assert!(html.contains("<title>Test &amp; Report</title>"));
+        assert!(html.contains("Sync &lt;word&gt;"));
+        assert!(html.contains("(disabled)"));
+        // Only the second row is drawn, with the bytes after the region dimmed
+        assert!(html.contains("<tr><th>00000004</th><td style=\"background:#ff0000;\">1A</td>"));
+        assert!(html.contains("<td style=\"opacity:0.35;\">04</td>"));
+        assert!(!html.contains("<th>00000000</th>"));
+        assert!(html.contains("Sync <code>0x1ACF</code>: 2 matches"));
+        assert!(html.contains("<td>32</td><td>0x4</td>"));
+    }
+}
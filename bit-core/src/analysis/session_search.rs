@@ -0,0 +1,135 @@
+// Session search - one pattern searched through the processed bits of every worksheet
+
+use super::pattern_locator::{Pattern, PatternMatch};
+use crate::processing::{BitOperation, ProgressReporter};
+use crate::storage::{read_file_as_bits_in_order, read_file_range_as_bits_in_order, read_files_as_bits_in_order, read_soft_file, Worksheet};
+use bitvec::prelude::*;
+
+/// Matches in one worksheet
+#[derive(Debug, Clone)]
+pub struct WorksheetMatches {
+    /// Worksheets are found by id when jumping, since they may be reordered meanwhile
+    pub worksheet_id: u64,
+    pub worksheet_name: String,
+    pub matches: Vec<PatternMatch>,
+    /// Why the worksheet couldn't be searched
+    pub error: Option<String>,
+}
+
+/// The bits `worksheet` shows once its operations are applied to its file
+pub fn worksheet_bits(worksheet: &Worksheet) -> Result<BitVec<u8, Msb0>, String> {
+    let enabled: Vec<&BitOperation> = worksheet.operations.iter().filter(|op| op.is_enabled()).collect();
+    let read = |path: &std::path::PathBuf| {
+        read_file_as_bits_in_order(path, worksheet.bit_order).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+    };
+
+    // Like applying the chain: source operations build the bits from scratch
+    let mut bits = match &worksheet.file_path {
+        Some(path) if !enabled.iter().any(|op| op.is_source()) => read(path)?,
+        _ => BitVec::new(),
+    };
+    for op in enabled {
+        match op {
+            BitOperation::LoadFile { file_path, offset, length, .. } => {
+                let file_bits = read_file_range_as_bits_in_order(file_path, *offset, *length, worksheet.bit_order)
+                    .map_err(|e| format!("Failed to read {}: {}", file_path.display(), e))?;
+                bits.extend_from_bitslice(&file_bits);
+            }
+            BitOperation::MultiFileLoad { file_paths, .. } => {
+                bits.extend_from_bitslice(&read_files_as_bits_in_order(file_paths, worksheet.bit_order)?);
+            }
+            BitOperation::LoadSoftBits { file_path, format, combine, .. } => {
+                bits.extend_from_bitslice(&read_soft_file(file_path, *format, combine.as_ref())?.bits);
+            }
+            BitOperation::MultiWorksheetLoad { .. } => {
+                return Err("Multi-Worksheet Load isn't followed; search the worksheets it reads".to_string());
+            }
+            _ => op.apply_in_place(&mut bits),
+        }
+    }
+    Ok(bits)
+}
+
+/// `pattern` searched in every worksheet, in worksheet order. The open worksheet may have
+/// unapplied changes, so its bits are passed in as `current_bits` rather than recomputed.
+/// Returns None if `progress` was cancelled.
+pub fn search_session(
+    pattern: &Pattern,
+    worksheets: &[Worksheet],
+    current: usize,
+    current_bits: &BitSlice<u8, Msb0>,
+    progress: &ProgressReporter,
+) -> Option<Vec<WorksheetMatches>> {
+    let mut results = Vec::with_capacity(worksheets.len());
+    for (index, worksheet) in worksheets.iter().enumerate() {
+        progress.report(index as f32 / worksheets.len() as f32, format!("Searching {}", worksheet.name));
+        let mut found = WorksheetMatches {
+            worksheet_id: worksheet.id,
+            worksheet_name: worksheet.name.clone(),
+            matches: Vec::new(),
+            error: None,
+        };
+        let mut pattern = pattern.clone();
+        let searched = if index == current {
+            pattern.search_with_progress(current_bits, progress)
+        } else {
+            match worksheet_bits(worksheet) {
+                Ok(bits) => pattern.search_with_progress(&bits, progress),
+                Err(e) => {
+                    found.error = Some(e);
+                    true
+                }
+            }
+        };
+        if !searched || progress.is_cancelled() {
+            return None;
+        }
+        found.matches = pattern.matches;
+        results.push(found);
+    }
+    Some(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::PatternFormat;
+    use crate::processing::BitOperation;
+
+    #[test]
+    fn test_search_covers_every_worksheet() {
+        let dir = tempfile::tempdir().unwrap();
+        let plain = dir.path().join("plain.bin");
+        let inverted = dir.path().join("inverted.bin");
+        std::fs::write(&plain, [0x00, 0x1A, 0xCF, 0x00]).unwrap();
+        std::fs::write(&inverted, [!0x1Au8, !0xCF, 0xFF, !0x1A, !0xCF]).unwrap();
+
+        let mut first = Worksheet::new("Plain".to_string());
+        first.file_path = Some(plain);
+        let mut second = Worksheet::new("Inverted".to_string());
+        second.file_path = Some(inverted);
+        second.operations.push(BitOperation::InvertBits { name: "Invert".to_string(), enabled: true });
+        let mut third = Worksheet::new("Missing".to_string());
+        third.operations.push(BitOperation::LoadFile {
+            name: "Load".to_string(),
+            file_path: dir.path().join("missing.bin"),
+            offset: 0,
+            length: None,
+            enabled: true,
+        });
+        let worksheets = vec![first, second, third];
+
+        let sync = Pattern::new("Sync".to_string(), PatternFormat::Hex, "0x1ACF".to_string(), 0).unwrap();
+        // The open worksheet's unapplied state wins over its file
+        let current_bits = [0x1Au8, 0xCF].view_bits::<Msb0>().to_bitvec();
+        let results = search_session(&sync, &worksheets, 0, &current_bits, &ProgressReporter::new()).unwrap();
+
+        assert_eq!(results.len(), 3);
+        let positions = |i: usize| results[i].matches.iter().map(|m| m.position).collect::<Vec<_>>();
+        assert_eq!(positions(0), vec![0]);
+        assert_eq!(positions(1), vec![0, 24]);
+        assert_eq!(results[1].worksheet_id, worksheets[1].id);
+        assert!(results[2].error.is_some());
+        assert!(results[2].matches.is_empty());
+    }
+}
@@ -0,0 +1,370 @@
+// Structure templates - simple sequential record definitions decoded into a tree of values
+
+use bitvec::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Colors given in turn to leaf fields that don't set their own
+const FIELD_COLORS: [[u8; 3]; 8] = [
+    [100, 150, 200],
+    [220, 120, 100],
+    [110, 190, 120],
+    [210, 180, 90],
+    [170, 120, 210],
+    [90, 190, 190],
+    [220, 140, 180],
+    [150, 150, 150],
+];
+
+/// Array elements decoded at most, to keep a bad count from hanging the UI
+const MAX_ARRAY_ELEMENTS: usize = 100_000;
+
+/// A named record definition, the root of a template file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructTemplate {
+    pub name: String,
+    pub fields: Vec<StructField>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    U8,
+    U16,
+    U32,
+    U64,
+    I8,
+    I16,
+    I32,
+    I64,
+    /// Unsigned integer of `bits` bits
+    Bits,
+    /// `length` raw bytes
+    Bytes,
+    /// `length` bytes of text
+    Ascii,
+    /// Nested record of `fields`
+    Struct,
+}
+
+impl FieldType {
+    /// Width of the fixed-size integer types
+    fn integer_bits(&self) -> Option<usize> {
+        match self {
+            FieldType::U8 | FieldType::I8 => Some(8),
+            FieldType::U16 | FieldType::I16 => Some(16),
+            FieldType::U32 | FieldType::I32 => Some(32),
+            FieldType::U64 | FieldType::I64 => Some(64),
+            _ => None,
+        }
+    }
+
+    fn is_signed(&self) -> bool {
+        matches!(self, FieldType::I8 | FieldType::I16 | FieldType::I32 | FieldType::I64)
+    }
+}
+
+/// A size given either as a number or as the name of an earlier integer field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FieldSize {
+    Fixed(usize),
+    Field(String),
+}
+
+/// Decode a field only when an earlier integer field, optionally masked, has a given value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldCondition {
+    pub field: String,
+    #[serde(default)]
+    pub mask: Option<u64>,
+    pub equals: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: FieldType,
+    /// Width of a `bits` field
+    #[serde(default)]
+    pub bits: Option<usize>,
+    /// Byte count of a `bytes` or `ascii` field
+    #[serde(default)]
+    pub length: Option<FieldSize>,
+    /// Decode the field this many times as an array
+    #[serde(default)]
+    pub count: Option<FieldSize>,
+    #[serde(default, rename = "if")]
+    pub condition: Option<FieldCondition>,
+    /// Integers are big-endian unless this is set
+    #[serde(default)]
+    pub little_endian: bool,
+    /// Members of a `struct` field
+    #[serde(default)]
+    pub fields: Vec<StructField>,
+    #[serde(default)]
+    pub color: Option<[u8; 3]>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodedValue {
+    Unsigned(u64),
+    Signed(i64),
+    Bytes(Vec<u8>),
+    Text(String),
+    Record(Vec<DecodedField>),
+    Array(Vec<DecodedField>),
+}
+
+/// One decoded field, positioned in the bits the template was applied to
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedField {
+    pub name: String,
+    pub bit_start: usize,
+    pub bit_len: usize,
+    pub value: DecodedValue,
+    pub color: [u8; 3],
+}
+
+impl DecodedField {
+    /// The value as shown next to the field name; records and arrays only give their size
+    pub fn value_text(&self) -> String {
+        match &self.value {
+            DecodedValue::Unsigned(value) => format!("{} (0x{:X})", value, value),
+            DecodedValue::Signed(value) => value.to_string(),
+            DecodedValue::Bytes(bytes) => bytes.iter().map(|byte| format!("{:02X}", byte)).collect::<Vec<_>>().join(" "),
+            DecodedValue::Text(text) => format!("\"{}\"", text),
+            DecodedValue::Record(fields) => format!("{} field(s)", fields.len()),
+            DecodedValue::Array(elements) => format!("[{}]", elements.len()),
+        }
+    }
+
+    /// Records and arrays contain further fields
+    pub fn children(&self) -> &[DecodedField] {
+        match &self.value {
+            DecodedValue::Record(fields) | DecodedValue::Array(fields) => fields,
+            _ => &[],
+        }
+    }
+
+    /// Every field without children, in bit order
+    pub fn leaves(&self) -> Vec<&DecodedField> {
+        if self.children().is_empty() {
+            return vec![self];
+        }
+        self.children().iter().flat_map(|child| child.leaves()).collect()
+    }
+}
+
+pub fn parse_template(json: &str) -> Result<StructTemplate, String> {
+    serde_json::from_str(json).map_err(|e| format!("Invalid structure template: {}", e))
+}
+
+/// Decode `template` from `bits` starting at `offset`. Positions in the result are absolute
+/// bit positions in `bits`.
+pub fn decode_structure(template: &StructTemplate, bits: &BitSlice<u8, Msb0>, offset: usize) -> Result<DecodedField, String> {
+    if offset > bits.len() {
+        return Err(format!("Offset {} is past the end of the data ({} bits)", offset, bits.len()));
+    }
+    let mut decoder = Decoder { bits, position: offset, scopes: vec![Vec::new()], next_color: 0 };
+    let fields = decoder.decode_fields(&template.fields)?;
+    Ok(DecodedField {
+        name: template.name.clone(),
+        bit_start: offset,
+        bit_len: decoder.position - offset,
+        value: DecodedValue::Record(fields),
+        color: FIELD_COLORS[0],
+    })
+}
+
+struct Decoder<'a> {
+    bits: &'a BitSlice<u8, Msb0>,
+    position: usize,
+    /// Integer values decoded so far, one list per enclosing record
+    scopes: Vec<Vec<(String, u64)>>,
+    next_color: usize,
+}
+
+impl Decoder<'_> {
+    fn decode_fields(&mut self, fields: &[StructField]) -> Result<Vec<DecodedField>, String> {
+        let mut decoded = Vec::new();
+        for field in fields {
+            if let Some(condition) = &field.condition {
+                let value = self.lookup(&condition.field, &field.name)?;
+                if value & condition.mask.unwrap_or(u64::MAX) != condition.equals {
+                    continue;
+                }
+            }
+            match &field.count {
+                Some(count) => {
+                    let count = self.resolve(count, &field.name)?;
+                    if count > MAX_ARRAY_ELEMENTS {
+                        return Err(format!("{}: count {} is more than {}", field.name, count, MAX_ARRAY_ELEMENTS));
+                    }
+                    let start = self.position;
+                    let elements = (0..count)
+                        .map(|i| self.decode_one(field, format!("[{}]", i)))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    decoded.push(DecodedField {
+                        name: field.name.clone(),
+                        bit_start: start,
+                        bit_len: self.position - start,
+                        value: DecodedValue::Array(elements),
+                        color: field.color.unwrap_or(FIELD_COLORS[0]),
+                    });
+                }
+                None => decoded.push(self.decode_one(field, field.name.clone())?),
+            }
+        }
+        Ok(decoded)
+    }
+
+    fn decode_one(&mut self, field: &StructField, name: String) -> Result<DecodedField, String> {
+        let start = self.position;
+        if field.kind == FieldType::Struct {
+            self.scopes.push(Vec::new());
+            let members = self.decode_fields(&field.fields);
+            self.scopes.pop();
+            return Ok(DecodedField {
+                name,
+                bit_start: start,
+                bit_len: self.position - start,
+                value: DecodedValue::Record(members?),
+                color: field.color.unwrap_or(FIELD_COLORS[0]),
+            });
+        }
+
+        let width = match (field.kind.integer_bits(), field.kind) {
+            (Some(width), _) => width,
+            (None, FieldType::Bits) => match field.bits {
+                Some(width @ 1..=64) => width,
+                _ => return Err(format!("{}: bits fields need a 'bits' width from 1 to 64", field.name)),
+            },
+            _ => {
+                let length = field.length.as_ref().ok_or_else(|| format!("{}: missing 'length'", field.name))?;
+                self.resolve(length, &field.name)? * 8
+            }
+        };
+        let end = start + width;
+        if end > self.bits.len() {
+            return Err(format!("{}: needs bits {}..{} but the data ends at {}", field.name, start, end, self.bits.len()));
+        }
+        let slice = &self.bits[start..end];
+        self.position = end;
+
+        let value = match field.kind {
+            FieldType::Bytes | FieldType::Ascii => {
+                let bytes: Vec<u8> = slice.chunks(8).map(|chunk| chunk.load_be::<u8>()).collect();
+                if field.kind == FieldType::Ascii {
+                    DecodedValue::Text(
+                        bytes
+                            .iter()
+                            .map(|&byte| if byte.is_ascii_graphic() || byte == b' ' { byte as char } else { '.' })
+                            .collect(),
+                    )
+                } else {
+                    DecodedValue::Bytes(bytes)
+                }
+            }
+            _ => {
+                let raw = if field.little_endian && width.is_multiple_of(8) {
+                    slice.chunks(8).rev().fold(0u64, |value, chunk| (value << 8) | chunk.load_be::<u64>())
+                } else {
+                    slice.load_be::<u64>()
+                };
+                if let Some(scope) = self.scopes.last_mut() {
+                    scope.push((field.name.clone(), raw));
+                }
+                if field.kind.is_signed() {
+                    let shift = 64 - width;
+                    DecodedValue::Signed(((raw << shift) as i64) >> shift)
+                } else {
+                    DecodedValue::Unsigned(raw)
+                }
+            }
+        };
+
+        let color = field.color.unwrap_or_else(|| {
+            let color = FIELD_COLORS[self.next_color % FIELD_COLORS.len()];
+            self.next_color += 1;
+            color
+        });
+        Ok(DecodedField { name, bit_start: start, bit_len: width, value, color })
+    }
+
+    fn resolve(&self, size: &FieldSize, field: &str) -> Result<usize, String> {
+        match size {
+            FieldSize::Fixed(value) => Ok(*value),
+            FieldSize::Field(name) => self.lookup(name, field).map(|value| value as usize),
+        }
+    }
+
+    /// The latest integer named `name`, looking in the current record then outwards
+    fn lookup(&self, name: &str, field: &str) -> Result<u64, String> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.iter().rev().find(|(n, _)| n == name).map(|(_, value)| *value))
+            .ok_or_else(|| format!("{}: no earlier integer field named '{}'", field, name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = r#"{
+        "name": "packet",
+        "fields": [
+            { "name": "version", "type": "bits", "bits": 4 },
+            { "name": "flags", "type": "bits", "bits": 4 },
+            { "name": "length", "type": "u16", "little_endian": true },
+            { "name": "items", "type": "struct", "count": 2, "fields": [
+                { "name": "id", "type": "u8" },
+                { "name": "delta", "type": "i8" }
+            ]},
+            { "name": "name", "type": "ascii", "length": "length" },
+            { "name": "extra", "type": "u8", "if": { "field": "flags", "mask": 1, "equals": 1 } },
+            { "name": "trailer", "type": "u8" }
+        ]
+    }"#;
+
+    #[test]
+    fn test_decodes_nested_records_arrays_and_conditions() {
+        let template = parse_template(TEMPLATE).unwrap();
+        let bytes = [0xAB, 0x03, 0x00, 0x01, 0xFF, 0x02, 0x05, b'a', b'b', b'c', 0x77, 0x99];
+        let mut bits: BitVec<u8, Msb0> = BitVec::repeat(false, 4);
+        bits.extend_from_bitslice(bytes.view_bits::<Msb0>());
+
+        let root = decode_structure(&template, &bits, 4).unwrap();
+        let fields = root.children();
+        assert_eq!(fields[0].value, DecodedValue::Unsigned(0xA));
+        assert_eq!(fields[1].value, DecodedValue::Unsigned(0xB));
+        assert_eq!(fields[2].value, DecodedValue::Unsigned(3));
+        let items = fields[3].children();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].children()[1].value, DecodedValue::Signed(-1));
+        assert_eq!(items[1].children()[0].bit_start, 4 + 40);
+        assert_eq!(fields[4].value, DecodedValue::Text("abc".to_string()));
+        assert_eq!(fields[5].name, "extra");
+        assert_eq!(fields[6].value, DecodedValue::Unsigned(0x99));
+        assert_eq!(root.bit_len, bytes.len() * 8);
+        assert_eq!(root.leaves().len(), 10);
+    }
+
+    #[test]
+    fn test_reports_missing_fields_and_short_data() {
+        let template = parse_template(TEMPLATE).unwrap();
+        let mut bytes = [0u8; 12];
+        bytes[0] = 0xA0;
+        bytes[1] = 0x03;
+        // Flags bit clear, so the record ends one byte earlier
+        let root = decode_structure(&template, bytes.view_bits::<Msb0>(), 0).unwrap();
+        assert!(root.children().iter().all(|field| field.name != "extra"));
+        assert!(decode_structure(&template, bytes[..8].view_bits::<Msb0>(), 0).is_err());
+
+        let bad = parse_template(r#"{"name": "x", "fields": [{"name": "data", "type": "bytes", "length": "size"}]}"#).unwrap();
+        let error = decode_structure(&bad, bytes.view_bits::<Msb0>(), 0).unwrap_err();
+        assert!(error.contains("size"));
+    }
+}
@@ -0,0 +1,105 @@
+// Sync suggestion - the longest run of near-constant bit columns in frames, as a sync word
+// candidate for the Pattern Locator
+
+use bitvec::prelude::*;
+
+/// Fewest bits a suggested sync word may have
+pub const MIN_SYNC_BITS: usize = 8;
+/// Share of frames that must agree on a column's bit for it to count as constant
+const MIN_CONSTANT_SHARE: f64 = 0.95;
+
+/// Bits that hold (almost) the same value at the same place in every frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncSuggestion {
+    /// First bit of the run within the frame; the run may wrap past the end of the frame
+    pub offset: usize,
+    /// The most common value of each column of the run
+    pub bits: BitVec<u8, Msb0>,
+    /// Percentage of frames agreeing on the least constant column of the run
+    pub constant_percent: f64,
+}
+
+impl SyncSuggestion {
+    pub fn bits_string(&self) -> String {
+        self.bits.iter().map(|b| if *b { '1' } else { '0' }).collect()
+    }
+}
+
+/// The longest run of at least `MIN_SYNC_BITS` near-constant columns of the `frame_length`-bit
+/// frames in `bits`. Frames that don't start at the sync word wrap it around the frame end, so
+/// runs are followed across it. A run of the same bit throughout is more often padding than
+/// sync, so runs holding both 0s and 1s win over longer uniform ones.
+pub fn suggest_sync_pattern(bits: &BitSlice<u8, Msb0>, frame_length: usize) -> Option<SyncSuggestion> {
+    if frame_length == 0 {
+        return None;
+    }
+    let frames: Vec<&BitSlice<u8, Msb0>> = bits.chunks_exact(frame_length).collect();
+    if frames.len() < 3 {
+        return None;
+    }
+
+    // Each column's common bit and the share of frames holding it, if that's high enough
+    let columns: Vec<Option<(bool, f64)>> = (0..frame_length)
+        .map(|column| {
+            let ones = frames.iter().filter(|frame| frame[column]).count();
+            let common = ones * 2 >= frames.len();
+            let share = ones.max(frames.len() - ones) as f64 / frames.len() as f64;
+            (share >= MIN_CONSTANT_SHARE).then_some((common, share))
+        })
+        .collect();
+    let column = |index: usize| columns[index % frame_length];
+
+    // Runs are measured from their first column only; every column constant is one run from 0
+    let all_constant = columns.iter().all(Option::is_some);
+    let mut best: Option<(bool, usize, usize)> = None;
+    for start in 0..frame_length {
+        let begins_run = column(start + frame_length - 1).is_none() || (all_constant && start == 0);
+        if column(start).is_none() || !begins_run {
+            continue;
+        }
+        let len = (0..frame_length).take_while(|&step| column(start + step).is_some()).count();
+        let mixed = (0..len).any(|step| column(start + step).map(|(bit, _)| bit) != column(start).map(|(bit, _)| bit));
+        if len >= MIN_SYNC_BITS && best.is_none_or(|(best_mixed, _, best_len)| (mixed, len) > (best_mixed, best_len)) {
+            best = Some((mixed, start, len));
+        }
+    }
+
+    let (_, offset, len) = best?;
+    let run: Vec<(bool, f64)> = (0..len).filter_map(|step| column(offset + step)).collect();
+    Some(SyncSuggestion {
+        offset,
+        bits: run.iter().map(|&(bit, _)| bit).collect(),
+        constant_percent: run.iter().map(|&(_, share)| share).fold(1.0, f64::min) * 100.0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sync_run_is_suggested() {
+        // 6-byte frames: 0x00 filler, a 0x1ACF sync word, a counter, then noise
+        let mut bytes = Vec::new();
+        let mut noise = 0x3Cu8;
+        for frame in 0..40u8 {
+            noise = noise.wrapping_mul(97).wrapping_add(31) ^ (noise >> 3);
+            bytes.extend([0x00, 0x1A, 0xCF, frame, noise, noise.rotate_left(3) ^ frame]);
+        }
+        let bits = bytes.view_bits::<Msb0>();
+        let suggestion = suggest_sync_pattern(bits, 48).unwrap();
+        // The filler and the counter's top two bits, which stay 0 below 64, join the sync word
+        assert_eq!(suggestion.offset, 0);
+        assert_eq!(suggestion.bits_string(), "00000000000110101100111100");
+        assert_eq!(suggestion.constant_percent, 100.0);
+
+        // Starting mid-frame wraps the sync word around the end of the frame
+        let shifted = &bits[24..bits.len() - 24];
+        let wrapped = suggest_sync_pattern(shifted, 48).unwrap();
+        assert_eq!(wrapped.offset, 24);
+        assert_eq!(wrapped.bits, suggestion.bits);
+
+        assert!(suggest_sync_pattern(bits, 0).is_none());
+        assert!(suggest_sync_pattern(&bits[..96], 48).is_none());
+    }
+}
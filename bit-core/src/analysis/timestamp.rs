@@ -0,0 +1,217 @@
+// Timestamps - decoding common time formats and finding fields in frames that hold one
+
+use bitvec::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Seconds from the GPS epoch (1980-01-06) back to the Unix epoch
+const GPS_EPOCH_UNIX: i64 = 315_964_800;
+/// GPS time is ahead of UTC by the leap seconds since 1980, 18 since 2017
+const GPS_LEAP_SECONDS: i64 = 18;
+/// Seconds from the CCSDS epoch (1958-01-01) to the Unix epoch
+const CCSDS_EPOCH_UNIX: i64 = -378_691_200;
+/// TAI is ahead of UTC by 37 seconds since 2017
+const TAI_UTC_SECONDS: i64 = 37;
+const MILLIS_PER_WEEK: u64 = 604_800_000;
+
+/// Times before 2000 or after 2040 are taken to be something else
+const PLAUSIBLE_MILLIS: std::ops::Range<i64> = 946_684_800_000..2_208_988_800_000;
+/// Largest typical step between frames for a field to count as a timestamp
+const MAX_FRAME_STEP_MILLIS: i64 = 86_400_000;
+/// Share of frame pairs whose time must not go backwards
+const MIN_INCREASING_SHARE: f64 = 0.9;
+
+/// A time format stored big-endian in a frame field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimestampFormat {
+    /// 32-bit seconds since 1970
+    UnixSeconds,
+    /// 64-bit milliseconds since 1970
+    UnixMillis,
+    /// 16-bit GPS week number then 32-bit milliseconds into the week
+    GpsWeekTow,
+    /// CCSDS unsegmented code: 32-bit seconds since 1958 (TAI) then 16-bit fraction
+    CcsdsCuc,
+}
+
+impl TimestampFormat {
+    pub const ALL: [TimestampFormat; 4] = [
+        TimestampFormat::UnixSeconds,
+        TimestampFormat::UnixMillis,
+        TimestampFormat::GpsWeekTow,
+        TimestampFormat::CcsdsCuc,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TimestampFormat::UnixSeconds => "Unix seconds",
+            TimestampFormat::UnixMillis => "Unix milliseconds",
+            TimestampFormat::GpsWeekTow => "GPS week + TOW (ms)",
+            TimestampFormat::CcsdsCuc => "CCSDS CUC (4+2)",
+        }
+    }
+
+    /// Bits the field takes
+    pub fn bits(&self) -> usize {
+        match self {
+            TimestampFormat::UnixSeconds => 32,
+            TimestampFormat::UnixMillis => 64,
+            TimestampFormat::GpsWeekTow | TimestampFormat::CcsdsCuc => 48,
+        }
+    }
+
+    /// `value` as UTC milliseconds since 1970, or None if it isn't valid in this format
+    pub fn unix_millis(&self, value: u64) -> Option<i64> {
+        match self {
+            TimestampFormat::UnixSeconds => Some(value as i64 * 1000),
+            TimestampFormat::UnixMillis => i64::try_from(value).ok(),
+            TimestampFormat::GpsWeekTow => {
+                let (week, tow) = ((value >> 32) & 0xFFFF, value & 0xFFFF_FFFF);
+                (tow < MILLIS_PER_WEEK)
+                    .then(|| (GPS_EPOCH_UNIX - GPS_LEAP_SECONDS) * 1000 + (week * MILLIS_PER_WEEK + tow) as i64)
+            }
+            TimestampFormat::CcsdsCuc => {
+                let (coarse, fine) = ((value >> 16) & 0xFFFF_FFFF, value & 0xFFFF);
+                Some((CCSDS_EPOCH_UNIX - TAI_UTC_SECONDS + coarse as i64) * 1000 + ((fine as i64 * 1000) >> 16))
+            }
+        }
+    }
+
+    /// `value` as a UTC date and time, if it's valid in this format
+    pub fn decode(&self, value: u64) -> Option<String> {
+        self.unix_millis(value).map(format_utc_millis)
+    }
+}
+
+/// UTC milliseconds since 1970 as `YYYY-MM-DD HH:MM:SS.mmm UTC`
+pub fn format_utc_millis(millis: i64) -> String {
+    let (days, day_millis) = (millis.div_euclid(86_400_000), millis.rem_euclid(86_400_000));
+    let (year, month, day) = civil_from_days(days);
+    let seconds = day_millis / 1000;
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:03} UTC",
+        year, month, day, seconds / 3600, seconds / 60 % 60, seconds % 60, day_millis % 1000
+    )
+}
+
+/// Proleptic Gregorian date of `days` since 1970-01-01
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// A frame field that reads as a plausible, mostly increasing time
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimestampCandidate {
+    /// First bit of the field within the frame
+    pub offset: usize,
+    pub format: TimestampFormat,
+    /// Times in the first and last frames, in UTC milliseconds since 1970
+    pub first_millis: i64,
+    pub last_millis: i64,
+    /// Median time between consecutive frames
+    pub step_millis: i64,
+    /// Percentage of frame pairs whose time doesn't go backwards
+    pub increasing_percent: f64,
+}
+
+/// Byte-aligned fields of the `frame_length`-bit frames in `bits` holding a timestamp: every
+/// frame's value is between 2000 and 2040, it rarely goes backwards and the median step is
+/// under a day. Best candidates first; a field overlapping a better one is left out, which
+/// drops the same time read a byte later with coarser steps.
+pub fn detect_timestamps(bits: &BitSlice<u8, Msb0>, frame_length: usize) -> Vec<TimestampCandidate> {
+    if frame_length == 0 {
+        return Vec::new();
+    }
+    let frames: Vec<&BitSlice<u8, Msb0>> = bits.chunks_exact(frame_length).collect();
+    if frames.len() < 3 {
+        return Vec::new();
+    }
+
+    let mut candidates = Vec::new();
+    for format in TimestampFormat::ALL {
+        let width = format.bits();
+        for offset in (0..frame_length.saturating_sub(width - 1)).step_by(8) {
+            let times: Option<Vec<i64>> = frames
+                .iter()
+                .map(|frame| format.unix_millis(frame[offset..offset + width].load_be::<u64>()))
+                .map(|time| time.filter(|time| PLAUSIBLE_MILLIS.contains(time)))
+                .collect();
+            let Some(times) = times else {
+                continue;
+            };
+            let mut steps: Vec<i64> = times.windows(2).map(|pair| pair[1] - pair[0]).collect();
+            let increasing = steps.iter().filter(|&&step| step >= 0).count() as f64 / steps.len() as f64;
+            steps.sort_unstable();
+            let median = steps[steps.len() / 2];
+            if increasing < MIN_INCREASING_SHARE || times.last() <= times.first() || median > MAX_FRAME_STEP_MILLIS {
+                continue;
+            }
+            candidates.push(TimestampCandidate {
+                offset,
+                format,
+                first_millis: times[0],
+                last_millis: times[times.len() - 1],
+                step_millis: median,
+                increasing_percent: increasing * 100.0,
+            });
+        }
+    }
+    candidates.sort_by(|a, b| {
+        b.increasing_percent.total_cmp(&a.increasing_percent)
+            .then(a.step_millis.cmp(&b.step_millis))
+            .then(a.offset.cmp(&b.offset))
+    });
+
+    let mut kept: Vec<TimestampCandidate> = Vec::new();
+    for candidate in candidates {
+        let end = candidate.offset + candidate.format.bits();
+        if !kept.iter().any(|other| candidate.offset < other.offset + other.format.bits() && other.offset < end) {
+            kept.push(candidate);
+        }
+    }
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formats_decode_to_utc() {
+        assert_eq!(TimestampFormat::UnixSeconds.decode(0), Some("1970-01-01 00:00:00.000 UTC".to_string()));
+        assert_eq!(TimestampFormat::UnixMillis.decode(1_709_210_096_789), Some("2024-02-29 12:34:56.789 UTC".to_string()));
+        // Week 2304, 1.5 s into it: 2024-03-03 00:00:01.5 GPS time
+        let gps = (2304u64 << 32) | 1500;
+        assert_eq!(TimestampFormat::GpsWeekTow.decode(gps), Some("2024-03-02 23:59:43.500 UTC".to_string()));
+        assert_eq!(TimestampFormat::GpsWeekTow.decode(MILLIS_PER_WEEK), None);
+        // 2000-01-01 00:00:00 UTC is 1325376037 s after 1958 in TAI, plus half a second
+        let cuc = (1_325_376_037u64 << 16) | 0x8000;
+        assert_eq!(TimestampFormat::CcsdsCuc.decode(cuc), Some("2000-01-01 00:00:00.500 UTC".to_string()));
+    }
+
+    #[test]
+    fn test_increasing_fields_are_found() {
+        // 10-byte frames: a sync byte, a counter, Unix seconds one per frame, filler that stays put
+        let mut bytes = Vec::new();
+        for frame in 0..10u32 {
+            bytes.extend([0xAA, frame as u8]);
+            bytes.extend((1_700_000_000 + frame).to_be_bytes());
+            bytes.extend([0x12, 0x34, 0x56, 0x78]);
+        }
+        let candidates = detect_timestamps(bytes.view_bits::<Msb0>(), 80);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].offset, 16);
+        assert_eq!(candidates[0].format, TimestampFormat::UnixSeconds);
+        assert_eq!(candidates[0].last_millis - candidates[0].first_millis, 9000);
+        assert_eq!(candidates[0].step_millis, 1000);
+        assert!(detect_timestamps(bytes.view_bits::<Msb0>(), 0).is_empty());
+    }
+}
@@ -0,0 +1,145 @@
+// XOR key recovery - frequency analysis for data XORed with a short repeating key
+
+use bitvec::prelude::*;
+
+/// Longest key length tried
+pub const MAX_XOR_KEY_LENGTH: usize = 64;
+
+/// Bytes analyzed at most; a few hundred per key position is already plenty
+const MAX_SCANNED_BYTES: usize = 1024 * 1024;
+
+/// Decrypted bytes kept for the preview
+const PREVIEW_BYTES: usize = 64;
+
+/// A key that turns the data into plausible ASCII text
+#[derive(Debug, Clone)]
+pub struct XorKeyCandidate {
+    pub key: Vec<u8>,
+    /// Average plaintext score per byte, higher is more text-like
+    pub score: f64,
+    /// Start of the decrypted data, non-printable bytes shown as '.'
+    pub preview: String,
+}
+
+impl XorKeyCandidate {
+    pub fn key_hex(&self) -> String {
+        let hex: String = self.key.iter().map(|byte| format!("{:02X}", byte)).collect();
+        format!("0x{}", hex)
+    }
+
+    /// The key as text when every byte is printable
+    pub fn key_ascii(&self) -> Option<String> {
+        self.key
+            .iter()
+            .all(|byte| byte.is_ascii_graphic() || *byte == b' ')
+            .then(|| self.key.iter().map(|&byte| byte as char).collect())
+    }
+}
+
+/// Score of one plaintext byte under English-like ASCII text
+fn byte_weight(byte: u8) -> f64 {
+    // Letter frequencies in English text, in percent
+    const LETTERS: [f64; 26] = [
+        8.2, 1.5, 2.8, 4.3, 12.7, 2.2, 2.0, 6.1, 7.0, 0.15, 0.8, 4.0, 2.4,
+        6.7, 7.5, 1.9, 0.1, 6.0, 6.3, 9.1, 2.8, 1.0, 2.4, 0.15, 2.0, 0.07,
+    ];
+    match byte {
+        b' ' => 15.0,
+        b'a'..=b'z' => LETTERS[(byte - b'a') as usize],
+        // Capitals are rarer than the same letter in lower case
+        b'A'..=b'Z' => LETTERS[(byte - b'A') as usize] * 0.3,
+        b'0'..=b'9' | b'\n' | b'\r' | b'\t' => 0.5,
+        _ if byte.is_ascii_punctuation() => 0.3,
+        _ => -5.0,
+    }
+}
+
+/// Suggest repeating XOR keys of `min_len..=max_len` bytes for `bits`, read as bytes from the
+/// first bit and assumed to encrypt ASCII text. Each key byte is picked by frequency analysis
+/// of the bytes it covers. Returns up to `limit` candidates, best first; a key that is just a
+/// shorter candidate repeated is left out.
+pub fn recover_xor_keys(bits: &BitSlice<u8, Msb0>, min_len: usize, max_len: usize, limit: usize) -> Vec<XorKeyCandidate> {
+    let bytes: Vec<u8> = bits
+        .chunks_exact(8)
+        .take(MAX_SCANNED_BYTES)
+        .map(|chunk| chunk.load_be::<u8>())
+        .collect();
+    let min_len = min_len.max(1);
+    let max_len = max_len.min(MAX_XOR_KEY_LENGTH).min(bytes.len() / 2);
+    if min_len > max_len {
+        return Vec::new();
+    }
+
+    let weights: Vec<f64> = (0..=255u8).map(byte_weight).collect();
+    let candidates: Vec<XorKeyCandidate> = (min_len..=max_len)
+        .map(|len| {
+            let mut total_score = 0.0;
+            let key: Vec<u8> = (0..len)
+                .map(|position| {
+                    let mut counts = [0usize; 256];
+                    for &byte in bytes.iter().skip(position).step_by(len) {
+                        counts[byte as usize] += 1;
+                    }
+                    let (key_byte, score) = (0..=255u8)
+                        .map(|k| {
+                            let score: f64 = counts
+                                .iter()
+                                .enumerate()
+                                .filter(|(_, count)| **count > 0)
+                                .map(|(value, count)| *count as f64 * weights[value ^ k as usize])
+                                .sum();
+                            (k, score)
+                        })
+                        .max_by(|a, b| a.1.total_cmp(&b.1))
+                        .unwrap_or((0, 0.0));
+                    total_score += score;
+                    key_byte
+                })
+                .collect();
+            let preview = bytes
+                .iter()
+                .take(PREVIEW_BYTES)
+                .enumerate()
+                .map(|(i, byte)| {
+                    let plain = byte ^ key[i % len];
+                    if plain.is_ascii_graphic() || plain == b' ' { plain as char } else { '.' }
+                })
+                .collect();
+            XorKeyCandidate { key, score: total_score / bytes.len() as f64, preview }
+        })
+        .collect();
+
+    let repeats_shorter = |candidate: &XorKeyCandidate| {
+        candidates.iter().any(|shorter| {
+            shorter.key.len() < candidate.key.len()
+                && candidate.key.len().is_multiple_of(shorter.key.len())
+                && candidate.key.chunks(shorter.key.len()).all(|part| part == shorter.key.as_slice())
+        })
+    };
+    let mut kept: Vec<XorKeyCandidate> = candidates.iter().filter(|c| !repeats_shorter(c)).cloned().collect();
+    kept.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.key.len().cmp(&b.key.len())));
+    kept.truncate(limit);
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recovers_repeating_key_of_ascii_text() {
+        let text = "The quick brown fox jumps over the lazy dog while the operator reads the \
+                    capture one frame at a time, looking for any sign of a sync word or text. \
+                    Most protocols carry some plain strings such as names, versions and status \
+                    messages that stand out once the right key is found.";
+        let key = b"KEY";
+        let encrypted: Vec<u8> = text.bytes().enumerate().map(|(i, byte)| byte ^ key[i % key.len()]).collect();
+
+        let candidates = recover_xor_keys(encrypted.view_bits::<Msb0>(), 1, 8, 5);
+        assert_eq!(candidates[0].key, key.to_vec());
+        assert_eq!(candidates[0].key_ascii().as_deref(), Some("KEY"));
+        assert!(candidates[0].preview.starts_with("The quick brown fox"));
+        // Six-byte KEYKEY scores the same but repeats the three-byte key
+        assert!(candidates.iter().all(|candidate| candidate.key.len() != 6));
+    }
+}
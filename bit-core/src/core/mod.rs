@@ -0,0 +1,5 @@
+pub mod notifications;
+pub mod types;
+
+pub use notifications::{Notification, NotificationLevel, Notifications};
+pub use types::{ViewMode, OperationType};
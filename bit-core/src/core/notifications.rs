@@ -0,0 +1,129 @@
+// Notifications - errors and warnings collected for the notification center
+
+use std::time::{Duration, Instant};
+
+/// Oldest notifications are dropped beyond this many
+const MAX_NOTIFICATIONS: usize = 100;
+
+/// How serious a notification is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationLevel {
+    Error,
+    Warning,
+}
+
+impl NotificationLevel {
+    pub fn icon(&self) -> &'static str {
+        match self {
+            NotificationLevel::Error => "❌",
+            NotificationLevel::Warning => "⚠",
+        }
+    }
+}
+
+/// One error or warning
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub id: u64,
+    pub level: NotificationLevel,
+    pub message: String,
+    /// When it was last raised
+    pub time: Instant,
+    /// Times it was raised in a row
+    pub count: usize,
+}
+
+/// Errors and warnings, oldest first, until the user dismisses them
+#[derive(Debug, Default)]
+pub struct Notifications {
+    items: Vec<Notification>,
+    next_id: u64,
+}
+
+impl Notifications {
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.push(NotificationLevel::Error, message.into());
+    }
+
+    pub fn warning(&mut self, message: impl Into<String>) {
+        self.push(NotificationLevel::Warning, message.into());
+    }
+
+    /// Adds a notification; the same message raised again right away bumps the last one's
+    /// count and time instead of adding a copy
+    pub fn push(&mut self, level: NotificationLevel, message: String) {
+        if let Some(last) = self.items.last_mut()
+            && last.level == level
+            && last.message == message
+        {
+            last.count += 1;
+            last.time = Instant::now();
+            return;
+        }
+        self.items.push(Notification { id: self.next_id, level, message, time: Instant::now(), count: 1 });
+        self.next_id += 1;
+        if self.items.len() > MAX_NOTIFICATIONS {
+            self.items.remove(0);
+        }
+    }
+
+    pub fn items(&self) -> &[Notification] {
+        &self.items
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.items.iter().filter(|item| item.level == NotificationLevel::Error).count()
+    }
+
+    /// Notifications raised within the last `within`, newest first
+    pub fn recent(&self, within: Duration) -> impl Iterator<Item = &Notification> {
+        self.items.iter().rev().filter(move |item| item.time.elapsed() < within)
+    }
+
+    pub fn dismiss(&mut self, id: u64) {
+        self.items.retain(|item| item.id != id);
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notifications_collect_and_dismiss() {
+        let mut notifications = Notifications::default();
+        notifications.error("Failed to open file");
+        notifications.warning("Pattern not found");
+        notifications.warning("Pattern not found");
+        assert_eq!(notifications.len(), 2);
+        assert_eq!(notifications.items()[1].count, 2);
+        assert_eq!(notifications.error_count(), 1);
+
+        let newest: Vec<&str> = notifications.recent(Duration::from_secs(60)).map(|item| item.message.as_str()).collect();
+        assert_eq!(newest, vec!["Pattern not found", "Failed to open file"]);
+
+        let first = notifications.items()[0].id;
+        notifications.dismiss(first);
+        assert_eq!(notifications.items()[0].message, "Pattern not found");
+        notifications.clear();
+        assert!(notifications.is_empty());
+
+        for index in 0..MAX_NOTIFICATIONS + 5 {
+            notifications.error(format!("Error {}", index));
+        }
+        assert_eq!(notifications.len(), MAX_NOTIFICATIONS);
+        assert_eq!(notifications.items()[0].message, "Error 5");
+    }
+}
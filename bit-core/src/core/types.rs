@@ -0,0 +1,162 @@
+// Core types used throughout the application
+
+use serde::{Deserialize, Serialize};
+
+/// View mode for switching between different bit visualizations
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ViewMode {
+    Bit,
+    Byte,
+    Ascii,
+    /// Fixed-width N-bit symbols
+    Symbol,
+}
+
+impl Default for ViewMode {
+    fn default() -> Self {
+        ViewMode::Bit
+    }
+}
+
+/// Available operation types that can be added
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OperationType {
+    LoadFile,
+    MultiFileLoad,
+    LoadSoftBits,
+    TakeSkipSequence,
+    InvertBits,
+    MultiWorksheetLoad,
+    TruncateBits,
+    InterleaveBits,
+    InsertBits,
+    DeleteBits,
+    RangeBits,
+    XorKey,
+    LineDecode,
+    SymbolConvert,
+    SymbolRemap,
+    MajorityVote,
+    NandLayout,
+    Conditional,
+    Variants,
+    Macro,
+    Group,
+    PatchBits,
+    Script,
+}
+
+impl OperationType {
+    pub fn name(&self) -> &str {
+        match self {
+            OperationType::LoadFile => "Load File",
+            OperationType::MultiFileLoad => "Multi-File Load",
+            OperationType::LoadSoftBits => "Load Soft Bits",
+            OperationType::TakeSkipSequence => "Take/Skip Sequence",
+            OperationType::InvertBits => "Invert Bits",
+            OperationType::MultiWorksheetLoad => "Multi-Worksheet Load",
+            OperationType::TruncateBits => "Truncate Bits",
+            OperationType::InterleaveBits => "Interleave Bits",
+            OperationType::InsertBits => "Insert Bits",
+            OperationType::DeleteBits => "Delete Bits",
+            OperationType::RangeBits => "Invert/Reverse Range",
+            OperationType::XorKey => "XOR With Key",
+            OperationType::LineDecode => "Line Code Decode",
+            OperationType::SymbolConvert => "Gray/BCD Convert",
+            OperationType::SymbolRemap => "Symbol Remap",
+            OperationType::MajorityVote => "Majority Vote",
+            OperationType::NandLayout => "NAND Page Layout",
+            OperationType::Conditional => "Conditional",
+            OperationType::Variants => "A/B Variants",
+            OperationType::Macro => "Macro",
+            OperationType::Group => "Group",
+            OperationType::PatchBits => "Bit Edits",
+            OperationType::Script => "Script",
+        }
+    }
+
+    pub fn icon(&self) -> &str {
+        match self {
+            OperationType::LoadFile => "📂",
+            OperationType::MultiFileLoad => "🗂",
+            OperationType::LoadSoftBits => "🌫",
+            OperationType::TakeSkipSequence => "📝",
+            OperationType::InvertBits => "🔄",
+            OperationType::MultiWorksheetLoad => "📚",
+            OperationType::TruncateBits => "✂️",
+            OperationType::InterleaveBits => "🔀",
+            OperationType::InsertBits => "➕",
+            OperationType::DeleteBits => "➖",
+            OperationType::RangeBits => "↔",
+            OperationType::XorKey => "⊕",
+            OperationType::LineDecode => "📶",
+            OperationType::SymbolConvert => "🔢",
+            OperationType::SymbolRemap => "🔁",
+            OperationType::MajorityVote => "🗳",
+            OperationType::NandLayout => "💾",
+            OperationType::Conditional => "🎯",
+            OperationType::Variants => "🆎",
+            OperationType::Macro => "📦",
+            OperationType::Group => "📁",
+            OperationType::PatchBits => "✏",
+            OperationType::Script => "📜",
+        }
+    }
+
+    pub fn description(&self) -> &str {
+        match self {
+            OperationType::LoadFile => "Load bits from a file",
+            OperationType::MultiFileLoad => "Append several files in order, e.g. capture_*.bin chunks",
+            OperationType::LoadSoftBits => "Load soft decisions (one byte or float per bit), keeping each bit's confidence",
+            OperationType::TakeSkipSequence => "Pattern-based bit extraction (t4r3i8s1)",
+            OperationType::InvertBits => "Invert all bits (0→1, 1→0)",
+            OperationType::MultiWorksheetLoad => "Load bits from multiple worksheets with operations",
+            OperationType::TruncateBits => "Keep bits in a range and discard the rest",
+            OperationType::InterleaveBits => "Interleave/de-interleave bits for error resilience",
+            OperationType::InsertBits => "Insert fill bits at a position, e.g. to correct a slip",
+            OperationType::DeleteBits => "Delete a bit range or remove stuffed bits (HDLC)",
+            OperationType::RangeBits => "Invert or reverse a bit range in place, leaving the rest untouched",
+            OperationType::XorKey => "XOR the data with a repeating byte key",
+            OperationType::LineDecode => "Decode 8b/10b or 64b/66b line code, marking code violations",
+            OperationType::SymbolConvert => "Convert N-bit Gray or BCD symbols to binary, or binary to Gray",
+            OperationType::SymbolRemap => "Map N-bit symbols through a lookup table, e.g. constellation points to bits",
+            OperationType::MajorityVote => "Decode repeated bits or frames by majority vote",
+            OperationType::NandLayout => "Strip or isolate the spare (OOB) area of NAND pages, checking their Hamming or BCH ECC",
+            OperationType::Conditional => "Apply an operation only to every Nth frame or to listed ranges",
+            OperationType::Variants => "Two versions of an operation, switched with one toggle to compare their output",
+            OperationType::Macro => "A saved chain of operations applied as one step",
+            OperationType::Group => "Named, collapsible set of operations enabled and moved together",
+            OperationType::PatchBits => "Bits overwritten by hand in edit mode",
+            OperationType::Script => "Custom transform written in a small sandboxed script language",
+        }
+    }
+    
+    #[allow(dead_code)]
+    pub fn all() -> &'static [OperationType] {
+        &[
+            OperationType::LoadFile,
+            OperationType::MultiFileLoad,
+            OperationType::LoadSoftBits,
+            OperationType::TakeSkipSequence,
+            OperationType::InvertBits,
+            OperationType::MultiWorksheetLoad,
+            OperationType::TruncateBits,
+            OperationType::InterleaveBits,
+            OperationType::InsertBits,
+            OperationType::DeleteBits,
+            OperationType::RangeBits,
+            OperationType::XorKey,
+            OperationType::LineDecode,
+            OperationType::SymbolConvert,
+            OperationType::SymbolRemap,
+            OperationType::MajorityVote,
+            OperationType::NandLayout,
+            OperationType::Conditional,
+            OperationType::Variants,
+            OperationType::Macro,
+            OperationType::Group,
+            OperationType::PatchBits,
+            OperationType::Script,
+        ]
+    }
+}
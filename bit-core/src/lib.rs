@@ -0,0 +1,14 @@
+// B.I.T. core - processing, analysis and storage without the GUI, for the app and for
+// headless use such as servers and CI
+
+pub mod analysis;
+pub mod core;
+pub mod processing;
+pub mod storage;
+pub mod utils;
+pub mod view;
+
+pub use analysis::{Pattern, PatternFormat};
+pub use core::{OperationType, ViewMode};
+pub use processing::{BitOperation, Operation, OperationSequence, WorksheetOperation};
+pub use storage::{read_file_as_bits, write_bits_to_file, AppSession, AppSettings, Worksheet};
@@ -0,0 +1,243 @@
+// Operation cache - keeps intermediate results of the operation chain between runs
+
+use super::operations::BitOperation;
+use bitvec::prelude::*;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// Output of the chain after some prefix of its operations
+struct CachedStep {
+    /// Hash of the input bits and every enabled operation up to and including this one
+    key: u64,
+    bits: Arc<BitVec<u8, Msb0>>,
+}
+
+/// Intermediate results keyed by input hash and operation parameters, so changing a late
+/// operation restarts the chain from the last unchanged step instead of from the input.
+///
+/// Only chains that start from the loaded bits are cached; source operations read files
+/// whose contents the key can't capture.
+pub struct OperationCache {
+    /// Oldest first; evicted from the front when over budget
+    steps: Vec<CachedStep>,
+    budget_bytes: usize,
+}
+
+impl OperationCache {
+    pub fn new(budget_bytes: usize) -> Self {
+        Self { steps: Vec::new(), budget_bytes }
+    }
+
+    pub fn clear(&mut self) {
+        self.steps.clear();
+    }
+
+    /// Change the memory limit, dropping the oldest steps that no longer fit
+    pub fn set_budget(&mut self, budget_bytes: usize) {
+        self.budget_bytes = budget_bytes;
+        while self.used_bytes() > self.budget_bytes {
+            self.steps.remove(0);
+        }
+    }
+
+    /// Bytes held by cached results
+    pub fn used_bytes(&self) -> usize {
+        self.steps.iter().map(|step| step.bits.as_raw_slice().len()).sum()
+    }
+
+    /// Run the enabled `operations` over `input`, resuming from the longest cached prefix.
+    /// `apply` is called with each operation's index in `operations`; when it fails the
+    /// steps completed so far stay cached.
+    pub fn run<F>(&mut self, input: BitVec<u8, Msb0>, operations: &[BitOperation], mut apply: F) -> Result<BitVec<u8, Msb0>, String>
+    where
+        F: FnMut(usize, &BitOperation, &mut BitVec<u8, Msb0>) -> Result<(), String>,
+    {
+        let enabled: Vec<(usize, &BitOperation)> = operations
+            .iter()
+            .enumerate()
+            .filter(|(_, op)| op.is_enabled())
+            .collect();
+
+        let mut key = hash_bits(&input);
+        let keys: Vec<u64> = enabled
+            .iter()
+            .map(|(_, op)| {
+                key = step_key(key, op);
+                key
+            })
+            .collect();
+
+        let resume = (1..=keys.len())
+            .rev()
+            .find_map(|done| self.find(keys[done - 1]).map(|bits| (done, bits)));
+        let (done, mut bits) = match resume {
+            Some((done, cached)) => (done, (*cached).clone()),
+            None => (0, input),
+        };
+
+        for (step, &(index, op)) in enabled.iter().enumerate().skip(done) {
+            apply(index, op, &mut bits)?;
+            self.store(keys[step], &bits);
+        }
+        Ok(bits)
+    }
+
+    /// Output of the enabled `operations` over `input`, if every step of it is still cached
+    pub fn cached_output(&self, input: &BitSlice<u8, Msb0>, operations: &[BitOperation]) -> Option<Arc<BitVec<u8, Msb0>>> {
+        let key = operations
+            .iter()
+            .filter(|op| op.is_enabled())
+            .fold(hash_bits(input), step_key);
+        self.find(key)
+    }
+
+    fn find(&self, key: u64) -> Option<Arc<BitVec<u8, Msb0>>> {
+        self.steps.iter().find(|step| step.key == key).map(|step| step.bits.clone())
+    }
+
+    fn store(&mut self, key: u64, bits: &BitVec<u8, Msb0>) {
+        if bits.as_raw_slice().len() > self.budget_bytes {
+            return;
+        }
+        self.steps.retain(|step| step.key != key);
+        self.steps.push(CachedStep { key, bits: Arc::new(bits.clone()) });
+        while self.used_bytes() > self.budget_bytes {
+            self.steps.remove(0);
+        }
+    }
+}
+
+fn hash_bits(bits: &BitSlice<u8, Msb0>) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    bits.len().hash(&mut hasher);
+    for chunk in bits.chunks(64) {
+        chunk.load_be::<u64>().hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Key of the step applying `op` to the output of the step keyed `previous`
+fn step_key(previous: u64, op: &BitOperation) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    previous.hash(&mut hasher);
+    // Everything that affects the output is serialized, so the JSON form identifies the
+    // parameters once the labels are taken out
+    let mut params = serde_json::to_value(op).unwrap_or_default();
+    strip_labels(&mut params);
+    params.to_string().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Remove the names and panel state of a serialized operation and the operations nested in
+/// it, which don't change what it outputs
+fn strip_labels(op: &mut serde_json::Value) {
+    let Some(fields) = op.as_object_mut().and_then(|variant| variant.values_mut().next()).and_then(|fields| fields.as_object_mut()) else {
+        return;
+    };
+    fields.remove("name");
+    fields.remove("collapsed");
+    if let Some(children) = fields.get_mut("operations").and_then(|children| children.as_array_mut()) {
+        children.iter_mut().for_each(strip_labels);
+    }
+    for child in ["operation", "a", "b"] {
+        if let Some(child) = fields.get_mut(child) {
+            strip_labels(child);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invert(name: &str) -> BitOperation {
+        BitOperation::InvertBits { name: name.to_string(), enabled: true }
+    }
+
+    fn truncate(end: usize) -> BitOperation {
+        BitOperation::TruncateBits { name: "Truncate".to_string(), start: 0, end, exprs: Default::default(), enabled: true }
+    }
+
+    #[test]
+    fn test_changing_the_last_operation_reuses_earlier_steps() {
+        let input = bitvec![u8, Msb0; 1, 0, 1, 1, 0, 0, 1, 0];
+        let mut cache = OperationCache::new(1024);
+        let mut chain = vec![invert("First"), invert("Second"), truncate(4)];
+
+        let mut applied = Vec::new();
+        let first = cache.run(input.clone(), &chain, |index, op, bits| {
+            applied.push(index);
+            op.apply_in_place(bits);
+            Ok(())
+        }).unwrap();
+        assert_eq!(applied, vec![0, 1, 2]);
+
+        chain[2] = truncate(6);
+        let mut applied = Vec::new();
+        let second = cache.run(input.clone(), &chain, |index, op, bits| {
+            applied.push(index);
+            op.apply_in_place(bits);
+            Ok(())
+        }).unwrap();
+        assert_eq!(applied, vec![2]);
+        assert_eq!(second[..4], first[..]);
+        assert_eq!(second.len(), 6);
+
+        // Disabling an early operation changes every later key; the invert left first is
+        // the same step as the old first one, labels aside, so only the truncate runs again
+        if let BitOperation::InvertBits { enabled, .. } = &mut chain[0] {
+            *enabled = false;
+        }
+        let mut applied = Vec::new();
+        let third = cache.run(input.clone(), &chain, |index, op, bits| {
+            applied.push(index);
+            op.apply_in_place(bits);
+            Ok(())
+        }).unwrap();
+        assert_eq!(applied, vec![2]);
+        assert_eq!(third, !input[..6].to_bitvec());
+    }
+
+    #[test]
+    fn test_renaming_or_collapsing_keeps_the_cache() {
+        let input = bitvec![u8, Msb0; 1, 0, 1, 1, 0, 0, 1, 0];
+        let mut cache = OperationCache::new(1024);
+        let group = |name: &str, collapsed| BitOperation::Group {
+            name: name.to_string(),
+            operations: vec![invert("Inner"), truncate(6)],
+            collapsed,
+            enabled: true,
+        };
+        let run = |cache: &mut OperationCache, chain: &[BitOperation]| {
+            let mut applied = Vec::new();
+            cache.run(input.clone(), chain, |index, op, bits| {
+                applied.push(index);
+                op.apply_in_place(bits);
+                Ok(())
+            }).unwrap();
+            applied
+        };
+        assert_eq!(run(&mut cache, &[invert("First"), group("Group", false)]), vec![0, 1]);
+        assert!(run(&mut cache, &[invert("Renamed"), group("Group", true)]).is_empty());
+
+        let mut renamed_child = group("Group", true);
+        if let BitOperation::Group { operations, .. } = &mut renamed_child {
+            operations[0] = invert("Renamed inner");
+        }
+        assert!(run(&mut cache, &[invert("First"), renamed_child]).is_empty());
+    }
+
+    #[test]
+    fn test_budget_evicts_oldest_steps() {
+        let input = bitvec![u8, Msb0; 0; 64];
+        let mut cache = OperationCache::new(16);
+        let chain = vec![invert("A"), invert("B"), invert("C")];
+        cache.run(input, &chain, |_, op, bits| {
+            op.apply_in_place(bits);
+            Ok(())
+        }).unwrap();
+        assert_eq!(cache.used_bytes(), 16);
+        assert_eq!(cache.steps.len(), 2);
+    }
+}
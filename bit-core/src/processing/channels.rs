@@ -0,0 +1,38 @@
+// Channels - splitting a round-robin interleaved stream into its sub-streams
+
+use bitvec::prelude::*;
+
+/// `bits` dealt out to `channels` sub-streams `unit_bits` at a time: the first unit to channel
+/// 0, the next to channel 1 and so on. With one-bit units and two channels this separates
+/// even and odd bits. A trailing partial unit goes to the channel whose turn it is.
+pub fn split_channels(bits: &BitSlice<u8, Msb0>, channels: usize, unit_bits: usize) -> Vec<BitVec<u8, Msb0>> {
+    if channels == 0 || unit_bits == 0 {
+        return Vec::new();
+    }
+    let mut outputs: Vec<BitVec<u8, Msb0>> = (0..channels)
+        .map(|_| BitVec::with_capacity(bits.len() / channels + unit_bits))
+        .collect();
+    for (index, unit) in bits.chunks(unit_bits).enumerate() {
+        outputs[index % channels].extend_from_bitslice(unit);
+    }
+    outputs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_robin_split() {
+        let bits = bitvec![u8, Msb0; 1, 0, 1, 0, 1, 1, 0];
+        let channels = split_channels(&bits, 2, 1);
+        assert_eq!(channels, vec![bitvec![u8, Msb0; 1, 1, 1, 0], bitvec![u8, Msb0; 0, 0, 1]]);
+
+        // Byte units over three channels
+        let bytes = [0x11u8, 0x22, 0x33, 0x44, 0x55];
+        let channels = split_channels(bytes.view_bits::<Msb0>(), 3, 8);
+        let as_bytes: Vec<Vec<u8>> = channels.iter().map(|channel| channel.clone().into_vec()).collect();
+        assert_eq!(as_bytes, vec![vec![0x11, 0x44], vec![0x22, 0x55], vec![0x33]]);
+        assert!(split_channels(&bits, 0, 1).is_empty());
+    }
+}
@@ -0,0 +1,423 @@
+// Error correcting codes for blocks of bytes - the Hamming and BCH codes NAND flash stores
+// alongside each sector of a page
+
+/// Sizes of the sectors a Hamming code can cover
+pub const HAMMING_SECTORS: [usize; 2] = [256, 512];
+
+/// The outcome of checking one sector against its stored code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EccStatus {
+    Clean,
+    /// This many bit errors were found and fixed, in the data or in the code itself
+    Corrected(usize),
+    Uncorrectable,
+}
+
+/// The 3 byte software Hamming code of Linux MTD (`nand_ecc.c`) over a 256 or 512 byte
+/// sector, correcting one bit. Parities are stored inverted so an erased sector's code
+/// reads as FF FF FF.
+pub fn hamming_encode(data: &[u8]) -> [u8; 3] {
+    let mut column = 0u8;
+    // Parity of the odd-parity bytes, overall and of those with each address bit set
+    let mut odd_bytes = false;
+    let mut odd_addresses = 0usize;
+    for (address, &byte) in data.iter().enumerate() {
+        column ^= byte;
+        if byte.count_ones() % 2 == 1 {
+            odd_bytes = !odd_bytes;
+            odd_addresses ^= address;
+        }
+    }
+
+    // Line parities of address bit k in pairs, the group with the bit set first
+    let line = |k: usize| -> u8 {
+        let set = (odd_addresses >> k) & 1 == 1;
+        let clear = set != odd_bytes;
+        (u8::from(set) << 1) | u8::from(clear)
+    };
+    let parity = |mask: u8| u8::from((column & mask).count_ones() % 2 == 1);
+
+    let code0 = (line(7) << 6) | (line(6) << 4) | (line(5) << 2) | line(4);
+    let code1 = (line(3) << 6) | (line(2) << 4) | (line(1) << 2) | line(0);
+    let low = if data.len() > 256 { line(8) } else { 0 };
+    let code2 = (parity(0xF0) << 7)
+        | (parity(0x0F) << 6)
+        | (parity(0xCC) << 5)
+        | (parity(0x33) << 4)
+        | (parity(0xAA) << 3)
+        | (parity(0x55) << 2)
+        | low;
+    // The two low bits of a 256 byte sector's column parity byte are unused and read as 1
+    [!code0, !code1, !code2]
+}
+
+/// Check `data` against its stored Hamming code, fixing a single flipped bit
+pub fn hamming_correct(data: &mut [u8], stored: &[u8]) -> EccStatus {
+    let calculated = hamming_encode(data);
+    let s = [stored[0] ^ calculated[0], stored[1] ^ calculated[1], stored[2] ^ calculated[2]];
+    if s == [0, 0, 0] {
+        return EccStatus::Clean;
+    }
+
+    // A data bit flips exactly one parity of every pair
+    let large = data.len() > 256;
+    let pairs_differ = |byte: u8, mask: u8| (byte ^ (byte >> 1)) & mask == mask;
+    if pairs_differ(s[0], 0x55) && pairs_differ(s[1], 0x55) && pairs_differ(s[2], if large { 0x55 } else { 0x54 }) {
+        let odd_bits = |byte: u8| -> usize {
+            (0..4).fold(0, |address, pair| (address << 1) | usize::from((byte >> (7 - 2 * pair)) & 1))
+        };
+        let mut address = (odd_bits(s[0]) << 4) | odd_bits(s[1]);
+        if large {
+            address |= usize::from((s[2] >> 1) & 1) << 8;
+        }
+        let bit = (((s[2] >> 7) & 1) << 2) | (((s[2] >> 5) & 1) << 1) | ((s[2] >> 3) & 1);
+        data[address] ^= 1 << bit;
+        return EccStatus::Corrected(1);
+    }
+
+    if s.iter().map(|byte| byte.count_ones()).sum::<u32>() == 1 {
+        // The flipped bit is in the stored code
+        EccStatus::Corrected(1)
+    } else {
+        EccStatus::Uncorrectable
+    }
+}
+
+/// Primitive polynomials of GF(2^m), as Linux's `bch.c` picks them, for m 5 through 15
+const PRIMITIVE_POLYNOMIALS: [u32; 11] = [0x25, 0x43, 0x83, 0x11D, 0x211, 0x409, 0x805, 0x1053, 0x201B, 0x402B, 0x8003];
+
+/// A binary BCH code correcting `strength` bit errors in sectors of `sector` bytes, built
+/// the way Linux's software BCH builds it: the smallest field that holds the sector, the
+/// parity bits written MSB first and padded to whole bytes, and the code XORed with a mask
+/// so an erased sector's code reads as all FF
+#[derive(Debug, Clone)]
+pub struct Bch {
+    m: usize,
+    strength: usize,
+    /// Field element of each power of alpha
+    exp: Vec<u32>,
+    /// Power of alpha of each nonzero field element
+    log: Vec<u32>,
+    /// Parity bits, `m * strength` at most
+    parity_bits: usize,
+    /// Remainder of each byte shifted past the parity bits, one row of `ecc_bytes` per byte
+    table: Vec<u8>,
+    mask: Vec<u8>,
+}
+
+impl Bch {
+    pub fn new(sector: usize, strength: usize) -> Result<Self, String> {
+        if sector == 0 {
+            return Err("Sector size must be at least 1 byte".to_string());
+        }
+        if strength == 0 {
+            return Err("BCH strength must be at least 1 bit".to_string());
+        }
+        let data_bits = sector * 8;
+        let mut m = (usize::BITS - (data_bits + 1).leading_zeros()) as usize;
+        while m <= 15 && (1usize << m) - 1 < data_bits + m * strength {
+            m += 1;
+        }
+        let m = m.max(5);
+        if m > 15 {
+            return Err(format!("A {} bit BCH code over {} byte sectors is too long", strength, sector));
+        }
+
+        let n = (1usize << m) - 1;
+        let polynomial = PRIMITIVE_POLYNOMIALS[m - 5];
+        let mut exp = vec![0u32; n];
+        let mut log = vec![0u32; n + 1];
+        let mut element = 1u32;
+        for (power, slot) in exp.iter_mut().enumerate() {
+            *slot = element;
+            log[element as usize] = power as u32;
+            element <<= 1;
+            if element & (1 << m) != 0 {
+                element ^= polynomial;
+            }
+        }
+
+        let mut bch = Bch { m, strength, exp, log, parity_bits: 0, table: Vec::new(), mask: Vec::new() };
+
+        // Generator: product of (x - alpha^r) over the conjugates of alpha^1, alpha^3, ...
+        let mut roots = vec![false; n];
+        for odd in (1..2 * strength).step_by(2) {
+            let mut root = odd % n;
+            while !roots[root] {
+                roots[root] = true;
+                root = (root * 2) % n;
+            }
+        }
+        // Coefficients lowest degree first
+        let mut generator = vec![1u32];
+        for root in (0..n).filter(|&root| roots[root]) {
+            let mut next = vec![0u32; generator.len() + 1];
+            for (degree, &coefficient) in generator.iter().enumerate() {
+                next[degree + 1] ^= coefficient;
+                next[degree] ^= bch.mul(coefficient, bch.exp[root]);
+            }
+            generator = next;
+        }
+        bch.parity_bits = generator.len() - 1;
+        if 8 * sector + bch.parity_bits > n {
+            return Err(format!("BCH strength {} is too high for {} byte sectors", strength, sector));
+        }
+
+        // Byte-wise division by the generator shifted up to a whole number of bytes, so the
+        // remainder's top bits are the parity, MSB first
+        let ecc_bytes = bch.ecc_bytes();
+        let register_bits = ecc_bytes * 8;
+        let mut shifted = vec![false; register_bits + 1];
+        for (degree, &coefficient) in generator.iter().enumerate() {
+            shifted[degree + register_bits - bch.parity_bits] = coefficient == 1;
+        }
+        bch.table = vec![0u8; 256 * ecc_bytes];
+        for byte in 0..256usize {
+            // Register bit i holds the coefficient of x^(register_bits - 1 - i)
+            let mut register = vec![false; register_bits];
+            for bit in (0..8).rev() {
+                let feedback = ((byte >> bit) & 1 == 1) ^ register[0];
+                register.remove(0);
+                register.push(false);
+                if feedback {
+                    for (i, slot) in register.iter_mut().enumerate() {
+                        *slot ^= shifted[register_bits - 1 - i];
+                    }
+                }
+            }
+            let row = &mut bch.table[byte * ecc_bytes..(byte + 1) * ecc_bytes];
+            for (i, &set) in register.iter().enumerate() {
+                if set {
+                    row[i / 8] |= 0x80 >> (i % 8);
+                }
+            }
+        }
+
+        // The mask makes the code of an all-FF sector read back as all FF
+        let erased = bch.remainder(&vec![0xFF; sector]);
+        bch.mask = erased.iter().map(|byte| !byte).collect();
+        Ok(bch)
+    }
+
+    /// Bytes of code stored per sector
+    pub fn ecc_bytes(&self) -> usize {
+        (self.m * self.strength).div_ceil(8)
+    }
+
+    /// Bits of the code that carry parity, the rest of the last byte is padding
+    pub fn parity_bits(&self) -> usize {
+        self.parity_bits
+    }
+
+    fn mul(&self, a: u32, b: u32) -> u32 {
+        if a == 0 || b == 0 {
+            return 0;
+        }
+        let n = self.exp.len();
+        self.exp[(self.log[a as usize] as usize + self.log[b as usize] as usize) % n]
+    }
+
+    fn div(&self, a: u32, b: u32) -> u32 {
+        if a == 0 {
+            return 0;
+        }
+        let n = self.exp.len();
+        self.exp[(self.log[a as usize] as usize + n - self.log[b as usize] as usize) % n]
+    }
+
+    /// Remainder of the sector times x^parity_bits, left aligned in `ecc_bytes` bytes
+    fn remainder(&self, data: &[u8]) -> Vec<u8> {
+        let ecc_bytes = self.ecc_bytes();
+        let mut register = vec![0u8; ecc_bytes];
+        for &byte in data {
+            let index = (register[0] ^ byte) as usize;
+            register.copy_within(1.., 0);
+            register[ecc_bytes - 1] = 0;
+            for (slot, &value) in register.iter_mut().zip(&self.table[index * ecc_bytes..(index + 1) * ecc_bytes]) {
+                *slot ^= value;
+            }
+        }
+        register
+    }
+
+    /// The code stored for `data`, one sector long
+    pub fn encode(&self, data: &[u8]) -> Vec<u8> {
+        let mut code = self.remainder(data);
+        for (byte, mask) in code.iter_mut().zip(&self.mask) {
+            *byte ^= mask;
+        }
+        code
+    }
+
+    /// Check `data` against its stored code, fixing up to `strength` flipped bits
+    pub fn correct(&self, data: &mut [u8], stored: &[u8]) -> EccStatus {
+        let calculated = self.encode(data);
+        // Codeword bits past the parity in the last byte are padding
+        let padding = self.ecc_bytes() * 8 - self.parity_bits;
+        let mut difference: Vec<u8> = calculated.iter().zip(stored).map(|(a, b)| a ^ b).collect();
+        if let Some(last) = difference.last_mut() {
+            *last &= !((1u16 << padding) - 1) as u8;
+        }
+        if difference.iter().all(|&byte| byte == 0) {
+            return EccStatus::Clean;
+        }
+
+        // The data with its calculated parity is a codeword, so the syndromes of the
+        // received word are those of the parity difference alone. Parity bit i is the
+        // coefficient of x^(parity_bits - 1 - i).
+        let n = self.exp.len();
+        let mut syndromes = vec![0u32; 2 * self.strength];
+        for i in (0..self.parity_bits).filter(|&i| difference[i / 8] & (0x80 >> (i % 8)) != 0) {
+            let degree = self.parity_bits - 1 - i;
+            for (j, syndrome) in syndromes.iter_mut().enumerate() {
+                *syndrome ^= self.exp[((j + 1) * degree) % n];
+            }
+        }
+
+        let Some(locator) = self.berlekamp_massey(&syndromes) else {
+            return EccStatus::Uncorrectable;
+        };
+        let errors = locator.len() - 1;
+
+        // Chien search over the shortened codeword: data bit i has degree length - 1 - i
+        let length = 8 * data.len() + self.parity_bits;
+        let mut positions = Vec::with_capacity(errors);
+        for degree in 0..length {
+            // Evaluate the locator at alpha^-degree
+            let inverse = (n - degree % n) % n;
+            let value = locator.iter().enumerate().fold(0u32, |sum, (k, &coefficient)| {
+                sum ^ self.mul(coefficient, self.exp[(k * inverse) % n])
+            });
+            if value == 0 {
+                positions.push(degree);
+            }
+        }
+        if positions.len() != errors {
+            return EccStatus::Uncorrectable;
+        }
+        for degree in positions.into_iter().filter(|&degree| degree >= self.parity_bits) {
+            let bit = length - 1 - degree;
+            data[bit / 8] ^= 0x80 >> (bit % 8);
+        }
+        EccStatus::Corrected(errors)
+    }
+
+    /// Error locator polynomial of the syndromes S1, S2, ..., lowest degree first, or None
+    /// when they describe more errors than the code corrects
+    fn berlekamp_massey(&self, syndromes: &[u32]) -> Option<Vec<u32>> {
+        let mut locator = vec![1u32];
+        let mut previous = vec![1u32];
+        let mut length = 0;
+        let mut shift = 1;
+        let mut previous_discrepancy = 1u32;
+        for step in 0..syndromes.len() {
+            let discrepancy = (1..=length).fold(syndromes[step], |sum, i| {
+                sum ^ self.mul(*locator.get(i).unwrap_or(&0), syndromes[step - i])
+            });
+            if discrepancy == 0 {
+                shift += 1;
+                continue;
+            }
+            let scale = self.div(discrepancy, previous_discrepancy);
+            let mut next = locator.clone();
+            next.resize(next.len().max(previous.len() + shift), 0);
+            for (i, &coefficient) in previous.iter().enumerate() {
+                next[i + shift] ^= self.mul(scale, coefficient);
+            }
+            if 2 * length <= step {
+                previous = std::mem::replace(&mut locator, next);
+                length = step + 1 - length;
+                previous_discrepancy = discrepancy;
+                shift = 1;
+            } else {
+                locator = next;
+                shift += 1;
+            }
+        }
+        while locator.len() > 1 && locator.last() == Some(&0) {
+            locator.pop();
+        }
+        let degree = locator.len() - 1;
+        (degree == length && (1..=self.strength).contains(&degree)).then_some(locator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sector(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i * 37 + i / 7) as u8).collect()
+    }
+
+    #[test]
+    fn test_hamming_corrects_one_bit() {
+        for len in HAMMING_SECTORS {
+            let original = sector(len);
+            let code = hamming_encode(&original);
+            assert_eq!(hamming_encode(&vec![0xFF; len]), [0xFF; 3]);
+
+            for (byte, bit) in [(0, 0), (len - 1, 7), (len / 3, 4)] {
+                let mut data = original.clone();
+                data[byte] ^= 1 << bit;
+                assert_eq!(hamming_correct(&mut data, &code), EccStatus::Corrected(1));
+                assert_eq!(data, original);
+            }
+
+            // A flip in the code itself leaves the data alone
+            let mut data = original.clone();
+            let mut bad_code = code;
+            bad_code[1] ^= 0x10;
+            assert_eq!(hamming_correct(&mut data, &bad_code), EccStatus::Corrected(1));
+            assert_eq!(data, original);
+
+            let mut data = original.clone();
+            data[3] ^= 0x01;
+            data[9] ^= 0x40;
+            assert_eq!(hamming_correct(&mut data, &code), EccStatus::Uncorrectable);
+        }
+    }
+
+    #[test]
+    fn test_bch_corrects_up_to_strength() {
+        let bch = Bch::new(512, 4).unwrap();
+        assert_eq!(bch.ecc_bytes(), 7);
+        assert_eq!(bch.encode(&[0xFF; 512]), vec![0xFF; 7]);
+
+        let original = sector(512);
+        let code = bch.encode(&original);
+        let mut data = original.clone();
+        assert_eq!(bch.correct(&mut data, &code), EccStatus::Clean);
+
+        for flips in 1..=4 {
+            let mut data = original.clone();
+            for k in 0..flips {
+                data[k * 101 + 3] ^= 0x80 >> k;
+            }
+            assert_eq!(bch.correct(&mut data, &code), EccStatus::Corrected(flips));
+            assert_eq!(data, original);
+        }
+
+        // Errors split between the data and the stored code
+        let mut data = original.clone();
+        data[200] ^= 0x04;
+        let mut bad_code = code.clone();
+        bad_code[2] ^= 0x20;
+        assert_eq!(bch.correct(&mut data, &bad_code), EccStatus::Corrected(2));
+        assert_eq!(data, original);
+
+        let mut data = original.clone();
+        for k in 0..6 {
+            data[k * 50] ^= 0x01;
+        }
+        assert_eq!(bch.correct(&mut data, &code), EccStatus::Uncorrectable);
+    }
+
+    #[test]
+    fn test_bch_rejects_impossible_codes() {
+        assert!(Bch::new(512, 0).is_err());
+        assert!(Bch::new(0, 4).is_err());
+        assert!(Bch::new(4096, 1).is_err());
+        assert_eq!(Bch::new(1024, 8).unwrap().ecc_bytes(), 14);
+    }
+}
@@ -3,6 +3,7 @@
 
 use bitvec::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 
 /// Type of interleaving to perform
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -55,90 +56,66 @@ impl BlockInterleaverConfig {
     }
 
     /// Apply block interleaving to input bits
+    #[allow(dead_code)]
     pub fn apply(&self, input: &BitVec<u8, Msb0>) -> BitVec<u8, Msb0> {
-        match self.direction {
-            InterleaverDirection::Interleave => self.interleave(input),
-            InterleaverDirection::Deinterleave => self.deinterleave(input),
-        }
+        let mut result = input.clone();
+        self.apply_in_place(&mut result);
+        result
     }
 
-    /// Block interleaving: write row-wise, read column-wise
-    fn interleave(&self, input: &BitVec<u8, Msb0>) -> BitVec<u8, Msb0> {
-        if input.is_empty() || self.block_size == 0 || self.depth == 0 {
-            return input.clone();
+    /// Apply block interleaving directly to `bits`, one matrix at a time
+    /// Only a single matrix worth of scratch space is allocated
+    pub fn apply_in_place(&self, bits: &mut BitVec<u8, Msb0>) {
+        if bits.is_empty() || self.block_size == 0 || self.depth == 0 {
+            return;
         }
 
-        let mut result = BitVec::new();
         let matrix_size = self.block_size * self.depth;
-        
+        let mut scratch = BitVec::with_capacity(matrix_size);
+
         // Process input in matrix_size chunks
-        for chunk_start in (0..input.len()).step_by(matrix_size) {
-            let chunk_end = (chunk_start + matrix_size).min(input.len());
-            let chunk = &input[chunk_start..chunk_end];
-            
-            // Create matrix: write row-wise (fill by rows)
-            // Matrix is depth rows x block_size columns
-            let mut matrix = vec![vec![false; self.block_size]; self.depth];
-            
-            for (i, bit) in chunk.iter().enumerate() {
-                let row = i / self.block_size;
-                let col = i % self.block_size;
-                if row < self.depth && col < self.block_size {
-                    matrix[row][col] = *bit;
-                }
+        for chunk_start in (0..bits.len()).step_by(matrix_size) {
+            let chunk_end = (chunk_start + matrix_size).min(bits.len());
+
+            scratch.clear();
+            match self.direction {
+                InterleaverDirection::Interleave => self.interleave(&bits[chunk_start..chunk_end], &mut scratch),
+                InterleaverDirection::Deinterleave => self.deinterleave(&bits[chunk_start..chunk_end], &mut scratch),
             }
-            
-            // Read column-wise
-            for col in 0..self.block_size {
-                for row in 0..self.depth {
-                    if chunk_start + row * self.block_size + col < chunk_end {
-                        result.push(matrix[row][col]);
-                    }
+
+            // Every input position is read exactly once, so the chunk keeps its length
+            bits[chunk_start..chunk_end].copy_from_bitslice(&scratch);
+        }
+    }
+
+    /// Block interleaving: write row-wise, read column-wise
+    fn interleave(&self, chunk: &BitSlice<u8, Msb0>, out: &mut BitVec<u8, Msb0>) {
+        // Matrix is depth rows x block_size columns, filled by rows,
+        // so the chunk itself is the matrix and reading a column is a strided walk
+        for col in 0..self.block_size {
+            for row in 0..self.depth {
+                let idx = row * self.block_size + col;
+                if idx < chunk.len() {
+                    out.push(chunk[idx]);
                 }
             }
         }
-        
-        result
     }
 
     /// Block deinterleaving: write column-wise, read row-wise
-    fn deinterleave(&self, input: &BitVec<u8, Msb0>) -> BitVec<u8, Msb0> {
-        if input.is_empty() || self.block_size == 0 || self.depth == 0 {
-            return input.clone();
-        }
-
-        let mut result = BitVec::new();
-        let matrix_size = self.block_size * self.depth;
-        
-        // Process input in matrix_size chunks
-        for chunk_start in (0..input.len()).step_by(matrix_size) {
-            let chunk_end = (chunk_start + matrix_size).min(input.len());
-            let chunk = &input[chunk_start..chunk_end];
-            
-            // Create matrix: write column-wise
-            let mut matrix = vec![vec![false; self.block_size]; self.depth];
-            
-            let mut bit_idx = 0;
+    fn deinterleave(&self, chunk: &BitSlice<u8, Msb0>, out: &mut BitVec<u8, Msb0>) {
+        // Interleaving a partial chunk reads only the cells it has, so the first
+        // `len % block_size` columns are one row taller than the rest; matrix cell
+        // (row, col) holds input bit column_start(col) + row, col * depth + row when full
+        let (full_rows, taller) = (chunk.len() / self.block_size, chunk.len() % self.block_size);
+        let column_start = |col: usize| col * full_rows + col.min(taller);
+        for row in 0..self.depth {
             for col in 0..self.block_size {
-                for row in 0..self.depth {
-                    if bit_idx < chunk.len() {
-                        matrix[row][col] = chunk[bit_idx];
-                        bit_idx += 1;
-                    }
-                }
-            }
-            
-            // Read row-wise
-            for row in 0..self.depth {
-                for col in 0..self.block_size {
-                    if chunk_start + row * self.block_size + col < chunk_end {
-                        result.push(matrix[row][col]);
-                    }
+                if row * self.block_size + col < chunk.len() {
+                    out.push(chunk[column_start(col) + row]);
                 }
             }
         }
-        
-        result
     }
 }
 
@@ -152,71 +129,47 @@ impl ConvolutionalInterleaverConfig {
     }
 
     /// Apply convolutional interleaving to input bits
+    #[allow(dead_code)]
     pub fn apply(&self, input: &BitVec<u8, Msb0>) -> BitVec<u8, Msb0> {
-        match self.direction {
-            InterleaverDirection::Interleave => self.interleave(input),
-            InterleaverDirection::Deinterleave => self.deinterleave(input),
-        }
-    }
-
-    /// Convolutional interleaving: distribute bits across branches with increasing delays
-    fn interleave(&self, input: &BitVec<u8, Msb0>) -> BitVec<u8, Msb0> {
-        if input.is_empty() || self.branches == 0 {
-            return input.clone();
-        }
-
-        // Create delay lines for each branch
-        // Branch i has delay of i * delay_increment
-        let mut delays: Vec<Vec<bool>> = (0..self.branches)
-            .map(|i| vec![false; i * self.delay_increment])
-            .collect();
-
-        let mut result = BitVec::new();
-        let mut current_branch = 0;
-
-        for bit in input.iter() {
-            // Add bit to end of current branch's delay line
-            delays[current_branch].push(*bit);
-            
-            // Extract output from front of current branch (FIFO)
-            let output_bit = delays[current_branch].remove(0);
-            result.push(output_bit);
-            
-            // Move to next branch (round-robin)
-            current_branch = (current_branch + 1) % self.branches;
-        }
-
+        let mut result = input.clone();
+        self.apply_in_place(&mut result);
         result
     }
 
-    /// Convolutional deinterleaving: reverse the interleaving process
-    fn deinterleave(&self, input: &BitVec<u8, Msb0>) -> BitVec<u8, Msb0> {
-        if input.is_empty() || self.branches == 0 {
-            return input.clone();
+    /// Apply convolutional interleaving directly to `bits`
+    /// Each output bit replaces the input bit at the same position, so only the delay lines need extra memory
+    pub fn apply_in_place(&self, bits: &mut BitVec<u8, Msb0>) {
+        if bits.is_empty() || self.branches == 0 {
+            return;
         }
 
-        // Create delay lines for each branch (inverse delays)
-        // For de-interleaving, branch i has delay of (branches - 1 - i) * delay_increment
-        let mut delays: Vec<Vec<bool>> = (0..self.branches)
-            .map(|i| vec![false; (self.branches - 1 - i) * self.delay_increment])
-            .collect();
+        let mut delays: Vec<VecDeque<bool>> = match self.direction {
+            // Convolutional interleaving: distribute bits across branches with increasing delays
+            // Branch i has delay of i * delay_increment
+            InterleaverDirection::Interleave => (0..self.branches)
+                .map(|i| VecDeque::from(vec![false; i * self.delay_increment]))
+                .collect(),
+            // Convolutional deinterleaving: reverse the interleaving process
+            // For de-interleaving, branch i has delay of (branches - 1 - i) * delay_increment
+            InterleaverDirection::Deinterleave => (0..self.branches)
+                .map(|i| VecDeque::from(vec![false; (self.branches - 1 - i) * self.delay_increment]))
+                .collect(),
+        };
 
-        let mut result = BitVec::new();
         let mut current_branch = 0;
 
-        for bit in input.iter() {
+        for i in 0..bits.len() {
             // Add bit to end of current branch's delay line
-            delays[current_branch].push(*bit);
-            
+            delays[current_branch].push_back(bits[i]);
+
             // Extract output from front of current branch (FIFO)
-            let output_bit = delays[current_branch].remove(0);
-            result.push(output_bit);
-            
+            if let Some(output_bit) = delays[current_branch].pop_front() {
+                bits.set(i, output_bit);
+            }
+
             // Move to next branch (round-robin)
             current_branch = (current_branch + 1) % self.branches;
         }
-
-        result
     }
 
     /// Calculate total delay introduced by the interleaver
@@ -239,114 +192,80 @@ impl SymbolInterleaverConfig {
     }
 
     /// Apply symbol-level interleaving to input bits
+    #[allow(dead_code)]
     pub fn apply(&self, input: &BitVec<u8, Msb0>) -> BitVec<u8, Msb0> {
-        match self.direction {
-            InterleaverDirection::Interleave => self.interleave(input),
-            InterleaverDirection::Deinterleave => self.deinterleave(input),
-        }
+        let mut result = input.clone();
+        self.apply_in_place(&mut result);
+        result
     }
 
-    /// Symbol interleaving: write symbols row-wise, read column-wise
-    /// Treats each symbol_size bits as an atomic unit
-    fn interleave(&self, input: &BitVec<u8, Msb0>) -> BitVec<u8, Msb0> {
-        if input.is_empty() || self.symbol_size == 0 || self.block_size == 0 || self.depth == 0 {
-            return input.clone();
+    /// Apply symbol-level interleaving directly to `bits`, one matrix at a time
+    /// Trailing bits that don't form a whole symbol are dropped, so the output is compacted
+    /// towards the front of the buffer and truncated at the end
+    pub fn apply_in_place(&self, bits: &mut BitVec<u8, Msb0>) {
+        if bits.is_empty() || self.symbol_size == 0 || self.block_size == 0 || self.depth == 0 {
+            return;
         }
 
-        let mut result = BitVec::new();
         let symbols_per_matrix = self.block_size * self.depth;
         let bits_per_matrix = symbols_per_matrix * self.symbol_size;
-        
+        let mut scratch = BitVec::with_capacity(bits_per_matrix);
+        let mut write_pos = 0;
+
         // Process input in matrix-sized chunks
-        for chunk_start in (0..input.len()).step_by(bits_per_matrix) {
-            let chunk_end = (chunk_start + bits_per_matrix).min(input.len());
-            let chunk = &input[chunk_start..chunk_end];
-            
-            // Extract symbols from chunk
-            let mut symbols: Vec<BitVec<u8, Msb0>> = Vec::new();
-            for symbol_start in (0..chunk.len()).step_by(self.symbol_size) {
-                let symbol_end = (symbol_start + self.symbol_size).min(chunk.len());
-                if symbol_end - symbol_start == self.symbol_size {
-                    symbols.push(chunk[symbol_start..symbol_end].to_bitvec());
-                }
-            }
-            
-            // Create symbol matrix: write row-wise
-            // Matrix is depth rows x block_size columns
-            let mut matrix: Vec<Vec<BitVec<u8, Msb0>>> = vec![vec![]; self.depth];
-            
-            for (i, symbol) in symbols.iter().enumerate() {
-                let row = i / self.block_size;
-                let col = i % self.block_size;
-                if row < self.depth {
-                    if matrix[row].len() <= col {
-                        matrix[row].resize(col + 1, BitVec::new());
-                    }
-                    matrix[row][col] = symbol.clone();
-                }
+        for chunk_start in (0..bits.len()).step_by(bits_per_matrix) {
+            let chunk_end = (chunk_start + bits_per_matrix).min(bits.len());
+
+            scratch.clear();
+            match self.direction {
+                InterleaverDirection::Interleave => self.interleave(&bits[chunk_start..chunk_end], &mut scratch),
+                InterleaverDirection::Deinterleave => self.deinterleave(&bits[chunk_start..chunk_end], &mut scratch),
             }
-            
-            // Read column-wise
-            for col in 0..self.block_size {
-                for row in 0..self.depth {
-                    if col < matrix[row].len() && !matrix[row][col].is_empty() {
-                        result.extend_from_bitslice(&matrix[row][col]);
-                    }
+
+            // Output never outgrows the input, so the write position trails the chunk being read
+            bits[write_pos..write_pos + scratch.len()].copy_from_bitslice(&scratch);
+            write_pos += scratch.len();
+        }
+
+        bits.truncate(write_pos);
+    }
+
+    /// Symbol interleaving: write symbols row-wise, read column-wise
+    /// Treats each symbol_size bits as an atomic unit
+    fn interleave(&self, chunk: &BitSlice<u8, Msb0>, out: &mut BitVec<u8, Msb0>) {
+        // Only complete symbols take part; symbol i sits at row i / block_size, col i % block_size
+        let symbol_count = chunk.len() / self.symbol_size;
+
+        // Read column-wise
+        for col in 0..self.block_size {
+            for row in 0..self.depth {
+                let idx = row * self.block_size + col;
+                if idx < symbol_count {
+                    out.extend_from_bitslice(self.symbol(chunk, idx));
                 }
             }
         }
-        
-        result
     }
 
     /// Symbol deinterleaving: write symbols column-wise, read row-wise
-    fn deinterleave(&self, input: &BitVec<u8, Msb0>) -> BitVec<u8, Msb0> {
-        if input.is_empty() || self.symbol_size == 0 || self.block_size == 0 || self.depth == 0 {
-            return input.clone();
-        }
+    fn deinterleave(&self, chunk: &BitSlice<u8, Msb0>, out: &mut BitVec<u8, Msb0>) {
+        // Only complete symbols take part; symbol i sits at row i % depth, col i / depth
+        let symbol_count = chunk.len() / self.symbol_size;
 
-        let mut result = BitVec::new();
-        let symbols_per_matrix = self.block_size * self.depth;
-        let bits_per_matrix = symbols_per_matrix * self.symbol_size;
-        
-        // Process input in matrix-sized chunks
-        for chunk_start in (0..input.len()).step_by(bits_per_matrix) {
-            let chunk_end = (chunk_start + bits_per_matrix).min(input.len());
-            let chunk = &input[chunk_start..chunk_end];
-            
-            // Extract symbols from chunk
-            let mut symbols: Vec<BitVec<u8, Msb0>> = Vec::new();
-            for symbol_start in (0..chunk.len()).step_by(self.symbol_size) {
-                let symbol_end = (symbol_start + self.symbol_size).min(chunk.len());
-                if symbol_end - symbol_start == self.symbol_size {
-                    symbols.push(chunk[symbol_start..symbol_end].to_bitvec());
-                }
-            }
-            
-            // Create symbol matrix: write column-wise
-            let mut matrix: Vec<Vec<BitVec<u8, Msb0>>> = vec![vec![BitVec::new(); self.block_size]; self.depth];
-            
-            let mut symbol_idx = 0;
+        // Read row-wise
+        for row in 0..self.depth {
             for col in 0..self.block_size {
-                for row in 0..self.depth {
-                    if symbol_idx < symbols.len() {
-                        matrix[row][col] = symbols[symbol_idx].clone();
-                        symbol_idx += 1;
-                    }
-                }
-            }
-            
-            // Read row-wise
-            for row in 0..self.depth {
-                for col in 0..self.block_size {
-                    if !matrix[row][col].is_empty() {
-                        result.extend_from_bitslice(&matrix[row][col]);
-                    }
+                let idx = col * self.depth + row;
+                if idx < symbol_count {
+                    out.extend_from_bitslice(self.symbol(chunk, idx));
                 }
             }
         }
-        
-        result
+    }
+
+    fn symbol<'a>(&self, chunk: &'a BitSlice<u8, Msb0>, index: usize) -> &'a BitSlice<u8, Msb0> {
+        let start = index * self.symbol_size;
+        &chunk[start..start + self.symbol_size]
     }
 }
 
@@ -378,6 +297,16 @@ mod tests {
         assert_eq!(result, bitvec![u8, Msb0; 1, 0, 1, 1]); // ABCD = 1011
     }
 
+    #[test]
+    fn test_block_deinterleave_partial_matrix() {
+        // Rows AB and C of a 2x3 matrix interleave to ACB, with a short second column
+        let input = bitvec![u8, Msb0; 1, 1, 0];
+        let interleaved = BlockInterleaverConfig::new(2, 3, InterleaverDirection::Interleave).apply(&input);
+        assert_eq!(interleaved, bitvec![u8, Msb0; 1, 0, 1]);
+        let recovered = BlockInterleaverConfig::new(2, 3, InterleaverDirection::Deinterleave).apply(&interleaved);
+        assert_eq!(recovered, input);
+    }
+
     #[test]
     fn test_block_interleave_roundtrip() {
         let input = bitvec![u8, Msb0; 1, 0, 1, 0, 1, 1, 0, 0];
@@ -0,0 +1,280 @@
+// Line codes - decoding 8b/10b and 64b/66b coded streams back to their data
+
+use bitvec::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LineCode {
+    /// 10-bit symbols with running disparity, transmitted abcdei fghj
+    EightBTenB,
+    /// 66-bit blocks of a 2-bit sync header and a payload scrambled with x^58 + x^39 + 1
+    SixtyFourBSixtySixB,
+}
+
+impl LineCode {
+    pub const ALL: [LineCode; 2] = [LineCode::EightBTenB, LineCode::SixtyFourBSixtySixB];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            LineCode::EightBTenB => "8b/10b",
+            LineCode::SixtyFourBSixtySixB => "64b/66b",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViolationKind {
+    /// Not a code group of the line code
+    InvalidCode,
+    /// A valid 8b/10b code group sent with the wrong running disparity
+    Disparity,
+    /// A 64b/66b sync header other than 01 or 10
+    SyncHeader,
+}
+
+impl ViolationKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ViolationKind::InvalidCode => "invalid code group",
+            ViolationKind::Disparity => "disparity error",
+            ViolationKind::SyncHeader => "invalid sync header",
+        }
+    }
+}
+
+/// A code violation, at the first decoded bit of the symbol or block it spoiled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineCodeViolation {
+    pub bit: usize,
+    pub kind: ViolationKind,
+}
+
+/// `bits` decoded with `code`, with the code violations found on the way.
+///
+/// Invalid 8b/10b code groups decode to 0x00; control symbols decode to their byte value.
+/// 64b/66b blocks are located at the offset with the most valid sync headers, and the
+/// payload comes out in transmission order. A trailing partial symbol or block is dropped.
+pub fn decode_line_code(code: LineCode, bits: &BitSlice<u8, Msb0>) -> (BitVec<u8, Msb0>, Vec<LineCodeViolation>) {
+    match code {
+        LineCode::EightBTenB => decode_8b10b(bits),
+        LineCode::SixtyFourBSixtySixB => decode_64b66b(bits),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Symbol {
+    byte: u8,
+    /// Running disparity after the symbol is negative
+    negative_after: bool,
+}
+
+/// abcdei for D.0 to D.31 when the running disparity is negative
+const CODES_5B6B: [u16; 32] = [
+    0b100111, 0b011101, 0b101101, 0b110001, 0b110101, 0b101001, 0b011001, 0b111000,
+    0b111001, 0b100101, 0b010101, 0b110100, 0b001101, 0b101100, 0b011100, 0b010111,
+    0b011011, 0b100011, 0b010011, 0b110010, 0b001011, 0b101010, 0b011010, 0b111010,
+    0b110011, 0b100110, 0b010110, 0b110110, 0b001110, 0b101110, 0b011110, 0b101011,
+];
+
+/// fghj for D.x.0 to D.x.7 (primary) when the running disparity is negative
+const CODES_3B4B: [u16; 8] = [0b1011, 0b1001, 0b0101, 0b1100, 0b1101, 0b1010, 0b0110, 0b1110];
+
+/// The alternate D.x.7, used where the primary would make a run of five equal bits
+const CODE_A7: u16 = 0b0111;
+
+fn disparity(code: u16, width: u32) -> i32 {
+    2 * code.count_ones() as i32 - width as i32
+}
+
+/// The 10-bit code group of a data (`control` false) or control byte, and the running
+/// disparity after it, or None for control bytes 8b/10b doesn't define
+fn encode_8b10b(byte: u8, control: bool, negative: bool) -> Option<(u16, bool)> {
+    let (x, y) = ((byte & 0x1F) as usize, (byte >> 5) as usize);
+    if control && !(x == 28 || (y == 7 && matches!(x, 23 | 27 | 29 | 30))) {
+        return None;
+    }
+
+    let mut six = if control && x == 28 { 0b001111 } else { CODES_5B6B[x] };
+    // D.7 is balanced but still alternates with the running disparity
+    if !negative && (disparity(six, 6) != 0 || x == 7) {
+        six ^= 0b111111;
+    }
+    let negative = if disparity(six, 6) != 0 { !negative } else { negative };
+
+    let alternate = y == 7 && (control || (negative && matches!(x, 17 | 18 | 20)) || (!negative && matches!(x, 11 | 13 | 14)));
+    let mut four = if alternate { CODE_A7 } else { CODES_3B4B[y] };
+    if !negative && (disparity(four, 4) != 0 || y == 3) {
+        four ^= 0b1111;
+    }
+    // Control symbols invert the balanced codes after K.28, so commas can't appear in data
+    if control && negative && matches!(y, 1 | 2 | 5 | 6) {
+        four ^= 0b1111;
+    }
+    let negative = if disparity(four, 4) != 0 { !negative } else { negative };
+
+    Some(((six << 4) | four, negative))
+}
+
+/// Code group to symbol when the running disparity is negative (index 0) or positive (index 1)
+fn decode_tables() -> &'static [Vec<Option<Symbol>>; 2] {
+    static TABLES: OnceLock<[Vec<Option<Symbol>>; 2]> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        let mut tables = [vec![None; 1024], vec![None; 1024]];
+        for (table, negative) in tables.iter_mut().zip([true, false]) {
+            for control in [false, true] {
+                for byte in 0..=255u8 {
+                    if let Some((code, negative_after)) = encode_8b10b(byte, control, negative) {
+                        table[code as usize] = Some(Symbol { byte, negative_after });
+                    }
+                }
+            }
+        }
+        tables
+    })
+}
+
+fn decode_8b10b(bits: &BitSlice<u8, Msb0>) -> (BitVec<u8, Msb0>, Vec<LineCodeViolation>) {
+    let tables = decode_tables();
+    let mut output = BitVec::with_capacity(bits.len() / 10 * 8);
+    let mut violations = Vec::new();
+    let mut negative = true;
+
+    for group in bits.chunks_exact(10) {
+        let code = group.load_be::<u16>() as usize;
+        let (expected, other) = if negative { (&tables[0], &tables[1]) } else { (&tables[1], &tables[0]) };
+        let byte = match (expected[code], other[code]) {
+            (Some(symbol), _) => {
+                negative = symbol.negative_after;
+                symbol.byte
+            }
+            (None, Some(symbol)) => {
+                violations.push(LineCodeViolation { bit: output.len(), kind: ViolationKind::Disparity });
+                negative = symbol.negative_after;
+                symbol.byte
+            }
+            (None, None) => {
+                violations.push(LineCodeViolation { bit: output.len(), kind: ViolationKind::InvalidCode });
+                0
+            }
+        };
+        output.extend_from_bitslice(byte.view_bits::<Msb0>());
+    }
+    (output, violations)
+}
+
+const BLOCK_BITS: usize = 66;
+/// Blocks checked when looking for the sync header position
+const ALIGNMENT_BLOCKS: usize = 256;
+
+fn valid_header(bits: &BitSlice<u8, Msb0>) -> bool {
+    bits[0] != bits[1]
+}
+
+fn decode_64b66b(bits: &BitSlice<u8, Msb0>) -> (BitVec<u8, Msb0>, Vec<LineCodeViolation>) {
+    let offset = (0..BLOCK_BITS.min(bits.len()))
+        .max_by_key(|&offset| {
+            let valid = bits[offset..]
+                .chunks_exact(BLOCK_BITS)
+                .take(ALIGNMENT_BLOCKS)
+                .filter(|block| valid_header(block))
+                .count();
+            // Prefer the earliest offset among equals
+            (valid, std::cmp::Reverse(offset))
+        })
+        .unwrap_or(0);
+
+    let mut output = BitVec::with_capacity(bits.len() / BLOCK_BITS * 64);
+    let mut violations = Vec::new();
+    // The last 58 scrambled bits, most recent in bit 0
+    let mut state: u64 = 0;
+    for block in bits[offset..].chunks_exact(BLOCK_BITS) {
+        if !valid_header(block) {
+            violations.push(LineCodeViolation { bit: output.len(), kind: ViolationKind::SyncHeader });
+        }
+        for bit in block[2..].iter().by_vals() {
+            let descrambled = bit ^ ((state >> 38) & 1 == 1) ^ ((state >> 57) & 1 == 1);
+            state = ((state << 1) | bit as u64) & ((1 << 58) - 1);
+            output.push(descrambled);
+        }
+    }
+    (output, violations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_bytes(symbols: &[(u8, bool)]) -> BitVec<u8, Msb0> {
+        let mut bits = BitVec::new();
+        let mut negative = true;
+        for &(byte, control) in symbols {
+            let (code, after) = encode_8b10b(byte, control, negative).unwrap();
+            negative = after;
+            for i in (0..10).rev() {
+                bits.push((code >> i) & 1 == 1);
+            }
+        }
+        bits
+    }
+
+    fn code_group(six: u16, four: u16) -> u16 {
+        (six << 4) | four
+    }
+
+    #[test]
+    fn test_8b10b_reference_code_groups() {
+        // D.0.0 and K.28.5 from both running disparities
+        assert_eq!(encode_8b10b(0x00, false, true), Some((code_group(0b100111, 0b0100), true)));
+        assert_eq!(encode_8b10b(0xBC, true, true), Some((code_group(0b001111, 0b1010), false)));
+        assert_eq!(encode_8b10b(0xBC, true, false), Some((code_group(0b110000, 0b0101), true)));
+        // D.11.7 takes the alternate code after a positive disparity
+        assert_eq!(encode_8b10b(0xEB, false, false).map(|(code, _)| code), Some(code_group(0b110100, 0b1000)));
+        assert_eq!(encode_8b10b(0x00, true, true), None);
+    }
+
+    #[test]
+    fn test_8b10b_round_trip_and_violations() {
+        let symbols: Vec<(u8, bool)> = [(0xBC, true)].into_iter().chain((0..=255u8).map(|b| (b, false))).collect();
+        let encoded = encode_bytes(&symbols);
+        let (decoded, violations) = decode_line_code(LineCode::EightBTenB, &encoded);
+        let expected: Vec<u8> = symbols.iter().map(|&(byte, _)| byte).collect();
+        assert_eq!(decoded.as_raw_slice(), &expected[..]);
+        assert!(violations.is_empty());
+
+        // Repeating an unbalanced code group breaks the disparity; all ones is never valid
+        let mut bad = encode_bytes(&[(0x20, false)]);
+        bad.extend_from_bitslice(&bad.clone());
+        bad.extend_from_bitslice(bits![u8, Msb0; 1; 10]);
+        let (decoded, violations) = decode_line_code(LineCode::EightBTenB, &bad);
+        assert_eq!(decoded.as_raw_slice(), &[0x20, 0x20, 0x00]);
+        assert_eq!(
+            violations,
+            vec![
+                LineCodeViolation { bit: 8, kind: ViolationKind::Disparity },
+                LineCodeViolation { bit: 16, kind: ViolationKind::InvalidCode },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_64b66b_aligns_and_descrambles() {
+        let payload: Vec<u8> = (0..160u8).map(|i| i.wrapping_mul(37)).collect();
+        let mut encoded: BitVec<u8, Msb0> = bitvec![u8, Msb0; 1, 0, 1];
+        let mut state: u64 = 0;
+        for (index, block) in payload.view_bits::<Msb0>().chunks(64).enumerate() {
+            // The fourth block carries a broken header
+            let header = if index == 3 { [true, true] } else { [false, true] };
+            encoded.extend(header);
+            for bit in block.iter().by_vals() {
+                let scrambled = bit ^ ((state >> 38) & 1 == 1) ^ ((state >> 57) & 1 == 1);
+                state = ((state << 1) | scrambled as u64) & ((1 << 58) - 1);
+                encoded.push(scrambled);
+            }
+        }
+
+        let (decoded, violations) = decode_line_code(LineCode::SixtyFourBSixtySixB, &encoded);
+        assert_eq!(decoded.as_raw_slice(), &payload[..]);
+        assert_eq!(violations, vec![LineCodeViolation { bit: 192, kind: ViolationKind::SyncHeader }]);
+    }
+}
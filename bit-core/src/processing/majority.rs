@@ -0,0 +1,194 @@
+// Majority vote - decoding repetition-coded data, where each bit or frame is sent several
+// times and noise is outvoted by the copies
+
+use bitvec::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Which bits are copies of each other
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VoteGrouping {
+    /// Every `repeats` consecutive bits are copies of one bit
+    Bits { repeats: usize },
+    /// Blocks of `block_frames` frames of `frame_length` bits, each block sent `repeats` times
+    /// in a row before the next. With `block_frames` 1 every frame is repeated on its own;
+    /// otherwise frame i is voted with frames i + block_frames, i + 2 * block_frames, ...
+    Frames { frame_length: usize, repeats: usize, block_frames: usize },
+}
+
+/// The value chosen when the copies are evenly split
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TieBreak {
+    Zero,
+    One,
+    /// The value of the first copy
+    First,
+}
+
+impl TieBreak {
+    pub const ALL: [TieBreak; 3] = [TieBreak::Zero, TieBreak::One, TieBreak::First];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TieBreak::Zero => "0",
+            TieBreak::One => "1",
+            TieBreak::First => "First copy",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MajorityVote {
+    pub grouping: VoteGrouping,
+    pub tie_break: TieBreak,
+}
+
+impl MajorityVote {
+    /// Why the vote can't run as configured
+    pub fn validate(&self) -> Result<(), String> {
+        match self.grouping {
+            VoteGrouping::Bits { repeats } if repeats < 2 => Err("Repeats must be at least 2".to_string()),
+            VoteGrouping::Frames { repeats, .. } if repeats < 2 => Err("Repeats must be at least 2".to_string()),
+            VoteGrouping::Frames { frame_length: 0, .. } => Err("Frame length must be at least 1".to_string()),
+            VoteGrouping::Frames { block_frames: 0, .. } => Err("Frames per block must be at least 1".to_string()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Input bits that decode to one group of output
+    pub fn group_bits(&self) -> usize {
+        match self.grouping {
+            VoteGrouping::Bits { repeats } => repeats,
+            VoteGrouping::Frames { frame_length, repeats, block_frames } => frame_length * repeats * block_frames,
+        }
+    }
+
+    pub fn description(&self) -> String {
+        let grouping = match self.grouping {
+            VoteGrouping::Bits { repeats } => format!("every {} bits", repeats),
+            VoteGrouping::Frames { frame_length, repeats, block_frames: 1 } => {
+                format!("{} repeats of each {}-bit frame", repeats, frame_length)
+            }
+            VoteGrouping::Frames { frame_length, repeats, block_frames } => {
+                format!("{} repeats of {} {}-bit frames", repeats, block_frames, frame_length)
+            }
+        };
+        format!("Majority vote over {}, ties give {}", grouping, self.tie_break.name())
+    }
+
+    /// Copies per group and the bits in each copy, which is also the distance between copies
+    fn copies(&self) -> (usize, usize) {
+        match self.grouping {
+            VoteGrouping::Bits { repeats } => (repeats, 1),
+            VoteGrouping::Frames { frame_length, repeats, block_frames } => (repeats, frame_length * block_frames),
+        }
+    }
+
+    /// `bits` decoded; a trailing incomplete group is dropped
+    pub fn apply(&self, bits: &BitSlice<u8, Msb0>) -> BitVec<u8, Msb0> {
+        if self.validate().is_err() {
+            return bits.to_bitvec();
+        }
+        let (repeats, stride) = self.copies();
+        let group_bits = self.group_bits();
+        let mut output = BitVec::with_capacity(bits.len() / repeats);
+        for group in bits.chunks_exact(group_bits) {
+            for bit in 0..stride {
+                let ones = (0..repeats).filter(|&copy| group[copy * stride + bit]).count();
+                let value = match (ones * 2).cmp(&repeats) {
+                    std::cmp::Ordering::Greater => true,
+                    std::cmp::Ordering::Less => false,
+                    std::cmp::Ordering::Equal => match self.tie_break {
+                        TieBreak::Zero => false,
+                        TieBreak::One => true,
+                        TieBreak::First => group[bit],
+                    },
+                };
+                output.push(value);
+            }
+        }
+        output
+    }
+
+    /// Soft samples (positive for a 1) combined the same way, by adding the copies up so that
+    /// confident copies outweigh doubtful ones. A tie is a sum of exactly zero, broken with the
+    /// smallest sample of the chosen sign.
+    pub fn combine_soft(&self, samples: &[f32]) -> Vec<f32> {
+        if self.validate().is_err() {
+            return samples.to_vec();
+        }
+        let (repeats, stride) = self.copies();
+        let mut output = Vec::with_capacity(samples.len() / repeats);
+        for group in samples.chunks_exact(self.group_bits()) {
+            for bit in 0..stride {
+                let sum: f32 = (0..repeats).map(|copy| group[copy * stride + bit]).sum();
+                let value = if sum != 0.0 {
+                    sum
+                } else {
+                    match self.tie_break {
+                        TieBreak::Zero => -f32::MIN_POSITIVE,
+                        TieBreak::One => f32::MIN_POSITIVE,
+                        TieBreak::First if group[bit] > 0.0 => f32::MIN_POSITIVE,
+                        TieBreak::First => -f32::MIN_POSITIVE,
+                    }
+                };
+                output.push(value);
+            }
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bit_repeats_outvote_errors() {
+        let vote = MajorityVote { grouping: VoteGrouping::Bits { repeats: 3 }, tie_break: TieBreak::Zero };
+        // One flipped copy in each group, then an incomplete group
+        let input = bitvec![u8, Msb0; 1, 1, 0, 0, 1, 0, 1, 1, 1, 1];
+        assert_eq!(vote.apply(&input), bitvec![u8, Msb0; 1, 0, 1]);
+
+        let split = bitvec![u8, Msb0; 1, 0, 0, 1];
+        let tie = |tie_break| MajorityVote { grouping: VoteGrouping::Bits { repeats: 2 }, tie_break }.apply(&split);
+        assert_eq!(tie(TieBreak::Zero), bitvec![u8, Msb0; 0, 0]);
+        assert_eq!(tie(TieBreak::One), bitvec![u8, Msb0; 1, 1]);
+        assert_eq!(tie(TieBreak::First), bitvec![u8, Msb0; 1, 0]);
+    }
+
+    #[test]
+    fn test_frame_repeats() {
+        // Two 4-bit frames sent three times as a block: AB AB AB, with errors in single copies
+        let input = bitvec![u8, Msb0;
+            1, 0, 1, 0,  0, 0, 1, 1,
+            1, 1, 1, 0,  0, 0, 1, 1,
+            1, 0, 1, 0,  0, 1, 1, 1,
+        ];
+        let vote = MajorityVote {
+            grouping: VoteGrouping::Frames { frame_length: 4, repeats: 3, block_frames: 2 },
+            tie_break: TieBreak::First,
+        };
+        assert_eq!(vote.apply(&input), bitvec![u8, Msb0; 1, 0, 1, 0, 0, 0, 1, 1]);
+
+        // Each frame repeated on its own: AAA
+        let vote = MajorityVote {
+            grouping: VoteGrouping::Frames { frame_length: 8, repeats: 3, block_frames: 1 },
+            tie_break: TieBreak::First,
+        };
+        assert_eq!(vote.apply(&input), bitvec![u8, Msb0; 1, 0, 1, 0, 0, 0, 1, 1]);
+        assert!(MajorityVote { grouping: VoteGrouping::Bits { repeats: 1 }, tie_break: TieBreak::Zero }.validate().is_err());
+    }
+
+    #[test]
+    fn test_soft_copies_are_weighted() {
+        let vote = MajorityVote { grouping: VoteGrouping::Bits { repeats: 3 }, tie_break: TieBreak::One };
+        // Two weak zeros lose to one strong one; the trailing sample is dropped
+        let combined = vote.combine_soft(&[-0.1, -0.2, 0.9, -1.0, 0.5, -0.5, 0.3]);
+        assert_eq!(combined.len(), 2);
+        assert!((combined[0] - 0.6).abs() < 1e-6);
+        assert!(combined[1] < 0.0);
+
+        let tie = vote.combine_soft(&[0.5, -0.25, -0.25]);
+        assert!(tie[0] > 0.0 && tie[0] < 1e-30);
+    }
+}
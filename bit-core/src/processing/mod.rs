@@ -0,0 +1,33 @@
+// Processing module - bit manipulation operations
+
+pub mod cache;
+pub mod ecc;
+pub mod channels;
+pub mod interleaver;
+pub mod line_code;
+pub mod majority;
+pub mod nand;
+pub mod operations;
+pub mod progress;
+pub mod script;
+pub mod soft_bits;
+pub mod symbol_code;
+pub mod takeskip_presets;
+
+#[allow(unused_imports)]
+pub use operations::{parse_bit_pattern, parse_hex_key, parse_ranges_csv, track_through_chain, BitCondition, BitOperation, BitPatch, DeleteMode, Operation, OperationMacro, OperationSequence, ParamExpressions, RangeAction, WorksheetOperation};
+pub use interleaver::{
+    BlockInterleaverConfig, ConvolutionalInterleaverConfig,
+    InterleaverDirection, InterleaverType,
+};
+pub use cache::OperationCache;
+pub use channels::split_channels;
+pub use line_code::{decode_line_code, LineCode};
+pub use majority::{MajorityVote, TieBreak, VoteGrouping};
+pub use nand::{NandArea, NandEcc, NandLayout, NandReport, SpareLayout};
+#[allow(unused_imports)]
+pub use progress::{BackgroundTask, CancellationToken, ProgressReporter, ProgressState};
+pub use script::{run_script, BitScript, DEFAULT_SCRIPT_TIME_LIMIT_MS};
+pub use soft_bits::{SoftBits, SoftFormat};
+pub use symbol_code::{SymbolCode, SymbolMap};
+pub use takeskip_presets::{builtin_takeskip_presets, TakeSkipPreset};
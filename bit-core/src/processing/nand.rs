@@ -0,0 +1,378 @@
+// NAND flash dumps - raw pages of data followed by a spare (OOB) area, split apart again
+// and optionally checked or corrected with the ECC kept in the spare bytes
+
+use super::ecc::{hamming_correct, Bch, EccStatus, HAMMING_SECTORS};
+use bitvec::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Where each page keeps its spare bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpareLayout {
+    /// All the data bytes, then all the spare bytes
+    Adjacent,
+    /// The page in `steps` equal runs of data, each followed by its share of the spare, as
+    /// controllers with per-sector ECC write it
+    Interleaved { steps: usize },
+}
+
+/// The part of each page the operation keeps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NandArea {
+    Data,
+    Spare,
+}
+
+/// The code protecting each `sector` bytes of a page's data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NandEcc {
+    None,
+    /// 3 bytes per 256 or 512 byte sector, see `ecc::hamming_encode`
+    Hamming { sector: usize },
+    /// Binary BCH correcting `strength` bits per sector, see `ecc::Bch`
+    Bch { sector: usize, strength: usize },
+}
+
+impl NandEcc {
+    pub fn name(&self) -> String {
+        match self {
+            NandEcc::None => "no ECC".to_string(),
+            NandEcc::Hamming { sector } => format!("Hamming per {} bytes", sector),
+            NandEcc::Bch { sector, strength } => format!("BCH-{} per {} bytes", strength, sector),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NandLayout {
+    /// Data bytes per page
+    pub page_size: usize,
+    /// Spare bytes per page
+    pub spare_size: usize,
+    pub spare_layout: SpareLayout,
+    pub keep: NandArea,
+    pub ecc: NandEcc,
+    /// First ECC byte within the spare area, or within each step's share of it when the spare
+    /// is interleaved. The codes of consecutive sectors follow each other from there.
+    pub ecc_offset: usize,
+    /// Fix the bit errors the ECC can correct before keeping the data
+    pub correct: bool,
+}
+
+/// What checking the ECC of a dump found
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NandReport {
+    pub pages: usize,
+    pub sectors: usize,
+    /// Sectors whose data and code are all FF, as left by an erase
+    pub erased: usize,
+    /// (page, sector, bits) of each sector with correctable errors
+    pub corrected: Vec<(usize, usize, usize)>,
+    /// (page, sector) of each sector with more errors than the code corrects
+    pub uncorrectable: Vec<(usize, usize)>,
+}
+
+impl NandReport {
+    pub fn corrected_bits(&self) -> usize {
+        self.corrected.iter().map(|&(_, _, bits)| bits).sum()
+    }
+}
+
+/// The ECC of one layout, built once per run
+enum Codec {
+    None,
+    Hamming,
+    Bch(Box<Bch>),
+}
+
+impl Codec {
+    fn ecc_bytes(&self) -> usize {
+        match self {
+            Codec::None => 0,
+            Codec::Hamming => 3,
+            Codec::Bch(bch) => bch.ecc_bytes(),
+        }
+    }
+}
+
+impl NandLayout {
+    /// Why the layout can't be applied as configured
+    pub fn validate(&self) -> Result<(), String> {
+        if self.page_size == 0 {
+            return Err("Page size must be at least 1 byte".to_string());
+        }
+        let steps = self.steps();
+        if steps == 0 {
+            return Err("Interleaved steps must be at least 1".to_string());
+        }
+        if !self.page_size.is_multiple_of(steps) || !self.spare_size.is_multiple_of(steps) {
+            return Err(format!("Page and spare sizes must divide into {} steps", steps));
+        }
+        let sector = match self.ecc {
+            NandEcc::None => return Ok(()),
+            NandEcc::Hamming { sector } if !HAMMING_SECTORS.contains(&sector) => {
+                return Err("Hamming ECC covers 256 or 512 byte sectors".to_string());
+            }
+            NandEcc::Hamming { sector } => sector,
+            NandEcc::Bch { sector, strength } => {
+                Bch::new(sector, strength)?;
+                sector
+            }
+        };
+        let step_data = self.page_size / steps;
+        if !step_data.is_multiple_of(sector) {
+            return Err(format!("{} data bytes per step don't divide into {} byte sectors", step_data, sector));
+        }
+        let needed = self.ecc_offset + step_data / sector * self.ecc_bytes();
+        if needed > self.spare_size / steps {
+            return Err(format!("The ECC needs {} spare bytes per step, there are {}", needed, self.spare_size / steps));
+        }
+        Ok(())
+    }
+
+    /// Bytes of a page as stored, data and spare together
+    pub fn raw_page_size(&self) -> usize {
+        self.page_size + self.spare_size
+    }
+
+    fn steps(&self) -> usize {
+        match self.spare_layout {
+            SpareLayout::Adjacent => 1,
+            SpareLayout::Interleaved { steps } => steps,
+        }
+    }
+
+    fn sector(&self) -> usize {
+        match self.ecc {
+            NandEcc::None => self.page_size,
+            NandEcc::Hamming { sector } | NandEcc::Bch { sector, .. } => sector,
+        }
+    }
+
+    fn ecc_bytes(&self) -> usize {
+        match self.ecc {
+            NandEcc::None => 0,
+            NandEcc::Hamming { .. } => 3,
+            NandEcc::Bch { sector, strength } => Bch::new(sector, strength).map(|bch| bch.ecc_bytes()).unwrap_or(0),
+        }
+    }
+
+    pub fn description(&self) -> String {
+        let area = match self.keep {
+            NandArea::Data => "data",
+            NandArea::Spare => "spare",
+        };
+        let layout = match self.spare_layout {
+            SpareLayout::Adjacent => String::new(),
+            SpareLayout::Interleaved { steps } => format!(", spare interleaved in {} steps", steps),
+        };
+        let ecc = match (self.ecc, self.correct) {
+            (NandEcc::None, _) => String::new(),
+            (ecc, true) => format!(", corrected with {}", ecc.name()),
+            (ecc, false) => format!(", {} not applied", ecc.name()),
+        };
+        format!("Keep {} of {}+{} byte pages{}{}", area, self.page_size, self.spare_size, layout, ecc)
+    }
+
+    /// Data and spare bytes of one raw page; an interleaved spare comes back as the steps'
+    /// shares in order
+    fn split_page(&self, page: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        let steps = self.steps();
+        let step_data = self.page_size / steps;
+        let step_spare = self.spare_size / steps;
+        let mut data = Vec::with_capacity(self.page_size);
+        let mut spare = Vec::with_capacity(self.spare_size);
+        for step in page.chunks_exact(step_data + step_spare) {
+            data.extend_from_slice(&step[..step_data]);
+            spare.extend_from_slice(&step[step_data..]);
+        }
+        (data, spare)
+    }
+
+    /// Check every sector of a page against its code, correcting the data when asked
+    fn check_page(&self, codec: &Codec, index: usize, data: &mut [u8], spare: &[u8], report: &mut NandReport) {
+        if matches!(codec, Codec::None) {
+            return;
+        }
+        let sector_size = self.sector();
+        let ecc_bytes = codec.ecc_bytes();
+        let step_spare = self.spare_size / self.steps();
+        let sectors_per_step = self.page_size / self.steps() / sector_size;
+        let mut scratch = Vec::new();
+        for (sector, bytes) in data.chunks_exact_mut(sector_size).enumerate() {
+            let start = (sector / sectors_per_step) * step_spare + self.ecc_offset + (sector % sectors_per_step) * ecc_bytes;
+            let stored = &spare[start..start + ecc_bytes];
+            report.sectors += 1;
+            if bytes.iter().chain(stored).all(|&byte| byte == 0xFF) {
+                report.erased += 1;
+                continue;
+            }
+
+            // Correction happens on a copy unless the fixed data is kept
+            let target: &mut [u8] = if self.correct {
+                bytes
+            } else {
+                scratch.clear();
+                scratch.extend_from_slice(bytes);
+                &mut scratch
+            };
+            let status = match codec {
+                Codec::None => EccStatus::Clean,
+                Codec::Hamming => hamming_correct(target, stored),
+                Codec::Bch(bch) => bch.correct(target, stored),
+            };
+            match status {
+                EccStatus::Clean => {}
+                EccStatus::Corrected(bits) => report.corrected.push((index, sector, bits)),
+                EccStatus::Uncorrectable => report.uncorrectable.push((index, sector)),
+            }
+        }
+    }
+
+    fn codec(&self) -> Result<Codec, String> {
+        self.validate()?;
+        Ok(match self.ecc {
+            NandEcc::None => Codec::None,
+            NandEcc::Hamming { .. } => Codec::Hamming,
+            NandEcc::Bch { sector, strength } => Codec::Bch(Box::new(Bch::new(sector, strength)?)),
+        })
+    }
+
+    /// The kept area of every page and what the ECC found. A trailing partial page is dropped.
+    pub fn process(&self, bits: &BitSlice<u8, Msb0>) -> Result<(BitVec<u8, Msb0>, NandReport), String> {
+        let codec = self.codec()?;
+        let raw_bits = self.raw_page_size() * 8;
+        let mut report = NandReport::default();
+        let kept = match self.keep {
+            NandArea::Data => self.page_size,
+            NandArea::Spare => self.spare_size,
+        };
+        let mut output = BitVec::with_capacity(bits.len() / raw_bits * kept * 8);
+        for (index, raw) in bits.chunks_exact(raw_bits).enumerate() {
+            let page: Vec<u8> = raw.chunks(8).map(|byte| byte.load_be::<u8>()).collect();
+            let (mut data, spare) = self.split_page(&page);
+            self.check_page(&codec, index, &mut data, &spare, &mut report);
+            let area = match self.keep {
+                NandArea::Data => &data,
+                NandArea::Spare => &spare,
+            };
+            output.extend_from_raw_slice(area);
+            report.pages += 1;
+        }
+        Ok((output, report))
+    }
+
+    /// `bits` with only the kept area of each page; an invalid layout leaves them unchanged
+    pub fn apply(&self, bits: &BitSlice<u8, Msb0>) -> BitVec<u8, Msb0> {
+        match self.process(bits) {
+            Ok((output, _)) => output,
+            Err(_) => bits.to_bitvec(),
+        }
+    }
+
+    /// What the ECC finds in `bits`, without changing them
+    pub fn check(&self, bits: &BitSlice<u8, Msb0>) -> Result<NandReport, String> {
+        self.process(bits).map(|(_, report)| report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processing::ecc::hamming_encode;
+
+    fn layout(spare_layout: SpareLayout, keep: NandArea, ecc: NandEcc, ecc_offset: usize) -> NandLayout {
+        NandLayout { page_size: 512, spare_size: 16, spare_layout, keep, ecc, ecc_offset, correct: true }
+    }
+
+    fn data(page: usize) -> Vec<u8> {
+        (0..512).map(|i| (i * 7 + page * 13) as u8).collect()
+    }
+
+    #[test]
+    fn test_strip_and_isolate_spare() {
+        // Two adjacent pages and a partial third
+        let mut raw = Vec::new();
+        for page in 0..2 {
+            raw.extend(data(page));
+            raw.extend([page as u8; 16]);
+        }
+        raw.extend([0u8; 100]);
+        let bits = BitVec::<u8, Msb0>::from_vec(raw);
+
+        let strip = layout(SpareLayout::Adjacent, NandArea::Data, NandEcc::None, 0);
+        let stripped = strip.apply(&bits);
+        assert_eq!(stripped.as_raw_slice(), [data(0), data(1)].concat());
+
+        let isolate = NandLayout { keep: NandArea::Spare, ..strip };
+        assert_eq!(isolate.apply(&bits).as_raw_slice(), [[0u8; 16], [1u8; 16]].concat());
+
+        // Four 128 byte steps, each followed by 4 spare bytes
+        let page = data(0);
+        let mut raw = Vec::new();
+        for (step, chunk) in page.chunks(128).enumerate() {
+            raw.extend_from_slice(chunk);
+            raw.extend([step as u8 + 0xA0; 4]);
+        }
+        let bits = BitVec::<u8, Msb0>::from_vec(raw);
+        let interleaved = layout(SpareLayout::Interleaved { steps: 4 }, NandArea::Data, NandEcc::None, 0);
+        assert_eq!(interleaved.apply(&bits).as_raw_slice(), page);
+        let spare = NandLayout { keep: NandArea::Spare, ..interleaved };
+        assert_eq!(spare.apply(&bits).as_raw_slice()[4..8], [0xA1; 4]);
+
+        assert!(layout(SpareLayout::Interleaved { steps: 3 }, NandArea::Data, NandEcc::None, 0).validate().is_err());
+    }
+
+    #[test]
+    fn test_hamming_pages_are_corrected() {
+        // Two 256 byte sectors, their codes at spare bytes 8..11 and 11..14
+        let hamming = layout(SpareLayout::Adjacent, NandArea::Data, NandEcc::Hamming { sector: 256 }, 8);
+        let page = data(0);
+        let mut spare = vec![0xFF; 16];
+        spare[8..11].copy_from_slice(&hamming_encode(&page[..256]));
+        spare[11..14].copy_from_slice(&hamming_encode(&page[256..]));
+        let mut raw = [page.clone(), spare].concat();
+        raw[300] ^= 0x08;
+        // An erased page follows
+        raw.extend([0xFF; 528]);
+        let bits = BitVec::<u8, Msb0>::from_vec(raw);
+
+        let (output, report) = hamming.process(&bits).unwrap();
+        assert_eq!(output.as_raw_slice()[..512], page);
+        assert_eq!(report.pages, 2);
+        assert_eq!(report.sectors, 4);
+        assert_eq!(report.erased, 2);
+        assert_eq!(report.corrected, vec![(0, 1, 1)]);
+        assert!(report.uncorrectable.is_empty());
+
+        // Checking alone reports the error but keeps it
+        let unchanged = NandLayout { correct: false, ..hamming }.apply(&bits);
+        assert_eq!(unchanged.as_raw_slice()[300], page[300] ^ 0x08);
+    }
+
+    #[test]
+    fn test_bch_interleaved_spare() {
+        // Each 256 byte step followed by 8 spare bytes, of which 2.. hold its BCH-2 code
+        let ecc = NandEcc::Bch { sector: 256, strength: 2 };
+        let bch = Bch::new(256, 2).unwrap();
+        let bch_layout = layout(SpareLayout::Interleaved { steps: 2 }, NandArea::Data, ecc, 2);
+        assert!(bch_layout.validate().is_ok());
+        let page = data(3);
+        let mut raw = Vec::new();
+        for chunk in page.chunks(256) {
+            raw.extend_from_slice(chunk);
+            let mut share = vec![0xFF; 8];
+            share[2..2 + bch.ecc_bytes()].copy_from_slice(&bch.encode(chunk));
+            raw.extend(share);
+        }
+        raw[10] ^= 0x81;
+        raw[264 + 50] ^= 0x01;
+        raw[264 + 51] ^= 0x10;
+        raw[264 + 52] ^= 0x02;
+        let bits = BitVec::<u8, Msb0>::from_vec(raw);
+
+        let (output, report) = bch_layout.process(&bits).unwrap();
+        assert_eq!(report.corrected, vec![(0, 0, 2)]);
+        assert_eq!(report.uncorrectable, vec![(0, 1)]);
+        assert_eq!(output.as_raw_slice()[..256], page[..256]);
+    }
+}
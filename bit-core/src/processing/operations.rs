@@ -0,0 +1,1643 @@
+use bitvec::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use crate::core::OperationType;
+use crate::utils::eval_expression_with;
+use super::interleaver::{BlockInterleaverConfig, ConvolutionalInterleaverConfig, SymbolInterleaverConfig, InterleaverType};
+use super::line_code::{decode_line_code, LineCode};
+use super::script::run_script;
+use super::majority::MajorityVote;
+use super::nand::NandLayout;
+use super::soft_bits::SoftFormat;
+use super::symbol_code::{convert_symbols, SymbolCode, SymbolMap};
+
+/// Target number of input bits per chunk when applying an operation incrementally
+pub const APPLY_CHUNK_BITS: usize = 8 * 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Operation {
+    Take(usize),
+    Reverse(usize),
+    Invert(usize),
+    Skip(usize),
+}
+
+impl Operation {
+    pub fn to_string(&self) -> String {
+        match self {
+            Operation::Take(n) => format!("t{}", n),
+            Operation::Reverse(n) => format!("r{}", n),
+            Operation::Invert(n) => format!("i{}", n),
+            Operation::Skip(n) => format!("s{}", n),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperationSequence {
+    pub operations: Vec<Operation>,
+}
+
+// Represents a complete operation that can be applied to bits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum BitOperation {
+    LoadFile {
+        name: String,
+        file_path: PathBuf,
+        /// First byte of the file to load
+        #[serde(default)]
+        offset: u64,
+        /// Bytes to load from `offset`, or the rest of the file
+        #[serde(default)]
+        length: Option<u64>,
+        enabled: bool,
+    },
+    /// Several files appended in order, for captures split into numbered chunks
+    MultiFileLoad {
+        name: String,
+        file_paths: Vec<PathBuf>,
+        enabled: bool,
+    },
+    /// A file of soft samples, one per bit, sliced to bits; see `soft_bits.rs`
+    LoadSoftBits {
+        name: String,
+        file_path: PathBuf,
+        format: SoftFormat,
+        /// Repeated copies added up before slicing, a soft majority vote
+        #[serde(default)]
+        combine: Option<MajorityVote>,
+        enabled: bool,
+    },
+    TakeSkipSequence {
+        name: String,
+        sequence: OperationSequence,
+        enabled: bool,
+    },
+    InvertBits {
+        name: String,
+        enabled: bool,
+    },
+    MultiWorksheetLoad {
+        name: String,
+        worksheet_operations: Vec<WorksheetOperation>,
+        enabled: bool,
+    },
+    TruncateBits {
+        name: String,
+        start: usize,
+        end: usize,
+        /// `start` and `end` as expressions of the input
+        #[serde(default, skip_serializing_if = "ParamExpressions::is_empty")]
+        exprs: ParamExpressions,
+        enabled: bool,
+    },
+    InterleaveBits {
+        name: String,
+        interleaver_type: InterleaverType,
+        block_config: Option<BlockInterleaverConfig>,
+        convolutional_config: Option<ConvolutionalInterleaverConfig>,
+        symbol_config: Option<SymbolInterleaverConfig>,
+        enabled: bool,
+    },
+    /// A saved chain of operations applied as a single step
+    Macro {
+        name: String,
+        operations: Vec<BitOperation>,
+        enabled: bool,
+    },
+    /// Operations organized under one name in the Active Operations panel; applied in order
+    /// and enabled, disabled or moved as a unit
+    Group {
+        name: String,
+        operations: Vec<BitOperation>,
+        /// Children hidden in the panel
+        #[serde(default)]
+        collapsed: bool,
+        enabled: bool,
+    },
+    /// Insert `count` bits at `position`, repeating the 0/1 pattern in `fill`
+    InsertBits {
+        name: String,
+        position: usize,
+        count: usize,
+        fill: String,
+        /// `position` and `count` as expressions of the input
+        #[serde(default, skip_serializing_if = "ParamExpressions::is_empty")]
+        exprs: ParamExpressions,
+        enabled: bool,
+    },
+    DeleteBits {
+        name: String,
+        mode: DeleteMode,
+        /// `position` and `count` of a range delete as expressions of the input
+        #[serde(default, skip_serializing_if = "ParamExpressions::is_empty")]
+        exprs: ParamExpressions,
+        enabled: bool,
+    },
+    /// Invert or reverse bits `start..end` in place, leaving the rest untouched;
+    /// `end` is clamped to the data, so usize::MAX means "to the end"
+    RangeBits {
+        name: String,
+        action: RangeAction,
+        start: usize,
+        end: usize,
+        /// `start` and `end` as expressions of the input
+        #[serde(default, skip_serializing_if = "ParamExpressions::is_empty")]
+        exprs: ParamExpressions,
+        enabled: bool,
+    },
+    /// Bits overwritten by hand, applied in order so later edits win
+    PatchBits {
+        name: String,
+        patches: Vec<BitPatch>,
+        enabled: bool,
+    },
+    /// XOR every byte with a repeating key, starting at the first bit
+    XorKey {
+        name: String,
+        key: Vec<u8>,
+        enabled: bool,
+    },
+    /// Decode a line-coded stream back to its data, see `line_code.rs`
+    LineDecode {
+        name: String,
+        code: LineCode,
+        enabled: bool,
+    },
+    /// Gray or BCD conversion of each `symbol_bits`-bit symbol, see `symbol_code.rs`
+    SymbolConvert {
+        name: String,
+        code: SymbolCode,
+        symbol_bits: usize,
+        enabled: bool,
+    },
+    /// Each symbol replaced through a lookup table, see `SymbolMap`
+    SymbolRemap {
+        name: String,
+        map: SymbolMap,
+        enabled: bool,
+    },
+    /// Repetition decoding, each group of copies replaced by its majority, see `majority.rs`
+    MajorityVote {
+        name: String,
+        vote: MajorityVote,
+        enabled: bool,
+    },
+    /// Raw NAND pages split into data and spare, keeping one, see `nand.rs`
+    NandLayout {
+        name: String,
+        layout: NandLayout,
+        enabled: bool,
+    },
+    /// `operation` applied separately to each part of the data `condition` selects;
+    /// everything else passes through unchanged
+    Conditional {
+        name: String,
+        operation: Box<BitOperation>,
+        condition: BitCondition,
+        enabled: bool,
+    },
+    /// Two versions of the same step, A and B; only the selected one is applied, so
+    /// parameter choices can be compared with one toggle
+    Variants {
+        name: String,
+        a: Box<BitOperation>,
+        b: Box<BitOperation>,
+        use_b: bool,
+        enabled: bool,
+    },
+    /// A user script run over the whole buffer, see `script.rs` for the language
+    Script {
+        name: String,
+        source: String,
+        time_limit_ms: u64,
+        enabled: bool,
+    },
+    // Future operations:
+    // FindPattern { name: String, pattern: String, highlight: bool },
+    // Replace { name: String, from_pattern: String, to_pattern: String },
+    // etc.
+}
+
+/// Represents a take/skip operation to apply to a specific worksheet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorksheetOperation {
+    /// Stable id of the source worksheet
+    #[serde(default)]
+    pub worksheet_id: u64,
+    /// Name of the source worksheet when the operation was saved; used when the id
+    /// isn't found (e.g. a worksheet file opened in another session) and in messages
+    #[serde(default)]
+    pub worksheet_name: String,
+    pub sequence: OperationSequence,
+}
+
+/// Operation parameters written as expressions such as `len-1024` or `frame_len*10`,
+/// evaluated against each input so they follow the data when its size changes.
+///
+/// `len` is the number of input bits, `frame_len` the frame length the operation was saved
+/// with, and `frames` the number of whole frames in the input.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ParamExpressions {
+    /// Expression as typed, by parameter name
+    pub exprs: BTreeMap<String, String>,
+    pub frame_len: usize,
+}
+
+impl ParamExpressions {
+    pub fn is_empty(&self) -> bool {
+        self.exprs.is_empty()
+    }
+
+    /// Variables an expression may use, for an input of `len` bits
+    pub fn variables(len: usize, frame_len: usize) -> [(&'static str, usize); 3] {
+        [("len", len), ("frame_len", frame_len), ("frames", len.checked_div(frame_len).unwrap_or(0))]
+    }
+
+    /// `param` evaluated for an input of `len` bits, `fixed` if it isn't an expression;
+    /// None when its expression doesn't evaluate for this input (e.g. `len-1024` on a
+    /// shorter one), since the saved number was worked out for different data
+    pub fn resolve(&self, param: &str, fixed: usize, len: usize) -> Option<usize> {
+        match self.exprs.get(param) {
+            Some(expr) => eval_expression_with(expr, &Self::variables(len, self.frame_len)).ok(),
+            None => Some(fixed),
+        }
+    }
+
+    /// Why the expressions can't be used for an input of `len` bits, if any of them fails
+    pub fn error(&self, len: usize) -> Option<String> {
+        self.exprs.iter().find_map(|(param, expr)| {
+            eval_expression_with(expr, &Self::variables(len, self.frame_len))
+                .err()
+                .map(|e| format!("{param} = {expr} doesn't evaluate for {len} input bits: {e}"))
+        })
+    }
+
+    /// How `param` reads in a description
+    pub fn display(&self, param: &str, fixed: usize) -> String {
+        self.exprs.get(param).cloned().unwrap_or_else(|| fixed.to_string())
+    }
+}
+
+/// Which bits a DeleteBits operation removes
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DeleteMode {
+    /// `count` bits starting at `position`
+    Range { position: usize, count: usize },
+    /// Bit-stuffing removal: the 0 inserted after every `run_length` consecutive 1s
+    /// (5 for HDLC); longer runs such as flags pass through unchanged
+    Unstuff { run_length: usize },
+}
+
+impl DeleteMode {
+    pub fn apply_in_place(&self, bits: &mut BitVec<u8, Msb0>) {
+        match *self {
+            DeleteMode::Range { position, count } => {
+                let len = bits.len();
+                let start = position.min(len);
+                let end = position.saturating_add(count).min(len);
+                if end < len {
+                    bits.copy_within(end..len, start);
+                }
+                bits.truncate(len - (end - start));
+            }
+            DeleteMode::Unstuff { run_length } => {
+                if run_length == 0 {
+                    return;
+                }
+                let len = bits.len();
+                let mut write_pos = 0;
+                let mut ones = 0;
+                for read_pos in 0..len {
+                    let bit = bits[read_pos];
+                    if !bit && ones == run_length {
+                        // Stuffed bit
+                        ones = 0;
+                        continue;
+                    }
+                    ones = if bit { ones + 1 } else { 0 };
+                    bits.set(write_pos, bit);
+                    write_pos += 1;
+                }
+                bits.truncate(write_pos);
+            }
+        }
+    }
+}
+
+/// What a RangeBits operation does to its range
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RangeAction {
+    Invert,
+    Reverse,
+}
+
+impl RangeAction {
+    pub fn apply_in_place(&self, bits: &mut BitVec<u8, Msb0>, start: usize, end: usize) {
+        let len = bits.len();
+        let (start, end) = (start.min(len), end.min(len));
+        if start >= end {
+            return;
+        }
+        match self {
+            RangeAction::Invert => invert_in_place(&mut bits[start..end]),
+            RangeAction::Reverse => bits[start..end].reverse(),
+        }
+    }
+}
+
+/// Which parts of the data a Conditional operation applies its child to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BitCondition {
+    /// Frames `offset`, `offset + every`, `offset + 2 * every`, ... of `frame_length` bits
+    EveryNthFrame { frame_length: usize, every: usize, offset: usize },
+    /// Explicit bit ranges as (start, end), end exclusive
+    Ranges { ranges: Vec<(usize, usize)> },
+}
+
+impl BitCondition {
+    /// Selected ranges of data `len` bits long, sorted and non-overlapping
+    pub fn segments(&self, len: usize) -> Vec<std::ops::Range<usize>> {
+        match self {
+            BitCondition::EveryNthFrame { frame_length, every, offset } => {
+                if *frame_length == 0 || *every == 0 {
+                    return Vec::new();
+                }
+                let frames = len.div_ceil(*frame_length);
+                (*offset..frames)
+                    .step_by(*every)
+                    .map(|frame| frame * frame_length..((frame + 1) * frame_length).min(len))
+                    .collect()
+            }
+            BitCondition::Ranges { ranges } => {
+                let mut clamped: Vec<std::ops::Range<usize>> = ranges
+                    .iter()
+                    .map(|&(start, end)| start.min(len)..end.min(len))
+                    .filter(|range| !range.is_empty())
+                    .collect();
+                clamped.sort_by_key(|range| range.start);
+                // Overlapping ranges are merged so no bit is processed twice
+                let mut merged: Vec<std::ops::Range<usize>> = Vec::with_capacity(clamped.len());
+                for range in clamped {
+                    match merged.last_mut() {
+                        Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                        _ => merged.push(range),
+                    }
+                }
+                merged
+            }
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            BitCondition::EveryNthFrame { frame_length, every, offset } => {
+                format!("every {} of {}-bit frames from frame {}", every, frame_length, offset)
+            }
+            BitCondition::Ranges { ranges } => format!("{} range(s)", ranges.len()),
+        }
+    }
+}
+
+/// Bit ranges from CSV text with one `start,end` pair per line; blank lines, `#` comments
+/// and a non-numeric header line are skipped. Values accept math expressions.
+pub fn parse_ranges_csv(text: &str) -> Result<Vec<(usize, usize)>, String> {
+    use crate::utils::eval_expression;
+
+    let mut ranges = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split([',', ';', '\t']).map(str::trim).collect();
+        let parsed = match fields.as_slice() {
+            [start, end, ..] => eval_expression(start).and_then(|start| Ok((start, eval_expression(end)?))),
+            _ => Err("expected start,end".to_string()),
+        };
+        match parsed {
+            Ok((start, end)) if start < end => ranges.push((start, end)),
+            Ok(_) => return Err(format!("Line {}: start must be less than end", index + 1)),
+            // A header row names the columns
+            Err(_) if ranges.is_empty() && index == 0 => {}
+            Err(e) => return Err(format!("Line {}: {}", index + 1, e)),
+        }
+    }
+    Ok(ranges)
+}
+
+/// Bits from a 0/1 pattern string, for the InsertBits fill
+pub fn parse_bit_pattern(pattern: &str) -> Result<Vec<bool>, String> {
+    let bits: Vec<bool> = pattern
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| match c {
+            '0' => Ok(false),
+            '1' => Ok(true),
+            _ => Err(format!("Invalid character '{}' in fill pattern; use 0 and 1", c)),
+        })
+        .collect::<Result<_, _>>()?;
+    if bits.is_empty() {
+        return Err("Fill pattern cannot be empty".to_string());
+    }
+    Ok(bits)
+}
+
+/// Key bytes from hex digits, with an optional 0x prefix and spaces between bytes
+pub fn parse_hex_key(text: &str) -> Result<Vec<u8>, String> {
+    let text = text.trim();
+    let digits: String = text
+        .strip_prefix("0x")
+        .or_else(|| text.strip_prefix("0X"))
+        .unwrap_or(text)
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    if digits.is_empty() {
+        return Err("Key cannot be empty".to_string());
+    }
+    if !digits.len().is_multiple_of(2) {
+        return Err("Key must be a whole number of bytes (two hex digits each)".to_string());
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&digits[i..i + 2], 16).map_err(|_| format!("Invalid hex byte '{}'", &digits[i..i + 2])))
+        .collect()
+}
+
+/// Overwrite `len` bits (at most 64) starting at `position` with the low bits of `value`, MSB first
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "BitPatchFields")]
+pub struct BitPatch {
+    pub position: usize,
+    pub len: usize,
+    pub value: u64,
+}
+
+/// A patch as saved, checked before it becomes a `BitPatch`
+#[derive(Deserialize)]
+struct BitPatchFields {
+    position: usize,
+    len: usize,
+    value: u64,
+}
+
+impl TryFrom<BitPatchFields> for BitPatch {
+    type Error = String;
+
+    fn try_from(fields: BitPatchFields) -> Result<Self, String> {
+        if fields.len > 64 {
+            return Err(format!("Bit edit at {} is {} bits long, at most 64 are allowed", fields.position, fields.len));
+        }
+        Ok(BitPatch { position: fields.position, len: fields.len, value: fields.value })
+    }
+}
+
+impl BitPatch {
+    pub fn bit(position: usize, value: bool) -> Self {
+        Self { position, len: 1, value: value as u64 }
+    }
+
+    pub fn byte(byte_index: usize, value: u8) -> Self {
+        Self { position: byte_index * 8, len: 8, value: value as u64 }
+    }
+
+    pub fn apply_in_place(&self, bits: &mut BitSlice<u8, Msb0>) {
+        // Longer patches can't be loaded; one built by hand is ignored rather than shifted past 64 bits
+        if self.len > 64 {
+            return;
+        }
+        for i in 0..self.len {
+            if let Some(mut bit) = bits.get_mut(self.position + i) {
+                *bit = (self.value >> (self.len - 1 - i)) & 1 == 1;
+            }
+        }
+    }
+}
+
+/// A named, reusable chain of operations kept in the macro library
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperationMacro {
+    pub name: String,
+    pub operations: Vec<BitOperation>,
+}
+
+impl OperationMacro {
+    /// An operation applying the whole chain as one step
+    pub fn to_operation(&self) -> BitOperation {
+        BitOperation::Macro {
+            name: self.name.clone(),
+            operations: self.operations.clone(),
+            enabled: true,
+        }
+    }
+}
+
+impl BitOperation {
+    pub fn name(&self) -> &str {
+        match self {
+            BitOperation::LoadFile { name, .. } => name,
+            BitOperation::MultiFileLoad { name, .. } => name,
+            BitOperation::LoadSoftBits { name, .. } => name,
+            BitOperation::TakeSkipSequence { name, .. } => name,
+            BitOperation::InvertBits { name, .. } => name,
+            BitOperation::MultiWorksheetLoad { name, .. } => name,
+            BitOperation::TruncateBits { name, .. } => name,
+            BitOperation::InterleaveBits { name, .. } => name,
+            BitOperation::Macro { name, .. } => name,
+            BitOperation::Group { name, .. } => name,
+            BitOperation::PatchBits { name, .. } => name,
+            BitOperation::InsertBits { name, .. } => name,
+            BitOperation::DeleteBits { name, .. } => name,
+            BitOperation::RangeBits { name, .. } => name,
+            BitOperation::Conditional { name, .. } => name,
+            BitOperation::XorKey { name, .. } => name,
+            BitOperation::LineDecode { name, .. } => name,
+            BitOperation::SymbolConvert { name, .. } => name,
+            BitOperation::SymbolRemap { name, .. } => name,
+            BitOperation::NandLayout { name, .. } => name,
+            BitOperation::MajorityVote { name, .. } => name,
+            BitOperation::Variants { name, .. } => name,
+            BitOperation::Script { name, .. } => name,
+        }
+    }
+    
+    pub fn is_enabled(&self) -> bool {
+        match self {
+            BitOperation::LoadFile { enabled, .. } => *enabled,
+            BitOperation::MultiFileLoad { enabled, .. } => *enabled,
+            BitOperation::LoadSoftBits { enabled, .. } => *enabled,
+            BitOperation::TakeSkipSequence { enabled, .. } => *enabled,
+            BitOperation::InvertBits { enabled, .. } => *enabled,
+            BitOperation::MultiWorksheetLoad { enabled, .. } => *enabled,
+            BitOperation::TruncateBits { enabled, .. } => *enabled,
+            BitOperation::InterleaveBits { enabled, .. } => *enabled,
+            BitOperation::Macro { enabled, .. } => *enabled,
+            BitOperation::Group { enabled, .. } => *enabled,
+            BitOperation::PatchBits { enabled, .. } => *enabled,
+            BitOperation::InsertBits { enabled, .. } => *enabled,
+            BitOperation::DeleteBits { enabled, .. } => *enabled,
+            BitOperation::RangeBits { enabled, .. } => *enabled,
+            BitOperation::Conditional { enabled, .. } => *enabled,
+            BitOperation::XorKey { enabled, .. } => *enabled,
+            BitOperation::LineDecode { enabled, .. } => *enabled,
+            BitOperation::SymbolConvert { enabled, .. } => *enabled,
+            BitOperation::SymbolRemap { enabled, .. } => *enabled,
+            BitOperation::NandLayout { enabled, .. } => *enabled,
+            BitOperation::MajorityVote { enabled, .. } => *enabled,
+            BitOperation::Variants { enabled, .. } => *enabled,
+            BitOperation::Script { enabled, .. } => *enabled,
+        }
+    }
+    
+    pub fn set_enabled(&mut self, new_enabled: bool) {
+        match self {
+            BitOperation::LoadFile { enabled, .. } => *enabled = new_enabled,
+            BitOperation::MultiFileLoad { enabled, .. } => *enabled = new_enabled,
+            BitOperation::LoadSoftBits { enabled, .. } => *enabled = new_enabled,
+            BitOperation::TakeSkipSequence { enabled, .. } => *enabled = new_enabled,
+            BitOperation::InvertBits { enabled, .. } => *enabled = new_enabled,
+            BitOperation::MultiWorksheetLoad { enabled, .. } => *enabled = new_enabled,
+            BitOperation::TruncateBits { enabled, .. } => *enabled = new_enabled,
+            BitOperation::InterleaveBits { enabled, .. } => *enabled = new_enabled,
+            BitOperation::Macro { enabled, .. } => *enabled = new_enabled,
+            BitOperation::Group { enabled, .. } => *enabled = new_enabled,
+            BitOperation::PatchBits { enabled, .. } => *enabled = new_enabled,
+            BitOperation::InsertBits { enabled, .. } => *enabled = new_enabled,
+            BitOperation::DeleteBits { enabled, .. } => *enabled = new_enabled,
+            BitOperation::RangeBits { enabled, .. } => *enabled = new_enabled,
+            BitOperation::Conditional { enabled, .. } => *enabled = new_enabled,
+            BitOperation::XorKey { enabled, .. } => *enabled = new_enabled,
+            BitOperation::LineDecode { enabled, .. } => *enabled = new_enabled,
+            BitOperation::SymbolConvert { enabled, .. } => *enabled = new_enabled,
+            BitOperation::SymbolRemap { enabled, .. } => *enabled = new_enabled,
+            BitOperation::NandLayout { enabled, .. } => *enabled = new_enabled,
+            BitOperation::MajorityVote { enabled, .. } => *enabled = new_enabled,
+            BitOperation::Variants { enabled, .. } => *enabled = new_enabled,
+            BitOperation::Script { enabled, .. } => *enabled = new_enabled,
+        }
+    }
+    
+    /// Call `f` on every file path the operation reads, including inside macros, groups
+    /// and conditionals
+    pub fn for_each_file_path_mut(&mut self, f: &mut dyn FnMut(&mut PathBuf)) {
+        match self {
+            BitOperation::LoadFile { file_path, .. } | BitOperation::LoadSoftBits { file_path, .. } => f(file_path),
+            BitOperation::MultiFileLoad { file_paths, .. } => file_paths.iter_mut().for_each(f),
+            BitOperation::Macro { operations, .. } | BitOperation::Group { operations, .. } => {
+                for op in operations {
+                    op.for_each_file_path_mut(f);
+                }
+            }
+            BitOperation::Conditional { operation, .. } => operation.for_each_file_path_mut(f),
+            _ => {}
+        }
+    }
+
+    /// The kind of operation, as listed under Available Operations
+    pub fn operation_type(&self) -> OperationType {
+        match self {
+            BitOperation::LoadFile { .. } => OperationType::LoadFile,
+            BitOperation::MultiFileLoad { .. } => OperationType::MultiFileLoad,
+            BitOperation::LoadSoftBits { .. } => OperationType::LoadSoftBits,
+            BitOperation::TakeSkipSequence { .. } => OperationType::TakeSkipSequence,
+            BitOperation::InvertBits { .. } => OperationType::InvertBits,
+            BitOperation::MultiWorksheetLoad { .. } => OperationType::MultiWorksheetLoad,
+            BitOperation::TruncateBits { .. } => OperationType::TruncateBits,
+            BitOperation::InterleaveBits { .. } => OperationType::InterleaveBits,
+            BitOperation::Macro { .. } => OperationType::Macro,
+            BitOperation::Group { .. } => OperationType::Group,
+            BitOperation::InsertBits { .. } => OperationType::InsertBits,
+            BitOperation::DeleteBits { .. } => OperationType::DeleteBits,
+            BitOperation::RangeBits { .. } => OperationType::RangeBits,
+            BitOperation::PatchBits { .. } => OperationType::PatchBits,
+            BitOperation::XorKey { .. } => OperationType::XorKey,
+            BitOperation::LineDecode { .. } => OperationType::LineDecode,
+            BitOperation::SymbolConvert { .. } => OperationType::SymbolConvert,
+            BitOperation::SymbolRemap { .. } => OperationType::SymbolRemap,
+            BitOperation::NandLayout { .. } => OperationType::NandLayout,
+            BitOperation::MajorityVote { .. } => OperationType::MajorityVote,
+            BitOperation::Conditional { .. } => OperationType::Conditional,
+            BitOperation::Variants { .. } => OperationType::Variants,
+            BitOperation::Script { .. } => OperationType::Script,
+        }
+    }
+
+    /// Whether the name, type or description contains `filter`, ignoring case; a group or
+    /// macro also matches when one of its steps does. An empty filter matches everything.
+    pub fn matches_filter(&self, filter: &str) -> bool {
+        let filter = filter.trim().to_lowercase();
+        filter.is_empty() || self.matches_lowercase(&filter)
+    }
+
+    fn matches_lowercase(&self, filter: &str) -> bool {
+        if self.name().to_lowercase().contains(filter)
+            || self.operation_type().name().to_lowercase().contains(filter)
+            || self.description().to_lowercase().contains(filter)
+        {
+            return true;
+        }
+        match self {
+            BitOperation::Macro { operations, .. } | BitOperation::Group { operations, .. } => {
+                operations.iter().any(|op| op.matches_lowercase(filter))
+            }
+            BitOperation::Conditional { operation, .. } => operation.matches_lowercase(filter),
+            BitOperation::Variants { a, b, .. } => a.matches_lowercase(filter) || b.matches_lowercase(filter),
+            _ => false,
+        }
+    }
+
+    /// The selected version of a Variants operation
+    pub fn active_variant(&self) -> Option<&BitOperation> {
+        match self {
+            BitOperation::Variants { a, b, use_b, .. } => Some(if *use_b { b } else { a }),
+            _ => None,
+        }
+    }
+
+    /// Whether the operation produces bits from a file or worksheet rather than transforming its input
+    pub fn is_source(&self) -> bool {
+        matches!(
+            self,
+            BitOperation::LoadFile { .. }
+                | BitOperation::MultiFileLoad { .. }
+                | BitOperation::LoadSoftBits { .. }
+                | BitOperation::MultiWorksheetLoad { .. }
+        )
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            BitOperation::LoadFile { file_path, offset, length, .. } => {
+                let file_name = file_path.file_name().unwrap_or_default().to_string_lossy();
+                match (offset, length) {
+                    (0, None) => format!("Load: {}", file_name),
+                    (offset, None) => format!("Load: {} from byte {}", file_name, offset),
+                    (offset, Some(length)) => format!("Load: {} bytes {}..{}", file_name, offset, offset + length),
+                }
+            }
+            BitOperation::MultiFileLoad { file_paths, .. } => match file_paths.as_slice() {
+                [first, .., last] => format!(
+                    "Load {} files: {} … {}",
+                    file_paths.len(),
+                    first.file_name().unwrap_or_default().to_string_lossy(),
+                    last.file_name().unwrap_or_default().to_string_lossy()
+                ),
+                _ => format!("Load {} file(s)", file_paths.len()),
+            },
+            BitOperation::LoadSoftBits { file_path, format, combine, .. } => {
+                let file_name = file_path.file_name().unwrap_or_default().to_string_lossy();
+                match combine {
+                    Some(vote) => format!("Load soft: {} as {}, {}", file_name, format.name(), vote.description()),
+                    None => format!("Load soft: {} as {}", file_name, format.name()),
+                }
+            }
+            BitOperation::TakeSkipSequence { sequence, .. } => sequence.to_string(),
+            BitOperation::InvertBits { .. } => "Inverts all bits".to_string(),
+            BitOperation::MultiWorksheetLoad { worksheet_operations, .. } => {
+                format!("Load from {} worksheet(s)", worksheet_operations.len())
+            }
+            BitOperation::TruncateBits { start, end, exprs, .. } => {
+                format!("Keep bits {}-{}", exprs.display("start", *start), exprs.display("end", *end))
+            }
+            BitOperation::InterleaveBits { interleaver_type, block_config, convolutional_config, symbol_config, .. } => {
+                match interleaver_type {
+                    InterleaverType::Block => {
+                        if let Some(cfg) = block_config {
+                            let dir = match cfg.direction {
+                                crate::processing::InterleaverDirection::Interleave => "Interleave",
+                                crate::processing::InterleaverDirection::Deinterleave => "Deinterleave",
+                            };
+                            format!("Block {}×{} {}", cfg.block_size, cfg.depth, dir)
+                        } else {
+                            "Block interleaver".to_string()
+                        }
+                    }
+                    InterleaverType::Convolutional => {
+                        if let Some(cfg) = convolutional_config {
+                            let dir = match cfg.direction {
+                                crate::processing::InterleaverDirection::Interleave => "Interleave",
+                                crate::processing::InterleaverDirection::Deinterleave => "Deinterleave",
+                            };
+                            format!("Conv B={} M={} {}", cfg.branches, cfg.delay_increment, dir)
+                        } else {
+                            "Convolutional interleaver".to_string()
+                        }
+                    }
+                    InterleaverType::Symbol => {
+                        if let Some(cfg) = symbol_config {
+                            let dir = match cfg.direction {
+                                crate::processing::InterleaverDirection::Interleave => "Interleave",
+                                crate::processing::InterleaverDirection::Deinterleave => "Deinterleave",
+                            };
+                            format!("Symbol {}×{} ({}bit) {}", cfg.block_size, cfg.depth, cfg.symbol_size, dir)
+                        } else {
+                            "Symbol interleaver".to_string()
+                        }
+                    }
+                }
+            }
+            BitOperation::Macro { operations, .. } => {
+                let steps: Vec<&str> = operations.iter().filter(|op| op.is_enabled()).map(|op| op.name()).collect();
+                format!("{} step(s): {}", steps.len(), steps.join(" → "))
+            }
+            BitOperation::Group { operations, .. } => {
+                let enabled = operations.iter().filter(|op| op.is_enabled()).count();
+                format!("Group of {} operation(s), {} enabled", operations.len(), enabled)
+            }
+            BitOperation::PatchBits { patches, .. } => {
+                let bits: usize = patches.iter().map(|patch| patch.len).sum();
+                format!("{} edit(s), {} bit(s)", patches.len(), bits)
+            }
+            BitOperation::InsertBits { position, count, fill, exprs, .. } => {
+                format!("Insert {} bit(s) of {} at {}", exprs.display("count", *count), fill, exprs.display("position", *position))
+            }
+            BitOperation::DeleteBits { mode, exprs, .. } => match mode {
+                DeleteMode::Range { position, count } => {
+                    format!("Delete {} bit(s) at {}", exprs.display("count", *count), exprs.display("position", *position))
+                }
+                DeleteMode::Unstuff { run_length } => format!("Remove 0 after {} ones", run_length),
+            },
+            BitOperation::RangeBits { action, start, end, exprs, .. } => {
+                let verb = match action {
+                    RangeAction::Invert => "Invert",
+                    RangeAction::Reverse => "Reverse",
+                };
+                let end = if *end == usize::MAX && !exprs.exprs.contains_key("end") { "end".to_string() } else { exprs.display("end", *end) };
+                format!("{} bits {}-{}", verb, exprs.display("start", *start), end)
+            }
+            BitOperation::Conditional { operation, condition, .. } => {
+                format!("{} on {}", operation.name(), condition.description())
+            }
+            BitOperation::Variants { a, b, use_b, .. } => {
+                let (active, label) = if *use_b { (b, "B") } else { (a, "A") };
+                format!("Using {}: {}", label, active.description())
+            }
+            BitOperation::XorKey { key, .. } => {
+                let hex: String = key.iter().map(|byte| format!("{:02X}", byte)).collect();
+                format!("XOR with 0x{} ({} byte key)", hex, key.len())
+            }
+            BitOperation::LineDecode { code, .. } => format!("Decode {}", code.name()),
+            BitOperation::SymbolConvert { code, symbol_bits, .. } => format!("{}, {}-bit symbols", code.name(), symbol_bits),
+            BitOperation::SymbolRemap { map, .. } => {
+                format!("Remap {}-bit symbols to {} bits, {} entries", map.input_bits, map.output_bits, map.table.len())
+            }
+            BitOperation::MajorityVote { vote, .. } => vote.description(),
+            BitOperation::NandLayout { layout, .. } => layout.description(),
+            BitOperation::Script { source, .. } => {
+                let lines = source.lines().filter(|line| !line.trim().is_empty()).count();
+                format!("Script, {} line(s)", lines)
+            }
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn apply(&self, input: &BitVec<u8, Msb0>) -> BitVec<u8, Msb0> {
+        let mut result = input.clone();
+        self.apply_in_place(&mut result);
+        result
+    }
+
+    /// Apply the operation directly to `bits`, reusing its buffer instead of building a new one
+    /// Apart from InsertBits and Script, operations only shrink or rearrange the data, so memory stays at about the input size
+    pub fn apply_in_place(&self, bits: &mut BitVec<u8, Msb0>) {
+        match self {
+            BitOperation::LoadFile { .. } | BitOperation::MultiFileLoad { .. } | BitOperation::LoadSoftBits { .. } => {
+                // LoadFile operations are handled specially in the main application
+                // since they need file I/O. Leave the input unchanged here.
+            }
+            BitOperation::TakeSkipSequence { sequence, .. } => sequence.apply_in_place(bits),
+            BitOperation::InvertBits { .. } => invert_in_place(bits),
+            BitOperation::MultiWorksheetLoad { .. } => {
+                // This operation type requires worksheet data, so it should be handled
+                // differently in the main application. For now, produce nothing.
+                bits.clear();
+            }
+            BitOperation::TruncateBits { start, end, exprs, .. } => {
+                let len = bits.len();
+                // A failing expression leaves the data alone; the operation list flags it
+                let (Some(actual_start), Some(actual_end)) = (exprs.resolve("start", *start, len), exprs.resolve("end", *end, len)) else {
+                    return;
+                };
+                let actual_start = actual_start.min(len);
+                let actual_end = actual_end.min(len);
+                
+                if actual_start >= actual_end {
+                    bits.clear();
+                    return;
+                }
+                
+                bits.copy_within(actual_start..actual_end, 0);
+                bits.truncate(actual_end - actual_start);
+            }
+            BitOperation::InterleaveBits { interleaver_type, block_config, convolutional_config, symbol_config, .. } => {
+                match interleaver_type {
+                    InterleaverType::Block => {
+                        if let Some(cfg) = block_config {
+                            cfg.apply_in_place(bits);
+                        }
+                    }
+                    InterleaverType::Convolutional => {
+                        if let Some(cfg) = convolutional_config {
+                            cfg.apply_in_place(bits);
+                        }
+                    }
+                    InterleaverType::Symbol => {
+                        if let Some(cfg) = symbol_config {
+                            cfg.apply_in_place(bits);
+                        }
+                    }
+                }
+            }
+            BitOperation::Macro { operations, .. } | BitOperation::Group { operations, .. } => {
+                for op in operations.iter().filter(|op| op.is_enabled()) {
+                    op.apply_in_place(bits);
+                }
+            }
+            BitOperation::PatchBits { patches, .. } => {
+                for patch in patches {
+                    patch.apply_in_place(bits);
+                }
+            }
+            BitOperation::InsertBits { position, count, fill, exprs, .. } => {
+                // Unparseable fills were rejected by the editor; insert nothing rather than guess
+                let Ok(pattern) = parse_bit_pattern(fill) else {
+                    return;
+                };
+                let len = bits.len();
+                let (Some(at), Some(count)) = (exprs.resolve("position", *position, len), exprs.resolve("count", *count, len)) else {
+                    return;
+                };
+                let at = at.min(len);
+                bits.resize(len + count, false);
+                if at < len {
+                    bits.copy_within(at..len, at + count);
+                }
+                for i in 0..count {
+                    bits.set(at + i, pattern[i % pattern.len()]);
+                }
+            }
+            BitOperation::DeleteBits { mode, exprs, .. } => match *mode {
+                DeleteMode::Range { position, count } => {
+                    let len = bits.len();
+                    let (Some(position), Some(count)) = (exprs.resolve("position", position, len), exprs.resolve("count", count, len)) else {
+                        return;
+                    };
+                    DeleteMode::Range { position, count }.apply_in_place(bits);
+                }
+                DeleteMode::Unstuff { .. } => mode.apply_in_place(bits),
+            },
+            BitOperation::RangeBits { action, start, end, exprs, .. } => {
+                let len = bits.len();
+                if let (Some(start), Some(end)) = (exprs.resolve("start", *start, len), exprs.resolve("end", *end, len)) {
+                    action.apply_in_place(bits, start, end);
+                }
+            }
+            BitOperation::XorKey { key, .. } => {
+                if key.is_empty() {
+                    return;
+                }
+                // A trailing partial byte is XORed with the top bits of its key byte
+                for (i, chunk) in bits.chunks_mut(8).enumerate() {
+                    let mask = key[i % key.len()] >> (8 - chunk.len());
+                    let value = chunk.load_be::<u8>() ^ mask;
+                    chunk.store_be(value);
+                }
+            }
+            BitOperation::LineDecode { code, .. } => *bits = decode_line_code(*code, bits).0,
+            BitOperation::SymbolConvert { code, symbol_bits, .. } => {
+                convert_symbols(*code, *symbol_bits, bits);
+            }
+            BitOperation::SymbolRemap { map, .. } => *bits = map.apply(bits),
+            BitOperation::MajorityVote { vote, .. } => *bits = vote.apply(bits),
+            BitOperation::NandLayout { layout, .. } => *bits = layout.apply(bits),
+            BitOperation::Conditional { operation, condition, .. } => {
+                let segments = condition.segments(bits.len());
+                if segments.is_empty() {
+                    return;
+                }
+                // The child may change a segment's length, so the output is rebuilt around it
+                let mut output = BitVec::with_capacity(bits.len());
+                let mut scratch = BitVec::new();
+                let mut pos = 0;
+                for segment in segments {
+                    output.extend_from_bitslice(&bits[pos..segment.start]);
+                    scratch.clear();
+                    scratch.extend_from_bitslice(&bits[segment.clone()]);
+                    operation.apply_in_place(&mut scratch);
+                    output.extend_from_bitslice(&scratch);
+                    pos = segment.end;
+                }
+                output.extend_from_bitslice(&bits[pos..]);
+                *bits = output;
+            }
+            BitOperation::Variants { .. } => {
+                if let Some(active) = self.active_variant() {
+                    active.apply_in_place(bits);
+                }
+            }
+            BitOperation::Script { source, time_limit_ms, .. } => {
+                // Scripts are checked by the editor and previewed there; one that fails
+                // at run time (bad index, time limit) leaves the data unchanged
+                let _ = run_script(source, bits, *time_limit_ms);
+            }
+        }
+    }
+
+    /// Input period (in bits) at which this operation can be split into independent chunks
+    /// Returns None when the output depends on the input as a whole (stateful or positional operations)
+    pub fn chunk_period(&self) -> Option<usize> {
+        match self {
+            BitOperation::TakeSkipSequence { sequence, .. } => {
+                let period = sequence.cycle_length();
+                if period > 0 { Some(period) } else { None }
+            }
+            BitOperation::InvertBits { .. } => Some(1),
+            BitOperation::Variants { .. } => self.active_variant().and_then(|active| active.chunk_period()),
+            BitOperation::XorKey { key, .. } => (!key.is_empty()).then_some(key.len() * 8),
+            BitOperation::SymbolConvert { symbol_bits, .. } => (*symbol_bits > 0).then_some(*symbol_bits),
+            // Chunks are written back over the input, so a remap that widens symbols would
+            // overtake bits not yet read
+            BitOperation::SymbolRemap { map, .. } => {
+                (map.validate().is_ok() && map.output_bits <= map.input_bits).then_some(map.input_bits)
+            }
+            BitOperation::MajorityVote { vote, .. } => vote.validate().is_ok().then(|| vote.group_bits()),
+            // Pages are checked and split independently
+            BitOperation::NandLayout { layout, .. } => layout.validate().is_ok().then(|| layout.raw_page_size() * 8),
+            BitOperation::InterleaveBits { interleaver_type, block_config, symbol_config, .. } => {
+                let period = match interleaver_type {
+                    InterleaverType::Block => block_config.as_ref().map(|cfg| cfg.block_size * cfg.depth),
+                    InterleaverType::Symbol => symbol_config.as_ref().map(|cfg| cfg.symbol_size * cfg.block_size * cfg.depth),
+                    // Delay lines carry state across the whole stream
+                    InterleaverType::Convolutional => None,
+                };
+                period.filter(|&p| p > 0)
+            }
+            BitOperation::LoadFile { .. }
+            | BitOperation::MultiFileLoad { .. }
+            | BitOperation::LoadSoftBits { .. }
+            | BitOperation::MultiWorksheetLoad { .. }
+            | BitOperation::TruncateBits { .. }
+            | BitOperation::Macro { .. }
+            | BitOperation::Group { .. }
+            | BitOperation::PatchBits { .. }
+            | BitOperation::InsertBits { .. }
+            | BitOperation::DeleteBits { .. }
+            | BitOperation::RangeBits { .. }
+            | BitOperation::Conditional { .. }
+            // Running disparity and the descrambler carry state from one symbol to the next
+            | BitOperation::LineDecode { .. }
+            | BitOperation::Script { .. } => None,
+        }
+    }
+
+    /// Why the operation's parameter expressions can't be used for an input of `input_len` bits;
+    /// it then passes the input through unchanged
+    pub fn expression_error(&self, input_len: usize) -> Option<String> {
+        match self {
+            BitOperation::TruncateBits { exprs, .. }
+            | BitOperation::InsertBits { exprs, .. }
+            | BitOperation::DeleteBits { exprs, .. }
+            | BitOperation::RangeBits { exprs, .. } => exprs.error(input_len),
+            _ => None,
+        }
+    }
+
+    /// Where bits at `positions` of an input `input_len` bits long end up, with the output length
+    ///
+    /// Positions the operation removes become None. Returns None when bits are moved or
+    /// rebuilt in a way that can't be followed one position at a time.
+    pub fn track_positions(&self, positions: &[usize], input_len: usize) -> Option<(Vec<Option<usize>>, usize)> {
+        let kept = |bit: usize| (bit < input_len).then_some(bit);
+        if self.expression_error(input_len).is_some() {
+            // The operation passes its input through unchanged
+            return Some((positions.iter().map(|&bit| kept(bit)).collect(), input_len));
+        }
+        // Every expression evaluates past the check above
+        let resolve = |exprs: &ParamExpressions, param: &str, fixed: usize| exprs.resolve(param, fixed, input_len).unwrap_or(fixed);
+        match self {
+            BitOperation::InvertBits { .. } | BitOperation::PatchBits { .. } | BitOperation::XorKey { .. } => {
+                Some((positions.iter().map(|&bit| kept(bit)).collect(), input_len))
+            }
+            BitOperation::RangeBits { action, start, end, exprs, .. } => {
+                let start = resolve(exprs, "start", *start).min(input_len);
+                let end = resolve(exprs, "end", *end).min(input_len);
+                let moved = |bit: usize| match action {
+                    RangeAction::Reverse if (start..end).contains(&bit) => start + end - 1 - bit,
+                    _ => bit,
+                };
+                Some((positions.iter().map(|&bit| kept(bit).map(moved)).collect(), input_len))
+            }
+            BitOperation::TruncateBits { start, end, exprs, .. } => {
+                let start = resolve(exprs, "start", *start).min(input_len);
+                let end = resolve(exprs, "end", *end).min(input_len);
+                let tracked = positions.iter().map(|&bit| (start..end).contains(&bit).then(|| bit - start)).collect();
+                Some((tracked, end.saturating_sub(start)))
+            }
+            BitOperation::InsertBits { position, count, fill, exprs, .. } => {
+                if parse_bit_pattern(fill).is_err() {
+                    return Some((positions.iter().map(|&bit| kept(bit)).collect(), input_len));
+                }
+                let at = resolve(exprs, "position", *position).min(input_len);
+                let count = resolve(exprs, "count", *count);
+                let tracked = positions.iter().map(|&bit| kept(bit).map(|bit| if bit < at { bit } else { bit + count })).collect();
+                Some((tracked, input_len + count))
+            }
+            BitOperation::DeleteBits { mode: DeleteMode::Range { position, count }, exprs, .. } => {
+                let position = resolve(exprs, "position", *position);
+                let count = resolve(exprs, "count", *count);
+                let start = position.min(input_len);
+                let end = position.saturating_add(count).min(input_len);
+                let tracked = positions
+                    .iter()
+                    .map(|&bit| match kept(bit) {
+                        Some(bit) if bit < start => Some(bit),
+                        Some(bit) if bit >= end => Some(bit - (end - start)),
+                        _ => None,
+                    })
+                    .collect();
+                Some((tracked, input_len - (end - start)))
+            }
+            BitOperation::Macro { operations, .. } | BitOperation::Group { operations, .. } => {
+                track_through_chain(operations, positions, input_len).ok()
+            }
+            BitOperation::Variants { .. } => match self.active_variant() {
+                Some(active) => active.track_positions(positions, input_len),
+                None => Some((positions.iter().map(|&bit| kept(bit)).collect(), input_len)),
+            },
+            _ => None,
+        }
+    }
+
+    /// Apply the operation in place in chunks of about APPLY_CHUNK_BITS, reporting progress after each chunk
+    ///
+    /// `on_progress` receives (processed_input_bits, total_input_bits) and returns false to
+    /// cancel, in which case false is returned and `bits` is left partially processed.
+    /// Operations that cannot be chunked are applied in one go with a single progress report at the end.
+    pub fn apply_chunked<F>(&self, bits: &mut BitVec<u8, Msb0>, on_progress: F) -> bool
+    where
+        F: FnMut(usize, usize) -> bool,
+    {
+        self.apply_in_chunks(bits, APPLY_CHUNK_BITS, on_progress)
+    }
+
+    /// Same as `apply_chunked` with an explicit target chunk size
+    pub fn apply_in_chunks<F>(&self, bits: &mut BitVec<u8, Msb0>, target_chunk_bits: usize, mut on_progress: F) -> bool
+    where
+        F: FnMut(usize, usize) -> bool,
+    {
+        let total = bits.len();
+        let period = match self.chunk_period() {
+            Some(period) if total > target_chunk_bits => period,
+            _ => {
+                self.apply_in_place(bits);
+                return on_progress(total, total);
+            }
+        };
+
+        // Largest multiple of the period that fits within the target chunk size
+        let chunk_bits = (target_chunk_bits / period).max(1) * period;
+        let mut scratch = BitVec::with_capacity(chunk_bits);
+        let mut read_pos = 0;
+        let mut write_pos = 0;
+
+        while read_pos < total {
+            let end = (read_pos + chunk_bits).min(total);
+            scratch.clear();
+            scratch.extend_from_bitslice(&bits[read_pos..end]);
+            self.apply_in_place(&mut scratch);
+
+            // Chunkable operations never grow their input, so output can't overtake unread bits
+            bits[write_pos..write_pos + scratch.len()].copy_from_bitslice(&scratch);
+            write_pos += scratch.len();
+            read_pos = end;
+
+            if !on_progress(read_pos, total) {
+                return false;
+            }
+        }
+
+        bits.truncate(write_pos);
+        true
+    }
+}
+
+/// `BitOperation::track_positions` through the enabled operations of a chain; fails with the
+/// first operation whose bits can't be followed
+pub fn track_through_chain<'a>(
+    operations: &'a [BitOperation],
+    positions: &[usize],
+    input_len: usize,
+) -> Result<(Vec<Option<usize>>, usize), &'a BitOperation> {
+    let mut tracked: Vec<Option<usize>> = positions.iter().map(|&bit| (bit < input_len).then_some(bit)).collect();
+    let mut len = input_len;
+    for op in operations.iter().filter(|op| op.is_enabled()) {
+        let live: Vec<usize> = tracked.iter().flatten().copied().collect();
+        let (moved, next_len) = op.track_positions(&live, len).ok_or(op)?;
+        let mut moved = moved.into_iter();
+        for slot in tracked.iter_mut().filter(|slot| slot.is_some()) {
+            *slot = moved.next().flatten();
+        }
+        len = next_len;
+    }
+    Ok((tracked, len))
+}
+
+/// Invert every bit of the slice in place, touching each storage element once
+fn invert_in_place(bits: &mut BitSlice<u8, Msb0>) {
+    let _ = !bits;
+}
+
+impl OperationSequence {
+    pub fn from_string(s: &str) -> Result<Self, String> {
+        let mut operations = Vec::new();
+        let mut chars = s.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            let op_type = c;
+            let mut num_str = String::new();
+
+            // Read the number following the operation character
+            while let Some(&next_char) = chars.peek() {
+                if next_char.is_numeric() {
+                    num_str.push(next_char);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+
+            if num_str.is_empty() {
+                return Err(format!("Expected number after '{}'", op_type));
+            }
+
+            let num: usize = num_str
+                .parse()
+                .map_err(|_| format!("Invalid number: {}", num_str))?;
+
+            let operation = match op_type {
+                't' | 'T' => Operation::Take(num),
+                'r' | 'R' => Operation::Reverse(num),
+                'i' | 'I' => Operation::Invert(num),
+                's' | 'S' => Operation::Skip(num),
+                _ => return Err(format!("Unknown operation: {}", op_type)),
+            };
+
+            operations.push(operation);
+        }
+
+        Ok(Self { operations })
+    }
+
+    pub fn to_string(&self) -> String {
+        self.operations
+            .iter()
+            .map(|op| op.to_string())
+            .collect::<Vec<_>>()
+            .join("")
+    }
+
+    /// Number of input bits consumed by one full pass over the sequence
+    pub fn cycle_length(&self) -> usize {
+        self.operations
+            .iter()
+            .map(|op| match op {
+                Operation::Take(n) | Operation::Reverse(n) | Operation::Invert(n) | Operation::Skip(n) => *n,
+            })
+            .sum()
+    }
+
+    #[allow(dead_code)]
+    pub fn apply(&self, input: &BitVec<u8, Msb0>) -> BitVec<u8, Msb0> {
+        let mut result = input.clone();
+        self.apply_in_place(&mut result);
+        result
+    }
+
+    /// Apply the sequence directly to `bits`
+    /// Kept bits are compacted towards the front of the buffer (the write position never
+    /// passes the read position) and the buffer is truncated to the output length at the end
+    pub fn apply_in_place(&self, bits: &mut BitVec<u8, Msb0>) {
+        let len = bits.len();
+        let mut pos = 0;
+        let mut write_pos = 0;
+
+        while pos < len {
+            let start_pos = pos;
+            
+            for operation in &self.operations {
+                if pos >= len {
+                    break;
+                }
+
+                let n = match operation {
+                    Operation::Take(n) | Operation::Reverse(n) | Operation::Invert(n) | Operation::Skip(n) => *n,
+                };
+                if n == 0 {
+                    // Skip zero-sized operations to prevent infinite loops
+                    continue;
+                }
+                let end = (pos + n).min(len);
+
+                if !matches!(operation, Operation::Skip(_)) {
+                    let count = end - pos;
+                    if write_pos != pos {
+                        bits.copy_within(pos..end, write_pos);
+                    }
+
+                    let target = &mut bits[write_pos..write_pos + count];
+                    match operation {
+                        Operation::Reverse(_) => target.reverse(),
+                        Operation::Invert(_) => invert_in_place(target),
+                        _ => {}
+                    }
+                    write_pos += count;
+                }
+                pos = end;
+            }
+            
+            // Prevent infinite loop if no progress was made
+            if pos == start_pos {
+                break;
+            }
+        }
+
+        bits.truncate(write_pos);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_operation_sequence() {
+        let seq = OperationSequence::from_string("t4r3i8s1").unwrap();
+        assert_eq!(seq.operations.len(), 4);
+        assert_eq!(seq.operations[0], Operation::Take(4));
+        assert_eq!(seq.operations[1], Operation::Reverse(3));
+        assert_eq!(seq.operations[2], Operation::Invert(8));
+        assert_eq!(seq.operations[3], Operation::Skip(1));
+    }
+
+    #[test]
+    fn test_operation_sequence_to_string() {
+        let seq = OperationSequence::from_string("t4r3i8s1").unwrap();
+        assert_eq!(seq.to_string(), "t4r3i8s1");
+    }
+
+    #[test]
+    fn test_take_operation() {
+        let input = bitvec![u8, Msb0; 1, 0, 1, 1, 0, 0, 1, 0];
+        let seq = OperationSequence::from_string("t4").unwrap();
+        let result = seq.apply(&input);
+        // t4 repeats: take 4, then take 4 again
+        assert_eq!(result, bitvec![u8, Msb0; 1, 0, 1, 1, 0, 0, 1, 0]);
+    }
+
+    #[test]
+    fn test_reverse_operation() {
+        let input = bitvec![u8, Msb0; 1, 1, 0];
+        let seq = OperationSequence::from_string("r3").unwrap();
+        let result = seq.apply(&input);
+        assert_eq!(result, bitvec![u8, Msb0; 0, 1, 1]);
+    }
+
+    #[test]
+    fn test_invert_operation() {
+        let input = bitvec![u8, Msb0; 1, 0, 1, 0];
+        let seq = OperationSequence::from_string("i4").unwrap();
+        let result = seq.apply(&input);
+        assert_eq!(result, bitvec![u8, Msb0; 0, 1, 0, 1]);
+    }
+
+    #[test]
+    fn test_skip_operation() {
+        let input = bitvec![u8, Msb0; 1, 0, 1, 1, 0, 0];
+        let seq = OperationSequence::from_string("t2s2t2").unwrap();
+        let result = seq.apply(&input);
+        assert_eq!(result, bitvec![u8, Msb0; 1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_complex_sequence() {
+        let input = bitvec![u8, Msb0; 1, 0, 1, 1, 0, 0, 1, 0, 1, 1, 1, 0];
+        let seq = OperationSequence::from_string("t4r3i2s1").unwrap();
+        let result = seq.apply(&input);
+        
+        // t4: take first 4 bits -> 1011
+        // r3: reverse next 3 bits (001) -> 100
+        // i2: invert next 2 bits (01) -> 10
+        // s1: skip 1 bit (skip 1)
+        // Then repeat...
+        // t4: take next 4 bits (10) -> only 2 bits left, so 10
+        
+        let expected = bitvec![u8, Msb0; 1, 0, 1, 1, 1, 0, 0, 1, 0, 1, 0];
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_macro_applies_enabled_steps_in_order() {
+        let mut skipped = BitOperation::TruncateBits { name: "Drop all".to_string(), start: 0, end: 0, exprs: Default::default(), enabled: true };
+        skipped.set_enabled(false);
+        let saved = OperationMacro {
+            name: "Descramble".to_string(),
+            operations: vec![
+                BitOperation::TakeSkipSequence {
+                    name: "Pairs".to_string(),
+                    sequence: OperationSequence::from_string("t2s2").unwrap(),
+                    enabled: true,
+                },
+                skipped,
+                BitOperation::InvertBits { name: "Invert".to_string(), enabled: true },
+            ],
+        };
+
+        let input = bitvec![u8, Msb0; 1, 0, 1, 1, 0, 0, 1, 0];
+        let result = saved.to_operation().apply(&input);
+        assert_eq!(result, bitvec![u8, Msb0; 0, 1, 1, 1]);
+    }
+
+    #[test]
+    fn test_xor_key_repeats_over_bytes() {
+        assert_eq!(parse_hex_key("0x4B 45").unwrap(), vec![0x4B, 0x45]);
+        assert!(parse_hex_key("0x4B4").is_err());
+        assert!(parse_hex_key("zz").is_err());
+
+        let op = BitOperation::XorKey { name: "XOR".to_string(), key: vec![0xFF, 0x0F], enabled: true };
+        let input = [0x00u8, 0x00, 0xAA].view_bits::<Msb0>()[..20].to_bitvec();
+        let output = op.apply(&input);
+        assert_eq!(output.len(), 20);
+        assert_eq!(output[..16], [0xFFu8, 0x0F].view_bits::<Msb0>()[..]);
+        // 0xA0 XOR 0xF0 in the last four bits
+        assert_eq!(output[16..], bits![u8, Msb0; 0, 1, 0, 1]);
+        assert_eq!(op.apply(&output), input);
+    }
+
+    #[test]
+    fn test_filter_matches_name_type_and_members() {
+        let xor = BitOperation::XorKey { name: "Descramble".to_string(), key: vec![0x4B], enabled: true };
+        assert!(xor.matches_filter(""));
+        assert!(xor.matches_filter("descr"));
+        assert!(xor.matches_filter("XOR with"));
+        assert!(!xor.matches_filter("invert"));
+
+        let group = BitOperation::Group { name: "Cleanup".to_string(), operations: vec![xor], enabled: true, collapsed: false };
+        assert!(group.matches_filter("Cleanup"));
+        assert!(group.matches_filter("descramble"));
+        assert!(!group.matches_filter("truncate"));
+    }
+
+    #[test]
+    fn test_variants_apply_the_selected_version() {
+        let input = bitvec![u8, Msb0; 1, 0, 1, 1, 0, 0, 1, 0];
+        let mut op = BitOperation::Variants {
+            name: "Take".to_string(),
+            a: Box::new(BitOperation::TakeSkipSequence { name: "A".to_string(), sequence: OperationSequence::from_string("t2s2").unwrap(), enabled: true }),
+            b: Box::new(BitOperation::TakeSkipSequence { name: "B".to_string(), sequence: OperationSequence::from_string("t4s4").unwrap(), enabled: true }),
+            use_b: false,
+            enabled: true,
+        };
+        assert_eq!(op.apply(&input), bitvec![u8, Msb0; 1, 0, 0, 0]);
+        assert_eq!(op.chunk_period(), Some(4));
+        if let BitOperation::Variants { use_b, .. } = &mut op {
+            *use_b = true;
+        }
+        assert_eq!(op.apply(&input), bitvec![u8, Msb0; 1, 0, 1, 1]);
+        assert_eq!(op.chunk_period(), Some(8));
+        assert!(op.description().starts_with("Using B"));
+    }
+
+    #[test]
+    fn test_group_applies_as_a_unit() {
+        let json = r#"{"Group":{"name":"Descramble","operations":[{"InvertBits":{"name":"Invert","enabled":true}}],"enabled":true}}"#;
+        let mut group: BitOperation = serde_json::from_str(json).unwrap();
+        assert!(matches!(group, BitOperation::Group { collapsed: false, .. }));
+
+        let input = bitvec![u8, Msb0; 1, 0, 1, 1];
+        assert_eq!(group.apply(&input), bitvec![u8, Msb0; 0, 1, 0, 0]);
+
+        // Disabling the group skips its children without touching their own flags
+        group.set_enabled(false);
+        let chain = [group];
+        let mut bits = input.clone();
+        for op in chain.iter().filter(|op| op.is_enabled()) {
+            op.apply_in_place(&mut bits);
+        }
+        assert_eq!(bits, input);
+        let BitOperation::Group { operations, .. } = &chain[0] else { unreachable!() };
+        assert!(operations[0].is_enabled());
+    }
+
+    #[test]
+    fn test_patches_apply_in_order() {
+        let op = BitOperation::PatchBits {
+            name: "Bit Edits".to_string(),
+            patches: vec![
+                BitPatch::byte(1, 0xA5),
+                BitPatch::bit(0, true),
+                BitPatch::bit(8, false),
+                // Past the end of the data; ignored
+                BitPatch::byte(4, 0xFF),
+            ],
+            enabled: true,
+        };
+
+        let input = bitvec![u8, Msb0; 0; 16];
+        let result = op.apply(&input);
+        assert_eq!(result.len(), 16);
+        assert_eq!(result.load_be::<u16>(), 0x8025);
+    }
+
+    #[test]
+    fn test_oversized_patch_is_rejected_on_load() {
+        let patch: BitPatch = serde_json::from_str(r#"{"position":3,"len":64,"value":1}"#).unwrap();
+        assert_eq!(patch.len, 64);
+        assert!(serde_json::from_str::<BitPatch>(r#"{"position":3,"len":65,"value":1}"#).is_err());
+
+        let mut bits = bitvec![u8, Msb0; 0; 128];
+        BitPatch { position: 0, len: 100, value: u64::MAX }.apply_in_place(&mut bits);
+        assert!(bits.not_any());
+    }
+
+    #[test]
+    fn test_insert_bits_repeats_fill() {
+        let op = BitOperation::InsertBits { name: "Insert".to_string(), position: 2, count: 5, fill: "10".to_string(), exprs: Default::default(), enabled: true };
+        let input = bitvec![u8, Msb0; 0, 0, 0, 0];
+        assert_eq!(op.apply(&input), bitvec![u8, Msb0; 0, 0, 1, 0, 1, 0, 1, 0, 0]);
+
+        // Past the end appends
+        let op = BitOperation::InsertBits { name: "Insert".to_string(), position: 99, count: 2, fill: "1".to_string(), exprs: Default::default(), enabled: true };
+        assert_eq!(op.apply(&input), bitvec![u8, Msb0; 0, 0, 0, 0, 1, 1]);
+    }
+
+    #[test]
+    fn test_param_expressions_follow_input_length() {
+        let mut exprs = ParamExpressions { frame_len: 4, ..Default::default() };
+        exprs.exprs.insert("start".to_string(), "frame_len".to_string());
+        exprs.exprs.insert("end".to_string(), "len-2".to_string());
+        let op = BitOperation::TruncateBits { name: "Trim".to_string(), start: 0, end: 0, exprs, enabled: true };
+
+        assert_eq!(op.apply(&bitvec![u8, Msb0; 0, 0, 0, 0, 1, 1, 0, 0]), bitvec![u8, Msb0; 1, 1]);
+        assert_eq!(op.apply(&bitvec![u8, Msb0; 0, 0, 0, 0, 1, 0, 1, 0, 0]), bitvec![u8, Msb0; 1, 0, 1]);
+        assert!(op.description().contains("len-2"));
+        assert_eq!(ParamExpressions::variables(10, 4), [("len", 10), ("frame_len", 4), ("frames", 2)]);
+    }
+
+    #[test]
+    fn test_failing_expression_leaves_input_unchanged() {
+        let mut exprs = ParamExpressions::default();
+        exprs.exprs.insert("end".to_string(), "len-1024".to_string());
+        // 2000 was right for the file the operation was saved with, not for this one
+        let op = BitOperation::TruncateBits { name: "Trim".to_string(), start: 2, end: 2000, exprs, enabled: true };
+        let input = bitvec![u8, Msb0; 1, 0, 1, 1, 0, 0, 1, 0];
+
+        assert_eq!(op.apply(&input), input);
+        assert!(op.expression_error(input.len()).is_some_and(|e| e.contains("len-1024")));
+        assert_eq!(op.track_positions(&[3], input.len()), Some((vec![Some(3)], input.len())));
+        assert_eq!(op.expression_error(4096), None);
+    }
+
+    #[test]
+    fn test_delete_bit_range() {
+        let input = bitvec![u8, Msb0; 1, 0, 1, 1, 0, 0, 1, 0];
+        let mut bits = input.clone();
+        DeleteMode::Range { position: 2, count: 3 }.apply_in_place(&mut bits);
+        assert_eq!(bits, bitvec![u8, Msb0; 1, 0, 0, 1, 0]);
+
+        let mut bits = input.clone();
+        DeleteMode::Range { position: 6, count: 10 }.apply_in_place(&mut bits);
+        assert_eq!(bits, bitvec![u8, Msb0; 1, 0, 1, 1, 0, 0]);
+    }
+
+    #[test]
+    fn test_range_bits_only_touch_their_range() {
+        let input = bitvec![u8, Msb0; 1, 1, 0, 0, 1, 0, 1, 1];
+        let invert = BitOperation::RangeBits { name: "Invert".to_string(), action: RangeAction::Invert, start: 2, end: 5, exprs: Default::default(), enabled: true };
+        assert_eq!(invert.apply(&input), bitvec![u8, Msb0; 1, 1, 1, 1, 0, 0, 1, 1]);
+
+        let reverse = BitOperation::RangeBits { name: "Reverse".to_string(), action: RangeAction::Reverse, start: 4, end: usize::MAX, exprs: Default::default(), enabled: true };
+        assert_eq!(reverse.apply(&input), bitvec![u8, Msb0; 1, 1, 0, 0, 1, 1, 0, 1]);
+        assert_eq!(reverse.description(), "Reverse bits 4-end");
+
+        // Ranges past the data leave it unchanged
+        let outside = BitOperation::RangeBits { name: "Invert".to_string(), action: RangeAction::Invert, start: 20, end: 30, exprs: Default::default(), enabled: true };
+        assert_eq!(outside.apply(&input), input);
+
+        // The last frame, whatever the input's length
+        let mut exprs = ParamExpressions { frame_len: 3, ..Default::default() };
+        exprs.exprs.insert("start".to_string(), "len-frame_len".to_string());
+        let last_frame = BitOperation::RangeBits { name: "Invert".to_string(), action: RangeAction::Invert, start: 5, end: usize::MAX, exprs, enabled: true };
+        assert_eq!(last_frame.apply(&input), bitvec![u8, Msb0; 1, 1, 0, 0, 1, 1, 0, 0]);
+        assert_eq!(last_frame.apply(&input[..6].to_bitvec()), bitvec![u8, Msb0; 1, 1, 0, 1, 0, 1]);
+        assert_eq!(last_frame.description(), "Invert bits len-frame_len-end");
+    }
+
+    #[test]
+    fn test_tracked_positions_follow_the_bits() {
+        let chain = BitOperation::Group {
+            name: "Reframe".to_string(),
+            operations: vec![
+                BitOperation::TruncateBits { name: "Trim".to_string(), start: 2, end: 30, exprs: Default::default(), enabled: true },
+                BitOperation::InsertBits { name: "Insert".to_string(), position: 4, count: 3, fill: "0".to_string(), exprs: Default::default(), enabled: true },
+                BitOperation::DeleteBits { name: "Delete".to_string(), mode: DeleteMode::Range { position: 10, count: 2 }, exprs: Default::default(), enabled: true },
+                BitOperation::RangeBits { name: "Reverse".to_string(), action: RangeAction::Reverse, start: 12, end: 20, exprs: Default::default(), enabled: true },
+            ],
+            collapsed: false,
+            enabled: true,
+        };
+        // A lone set bit lands wherever the tracked position says it does
+        let positions: Vec<usize> = (0..32).collect();
+        let (tracked, len) = chain.track_positions(&positions, 32).unwrap();
+        for (&bit, moved) in positions.iter().zip(&tracked) {
+            let mut input = bitvec![u8, Msb0; 0; 32];
+            input.set(bit, true);
+            let output = chain.apply(&input);
+            assert_eq!(output.len(), len);
+            assert_eq!(output.first_one(), *moved, "bit {bit}");
+        }
+
+        // Line codes rebuild the stream, so their positions are lost
+        let decode = BitOperation::LineDecode { name: "8b/10b".to_string(), code: LineCode::EightBTenB, enabled: true };
+        assert!(decode.track_positions(&positions, 32).is_none());
+    }
+
+    #[test]
+    fn test_conditional_applies_to_every_other_frame() {
+        let op = BitOperation::Conditional {
+            name: "Descramble odd frames".to_string(),
+            operation: Box::new(BitOperation::InvertBits { name: "Invert".to_string(), enabled: true }),
+            condition: BitCondition::EveryNthFrame { frame_length: 4, every: 2, offset: 1 },
+            enabled: true,
+        };
+        let input = bitvec![u8, Msb0; 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(op.apply(&input), bitvec![u8, Msb0; 0, 0, 0, 0, 1, 1, 1, 1, 0, 0, 0, 0, 1, 1]);
+    }
+
+    #[test]
+    fn test_conditional_ranges_from_csv() {
+        let ranges = parse_ranges_csv("start,end\n# sync\n8, 12\n2,4\n3,5\n").unwrap();
+        assert_eq!(ranges, vec![(8, 12), (2, 4), (3, 5)]);
+        assert!(parse_ranges_csv("1,2\nx,3\n").is_err());
+
+        // Overlapping ranges are merged; a child that shrinks its part shifts what follows
+        let op = BitOperation::Conditional {
+            name: "Drop".to_string(),
+            operation: Box::new(BitOperation::TruncateBits { name: "First bit".to_string(), start: 0, end: 1, exprs: Default::default(), enabled: true }),
+            condition: BitCondition::Ranges { ranges },
+            enabled: true,
+        };
+        let input = bitvec![u8, Msb0; 0, 0, 1, 1, 1, 0, 0, 0, 1, 0, 1, 0];
+        assert_eq!(op.apply(&input), bitvec![u8, Msb0; 0, 0, 1, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_unstuff_removes_zero_after_five_ones() {
+        // Flag, then data 11111 1 stuffed as 111110 1, then flag
+        let stuffed = bitvec![u8, Msb0; 0, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 0, 1, 0, 1, 1, 1, 1, 1, 1, 0];
+        let mut bits = stuffed.clone();
+        DeleteMode::Unstuff { run_length: 5 }.apply_in_place(&mut bits);
+        assert_eq!(bits, bitvec![u8, Msb0; 0, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 0, 1, 1, 1, 1, 1, 1, 0]);
+    }
+}
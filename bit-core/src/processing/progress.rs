@@ -0,0 +1,148 @@
+// Progress reporting and cancellation for long-running work
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Shared flag a long task polls to find out whether it should stop
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Latest progress published by a task
+#[derive(Debug, Clone, Default)]
+pub struct ProgressState {
+    /// 0.0 to 1.0
+    pub fraction: f32,
+    pub message: String,
+}
+
+/// Handed to a task so it can publish progress and notice cancellation
+#[derive(Debug, Clone, Default)]
+pub struct ProgressReporter {
+    pub token: CancellationToken,
+    state: Arc<Mutex<ProgressState>>,
+}
+
+impl ProgressReporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn report(&self, fraction: f32, message: impl Into<String>) {
+        if let Ok(mut state) = self.state.lock() {
+            state.fraction = fraction.clamp(0.0, 1.0);
+            state.message = message.into();
+        }
+    }
+
+    /// Update the fraction, keeping the last message
+    pub fn set_fraction(&self, fraction: f32) {
+        if let Ok(mut state) = self.state.lock() {
+            state.fraction = fraction.clamp(0.0, 1.0);
+        }
+    }
+
+    pub fn state(&self) -> ProgressState {
+        self.state.lock().map(|state| state.clone()).unwrap_or_default()
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+}
+
+/// Work running on its own thread, with progress the UI can show and a way to cancel it
+pub struct BackgroundTask<T> {
+    /// Shown as the progress dialog title
+    pub title: String,
+    progress: ProgressReporter,
+    receiver: Receiver<T>,
+}
+
+impl<T: Send + 'static> BackgroundTask<T> {
+    pub fn spawn<F>(title: impl Into<String>, work: F) -> Self
+    where
+        F: FnOnce(&ProgressReporter) -> T + Send + 'static,
+    {
+        let progress = ProgressReporter::new();
+        let (tx, receiver) = channel();
+        let worker_progress = progress.clone();
+        thread::spawn(move || {
+            let _ = tx.send(work(&worker_progress));
+        });
+        Self { title: title.into(), progress, receiver }
+    }
+
+    pub fn progress(&self) -> ProgressState {
+        self.progress.state()
+    }
+
+    /// Ask the task to stop; it notices at its next progress check
+    pub fn cancel(&self) {
+        self.progress.token.cancel();
+    }
+
+    /// The result once the task has finished, Some(Err) if its thread panicked
+    pub fn try_finish(&self) -> Option<Result<T, String>> {
+        match self.receiver.try_recv() {
+            Ok(result) => Some(Ok(result)),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(format!("{} failed", self.title))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn wait_for<T: Send + 'static>(task: &BackgroundTask<T>) -> Result<T, String> {
+        for _ in 0..200 {
+            if let Some(result) = task.try_finish() {
+                return result;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        panic!("task did not finish");
+    }
+
+    #[test]
+    fn test_task_reports_result_and_progress() {
+        let task = BackgroundTask::spawn("Counting", |progress| {
+            progress.report(0.5, "Halfway");
+            21 * 2
+        });
+        assert_eq!(wait_for(&task), Ok(42));
+        assert_eq!(task.progress().message, "Halfway");
+    }
+
+    #[test]
+    fn test_cancelled_task_stops_early() {
+        let task = BackgroundTask::spawn("Spinning", |progress| {
+            let mut rounds = 0;
+            while !progress.is_cancelled() {
+                rounds += 1;
+                thread::sleep(Duration::from_millis(1));
+            }
+            rounds
+        });
+        task.cancel();
+        assert!(wait_for(&task).is_ok());
+    }
+}
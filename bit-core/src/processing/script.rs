@@ -0,0 +1,675 @@
+// Bit scripts - a small sandboxed language for one-off transforms of the bit buffer
+//
+// Scripts only see the bit buffer through the built-in functions below; there is no
+// file, network or process access. Every run has a step budget and a wall-clock limit.
+//
+//   let n = len();                   // number of bits
+//   get(i) / set(i, v)               // read / write one bit (v is 0 or 1)
+//   get_bits(i, n) / set_bits(i, n, v)   // up to 64 bits as a number, MSB first
+//   push(v) / push_bits(n, v)        // append bits
+//   truncate(n)                      // keep the first n bits
+//   copy(from, to, n)                // copy n bits within the buffer
+//   reverse(i, n) / invert(i, n)     // reverse or invert a slice
+//
+// Statements: `let x = e;`, `x = e;`, `if c { } else { }`, `while c { }`, `for i in a..b { }`.
+// Values are 64-bit integers; comparisons and `true`/`false` give 1 or 0.
+
+use bitvec::prelude::*;
+use std::time::{Duration, Instant};
+
+/// Statements and loop iterations a script may run before it is stopped
+const MAX_STEPS: u64 = 500_000_000;
+
+/// Largest buffer a script may grow, in bits (1 GiB)
+const MAX_SCRIPT_BITS: usize = 1 << 33;
+
+/// How often (in steps) the wall-clock limit is checked
+const DEADLINE_CHECK_STEPS: u64 = 4096;
+
+/// Default wall-clock limit for a script run, in milliseconds
+pub const DEFAULT_SCRIPT_TIME_LIMIT_MS: u64 = 5000;
+
+/// A parsed script, ready to run
+#[derive(Debug, Clone)]
+pub struct BitScript {
+    statements: Vec<Stmt>,
+    variable_count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Int(i64),
+    Ident(String),
+    Symbol(&'static str),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BinOp {
+    Or, And, BitOr, BitXor, BitAnd,
+    Eq, Ne, Lt, Le, Gt, Ge,
+    Shl, Shr, Add, Sub, Mul, Div, Rem,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Int(i64),
+    Var(usize),
+    Neg(Box<Expr>),
+    Not(Box<Expr>),
+    Binary(BinOp, Box<Expr>, Box<Expr>),
+    Call(Builtin, Vec<Expr>),
+}
+
+#[derive(Debug, Clone)]
+enum Stmt {
+    Assign(usize, Expr),
+    Expr(Expr),
+    If(Expr, Vec<Stmt>, Vec<Stmt>),
+    While(Expr, Vec<Stmt>),
+    For(usize, Expr, Expr, Vec<Stmt>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Builtin {
+    Len, Get, Set, GetBits, SetBits, Push, PushBits, Truncate, Copy, Reverse, Invert,
+}
+
+impl Builtin {
+    fn lookup(name: &str) -> Option<(Builtin, usize)> {
+        Some(match name {
+            "len" => (Builtin::Len, 0),
+            "get" => (Builtin::Get, 1),
+            "set" => (Builtin::Set, 2),
+            "get_bits" => (Builtin::GetBits, 2),
+            "set_bits" => (Builtin::SetBits, 3),
+            "push" => (Builtin::Push, 1),
+            "push_bits" => (Builtin::PushBits, 2),
+            "truncate" => (Builtin::Truncate, 1),
+            "copy" => (Builtin::Copy, 3),
+            "reverse" => (Builtin::Reverse, 2),
+            "invert" => (Builtin::Invert, 2),
+            _ => return None,
+        })
+    }
+}
+
+const SYMBOLS: &[&str] = &[
+    "..", "==", "!=", "<=", ">=", "&&", "||", "<<", ">>",
+    "(", ")", "{", "}", ",", ";", "=", "+", "-", "*", "/", "%", "<", ">", "!", "&", "|", "^",
+];
+
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, String> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = source.chars().collect();
+    let mut i = 0;
+    let mut line = 1;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\n' {
+            line += 1;
+            i += 1;
+        } else if c.is_whitespace() {
+            i += 1;
+        } else if c == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().filter(|&&c| c != '_').collect();
+            let value = if let Some(hex) = text.strip_prefix("0x") {
+                i64::from_str_radix(hex, 16)
+            } else if let Some(bin) = text.strip_prefix("0b") {
+                i64::from_str_radix(bin, 2)
+            } else {
+                text.parse()
+            };
+            let value = value.map_err(|_| format!("line {}: invalid number '{}'", line, text))?;
+            tokens.push((Token::Int(value), line));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push((Token::Ident(chars[start..i].iter().collect()), line));
+        } else {
+            let rest: String = chars[i..(i + 2).min(chars.len())].iter().collect();
+            let symbol = SYMBOLS
+                .iter()
+                .find(|symbol| rest.starts_with(**symbol))
+                .ok_or_else(|| format!("line {}: unexpected character '{}'", line, c))?;
+            tokens.push((Token::Symbol(symbol), line));
+            i += symbol.len();
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    variables: Vec<String>,
+}
+
+impl Parser {
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .or(self.tokens.last())
+            .map_or(1, |(_, line)| *line)
+    }
+
+    fn error<T>(&self, message: impl std::fmt::Display) -> Result<T, String> {
+        Err(format!("line {}: {}", self.line(), message))
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    fn peek_symbol(&self, symbol: &str) -> bool {
+        matches!(self.peek(), Some(Token::Symbol(s)) if *s == symbol)
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Ident(s)) if s == keyword)
+    }
+
+    fn eat_symbol(&mut self, symbol: &str) -> bool {
+        let found = self.peek_symbol(symbol);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> Result<(), String> {
+        if self.eat_symbol(symbol) {
+            Ok(())
+        } else {
+            self.error(format!("expected '{}'", symbol))
+        }
+    }
+
+    fn expect_ident(&mut self) -> Result<String, String> {
+        match self.peek() {
+            Some(Token::Ident(name)) if !is_keyword(name) => {
+                let name = name.clone();
+                self.pos += 1;
+                Ok(name)
+            }
+            _ => self.error("expected a name"),
+        }
+    }
+
+    fn variable(&self, name: &str) -> Option<usize> {
+        self.variables.iter().position(|v| v == name)
+    }
+
+    /// Slot for `name`, creating it on first declaration
+    fn declare(&mut self, name: String) -> usize {
+        self.variable(&name).unwrap_or_else(|| {
+            self.variables.push(name);
+            self.variables.len() - 1
+        })
+    }
+
+    fn block(&mut self) -> Result<Vec<Stmt>, String> {
+        self.expect_symbol("{")?;
+        let mut statements = Vec::new();
+        while !self.eat_symbol("}") {
+            if self.peek().is_none() {
+                return self.error("expected '}'");
+            }
+            statements.push(self.statement()?);
+        }
+        Ok(statements)
+    }
+
+    fn statement(&mut self) -> Result<Stmt, String> {
+        if self.peek_keyword("let") {
+            self.pos += 1;
+            let name = self.expect_ident()?;
+            self.expect_symbol("=")?;
+            let value = self.expression()?;
+            self.expect_symbol(";")?;
+            return Ok(Stmt::Assign(self.declare(name), value));
+        }
+
+        if self.peek_keyword("if") {
+            self.pos += 1;
+            let condition = self.expression()?;
+            let then_block = self.block()?;
+            let else_block = if self.peek_keyword("else") {
+                self.pos += 1;
+                if self.peek_keyword("if") {
+                    vec![self.statement()?]
+                } else {
+                    self.block()?
+                }
+            } else {
+                Vec::new()
+            };
+            return Ok(Stmt::If(condition, then_block, else_block));
+        }
+
+        if self.peek_keyword("while") {
+            self.pos += 1;
+            let condition = self.expression()?;
+            return Ok(Stmt::While(condition, self.block()?));
+        }
+
+        if self.peek_keyword("for") {
+            self.pos += 1;
+            let name = self.expect_ident()?;
+            if !self.peek_keyword("in") {
+                return self.error("expected 'in'");
+            }
+            self.pos += 1;
+            let from = self.expression()?;
+            self.expect_symbol("..")?;
+            let to = self.expression()?;
+            let slot = self.declare(name);
+            return Ok(Stmt::For(slot, from, to, self.block()?));
+        }
+
+        // Assignment to an existing variable
+        if let (Some(Token::Ident(name)), Some((Token::Symbol("="), _))) = (self.peek(), self.tokens.get(self.pos + 1)) {
+            let Some(slot) = self.variable(name) else {
+                return self.error(format!("unknown variable '{}' (declare it with let)", name));
+            };
+            self.pos += 2;
+            let value = self.expression()?;
+            self.expect_symbol(";")?;
+            return Ok(Stmt::Assign(slot, value));
+        }
+
+        let expr = self.expression()?;
+        self.expect_symbol(";")?;
+        Ok(Stmt::Expr(expr))
+    }
+
+    fn expression(&mut self) -> Result<Expr, String> {
+        self.binary(0)
+    }
+
+    fn binary(&mut self, min_precedence: u8) -> Result<Expr, String> {
+        let mut lhs = self.unary()?;
+        while let Some((op, precedence)) = self.peek().and_then(binary_operator) {
+            if precedence < min_precedence {
+                break;
+            }
+            self.pos += 1;
+            let rhs = self.binary(precedence + 1)?;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.eat_symbol("-") {
+            return Ok(Expr::Neg(Box::new(self.unary()?)));
+        }
+        if self.eat_symbol("!") {
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        match self.peek().cloned() {
+            Some(Token::Int(value)) => {
+                self.pos += 1;
+                Ok(Expr::Int(value))
+            }
+            Some(Token::Symbol("(")) => {
+                self.pos += 1;
+                let expr = self.expression()?;
+                self.expect_symbol(")")?;
+                Ok(expr)
+            }
+            Some(Token::Ident(name)) if name == "true" || name == "false" => {
+                self.pos += 1;
+                Ok(Expr::Int((name == "true") as i64))
+            }
+            Some(Token::Ident(name)) if self.tokens.get(self.pos + 1).is_some_and(|(t, _)| *t == Token::Symbol("(")) => {
+                let Some((builtin, arity)) = Builtin::lookup(&name) else {
+                    return self.error(format!("unknown function '{}'", name));
+                };
+                self.pos += 2;
+                let mut args = Vec::new();
+                if !self.eat_symbol(")") {
+                    loop {
+                        args.push(self.expression()?);
+                        if self.eat_symbol(")") {
+                            break;
+                        }
+                        self.expect_symbol(",")?;
+                    }
+                }
+                if args.len() != arity {
+                    return self.error(format!("{}() takes {} argument(s), got {}", name, arity, args.len()));
+                }
+                Ok(Expr::Call(builtin, args))
+            }
+            Some(Token::Ident(name)) if !is_keyword(&name) => match self.variable(&name) {
+                Some(slot) => {
+                    self.pos += 1;
+                    Ok(Expr::Var(slot))
+                }
+                None => self.error(format!("unknown variable '{}'", name)),
+            },
+            _ => self.error("expected an expression"),
+        }
+    }
+}
+
+fn is_keyword(name: &str) -> bool {
+    matches!(name, "let" | "if" | "else" | "while" | "for" | "in" | "true" | "false")
+}
+
+/// Operator and precedence for a binary operator token; higher binds tighter
+fn binary_operator(token: &Token) -> Option<(BinOp, u8)> {
+    let Token::Symbol(symbol) = token else {
+        return None;
+    };
+    Some(match *symbol {
+        "||" => (BinOp::Or, 1),
+        "&&" => (BinOp::And, 2),
+        "|" => (BinOp::BitOr, 3),
+        "^" => (BinOp::BitXor, 4),
+        "&" => (BinOp::BitAnd, 5),
+        "==" => (BinOp::Eq, 6),
+        "!=" => (BinOp::Ne, 6),
+        "<" => (BinOp::Lt, 7),
+        "<=" => (BinOp::Le, 7),
+        ">" => (BinOp::Gt, 7),
+        ">=" => (BinOp::Ge, 7),
+        "<<" => (BinOp::Shl, 8),
+        ">>" => (BinOp::Shr, 8),
+        "+" => (BinOp::Add, 9),
+        "-" => (BinOp::Sub, 9),
+        "*" => (BinOp::Mul, 10),
+        "/" => (BinOp::Div, 10),
+        "%" => (BinOp::Rem, 10),
+        _ => return None,
+    })
+}
+
+/// State of one script run
+struct Machine<'a> {
+    bits: &'a mut BitVec<u8, Msb0>,
+    variables: Vec<i64>,
+    steps: u64,
+    deadline: Instant,
+}
+
+impl Machine<'_> {
+    fn step(&mut self) -> Result<(), String> {
+        self.steps += 1;
+        if self.steps > MAX_STEPS {
+            return Err(format!("script stopped after {} steps", MAX_STEPS));
+        }
+        if self.steps.is_multiple_of(DEADLINE_CHECK_STEPS) && Instant::now() > self.deadline {
+            return Err("script exceeded its time limit".to_string());
+        }
+        Ok(())
+    }
+
+    fn run_block(&mut self, statements: &[Stmt]) -> Result<(), String> {
+        for statement in statements {
+            self.run(statement)?;
+        }
+        Ok(())
+    }
+
+    fn run(&mut self, statement: &Stmt) -> Result<(), String> {
+        self.step()?;
+        match statement {
+            Stmt::Assign(slot, value) => {
+                self.variables[*slot] = self.eval(value)?;
+            }
+            Stmt::Expr(expr) => {
+                self.eval(expr)?;
+            }
+            Stmt::If(condition, then_block, else_block) => {
+                if self.eval(condition)? != 0 {
+                    self.run_block(then_block)?;
+                } else {
+                    self.run_block(else_block)?;
+                }
+            }
+            Stmt::While(condition, body) => {
+                while self.eval(condition)? != 0 {
+                    self.step()?;
+                    self.run_block(body)?;
+                }
+            }
+            Stmt::For(slot, from, to, body) => {
+                let (from, to) = (self.eval(from)?, self.eval(to)?);
+                for i in from..to {
+                    self.step()?;
+                    self.variables[*slot] = i;
+                    self.run_block(body)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn eval(&mut self, expr: &Expr) -> Result<i64, String> {
+        Ok(match expr {
+            Expr::Int(value) => *value,
+            Expr::Var(slot) => self.variables[*slot],
+            Expr::Neg(inner) => self.eval(inner)?.wrapping_neg(),
+            Expr::Not(inner) => (self.eval(inner)? == 0) as i64,
+            Expr::Binary(BinOp::And, lhs, rhs) => (self.eval(lhs)? != 0 && self.eval(rhs)? != 0) as i64,
+            Expr::Binary(BinOp::Or, lhs, rhs) => (self.eval(lhs)? != 0 || self.eval(rhs)? != 0) as i64,
+            Expr::Binary(op, lhs, rhs) => {
+                let (a, b) = (self.eval(lhs)?, self.eval(rhs)?);
+                match op {
+                    BinOp::BitOr => a | b,
+                    BinOp::BitXor => a ^ b,
+                    BinOp::BitAnd => a & b,
+                    BinOp::Eq => (a == b) as i64,
+                    BinOp::Ne => (a != b) as i64,
+                    BinOp::Lt => (a < b) as i64,
+                    BinOp::Le => (a <= b) as i64,
+                    BinOp::Gt => (a > b) as i64,
+                    BinOp::Ge => (a >= b) as i64,
+                    BinOp::Shl => a.wrapping_shl(b as u32),
+                    BinOp::Shr => ((a as u64).wrapping_shr(b as u32)) as i64,
+                    BinOp::Add => a.wrapping_add(b),
+                    BinOp::Sub => a.wrapping_sub(b),
+                    BinOp::Mul => a.wrapping_mul(b),
+                    BinOp::Div | BinOp::Rem if b == 0 => return Err("division by zero".to_string()),
+                    BinOp::Div => a.wrapping_div(b),
+                    BinOp::Rem => a.wrapping_rem(b),
+                    BinOp::And | BinOp::Or => unreachable!("short-circuit operators are handled above"),
+                }
+            }
+            Expr::Call(builtin, args) => {
+                let mut values = [0i64; 3];
+                for (value, arg) in values.iter_mut().zip(args) {
+                    *value = self.eval(arg)?;
+                }
+                self.call(*builtin, values)?
+            }
+        })
+    }
+
+    /// `start..start + count` as a range inside the buffer
+    fn range(&self, start: i64, count: i64) -> Result<std::ops::Range<usize>, String> {
+        let len = self.bits.len();
+        if start < 0 || count < 0 || start as usize > len || count as usize > len - start as usize {
+            return Err(format!("bits {}..{} are outside the buffer ({} bits)", start, start.saturating_add(count), len));
+        }
+        Ok(start as usize..(start + count) as usize)
+    }
+
+    fn check_growth(&self, extra: usize) -> Result<(), String> {
+        if self.bits.len() + extra > MAX_SCRIPT_BITS {
+            return Err(format!("script output is larger than {} bits", MAX_SCRIPT_BITS));
+        }
+        Ok(())
+    }
+
+    fn call(&mut self, builtin: Builtin, [a, b, c]: [i64; 3]) -> Result<i64, String> {
+        match builtin {
+            Builtin::Len => return Ok(self.bits.len() as i64),
+            Builtin::Get => {
+                let range = self.range(a, 1)?;
+                return Ok(self.bits[range.start] as i64);
+            }
+            Builtin::Set => {
+                let range = self.range(a, 1)?;
+                self.bits.set(range.start, b != 0);
+            }
+            Builtin::GetBits => {
+                let range = self.range(a, b.clamp(0, 64))?;
+                if range.is_empty() {
+                    return Ok(0);
+                }
+                return Ok(self.bits[range].load_be::<u64>() as i64);
+            }
+            Builtin::SetBits => {
+                let range = self.range(a, b.clamp(0, 64))?;
+                if !range.is_empty() {
+                    self.bits[range].store_be(c as u64);
+                }
+            }
+            Builtin::Push => {
+                self.check_growth(1)?;
+                self.bits.push(a != 0);
+            }
+            Builtin::PushBits => {
+                let count = a.clamp(0, 64) as usize;
+                self.check_growth(count)?;
+                for i in (0..count).rev() {
+                    self.bits.push((b as u64 >> i) & 1 == 1);
+                }
+            }
+            Builtin::Truncate => self.bits.truncate(a.max(0) as usize),
+            Builtin::Copy => {
+                let from = self.range(a, c)?;
+                let to = self.range(b, c)?;
+                if !from.is_empty() {
+                    self.bits.copy_within(from, to.start);
+                }
+            }
+            Builtin::Reverse => {
+                let range = self.range(a, b)?;
+                self.bits[range].reverse();
+            }
+            Builtin::Invert => {
+                let range = self.range(a, b)?;
+                let _ = !&mut self.bits[range];
+            }
+        }
+        Ok(0)
+    }
+}
+
+impl BitScript {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut parser = Parser { tokens: tokenize(source)?, pos: 0, variables: Vec::new() };
+        let mut statements = Vec::new();
+        while parser.peek().is_some() {
+            statements.push(parser.statement()?);
+        }
+        Ok(Self { statements, variable_count: parser.variables.len() })
+    }
+
+    /// Run the script over `bits`, stopping with an error after `time_limit`
+    /// On error `bits` holds whatever the script had done so far
+    pub fn run(&self, bits: &mut BitVec<u8, Msb0>, time_limit: Duration) -> Result<(), String> {
+        let mut machine = Machine {
+            bits,
+            variables: vec![0; self.variable_count],
+            steps: 0,
+            deadline: Instant::now() + time_limit,
+        };
+        machine.run_block(&self.statements)
+    }
+}
+
+/// Parse and run `source` over `bits`, leaving `bits` unchanged if the script fails
+pub fn run_script(source: &str, bits: &mut BitVec<u8, Msb0>, time_limit_ms: u64) -> Result<(), String> {
+    let script = BitScript::parse(source)?;
+    let mut output = bits.clone();
+    script.run(&mut output, Duration::from_millis(time_limit_ms))?;
+    *bits = output;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(source: &str, input: BitVec<u8, Msb0>) -> Result<BitVec<u8, Msb0>, String> {
+        let mut bits = input;
+        run_script(source, &mut bits, 1000)?;
+        Ok(bits)
+    }
+
+    #[test]
+    fn test_script_rewrites_bits() {
+        // Differential decode: each output bit is the XOR of neighbouring input bits
+        let source = "
+            let prev = 0;
+            for i in 0..len() {
+                let cur = get(i);
+                set(i, cur ^ prev);
+                prev = cur;
+            }
+        ";
+        let result = run(source, bitvec![u8, Msb0; 1, 1, 0, 0, 1]).unwrap();
+        assert_eq!(result, bitvec![u8, Msb0; 1, 0, 1, 0, 1]);
+    }
+
+    #[test]
+    fn test_script_builds_new_output() {
+        // Swap the nibbles of each byte, writing the result after the input
+        let source = "
+            let n = len() / 8;
+            for i in 0..n {
+                let b = get_bits(i * 8, 8);
+                push_bits(8, ((b << 4) | (b >> 4)) & 0xFF);
+            }
+            copy(n * 8, 0, n * 8);
+            truncate(n * 8);
+        ";
+        let mut input = BitVec::<u8, Msb0>::new();
+        input.extend_from_bitslice([0x12u8, 0xAB].view_bits::<Msb0>());
+        let result = run(source, input).unwrap();
+        assert_eq!(result.load_be::<u16>(), 0x21BA);
+    }
+
+    #[test]
+    fn test_parse_errors_report_line() {
+        let err = BitScript::parse("let a = 1;\nb = 2;").unwrap_err();
+        assert!(err.starts_with("line 2"), "{}", err);
+        assert!(BitScript::parse("set(1);").unwrap_err().contains("takes 2"));
+    }
+
+    #[test]
+    fn test_failed_script_leaves_input_unchanged() {
+        let input = bitvec![u8, Msb0; 1, 0, 1];
+        let mut bits = input.clone();
+        let err = run_script("set(0, 0); get(99);", &mut bits, 1000).unwrap_err();
+        assert!(err.contains("outside"), "{}", err);
+        assert_eq!(bits, input);
+    }
+
+    #[test]
+    fn test_runaway_script_is_stopped() {
+        let mut bits = BitVec::<u8, Msb0>::new();
+        let err = run_script("while true { }", &mut bits, 50).unwrap_err();
+        assert!(err.contains("time limit"), "{}", err);
+    }
+}
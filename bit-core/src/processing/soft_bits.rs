@@ -0,0 +1,111 @@
+// Soft bits - demodulator output with one sample per bit, sliced to bits while keeping how
+// sure each decision was
+
+use super::majority::MajorityVote;
+use bitvec::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// How each sample is stored; in every format a positive sample is a 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SoftFormat {
+    /// Signed bytes, -128 to 127
+    SignedByte,
+    /// Unsigned bytes centred on 128, as RTL-SDR style tools write them
+    UnsignedByte,
+    /// Little-endian 32-bit floats, at any scale
+    Float32,
+}
+
+impl SoftFormat {
+    pub const ALL: [SoftFormat; 3] = [SoftFormat::SignedByte, SoftFormat::UnsignedByte, SoftFormat::Float32];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SoftFormat::SignedByte => "Signed bytes (i8)",
+            SoftFormat::UnsignedByte => "Unsigned bytes (u8, 128 = 0)",
+            SoftFormat::Float32 => "32-bit floats (LE)",
+        }
+    }
+
+    pub fn sample_bytes(&self) -> usize {
+        match self {
+            SoftFormat::SignedByte | SoftFormat::UnsignedByte => 1,
+            SoftFormat::Float32 => 4,
+        }
+    }
+
+    /// Samples stored in `bytes`, centred on 0; a trailing partial sample is dropped and
+    /// non-finite floats read as 0
+    pub fn samples(&self, bytes: &[u8]) -> Vec<f32> {
+        match self {
+            SoftFormat::SignedByte => bytes.iter().map(|&b| b as i8 as f32).collect(),
+            SoftFormat::UnsignedByte => bytes.iter().map(|&b| b as f32 - 128.0).collect(),
+            SoftFormat::Float32 => bytes
+                .chunks_exact(4)
+                .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+                .map(|sample| if sample.is_finite() { sample } else { 0.0 })
+                .collect(),
+        }
+    }
+}
+
+/// Hard decisions with a confidence per bit, from 0 (a sample on the threshold) to 255
+/// (as strong as the strongest sample)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SoftBits {
+    pub bits: BitVec<u8, Msb0>,
+    pub confidence: Vec<u8>,
+}
+
+impl SoftBits {
+    /// `samples` sliced at 0
+    pub fn from_samples(samples: &[f32]) -> Self {
+        let peak = samples.iter().fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+        let scale = if peak > 0.0 { 255.0 / peak } else { 0.0 };
+        Self {
+            bits: samples.iter().map(|&sample| sample > 0.0).collect(),
+            confidence: samples.iter().map(|sample| (sample.abs() * scale).round() as u8).collect(),
+        }
+    }
+
+    /// Soft data stored as `format` in `bytes`, with repeated copies combined by `combine`
+    /// before slicing so the confidences decide the vote
+    pub fn decode(bytes: &[u8], format: SoftFormat, combine: Option<&MajorityVote>) -> Self {
+        let samples = format.samples(bytes);
+        match combine {
+            Some(vote) => Self::from_samples(&vote.combine_soft(&samples)),
+            None => Self::from_samples(&samples),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processing::{TieBreak, VoteGrouping};
+
+    #[test]
+    fn test_samples_slice_with_confidence() {
+        let soft = SoftBits::decode(&[0x7F, 0x81, 0x00, 0x20], SoftFormat::SignedByte, None);
+        assert_eq!(soft.bits, bitvec![u8, Msb0; 1, 0, 0, 1]);
+        assert_eq!(soft.confidence, vec![255, 255, 0, 64]);
+
+        let soft = SoftBits::decode(&[200, 128, 56], SoftFormat::UnsignedByte, None);
+        assert_eq!(soft.bits, bitvec![u8, Msb0; 1, 0, 0]);
+        assert_eq!(soft.confidence, vec![255, 0, 255]);
+
+        let bytes: Vec<u8> = [0.5f32, -2.0, f32::NAN].iter().flat_map(|s| s.to_le_bytes()).chain([1]).collect();
+        let soft = SoftBits::decode(&bytes, SoftFormat::Float32, None);
+        assert_eq!(soft.bits, bitvec![u8, Msb0; 1, 0, 0]);
+        assert_eq!(soft.confidence, vec![64, 255, 0]);
+    }
+
+    #[test]
+    fn test_combined_copies() {
+        let vote = MajorityVote { grouping: VoteGrouping::Bits { repeats: 3 }, tie_break: TieBreak::Zero };
+        // A hard vote would give 0; the one confident copy wins
+        let soft = SoftBits::decode(&[0xFE, 0xFE, 0x70], SoftFormat::SignedByte, Some(&vote));
+        assert_eq!(soft.bits, bitvec![u8, Msb0; 1]);
+        assert_eq!(soft.confidence, vec![255]);
+    }
+}
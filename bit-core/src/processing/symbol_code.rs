@@ -0,0 +1,238 @@
+// Symbol codes - Gray and BCD coded values converted symbol by symbol, as read from
+// rotary encoders and industrial telemetry, and symbols remapped through lookup tables
+
+use bitvec::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SymbolCode {
+    /// Reflected binary Gray code to plain binary
+    GrayToBinary,
+    /// Plain binary to reflected binary Gray code
+    BinaryToGray,
+    /// Packed BCD, four bits per decimal digit with the most significant digit first, to binary
+    BcdToBinary,
+}
+
+impl SymbolCode {
+    pub const ALL: [SymbolCode; 3] = [SymbolCode::GrayToBinary, SymbolCode::BinaryToGray, SymbolCode::BcdToBinary];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SymbolCode::GrayToBinary => "Gray to Binary",
+            SymbolCode::BinaryToGray => "Binary to Gray",
+            SymbolCode::BcdToBinary => "BCD to Binary",
+        }
+    }
+
+    /// Why `symbol_bits` can't be used with this code
+    pub fn check_width(&self, symbol_bits: usize) -> Result<(), String> {
+        if !(1..=64).contains(&symbol_bits) {
+            return Err("Symbol width must be 1 to 64 bits".to_string());
+        }
+        if *self == SymbolCode::BcdToBinary && !symbol_bits.is_multiple_of(4) {
+            return Err("BCD symbols must be a multiple of 4 bits".to_string());
+        }
+        Ok(())
+    }
+
+    /// `value` converted, or None if it isn't valid in this code
+    fn convert(&self, value: u64) -> Option<u64> {
+        match self {
+            SymbolCode::GrayToBinary => {
+                let mut binary = value;
+                let mut shift = 1;
+                while shift < 64 {
+                    binary ^= binary >> shift;
+                    shift <<= 1;
+                }
+                Some(binary)
+            }
+            SymbolCode::BinaryToGray => Some(value ^ (value >> 1)),
+            SymbolCode::BcdToBinary => {
+                let mut binary = 0u64;
+                for digit in (0..16).rev().map(|i| (value >> (i * 4)) & 0xF) {
+                    if digit > 9 {
+                        return None;
+                    }
+                    binary = binary * 10 + digit;
+                }
+                Some(binary)
+            }
+        }
+    }
+}
+
+/// `bits` converted with `code` in `symbol_bits`-bit symbols from the first bit. Symbols keep
+/// their width, since the converted value always fits. A trailing partial symbol and BCD
+/// symbols with a digit above 9 are left unchanged. Returns the number of symbols left
+/// unchanged because they weren't valid.
+pub fn convert_symbols(code: SymbolCode, symbol_bits: usize, bits: &mut BitSlice<u8, Msb0>) -> usize {
+    if code.check_width(symbol_bits).is_err() {
+        return 0;
+    }
+    let mut invalid = 0;
+    for symbol in bits.chunks_exact_mut(symbol_bits) {
+        match code.convert(symbol.load_be::<u64>()) {
+            Some(value) => symbol.store_be(value),
+            None => invalid += 1,
+        }
+    }
+    invalid
+}
+
+/// A lookup table from `input_bits`-bit symbols to `output_bits`-bit symbols, for custom
+/// constellation-to-bits mappings or nibble substitutions
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SymbolMap {
+    pub input_bits: usize,
+    pub output_bits: usize,
+    /// Output value of each input value; values not listed pass through, cut to `output_bits`
+    pub table: BTreeMap<u64, u64>,
+}
+
+impl SymbolMap {
+    /// Why the map can't be applied
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=64).contains(&self.input_bits) || !(1..=64).contains(&self.output_bits) {
+            return Err("Symbol widths must be 1 to 64 bits".to_string());
+        }
+        let too_wide = |value: u64, bits: usize| bits < 64 && value >> bits != 0;
+        if let Some(input) = self.table.keys().find(|&&input| too_wide(input, self.input_bits)) {
+            return Err(format!("Input 0x{:X} doesn't fit in {} bits", input, self.input_bits));
+        }
+        if let Some(output) = self.table.values().find(|&&output| too_wide(output, self.output_bits)) {
+            return Err(format!("Output 0x{:X} doesn't fit in {} bits", output, self.output_bits));
+        }
+        Ok(())
+    }
+
+    /// `bits` mapped symbol by symbol from the first bit; a trailing partial symbol is dropped
+    pub fn apply(&self, bits: &BitSlice<u8, Msb0>) -> BitVec<u8, Msb0> {
+        if self.validate().is_err() {
+            return bits.to_bitvec();
+        }
+        let mask = if self.output_bits == 64 { u64::MAX } else { (1u64 << self.output_bits) - 1 };
+        let symbols = bits.len() / self.input_bits;
+        let mut output = BitVec::with_capacity(symbols * self.output_bits);
+        let mut symbol = bitvec![u8, Msb0; 0; self.output_bits];
+        for chunk in bits.chunks_exact(self.input_bits) {
+            let value = chunk.load_be::<u64>();
+            symbol.store_be(self.table.get(&value).copied().unwrap_or(value) & mask);
+            output.extend_from_bitslice(&symbol);
+        }
+        output
+    }
+
+    /// A table from text with one `input,output` pair per line, as typed or read from CSV.
+    /// Values are decimal, 0x hex or 0b binary; blank lines, `#` comments and a non-numeric
+    /// header line are skipped.
+    pub fn parse_table(text: &str) -> Result<BTreeMap<u64, u64>, String> {
+        let mut table = BTreeMap::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields: Vec<&str> = line.split([',', ';', '\t', '=']).map(str::trim).collect();
+            let parsed = match fields.as_slice() {
+                [input, output, ..] => parse_value(input).and_then(|input| Ok((input, parse_value(output)?))),
+                _ => Err("expected input,output".to_string()),
+            };
+            match parsed {
+                Ok((input, output)) => {
+                    if table.insert(input, output).is_some() {
+                        return Err(format!("Line {}: 0x{:X} is mapped twice", index + 1, input));
+                    }
+                }
+                // A header row names the columns
+                Err(_) if table.is_empty() && index == 0 => {}
+                Err(e) => return Err(format!("Line {}: {}", index + 1, e)),
+            }
+        }
+        Ok(table)
+    }
+
+    /// The table as `parse_table` reads it, in hex
+    pub fn table_text(&self) -> String {
+        self.table.iter().map(|(input, output)| format!("0x{:X},0x{:X}\n", input, output)).collect()
+    }
+}
+
+fn parse_value(text: &str) -> Result<u64, String> {
+    let text = text.trim();
+    let (digits, radix) = if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        (hex, 16)
+    } else if let Some(binary) = text.strip_prefix("0b").or_else(|| text.strip_prefix("0B")) {
+        (binary, 2)
+    } else {
+        (text, 10)
+    };
+    u64::from_str_radix(&digits.replace('_', ""), radix).map_err(|_| format!("Invalid value '{}'", text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn symbols(values: &[u64], symbol_bits: usize) -> BitVec<u8, Msb0> {
+        let mut bits = BitVec::new();
+        for &value in values {
+            bits.extend((0..symbol_bits).rev().map(|i| (value >> i) & 1 == 1));
+        }
+        bits
+    }
+
+    #[test]
+    fn test_gray_round_trip() {
+        let binary: Vec<u64> = (0..32).collect();
+        let gray = [0, 1, 3, 2, 6, 7, 5, 4, 12, 13, 15, 14, 10, 11, 9, 8];
+        let mut bits = symbols(&binary, 5);
+        assert_eq!(convert_symbols(SymbolCode::BinaryToGray, 5, &mut bits), 0);
+        assert_eq!(&bits[..16 * 5], &symbols(&gray, 5)[..]);
+
+        convert_symbols(SymbolCode::GrayToBinary, 5, &mut bits);
+        assert_eq!(bits, symbols(&binary, 5));
+
+        // Full-width symbols convert too
+        let mut wide = symbols(&[u64::MAX], 64);
+        convert_symbols(SymbolCode::GrayToBinary, 64, &mut wide);
+        assert_eq!(wide, symbols(&[0xAAAA_AAAA_AAAA_AAAA], 64));
+    }
+
+    #[test]
+    fn test_bcd_to_binary() {
+        let mut bits = symbols(&[0x1234, 0x0099, 0x12A4], 16);
+        // A trailing partial symbol is left alone
+        bits.extend([true, false, true]);
+        assert_eq!(convert_symbols(SymbolCode::BcdToBinary, 16, &mut bits), 1);
+
+        let mut expected = symbols(&[1234, 99, 0x12A4], 16);
+        expected.extend([true, false, true]);
+        assert_eq!(bits, expected);
+
+        assert!(SymbolCode::BcdToBinary.check_width(10).is_err());
+        assert!(SymbolCode::GrayToBinary.check_width(10).is_ok());
+        assert!(SymbolCode::GrayToBinary.check_width(65).is_err());
+    }
+
+    #[test]
+    fn test_symbol_map() {
+        let table = SymbolMap::parse_table("in,out\n# QPSK\n0, 0b00\n1=0b01\n0x2;3\n").unwrap();
+        assert_eq!(table.len(), 3);
+        assert!(SymbolMap::parse_table("0,1\n0,2").is_err());
+        assert!(SymbolMap::parse_table("0,1\nzz,2").is_err());
+
+        // 2-bit symbols in, 4-bit symbols out; 3 isn't listed and passes through
+        let map = SymbolMap { input_bits: 2, output_bits: 4, table };
+        let input = bitvec![u8, Msb0; 0, 0, 0, 1, 1, 0, 1, 1, 1];
+        assert_eq!(map.apply(&input), bitvec![u8, Msb0; 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 1, 1, 0, 0, 1, 1]);
+        assert_eq!(SymbolMap::parse_table(&map.table_text()).unwrap(), map.table);
+        let json = serde_json::to_string(&map).unwrap();
+        assert_eq!(serde_json::from_str::<SymbolMap>(&json).unwrap(), map);
+
+        let too_wide = SymbolMap { input_bits: 2, output_bits: 1, table: map.table.clone() };
+        assert!(too_wide.validate().is_err());
+    }
+}
@@ -0,0 +1,57 @@
+// Take/Skip presets - named sequences for common bit layouts, built in or saved by the user
+
+use serde::{Deserialize, Serialize};
+
+/// A named Take/Skip sequence
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TakeSkipPreset {
+    pub name: String,
+    pub sequence: String,
+    /// What the sequence does, shown on hover
+    #[serde(default)]
+    pub description: String,
+}
+
+impl TakeSkipPreset {
+    pub fn new(name: impl Into<String>, sequence: impl Into<String>, description: impl Into<String>) -> Self {
+        Self { name: name.into(), sequence: sequence.into(), description: description.into() }
+    }
+}
+
+/// Sequences for layouts that come up again and again
+pub fn builtin_takeskip_presets() -> Vec<TakeSkipPreset> {
+    [
+        ("Drop parity bit every 9th", "t8s1", "Keep 8 data bits, drop the parity bit after them"),
+        ("Strip UART 8N1 framing", "s1t8s1", "Drop the start and stop bit around each 8-bit character"),
+        ("Every other byte", "t8s8", "Keep bytes 0, 2, 4, ..."),
+        ("Odd bytes", "s8t8", "Keep bytes 1, 3, 5, ..."),
+        ("Even bits", "t1s1", "Keep bits 0, 2, 4, ... of a two-channel bit interleave"),
+        ("Odd bits", "s1t1", "Keep bits 1, 3, 5, ... of a two-channel bit interleave"),
+        ("Strip 4-bit header per 68-bit frame", "s4t64", "Drop a 4-bit header in front of each 64-bit payload"),
+        ("Strip 16-bit CRC per 256-byte block", "t2032s16", "Keep 254 bytes, drop the 2-byte checksum ending each block"),
+        ("Reverse bit order in each byte", "r8", "LSB-first bytes to MSB-first"),
+        ("Reverse each nibble", "r4", "Flip the bit order of every 4-bit group"),
+        ("Invert every other byte", "t8i8", "Undo an alternating-inversion scrambler"),
+    ]
+    .into_iter()
+    .map(|(name, sequence, description)| TakeSkipPreset::new(name, sequence, description))
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processing::OperationSequence;
+
+    #[test]
+    fn test_builtin_presets_parse() {
+        let presets = builtin_takeskip_presets();
+        for preset in &presets {
+            assert!(OperationSequence::from_string(&preset.sequence).is_ok(), "{}", preset.name);
+        }
+        let parity = presets.iter().find(|preset| preset.sequence == "t8s1").unwrap();
+        assert_eq!(OperationSequence::from_string(&parity.sequence).unwrap().cycle_length(), 9);
+        let header = presets.iter().find(|preset| preset.name.contains("68-bit")).unwrap();
+        assert_eq!(OperationSequence::from_string(&header.sequence).unwrap().cycle_length(), 68);
+    }
+}
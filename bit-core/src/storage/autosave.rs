@@ -0,0 +1,154 @@
+// Autosave - rotating session snapshots and unclean-exit detection for crash recovery
+
+use super::migration::{migrate_session, parse_versioned};
+use super::session::AppSession;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Number of snapshot files kept; the oldest is overwritten first
+pub const SNAPSHOT_SLOTS: usize = 5;
+
+/// A session snapshot found on disk
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub path: PathBuf,
+    pub saved_at: SystemTime,
+    pub session: AppSession,
+}
+
+impl Snapshot {
+    /// How long ago the snapshot was written, e.g. "5 min ago"
+    pub fn age_label(&self) -> String {
+        let secs = SystemTime::now()
+            .duration_since(self.saved_at)
+            .map(|age| age.as_secs())
+            .unwrap_or(0);
+        match secs {
+            0..60 => format!("{} s ago", secs),
+            60..3600 => format!("{} min ago", secs / 60),
+            3600..86400 => format!("{} h ago", secs / 3600),
+            _ => format!("{} days ago", secs / 86400),
+        }
+    }
+}
+
+fn config_dir() -> PathBuf {
+    let mut path = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
+    path.push("bit");
+    path
+}
+
+pub fn snapshot_dir() -> PathBuf {
+    config_dir().join("snapshots")
+}
+
+/// Marker file that exists while the app is running
+fn running_marker_path() -> PathBuf {
+    config_dir().join("running.lock")
+}
+
+/// Record that the app is running; returns true if the previous run never
+/// reached `mark_clean_exit` (it crashed or was killed)
+pub fn mark_running() -> bool {
+    let path = running_marker_path();
+    let crashed = path.exists();
+    std::fs::create_dir_all(config_dir()).ok();
+    std::fs::write(&path, std::process::id().to_string()).ok();
+    crashed
+}
+
+pub fn mark_clean_exit() {
+    std::fs::remove_file(running_marker_path()).ok();
+}
+
+pub fn write_snapshot(session: &AppSession) -> Result<PathBuf, String> {
+    write_snapshot_in(&snapshot_dir(), session)
+}
+
+/// Write `session` into the oldest (or first unused) snapshot slot in `dir`
+pub fn write_snapshot_in(dir: &Path, session: &AppSession) -> Result<PathBuf, String> {
+    std::fs::create_dir_all(dir)
+        .map_err(|e| format!("Failed to create snapshot directory: {}", e))?;
+
+    let slot = (0..SNAPSHOT_SLOTS)
+        .map(|slot| dir.join(format!("snapshot-{}.json", slot)))
+        .min_by_key(|path| {
+            // Missing slots sort first, then the least recently written
+            std::fs::metadata(path).and_then(|meta| meta.modified()).ok()
+        })
+        .expect("SNAPSHOT_SLOTS is non-zero");
+
+    let json = serde_json::to_string_pretty(session)
+        .map_err(|e| format!("Failed to serialize snapshot: {}", e))?;
+
+    // Write then rename, so a crash mid-write can't corrupt the slot being replaced
+    let tmp = slot.with_extension("json.tmp");
+    std::fs::write(&tmp, json)
+        .map_err(|e| format!("Failed to write snapshot: {}", e))?;
+    std::fs::rename(&tmp, &slot)
+        .map_err(|e| format!("Failed to write snapshot: {}", e))?;
+
+    Ok(slot)
+}
+
+pub fn list_snapshots() -> Vec<Snapshot> {
+    list_snapshots_in(&snapshot_dir())
+}
+
+/// Readable snapshots in `dir`, newest first
+pub fn list_snapshots_in(dir: &Path) -> Vec<Snapshot> {
+    let mut snapshots: Vec<Snapshot> = (0..SNAPSHOT_SLOTS)
+        .filter_map(|slot| {
+            let path = dir.join(format!("snapshot-{}.json", slot));
+            let saved_at = std::fs::metadata(&path).and_then(|meta| meta.modified()).ok()?;
+            let json = std::fs::read_to_string(&path).ok()?;
+            let session = parse_versioned(&json, migrate_session).ok()?;
+            Some(Snapshot { path, saved_at, session })
+        })
+        .collect();
+
+    snapshots.sort_by_key(|snapshot| std::cmp::Reverse(snapshot.saved_at));
+    snapshots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::Worksheet;
+    use std::time::Duration;
+
+    fn session_named(name: &str) -> AppSession {
+        AppSession::new(vec![Worksheet::new(name.to_string())], 0)
+    }
+
+    #[test]
+    fn test_snapshots_rotate_through_slots() {
+        let dir = tempfile::tempdir().unwrap();
+
+        for i in 0..SNAPSHOT_SLOTS + 2 {
+            write_snapshot_in(dir.path(), &session_named(&format!("Sheet {}", i))).unwrap();
+            // Keep modification times distinct on coarse filesystem clocks
+            std::thread::sleep(Duration::from_millis(20));
+        }
+
+        let snapshots = list_snapshots_in(dir.path());
+        assert_eq!(snapshots.len(), SNAPSHOT_SLOTS);
+
+        // Newest first, and the two oldest were overwritten
+        let names: Vec<&str> = snapshots.iter().map(|s| s.session.worksheets[0].name.as_str()).collect();
+        assert_eq!(names[0], format!("Sheet {}", SNAPSHOT_SLOTS + 1));
+        assert!(!names.contains(&"Sheet 0"));
+        assert!(!names.contains(&"Sheet 1"));
+    }
+
+    #[test]
+    fn test_unreadable_snapshots_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        write_snapshot_in(dir.path(), &session_named("Good")).unwrap();
+        std::fs::write(dir.path().join("snapshot-4.json"), "not json").unwrap();
+
+        let snapshots = list_snapshots_in(dir.path());
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].session.worksheets[0].name, "Good");
+    }
+}
@@ -0,0 +1,118 @@
+// File globs - `capture_*.bin` style patterns expanded to the files they match
+
+use std::cmp::Ordering;
+use std::path::{Path, PathBuf};
+
+/// Whether `name` matches `pattern`, where `*` stands for any run of characters and `?`
+/// for exactly one
+pub fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Where the last `*` was, and the name position it's matched up to
+    let mut star: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = star {
+            // Let the `*` take one more character
+            p = star_p + 1;
+            n = star_n + 1;
+            star = Some((star_p, n));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Compares file names with digit runs compared by value, so `chunk_2` sorts before `chunk_10`
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a, b);
+    loop {
+        let (Some(ca), Some(cb)) = (a.chars().next(), b.chars().next()) else {
+            return a.len().cmp(&b.len());
+        };
+        if ca.is_ascii_digit() && cb.is_ascii_digit() {
+            let digits = |s: &str| s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+            let (na, nb) = (digits(a), digits(b));
+            let (va, vb) = (a[..na].trim_start_matches('0'), b[..nb].trim_start_matches('0'));
+            let ordering = va.len().cmp(&vb.len()).then_with(|| va.cmp(vb));
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+            a = &a[na..];
+            b = &b[nb..];
+        } else {
+            if ca != cb {
+                return ca.cmp(&cb);
+            }
+            a = &a[ca.len_utf8()..];
+            b = &b[cb.len_utf8()..];
+        }
+    }
+}
+
+/// The files in a folder whose names match the last component of `pattern`, in natural
+/// order. Only the file name may hold wildcards; a relative folder is taken from the
+/// current directory.
+pub fn expand_glob(pattern: &Path) -> Result<Vec<PathBuf>, String> {
+    let file_pattern = pattern
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| format!("'{}' doesn't end in a file name", pattern.display()))?;
+    let folder = match pattern.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    if folder.to_string_lossy().contains(['*', '?']) {
+        return Err("Wildcards are only supported in the file name".to_string());
+    }
+
+    let entries = std::fs::read_dir(folder).map_err(|e| format!("Failed to read {}: {}", folder.display(), e))?;
+    let mut matches: Vec<(String, PathBuf)> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_file()))
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            wildcard_match(file_pattern, &name).then(|| (name, entry.path()))
+        })
+        .collect();
+    matches.sort_by(|(a, _), (b, _)| natural_cmp(a, b));
+    Ok(matches.into_iter().map(|(_, path)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("capture_*.bin", "capture_01.bin"));
+        assert!(wildcard_match("capture_*.bin", "capture_.bin"));
+        assert!(!wildcard_match("capture_*.bin", "capture_01.bin.bak"));
+        assert!(wildcard_match("*a*b", "xaab"));
+        assert!(wildcard_match("part?.dat", "part7.dat"));
+        assert!(!wildcard_match("part?.dat", "part17.dat"));
+        assert!(wildcard_match("*", ""));
+    }
+
+    #[test]
+    fn test_expand_glob_sorts_numbered_chunks() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["capture_10.bin", "capture_2.bin", "capture_1.bin", "notes.txt"] {
+            std::fs::write(dir.path().join(name), [0u8]).unwrap();
+        }
+        std::fs::create_dir(dir.path().join("capture_3.bin")).unwrap();
+
+        let files = expand_glob(&dir.path().join("capture_*.bin")).unwrap();
+        let names: Vec<_> = files.iter().map(|path| path.file_name().unwrap().to_str().unwrap()).collect();
+        assert_eq!(names, vec!["capture_1.bin", "capture_2.bin", "capture_10.bin"]);
+        assert!(expand_glob(&dir.path().join("*").join("x.bin")).is_err());
+    }
+}
@@ -0,0 +1,535 @@
+use bitvec::prelude::*;
+use memmap2::Mmap;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
+use crate::processing::{CancellationToken, MajorityVote, ProgressReporter, SoftBits, SoftFormat};
+
+/// Maximum file size to read (1 GB)
+pub const MAX_FILE_SIZE: u64 = 1024 * 1024 * 1024;
+
+/// Bits written per chunk when saving (1 MB)
+const WRITE_CHUNK_BITS: usize = 8 * 1024 * 1024;
+
+/// Order in which each byte's bits are read from a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BitOrder {
+    /// Most significant bit first, the usual order
+    #[default]
+    MsbFirst,
+    /// Least significant bit first, as UART and many serial links send them
+    LsbFirst,
+}
+
+impl BitOrder {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BitOrder::MsbFirst => "MSB first",
+            BitOrder::LsbFirst => "LSB first",
+        }
+    }
+}
+
+/// Reorder the bits of each byte read from a file; MSB first leaves them as they are
+pub fn apply_bit_order(bits: &mut BitVec<u8, Msb0>, order: BitOrder) {
+    if order == BitOrder::LsbFirst {
+        for byte in bits.as_raw_mut_slice() {
+            *byte = byte.reverse_bits();
+        }
+    }
+}
+
+/// Bytes a read of `length` bytes from `offset` gets from a file of `file_len` bytes;
+/// a None length reads to the end
+pub fn range_len(file_len: u64, offset: u64, length: Option<u64>) -> u64 {
+    let available = file_len.saturating_sub(offset);
+    length.map_or(available, |length| length.min(available))
+}
+
+/// Open `path` and position it at `offset`, checking that the `length` bytes to read from
+/// there fit in MAX_FILE_SIZE. Returns the file and the number of bytes to read.
+fn open_range(path: &Path, offset: u64, length: Option<u64>) -> io::Result<(File, u64)> {
+    let mut file = File::open(path)?;
+    let size = range_len(file.metadata()?.len(), offset, length);
+    if size > MAX_FILE_SIZE {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("File too large: {} bytes (max {} bytes)", size, MAX_FILE_SIZE)
+        ));
+    }
+    file.seek(SeekFrom::Start(offset))?;
+    Ok((file, size))
+}
+
+/// Progress callback for file loading
+pub enum LoadProgress {
+    Progress { loaded: u64, total: u64 },
+    Complete(Result<BitVec<u8, Msb0>, String>),
+}
+
+/// Read a file and convert its contents to a bit vector with progress reporting,
+/// stopping with an error once `cancel` is set
+/// 
+/// # Errors
+/// 
+/// Returns an error if:
+/// - The file cannot be opened
+/// - The file is larger than MAX_FILE_SIZE
+/// - Reading the file fails
+/// - Loading was cancelled
+pub fn read_file_as_bits_with_progress(
+    path: &Path,
+    progress_tx: Sender<LoadProgress>,
+    cancel: &CancellationToken,
+    order: BitOrder,
+) -> std::io::Result<()> {
+    read_file_range_with_progress(path, 0, None, progress_tx, cancel, order)
+}
+
+/// Same as `read_file_as_bits_with_progress`, reading only `length` bytes from byte `offset`
+/// (to the end of the file if None). Only the range has to fit in MAX_FILE_SIZE.
+pub fn read_file_range_with_progress(
+    path: &Path,
+    offset: u64,
+    length: Option<u64>,
+    progress_tx: Sender<LoadProgress>,
+    cancel: &CancellationToken,
+    order: BitOrder,
+) -> std::io::Result<()> {
+    let result = (|| -> std::io::Result<BitVec<u8, Msb0>> {
+        let (file, total_size) = open_range(path, offset, length)?;
+        let mut file = file.take(total_size);
+        let mut buffer = Vec::new();
+        let chunk_size = 1024 * 1024; // 1MB chunks
+        let mut total_read: u64 = 0;
+        
+        loop {
+            if cancel.is_cancelled() {
+                return Err(io::Error::new(io::ErrorKind::Interrupted, "Loading cancelled"));
+            }
+            let mut chunk = vec![0u8; chunk_size];
+            match file.read(&mut chunk)? {
+                0 => break,
+                n => {
+                    buffer.extend_from_slice(&chunk[..n]);
+                    total_read += n as u64;
+                    
+                    // Send progress update
+                    let _ = progress_tx.send(LoadProgress::Progress {
+                        loaded: total_read,
+                        total: total_size,
+                    });
+                }
+            }
+        }
+        
+        let mut bits = BitVec::<u8, Msb0>::from_vec(buffer);
+        apply_bit_order(&mut bits, order);
+        Ok(bits)
+    })();
+    
+    // Send completion message
+    let _ = progress_tx.send(LoadProgress::Complete(
+        result.map_err(|e| e.to_string())
+    ));
+    
+    Ok(())
+}
+
+/// Read a file and convert its contents to a bit vector
+/// 
+/// # Errors
+/// 
+/// Returns an error if:
+/// - The file cannot be opened
+/// - The file is larger than MAX_FILE_SIZE
+/// - Reading the file fails
+pub fn read_file_as_bits(path: &Path) -> std::io::Result<BitVec<u8, Msb0>> {
+    read_file_as_bits_in_order(path, BitOrder::MsbFirst)
+}
+
+/// Same as `read_file_as_bits`, reading each byte's bits in `order`
+pub fn read_file_as_bits_in_order(path: &Path, order: BitOrder) -> std::io::Result<BitVec<u8, Msb0>> {
+    read_file_range_as_bits_in_order(path, 0, None, order)
+}
+
+/// Same as `read_file_as_bits_in_order`, reading only `length` bytes from byte `offset`
+/// (to the end of the file if None), so part of a huge file can be loaded without reading
+/// the rest. Only the range has to fit in MAX_FILE_SIZE.
+pub fn read_file_range_as_bits_in_order(
+    path: &Path,
+    offset: u64,
+    length: Option<u64>,
+    order: BitOrder,
+) -> std::io::Result<BitVec<u8, Msb0>> {
+    let (file, size) = open_range(path, offset, length)?;
+    let mut buffer = Vec::with_capacity(size as usize);
+    file.take(size).read_to_end(&mut buffer)?;
+    
+    let mut bits = BitVec::<u8, Msb0>::from_vec(buffer);
+    apply_bit_order(&mut bits, order);
+    Ok(bits)
+}
+
+/// `paths` read one after the other into a single stream, failing on the first file that can't be read
+pub fn read_files_as_bits_in_order(paths: &[PathBuf], order: BitOrder) -> Result<BitVec<u8, Msb0>, String> {
+    let mut bits = BitVec::new();
+    for path in paths {
+        let file_bits = read_file_as_bits_in_order(path, order)
+            .map_err(|e| format!("Failed to load file {}: {}", path.display(), e))?;
+        bits.extend_from_bitslice(&file_bits);
+    }
+    Ok(bits)
+}
+
+/// `path` read as soft samples in `format`, see `SoftBits::decode`
+pub fn read_soft_file(path: &Path, format: SoftFormat, combine: Option<&MajorityVote>) -> Result<SoftBits, String> {
+    let read = || -> io::Result<Vec<u8>> {
+        let (file, size) = open_range(path, 0, None)?;
+        let mut buffer = Vec::with_capacity(size as usize);
+        file.take(size).read_to_end(&mut buffer)?;
+        Ok(buffer)
+    };
+    let bytes = read().map_err(|e| format!("Failed to load file {}: {}", path.display(), e))?;
+    Ok(SoftBits::decode(&bytes, format, combine))
+}
+
+/// Read-only memory-mapped view of a file's bits
+///
+/// Nothing is read up front: the OS pages data in as bits are accessed, so the views only
+/// touch the rows they draw. This allows captures far larger than MAX_FILE_SIZE (or RAM) to be opened.
+pub struct MappedBits {
+    path: PathBuf,
+    // Zero-length files can't be mapped on every platform
+    mmap: Option<Mmap>,
+}
+
+impl MappedBits {
+    /// Memory-map a file for viewing
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or mapped
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        let file = File::open(path)?;
+        let mmap = if file.metadata()?.len() == 0 {
+            None
+        } else {
+            // SAFETY: the mapping is read-only. If another process truncates the file while it
+            // is mapped, accessing the missing pages faults; on-disk captures are treated as immutable.
+            Some(unsafe { Mmap::map(&file)? })
+        };
+        
+        Ok(Self {
+            path: path.to_path_buf(),
+            mmap,
+        })
+    }
+    
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    
+    pub fn as_bytes(&self) -> &[u8] {
+        self.mmap.as_deref().unwrap_or(&[])
+    }
+    
+    /// All bits of the file, backed directly by the mapping
+    pub fn bits(&self) -> &BitSlice<u8, Msb0> {
+        BitSlice::from_slice(self.as_bytes())
+    }
+    
+    /// Number of bits in the file
+    pub fn len(&self) -> usize {
+        self.as_bytes().len() * 8
+    }
+    
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.as_bytes().is_empty()
+    }
+    
+    /// Copy `len` bits starting at `start` out of the mapping (clamped to the end of the file)
+    #[allow(dead_code)]
+    pub fn bits_range(&self, start: usize, len: usize) -> BitVec<u8, Msb0> {
+        let bits = self.bits();
+        let start = start.min(bits.len());
+        let end = start.saturating_add(len).min(bits.len());
+        bits[start..end].to_bitvec()
+    }
+}
+
+/// Whether a file is big enough to be memory-mapped rather than read into memory
+pub fn should_memory_map(path: &Path, threshold_bytes: u64) -> bool {
+    std::fs::metadata(path).map(|m| m.len() > threshold_bytes).unwrap_or(false)
+}
+
+#[allow(dead_code)]
+pub fn write_bits_to_file(path: &Path, bits: &BitVec<u8, Msb0>) -> std::io::Result<()> {
+    write_bits_to_file_with_progress(path, bits, &ProgressReporter::new())
+}
+
+/// Write bits to a file a chunk at a time, publishing the fraction written to `progress`
+/// 
+/// # Errors
+/// 
+/// Returns an error if:
+/// - There are no bits to write
+/// - The file cannot be created or written
+/// - Saving was cancelled (the partial file is removed)
+pub fn write_bits_to_file_with_progress(
+    path: &Path,
+    bits: &BitSlice<u8, Msb0>,
+    progress: &ProgressReporter,
+) -> std::io::Result<()> {
+    if bits.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Cannot write empty bit vector to file"
+        ));
+    }
+    
+    let mut file = File::create(path)?;
+    let mut written = 0;
+    
+    for chunk in bits.chunks(WRITE_CHUNK_BITS) {
+        if progress.is_cancelled() {
+            drop(file);
+            let _ = std::fs::remove_file(path);
+            return Err(io::Error::new(io::ErrorKind::Interrupted, "Saving cancelled"));
+        }
+        
+        // Convert bits to bytes
+        // If the bit count is not a multiple of 8, pad with zeros
+        // In-place operations can leave stale bits past the end of the last byte
+        let mut padded = chunk.to_bitvec();
+        padded.set_uninitialized(false);
+        file.write_all(padded.as_raw_slice())?;
+        
+        written += chunk.len();
+        progress.set_fraction(written as f32 / bits.len() as f32);
+    }
+    
+    file.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_read_write_roundtrip() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        let test_data = vec![0b10101010, 0b11110000, 0b00001111];
+        temp_file.write_all(&test_data).unwrap();
+        temp_file.flush().unwrap();
+
+        let bits = read_file_as_bits(temp_file.path()).unwrap();
+        assert_eq!(bits.len(), 24);
+
+        let output_file = NamedTempFile::new().unwrap();
+        write_bits_to_file(output_file.path(), &bits).unwrap();
+
+        let mut output_data = Vec::new();
+        File::open(output_file.path())
+            .unwrap()
+            .read_to_end(&mut output_data)
+            .unwrap();
+
+        assert_eq!(output_data, test_data);
+    }
+    
+    #[test]
+    fn test_chunked_write_matches_bits() {
+        let temp_file = NamedTempFile::new().unwrap();
+        // Spans several chunks and ends mid-byte
+        let bytes: Vec<u8> = (0..WRITE_CHUNK_BITS / 4 + 2).map(|i| i as u8 | 0x01).collect();
+        let mut bits = BitVec::<u8, Msb0>::from_vec(bytes);
+        bits.truncate(WRITE_CHUNK_BITS * 2 + 12);
+        let progress = ProgressReporter::new();
+        
+        write_bits_to_file_with_progress(temp_file.path(), &bits, &progress).unwrap();
+        
+        let read_bits = read_file_as_bits(temp_file.path()).unwrap();
+        assert_eq!(read_bits.len(), WRITE_CHUNK_BITS * 2 + 16);
+        assert_eq!(read_bits[..bits.len()], bits[..]);
+        assert!(read_bits[bits.len()..].not_any());
+        assert_eq!(progress.state().fraction, 1.0);
+    }
+    
+    #[test]
+    fn test_cancelled_write_removes_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.bin");
+        let progress = ProgressReporter::new();
+        progress.token.cancel();
+        
+        let result = write_bits_to_file_with_progress(&path, &bitvec![u8, Msb0; 1; 16], &progress);
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::Interrupted);
+        assert!(!path.exists());
+    }
+    
+    #[test]
+    fn test_lsb_first_reverses_each_byte() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(&[0b1100_0001, 0b0000_0010]).unwrap();
+        temp_file.flush().unwrap();
+        
+        let bits = read_file_as_bits_in_order(temp_file.path(), BitOrder::LsbFirst).unwrap();
+        assert_eq!(bits.as_raw_slice(), &[0b1000_0011, 0b0100_0000]);
+        let bits = read_file_as_bits_in_order(temp_file.path(), BitOrder::MsbFirst).unwrap();
+        assert_eq!(bits.as_raw_slice(), &[0b1100_0001, 0b0000_0010]);
+    }
+    
+    #[test]
+    fn test_read_empty_file() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let bits = read_file_as_bits(temp_file.path()).unwrap();
+        assert_eq!(bits.len(), 0);
+    }
+    
+    #[test]
+    fn test_read_single_byte() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(&[0xFF]).unwrap();
+        temp_file.flush().unwrap();
+        
+        let bits = read_file_as_bits(temp_file.path()).unwrap();
+        assert_eq!(bits.len(), 8);
+        assert!(bits.all());
+    }
+    
+    #[test]
+    fn test_read_file_range() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(&[0x00, 0x11, 0x22, 0x33, 0x44]).unwrap();
+        temp_file.flush().unwrap();
+        
+        let bits = read_file_range_as_bits_in_order(temp_file.path(), 1, Some(2), BitOrder::MsbFirst).unwrap();
+        assert_eq!(bits.as_raw_slice(), &[0x11, 0x22]);
+        // Ranges running past the end are cut short
+        let bits = read_file_range_as_bits_in_order(temp_file.path(), 3, Some(10), BitOrder::MsbFirst).unwrap();
+        assert_eq!(bits.as_raw_slice(), &[0x33, 0x44]);
+        let bits = read_file_range_as_bits_in_order(temp_file.path(), 4, None, BitOrder::MsbFirst).unwrap();
+        assert_eq!(bits.as_raw_slice(), &[0x44]);
+        assert!(read_file_range_as_bits_in_order(temp_file.path(), 9, None, BitOrder::MsbFirst).unwrap().is_empty());
+    }
+    
+    #[test]
+    fn test_read_files_appends_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = dir.path().join("a.bin");
+        let second = dir.path().join("b.bin");
+        std::fs::write(&first, [0x12]).unwrap();
+        std::fs::write(&second, [0x34, 0x56]).unwrap();
+        
+        let bits = read_files_as_bits_in_order(&[second.clone(), first.clone()], BitOrder::MsbFirst).unwrap();
+        assert_eq!(bits.as_raw_slice(), &[0x34, 0x56, 0x12]);
+        
+        let missing = dir.path().join("missing.bin");
+        let error = read_files_as_bits_in_order(&[first, missing], BitOrder::MsbFirst).unwrap_err();
+        assert!(error.contains("missing.bin"));
+    }
+    
+    #[test]
+    fn test_read_nonexistent_file() {
+        let result = read_file_as_bits(Path::new("nonexistent_file.bin"));
+        assert!(result.is_err());
+    }
+    
+    #[test]
+    fn test_write_empty_bits() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let empty_bits = BitVec::<u8, Msb0>::new();
+        let result = write_bits_to_file(temp_file.path(), &empty_bits);
+        assert!(result.is_err());
+    }
+    
+    #[test]
+    fn test_write_single_bit() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut bits = BitVec::<u8, Msb0>::new();
+        bits.push(true);
+        
+        write_bits_to_file(temp_file.path(), &bits).unwrap();
+        
+        let mut data = Vec::new();
+        File::open(temp_file.path())
+            .unwrap()
+            .read_to_end(&mut data)
+            .unwrap();
+        
+        // Single bit true, padded to byte = 10000000 = 0x80
+        assert_eq!(data, vec![0x80]);
+    }
+    
+    #[test]
+    fn test_bit_preservation() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mut bits = BitVec::<u8, Msb0>::new();
+        
+        // Create specific pattern: 10101010 11110000
+        for &b in &[true, false, true, false, true, false, true, false] {
+            bits.push(b);
+        }
+        for &b in &[true, true, true, true, false, false, false, false] {
+            bits.push(b);
+        }
+        
+        write_bits_to_file(temp_file.path(), &bits).unwrap();
+        let read_bits = read_file_as_bits(temp_file.path()).unwrap();
+        
+        assert_eq!(bits, read_bits);
+    }
+    
+    #[test]
+    fn test_mapped_bits_match_read() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        let test_data: Vec<u8> = (0..=255).collect();
+        temp_file.write_all(&test_data).unwrap();
+        temp_file.flush().unwrap();
+        
+        let mapped = MappedBits::open(temp_file.path()).unwrap();
+        let bits = read_file_as_bits(temp_file.path()).unwrap();
+        
+        assert_eq!(mapped.len(), bits.len());
+        assert_eq!(mapped.bits(), bits.as_bitslice());
+    }
+    
+    #[test]
+    fn test_mapped_bits_range() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(&[0b10101010, 0b11110000]).unwrap();
+        temp_file.flush().unwrap();
+        
+        let mapped = MappedBits::open(temp_file.path()).unwrap();
+        assert_eq!(mapped.bits_range(6, 4), bitvec![u8, Msb0; 1, 0, 1, 1]);
+        // Ranges past the end are clamped
+        assert_eq!(mapped.bits_range(12, 100), bitvec![u8, Msb0; 0, 0, 0, 0]);
+        assert!(mapped.bits_range(100, 8).is_empty());
+    }
+    
+    #[test]
+    fn test_map_empty_file() {
+        let temp_file = NamedTempFile::new().unwrap();
+        let mapped = MappedBits::open(temp_file.path()).unwrap();
+        assert!(mapped.is_empty());
+        assert!(mapped.bits().is_empty());
+    }
+    
+    #[test]
+    fn test_should_memory_map() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(&[0u8; 16]).unwrap();
+        temp_file.flush().unwrap();
+        
+        assert!(should_memory_map(temp_file.path(), 8));
+        assert!(!should_memory_map(temp_file.path(), 16));
+        assert!(!should_memory_map(Path::new("nonexistent_file.bin"), 0));
+    }
+}
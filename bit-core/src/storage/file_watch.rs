@@ -0,0 +1,74 @@
+// File watching - notices when a file is rewritten, by polling its size and modification time
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// What a poll can see of a file without reading it
+type FileStamp = (Option<SystemTime>, u64);
+
+fn stamp(path: &Path) -> Option<FileStamp> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok(), metadata.len()))
+}
+
+/// Reports a change once the file has looked the same for two polls in a row, so a file
+/// still being written isn't reloaded halfway through
+pub struct FileWatcher {
+    path: PathBuf,
+    seen: Option<FileStamp>,
+    changed: Option<FileStamp>,
+}
+
+impl FileWatcher {
+    pub fn new(path: PathBuf) -> Self {
+        let seen = stamp(&path);
+        Self { path, seen, changed: None }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether the file changed and settled since it was last reported. A missing file is
+    /// taken to be in the middle of being replaced.
+    pub fn poll(&mut self) -> bool {
+        let Some(now) = stamp(&self.path) else {
+            return false;
+        };
+        if Some(now) == self.seen {
+            self.changed = None;
+            return false;
+        }
+        if self.changed == Some(now) {
+            self.seen = Some(now);
+            self.changed = None;
+            return true;
+        }
+        self.changed = Some(now);
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_change_is_reported_once_settled() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("capture.bin");
+        std::fs::write(&path, [1u8, 2]).unwrap();
+        let mut watcher = FileWatcher::new(path.clone());
+        assert!(!watcher.poll());
+
+        // The size changes even where the modification time is too coarse to
+        std::fs::write(&path, [1u8, 2, 3]).unwrap();
+        assert!(!watcher.poll());
+        assert!(watcher.poll());
+        assert!(!watcher.poll());
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(!watcher.poll());
+        assert!(!watcher.poll());
+    }
+}
@@ -0,0 +1,243 @@
+// Versioned JSON formats - upgrades files written by older versions before they are parsed
+
+use crate::storage::worksheet::new_worksheet_id;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// Current format versions, bumped whenever a schema change needs a migration
+pub const SESSION_VERSION: u32 = 2;
+pub const WORKSHEET_VERSION: u32 = 1;
+pub const SETTINGS_VERSION: u32 = 1;
+pub const PROJECT_VERSION: u32 = 2;
+pub const OPERATION_CHAIN_VERSION: u32 = 1;
+
+/// A step that upgrades a JSON value from version `n` to `n + 1`
+type Migration = fn(&mut Value) -> Result<(), String>;
+
+/// Migrations indexed by the version they upgrade from.
+/// Files written before versioning count as version 0; version 1 only added the field.
+const SESSION_MIGRATIONS: &[Migration] = &[no_changes, worksheet_ids];
+const WORKSHEET_MIGRATIONS: &[Migration] = &[no_changes];
+const SETTINGS_MIGRATIONS: &[Migration] = &[no_changes];
+const PROJECT_MIGRATIONS: &[Migration] = &[no_changes, worksheet_ids];
+const OPERATION_CHAIN_MIGRATIONS: &[Migration] = &[no_changes];
+
+fn no_changes(_value: &mut Value) -> Result<(), String> {
+    Ok(())
+}
+
+/// Version 2: Multi-Worksheet Load reads its worksheets by id and name rather than by
+/// position, so reordering worksheets no longer changes what it loads
+fn worksheet_ids(value: &mut Value) -> Result<(), String> {
+    let Some(Value::Array(worksheets)) = value.get_mut("worksheets") else {
+        return Ok(());
+    };
+    let mut targets = Vec::with_capacity(worksheets.len());
+    for worksheet in worksheets.iter_mut() {
+        let Value::Object(map) = worksheet else {
+            return Err("worksheet is not an object".to_string());
+        };
+        let id = map.entry("id").or_insert_with(|| Value::from(new_worksheet_id())).clone();
+        targets.push((id, map.get("name").cloned().unwrap_or_default()));
+    }
+    for worksheet in worksheets.iter_mut() {
+        if let Some(operations) = worksheet.get_mut("operations") {
+            replace_worksheet_indices(operations, &targets);
+        }
+    }
+    Ok(())
+}
+
+/// Turn every `worksheet_index` under `value`, including ones in macros and groups,
+/// into the id and name of the worksheet at that position
+fn replace_worksheet_indices(value: &mut Value, targets: &[(Value, Value)]) {
+    match value {
+        Value::Object(map) => {
+            if let Some(index) = map.remove("worksheet_index")
+                && let Some((id, name)) = index.as_u64().and_then(|index| targets.get(index as usize))
+            {
+                map.insert("worksheet_id".to_string(), id.clone());
+                map.insert("worksheet_name".to_string(), name.clone());
+            }
+            map.values_mut().for_each(|child| replace_worksheet_indices(child, targets));
+        }
+        Value::Array(items) => items.iter_mut().for_each(|child| replace_worksheet_indices(child, targets)),
+        _ => {}
+    }
+}
+
+/// The format version recorded in `value`; files without one are version 0
+pub fn format_version(value: &Value) -> u32 {
+    value.get("version").and_then(Value::as_u64).unwrap_or(0) as u32
+}
+
+/// Upgrade `value` to the latest version using `migrations`
+fn migrate(value: &mut Value, what: &str, migrations: &[Migration]) -> Result<(), String> {
+    let latest = migrations.len() as u32;
+    let version = format_version(value);
+
+    if version > latest {
+        return Err(format!(
+            "{} format version {} is newer than this build supports (up to {})",
+            what, version, latest
+        ));
+    }
+
+    for (from, migration) in migrations.iter().enumerate().skip(version as usize) {
+        migration(value).map_err(|e| format!("Failed to upgrade {} from version {}: {}", what, from, e))?;
+    }
+
+    if let Value::Object(map) = value {
+        map.insert("version".to_string(), Value::from(latest));
+    }
+    Ok(())
+}
+
+pub fn migrate_worksheet(value: &mut Value) -> Result<(), String> {
+    migrate(value, "worksheet", WORKSHEET_MIGRATIONS)
+}
+
+/// Upgrade a session, including every worksheet inside it
+pub fn migrate_session(value: &mut Value) -> Result<(), String> {
+    migrate(value, "session", SESSION_MIGRATIONS)?;
+    migrate_worksheets(value)
+}
+
+/// Upgrade a project, including every worksheet inside it
+pub fn migrate_project(value: &mut Value) -> Result<(), String> {
+    migrate(value, "project", PROJECT_MIGRATIONS)?;
+    migrate_worksheets(value)
+}
+
+fn migrate_worksheets(value: &mut Value) -> Result<(), String> {
+    if let Some(Value::Array(worksheets)) = value.get_mut("worksheets") {
+        for worksheet in worksheets {
+            migrate_worksheet(worksheet)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn migrate_settings(value: &mut Value) -> Result<(), String> {
+    migrate(value, "settings", SETTINGS_MIGRATIONS)
+}
+
+pub fn migrate_operation_chain(value: &mut Value) -> Result<(), String> {
+    migrate(value, "operations", OPERATION_CHAIN_MIGRATIONS)
+}
+
+/// Parse `json`, upgrade it with `migrate_fn`, then deserialize it as `T`
+pub fn parse_versioned<T: DeserializeOwned>(
+    json: &str,
+    migrate_fn: fn(&mut Value) -> Result<(), String>,
+) -> Result<T, String> {
+    let mut value: Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    migrate_fn(&mut value)?;
+    serde_json::from_value(value).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processing::BitOperation;
+    use crate::core::ViewMode;
+    use crate::storage::worksheet::{find_worksheet, WorksheetView};
+    use crate::storage::{AppSession, Worksheet};
+    use serde_json::json;
+
+    #[test]
+    fn test_unversioned_session_loads() {
+        let json = r#"{
+            "worksheets": [{ "name": "Sheet", "file_path": null, "operations": [] }],
+            "current_worksheet_index": 0
+        }"#;
+        let session: AppSession = parse_versioned(json, migrate_session).unwrap();
+        assert_eq!(session.version, SESSION_VERSION);
+        assert_eq!(session.worksheets.len(), 1);
+        assert_eq!(session.worksheets[0].version, WORKSHEET_VERSION);
+    }
+
+    #[test]
+    fn test_index_references_resolve_to_ids() {
+        let json = r#"{
+            "worksheets": [
+                { "name": "Source", "file_path": null, "operations": [] },
+                { "name": "Combined", "file_path": null, "operations": [
+                    { "MultiWorksheetLoad": {
+                        "name": "Load",
+                        "worksheet_operations": [{ "worksheet_index": 0, "sequence": { "operations": [] } }],
+                        "enabled": true
+                    } }
+                ] }
+            ],
+            "current_worksheet_index": 1
+        }"#;
+        let mut session: AppSession = parse_versioned(json, migrate_session).unwrap();
+        assert_eq!(session.version, SESSION_VERSION);
+        
+        let source_id = session.worksheets[0].id;
+        assert_ne!(source_id, session.worksheets[1].id);
+        
+        // Moving the source worksheet no longer changes what the operation reads
+        session.worksheets.swap(0, 1);
+        let BitOperation::MultiWorksheetLoad { worksheet_operations, .. } = &session.worksheets[0].operations[0] else {
+            panic!("expected a multi-worksheet load");
+        };
+        assert_eq!(worksheet_operations[0].worksheet_id, source_id);
+        assert_eq!(find_worksheet(&session.worksheets, &worksheet_operations[0]), Some(1));
+    }
+
+    #[test]
+    fn test_newer_version_is_rejected() {
+        let mut value = json!({ "version": WORKSHEET_VERSION + 1, "name": "x", "operations": [] });
+        let err = migrate_worksheet(&mut value).unwrap_err();
+        assert!(err.contains("newer"));
+    }
+
+    #[test]
+    fn test_round_trip_keeps_version() {
+        let worksheet = Worksheet::new("Sheet".to_string());
+        let json = serde_json::to_string(&worksheet).unwrap();
+        assert_eq!(format_version(&serde_json::from_str(&json).unwrap()), WORKSHEET_VERSION);
+
+        let loaded: Worksheet = parse_versioned(&json, migrate_worksheet).unwrap();
+        assert_eq!(loaded.name, "Sheet");
+    }
+
+    #[test]
+    fn test_frame_width_is_saved_with_the_view() {
+        let mut worksheet = Worksheet::new("Sheet".to_string());
+        worksheet.view = Some(WorksheetView {
+            view_mode: ViewMode::Bit,
+            frame_length: 1632,
+            bit_size: 4.0,
+            show_original: false,
+            scroll_offset: [0.0, 0.0],
+            super_frame_length: 0,
+        });
+        let value = serde_json::to_value(&worksheet).unwrap();
+        assert!(value.get("frame_length").is_none());
+        assert_eq!(value["view"]["frame_length"], json!(1632));
+
+        let loaded: Worksheet = parse_versioned(&value.to_string(), migrate_worksheet).unwrap();
+        assert_eq!(loaded.view.map(|view| view.frame_length), Some(1632));
+    }
+
+    #[test]
+    fn test_migrations_run_from_recorded_version() {
+        fn add_marker(value: &mut Value) -> Result<(), String> {
+            value["marker"] = json!(true);
+            Ok(())
+        }
+        let migrations: &[Migration] = &[no_changes, add_marker];
+
+        let mut old = json!({ "version": 1 });
+        migrate(&mut old, "test", migrations).unwrap();
+        assert_eq!(old["marker"], json!(true));
+        assert_eq!(format_version(&old), 2);
+
+        let mut current = json!({ "version": 2 });
+        migrate(&mut current, "test", migrations).unwrap();
+        assert!(current.get("marker").is_none());
+    }
+}
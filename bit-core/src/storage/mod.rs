@@ -0,0 +1,26 @@
+// Storage module - file I/O, sessions, settings, and worksheets
+
+pub mod autosave;
+pub mod file_glob;
+pub mod file_io;
+pub mod file_watch;
+pub mod migration;
+pub mod network;
+pub mod operation_chain;
+pub mod paths;
+pub mod project;
+pub mod session;
+pub mod settings;
+pub mod worksheet;
+
+#[allow(unused_imports)]
+pub use file_glob::{expand_glob, natural_cmp};
+#[allow(unused_imports)]
+pub use file_io::{read_file_as_bits, read_file_as_bits_in_order, read_files_as_bits_in_order, read_file_as_bits_with_progress, read_file_range_as_bits_in_order, read_file_range_with_progress, read_soft_file, range_len, BitOrder, should_memory_map, write_bits_to_file, write_bits_to_file_with_progress, LoadProgress, MappedBits};
+pub use file_watch::FileWatcher;
+pub use network::{burst_starts, NetworkListener, NetworkProtocol, ReceivedSegment};
+pub use operation_chain::OperationChain;
+pub use project::Project;
+pub use session::AppSession;
+pub use settings::{AppSettings, AppTheme, Language, MAX_UI_SCALE, MIN_UI_SCALE};
+pub use worksheet::{FrameWidthScan, Worksheet, WorksheetView, MAX_FRAME_WIDTH_SCANS};
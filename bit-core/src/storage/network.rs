@@ -0,0 +1,236 @@
+// Network sources - receive bytes over UDP or TCP, e.g. from an SDR pipeline
+
+use std::io::{ErrorKind, Read};
+use std::net::{SocketAddr, TcpListener, TcpStream, UdpSocket};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime};
+
+/// How long socket reads block before checking whether the listener was stopped
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Largest UDP datagram payload
+const MAX_DATAGRAM_BYTES: usize = 65536;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkProtocol {
+    Udp,
+    Tcp,
+}
+
+impl NetworkProtocol {
+    pub fn name(&self) -> &str {
+        match self {
+            NetworkProtocol::Udp => "UDP",
+            NetworkProtocol::Tcp => "TCP",
+        }
+    }
+}
+
+/// Bytes from one datagram or one read of a TCP connection
+#[derive(Debug, Clone)]
+pub struct ReceivedChunk {
+    pub received_at: SystemTime,
+    pub peer: SocketAddr,
+    pub bytes: Vec<u8>,
+}
+
+/// Where a received chunk landed in the bit stream
+#[derive(Debug, Clone)]
+pub struct ReceivedSegment {
+    pub received_at: SystemTime,
+    pub peer: SocketAddr,
+    pub bit_offset: usize,
+    pub bit_len: usize,
+}
+
+/// Bit offsets where a new burst starts, with the silence before it.
+/// Chunks that arrive less than `min_gap` apart are collapsed into one burst.
+pub fn burst_starts(segments: &[ReceivedSegment], min_gap: Duration) -> Vec<(usize, Duration)> {
+    segments
+        .windows(2)
+        .filter_map(|pair| {
+            let gap = pair[1].received_at.duration_since(pair[0].received_at).ok()?;
+            (gap >= min_gap).then_some((pair[1].bit_offset, gap))
+        })
+        .collect()
+}
+
+/// Background listener that collects received payloads until it is stopped or dropped
+pub struct NetworkListener {
+    pub protocol: NetworkProtocol,
+    pub local_addr: SocketAddr,
+    receiver: Receiver<ReceivedChunk>,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl NetworkListener {
+    /// Listen on `bind_address:port`; port 0 picks a free port (see `local_addr`)
+    pub fn start(protocol: NetworkProtocol, bind_address: &str, port: u16) -> Result<Self, String> {
+        let address = format!("{}:{}", bind_address, port);
+        let stop = Arc::new(AtomicBool::new(false));
+        let (sender, receiver) = mpsc::channel();
+
+        let (local_addr, handle) = match protocol {
+            NetworkProtocol::Udp => {
+                let socket = UdpSocket::bind(&address)
+                    .map_err(|e| format!("Failed to listen on UDP {}: {}", address, e))?;
+                socket.set_read_timeout(Some(POLL_INTERVAL)).map_err(|e| e.to_string())?;
+                let local_addr = socket.local_addr().map_err(|e| e.to_string())?;
+                let stop = stop.clone();
+                (local_addr, thread::spawn(move || receive_udp(socket, sender, stop)))
+            }
+            NetworkProtocol::Tcp => {
+                let listener = TcpListener::bind(&address)
+                    .map_err(|e| format!("Failed to listen on TCP {}: {}", address, e))?;
+                listener.set_nonblocking(true).map_err(|e| e.to_string())?;
+                let local_addr = listener.local_addr().map_err(|e| e.to_string())?;
+                let stop = stop.clone();
+                (local_addr, thread::spawn(move || accept_tcp(listener, sender, stop)))
+            }
+        };
+
+        Ok(Self {
+            protocol,
+            local_addr,
+            receiver,
+            stop,
+            handle: Some(handle),
+        })
+    }
+
+    /// Chunks received since the last call, oldest first
+    pub fn drain(&self) -> Vec<ReceivedChunk> {
+        self.receiver.try_iter().collect()
+    }
+
+    /// False once the listening thread has exited, e.g. after a socket error
+    pub fn is_running(&self) -> bool {
+        self.handle.as_ref().is_some_and(|handle| !handle.is_finished())
+    }
+}
+
+impl Drop for NetworkListener {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            handle.join().ok();
+        }
+    }
+}
+
+fn receive_udp(socket: UdpSocket, sender: Sender<ReceivedChunk>, stop: Arc<AtomicBool>) {
+    let mut buffer = vec![0u8; MAX_DATAGRAM_BYTES];
+    while !stop.load(Ordering::Relaxed) {
+        match socket.recv_from(&mut buffer) {
+            Ok((len, peer)) => {
+                let chunk = ReceivedChunk { received_at: SystemTime::now(), peer, bytes: buffer[..len].to_vec() };
+                if sender.send(chunk).is_err() {
+                    return;
+                }
+            }
+            Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(_) => return,
+        }
+    }
+}
+
+fn accept_tcp(listener: TcpListener, sender: Sender<ReceivedChunk>, stop: Arc<AtomicBool>) {
+    let mut connections = Vec::new();
+    while !stop.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, peer)) => {
+                let (sender, stop) = (sender.clone(), stop.clone());
+                connections.push(thread::spawn(move || receive_tcp(stream, peer, sender, stop)));
+            }
+            Err(e) if e.kind() == ErrorKind::WouldBlock => thread::sleep(POLL_INTERVAL),
+            Err(_) => break,
+        }
+    }
+    for connection in connections {
+        connection.join().ok();
+    }
+}
+
+fn receive_tcp(mut stream: TcpStream, peer: SocketAddr, sender: Sender<ReceivedChunk>, stop: Arc<AtomicBool>) {
+    if stream.set_nonblocking(false).is_err() || stream.set_read_timeout(Some(POLL_INTERVAL)).is_err() {
+        return;
+    }
+    let mut buffer = vec![0u8; MAX_DATAGRAM_BYTES];
+    while !stop.load(Ordering::Relaxed) {
+        match stream.read(&mut buffer) {
+            // Connection closed by the peer
+            Ok(0) => return,
+            Ok(len) => {
+                let chunk = ReceivedChunk { received_at: SystemTime::now(), peer, bytes: buffer[..len].to_vec() };
+                if sender.send(chunk).is_err() {
+                    return;
+                }
+            }
+            Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut | ErrorKind::Interrupted) => {}
+            Err(_) => return,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    /// Collect chunks until `expected` bytes have arrived or a few seconds pass
+    fn receive_bytes(listener: &NetworkListener, expected: usize) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for _ in 0..100 {
+            for chunk in listener.drain() {
+                bytes.extend(chunk.bytes);
+            }
+            if bytes.len() >= expected {
+                break;
+            }
+            thread::sleep(Duration::from_millis(30));
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_close_chunks_collapse_into_one_burst() {
+        let start = SystemTime::now();
+        let peer: SocketAddr = "127.0.0.1:9".parse().unwrap();
+        let segment = |ms: u64, bit_offset: usize| ReceivedSegment {
+            received_at: start + Duration::from_millis(ms),
+            peer,
+            bit_offset,
+            bit_len: 8,
+        };
+        let segments = [segment(0, 0), segment(5, 8), segment(500, 16), segment(510, 24)];
+
+        let starts = burst_starts(&segments, Duration::from_millis(100));
+        assert_eq!(starts, vec![(16, Duration::from_millis(495))]);
+        assert_eq!(burst_starts(&segments, Duration::ZERO).len(), 3);
+    }
+
+    #[test]
+    fn test_udp_datagrams_are_received_in_order() {
+        let listener = NetworkListener::start(NetworkProtocol::Udp, "127.0.0.1", 0).unwrap();
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        socket.send_to(&[1, 2, 3], listener.local_addr).unwrap();
+        socket.send_to(&[4, 5], listener.local_addr).unwrap();
+
+        assert_eq!(receive_bytes(&listener, 5), vec![1, 2, 3, 4, 5]);
+        assert!(listener.is_running());
+    }
+
+    #[test]
+    fn test_tcp_stream_is_received() {
+        let listener = NetworkListener::start(NetworkProtocol::Tcp, "127.0.0.1", 0).unwrap();
+        let mut stream = TcpStream::connect(listener.local_addr).unwrap();
+        stream.write_all(b"hello").unwrap();
+        drop(stream);
+
+        assert_eq!(receive_bytes(&listener, 5), b"hello".to_vec());
+    }
+}
@@ -0,0 +1,110 @@
+use super::migration::{migrate_operation_chain, parse_versioned, OPERATION_CHAIN_VERSION};
+use crate::analysis::Pattern;
+use crate::processing::BitOperation;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Just the operation pipeline and the patterns that go with it, to share or keep under
+/// version control without a worksheet's file path
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperationChain {
+    /// Format version, see `migration`
+    #[serde(default)]
+    pub version: u32,
+    pub operations: Vec<BitOperation>,
+    #[serde(default)]
+    pub patterns: Vec<Pattern>,
+}
+
+impl OperationChain {
+    pub const FILE_EXTENSION: &'static str = "bitops.json";
+
+    pub fn new(operations: Vec<BitOperation>, patterns: Vec<Pattern>) -> Self {
+        Self { version: OPERATION_CHAIN_VERSION, operations, patterns }
+    }
+
+    pub fn save_to_file(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize operations: {}", e))?;
+
+        std::fs::write(path, json)
+            .map_err(|e| format!("Failed to write operations file: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Load a chain; patterns come back parsed but without matches
+    pub fn load_from_file(path: &Path) -> Result<Self, String> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read operations file: {}", e))?;
+
+        let mut chain: OperationChain = parse_versioned(&json, migrate_operation_chain)
+            .map_err(|e| format!("Failed to parse operations file: {}", e))?;
+
+        // Pattern bits aren't serialized, so rebuild them from the saved input
+        for pattern in &mut chain.patterns {
+            pattern.update_bits()
+                .map_err(|e| format!("Invalid pattern '{}' in operations file: {}", pattern.name, e))?;
+        }
+
+        Ok(chain)
+    }
+
+    /// Files that source operations read and that don't exist here
+    pub fn missing_files(&self) -> Vec<&Path> {
+        let mut missing = Vec::new();
+        collect_missing_files(&self.operations, &mut missing);
+        missing
+    }
+}
+
+fn collect_missing_files<'a>(operations: &'a [BitOperation], missing: &mut Vec<&'a Path>) {
+    for op in operations {
+        match op {
+            BitOperation::LoadFile { file_path, .. } | BitOperation::LoadSoftBits { file_path, .. } if !file_path.exists() => {
+                missing.push(file_path)
+            }
+            BitOperation::MultiFileLoad { file_paths, .. } => {
+                missing.extend(file_paths.iter().filter(|path| !path.exists()).map(PathBuf::as_path));
+            }
+            BitOperation::Macro { operations, .. } | BitOperation::Group { operations, .. } => {
+                collect_missing_files(operations, missing);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::PatternFormat;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_operation_chain_round_trip() {
+        let operations = vec![
+            BitOperation::InvertBits { name: "Invert".to_string(), enabled: true },
+            BitOperation::LoadFile {
+                name: "Load".to_string(),
+                file_path: "/nowhere/capture.bin".into(),
+                offset: 0,
+                length: None,
+                enabled: false,
+            },
+        ];
+        let pattern = Pattern::new("Sync".to_string(), PatternFormat::Hex, "0x1ACF".to_string(), 1).unwrap();
+        let chain = OperationChain::new(operations, vec![pattern]);
+
+        let file = NamedTempFile::new().unwrap();
+        chain.save_to_file(file.path()).unwrap();
+        let loaded = OperationChain::load_from_file(file.path()).unwrap();
+
+        assert_eq!(loaded.version, OPERATION_CHAIN_VERSION);
+        assert_eq!(loaded.operations.len(), 2);
+        assert_eq!(loaded.operations[0].name(), "Invert");
+        assert!(!loaded.operations[1].is_enabled());
+        assert_eq!(loaded.patterns[0].bits.len(), 16);
+        assert_eq!(loaded.missing_files(), vec![Path::new("/nowhere/capture.bin")]);
+    }
+}
@@ -0,0 +1,112 @@
+// File paths in saved files - relative to the saved file where possible, and remapped when
+// the files were moved
+
+use super::worksheet::Worksheet;
+use std::path::{Path, PathBuf};
+
+/// Store paths under `base` relative to it; paths elsewhere stay absolute
+pub fn make_paths_relative(worksheets: &mut [Worksheet], base: &Path) {
+    for worksheet in worksheets {
+        worksheet.for_each_file_path_mut(&mut |path| {
+            if let Ok(relative) = path.strip_prefix(base) {
+                *path = relative.to_path_buf();
+            }
+        });
+    }
+}
+
+/// Turn paths stored relative to `base` back into full paths
+pub fn resolve_relative_paths(worksheets: &mut [Worksheet], base: &Path) {
+    for worksheet in worksheets {
+        worksheet.for_each_file_path_mut(&mut |path| {
+            if path.is_relative() {
+                *path = base.join(&*path);
+            }
+        });
+    }
+}
+
+/// Files the worksheets read that don't exist, each once, in the order first referenced
+pub fn missing_files(worksheets: &mut [Worksheet]) -> Vec<PathBuf> {
+    let mut missing: Vec<PathBuf> = Vec::new();
+    for worksheet in worksheets {
+        worksheet.for_each_file_path_mut(&mut |path| {
+            if !path.exists() && !missing.contains(path) {
+                missing.push(path.clone());
+            }
+        });
+    }
+    missing
+}
+
+/// New locations for `missing` files that exist under `folder` with the same file name
+pub fn remap_to_folder(missing: &[PathBuf], folder: &Path) -> Vec<(PathBuf, PathBuf)> {
+    missing
+        .iter()
+        .filter_map(|old| {
+            let new = folder.join(old.file_name()?);
+            new.exists().then(|| (old.clone(), new))
+        })
+        .collect()
+}
+
+/// Replace every path in `remap` (old, new) throughout the worksheets
+pub fn apply_remap(worksheets: &mut [Worksheet], remap: &[(PathBuf, PathBuf)]) {
+    for worksheet in worksheets {
+        worksheet.for_each_file_path_mut(&mut |path| {
+            if let Some((_, new)) = remap.iter().find(|(old, _)| old == path) {
+                *path = new.clone();
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processing::BitOperation;
+
+    fn worksheet(file: &str, loaded: &str) -> Worksheet {
+        let mut worksheet = Worksheet::new("W".to_string());
+        worksheet.file_path = Some(PathBuf::from(file));
+        worksheet.operations.push(BitOperation::Group {
+            name: "G".to_string(),
+            operations: vec![BitOperation::LoadFile { name: "L".to_string(), file_path: loaded.into(), offset: 0, length: None, enabled: true }],
+            collapsed: false,
+            enabled: true,
+        });
+        worksheet
+    }
+
+    fn paths(worksheet: &mut Worksheet) -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+        worksheet.for_each_file_path_mut(&mut |path| paths.push(path.clone()));
+        paths
+    }
+
+    #[test]
+    fn test_paths_under_base_round_trip_as_relative() {
+        let mut worksheets = vec![worksheet("/work/captures/a.bin", "/elsewhere/b.bin")];
+        make_paths_relative(&mut worksheets, Path::new("/work"));
+        assert_eq!(paths(&mut worksheets[0]), vec![PathBuf::from("captures/a.bin"), PathBuf::from("/elsewhere/b.bin")]);
+
+        resolve_relative_paths(&mut worksheets, Path::new("/moved"));
+        assert_eq!(paths(&mut worksheets[0]), vec![PathBuf::from("/moved/captures/a.bin"), PathBuf::from("/elsewhere/b.bin")]);
+    }
+
+    #[test]
+    fn test_missing_files_remap_to_folder() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.bin"), [0u8]).unwrap();
+        let mut worksheets = vec![worksheet("/gone/a.bin", "/gone/b.bin"), worksheet("/gone/a.bin", "/gone/a.bin")];
+
+        let missing = missing_files(&mut worksheets);
+        assert_eq!(missing, vec![PathBuf::from("/gone/a.bin"), PathBuf::from("/gone/b.bin")]);
+
+        // Only files that exist in the new folder are remapped
+        let remap = remap_to_folder(&missing, dir.path());
+        assert_eq!(remap, vec![(PathBuf::from("/gone/a.bin"), dir.path().join("a.bin"))]);
+        apply_remap(&mut worksheets, &remap);
+        assert_eq!(missing_files(&mut worksheets), vec![PathBuf::from("/gone/b.bin")]);
+    }
+}
@@ -0,0 +1,148 @@
+use super::migration::{migrate_project, parse_versioned, PROJECT_VERSION};
+use super::paths::{make_paths_relative, resolve_relative_paths};
+use super::worksheet::Worksheet;
+use crate::analysis::Pattern;
+use crate::view::ByteViewConfig;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A named investigation saved to a file of its own: every worksheet plus the
+/// patterns and byte view layout, independent of the auto-restored session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Project {
+    /// Format version, see `migration`
+    #[serde(default)]
+    pub version: u32,
+    pub worksheets: Vec<Worksheet>,
+    pub current_worksheet_index: usize,
+    #[serde(default)]
+    pub patterns: Vec<Pattern>,
+    #[serde(default)]
+    pub byte_view_config: ByteViewConfig,
+}
+
+impl Project {
+    pub const FILE_EXTENSION: &'static str = "bitproj";
+
+    pub fn new(
+        worksheets: Vec<Worksheet>,
+        current_worksheet_index: usize,
+        patterns: Vec<Pattern>,
+        byte_view_config: ByteViewConfig,
+    ) -> Self {
+        Self {
+            version: PROJECT_VERSION,
+            worksheets,
+            current_worksheet_index,
+            patterns,
+            byte_view_config,
+        }
+    }
+
+    /// Save with file paths inside the project file's folder stored relative to it, so the
+    /// folder can be moved or shared as a whole
+    pub fn save_to_file(&self, path: &Path) -> Result<(), String> {
+        let mut project = self.clone();
+        if let Some(base) = path.parent() {
+            make_paths_relative(&mut project.worksheets, base);
+        }
+        let json = serde_json::to_string_pretty(&project)
+            .map_err(|e| format!("Failed to serialize project: {}", e))?;
+
+        std::fs::write(path, json)
+            .map_err(|e| format!("Failed to write project file: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Load a project; patterns come back parsed but without matches
+    pub fn load_from_file(path: &Path) -> Result<Self, String> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read project file: {}", e))?;
+
+        let mut project: Project = parse_versioned(&json, migrate_project)
+            .map_err(|e| format!("Failed to parse project file: {}", e))?;
+
+        if project.worksheets.is_empty() {
+            return Err("Project file contains no worksheets".to_string());
+        }
+        if let Some(base) = path.parent() {
+            resolve_relative_paths(&mut project.worksheets, base);
+        }
+
+        // Pattern bits aren't serialized, so rebuild them from the saved input
+        project.patterns = project.patterns
+            .into_iter()
+            .map(|pattern| {
+                let slips = pattern.slips;
+                Pattern::new(pattern.name, pattern.format, pattern.input, pattern.garbles)
+                    .map(|rebuilt| Pattern { slips, ..rebuilt })
+            })
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("Invalid pattern in project file: {}", e))?;
+
+        Ok(project)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::PatternFormat;
+    use crate::core::ViewMode;
+    use crate::storage::{FrameWidthScan, WorksheetView};
+    use crate::view::ByteColumn;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_project_round_trip() {
+        let mut worksheet = Worksheet::new("Capture A".to_string());
+        worksheet.file_path = Some("capture.bin".into());
+        let view = WorksheetView {
+            view_mode: ViewMode::Byte,
+            frame_length: 80,
+            bit_size: 2.5,
+            show_original: true,
+            scroll_offset: [0.0, 640.0],
+            super_frame_length: 16,
+        };
+        worksheet.view = Some(view);
+        let mut layout = ByteViewConfig::default();
+        layout.columns.push(ByteColumn::new("Sync".to_string(), 0, 15, [255, 0, 0]));
+        worksheet.byte_view = Some(layout.clone());
+        let scan = FrameWidthScan { widths: "96..1200 step 8".to_string(), delta: 0, best_width: 200, best_score: 0.75 };
+        worksheet.frame_width_scans.push(scan.clone());
+        let pattern = Pattern::new("Sync".to_string(), PatternFormat::Hex, "0x1ACF".to_string(), 1).unwrap();
+        let config = ByteViewConfig {
+            bytes_per_row: 24,
+            ..ByteViewConfig::default()
+        };
+        let project = Project::new(vec![worksheet, Worksheet::new("B".to_string())], 1, vec![pattern], config);
+
+        let file = NamedTempFile::new().unwrap();
+        project.save_to_file(file.path()).unwrap();
+        let loaded = Project::load_from_file(file.path()).unwrap();
+
+        assert_eq!(loaded.version, PROJECT_VERSION);
+        assert_eq!(loaded.worksheets.len(), 2);
+        assert_eq!(loaded.worksheets[0].name, "Capture A");
+        assert_eq!(loaded.worksheets[0].view, Some(view));
+        assert_eq!(loaded.worksheets[1].view, None);
+        assert_eq!(loaded.worksheets[0].byte_view, Some(layout));
+        assert_eq!(loaded.worksheets[1].byte_view, None);
+        assert_eq!(loaded.worksheets[0].frame_width_scans, vec![scan]);
+        assert!(loaded.worksheets[1].frame_width_scans.is_empty());
+        assert_eq!(loaded.current_worksheet_index, 1);
+        assert_eq!(loaded.byte_view_config.bytes_per_row, 24);
+        assert_eq!(loaded.patterns.len(), 1);
+        assert_eq!(loaded.patterns[0].bits.len(), 16);
+        assert_eq!(loaded.patterns[0].garbles, 1);
+    }
+
+    #[test]
+    fn test_project_without_worksheets_is_rejected() {
+        let file = NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), r#"{ "worksheets": [], "current_worksheet_index": 0 }"#).unwrap();
+        assert!(Project::load_from_file(file.path()).is_err());
+    }
+}
@@ -1,9 +1,14 @@
+use super::migration::{migrate_session, parse_versioned, SESSION_VERSION};
+use super::paths::{make_paths_relative, resolve_relative_paths};
 use super::worksheet::Worksheet;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppSession {
+    /// Format version, see `migration`
+    #[serde(default)]
+    pub version: u32,
     pub worksheets: Vec<Worksheet>,
     pub current_worksheet_index: usize,
 }
@@ -11,6 +16,7 @@ pub struct AppSession {
 impl AppSession {
     pub fn new(worksheets: Vec<Worksheet>, current_worksheet_index: usize) -> Self {
         Self {
+            version: SESSION_VERSION,
             worksheets,
             current_worksheet_index,
         }
@@ -26,7 +32,11 @@ impl AppSession {
     
     pub fn save(&self) -> Result<(), String> {
         let path = Self::session_file_path();
-        let json = serde_json::to_string_pretty(self)
+        let mut session = self.clone();
+        if let Some(base) = path.parent() {
+            make_paths_relative(&mut session.worksheets, base);
+        }
+        let json = serde_json::to_string_pretty(&session)
             .map_err(|e| format!("Failed to serialize session: {}", e))?;
         
         std::fs::write(&path, json)
@@ -45,8 +55,11 @@ impl AppSession {
         let json = std::fs::read_to_string(&path)
             .map_err(|e| format!("Failed to read session file: {}", e))?;
         
-        let session: Self = serde_json::from_str(&json)
+        let mut session: Self = parse_versioned(&json, migrate_session)
             .map_err(|e| format!("Failed to parse session file: {}", e))?;
+        if let Some(base) = path.parent() {
+            resolve_relative_paths(&mut session.worksheets, base);
+        }
         
         Ok(session)
     }
@@ -60,8 +73,16 @@ impl AppSession {
         Ok(())
     }
     
-    #[allow(dead_code)]
     pub fn exists() -> bool {
         Self::session_file_path().exists()
     }
+    
+    /// Move an unreadable session file aside so the next save doesn't overwrite it
+    pub fn back_up_unreadable() -> Result<PathBuf, String> {
+        let path = Self::session_file_path();
+        let backup = path.with_file_name("last_session.unreadable.json");
+        std::fs::rename(&path, &backup)
+            .map_err(|e| format!("Failed to back up session file: {}", e))?;
+        Ok(backup)
+    }
 }
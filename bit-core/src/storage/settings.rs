@@ -0,0 +1,243 @@
+use super::migration::{migrate_settings, parse_versioned, SETTINGS_VERSION};
+use crate::processing::{OperationMacro, TakeSkipPreset};
+use crate::view::{BitColors, BitShape};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Which egui theme to use
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AppTheme {
+    #[default]
+    System,
+    Dark,
+    Light,
+}
+
+impl AppTheme {
+    pub fn label(&self) -> &'static str {
+        match self {
+            AppTheme::System => "Follow System",
+            AppTheme::Dark => "Dark",
+            AppTheme::Light => "Light",
+        }
+    }
+}
+
+/// Language of the UI
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::German];
+
+    /// The language's own name for itself
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppSettings {
+    /// Format version, see `migration`
+    #[serde(default)]
+    pub version: u32,
+    pub bit_shape: BitShape,
+    pub show_grid: bool,
+    pub thick_grid_interval_horizontal: usize,
+    pub thick_grid_interval_vertical: usize,
+    pub thick_grid_spacing_horizontal: f32,
+    pub thick_grid_spacing_vertical: f32,
+    pub font_size: f32,
+    /// Scale of the whole UI on top of the display's own scaling, for high-DPI screens
+    #[serde(default = "default_ui_scale")]
+    pub ui_scale: f32,
+    #[serde(default)]
+    pub theme: AppTheme,
+    #[serde(default)]
+    pub language: Language,
+    pub frame_length: usize,
+    #[serde(default = "default_show_minimap")]
+    pub show_minimap: bool,
+    /// Show per-column ones density above the bit grid
+    #[serde(default)]
+    pub show_column_stats: bool,
+    /// Label bit view rows with their offset and columns with their index
+    #[serde(default)]
+    pub show_bit_offsets: bool,
+    /// Draw each bit view row's fraction of ones beside it
+    #[serde(default)]
+    pub show_row_density: bool,
+    /// Show worksheets as tabs above the viewer
+    #[serde(default)]
+    pub show_worksheet_tabs: bool,
+    /// Custom bit and highlight colors; `None` follows the light/dark theme
+    #[serde(default)]
+    pub bit_colors: Option<BitColors>,
+    /// Memory-map files above `memory_map_threshold_mb` instead of reading them into memory
+    #[serde(default = "default_memory_map_large_files")]
+    pub memory_map_large_files: bool,
+    #[serde(default = "default_memory_map_threshold_mb")]
+    pub memory_map_threshold_mb: u64,
+    /// Memory kept for intermediate operation results on top of the data itself, in MB;
+    /// 0 turns the cache off
+    #[serde(default = "default_operation_cache_mb")]
+    pub operation_cache_mb: u64,
+    /// Periodically snapshot the session for crash recovery
+    #[serde(default = "default_autosave_enabled")]
+    pub autosave_enabled: bool,
+    #[serde(default = "default_autosave_interval_secs")]
+    pub autosave_interval_secs: u64,
+    /// Saved operation chains listed under Available Operations
+    #[serde(default)]
+    pub macros: Vec<OperationMacro>,
+    /// Take/Skip sequences saved from the editor, listed after the built-in presets
+    #[serde(default)]
+    pub takeskip_presets: Vec<TakeSkipPreset>,
+    /// Recompute after every operation edit; when off, edits wait for "Reapply All"
+    #[serde(default = "default_auto_apply_operations")]
+    pub auto_apply_operations: bool,
+    /// Flag operations that drop more than this percentage of their input
+    #[serde(default = "default_max_drop_percent")]
+    pub max_drop_percent: f32,
+}
+
+/// Range of the UI scale setting
+pub const MIN_UI_SCALE: f32 = 0.5;
+pub const MAX_UI_SCALE: f32 = 3.0;
+
+fn default_ui_scale() -> f32 {
+    1.0
+}
+
+fn default_show_minimap() -> bool {
+    true
+}
+
+fn default_memory_map_large_files() -> bool {
+    true
+}
+
+fn default_memory_map_threshold_mb() -> u64 {
+    256
+}
+
+fn default_operation_cache_mb() -> u64 {
+    256
+}
+
+fn default_autosave_enabled() -> bool {
+    true
+}
+
+fn default_autosave_interval_secs() -> u64 {
+    120
+}
+
+fn default_auto_apply_operations() -> bool {
+    true
+}
+
+fn default_max_drop_percent() -> f32 {
+    90.0
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            bit_shape: BitShape::Square,
+            show_grid: true,
+            thick_grid_interval_horizontal: 0,
+            thick_grid_interval_vertical: 0,
+            thick_grid_spacing_horizontal: 0.0,
+            thick_grid_spacing_vertical: 0.0,
+            font_size: 14.0,
+            ui_scale: default_ui_scale(),
+            theme: AppTheme::System,
+            language: Language::English,
+            frame_length: 64,
+            show_minimap: default_show_minimap(),
+            show_column_stats: false,
+            show_row_density: false,
+            show_bit_offsets: false,
+            show_worksheet_tabs: false,
+            bit_colors: None,
+            memory_map_large_files: default_memory_map_large_files(),
+            memory_map_threshold_mb: default_memory_map_threshold_mb(),
+            operation_cache_mb: default_operation_cache_mb(),
+            autosave_enabled: default_autosave_enabled(),
+            autosave_interval_secs: default_autosave_interval_secs(),
+            macros: Vec::new(),
+            takeskip_presets: Vec::new(),
+            auto_apply_operations: default_auto_apply_operations(),
+            max_drop_percent: default_max_drop_percent(),
+        }
+    }
+}
+
+impl AppSettings {
+    /// File size in bytes above which files are memory-mapped, if enabled
+    pub fn memory_map_threshold(&self) -> Option<u64> {
+        if self.memory_map_large_files {
+            Some(self.memory_map_threshold_mb * 1024 * 1024)
+        } else {
+            None
+        }
+    }
+    
+    /// Bytes the operation cache may hold
+    pub fn operation_cache_budget(&self) -> usize {
+        (self.operation_cache_mb as usize).saturating_mul(1024 * 1024)
+    }
+    
+    pub fn settings_file_path() -> PathBuf {
+        let mut path = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
+        path.push("bit");
+        std::fs::create_dir_all(&path).ok();
+        path.push("settings.json");
+        path
+    }
+    
+    pub fn save_to_file(&self, path: &PathBuf) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+        
+        std::fs::write(path, json)
+            .map_err(|e| format!("Failed to write settings file: {}", e))?;
+        
+        Ok(())
+    }
+    
+    pub fn load_from_file(path: &PathBuf) -> Result<Self, String> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read settings file: {}", e))?;
+        
+        let settings: AppSettings = parse_versioned(&json, migrate_settings)
+            .map_err(|e| format!("Failed to parse settings file: {}", e))?;
+        
+        Ok(settings)
+    }
+    
+    pub fn auto_save(&self) {
+        let path = Self::settings_file_path();
+        self.save_to_file(&path).ok(); // Ignore errors for auto-save
+    }
+    
+    /// Load the auto-saved settings, or defaults if there are none yet.
+    /// Errors only when a settings file exists but can't be read.
+    pub fn auto_load() -> Result<Self, String> {
+        let path = Self::settings_file_path();
+        if path.exists() {
+            Self::load_from_file(&path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+}
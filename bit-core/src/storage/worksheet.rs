@@ -0,0 +1,144 @@
+use super::file_io::BitOrder;
+use super::migration::{migrate_worksheet, parse_versioned, WORKSHEET_VERSION};
+use super::paths::{make_paths_relative, resolve_relative_paths};
+use crate::core::ViewMode;
+use crate::processing::{BitOperation, WorksheetOperation};
+use crate::view::ByteViewConfig;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static WORKSHEET_ID_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// A fresh worksheet id, unique across sessions in practice
+pub fn new_worksheet_id() -> u64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0);
+    // Never 0, which marks a missing id
+    nanos.wrapping_add(WORKSHEET_ID_COUNTER.fetch_add(1, Ordering::Relaxed)).max(1)
+}
+
+/// Where a worksheet was being looked at, so switching back to it returns to the same place
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WorksheetView {
+    pub view_mode: ViewMode,
+    /// Bit view frame width; a width locked in from an analysis is kept here too
+    pub frame_length: usize,
+    /// Bit view zoom
+    pub bit_size: f32,
+    pub show_original: bool,
+    /// Bit view scroll offset, in points
+    pub scroll_offset: [f32; 2],
+    /// Frames per super-frame, 0 for none
+    #[serde(default)]
+    pub super_frame_length: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Worksheet {
+    /// Format version, see `migration`
+    #[serde(default)]
+    pub version: u32,
+    /// Stable id that Multi-Worksheet Load operations refer to
+    #[serde(default = "new_worksheet_id")]
+    pub id: u64,
+    pub name: String,
+    pub file_path: Option<PathBuf>,
+    pub operations: Vec<BitOperation>,
+    /// Bit order used for every file this worksheet reads
+    #[serde(default)]
+    pub bit_order: BitOrder,
+    /// View state when the worksheet was last left; None keeps the current view
+    #[serde(default)]
+    pub view: Option<WorksheetView>,
+    /// Byte view columns and layout when the worksheet was last left; None keeps the current ones
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub byte_view: Option<ByteViewConfig>,
+    /// Frame width scans run on this worksheet, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub frame_width_scans: Vec<FrameWidthScan>,
+}
+
+/// Most frame width scans a worksheet remembers
+pub const MAX_FRAME_WIDTH_SCANS: usize = 10;
+
+/// The widths a frame width scan tried and the best one it found
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FrameWidthScan {
+    /// Width list as typed, like `96..1200 step 8`
+    pub widths: String,
+    pub delta: usize,
+    pub best_width: usize,
+    pub best_score: f64,
+}
+
+impl Worksheet {
+    pub fn new(name: String) -> Self {
+        Self {
+            version: WORKSHEET_VERSION,
+            id: new_worksheet_id(),
+            name,
+            file_path: None,
+            operations: Vec::new(),
+            bit_order: BitOrder::default(),
+            view: None,
+            byte_view: None,
+            frame_width_scans: Vec::new(),
+        }
+    }
+    
+    /// Call `f` on the worksheet's file and every file its operations read
+    pub fn for_each_file_path_mut(&mut self, f: &mut dyn FnMut(&mut PathBuf)) {
+        if let Some(path) = &mut self.file_path {
+            f(path);
+        }
+        for op in &mut self.operations {
+            op.for_each_file_path_mut(f);
+        }
+    }
+    
+    /// Save with file paths inside the worksheet file's folder stored relative to it
+    pub fn save_to_file(&self, path: &PathBuf) -> Result<(), String> {
+        let mut worksheet = self.clone();
+        if let Some(base) = path.parent() {
+            make_paths_relative(std::slice::from_mut(&mut worksheet), base);
+        }
+        let json = serde_json::to_string_pretty(&worksheet)
+            .map_err(|e| format!("Failed to serialize worksheet: {}", e))?;
+        
+        std::fs::write(path, json)
+            .map_err(|e| format!("Failed to write worksheet file: {}", e))?;
+        
+        Ok(())
+    }
+    
+    pub fn load_from_file(path: &PathBuf) -> Result<Self, String> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read worksheet file: {}", e))?;
+        
+        let mut worksheet: Worksheet = parse_versioned(&json, migrate_worksheet)
+            .map_err(|e| format!("Failed to parse worksheet file: {}", e))?;
+        if let Some(base) = path.parent() {
+            resolve_relative_paths(std::slice::from_mut(&mut worksheet), base);
+        }
+        
+        Ok(worksheet)
+    }
+}
+
+/// Index of the worksheet `reference` reads from: by id, falling back to name
+pub fn find_worksheet(worksheets: &[Worksheet], reference: &WorksheetOperation) -> Option<usize> {
+    worksheets
+        .iter()
+        .position(|ws| ws.id == reference.worksheet_id)
+        .or_else(|| {
+            if reference.worksheet_name.is_empty() {
+                None
+            } else {
+                worksheets.iter().position(|ws| ws.name == reference.worksheet_name)
+            }
+        })
+}
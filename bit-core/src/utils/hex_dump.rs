@@ -0,0 +1,47 @@
+// Hex dump text - byte ranges formatted for pasting into chats and issues
+
+/// `bytes` as lines of `bytes_per_line` bytes, each with the offset of its first byte counted
+/// from `start_offset`, the bytes in hex and their printable ASCII, like `hexdump -C`
+pub fn hex_dump(bytes: &[u8], start_offset: usize, bytes_per_line: usize) -> String {
+    let bytes_per_line = bytes_per_line.max(1);
+    let mut text = String::new();
+    for (line, chunk) in bytes.chunks(bytes_per_line).enumerate() {
+        let hex: Vec<String> = chunk.iter().map(|byte| format!("{:02X}", byte)).collect();
+        let ascii: String = chunk
+            .iter()
+            .map(|&byte| if byte.is_ascii_graphic() || byte == b' ' { byte as char } else { '.' })
+            .collect();
+        text.push_str(&format!(
+            "{:08X}  {:<width$}  |{}|\n",
+            start_offset + line * bytes_per_line,
+            hex.join(" "),
+            ascii,
+            width = bytes_per_line * 3 - 1
+        ));
+    }
+    text
+}
+
+/// `bytes` as one unbroken run of hex digits
+pub fn raw_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02X}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_dump_lines() {
+        let bytes = b"\x1A\xCF\xFC\x1DHi!\x00\x7F";
+        let dump = hex_dump(bytes, 0x20, 4);
+        let lines: Vec<&str> = dump.lines().collect();
+        assert_eq!(lines, vec![
+            "00000020  1A CF FC 1D  |....|",
+            "00000024  48 69 21 00  |Hi!.|",
+            "00000028  7F           |.|",
+        ]);
+        assert_eq!(raw_hex(&bytes[..4]), "1ACFFC1D");
+        assert_eq!(hex_dump(&[], 0, 16), "");
+    }
+}
@@ -2,6 +2,11 @@
 /// Supports +, -, *, / operations and follows order of operations
 
 pub fn eval_expression(expr: &str) -> Result<usize, String> {
+    eval_expression_with(expr, &[])
+}
+
+/// Same as `eval_expression`, with names such as `len` standing for the values in `vars`
+pub fn eval_expression_with(expr: &str, vars: &[(&str, usize)]) -> Result<usize, String> {
     let expr = expr.trim().replace(" ", "");
     
     if expr.is_empty() {
@@ -14,7 +19,7 @@ pub fn eval_expression(expr: &str) -> Result<usize, String> {
     }
     
     // Parse and evaluate the expression
-    let tokens = tokenize(&expr)?;
+    let tokens = tokenize(&expr, vars)?;
     let result = evaluate(&tokens)?;
     
     Ok(result)
@@ -29,15 +34,32 @@ enum Token {
     Divide,
 }
 
-fn tokenize(expr: &str) -> Result<Vec<Token>, String> {
+fn tokenize(expr: &str, vars: &[(&str, usize)]) -> Result<Vec<Token>, String> {
     let mut tokens = Vec::new();
     let mut current_number = String::new();
+    let mut chars = expr.chars().peekable();
     
-    for ch in expr.chars() {
+    while let Some(ch) = chars.next() {
         match ch {
             '0'..='9' => {
                 current_number.push(ch);
             }
+            'a'..='z' | 'A'..='Z' | '_' => {
+                let mut name = ch.to_string();
+                while let Some(&next) = chars.peek().filter(|c| c.is_ascii_alphanumeric() || **c == '_') {
+                    name.push(next);
+                    chars.next();
+                }
+                if !current_number.is_empty() || matches!(tokens.last(), Some(Token::Number(_))) {
+                    return Err(format!("Missing operator before {}", name));
+                }
+                let value = vars
+                    .iter()
+                    .find(|(var, _)| *var == name)
+                    .map(|&(_, value)| value)
+                    .ok_or_else(|| format!("Unknown variable: {}", name))?;
+                tokens.push(Token::Number(value));
+            }
             '+' | '-' | '*' | '/' => {
                 if !current_number.is_empty() {
                     let num = current_number.parse::<usize>()
@@ -244,4 +266,15 @@ mod tests {
         assert!(eval_expression("*5").is_err());
         assert!(eval_expression("abc").is_err());
     }
+    
+    #[test]
+    fn test_variables() {
+        let vars = [("len", 4096), ("frame_len", 64)];
+        assert_eq!(eval_expression_with("len-1024", &vars).unwrap(), 3072);
+        assert_eq!(eval_expression_with("frame_len*10", &vars).unwrap(), 640);
+        assert_eq!(eval_expression_with("len / frame_len", &vars).unwrap(), 64);
+        assert!(eval_expression_with("frames", &vars).unwrap_err().contains("Unknown variable"));
+        assert!(eval_expression_with("2len", &vars).is_err());
+        assert!(eval_expression("len").is_err());
+    }
 }
@@ -0,0 +1,11 @@
+pub mod csv;
+pub mod hex_dump;
+pub mod math_eval;
+pub mod number_base;
+pub mod text_encoding;
+
+pub use csv::csv_field;
+pub use hex_dump::{hex_dump, raw_hex};
+pub use math_eval::{eval_expression, eval_expression_with};
+pub use number_base::{format_number, parse_number, to_signed, NumberBase};
+pub use text_encoding::{char_code, TextDecoder, TextEncoding};
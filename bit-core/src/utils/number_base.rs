@@ -0,0 +1,156 @@
+// Number bases - parsing and formatting values for the converter scratchpad
+
+use super::eval_expression;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumberBase {
+    Binary,
+    Octal,
+    Decimal,
+    Hex,
+    Ascii,
+}
+
+impl NumberBase {
+    pub const ALL: [NumberBase; 5] = [
+        NumberBase::Binary,
+        NumberBase::Octal,
+        NumberBase::Decimal,
+        NumberBase::Hex,
+        NumberBase::Ascii,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            NumberBase::Binary => "Binary",
+            NumberBase::Octal => "Octal",
+            NumberBase::Decimal => "Decimal",
+            NumberBase::Hex => "Hex",
+            NumberBase::Ascii => "ASCII",
+        }
+    }
+
+    fn radix(&self) -> Option<u32> {
+        match self {
+            NumberBase::Binary => Some(2),
+            NumberBase::Octal => Some(8),
+            NumberBase::Decimal => Some(10),
+            NumberBase::Hex => Some(16),
+            NumberBase::Ascii => None,
+        }
+    }
+}
+
+/// Read `text` as a number in `base`. Decimal accepts math expressions like the other value
+/// fields; binary, octal and hex take an optional 0b/0o/0x prefix and ignore spaces and
+/// underscores; ASCII packs the characters' bytes big-endian.
+pub fn parse_number(text: &str, base: NumberBase) -> Result<u64, String> {
+    match base {
+        NumberBase::Decimal => eval_expression(text).map(|value| value as u64),
+        NumberBase::Ascii => {
+            if text.is_empty() {
+                return Err("Enter some text".to_string());
+            }
+            if !text.is_ascii() || text.len() > 8 {
+                return Err("ASCII input must be at most 8 ASCII characters".to_string());
+            }
+            Ok(text.bytes().fold(0u64, |value, byte| (value << 8) | byte as u64))
+        }
+        _ => {
+            let radix = base.radix().unwrap_or(10);
+            let prefix = match base {
+                NumberBase::Binary => "0b",
+                NumberBase::Octal => "0o",
+                _ => "0x",
+            };
+            let digits: String = text.chars().filter(|c| !c.is_whitespace() && *c != '_').collect();
+            let lower = digits.to_ascii_lowercase();
+            let digits = lower.strip_prefix(prefix).unwrap_or(&lower);
+            if digits.is_empty() {
+                return Err("Enter a number".to_string());
+            }
+            u64::from_str_radix(digits, radix).map_err(|e| format!("Invalid {} number: {}", base.name().to_lowercase(), e))
+        }
+    }
+}
+
+/// `value` cut to its low `width` bits (1 to 64)
+pub fn mask_to_width(value: u64, width: u32) -> u64 {
+    if width >= 64 { value } else { value & ((1u64 << width) - 1) }
+}
+
+/// The low `width` bits of `value` read as two's complement
+pub fn to_signed(value: u64, width: u32) -> i64 {
+    let width = width.clamp(1, 64);
+    let shift = 64 - width;
+    ((mask_to_width(value, width) << shift) as i64) >> shift
+}
+
+/// The low `width` bits of `value` in `base`: binary and hex are zero-padded to the width and
+/// grouped, ASCII shows one character per byte with '.' for non-printable bytes
+pub fn format_number(value: u64, base: NumberBase, width: u32) -> String {
+    let width = width.clamp(1, 64);
+    let value = mask_to_width(value, width);
+    match base {
+        NumberBase::Binary => {
+            let digits = format!("{:0width$b}", value, width = width as usize);
+            group_from_right(&digits, 4)
+        }
+        NumberBase::Octal => format!("{:o}", value),
+        NumberBase::Decimal => value.to_string(),
+        NumberBase::Hex => {
+            let digits = format!("{:0width$X}", value, width = width.div_ceil(4) as usize);
+            group_from_right(&digits, 2)
+        }
+        NumberBase::Ascii => (0..width.div_ceil(8))
+            .rev()
+            .map(|byte| {
+                let byte = (value >> (byte * 8)) as u8;
+                if byte.is_ascii_graphic() || byte == b' ' { byte as char } else { '.' }
+            })
+            .collect(),
+    }
+}
+
+fn group_from_right(digits: &str, size: usize) -> String {
+    let chars: Vec<char> = digits.chars().collect();
+    let first = match chars.len() % size {
+        0 => size,
+        rest => rest,
+    };
+    let mut grouped = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if i >= first && (i - first).is_multiple_of(size) {
+            grouped.push(' ');
+        }
+        grouped.push(*c);
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_each_base() {
+        assert_eq!(parse_number("0x1A_CF", NumberBase::Hex), Ok(0x1ACF));
+        assert_eq!(parse_number("1010 0101", NumberBase::Binary), Ok(0xA5));
+        assert_eq!(parse_number("0o17", NumberBase::Octal), Ok(15));
+        assert_eq!(parse_number("64*8+3", NumberBase::Decimal), Ok(515));
+        assert_eq!(parse_number("OK", NumberBase::Ascii), Ok(0x4F4B));
+        assert!(parse_number("12G", NumberBase::Hex).is_err());
+    }
+
+    #[test]
+    fn test_format_to_width() {
+        assert_eq!(format_number(0x1ACF, NumberBase::Hex, 16), "1A CF");
+        assert_eq!(format_number(0x1ACF, NumberBase::Hex, 12), "A CF");
+        assert_eq!(format_number(5, NumberBase::Binary, 6), "00 0101");
+        assert_eq!(format_number(0x4F4B00, NumberBase::Ascii, 24), "OK.");
+        assert_eq!(format_number(0x1FF, NumberBase::Decimal, 8), "255");
+        assert_eq!(to_signed(0xFF, 8), -1);
+        assert_eq!(to_signed(0x7F, 8), 127);
+        assert_eq!(to_signed(u64::MAX, 64), -1);
+    }
+}
@@ -0,0 +1,252 @@
+// Character encodings for the ASCII view
+
+use bitvec::prelude::*;
+
+/// Baudot/ITA2 code that switches to the letters shift
+const BAUDOT_LTRS: u8 = 0x1F;
+/// Baudot/ITA2 code that switches to the figures shift
+const BAUDOT_FIGS: u8 = 0x1B;
+/// How many characters to look back for a Baudot shift code before assuming letters
+const BAUDOT_SHIFT_LOOKBACK: usize = 65536;
+
+/// How raw character codes are turned into text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextEncoding {
+    #[default]
+    Ascii,
+    Ascii7,
+    Ebcdic,
+    Baudot,
+    Cp437,
+}
+
+impl TextEncoding {
+    pub const ALL: [TextEncoding; 5] = [
+        TextEncoding::Ascii,
+        TextEncoding::Ascii7,
+        TextEncoding::Ebcdic,
+        TextEncoding::Baudot,
+        TextEncoding::Cp437,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TextEncoding::Ascii => "ASCII",
+            TextEncoding::Ascii7 => "7-bit ASCII",
+            TextEncoding::Ebcdic => "EBCDIC",
+            TextEncoding::Baudot => "Baudot/ITA2",
+            TextEncoding::Cp437 => "CP437",
+        }
+    }
+
+    /// Number of bits in one code of this encoding
+    pub fn code_bits(&self) -> usize {
+        match self {
+            TextEncoding::Ascii | TextEncoding::Ebcdic | TextEncoding::Cp437 => 8,
+            TextEncoding::Ascii7 => 7,
+            TextEncoding::Baudot => 5,
+        }
+    }
+}
+
+/// Decodes one character code at a time, tracking Baudot shift state
+#[derive(Debug, Clone, Copy)]
+pub struct TextDecoder {
+    encoding: TextEncoding,
+    figures: bool,
+}
+
+impl TextDecoder {
+    pub fn new(encoding: TextEncoding) -> Self {
+        Self {
+            encoding,
+            figures: false,
+        }
+    }
+
+    /// Create a decoder for character `index` of `bits`, recovering the Baudot
+    /// shift state from the last LTRS or FIGS code before it
+    pub fn starting_at(encoding: TextEncoding, bits: &BitSlice<u8, Msb0>, bits_per_char: usize, index: usize) -> Self {
+        let mut decoder = Self::new(encoding);
+        if encoding != TextEncoding::Baudot || bits_per_char == 0 {
+            return decoder;
+        }
+
+        let first = index.saturating_sub(BAUDOT_SHIFT_LOOKBACK);
+        for char_index in (first..index).rev() {
+            let start = char_index * bits_per_char;
+            let end = (start + bits_per_char).min(bits.len());
+            match char_code(&bits[start..end], encoding) {
+                BAUDOT_LTRS => break,
+                BAUDOT_FIGS => {
+                    decoder.figures = true;
+                    break;
+                }
+                _ => {}
+            }
+        }
+        decoder
+    }
+
+    /// Decode one code; `None` for control and unmapped codes
+    pub fn decode(&mut self, code: u8) -> Option<char> {
+        let ch = match self.encoding {
+            TextEncoding::Ascii | TextEncoding::Ascii7 => {
+                if (32..=126).contains(&code) { code as char } else { '\0' }
+            }
+            TextEncoding::Ebcdic => {
+                if (0x40..0xFF).contains(&code) { EBCDIC_0X40[(code - 0x40) as usize] } else { '\0' }
+            }
+            TextEncoding::Cp437 => match code {
+                0 => '\0',
+                1..=0x1F => CP437_LOW[(code - 1) as usize],
+                0x20..=0x7E => code as char,
+                0x7F => '⌂',
+                _ => CP437_HIGH[(code - 0x80) as usize],
+            },
+            TextEncoding::Baudot => {
+                let code = code & 0x1F;
+                if code == BAUDOT_LTRS {
+                    self.figures = false;
+                } else if code == BAUDOT_FIGS {
+                    self.figures = true;
+                }
+                if self.figures { BAUDOT_FIGURES[code as usize] } else { BAUDOT_LETTERS[code as usize] }
+            }
+        };
+        (ch != '\0').then_some(ch)
+    }
+}
+
+/// Read one character's bits MSB first, keeping only the low `code_bits` of the encoding
+/// (e.g. the parity bit in front of a 7-bit ASCII character is dropped)
+pub fn char_code(bits: &BitSlice<u8, Msb0>, encoding: TextEncoding) -> u8 {
+    let mut code = 0u16;
+    for bit in bits.iter().take(16) {
+        code = (code << 1) | (*bit as u16);
+    }
+    let mask = (1u16 << encoding.code_bits()) - 1;
+    (code & mask) as u8
+}
+
+/// ITA2 letters shift; NUL marks codes without a printable character
+const BAUDOT_LETTERS: [char; 32] = [
+    '\0', 'E', '\0', 'A', ' ', 'S', 'I', 'U', '\0', 'D', 'R', 'J', 'N', 'F', 'C', 'K',
+    'T', 'Z', 'L', 'W', 'H', 'Y', 'P', 'Q', 'O', 'B', 'G', '\0', 'M', 'X', 'V', '\0',
+];
+
+/// ITA2 figures shift; national-use positions use the US teleprinter symbols
+const BAUDOT_FIGURES: [char; 32] = [
+    '\0', '3', '\0', '-', ' ', '\'', '8', '7', '\0', '\0', '4', '\0', ',', '!', ':', '(',
+    '5', '+', ')', '2', '#', '6', '0', '1', '9', '?', '&', '\0', '.', '/', '=', '\0',
+];
+
+/// EBCDIC (code page 037) characters for codes 0x40..=0xFE; lower codes are controls
+const EBCDIC_0X40: [char; 191] = [
+    ' ', ' ', 'â', 'ä', 'à', 'á', 'ã', 'å', 'ç', 'ñ', '¢', '.', '<', '(', '+', '|',
+    '&', 'é', 'ê', 'ë', 'è', 'í', 'î', 'ï', 'ì', 'ß', '!', '$', '*', ')', ';', '¬',
+    '-', '/', 'Â', 'Ä', 'À', 'Á', 'Ã', 'Å', 'Ç', 'Ñ', '¦', ',', '%', '_', '>', '?',
+    'ø', 'É', 'Ê', 'Ë', 'È', 'Í', 'Î', 'Ï', 'Ì', '`', ':', '#', '@', '\'', '=', '"',
+    'Ø', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', '«', '»', 'ð', 'ý', 'þ', '±',
+    '°', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 'ª', 'º', 'æ', '¸', 'Æ', '¤',
+    'µ', '~', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z', '¡', '¿', 'Ð', 'Ý', 'Þ', '®',
+    '^', '£', '¥', '·', '©', '§', '¶', '¼', '½', '¾', '[', ']', '¯', '¨', '´', '×',
+    '{', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', '-', 'ô', 'ö', 'ò', 'ó', 'õ',
+    '}', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', '¹', 'û', 'ü', 'ù', 'ú', 'ÿ',
+    '\\', '÷', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '²', 'Ô', 'Ö', 'Ò', 'Ó', 'Õ',
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', '³', 'Û', 'Ü', 'Ù', 'Ú',
+];
+
+/// CP437 glyphs for codes 0x01..=0x1F
+const CP437_LOW: [char; 31] = [
+    '☺', '☻', '♥', '♦', '♣', '♠', '•', '◘', '○', '◙', '♂', '♀', '♪', '♫', '☼', '►',
+    '◄', '↕', '‼', '¶', '§', '▬', '↨', '↑', '↓', '→', '←', '∟', '↔', '▲', '▼',
+];
+
+/// CP437 characters for codes 0x80..=0xFF
+const CP437_HIGH: [char; 128] = [
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å',
+    'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ',
+    'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»',
+    '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕', '╣', '║', '╗', '╝', '╜', '╛', '┐',
+    '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦', '╠', '═', '╬', '╧',
+    '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐', '▀',
+    'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩',
+    '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', ' ',
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_all(encoding: TextEncoding, bits: &BitSlice<u8, Msb0>, bits_per_char: usize) -> String {
+        let mut decoder = TextDecoder::new(encoding);
+        bits.chunks(bits_per_char)
+            .map(|chunk| decoder.decode(char_code(chunk, encoding)).unwrap_or('.'))
+            .collect()
+    }
+
+    #[test]
+    fn test_ascii() {
+        let bits = BitVec::<u8, Msb0>::from_slice(b"Hi\n");
+        assert_eq!(decode_all(TextEncoding::Ascii, &bits, 8), "Hi.");
+    }
+
+    #[test]
+    fn test_ascii7_drops_parity_bit() {
+        // 'A' (0x41) with the parity bit set
+        let bits = BitVec::<u8, Msb0>::from_slice(&[0xC1]);
+        assert_eq!(decode_all(TextEncoding::Ascii, &bits, 8), ".");
+        assert_eq!(decode_all(TextEncoding::Ascii7, &bits, 8), "A");
+    }
+
+    #[test]
+    fn test_packed_7bit_ascii() {
+        // "OK" as two 7-bit characters: 1001111 1001011
+        let bits: BitVec<u8, Msb0> = [1, 0, 0, 1, 1, 1, 1, 1, 0, 0, 1, 0, 1, 1].iter().map(|&b| b == 1).collect();
+        assert_eq!(decode_all(TextEncoding::Ascii7, &bits, 7), "OK");
+    }
+
+    #[test]
+    fn test_ebcdic() {
+        let bits = BitVec::<u8, Msb0>::from_slice(&[0xC8, 0x89, 0x40, 0xF1, 0x25]);
+        assert_eq!(decode_all(TextEncoding::Ebcdic, &bits, 8), "Hi 1.");
+    }
+
+    #[test]
+    fn test_cp437() {
+        let bits = BitVec::<u8, Msb0>::from_slice(&[0x01, 0x41, 0xB0, 0xDB, 0x00]);
+        assert_eq!(decode_all(TextEncoding::Cp437, &bits, 8), "☺A░█.");
+    }
+
+    fn baudot_bits(codes: &[u8]) -> BitVec<u8, Msb0> {
+        let mut bits = BitVec::new();
+        for &code in codes {
+            for i in (0..5).rev() {
+                bits.push((code >> i) & 1 == 1);
+            }
+        }
+        bits
+    }
+
+    #[test]
+    fn test_baudot_shift_states() {
+        // A B FIGS 1 2 LTRS C
+        let bits = baudot_bits(&[0x03, 0x19, BAUDOT_FIGS, 0x17, 0x13, BAUDOT_LTRS, 0x0E]);
+        assert_eq!(decode_all(TextEncoding::Baudot, &bits, 5), "AB.12.C");
+    }
+
+    #[test]
+    fn test_baudot_starting_at_recovers_shift() {
+        let bits = baudot_bits(&[0x03, BAUDOT_FIGS, 0x17, 0x13, BAUDOT_LTRS, 0x0E]);
+
+        let mut decoder = TextDecoder::starting_at(TextEncoding::Baudot, &bits, 5, 3);
+        assert_eq!(decoder.decode(0x13), Some('2'));
+
+        let mut decoder = TextDecoder::starting_at(TextEncoding::Baudot, &bits, 5, 5);
+        assert_eq!(decoder.decode(0x0E), Some('C'));
+
+        let mut decoder = TextDecoder::starting_at(TextEncoding::Baudot, &bits, 5, 1);
+        assert_eq!(decoder.decode(0x03), Some('A'));
+    }
+}
@@ -0,0 +1,192 @@
+// Byte view layout - labeled columns and how words are read, shared by the byte viewer and
+// the exports
+
+use crate::analysis::TimestampFormat;
+use serde::{Deserialize, Serialize};
+
+/// Represents a labeled column in the byte view
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ByteColumn {
+    pub label: String,
+    pub bit_start: usize,  // Start bit within the byte row (0-based)
+    pub bit_end: usize,    // End bit within the byte row (inclusive)
+    pub color: [u8; 3],    // RGB color
+    #[serde(default = "default_visible")]
+    pub visible: bool,     // Hidden columns keep their definition but aren't drawn
+    /// Decode the field as a time in tooltips
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<TimestampFormat>,
+}
+
+fn default_visible() -> bool {
+    true
+}
+
+impl ByteColumn {
+    pub fn new(label: String, bit_start: usize, bit_end: usize, color: [u8; 3]) -> Self {
+        Self {
+            label,
+            bit_start,
+            bit_end,
+            color,
+            visible: true,
+            timestamp: None,
+        }
+    }
+
+    /// Number of bits in the field
+    pub fn width(&self) -> usize {
+        self.bit_end.saturating_sub(self.bit_start) + 1
+    }
+
+    pub fn byte_range(&self, bytes_per_row: usize) -> (usize, usize) {
+        let start_byte = self.bit_start / 8;
+        let end_byte = self.bit_end / 8;
+        (start_byte.min(bytes_per_row - 1), end_byte.min(bytes_per_row - 1))
+    }
+}
+
+/// How the bytes of a word are interpreted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum WordKind {
+    #[default]
+    Hex,
+    Unsigned,
+    Signed,
+    Float,
+}
+
+impl WordKind {
+    pub const ALL: [WordKind; 4] = [WordKind::Hex, WordKind::Unsigned, WordKind::Signed, WordKind::Float];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            WordKind::Hex => "Hex",
+            WordKind::Unsigned => "Unsigned",
+            WordKind::Signed => "Signed",
+            WordKind::Float => "IEEE Float",
+        }
+    }
+
+    /// Word sizes in bytes this kind can be shown at
+    pub fn sizes(&self) -> &'static [usize] {
+        match self {
+            WordKind::Float => &[4, 8],
+            _ => &[1, 2, 4, 8],
+        }
+    }
+}
+
+/// Byte order of multi-byte words
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Endianness {
+    #[default]
+    Big,
+    Little,
+}
+
+/// Configuration for the byte viewer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ByteViewConfig {
+    pub bytes_per_row: usize,
+    pub columns: Vec<ByteColumn>,
+    pub show_hex_offset: bool,
+    /// Bytes per cell: 1, 2, 4 or 8
+    #[serde(default = "default_word_size")]
+    pub word_size: usize,
+    #[serde(default)]
+    pub word_kind: WordKind,
+    #[serde(default)]
+    pub endianness: Endianness,
+    /// Bits per logical frame that column ranges are relative to;
+    /// `None` makes them relative to the display row
+    #[serde(default)]
+    pub column_frame_length: Option<usize>,
+}
+
+fn default_word_size() -> usize {
+    1
+}
+
+impl Default for ByteViewConfig {
+    fn default() -> Self {
+        Self {
+            bytes_per_row: 16,
+            columns: Vec::new(),
+            show_hex_offset: true,
+            word_size: default_word_size(),
+            word_kind: WordKind::Hex,
+            endianness: Endianness::Big,
+            column_frame_length: None,
+        }
+    }
+}
+
+impl ByteViewConfig {
+    /// Bits that column ranges repeat over
+    pub fn column_period(&self) -> usize {
+        match self.column_frame_length {
+            Some(frame_length) if frame_length > 0 => frame_length,
+            _ => self.bytes_per_row * 8,
+        }
+    }
+
+    /// Offset of absolute bit `bit_pos` within its frame (or display row)
+    pub fn column_bit_offset(&self, bit_pos: usize) -> usize {
+        bit_pos % self.column_period()
+    }
+
+    /// Index of the first visible column covering `bit_offset` within a frame (end bit inclusive)
+    pub fn column_at(&self, bit_offset: usize) -> Option<usize> {
+        self.columns.iter().position(|column| {
+            column.visible && bit_offset >= column.bit_start && bit_offset <= column.bit_end
+        })
+    }
+
+    /// Whether column headers line up with the display rows
+    pub fn columns_follow_rows(&self) -> bool {
+        self.column_period() == self.bytes_per_row * 8
+    }
+
+    /// Assemble `bytes` into one word value using the configured byte order
+    pub fn word_value(&self, bytes: &[u8]) -> u64 {
+        let fold = |value: u64, byte: &u8| (value << 8) | *byte as u64;
+        match self.endianness {
+            Endianness::Big => bytes.iter().fold(0, fold),
+            Endianness::Little => bytes.iter().rev().fold(0, fold),
+        }
+    }
+
+    /// Format a word for a cell; the flag is false for values unlikely to be real
+    /// data (NaN, infinities, subnormals and extreme magnitudes of floats)
+    pub fn format_word(&self, bytes: &[u8]) -> (String, bool) {
+        let value = self.word_value(bytes);
+        let bits = bytes.len() * 8;
+
+        // A partial trailing word is always shown as raw hex
+        if bytes.len() < self.word_size {
+            return (format!("{:0width$X}", value, width = bytes.len() * 2), true);
+        }
+
+        match self.word_kind {
+            WordKind::Hex => (format!("{:0width$X}", value, width = bytes.len() * 2), true),
+            WordKind::Unsigned => (value.to_string(), true),
+            WordKind::Signed => {
+                // Sign-extend from the word width
+                let shift = 64 - bits;
+                (((value << shift) as i64 >> shift).to_string(), true)
+            }
+            WordKind::Float => {
+                let float = if bits == 32 { f32::from_bits(value as u32) as f64 } else { f64::from_bits(value) };
+                let plausible = float == 0.0 || (float.is_normal() && (1e-12..1e12).contains(&float.abs()));
+                let text = if float != 0.0 && (float.abs() >= 1e6 || float.abs() < 1e-3) {
+                    format!("{:.3e}", float)
+                } else {
+                    format!("{:.4}", float)
+                };
+                (text, plausible)
+            }
+        }
+    }
+}
+
@@ -0,0 +1,120 @@
+use crate::analysis::Pattern;
+use serde::{Deserialize, Serialize};
+
+/// Colors used to draw bits and highlights in the viewers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BitColors {
+    pub zero: [u8; 3],       // RGB color of clear bits
+    pub one: [u8; 3],        // RGB color of set bits
+    pub background: [u8; 3], // RGB color behind the bit grid
+    pub grid: [u8; 3],       // RGB color of grid lines
+    pub highlight: [u8; 4],  // RGBA overlay for highlighted bits
+    /// Colors of the first patterns' matches; later patterns get generated hues
+    pub pattern_palette: Vec<[u8; 3]>,
+}
+
+impl Default for BitColors {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+impl BitColors {
+    /// Black set bits on white, the original look
+    pub fn light() -> Self {
+        Self {
+            zero: [255, 255, 255],
+            one: [0, 0, 0],
+            background: [235, 235, 235],
+            grid: [160, 160, 160],
+            highlight: [255, 255, 0, 150],
+            pattern_palette: vec![
+                [255, 100, 100], // Red
+                [100, 255, 100], // Green
+                [100, 100, 255], // Blue
+                [255, 255, 100], // Yellow
+                [255, 100, 255], // Magenta
+                [100, 255, 255], // Cyan
+                [255, 150, 100], // Orange
+                [150, 100, 255], // Purple
+            ],
+        }
+    }
+
+    /// Light set bits on a dark background
+    pub fn dark() -> Self {
+        Self {
+            zero: [40, 40, 40],
+            one: [230, 230, 230],
+            background: [20, 20, 20],
+            grid: [90, 90, 90],
+            highlight: [255, 200, 0, 140],
+            ..Self::light()
+        }
+    }
+
+    /// Okabe-Ito palette, distinguishable with the common forms of color blindness
+    pub fn colorblind_safe() -> Self {
+        Self {
+            zero: [255, 255, 255],
+            one: [0, 114, 178],
+            background: [235, 235, 235],
+            grid: [160, 160, 160],
+            highlight: [230, 159, 0, 170],
+            pattern_palette: vec![
+                [230, 159, 0],   // Orange
+                [86, 180, 233],  // Sky blue
+                [0, 158, 115],   // Bluish green
+                [240, 228, 66],  // Yellow
+                [0, 114, 178],   // Blue
+                [213, 94, 0],    // Vermillion
+                [204, 121, 167], // Reddish purple
+            ],
+        }
+    }
+
+    /// Default colors for the current egui theme
+    pub fn for_dark_mode(dark_mode: bool) -> Self {
+        if dark_mode { Self::dark() } else { Self::light() }
+    }
+
+    /// Palette color for the pattern at `index`. Past the end of the palette, hues are spread
+    /// by the golden angle so that no two patterns share a color.
+    pub fn pattern_color(&self, index: usize) -> [u8; 3] {
+        if let Some(&color) = self.pattern_palette.get(index) {
+            return color;
+        }
+        let step = index - self.pattern_palette.len();
+        let hue = (0.1 + step as f32 * 0.381_966).fract();
+        hsv(hue, 0.65, 0.9)
+    }
+
+    /// Color of `pattern`'s matches: its own if it has one, else the palette color for `index`
+    pub fn color_of(&self, pattern: &Pattern, index: usize) -> [u8; 3] {
+        pattern.color.unwrap_or_else(|| self.pattern_color(index))
+    }
+}
+
+/// RGB of a hue, saturation and value taken in linear light, as egui's `Hsva` does
+fn hsv(hue: f32, saturation: f32, value: f32) -> [u8; 3] {
+    let sector = hue.fract() * 6.0;
+    let f = sector.fract();
+    let p = value * (1.0 - saturation);
+    let q = value * (1.0 - saturation * f);
+    let t = value * (1.0 - saturation * (1.0 - f));
+    let (r, g, b) = match sector as u32 {
+        0 => (value, t, p),
+        1 => (q, value, p),
+        2 => (p, value, t),
+        3 => (p, q, value),
+        4 => (t, p, value),
+        _ => (value, p, q),
+    };
+    [r, g, b].map(srgb)
+}
+
+/// Linear light to an sRGB byte
+fn srgb(linear: f32) -> u8 {
+    let encoded = if linear <= 0.003_130_8 { 12.92 * linear } else { 1.055 * linear.powf(1.0 / 2.4) - 0.055 };
+    (encoded.clamp(0.0, 1.0) * 255.0).round() as u8
+}
@@ -0,0 +1,16 @@
+// View settings - the parts of the viewers' state saved in settings, worksheets and projects
+
+pub mod byte_view;
+pub mod colors;
+
+use serde::{Deserialize, Serialize};
+
+pub use byte_view::{ByteColumn, ByteViewConfig, Endianness, WordKind};
+pub use colors::BitColors;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BitShape {
+    Square,
+    Circle,
+    Octagon,
+}
@@ -0,0 +1,325 @@
+// Property tests - invariants of the operations and the pattern search checked on seeded
+// random inputs. A failure prints its seed; set BIT_PROPERTY_SEED to it to replay just that
+// case, and BIT_PROPERTY_CASES to run more (or fewer) cases per property.
+
+use bit_core::analysis::{Pattern, PatternFormat};
+use bit_core::processing::{
+    BitOperation, BlockInterleaverConfig, ConvolutionalInterleaverConfig, InterleaverDirection, Operation,
+    OperationSequence,
+};
+use bit_core::processing::ecc::{hamming_correct, hamming_encode, Bch, EccStatus};
+use bit_core::processing::interleaver::SymbolInterleaverConfig;
+use bitvec::prelude::*;
+
+/// Cases per property unless BIT_PROPERTY_CASES says otherwise
+const DEFAULT_CASES: u64 = 256;
+/// Cases are seeded from this so every run checks the same inputs
+const BASE_SEED: u64 = 0xB17_5EED;
+
+/// SplitMix64, small and good enough to spread test inputs
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`, which must not be empty
+    fn range(&mut self, range: std::ops::RangeInclusive<usize>) -> usize {
+        let span = (range.end() - range.start()) as u64 + 1;
+        range.start() + (self.next_u64() % span) as usize
+    }
+
+    fn bits(&mut self, len: usize) -> BitVec<u8, Msb0> {
+        (0..len).map(|_| self.next_u64() & 1 == 1).collect()
+    }
+
+    /// Random bits of a length drawn from `lengths`
+    fn bits_in(&mut self, lengths: std::ops::RangeInclusive<usize>) -> BitVec<u8, Msb0> {
+        let len = self.range(lengths);
+        self.bits(len)
+    }
+}
+
+fn parse_seed(text: &str) -> Option<u64> {
+    match text.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => text.parse().ok(),
+    }
+}
+
+/// Runs `property` once per seeded case and panics with the seed of the first failing one
+fn check(name: &str, property: impl Fn(&mut Rng) -> Result<(), String>) {
+    let seeds: Vec<u64> = match std::env::var("BIT_PROPERTY_SEED").ok().and_then(|text| parse_seed(&text)) {
+        Some(seed) => vec![seed],
+        None => {
+            let cases = std::env::var("BIT_PROPERTY_CASES").ok().and_then(|text| text.parse().ok()).unwrap_or(DEFAULT_CASES);
+            (0..cases).map(|case| BASE_SEED ^ case.wrapping_mul(0x2545_F491_4F6C_DD1D)).collect()
+        }
+    };
+    for seed in seeds {
+        if let Err(message) = property(&mut Rng(seed)) {
+            panic!("{} failed: {}\nReplay with BIT_PROPERTY_SEED={:#x}", name, message, seed);
+        }
+    }
+}
+
+fn ensure(condition: bool, message: impl FnOnce() -> String) -> Result<(), String> {
+    if condition { Ok(()) } else { Err(message()) }
+}
+
+fn random_sequence(rng: &mut Rng) -> OperationSequence {
+    let operations = (0..rng.range(1..=5))
+        .map(|_| {
+            let n = rng.range(0..=12);
+            match rng.range(0..=3) {
+                0 => Operation::Take(n),
+                1 => Operation::Skip(n),
+                2 => Operation::Reverse(n),
+                _ => Operation::Invert(n),
+            }
+        })
+        .collect();
+    OperationSequence { operations }
+}
+
+/// Take/skip applied the obvious way: walk the operations in a cycle, building a new vector
+fn reference_sequence(sequence: &OperationSequence, bits: &BitSlice<u8, Msb0>) -> BitVec<u8, Msb0> {
+    let count = |operation: &Operation| match operation {
+        Operation::Take(n) | Operation::Skip(n) | Operation::Reverse(n) | Operation::Invert(n) => *n,
+    };
+    let mut output = BitVec::new();
+    if sequence.operations.iter().all(|operation| count(operation) == 0) {
+        return output;
+    }
+    let mut pos = 0;
+    while pos < bits.len() {
+        for operation in &sequence.operations {
+            let end = (pos + count(operation)).min(bits.len());
+            let chunk = &bits[pos..end];
+            match operation {
+                Operation::Take(_) => output.extend(chunk.iter().by_vals()),
+                Operation::Reverse(_) => output.extend(chunk.iter().by_vals().rev()),
+                Operation::Invert(_) => output.extend(chunk.iter().by_vals().map(|bit| !bit)),
+                Operation::Skip(_) => {}
+            }
+            pos = end;
+        }
+    }
+    output
+}
+
+#[test]
+fn prop_take_skip_matches_reference() {
+    check("take/skip", |rng| {
+        let sequence = random_sequence(rng);
+        let bits = rng.bits_in(0..=300);
+        let output = sequence.apply(&bits);
+        let expected = reference_sequence(&sequence, &bits);
+        ensure(output == expected, || format!("'{}' on {} bits gave {} bits, expected {}", sequence.to_string(), bits.len(), output.len(), expected.len()))?;
+
+        // Only skips drop bits, so the taken share of whole cycles is exact
+        let cycle = sequence.cycle_length();
+        let kept: usize = sequence.operations.iter().map(|operation| match operation {
+            Operation::Skip(_) => 0,
+            Operation::Take(n) | Operation::Reverse(n) | Operation::Invert(n) => *n,
+        }).sum();
+        if let Some(whole) = bits.len().checked_div(cycle) {
+            ensure(output.len() >= whole * kept && output.len() <= (whole + 1) * kept, || {
+                format!("'{}' kept {} of {} bits", sequence.to_string(), output.len(), bits.len())
+            })?;
+        }
+
+        let reparsed = OperationSequence::from_string(&sequence.to_string());
+        ensure(reparsed.is_ok_and(|reparsed| reparsed.operations == sequence.operations), || {
+            format!("'{}' doesn't parse back", sequence.to_string())
+        })
+    });
+}
+
+#[test]
+fn prop_truncate_stays_in_bounds() {
+    check("truncate", |rng| {
+        let bits = rng.bits_in(0..=200);
+        let (start, end) = (rng.range(0..=250), rng.range(0..=250));
+        let operation = BitOperation::TruncateBits {
+            name: "truncate".to_string(),
+            start,
+            end,
+            exprs: Default::default(),
+            enabled: true,
+        };
+        let output = operation.apply(&bits);
+        let (from, to) = (start.min(bits.len()), end.min(bits.len()));
+        let expected = if from < to { bits[from..to].to_bitvec() } else { BitVec::new() };
+        ensure(output == expected, || format!("{}..{} of {} bits gave {} bits", start, end, bits.len(), output.len()))
+    });
+}
+
+#[test]
+fn prop_block_interleave_round_trips() {
+    check("block interleave", |rng| {
+        let (block_size, depth) = (rng.range(1..=16), rng.range(1..=16));
+        // Often whole matrices, otherwise ending in a partial one
+        let bits = if rng.range(0..=1) == 0 {
+            let matrices = rng.range(0..=4);
+            rng.bits(block_size * depth * matrices)
+        } else {
+            rng.bits_in(0..=600)
+        };
+        let interleaved = BlockInterleaverConfig::new(block_size, depth, InterleaverDirection::Interleave).apply(&bits);
+        ensure(interleaved.len() == bits.len(), || "interleaving changed the length".to_string())?;
+        ensure(interleaved.count_ones() == bits.count_ones(), || "interleaving isn't a permutation".to_string())?;
+        let recovered = BlockInterleaverConfig::new(block_size, depth, InterleaverDirection::Deinterleave).apply(&interleaved);
+        ensure(recovered == bits, || format!("{}x{} on {} bits didn't round-trip", block_size, depth, bits.len()))
+    });
+}
+
+#[test]
+fn prop_symbol_interleave_round_trips() {
+    check("symbol interleave", |rng| {
+        let (symbol_size, block_size, depth) = (rng.range(1..=8), rng.range(1..=8), rng.range(1..=8));
+        let matrices = rng.range(0..=3);
+        let bits = rng.bits(symbol_size * block_size * depth * matrices);
+        let interleave = SymbolInterleaverConfig::new(symbol_size, block_size, depth, InterleaverDirection::Interleave);
+        let deinterleave = SymbolInterleaverConfig::new(symbol_size, block_size, depth, InterleaverDirection::Deinterleave);
+        let recovered = deinterleave.apply(&interleave.apply(&bits));
+        ensure(recovered == bits, || {
+            format!("{}-bit symbols {}x{} on {} bits didn't round-trip", symbol_size, block_size, depth, bits.len())
+        })
+    });
+}
+
+#[test]
+fn prop_symbol_interleave_drops_only_partial_symbols() {
+    check("symbol interleave, partial symbol", |rng| {
+        let (symbol_size, block_size, depth) = (rng.range(1..=8), rng.range(1..=8), rng.range(1..=8));
+        let bits = rng.bits_in(0..=400);
+        let interleaved = SymbolInterleaverConfig::new(symbol_size, block_size, depth, InterleaverDirection::Interleave).apply(&bits);
+        let whole = bits.len() / symbol_size * symbol_size;
+        ensure(interleaved.len() == whole, || format!("{} bits of {}-bit symbols gave {} bits", bits.len(), symbol_size, interleaved.len()))
+    });
+}
+
+#[test]
+fn prop_convolutional_interleave_round_trips_after_delay() {
+    check("convolutional interleave", |rng| {
+        let (branches, increment) = (rng.range(1..=8), rng.range(0..=4));
+        let bits = rng.bits_in(0..=400);
+        let interleave = ConvolutionalInterleaverConfig::new(branches, increment, InterleaverDirection::Interleave);
+        let deinterleave = ConvolutionalInterleaverConfig::new(branches, increment, InterleaverDirection::Deinterleave);
+        let recovered = deinterleave.apply(&interleave.apply(&bits));
+        ensure(recovered.len() == bits.len(), || "the length changed".to_string())?;
+
+        // Each branch is visited once per `branches` bits, so the delay in bits is that many times larger
+        let delay = interleave.total_delay() * branches;
+        let lined_up = delay >= bits.len() || recovered[delay..] == bits[..bits.len() - delay];
+        ensure(lined_up, || format!("{} branches x {} on {} bits didn't round-trip", branches, increment, bits.len()))
+    });
+}
+
+/// `count` distinct bit positions below `len`
+fn distinct_positions(rng: &mut Rng, count: usize, len: usize) -> Vec<usize> {
+    let mut positions = Vec::with_capacity(count);
+    while positions.len() < count {
+        let position = rng.range(0..=len - 1);
+        if !positions.contains(&position) {
+            positions.push(position);
+        }
+    }
+    positions
+}
+
+fn flip(bytes: &mut [u8], bit: usize) {
+    bytes[bit / 8] ^= 0x80 >> (bit % 8);
+}
+
+#[test]
+fn prop_hamming_corrects_any_single_flip() {
+    check("hamming single flip", |rng| {
+        let len = if rng.range(0..=1) == 0 { 256 } else { 512 };
+        let original: Vec<u8> = (0..len).map(|_| rng.next_u64() as u8).collect();
+        let code = hamming_encode(&original);
+        let mut data = original.clone();
+        let bit = rng.range(0..=len * 8 - 1);
+        flip(&mut data, bit);
+        let status = hamming_correct(&mut data, &code);
+        ensure(status == EccStatus::Corrected(1) && data == original, || format!("flip of bit {} in {} bytes gave {:?}", bit, len, status))
+    });
+}
+
+#[test]
+fn prop_bch_corrects_up_to_strength() {
+    check("bch correction", |rng| {
+        let sector = if rng.range(0..=1) == 0 { 256 } else { 512 };
+        let strength = rng.range(1..=8);
+        let bch = Bch::new(sector, strength)?;
+        let original: Vec<u8> = (0..sector).map(|_| rng.next_u64() as u8).collect();
+        let mut code = bch.encode(&original);
+
+        // Errors anywhere in the data and the code, never more than the code corrects
+        let errors = rng.range(0..=strength);
+        let mut data = original.clone();
+        for position in distinct_positions(rng, errors, sector * 8 + bch.parity_bits()) {
+            if position < sector * 8 {
+                flip(&mut data, position);
+            } else {
+                flip(&mut code, position - sector * 8);
+            }
+        }
+        let status = bch.correct(&mut data, &code);
+        let expected = if errors == 0 { EccStatus::Clean } else { EccStatus::Corrected(errors) };
+        ensure(status == expected && data == original, || {
+            format!("BCH-{} over {} bytes with {} errors gave {:?}", strength, sector, errors, status)
+        })
+    });
+}
+
+#[test]
+fn prop_pattern_search_matches_reference() {
+    check("pattern search", |rng| {
+        let haystack = rng.bits_in(1..=400);
+        // Usually cut the pattern out of the haystack so there is at least one match
+        let length = rng.range(1..=24.min(haystack.len()));
+        let needle = if rng.range(0..=3) > 0 {
+            let start = rng.range(0..=haystack.len() - length);
+            haystack[start..start + length].to_bitvec()
+        } else {
+            rng.bits(length)
+        };
+        let garbles = rng.range(0..=3.min(length));
+
+        let input: String = needle.iter().map(|bit| if *bit { '1' } else { '0' }).collect();
+        let mut pattern = Pattern::new("property".to_string(), PatternFormat::Bits, input, garbles).map_err(|e| e.to_string())?;
+        pattern.search(&haystack);
+
+        let expected: Vec<(usize, usize)> = (0..=haystack.len() - length)
+            .map(|start| (start, haystack[start..start + length].iter().zip(needle.iter()).filter(|(a, b)| a != b).count()))
+            .filter(|&(_, mismatches)| mismatches <= garbles)
+            .collect();
+        let found: Vec<(usize, usize)> = pattern.matches.iter().map(|m| (m.position, m.mismatches)).collect();
+        ensure(found == expected, || format!("{}-bit pattern with {} garbles: found {} matches, expected {}", length, garbles, found.len(), expected.len()))?;
+
+        let deltas_line_up = pattern.matches.windows(2).all(|pair| pair[1].delta == Some(pair[1].position - pair[0].position));
+        ensure(deltas_line_up && pattern.matches.first().is_none_or(|m| m.delta.is_none()), || "deltas don't match the positions".to_string())
+    });
+}
+
+#[test]
+fn fuzz_sequence_parser_never_panics() {
+    const ALPHABET: &[u8] = b"tsriTSRI0123456789 ,x-";
+    check("sequence parser", |rng| {
+        let text: String = (0..rng.range(0..=24)).map(|_| ALPHABET[rng.range(0..=ALPHABET.len() - 1)] as char).collect();
+        if let Ok(sequence) = OperationSequence::from_string(&text) {
+            // Whatever parses must apply without panicking
+            let bits = rng.bits_in(0..=64);
+            sequence.apply(&bits);
+        }
+        Ok(())
+    });
+}
+
@@ -0,0 +1,192 @@
+// Python bindings - the take/skip sequences, interleavers, pattern search and frame width
+// analysis the GUI runs, for batch scripts
+//
+// Bits cross the boundary as `bytes` read most significant bit first, with an optional bit
+// length for data that doesn't end on a byte boundary; results come back the same way.
+
+use bit_core::analysis::frame_width::find_best_width as core_find_best_width;
+use bit_core::analysis::{Pattern, PatternFormat};
+use bit_core::processing::interleaver::{
+    BlockInterleaverConfig, ConvolutionalInterleaverConfig, InterleaverDirection, SymbolInterleaverConfig,
+};
+use bit_core::processing::OperationSequence as CoreSequence;
+use bitvec::prelude::*;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+/// The first `bit_len` bits of `data`, or all of them
+fn to_bits(data: &[u8], bit_len: Option<usize>) -> PyResult<BitVec<u8, Msb0>> {
+    let mut bits = BitVec::<u8, Msb0>::from_slice(data);
+    if let Some(len) = bit_len {
+        if len > bits.len() {
+            return Err(PyValueError::new_err(format!("bit_len {} is longer than the {} bits given", len, bits.len())));
+        }
+        bits.truncate(len);
+    }
+    Ok(bits)
+}
+
+/// `bits` as bytes, a partial last byte padded with zeros, and the number of bits
+fn to_py(py: Python<'_>, mut bits: BitVec<u8, Msb0>) -> (Bound<'_, PyBytes>, usize) {
+    bits.set_uninitialized(false);
+    (PyBytes::new_bound(py, bits.as_raw_slice()), bits.len())
+}
+
+fn direction(deinterleave: bool) -> InterleaverDirection {
+    if deinterleave { InterleaverDirection::Deinterleave } else { InterleaverDirection::Interleave }
+}
+
+/// A take/skip sequence such as `t8s4`, written as in the GUI
+#[pyclass]
+struct OperationSequence {
+    inner: CoreSequence,
+}
+
+#[pymethods]
+impl OperationSequence {
+    #[new]
+    fn new(spec: &str) -> PyResult<Self> {
+        CoreSequence::from_string(spec).map(|inner| Self { inner }).map_err(PyValueError::new_err)
+    }
+
+    /// Input bits one pass over the sequence consumes
+    fn cycle_length(&self) -> usize {
+        self.inner.cycle_length()
+    }
+
+    #[pyo3(signature = (data, bit_len = None))]
+    fn apply<'py>(&self, py: Python<'py>, data: &[u8], bit_len: Option<usize>) -> PyResult<(Bound<'py, PyBytes>, usize)> {
+        Ok(to_py(py, self.inner.apply(&to_bits(data, bit_len)?)))
+    }
+
+    fn __str__(&self) -> String {
+        self.inner.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("OperationSequence('{}')", self.inner.to_string())
+    }
+}
+
+/// Block interleaver: `depth` rows of `block_size` bits, written by row and read by column
+#[pyclass]
+struct BlockInterleaver {
+    inner: BlockInterleaverConfig,
+}
+
+#[pymethods]
+impl BlockInterleaver {
+    #[new]
+    #[pyo3(signature = (block_size, depth, deinterleave = false))]
+    fn new(block_size: usize, depth: usize, deinterleave: bool) -> Self {
+        Self { inner: BlockInterleaverConfig::new(block_size, depth, direction(deinterleave)) }
+    }
+
+    #[pyo3(signature = (data, bit_len = None))]
+    fn apply<'py>(&self, py: Python<'py>, data: &[u8], bit_len: Option<usize>) -> PyResult<(Bound<'py, PyBytes>, usize)> {
+        Ok(to_py(py, self.inner.apply(&to_bits(data, bit_len)?)))
+    }
+}
+
+/// Convolutional interleaver: `branches` branches, each delayed `delay_increment` more than the last
+#[pyclass]
+struct ConvolutionalInterleaver {
+    inner: ConvolutionalInterleaverConfig,
+}
+
+#[pymethods]
+impl ConvolutionalInterleaver {
+    #[new]
+    #[pyo3(signature = (branches, delay_increment, deinterleave = false))]
+    fn new(branches: usize, delay_increment: usize, deinterleave: bool) -> Self {
+        Self { inner: ConvolutionalInterleaverConfig::new(branches, delay_increment, direction(deinterleave)) }
+    }
+
+    /// Bits of delay the interleaver introduces
+    fn total_delay(&self) -> usize {
+        self.inner.total_delay()
+    }
+
+    #[pyo3(signature = (data, bit_len = None))]
+    fn apply<'py>(&self, py: Python<'py>, data: &[u8], bit_len: Option<usize>) -> PyResult<(Bound<'py, PyBytes>, usize)> {
+        Ok(to_py(py, self.inner.apply(&to_bits(data, bit_len)?)))
+    }
+}
+
+/// Block interleaver over `symbol_size`-bit symbols, which move as a whole
+#[pyclass]
+struct SymbolInterleaver {
+    inner: SymbolInterleaverConfig,
+}
+
+#[pymethods]
+impl SymbolInterleaver {
+    #[new]
+    #[pyo3(signature = (symbol_size, block_size, depth, deinterleave = false))]
+    fn new(symbol_size: usize, block_size: usize, depth: usize, deinterleave: bool) -> Self {
+        Self { inner: SymbolInterleaverConfig::new(symbol_size, block_size, depth, direction(deinterleave)) }
+    }
+
+    #[pyo3(signature = (data, bit_len = None))]
+    fn apply<'py>(&self, py: Python<'py>, data: &[u8], bit_len: Option<usize>) -> PyResult<(Bound<'py, PyBytes>, usize)> {
+        Ok(to_py(py, self.inner.apply(&to_bits(data, bit_len)?)))
+    }
+}
+
+/// Matches of `pattern` in `data` as (bit position, mismatched bits, slipped bits)
+///
+/// `format` is `hex` (`0x...`), `ascii` or `bits` (`0`s and `1`s), as in the pattern locator.
+#[pyfunction]
+#[pyo3(signature = (data, pattern, format = "hex", garbles = 0, slips = 0, bit_len = None))]
+fn find_pattern(
+    data: &[u8],
+    pattern: &str,
+    format: &str,
+    garbles: usize,
+    slips: usize,
+    bit_len: Option<usize>,
+) -> PyResult<Vec<(usize, usize, usize)>> {
+    let format = match format.to_ascii_lowercase().as_str() {
+        "hex" => PatternFormat::Hex,
+        "ascii" => PatternFormat::Ascii,
+        "bits" => PatternFormat::Bits,
+        other => return Err(PyValueError::new_err(format!("Unknown pattern format '{}'; use hex, ascii or bits", other))),
+    };
+    let mut found = Pattern::new(String::new(), format, pattern.to_string(), garbles).map_err(PyValueError::new_err)?;
+    found.slips = slips;
+    found.search(&to_bits(data, bit_len)?);
+    Ok(found.matches.iter().map(|m| (m.position, m.mismatches, m.slips)).collect())
+}
+
+/// Frame width in `min_width..=max_width` whose bit positions are most consistent from frame
+/// to frame, as (best width, its score, (width, score) for every width tried)
+///
+/// A `delta` above 0 compares each frame with the one `delta` frames later instead, which
+/// finds repetition periods rather than frame widths.
+#[pyfunction]
+#[pyo3(signature = (data, min_width, max_width, delta = 0, bit_len = None))]
+fn find_best_width(
+    data: &[u8],
+    min_width: usize,
+    max_width: usize,
+    delta: usize,
+    bit_len: Option<usize>,
+) -> PyResult<(usize, f64, Vec<(usize, f64)>)> {
+    if min_width == 0 || min_width > max_width {
+        return Err(PyValueError::new_err(format!("Widths {}..={} must be at least 1 and in order", min_width, max_width)));
+    }
+    let analysis = core_find_best_width(&to_bits(data, bit_len)?, min_width, max_width, delta);
+    Ok((analysis.best_width, analysis.best_score, analysis.width_scores))
+}
+
+#[pymodule]
+fn bit_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<OperationSequence>()?;
+    m.add_class::<BlockInterleaver>()?;
+    m.add_class::<ConvolutionalInterleaver>()?;
+    m.add_class::<SymbolInterleaver>()?;
+    m.add_function(wrap_pyfunction!(find_pattern, m)?)?;
+    m.add_function(wrap_pyfunction!(find_best_width, m)?)?;
+    Ok(())
+}
@@ -1,22 +1,151 @@
 // Main application state and logic
 
-use crate::analysis::{Pattern, PatternFormat, FrameWidthAnalysis};
-use crate::core::{ViewMode, OperationType};
-use crate::processing::{BitOperation, OperationSequence, WorksheetOperation};
-use crate::storage::{read_file_as_bits, read_file_as_bits_with_progress, write_bits_to_file, AppSession, AppSettings, Worksheet, LoadProgress};
-use crate::viewers::{BitViewer, ByteViewer};
+use crate::analysis::{Pattern, PatternFormat, FrameWidthAnalysis, Frame, FrameCrc, HdlcFrame, HdlcOptions, RepeatCandidate, PeriodicitySpectrum, BatchRow, XorKeyCandidate, ChecksumReport, DecodedField, WorksheetMatches, ColumnStats, TimestampCandidate, TimestampFormat, BitDiff, bit_diff, BitStats, bit_stats, MatchIndex, FrameDistances};
+use crate::core::{Notifications, ViewMode, OperationType};
+use crate::i18n::tr;
+use crate::processing::{decode_line_code, parse_bit_pattern, parse_hex_key, parse_ranges_csv, run_script, BackgroundTask, BitCondition, BitOperation, BitPatch, BitScript, CancellationToken, DeleteMode, LineCode, MajorityVote, NandArea, NandEcc, NandLayout, NandReport, OperationCache, OperationMacro, OperationSequence, ParamExpressions, ProgressReporter, RangeAction, SoftFormat, SpareLayout, SymbolCode, SymbolMap, TakeSkipPreset, track_through_chain, TieBreak, VoteGrouping, WorksheetOperation, DEFAULT_SCRIPT_TIME_LIMIT_MS};
+use crate::storage::{expand_glob, range_len, read_file_as_bits_in_order, read_file_range_as_bits_in_order, read_file_range_with_progress, read_files_as_bits_in_order, read_file_as_bits_with_progress, read_soft_file, BitOrder, should_memory_map, write_bits_to_file_with_progress, AppSession, AppSettings, FileWatcher, OperationChain, Project, Worksheet, WorksheetView, FrameWidthScan, MAX_FRAME_WIDTH_SCANS, LoadProgress, MappedBits, NetworkListener, NetworkProtocol, ReceivedSegment, burst_starts};
+use crate::storage::worksheet::{find_worksheet, new_worksheet_id};
+use crate::storage::paths::{apply_remap, missing_files, remap_to_folder};
+use crate::storage::autosave::{self, Snapshot};
+use crate::storage::file_io::MAX_FILE_SIZE;
+use crate::utils::{char_code, NumberBase, TextDecoder, TextEncoding};
+use crate::viewers::{rgb, BitColors, BitViewer, ByteViewer, SymbolViewer};
+use crate::viewers::byte_viewer::ByteViewConfig;
 use bitvec::prelude::*;
+use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
 use std::sync::mpsc::{channel, Receiver};
+use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::{Duration, Instant};
+
+/// Total input size above which operations are processed on a background thread
+const ASYNC_THRESHOLD_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Input bits fed through the operation chain for an editor's live preview
+const PREVIEW_INPUT_BITS: usize = 8192;
+
+/// Name given to the operation that collects edits made in edit mode
+const BIT_EDITS_NAME: &str = "Bit Edits";
+
+/// Byte view layout changes kept for undo
+const MAX_BYTE_VIEW_UNDO: usize = 50;
+
+/// Starting point for a new script: reverse the bit order of every byte
+const DEFAULT_SCRIPT: &str = "// Reverse the bit order of every byte\nfor i in 0..len() / 8 {\n    reverse(i * 8, 8);\n}\n";
+
+/// Starting point for the structure window: a small header with a length-prefixed payload
+const DEFAULT_STRUCTURE_TEMPLATE: &str = r#"{
+  "name": "header",
+  "fields": [
+    { "name": "sync", "type": "u16" },
+    { "name": "version", "type": "bits", "bits": 4 },
+    { "name": "flags", "type": "bits", "bits": 4 },
+    { "name": "length", "type": "u8" },
+    { "name": "payload", "type": "bytes", "length": "length" },
+    { "name": "crc", "type": "u16", "if": { "field": "flags", "mask": 1, "equals": 1 } }
+  ]
+}"#;
+
+/// Bits before and after an operation, for the editor preview
+pub struct OperationPreview {
+    pub before: BitVec<u8, Msb0>,
+    pub after: BitVec<u8, Msb0>,
+}
+
+/// Which bits the analysis windows look at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnalysisScope {
+    /// The whole displayed stream
+    Whole,
+    /// The range shift-dragged in the bit viewer
+    Selection,
+    /// A range typed in by offset
+    Range,
+}
+
+/// Bits handed to a background task; mapped files are shared rather than copied
+enum TaskBits {
+    Copied(BitVec<u8, Msb0>),
+    Mapped(Arc<MappedBits>, std::ops::Range<usize>),
+}
+
+impl TaskBits {
+    fn bits(&self) -> &BitSlice<u8, Msb0> {
+        match self {
+            TaskBits::Copied(bits) => bits,
+            TaskBits::Mapped(mapped, range) => &mapped.bits()[range.clone()],
+        }
+    }
+}
+
+/// Finished background analysis, applied to the app when polled
+pub enum AnalysisResult {
+    FrameWidth(FrameWidthAnalysis),
+    PatternMatches { index: usize, pattern: Pattern },
+    /// Every pattern, searched together
+    AllPatternMatches(Vec<Pattern>),
+    /// One pattern searched in every worksheet
+    SessionMatches { pattern: String, results: Vec<WorksheetMatches> },
+}
 
 /// Message from async operation processing
 pub enum OperationProgress {
     LoadingFile { path: PathBuf, loaded: u64, total: u64 },
     ProcessingOperation { index: usize, total: usize, description: String },
+    ProcessingChunk { index: usize, total: usize, processed: usize, total_bits: usize },
+    /// The operation at `index` in the list finished
+    OperationTimed { index: usize, timing: OperationTiming },
     Complete(Result<BitVec<u8, Msb0>, String>),
 }
 
+/// How an operation fared in the last apply
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OperationTiming {
+    Ran { duration: Duration, input_bits: usize, output_bits: usize },
+    /// Output reused from the operation cache, with the input and output sizes from when it
+    /// last ran if known
+    Cached { sizes: Option<(usize, usize)> },
+}
+
+impl OperationTiming {
+    fn since(started: Instant, input_bits: usize, output: &BitSlice<u8, Msb0>) -> Self {
+        OperationTiming::Ran { duration: started.elapsed(), input_bits, output_bits: output.len() }
+    }
+    
+    /// Bits in and out of the operation
+    pub fn sizes(&self) -> Option<(usize, usize)> {
+        match *self {
+            OperationTiming::Ran { input_bits, output_bits, .. } => Some((input_bits, output_bits)),
+            OperationTiming::Cached { sizes } => sizes,
+        }
+    }
+    
+    pub fn summary(&self) -> String {
+        match self {
+            OperationTiming::Ran { duration, output_bits, .. } => {
+                format!("⏱ {:.1} ms → {} bits", duration.as_secs_f64() * 1000.0, output_bits)
+            }
+            OperationTiming::Cached { sizes: Some((_, output_bits)) } => format!("⏱ cached → {} bits", output_bits),
+            OperationTiming::Cached { sizes: None } => "⏱ cached".to_string(),
+        }
+    }
+    
+    /// Why the output looks wrong: empty, or more than `max_drop_percent` of the input dropped
+    pub fn warning(&self, max_drop_percent: f32) -> Option<String> {
+        let (input_bits, output_bits) = self.sizes()?;
+        if output_bits == 0 {
+            return Some(format!("Produced no bits from {} input bits; check its ranges and parameters", input_bits));
+        }
+        let dropped = input_bits.saturating_sub(output_bits) as f32 * 100.0 / input_bits.max(1) as f32;
+        (dropped > max_drop_percent).then(|| format!(
+            "Dropped {:.1}% of its input ({} → {} bits), more than the {:.0}% warning threshold",
+            dropped, input_bits, output_bits, max_drop_percent
+        ))
+    }
+}
+
 /// Message for view rendering progress
 #[allow(dead_code)]
 pub enum RenderProgress {
@@ -28,15 +157,28 @@ pub enum RenderProgress {
 pub struct BitApp {
     pub original_bits: BitVec<u8, Msb0>,
     pub processed_bits: BitVec<u8, Msb0>,
+    // Memory-mapped files standing in for original_bits/processed_bits (which are empty while set)
+    pub mapped_original: Option<Arc<MappedBits>>,
+    pub mapped_processed: Option<Arc<MappedBits>>,
     pub viewer: BitViewer,
     pub byte_viewer: ByteViewer,
+    /// Byte view layouts to step back and forward through, most recent last
+    byte_view_undo: Vec<ByteViewConfig>,
+    byte_view_redo: Vec<ByteViewConfig>,
+    /// The layout as last recorded, to notice edits made anywhere in the UI
+    byte_view_snapshot: ByteViewConfig,
+    pub symbol_viewer: SymbolViewer,
     pub view_mode: ViewMode,
     pub operations: Vec<BitOperation>,
     pub current_file_path: Option<PathBuf>,
-    pub error_message: Option<String>,
+    /// Errors and warnings shown in the notification center
+    pub notifications: Notifications,
+    pub show_notifications: bool,
     pub show_original: bool,
     pub show_settings: bool,
     pub font_size: f32,
+    /// UI scale slider value, applied to the settings when the drag ends
+    pub ui_scale_input: f32,
     pub settings: AppSettings,
     
     // Worksheet management
@@ -44,13 +186,30 @@ pub struct BitApp {
     pub current_worksheet_index: usize,
     pub renaming_worksheet: Option<usize>,
     pub worksheet_name_buffer: String,
+    /// Filters narrowing the Worksheets and Active Operations lists
+    pub worksheet_filter: String,
+    pub operation_filter: String,
     
     // Operation creation/editing state
     pub show_operation_menu: Option<OperationType>,
     pub editing_operation_index: Option<usize>,
+    /// Child of the group at `editing_operation_index` being edited, if any
+    pub editing_group_child: Option<usize>,
+    /// Operations changed since the last apply, in manual apply mode
+    pub pipeline_dirty: bool,
     
     // Drag and drop state
     pub dragging_operation: Option<usize>,
+    pub dragging_worksheet: Option<usize>,
+    
+    // Go-to-offset state
+    pub goto_input: String,
+    pub goto_in_bytes: bool,
+    pub ascii_jump_to_bit: Option<usize>,
+    
+    // ASCII view state
+    pub ascii_encoding: TextEncoding,
+    pub ascii_bits_per_char: usize,
     
     // Take/Skip Sequence editor state
     pub takeskip_name: String,
@@ -59,6 +218,19 @@ pub struct BitApp {
     // Load File editor state
     pub loadfile_name: String,
     pub loadfile_path: Option<PathBuf>,
+    pub loadfile_offset: String,
+    pub loadfile_length: String,
+    
+    // Multi-File Load editor state
+    pub multifile_name: String,
+    pub multifile_paths: Vec<PathBuf>,
+    pub multifile_glob: String,
+    
+    // Load Soft Bits editor state; the vote to combine copies with uses the Majority Vote fields
+    pub softload_name: String,
+    pub softload_path: Option<PathBuf>,
+    pub softload_format: SoftFormat,
+    pub softload_combine: bool,
     
     // Invert Bits editor state
     pub invert_name: String,
@@ -68,6 +240,86 @@ pub struct BitApp {
     pub truncate_start: String,
     pub truncate_end: String,
     
+    // Invert/Reverse Range editor state
+    pub range_name: String,
+    pub range_action: RangeAction,
+    pub range_start: String,
+    pub range_end: String,
+    
+    // XOR With Key editor state
+    pub xor_name: String,
+    pub xor_key: String,
+    
+    // Line code decode editor state
+    pub line_code_name: String,
+    pub line_code: LineCode,
+    pub symbol_code_name: String,
+    pub symbol_code: SymbolCode,
+    pub symbol_code_bits: String,
+    pub remap_name: String,
+    pub remap_input_bits: String,
+    pub remap_output_bits: String,
+    /// One `input,output` pair per line
+    pub remap_table: String,
+    pub majority_name: String,
+    /// Vote across repeated frames rather than repeated bits
+    pub majority_frames: bool,
+    pub majority_repeats: String,
+    pub majority_frame_length: String,
+    pub majority_block_frames: String,
+    pub majority_tie_break: TieBreak,
+    pub nand_name: String,
+    pub nand_page_size: String,
+    pub nand_spare_size: String,
+    pub nand_interleaved: bool,
+    pub nand_steps: String,
+    pub nand_keep: NandArea,
+    pub nand_ecc: NandEcc,
+    pub nand_ecc_offset: String,
+    pub nand_correct: bool,
+    /// What the editor's last ECC check found
+    pub nand_report: Option<NandReport>,
+    
+    // Group editor state
+    pub group_name: String,
+    pub group_operations: Vec<BitOperation>,
+    pub group_collapsed: bool,
+    
+    // Conditional editor state
+    pub conditional_name: String,
+    pub conditional_operation: Option<BitOperation>,
+    pub conditional_by_ranges: bool,
+    pub conditional_frame_length: String,
+    pub conditional_every: String,
+    pub conditional_offset: String,
+    pub conditional_ranges: String,
+    
+    // A/B variants editor state
+    pub variants_name: String,
+    pub variants_a: Option<BitOperation>,
+    pub variants_b: Option<BitOperation>,
+    pub variants_use_b: bool,
+    /// Outputs of the edited variants' A and B compared
+    pub variant_comparison: Option<BitDiff>,
+    
+    // Script editor state
+    pub script_name: String,
+    pub script_source: String,
+    pub script_time_limit_ms: u64,
+    
+    // Insert Bits editor state
+    pub insert_name: String,
+    pub insert_position: String,
+    pub insert_count: String,
+    pub insert_fill: String,
+    
+    // Delete Bits editor state
+    pub delete_name: String,
+    pub delete_unstuff: bool,
+    pub delete_position: String,
+    pub delete_count: String,
+    pub delete_run_length: String,
+    
     // Interleave Bits editor state
     pub interleave_name: String,
     pub interleave_type: crate::processing::InterleaverType,
@@ -83,32 +335,74 @@ pub struct BitApp {
     
     // Multi-Worksheet Load editor state
     pub multiworksheet_name: String,
-    pub multiworksheet_ops: Vec<(usize, String)>, // (worksheet_index, sequence_string)
+    pub multiworksheet_ops: Vec<(u64, String)>, // (worksheet_id, sequence_string)
     pub multiworksheet_input: String, // Temporary input for adding new worksheet operations
-    pub multiworksheet_selected_worksheet: usize,
+    pub multiworksheet_selected_worksheet: u64, // worksheet id
+    
+    // Macro editor state
+    pub macro_name: String,
+    pub macro_operations: Vec<BitOperation>,
+    /// Name typed for "Save as Macro" in the Active Operations panel
+    pub new_macro_name: String,
+    
+    // Bit Edits editor state
+    pub patch_name: String,
+    pub patch_list: Vec<BitPatch>,
+    
+    /// Clicking bits toggles them and typing in the byte view overwrites bytes
+    pub edit_mode: bool,
+    
+    // Analysis scope, shared by the analysis windows
+    pub analysis_scope: AnalysisScope,
+    pub analysis_range_start: usize,
+    pub analysis_range_end: usize,
     
     // Pattern Locator state
     pub patterns: Vec<Pattern>,
+    /// Matches of `patterns` by position, shared by the views that highlight them
+    pub match_index: MatchIndex,
     pub show_pattern_locator: bool,
     pub pattern_name_input: String,
     pub pattern_input: String,
     pub pattern_format: PatternFormat,
     pub pattern_garbles: usize,
+    pub pattern_slips: usize,
+    pub match_export_context_bytes: usize,
     pub selected_pattern: Option<usize>,
+    /// Pattern and match last stepped to with F3/Shift+F3
+    pub current_match: Option<(usize, usize)>,
+    /// Last pattern searched across every worksheet, by name, with its matches per worksheet
+    pub session_matches: Option<(String, Vec<WorksheetMatches>)>,
     
     // Session restore state
     pub show_restore_dialog: bool,
     pub pending_session: Option<AppSession>,
+    /// Why the previous session file couldn't be restored, shown once at startup
+    pub session_load_error: Option<String>,
+    
+    // Project file the workspace was last saved to or opened from
+    pub project_path: Option<PathBuf>,
+    
+    // Autosave state
+    pub last_autosave_time: f64,
+    last_autosave_hash: u64,
+    /// Reports the background snapshot write's failure, if it fails
+    pub autosave_receiver: Option<Receiver<String>>,
+    /// Snapshots offered for recovery after an unclean exit
+    pub recovery_snapshots: Vec<Snapshot>,
     
     // Byte view column editor state
     pub show_column_editor: bool,
+    pub column_editor_index: Option<usize>,  // None when adding a new column
     pub column_editor_label: String,
     pub column_editor_bit_start: String,
     pub column_editor_bit_end: String,
     pub column_editor_color: [u8; 3],
+    pub column_editor_timestamp: Option<TimestampFormat>,
     
     // File loading state
     pub loading_receiver: Option<Receiver<LoadProgress>>,
+    pub loading_cancel: CancellationToken,
     pub loading_file_path: Option<PathBuf>,
     pub loading_progress: f32,
     pub loading_total: u64,
@@ -117,6 +411,13 @@ pub struct BitApp {
     pub operation_receiver: Option<Receiver<OperationProgress>>,
     pub operation_progress_message: String,
     pub operation_progress: f32,
+    pub operation_cancel: CancellationToken,
+    /// Intermediate results of the operation chain, shared with the processing thread
+    pub operation_cache: Arc<Mutex<OperationCache>>,
+    /// Per-operation results of the last apply, indexed like `operations`
+    pub operation_timings: Vec<Option<OperationTiming>>,
+    /// Sizes from the apply before the last, for operations the last one took from the cache
+    previous_operation_sizes: Vec<Option<(usize, usize)>>,
     
     // Rendering state
     #[allow(dead_code)]
@@ -131,22 +432,171 @@ pub struct BitApp {
     pub frame_width_min: usize,
     pub frame_width_max: usize,
     pub frame_width_delta: usize,
+    /// Score the widths in `frame_width_list` rather than min..=max
+    pub frame_width_use_list: bool,
+    pub frame_width_list: String,
     pub frame_width_analysis: Option<FrameWidthAnalysis>,
+    /// Worksheet id, width list and delta of the scan in progress, recorded when it finishes
+    frame_width_pending_scan: Option<(u64, String, usize)>,
     pub frame_width_sort_by_score: bool, // true = sort by score, false = sort by width
     pub frame_width_selected: Option<usize>, // Last clicked width
+    
+    /// Pattern search or frame width analysis running on a large input; None when cancelled
+    pub analysis_task: Option<BackgroundTask<Option<AnalysisResult>>>,
+    /// Large export being written on a background thread
+    pub save_task: Option<BackgroundTask<Result<(), String>>>,
+    
+    // HDLC Decoder state
+    pub show_hdlc_decoder: bool,
+    pub hdlc_options: HdlcOptions,
+    pub hdlc_frames: Option<Vec<HdlcFrame>>,
+    pub hdlc_only_valid: bool, // Hide frames whose FCS doesn't match
+    pub hdlc_selected: Option<usize>,
+    
+    // Framer state
+    pub show_framer: bool,
+    pub framer_sync_format: PatternFormat,
+    pub framer_sync_input: String,
+    pub framer_sync_garbles: usize,
+    /// Optional end marker, in the sync word's format; frames then run through it
+    pub framer_end_input: String,
+    pub framer_frame_length: usize, // Bits including the sync word, 0 = up to the next sync
+    pub framer_crc: FrameCrc,
+    pub framer_frames: Option<Vec<Frame>>,
+    pub framer_selected: Option<usize>,
+    
+    // Repeating Sequences state
+    pub show_repeats: bool,
+    pub repeat_min_len: usize,
+    pub repeat_max_len: usize,
+    pub repeat_results: Option<Vec<RepeatCandidate>>,
+    
+    // Channel export state
+    pub show_channel_export: bool,
+    pub channel_count: usize,
+    pub channel_unit_bits: usize,
+    
+    // Frame statistics state
+    pub show_frame_stats: bool,
+    pub frame_stats_column_bits: usize,
+    /// Column statistics and the frame length they were computed for
+    pub frame_stats: Option<(usize, Vec<ColumnStats>)>,
+    /// Position within the super-frame to limit frame statistics to, if any
+    pub frame_stats_super_position: Option<usize>,
+    /// Position and super-frame length the statistics were limited to
+    pub frame_stats_super_frame: Option<(usize, usize)>,
+    /// Timestamp fields found and the frame length they were found for
+    pub timestamp_candidates: Option<(usize, Vec<TimestampCandidate>)>,
+    
+    // Frame distance state
+    pub show_frame_distance: bool,
+    /// Distances between consecutive frames and the first bit of the scope they were computed over
+    pub frame_distances: Option<(usize, FrameDistances)>,
+    
+    // Frames to CSV export state
+    pub show_frames_csv: bool,
+    /// Field specs, one `name start..end [int|hex|bits]` per line
+    pub frames_csv_spec: String,
+    
+    // Base converter scratchpad state
+    pub show_converter: bool,
+    pub converter_input: String,
+    pub converter_base: NumberBase,
+    /// Bits the value is shown in, 1 to 64
+    pub converter_width: u32,
+    
+    // Checksum calculator state
+    pub show_checksums: bool,
+    pub checksum_report: Option<ChecksumReport>,
+    
+    // Structure template state
+    pub show_structures: bool,
+    pub structure_template: String,
+    pub structure_offset: String,
+    pub structure_result: Option<DecodedField>,
+    
+    // Column auto-layout state
+    pub auto_layout_pattern: usize,
+    pub auto_layout_field_bytes: usize,
+    
+    /// Reloads the current file whenever it is rewritten, while Some
+    pub file_watcher: Option<FileWatcher>,
+    
+    /// Files the worksheets read that weren't found when they were opened; the window
+    /// offering to locate them is shown while this isn't empty
+    pub missing_files: Vec<PathBuf>,
+    
+    // HTML report state
+    pub show_report: bool,
+    pub report_title: String,
+    /// Regions drawn as byte view snapshots, as (start, end) bit ranges
+    pub report_regions: Vec<(usize, usize)>,
+    
+    // XOR key recovery state
+    pub show_xor_key: bool,
+    pub xor_min_key_len: usize,
+    pub xor_max_key_len: usize,
+    pub xor_results: Option<Vec<XorKeyCandidate>>,
+    
+    // Periodicity Spectrum state
+    pub show_periodicity: bool,
+    pub periodicity_min: usize,
+    pub periodicity_max: usize,
+    pub periodicity_spectrum: Option<PeriodicitySpectrum>,
+    
+    // Batch report state
+    pub show_batch: bool,
+    pub batch_folder: Option<PathBuf>,
+    pub batch_pattern_names: Vec<String>,
+    pub batch_rows: Option<Vec<BatchRow>>,
+    pub batch_receiver: Option<Receiver<Vec<BatchRow>>>,
+    
+    // Network source state
+    pub show_sources: bool,
+    pub source_protocol: NetworkProtocol,
+    pub source_port: u16,
+    pub network_listener: Option<NetworkListener>,
+    pub received_segments: Vec<ReceivedSegment>,
+    pub gap_threshold_ms: u64, // Chunks closer together than this count as one burst
+    /// Why burst gaps can't be marked in the processed data, shown in the sources window
+    pub gap_markers_note: Option<String>,
+    /// Confidences read for the bit view, with the soft-bit load they came from
+    soft_confidence: Option<(SoftSource, Arc<Vec<u8>>)>,
+    /// Statistics of the displayed bits, with the fingerprint of the data they were computed for
+    stream_stats: Option<(u64, BitStats)>,
 }
 
+/// Bits scanned for run lengths in the info section's statistics
+const STREAM_STATS_RUN_BITS: usize = 64 * 1024 * 1024;
+/// Bytes at each end of the displayed bits that go into their fingerprint
+const FINGERPRINT_BYTES: usize = 4096;
+
+/// File, format and vote of a soft-bit load, to tell when its confidences have to be read again
+type SoftSource = (PathBuf, SoftFormat, Option<MajorityVote>);
+
 impl Default for BitApp {
     fn default() -> Self {
         let mut worksheets = Vec::new();
         worksheets.push(Worksheet::new("Worksheet 1".to_string()));
         
         // Load settings from file
-        let settings = AppSettings::auto_load();
+        let (settings, settings_error) = match AppSettings::auto_load() {
+            Ok(settings) => (settings, None),
+            Err(e) => (AppSettings::default(), Some(format!("{} - using default settings", e))),
+        };
+        let operation_cache_budget = settings.operation_cache_budget();
         
         // Check if there's a previous session to restore
-        let pending_session = AppSession::load().ok();
-        let show_restore_dialog = pending_session.is_some();
+        let (pending_session, session_load_error) = match AppSession::load() {
+            Ok(session) => (Some(session), None),
+            Err(e) if AppSession::exists() => (None, Some(e)),
+            Err(_) => (None, None),
+        };
+        
+        // After a crash, offer the autosave snapshots instead of the (stale) last clean session
+        let crashed = autosave::mark_running();
+        let recovery_snapshots = if crashed { autosave::list_snapshots() } else { Vec::new() };
+        let show_restore_dialog = pending_session.is_some() && recovery_snapshots.is_empty();
         
         let mut viewer = BitViewer::new();
         viewer.shape = settings.bit_shape;
@@ -156,35 +606,128 @@ impl Default for BitApp {
         viewer.thick_grid_spacing_horizontal = settings.thick_grid_spacing_horizontal;
         viewer.thick_grid_spacing_vertical = settings.thick_grid_spacing_vertical;
         viewer.frame_length = settings.frame_length;
+        viewer.show_minimap = settings.show_minimap;
+        viewer.show_column_stats = settings.show_column_stats;
+        viewer.show_offsets = settings.show_bit_offsets;
+        viewer.show_row_density = settings.show_row_density;
+        viewer.colors = settings.bit_colors.clone();
         
-        Self {
+        let mut byte_viewer = ByteViewer::new();
+        byte_viewer.colors = settings.bit_colors.clone();
+        
+        let mut app = Self {
             original_bits: BitVec::new(),
             processed_bits: BitVec::new(),
+            mapped_original: None,
+            mapped_processed: None,
             viewer,
-            byte_viewer: ByteViewer::new(),
+            byte_viewer,
+            byte_view_undo: Vec::new(),
+            byte_view_redo: Vec::new(),
+            byte_view_snapshot: ByteViewConfig::default(),
+            symbol_viewer: SymbolViewer::default(),
             view_mode: ViewMode::Bit,
             operations: Vec::new(),
             current_file_path: None,
-            error_message: None,
+            notifications: Notifications::default(),
+            show_notifications: false,
             show_original: true,
             show_settings: false,
             font_size: settings.font_size,
+            ui_scale_input: settings.ui_scale,
             settings,
             worksheets,
             current_worksheet_index: 0,
             renaming_worksheet: None,
             worksheet_name_buffer: String::new(),
+            worksheet_filter: String::new(),
+            operation_filter: String::new(),
             show_operation_menu: None,
             editing_operation_index: None,
+            editing_group_child: None,
+            pipeline_dirty: false,
             dragging_operation: None,
+            dragging_worksheet: None,
+            goto_input: String::new(),
+            goto_in_bytes: false,
+            ascii_jump_to_bit: None,
+            ascii_encoding: TextEncoding::default(),
+            ascii_bits_per_char: 8,
             takeskip_name: String::new(),
             takeskip_input: String::new(),
             loadfile_name: String::new(),
             loadfile_path: None,
+            loadfile_offset: String::from("0"),
+            loadfile_length: String::new(),
+            multifile_name: String::new(),
+            multifile_paths: Vec::new(),
+            multifile_glob: String::new(),
+            softload_name: String::new(),
+            softload_path: None,
+            softload_format: SoftFormat::SignedByte,
+            softload_combine: false,
             invert_name: String::new(),
             truncate_name: String::new(),
             truncate_start: String::from("0"),
             truncate_end: String::new(),
+            range_name: String::new(),
+            range_action: RangeAction::Invert,
+            range_start: String::from("0"),
+            range_end: String::new(),
+            xor_name: String::new(),
+            xor_key: String::new(),
+            line_code_name: String::new(),
+            line_code: LineCode::EightBTenB,
+            symbol_code_name: String::new(),
+            symbol_code: SymbolCode::GrayToBinary,
+            symbol_code_bits: "8".to_string(),
+            remap_name: String::new(),
+            remap_input_bits: "4".to_string(),
+            remap_output_bits: "4".to_string(),
+            remap_table: String::new(),
+            majority_name: String::new(),
+            majority_frames: false,
+            majority_repeats: "3".to_string(),
+            majority_frame_length: String::new(),
+            majority_block_frames: "1".to_string(),
+            majority_tie_break: TieBreak::First,
+            nand_name: String::new(),
+            nand_page_size: "2048".to_string(),
+            nand_spare_size: "64".to_string(),
+            nand_interleaved: false,
+            nand_steps: "4".to_string(),
+            nand_keep: NandArea::Data,
+            nand_ecc: NandEcc::None,
+            nand_ecc_offset: "0".to_string(),
+            nand_correct: true,
+            nand_report: None,
+            group_name: String::new(),
+            group_operations: Vec::new(),
+            group_collapsed: false,
+            conditional_name: String::new(),
+            conditional_operation: None,
+            conditional_by_ranges: false,
+            conditional_frame_length: String::from("64"),
+            conditional_every: String::from("2"),
+            conditional_offset: String::from("0"),
+            conditional_ranges: String::new(),
+            variants_name: String::new(),
+            variants_a: None,
+            variants_b: None,
+            variants_use_b: false,
+            variant_comparison: None,
+            script_name: String::new(),
+            script_source: String::from(DEFAULT_SCRIPT),
+            script_time_limit_ms: DEFAULT_SCRIPT_TIME_LIMIT_MS,
+            insert_name: String::new(),
+            insert_position: String::from("0"),
+            insert_count: String::from("1"),
+            insert_fill: String::from("0"),
+            delete_name: String::new(),
+            delete_unstuff: false,
+            delete_position: String::from("0"),
+            delete_count: String::from("1"),
+            delete_run_length: String::from("5"),
             interleave_name: String::new(),
             interleave_type: crate::processing::InterleaverType::Block,
             interleave_direction: crate::processing::InterleaverDirection::Interleave,
@@ -197,27 +740,54 @@ impl Default for BitApp {
             multiworksheet_ops: Vec::new(),
             multiworksheet_input: String::new(),
             multiworksheet_selected_worksheet: 0,
+            macro_name: String::new(),
+            macro_operations: Vec::new(),
+            new_macro_name: String::new(),
+            patch_name: String::new(),
+            patch_list: Vec::new(),
+            edit_mode: false,
+            analysis_scope: AnalysisScope::Whole,
+            analysis_range_start: 0,
+            analysis_range_end: 0,
             patterns: Vec::new(),
+            match_index: MatchIndex::default(),
             show_pattern_locator: false,
             pattern_name_input: String::new(),
             pattern_input: String::new(),
             pattern_format: PatternFormat::Bits,
             pattern_garbles: 0,
+            pattern_slips: 0,
+            match_export_context_bytes: 4,
             selected_pattern: None,
+            current_match: None,
+            session_matches: None,
             show_restore_dialog,
             pending_session,
+            session_load_error,
+            project_path: None,
+            last_autosave_time: 0.0,
+            last_autosave_hash: 0,
+            autosave_receiver: None,
+            recovery_snapshots,
             show_column_editor: false,
+            column_editor_index: None,
             column_editor_label: String::new(),
             column_editor_bit_start: String::from("0"),
             column_editor_bit_end: String::from("7"),
             column_editor_color: [100, 150, 200],
+            column_editor_timestamp: None,
             loading_receiver: None,
+            loading_cancel: CancellationToken::new(),
             loading_file_path: None,
             loading_progress: 0.0,
             loading_total: 0,
             operation_receiver: None,
             operation_progress_message: String::new(),
+            operation_timings: Vec::new(),
+            previous_operation_sizes: Vec::new(),
             operation_progress: 0.0,
+            operation_cancel: CancellationToken::new(),
+            operation_cache: Arc::new(Mutex::new(OperationCache::new(operation_cache_budget))),
             is_rendering: false,
             render_progress_message: String::new(),
             render_progress: 0.0,
@@ -226,14 +796,215 @@ impl Default for BitApp {
             frame_width_min: 4,
             frame_width_max: 200,
             frame_width_delta: 0,
+            frame_width_use_list: false,
+            frame_width_list: "8..1024 step 8".to_string(),
             frame_width_analysis: None,
+            frame_width_pending_scan: None,
             frame_width_sort_by_score: true, // Default to sorting by score
             frame_width_selected: None,
+            analysis_task: None,
+            save_task: None,
+            show_hdlc_decoder: false,
+            hdlc_options: HdlcOptions::default(),
+            hdlc_frames: None,
+            hdlc_only_valid: false,
+            hdlc_selected: None,
+            show_framer: false,
+            framer_sync_format: PatternFormat::Hex,
+            framer_sync_input: String::new(),
+            framer_sync_garbles: 0,
+            framer_end_input: String::new(),
+            framer_frame_length: 0,
+            framer_crc: FrameCrc::None,
+            framer_frames: None,
+            framer_selected: None,
+            show_repeats: false,
+            repeat_min_len: 8,
+            repeat_max_len: 32,
+            repeat_results: None,
+            show_channel_export: false,
+            channel_count: 2,
+            channel_unit_bits: 1,
+            show_frame_stats: false,
+            frame_stats_column_bits: 8,
+            frame_stats: None,
+            frame_stats_super_position: None,
+            frame_stats_super_frame: None,
+            timestamp_candidates: None,
+            show_frame_distance: false,
+            frame_distances: None,
+            show_frames_csv: false,
+            frames_csv_spec: String::new(),
+            show_converter: false,
+            converter_input: String::new(),
+            converter_base: NumberBase::Hex,
+            converter_width: 32,
+            show_checksums: false,
+            checksum_report: None,
+            show_structures: false,
+            structure_template: String::from(DEFAULT_STRUCTURE_TEMPLATE),
+            structure_offset: String::from("0"),
+            structure_result: None,
+            auto_layout_pattern: 0,
+            auto_layout_field_bytes: 1,
+            missing_files: Vec::new(),
+            file_watcher: None,
+            show_report: false,
+            report_title: String::from("Protocol Report"),
+            report_regions: Vec::new(),
+            show_xor_key: false,
+            xor_min_key_len: 1,
+            xor_max_key_len: 16,
+            xor_results: None,
+            show_periodicity: false,
+            periodicity_min: 1,
+            periodicity_max: 512,
+            periodicity_spectrum: None,
+            show_batch: false,
+            batch_folder: None,
+            batch_pattern_names: Vec::new(),
+            batch_rows: None,
+            batch_receiver: None,
+            show_sources: false,
+            source_protocol: NetworkProtocol::Udp,
+            source_port: 5000,
+            network_listener: None,
+            received_segments: Vec::new(),
+            gap_threshold_ms: 100,
+            gap_markers_note: None,
+            soft_confidence: None,
+            stream_stats: None,
+        };
+        if let Some(error) = settings_error {
+            app.notifications.warning(error);
         }
+        app
     }
 }
 
 impl BitApp {
+    /// Original bits, from memory or from a memory-mapped file
+    pub fn original_view(&self) -> &BitSlice<u8, Msb0> {
+        match &self.mapped_original {
+            Some(mapped) => mapped.bits(),
+            None => &self.original_bits,
+        }
+    }
+    
+    /// Processed bits, from memory or from a memory-mapped file
+    pub fn processed_view(&self) -> &BitSlice<u8, Msb0> {
+        match &self.mapped_processed {
+            Some(mapped) => mapped.bits(),
+            None => &self.processed_bits,
+        }
+    }
+    
+    /// The bits currently selected for display (original or processed)
+    pub fn display_bits(&self) -> &BitSlice<u8, Msb0> {
+        if self.show_original {
+            self.original_view()
+        } else {
+            self.processed_view()
+        }
+    }
+    
+    /// Statistics of the displayed bits, recomputed only when they change
+    pub fn stream_stats(&mut self) -> &BitStats {
+        let fingerprint = self.display_fingerprint();
+        if self.stream_stats.as_ref().is_none_or(|(cached, _)| *cached != fingerprint) {
+            let stats = bit_stats(self.display_bits(), STREAM_STATS_RUN_BITS);
+            self.stream_stats = Some((fingerprint, stats));
+        }
+        &self.stream_stats.as_ref().expect("stats were just computed").1
+    }
+    
+    /// Cheap stand-in for the displayed bits' identity: which buffer, its length and the bytes
+    /// at both ends. Edits rebuild the processed buffer, so they change it too.
+    fn display_fingerprint(&self) -> u64 {
+        let bits = self.display_bits();
+        let bytes = bits.domain().region().map(|(_, body, _)| body).unwrap_or_default();
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        (self.show_original, bits.len(), bits.as_bitptr().pointer() as usize).hash(&mut hasher);
+        bytes[..bytes.len().min(FINGERPRINT_BYTES)].hash(&mut hasher);
+        bytes[bytes.len().saturating_sub(FINGERPRINT_BYTES)..].hash(&mut hasher);
+        hasher.finish()
+    }
+    
+    /// The part of the displayed bits the analyses run on, as an absolute bit range
+    pub fn analysis_range(&self) -> Result<std::ops::Range<usize>, String> {
+        let total = self.display_bits().len();
+        let (start, end) = match self.analysis_scope {
+            AnalysisScope::Whole => return Ok(0..total),
+            AnalysisScope::Selection => self.viewer.selection
+                .ok_or_else(|| "No selection; shift-drag in the bit viewer to select bits".to_string())?,
+            AnalysisScope::Range => (self.analysis_range_start, self.analysis_range_end),
+        };
+        let end = end.min(total);
+        if start >= end {
+            return Err(format!("Analysis range {}..{} is empty ({} bits available)", start, end, total));
+        }
+        Ok(start..end)
+    }
+    
+    /// The bits in `analysis_range`, with the offset of their first bit
+    pub fn analysis_bits(&self) -> Result<(usize, &BitSlice<u8, Msb0>), String> {
+        let range = self.analysis_range()?;
+        Ok((range.start, &self.display_bits()[range]))
+    }
+    
+    /// Use `colors` in all views and persist them; `None` follows the theme
+    pub fn set_bit_colors(&mut self, colors: Option<BitColors>) {
+        self.viewer.colors = colors.clone();
+        self.byte_viewer.colors = colors.clone();
+        self.settings.bit_colors = colors;
+        self.settings.auto_save();
+    }
+    
+    /// The bits in `analysis_range` for a background task, with the offset of their first bit
+    fn analysis_input(&self) -> Result<(usize, TaskBits), String> {
+        let range = self.analysis_range()?;
+        let mapped = if self.show_original { &self.mapped_original } else { &self.mapped_processed };
+        let input = match mapped {
+            Some(mapped) => TaskBits::Mapped(mapped.clone(), range.clone()),
+            None => TaskBits::Copied(self.display_bits()[range.clone()].to_bitvec()),
+        };
+        Ok((range.start, input))
+    }
+    
+    /// Apply the cache size from the settings; a cache held by a background run picks it up
+    /// on the next run
+    pub fn update_operation_cache_budget(&mut self) {
+        if let Ok(mut cache) = self.operation_cache.try_lock() {
+            cache.set_budget(self.settings.operation_cache_budget());
+        }
+    }
+    
+    /// Memory-map `path` if it is a large file and memory mapping is enabled
+    fn map_if_large(&mut self, path: &std::path::Path) -> Option<Arc<MappedBits>> {
+        let threshold = self.settings.memory_map_threshold()?;
+        if !should_memory_map(path, threshold) {
+            return None;
+        }
+        // A mapping can only be viewed as it is on disk
+        if self.current_worksheet().bit_order != BitOrder::MsbFirst {
+            return None;
+        }
+        // A watched file is rewritten under us, and a mapping of a file that shrinks faults on
+        // the pages that are gone
+        if self.file_watcher.as_ref().is_some_and(|watcher| watcher.path() == path) {
+            return None;
+        }
+        
+        match MappedBits::open(path) {
+            Ok(mapped) => Some(Arc::new(mapped)),
+            Err(e) => {
+                // Fall back to reading the file normally
+                self.notifications.error(format!("Failed to memory-map {}: {}", path.display(), e));
+                None
+            }
+        }
+    }
+    
     pub fn current_worksheet(&self) -> &Worksheet {
         &self.worksheets[self.current_worksheet_index]
     }
@@ -253,10 +1024,189 @@ impl BitApp {
         }
     }
     
+    /// Write a crash recovery snapshot if autosave is due and the session changed since the last one.
+    /// The file is written on a background thread.
+    pub fn autosave_if_due(&mut self, now: f64) {
+        if !self.settings.autosave_enabled || now - self.last_autosave_time < self.settings.autosave_interval_secs as f64 {
+            return;
+        }
+        self.last_autosave_time = now;
+        
+        let session = AppSession::new(self.worksheets.clone(), self.current_worksheet_index);
+        let Ok(json) = serde_json::to_string(&session) else {
+            return;
+        };
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        json.hash(&mut hasher);
+        let hash = hasher.finish();
+        if hash == self.last_autosave_hash {
+            return;
+        }
+        self.last_autosave_hash = hash;
+        
+        let (sender, receiver) = channel();
+        self.autosave_receiver = Some(receiver);
+        thread::spawn(move || {
+            if let Err(e) = autosave::write_snapshot(&session) {
+                let _ = sender.send(e);
+            }
+        });
+    }
+    
+    /// Report a failed autosave once its thread finishes
+    pub fn poll_autosave(&mut self) {
+        let Some(receiver) = &self.autosave_receiver else {
+            return;
+        };
+        match receiver.try_recv() {
+            Ok(e) => {
+                self.autosave_receiver = None;
+                // Nothing was saved, so try again at the next interval even if nothing changes
+                self.last_autosave_hash = 0;
+                self.notifications.error(format!("Autosave failed: {}", e));
+            }
+            Err(std::sync::mpsc::TryRecvError::Empty) => {}
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => self.autosave_receiver = None,
+        }
+    }
+    
+    /// Save everything to the current project file, asking for one if there isn't one yet
+    pub fn save_project(&mut self) {
+        match self.project_path.clone() {
+            Some(path) => self.write_project(&path),
+            None => self.save_project_as(),
+        }
+    }
+    
+    pub fn save_project_as(&mut self) {
+        let mut dialog = rfd::FileDialog::new()
+            .add_filter("B.I.T. Project", &[Project::FILE_EXTENSION])
+            .set_file_name(format!("project.{}", Project::FILE_EXTENSION));
+        if let Some(dir) = self.project_path.as_ref().and_then(|path| path.parent()) {
+            dialog = dialog.set_directory(dir);
+        }
+        
+        if let Some(path) = dialog.save_file() {
+            self.write_project(&path);
+        }
+    }
+    
+    fn write_project(&mut self, path: &std::path::Path) {
+        let project = Project::new(
+            self.worksheets.clone(),
+            self.current_worksheet_index,
+            self.patterns.clone(),
+            self.byte_viewer.config.clone(),
+        );
+        
+        match project.save_to_file(path) {
+            Ok(()) => {
+                self.project_path = Some(path.to_path_buf());
+            }
+            Err(e) => self.notifications.error(e),
+        }
+    }
+    
+    pub fn open_project(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("B.I.T. Project", &[Project::FILE_EXTENSION])
+            .add_filter("All Files", &["*"])
+            .pick_file() else {
+            return;
+        };
+        
+        match Project::load_from_file(&path) {
+            Ok(project) => {
+                self.patterns = project.patterns;
+                self.selected_pattern = None;
+                self.viewer.clear_highlights();
+                self.byte_viewer.config = project.byte_view_config;
+                self.project_path = Some(path);
+                self.restore_session(AppSession::new(project.worksheets, project.current_worksheet_index));
+            }
+            Err(e) => self.notifications.error(e),
+        }
+    }
+    
+    /// Name shown for the open project, if any
+    pub fn project_name(&self) -> Option<String> {
+        self.project_path
+            .as_ref()
+            .and_then(|path| path.file_stem())
+            .map(|stem| stem.to_string_lossy().into_owned())
+    }
+    
     pub fn restore_session(&mut self, session: AppSession) {
         self.worksheets = session.worksheets;
         self.current_worksheet_index = session.current_worksheet_index.min(self.worksheets.len().saturating_sub(1));
         self.load_from_worksheet();
+        self.missing_files = missing_files(&mut self.worksheets);
+    }
+    
+    /// Point the missing file at `index` at a file picked by hand. Other missing files from
+    /// the same folder are remapped too when they're next to the picked one.
+    pub fn locate_missing_file(&mut self, index: usize) {
+        let Some(old) = self.missing_files.get(index).cloned() else {
+            return;
+        };
+        let mut dialog = rfd::FileDialog::new().set_title(format!("Locate {}", old.display()));
+        if let Some(name) = old.file_name() {
+            dialog = dialog.set_file_name(name.to_string_lossy());
+        }
+        let Some(new) = dialog.pick_file() else {
+            return;
+        };
+        
+        let mut remap = vec![(old.clone(), new.clone())];
+        if let Some(folder) = new.parent() {
+            let siblings: Vec<PathBuf> = self.missing_files
+                .iter()
+                .filter(|path| **path != old && path.parent() == old.parent())
+                .cloned()
+                .collect();
+            remap.extend(remap_to_folder(&siblings, folder));
+        }
+        self.remap_files(&remap);
+    }
+    
+    /// Look for every missing file, by name, in a folder picked by hand
+    pub fn remap_missing_folder(&mut self) {
+        let Some(folder) = rfd::FileDialog::new().pick_folder() else {
+            return;
+        };
+        
+        let remap = remap_to_folder(&self.missing_files, &folder);
+        if remap.is_empty() {
+            self.notifications.error(format!("None of the missing files are in {}", folder.display()));
+            return;
+        }
+        self.remap_files(&remap);
+    }
+    
+    /// Replace (old, new) paths in every worksheet and reload the current one
+    fn remap_files(&mut self, remap: &[(PathBuf, PathBuf)]) {
+        apply_remap(&mut self.worksheets, remap);
+        for op in &mut self.operations {
+            op.for_each_file_path_mut(&mut |path| {
+                if let Some((_, new)) = remap.iter().find(|(old, _)| old == path) {
+                    *path = new.clone();
+                }
+            });
+        }
+        self.current_worksheet_mut().operations = self.operations.clone();
+        self.load_from_worksheet();
+        self.missing_files = missing_files(&mut self.worksheets);
+    }
+    
+    /// Reapply after the operation list was edited, or in manual apply mode just note that
+    /// the processed view is out of date
+    pub fn operations_changed(&mut self) {
+        if self.settings.auto_apply_operations {
+            self.apply_operations();
+        } else {
+            self.pipeline_dirty = true;
+            self.sync_to_worksheet();
+        }
     }
     
     /// Clear all pattern matches
@@ -269,20 +1219,64 @@ impl BitApp {
     }
     
     pub fn apply_operations(&mut self) {
+        self.pipeline_dirty = false;
+        self.update_operation_cache_budget();
+        self.previous_operation_sizes = self.operation_timings.iter().map(|timing| timing.and_then(|t| t.sizes())).collect();
+        self.operation_timings = vec![None; self.operations.len()];
+        
         // Don't clear pattern matches here - they should only be cleared when operations list changes
         // Pattern matches are based on the processed bits, which may not change even if we reapply
         
-        // Check if we need async processing (large files in operations)
-        let needs_async = self.operations.iter().any(|op| {
-            if let BitOperation::LoadFile { file_path, enabled, .. } = op {
-                if *enabled {
-                    if let Ok(metadata) = std::fs::metadata(file_path) {
-                        return metadata.len() > 10 * 1024 * 1024; // >10MB
-                    }
+        let enabled_ops: Vec<&BitOperation> = self.operations.iter().filter(|op| op.is_enabled()).collect();
+        let lone_load_file = match enabled_ops.as_slice() {
+            // Only a whole file can be shown straight from the mapping
+            [BitOperation::LoadFile { file_path, offset: 0, length: None, .. }] => Some(file_path.clone()),
+            _ => None,
+        };
+        let no_enabled_ops = enabled_ops.is_empty();
+        let uses_original = !enabled_ops.iter().any(|op| op.is_source());
+        
+        // A large file loaded on its own is only going to be viewed, so map it instead of reading it in
+        if let Some(mapped) = lone_load_file.and_then(|path| self.map_if_large(&path)) {
+            self.processed_bits = BitVec::new();
+            self.mapped_processed = Some(mapped);
+            self.show_original = false;
+            self.update_viewer();
+            self.sync_to_worksheet();
+            return;
+        }
+        
+        if let Some(mapped) = &self.mapped_original {
+            if no_enabled_ops {
+                // Nothing to apply: the processed view is the mapped file itself
+                self.processed_bits = BitVec::new();
+                self.mapped_processed = Some(mapped.clone());
+                self.update_viewer();
+                self.sync_to_worksheet();
+                return;
+            }
+            
+            if uses_original {
+                // Operations need the bits in memory: load the file for real, the operations
+                // are applied again once loading completes
+                if (mapped.len() / 8) as u64 > MAX_FILE_SIZE {
+                    self.notifications.error(format!(
+                        "File is memory-mapped for viewing only; applying operations needs it in memory (max {} MB)",
+                        MAX_FILE_SIZE / (1024 * 1024)
+                    ));
+                } else if !self.is_loading() {
+                    let path = mapped.path().to_path_buf();
+                    self.start_loading_file(path);
                 }
+                return;
             }
-            false
-        });
+        }
+        
+        self.mapped_processed = None;
+        
+        // Go async whenever the pipeline has a lot of data to chew through,
+        // whether it comes from the loaded file or from LoadFile/MultiWorksheetLoad sources
+        let needs_async = self.estimated_input_bytes() > ASYNC_THRESHOLD_BYTES;
         
         if needs_async {
             // Use async processing for heavy operations
@@ -296,43 +1290,75 @@ impl BitApp {
             matches!(op, BitOperation::MultiWorksheetLoad { enabled: true, .. })
         });
         let has_loadfile = self.operations.iter().any(|op| {
-            matches!(op, BitOperation::LoadFile { enabled: true, .. })
+            matches!(
+                op,
+                BitOperation::LoadFile { enabled: true, .. }
+                    | BitOperation::MultiFileLoad { enabled: true, .. }
+                    | BitOperation::LoadSoftBits { enabled: true, .. }
+            )
         });
         
         if has_multiworksheet || has_loadfile {
             // MultiWorksheetLoad or LoadFile creates new bits from scratch
+            let bit_order = self.current_worksheet().bit_order;
             let mut result = BitVec::new();
+            let mut timings = vec![None; self.operations.len()];
             
-            for op in &self.operations {
+            for (index, op) in self.operations.iter().enumerate() {
                 // Skip disabled operations
                 if !op.is_enabled() {
                     continue;
                 }
                 
+                let started = Instant::now();
+                let input_bits = result.len();
                 match op {
-                    BitOperation::LoadFile { file_path, .. } => {
+                    BitOperation::LoadFile { file_path, offset, length, .. } => {
                         // Load bits from the file
-                        match read_file_as_bits(file_path) {
+                        match read_file_range_as_bits_in_order(file_path, *offset, *length, bit_order) {
                             Ok(bits) => {
                                 result.extend(bits);
                             }
                             Err(e) => {
-                                self.error_message = Some(format!("Failed to load file {}: {}", 
+                                self.notifications.error(format!("Failed to load file {}: {}", 
                                     file_path.display(), e));
                                 continue; // Skip if file can't be loaded
                             }
                         }
                     }
+                    BitOperation::MultiFileLoad { file_paths, .. } => {
+                        match read_files_as_bits_in_order(file_paths, bit_order) {
+                            Ok(bits) => result.extend(bits),
+                            Err(e) => {
+                                self.notifications.error(e);
+                                continue;
+                            }
+                        }
+                    }
+                    BitOperation::LoadSoftBits { file_path, format, combine, .. } => {
+                        match read_soft_file(file_path, *format, combine.as_ref()) {
+                            Ok(soft) => result.extend(soft.bits),
+                            Err(e) => {
+                                self.notifications.error(e);
+                                continue;
+                            }
+                        }
+                    }
                     BitOperation::MultiWorksheetLoad { worksheet_operations, .. } => {
                         // Process each worksheet operation
                         for wo in worksheet_operations {
-                            if wo.worksheet_index < self.worksheets.len() && wo.worksheet_index != self.current_worksheet_index {
+                            let Some(ws_idx) = find_worksheet(&self.worksheets, wo) else {
+                                self.notifications.error(format!("Worksheet '{}' no longer exists", wo.worksheet_name));
+                                continue;
+                            };
+                            if ws_idx != self.current_worksheet_index {
                                 // Get the source worksheet's processed bits (if it has a file loaded)
-                                let source_bits = if let Some(file_path) = &self.worksheets[wo.worksheet_index].file_path {
-                                    match read_file_as_bits(file_path) {
+                                let source = &self.worksheets[ws_idx];
+                                let mut source_bits = if let Some(file_path) = &source.file_path {
+                                    match read_file_as_bits_in_order(file_path, source.bit_order) {
                                         Ok(bits) => bits,
                                         Err(e) => {
-                                            self.error_message = Some(format!("Failed to load worksheet {}: {}", wo.worksheet_index + 1, e));
+                                            self.notifications.error(format!("Failed to load worksheet {}: {}", self.worksheets[ws_idx].name, e));
                                             continue; // Skip if file can't be loaded
                                         }
                                     }
@@ -341,18 +1367,20 @@ impl BitApp {
                                 };
                                 
                                 // Apply the sequence to these bits
-                                let processed = wo.sequence.apply(&source_bits);
-                                result.extend(processed);
+                                wo.sequence.apply_in_place(&mut source_bits);
+                                result.extend_from_bitslice(&source_bits);
                             }
                         }
                     }
                     _ => {
                         // Regular operations are applied to result so far
-                        result = op.apply(&result);
+                        op.apply_in_place(&mut result);
                     }
                 }
+                timings[index] = Some(OperationTiming::since(started, input_bits, &result));
             }
             
+            self.operation_timings = timings;
             self.processed_bits = result;
             // When using MultiWorksheetLoad or LoadFile, automatically switch to viewing processed bits
             self.show_original = false;
@@ -363,11 +1391,26 @@ impl BitApp {
             }
 
             let mut result = self.original_bits.clone();
+            let mut timings = vec![None; self.operations.len()];
+            let mut apply = |index: usize, op: &BitOperation, bits: &mut BitVec<u8, Msb0>| {
+                let started = Instant::now();
+                let input_bits = bits.len();
+                op.apply_in_place(bits);
+                timings[index] = Some(OperationTiming::since(started, input_bits, bits));
+                Ok(())
+            };
             
-            for op in &self.operations {
-                result = op.apply(&result);
+            // The cache is busy while a background run holds it; apply everything then
+            if let Ok(mut cache) = self.operation_cache.try_lock() {
+                result = cache.run(result, &self.operations, apply).unwrap_or_default();
+            } else {
+                for (index, op) in self.operations.iter().enumerate().filter(|(_, op)| op.is_enabled()) {
+                    let _ = apply(index, op, &mut result);
+                }
             }
 
+            self.operation_timings = timings;
+            self.mark_cached_operations();
             self.processed_bits = result;
         }
         
@@ -375,24 +1418,98 @@ impl BitApp {
         self.sync_to_worksheet();
     }
     
+    /// Enabled operations the last successful apply didn't run were resumed from the cache
+    fn mark_cached_operations(&mut self) {
+        for (index, (op, timing)) in self.operations.iter().zip(&mut self.operation_timings).enumerate() {
+            if op.is_enabled() && timing.is_none() {
+                let sizes = self.previous_operation_sizes.get(index).copied().flatten();
+                *timing = Some(OperationTiming::Cached { sizes });
+            }
+        }
+    }
+    
+    /// Total time spent in the operations of the last apply
+    pub fn total_operation_time(&self) -> Duration {
+        self.operation_timings
+            .iter()
+            .filter_map(|timing| match timing {
+                Some(OperationTiming::Ran { duration, .. }) => Some(*duration),
+                _ => None,
+            })
+            .sum()
+    }
+    
+    /// One line about the last apply for the operations panel, None before anything ran
+    pub fn operation_timing_summary(&self) -> Option<String> {
+        let ran = self.operation_timings.iter().filter(|t| matches!(t, Some(OperationTiming::Ran { .. }))).count();
+        let cached = self.operation_timings.iter().filter(|t| matches!(t, Some(OperationTiming::Cached { .. }))).count();
+        if ran + cached == 0 {
+            return None;
+        }
+        let mut summary = format!("Last run: {:.1} ms", self.total_operation_time().as_secs_f64() * 1000.0);
+        if cached > 0 {
+            summary.push_str(&format!(", {} from cache", cached));
+        }
+        summary.push_str(&format!(", {} bits out", self.processed_view().len()));
+        Some(summary)
+    }
+    
+    /// Estimate how many bytes of input the enabled operations will process
+    pub fn estimated_input_bytes(&self) -> u64 {
+        let file_size = |path: &std::path::Path| std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        let mut total = (self.original_bits.len() / 8) as u64;
+        
+        for op in self.operations.iter().filter(|op| op.is_enabled()) {
+            match op {
+                BitOperation::LoadFile { file_path, offset, length, .. } => {
+                    total += range_len(file_size(file_path), *offset, *length);
+                }
+                BitOperation::MultiFileLoad { file_paths, .. } => {
+                    total += file_paths.iter().map(|path| file_size(path)).sum::<u64>();
+                }
+                BitOperation::LoadSoftBits { file_path, format, .. } => {
+                    total += file_size(file_path) / format.sample_bytes() as u64 / 8;
+                }
+                BitOperation::MultiWorksheetLoad { worksheet_operations, .. } => {
+                    for wo in worksheet_operations {
+                        if let Some(path) = find_worksheet(&self.worksheets, wo).and_then(|idx| self.worksheets[idx].file_path.as_ref()) {
+                            total += file_size(path);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        
+        total
+    }
+    
     #[allow(dead_code)]
     pub fn clear_error(&mut self) {
-        self.error_message = None;
+        self.notifications.clear();
     }
 
     #[allow(dead_code)]
     pub fn set_error(&mut self, message: String) {
-        self.error_message = Some(message);
+        self.notifications.error(message);
     }
     
     /// Start loading a file asynchronously with progress reporting
     pub fn start_loading_file(&mut self, path: PathBuf) {
+        // Received segments describe the bits being replaced
+        self.received_segments.clear();
+        if let Ok(mut cache) = self.operation_cache.try_lock() {
+            cache.clear();
+        }
         let (tx, rx) = channel();
         let path_clone = path.clone();
+        let cancel = CancellationToken::new();
+        self.loading_cancel = cancel.clone();
+        let bit_order = self.current_worksheet().bit_order;
         
         // Spawn background thread to load file
         thread::spawn(move || {
-            let _ = read_file_as_bits_with_progress(&path_clone, tx);
+            let _ = read_file_as_bits_with_progress(&path_clone, tx, &cancel, bit_order);
         });
         
         self.loading_receiver = Some(rx);
@@ -439,8 +1556,9 @@ impl BitApp {
                     Ok(bits) => {
                         self.original_bits = bits.clone();
                         self.processed_bits = bits;
+                        self.mapped_original = None;
+                        self.mapped_processed = None;
                         self.current_file_path = path_to_load;
-                        self.error_message = None;
                         self.clear_pattern_matches(); // New file loaded, clear old patterns
                         self.apply_operations();
                         
@@ -453,13 +1571,21 @@ impl BitApp {
                         }
                     }
                     Err(e) => {
-                        self.error_message = Some(format!("Failed to load file: {}", e));
+                        self.notifications.error(format!("Failed to load file: {}", e));
                     }
                 }
             }
         }
     }
     
+    /// Stop loading the file; the bits already shown stay as they are
+    pub fn cancel_loading(&mut self) {
+        self.loading_cancel.cancel();
+        self.loading_receiver = None;
+        self.loading_file_path = None;
+        self.loading_progress = 0.0;
+    }
+    
     /// Check if currently loading a file
     pub fn is_loading(&self) -> bool {
         self.loading_receiver.is_some()
@@ -476,6 +1602,11 @@ impl BitApp {
         let original_bits = self.original_bits.clone();
         let worksheets = self.worksheets.clone();
         let current_worksheet_index = self.current_worksheet_index;
+        let cache = self.operation_cache.clone();
+        
+        // Fresh flag per run so a late cancel can't leak into the next one
+        let cancel = CancellationToken::new();
+        self.operation_cancel = cancel.clone();
         
         let (tx, rx) = channel();
         
@@ -485,7 +1616,9 @@ impl BitApp {
                 original_bits,
                 worksheets,
                 current_worksheet_index,
-                tx
+                cache,
+                tx,
+                cancel,
             );
         });
         
@@ -500,13 +1633,29 @@ impl BitApp {
         original_bits: BitVec<u8, Msb0>,
         worksheets: Vec<Worksheet>,
         current_worksheet_index: usize,
+        cache: Arc<Mutex<OperationCache>>,
         tx: std::sync::mpsc::Sender<OperationProgress>,
+        cancel: CancellationToken,
     ) -> std::io::Result<()> {
+        const CANCELLED: &str = "Operation processing cancelled";
+        
+        // Apply a regular operation chunk by chunk, forwarding progress and honoring cancellation
+        let apply_op = |op: &BitOperation, bits: &mut BitVec<u8, Msb0>, index: usize, total: usize| {
+            let completed = op.apply_chunked(bits, |processed, total_bits| {
+                let _ = tx.send(OperationProgress::ProcessingChunk { index, total, processed, total_bits });
+                !cancel.is_cancelled()
+            });
+            if completed { Ok(()) } else { Err(CANCELLED.to_string()) }
+        };
+        
         let result = (|| -> Result<BitVec<u8, Msb0>, String> {
             let has_multiworksheet = operations.iter().any(|op| matches!(op, BitOperation::MultiWorksheetLoad { .. }));
-            let has_loadfile = operations.iter().any(|op| matches!(op, BitOperation::LoadFile { .. }));
+            let has_loadfile = operations.iter().any(|op| {
+                matches!(op, BitOperation::LoadFile { .. } | BitOperation::MultiFileLoad { .. } | BitOperation::LoadSoftBits { .. })
+            });
             
             if has_multiworksheet || has_loadfile {
+                let bit_order = worksheets[current_worksheet_index].bit_order;
                 let mut result = BitVec::new();
                 let total_ops = operations.len();
                 
@@ -516,8 +1665,15 @@ impl BitApp {
                         continue;
                     }
                     
+                    if cancel.is_cancelled() {
+                        return Err(CANCELLED.to_string());
+                    }
+                    
+                    let started = Instant::now();
+                    let input_bits = result.len();
                     match op {
-                        BitOperation::LoadFile { file_path, name, .. } => {
+                        BitOperation::LoadFile { file_path, name, offset, length, .. } => {
+                            let (offset, length) = (*offset, *length);
                             let _ = tx.send(OperationProgress::ProcessingOperation {
                                 index: idx + 1,
                                 total: total_ops,
@@ -526,16 +1682,17 @@ impl BitApp {
                             
                             // Check file size for progress reporting
                             if let Ok(metadata) = std::fs::metadata(file_path) {
-                                let file_size = metadata.len();
+                                let file_size = range_len(metadata.len(), offset, length);
                                 
                                 if file_size > 10 * 1024 * 1024 {
                                     // Large file - use progress reporting
                                     let (file_tx, file_rx) = channel();
                                     let path_clone = file_path.clone();
+                                    let file_cancel = cancel.clone();
                                     
                                     // Load file with progress
                                     thread::spawn(move || {
-                                        let _ = read_file_as_bits_with_progress(&path_clone, file_tx);
+                                        let _ = read_file_range_with_progress(&path_clone, offset, length, file_tx, &file_cancel, bit_order);
                                     });
                                     
                                     // Forward progress messages
@@ -562,39 +1719,64 @@ impl BitApp {
                                     }
                                 } else {
                                     // Small file - load directly
-                                    match read_file_as_bits(file_path) {
+                                    match read_file_range_as_bits_in_order(file_path, offset, length, bit_order) {
                                         Ok(bits) => result.extend(bits),
                                         Err(e) => return Err(format!("Failed to load file {}: {}", file_path.display(), e)),
                                     }
                                 }
                             } else {
                                 // Can't get metadata, try loading anyway
-                                match read_file_as_bits(file_path) {
+                                match read_file_range_as_bits_in_order(file_path, offset, length, bit_order) {
                                     Ok(bits) => result.extend(bits),
                                     Err(e) => return Err(format!("Failed to load file {}: {}", file_path.display(), e)),
                                 }
                             }
                         }
-                        BitOperation::MultiWorksheetLoad { worksheet_operations, .. } => {
+                        BitOperation::MultiFileLoad { file_paths, name, .. } => {
+                            for (file_index, file_path) in file_paths.iter().enumerate() {
+                                if cancel.is_cancelled() {
+                                    return Err(CANCELLED.to_string());
+                                }
+                                let _ = tx.send(OperationProgress::ProcessingOperation {
+                                    index: idx + 1,
+                                    total: total_ops,
+                                    description: format!("{}: file {}/{}", name, file_index + 1, file_paths.len()),
+                                });
+                                result.extend(read_files_as_bits_in_order(std::slice::from_ref(file_path), bit_order)?);
+                            }
+                        }
+                        BitOperation::LoadSoftBits { file_path, name, format, combine, .. } => {
                             let _ = tx.send(OperationProgress::ProcessingOperation {
                                 index: idx + 1,
                                 total: total_ops,
-                                description: "Processing multi-worksheet load".to_string(),
+                                description: format!("Loading soft bits: {}", name),
                             });
-                            
+                            result.extend(read_soft_file(file_path, *format, combine.as_ref())?.bits);
+                        }
+                        BitOperation::MultiWorksheetLoad { worksheet_operations, .. } => {
+                            let _ = tx.send(OperationProgress::ProcessingOperation {
+                                index: idx + 1,
+                                total: total_ops,
+                                description: "Processing multi-worksheet load".to_string(),
+                            });
+                            
                             for wo in worksheet_operations {
-                                if wo.worksheet_index < worksheets.len() && wo.worksheet_index != current_worksheet_index {
-                                    let source_bits = if let Some(file_path) = &worksheets[wo.worksheet_index].file_path {
-                                        match read_file_as_bits(file_path) {
+                                let Some(ws_idx) = find_worksheet(&worksheets, wo) else {
+                                    return Err(format!("Worksheet '{}' no longer exists", wo.worksheet_name));
+                                };
+                                if ws_idx != current_worksheet_index {
+                                    let source = &worksheets[ws_idx];
+                                    let mut source_bits = if let Some(file_path) = &source.file_path {
+                                        match read_file_as_bits_in_order(file_path, source.bit_order) {
                                             Ok(bits) => bits,
-                                            Err(e) => return Err(format!("Failed to load worksheet {}: {}", wo.worksheet_index + 1, e)),
+                                            Err(e) => return Err(format!("Failed to load worksheet {}: {}", worksheets[ws_idx].name, e)),
                                         }
                                     } else {
                                         continue;
                                     };
                                     
-                                    let processed = wo.sequence.apply(&source_bits);
-                                    result.extend(processed);
+                                    wo.sequence.apply_in_place(&mut source_bits);
+                                    result.extend_from_bitslice(&source_bits);
                                 }
                             }
                         }
@@ -604,9 +1786,10 @@ impl BitApp {
                                 total: total_ops,
                                 description: format!("Applying operation {}/{}", idx + 1, total_ops),
                             });
-                            result = op.apply(&result);
+                            apply_op(op, &mut result, idx + 1, total_ops)?;
                         }
                     }
+                    let _ = tx.send(OperationProgress::OperationTimed { index: idx, timing: OperationTiming::since(started, input_bits, &result) });
                 }
                 
                 Ok(result)
@@ -615,19 +1798,26 @@ impl BitApp {
                     return Ok(BitVec::new());
                 }
                 
-                let mut result = original_bits;
                 let total_ops = operations.len();
                 
-                for (idx, op) in operations.iter().enumerate() {
+                // Steps whose input and parameters are unchanged since the last run come from the cache
+                let mut cache = cache.lock().unwrap_or_else(|e| e.into_inner());
+                cache.run(original_bits, &operations, |idx, op, bits| {
+                    if cancel.is_cancelled() {
+                        return Err(CANCELLED.to_string());
+                    }
+                    
                     let _ = tx.send(OperationProgress::ProcessingOperation {
                         index: idx + 1,
                         total: total_ops,
                         description: format!("Applying operation {}/{}", idx + 1, total_ops),
                     });
-                    result = op.apply(&result);
-                }
-                
-                Ok(result)
+                    let started = Instant::now();
+                    let input_bits = bits.len();
+                    apply_op(op, bits, idx + 1, total_ops)?;
+                    let _ = tx.send(OperationProgress::OperationTimed { index: idx, timing: OperationTiming::since(started, input_bits, bits) });
+                    Ok(())
+                })
             }
         })();
         
@@ -654,9 +1844,24 @@ impl BitApp {
                         );
                     }
                     OperationProgress::ProcessingOperation { index, total, description } => {
-                        self.operation_progress = index as f32 / total as f32;
+                        self.operation_progress = index.saturating_sub(1) as f32 / total as f32;
                         self.operation_progress_message = description;
                     }
+                    OperationProgress::ProcessingChunk { index, total, processed, total_bits } => {
+                        let fraction = if total_bits > 0 { processed as f32 / total_bits as f32 } else { 1.0 };
+                        self.operation_progress = (index.saturating_sub(1) as f32 + fraction) / total as f32;
+                        self.operation_progress_message = format!(
+                            "Applying operation {}/{} ({:.0}%)",
+                            index,
+                            total,
+                            fraction * 100.0
+                        );
+                    }
+                    OperationProgress::OperationTimed { index, timing } => {
+                        if let Some(slot) = self.operation_timings.get_mut(index) {
+                            *slot = Some(timing);
+                        }
+                    }
                     OperationProgress::Complete(result) => {
                         should_clear = true;
                         result_bits = Some(result);
@@ -673,7 +1878,7 @@ impl BitApp {
                     Ok(bits) => {
                         self.processed_bits = bits;
                         self.show_original = false;
-                        self.error_message = None;
+                        self.mark_cached_operations();
                         
                         // If we processed a large amount of data, defer the first render to show "preparing" message
                         if self.processed_bits.len() > 10_000_000 {
@@ -684,27 +1889,119 @@ impl BitApp {
                         }
                     }
                     Err(e) => {
-                        self.error_message = Some(e);
+                        self.notifications.error(e);
                     }
                 }
             }
         }
     }
+    
+    /// Cancel background operation processing
+    ///
+    /// The worker stops at the next chunk boundary; its result is discarded.
+    pub fn cancel_async_operations(&mut self) {
+        self.operation_cancel.cancel();
+        self.operation_receiver = None;
+        self.operation_progress = 0.0;
+        self.notifications.warning("Operation processing cancelled");
+    }
 
     pub fn sync_to_worksheet(&mut self) {
         let file_path = self.current_file_path.clone();
         let operations = self.operations.clone();
+        let view = WorksheetView {
+            view_mode: self.view_mode,
+            frame_length: self.viewer.frame_length,
+            bit_size: self.viewer.bit_size,
+            show_original: self.show_original,
+            scroll_offset: self.viewer.scroll_offset().into(),
+            super_frame_length: self.viewer.super_frame_length,
+        };
+        let byte_view = self.byte_viewer.config.clone();
         let worksheet = self.current_worksheet_mut();
         worksheet.file_path = file_path;
         worksheet.operations = operations;
+        worksheet.view = Some(view);
+        worksheet.byte_view = Some(byte_view);
+    }
+    
+    /// Use `width` bits as the frame in every view: bit view rows, byte view rows (rounded to
+    /// whole bytes) and the text view, whose rows follow the bit view
+    fn apply_frame_width(&mut self, width: usize) {
+        self.viewer.frame_length = width.max(1);
+        self.byte_viewer.set_bytes_per_row((width as f64 / 8.0).round() as usize);
+    }
+    
+    /// Mark super-frames of `frames` frames in the bit and byte views; 0 or 1 turns them off
+    pub fn set_super_frame_length(&mut self, frames: usize) {
+        self.viewer.super_frame_length = frames;
+        self.byte_viewer.super_frame_length = frames;
+    }
+    
+    /// Show the views at `width` without remembering it, to try out a candidate width
+    pub fn preview_frame_width(&mut self, width: usize) {
+        self.apply_frame_width(width);
+        self.update_viewer();
+    }
+    
+    /// Apply a frame width found by an analysis and remember it for this worksheet
+    pub fn lock_frame_width(&mut self, width: usize) {
+        self.apply_frame_width(width);
+        self.sync_to_worksheet();
+        self.settings.frame_length = width;
+        self.settings.auto_save();
+        self.update_viewer();
+    }
+    
+    /// Read every file in the current worksheet with `order` from now on, reloading them
+    pub fn set_bit_order(&mut self, order: BitOrder) {
+        if self.current_worksheet().bit_order == order {
+            return;
+        }
+        self.sync_to_worksheet();
+        self.current_worksheet_mut().bit_order = order;
+        if let Ok(mut cache) = self.operation_cache.try_lock() {
+            cache.clear();
+        }
+        self.clear_pattern_matches();
+        self.load_from_worksheet();
     }
     
     pub fn load_from_worksheet(&mut self) {
         let worksheet = self.current_worksheet().clone();
+        self.received_segments.clear();
+        // The soft file may have changed on disk
+        self.soft_confidence = None;
+        if let Some(view) = worksheet.view {
+            self.view_mode = view.view_mode;
+            self.apply_frame_width(view.frame_length);
+            self.set_super_frame_length(view.super_frame_length);
+            self.viewer.bit_size = view.bit_size;
+            self.show_original = view.show_original;
+            self.viewer.set_scroll_offset(egui::Vec2::from(view.scroll_offset));
+        }
+        // After the frame width, which would otherwise set the rows per byte
+        if let Some(config) = worksheet.byte_view.clone() {
+            self.byte_viewer.config = config;
+        }
+        self.reset_byte_view_history();
         
         // Load file if specified
         if let Some(path) = &worksheet.file_path {
             if path.exists() {
+                // Very large files are memory-mapped rather than loaded
+                if let Some(mapped) = self.map_if_large(path) {
+                    self.original_bits = BitVec::new();
+                    self.processed_bits = BitVec::new();
+                    self.mapped_original = Some(mapped);
+                    self.current_file_path = Some(path.clone());
+                    self.operations = worksheet.operations.clone();
+                    self.clear_pattern_matches();
+                    self.apply_operations();
+                    self.update_viewer();
+                    return;
+                }
+                
                 // Check file size to decide if we should use async loading
                 if let Ok(metadata) = std::fs::metadata(path) {
                     // Use async loading for files larger than 10MB
@@ -717,21 +2014,24 @@ impl BitApp {
                 }
                 
                 // For smaller files, load synchronously
-                match read_file_as_bits(path) {
+                match read_file_as_bits_in_order(path, worksheet.bit_order) {
                     Ok(bits) => {
                         self.original_bits = bits.clone();
                         self.processed_bits = bits;
+                        self.mapped_original = None;
+                        self.mapped_processed = None;
                         self.current_file_path = Some(path.clone());
-                        self.error_message = None;
                     }
                     Err(e) => {
-                        self.error_message = Some(format!("Failed to load file: {}", e));
+                        self.notifications.error(format!("Failed to load file: {}", e));
                     }
                 }
             }
         } else {
             self.original_bits = BitVec::new();
             self.processed_bits = BitVec::new();
+            self.mapped_original = None;
+            self.mapped_processed = None;
             self.current_file_path = None;
         }
         
@@ -750,6 +2050,24 @@ impl BitApp {
         }
     }
     
+    /// Move the worksheet at `from` so it ends up at `to`
+    pub fn move_worksheet(&mut self, from: usize, to: usize) {
+        if from >= self.worksheets.len() || to >= self.worksheets.len() || from == to {
+            return;
+        }
+        
+        // Multi-Worksheet Load refers to worksheets by id, so only positions need updating
+        let current_id = self.current_worksheet().id;
+        let renaming_id = self.renaming_worksheet.and_then(|idx| self.worksheets.get(idx)).map(|ws| ws.id);
+        
+        let worksheet = self.worksheets.remove(from);
+        self.worksheets.insert(to, worksheet);
+        
+        let position = |id: u64| self.worksheets.iter().position(|ws| ws.id == id);
+        self.current_worksheet_index = position(current_id).unwrap_or(0);
+        self.renaming_worksheet = renaming_id.and_then(position);
+    }
+    
     pub fn save_worksheet_to_file(&mut self) {
         self.sync_to_worksheet();
         
@@ -758,7 +2076,7 @@ impl BitApp {
             .save_file()
         {
             if let Err(e) = self.current_worksheet().save_to_file(&path) {
-                self.error_message = Some(e);
+                self.notifications.error(e);
             }
         }
     }
@@ -769,14 +2087,19 @@ impl BitApp {
             .pick_file()
         {
             match Worksheet::load_from_file(&path) {
-                Ok(worksheet) => {
+                Ok(mut worksheet) => {
+                    self.sync_to_worksheet();
+                    // Opening the same file twice mustn't give two worksheets the same id
+                    if self.worksheets.iter().any(|ws| ws.id == worksheet.id) {
+                        worksheet.id = new_worksheet_id();
+                    }
                     self.worksheets.push(worksheet);
                     self.current_worksheet_index = self.worksheets.len() - 1;
                     self.load_from_worksheet();
-                    self.error_message = None;
+                    self.missing_files = missing_files(&mut self.worksheets);
                 }
                 Err(e) => {
-                    self.error_message = Some(e);
+                    self.notifications.error(e);
                 }
             }
         }
@@ -784,37 +2107,226 @@ impl BitApp {
     
     pub fn save_file(&mut self) {
         if let Some(path) = rfd::FileDialog::new().save_file() {
-            let bits_to_save = if self.show_original {
-                &self.original_bits
+            let (bits_to_save, mapped) = if self.show_original {
+                (&self.original_bits, &self.mapped_original)
             } else {
-                &self.processed_bits
+                (&self.processed_bits, &self.mapped_processed)
             };
-
-            match write_bits_to_file(&path, bits_to_save) {
-                Ok(_) => {
-                    self.error_message = None;
-                }
+            
+            // Mapped files are streamed out of the mapping rather than materialized
+            let bits = match mapped {
+                Some(mapped) => TaskBits::Mapped(mapped.clone(), 0..mapped.bits().len()),
+                None => TaskBits::Copied(bits_to_save.clone()),
+            };
+            
+            // Large exports are written on a background thread so the UI stays responsive
+            if bits.bits().len() as u64 > ASYNC_THRESHOLD_BYTES * 8 {
+                let file_name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+                self.save_task = Some(BackgroundTask::spawn("Saving File", move |progress| {
+                    progress.report(0.0, format!("Saving: {}", file_name));
+                    write_bits_to_file_with_progress(&path, bits.bits(), progress)
+                        .map_err(|e| e.to_string())
+                }));
+                return;
+            }
+            
+            match write_bits_to_file_with_progress(&path, bits.bits(), &ProgressReporter::new()) {
+                Ok(_) => {}
                 Err(e) => {
-                    self.error_message = Some(format!("Failed to save file: {}", e));
+                    self.notifications.error(format!("Failed to save file: {}", e));
                 }
             }
         }
     }
     
+    /// Report the outcome of a background save once it has finished
+    pub fn poll_save_task(&mut self) {
+        let Some(result) = self.save_task.as_ref().and_then(|task| task.try_finish()) else {
+            return;
+        };
+        self.save_task = None;
+        match result.and_then(|written| written) {
+            Ok(()) => {}
+            Err(e) => self.notifications.error(format!("Failed to save file: {}", e)),
+        }
+    }
+    
+    /// Stop the background save; the partial file is removed
+    pub fn cancel_save(&mut self) {
+        if let Some(task) = self.save_task.take() {
+            task.cancel();
+        }
+    }
+    
     pub fn update_viewer(&mut self) {
+        // Every view marks burst gaps, so they are followed through the chain first
+        self.update_gap_markers();
         // Only update the bit viewer if we're in bit view mode
         // This prevents freezing when in Byte or ASCII view with large files
         if self.view_mode != ViewMode::Bit {
             return;
         }
         
-        let bits_to_show = if self.show_original {
-            &self.original_bits
+        let (bits_to_show, mapped) = if self.show_original {
+            (&self.original_bits, &self.mapped_original)
+        } else {
+            (&self.processed_bits, &self.mapped_processed)
+        };
+        
+        match mapped {
+            Some(mapped) => self.viewer.set_mapped(mapped.clone()),
+            None => self.viewer.set_bits(bits_to_show.clone()),
+        }
+        self.update_violation_markers();
+        self.update_soft_confidence();
+    }
+    
+    /// Show how sure each bit is while a soft-bit load is the only operation; anything after
+    /// it moves bits around, so the confidences would no longer line up
+    pub fn update_soft_confidence(&mut self) {
+        let enabled: Vec<&BitOperation> = self.operations.iter().filter(|op| op.is_enabled()).collect();
+        let source = match enabled.as_slice() {
+            [BitOperation::LoadSoftBits { file_path, format, combine, .. }] if !self.show_original => {
+                Some((file_path.clone(), *format, *combine))
+            }
+            _ => None,
+        };
+        let Some(source) = source else {
+            self.viewer.set_confidence(None);
+            return;
+        };
+        
+        let cached = self.soft_confidence.as_ref().filter(|(cached, _)| *cached == source).map(|(_, confidence)| confidence.clone());
+        let confidence = match cached {
+            Some(confidence) => confidence,
+            None => match read_soft_file(&source.0, source.1, source.2.as_ref()) {
+                Ok(soft) => {
+                    let confidence = Arc::new(soft.confidence);
+                    self.soft_confidence = Some((source, confidence.clone()));
+                    confidence
+                }
+                Err(_) => {
+                    self.viewer.set_confidence(None);
+                    return;
+                }
+            },
+        };
+        let lines_up = confidence.len() == self.processed_view().len();
+        self.viewer.set_confidence(lines_up.then_some(confidence));
+    }
+    
+    /// Mark burst boundaries of received data in the bit, byte and ASCII views, following
+    /// them through the enabled operations. When one of them moves bits in a way that can't
+    /// be followed, nothing is marked and `gap_markers_note` says why.
+    pub fn update_gap_markers(&mut self) {
+        let starts = burst_starts(&self.received_segments, std::time::Duration::from_millis(self.gap_threshold_ms));
+        let positions: Vec<usize> = starts.iter().map(|&(bit, _)| bit).collect();
+        self.gap_markers_note = None;
+        let tracked = if self.show_original {
+            positions.into_iter().map(Some).collect()
+        } else {
+            match track_through_chain(&self.operations, &positions, self.original_view().len()) {
+                Ok((tracked, _)) => tracked,
+                Err(op) => {
+                    self.gap_markers_note = Some(format!("\"{}\" {}", op.name(), tr("rearranges the bits, so gaps are only marked in the original view")));
+                    Vec::new()
+                }
+            }
+        };
+        let mut markers: Vec<(usize, f64)> = tracked
+            .into_iter()
+            .zip(&starts)
+            .filter_map(|(bit, (_, gap))| bit.map(|bit| (bit, gap.as_secs_f64())))
+            .collect();
+        // Reversed ranges turn the order around
+        markers.sort_by_key(|&(bit, _)| bit);
+        self.viewer.gaps.starts = markers;
+        self.byte_viewer.gaps = self.viewer.gaps.clone();
+    }
+    
+    /// Mark code violations found by a line code decode at the end of the chain. Its input
+    /// is taken from the operation cache, so nothing is marked when that step isn't cached.
+    pub fn update_violation_markers(&mut self) {
+        self.viewer.violation_markers.clear();
+        if self.show_original || self.operations.iter().any(|op| op.is_enabled() && op.is_source()) {
+            return;
+        }
+        let Some((last, code)) = self.operations.iter().enumerate().rev().find(|(_, op)| op.is_enabled()).and_then(|(index, op)| match op {
+            BitOperation::LineDecode { code, .. } => Some((index, *code)),
+            _ => None,
+        }) else {
+            return;
+        };
+        
+        let earlier = &self.operations[..last];
+        let input = if earlier.iter().any(|op| op.is_enabled()) {
+            match self.operation_cache.try_lock().ok().and_then(|cache| cache.cached_output(&self.original_bits, earlier)) {
+                Some(bits) => bits,
+                None => return,
+            }
         } else {
-            &self.processed_bits
+            Arc::new(self.original_bits.clone())
+        };
+        let (_, violations) = decode_line_code(code, &input);
+        self.viewer.violation_markers = violations
+            .into_iter()
+            .map(|violation| (violation.bit, violation.kind.name()))
+            .collect();
+    }
+    
+    /// Scroll every view to the offset typed in the "Go to" box and flash it
+    pub fn go_to_offset(&mut self, now: f64) {
+        use crate::utils::eval_expression;
+        
+        let offset = match eval_expression(&self.goto_input) {
+            Ok(offset) => offset,
+            Err(e) => {
+                self.notifications.error(format!("Invalid offset: {}", e));
+                return;
+            }
         };
         
-        self.viewer.set_bits(bits_to_show.clone());
+        let (bit_pos, len) = if self.goto_in_bytes { (offset * 8, 8) } else { (offset, 1) };
+        let total_bits = self.display_bits().len();
+        if bit_pos >= total_bits {
+            self.notifications.error(format!("Offset {} is past the end of the data ({} bits)", offset, total_bits));
+            return;
+        }
+        
+        self.goto_input = offset.to_string();
+        self.jump_to_range(bit_pos, len, now);
+    }
+    
+    /// Scroll every view to `bit_pos` and flash the `len` bits starting there
+    pub fn jump_to_range(&mut self, bit_pos: usize, len: usize, now: f64) {
+        self.viewer.jump_to_position(bit_pos);
+        self.viewer.flash_range(bit_pos, len, now);
+        self.byte_viewer.jump_to_bit = Some(bit_pos);
+        self.byte_viewer.flash = self.viewer.flash;
+        self.ascii_jump_to_bit = Some(bit_pos);
+        self.symbol_viewer.jump_to_bit = Some(bit_pos);
+        self.symbol_viewer.flash = self.viewer.flash;
+    }
+    
+    /// Open the column editor for the column at `index`, or for a new column after the last one
+    pub fn open_column_editor(&mut self, index: Option<usize>) {
+        let existing = index.and_then(|index| self.byte_viewer.config.columns.get(index));
+        if let Some(column) = existing {
+            self.column_editor_label = column.label.clone();
+            self.column_editor_bit_start = column.bit_start.to_string();
+            self.column_editor_bit_end = column.bit_end.to_string();
+            self.column_editor_color = column.color;
+            self.column_editor_timestamp = column.timestamp;
+            self.column_editor_index = index;
+        } else {
+            let start = self.byte_viewer.config.columns.last().map_or(0, |column| column.bit_end + 1);
+            self.column_editor_label.clear();
+            self.column_editor_bit_start = start.to_string();
+            self.column_editor_bit_end = (start + 7).to_string();
+            self.column_editor_timestamp = None;
+            self.column_editor_index = None;
+        }
+        self.show_column_editor = true;
     }
     
     pub fn open_operation_creator(&mut self, op_type: OperationType) {
@@ -830,6 +2342,10 @@ impl BitApp {
         self.truncate_name.clear();
         self.truncate_start = String::from("0");
         self.truncate_end.clear();
+        self.reset_insert_delete_editors();
+        self.script_name.clear();
+        self.script_source = String::from(DEFAULT_SCRIPT);
+        self.script_time_limit_ms = DEFAULT_SCRIPT_TIME_LIMIT_MS;
         self.interleave_name.clear();
         self.interleave_type = crate::processing::InterleaverType::Block;
         self.interleave_direction = crate::processing::InterleaverDirection::Interleave;
@@ -841,295 +2357,966 @@ impl BitApp {
         self.multiworksheet_name.clear();
         self.multiworksheet_ops.clear();
         self.multiworksheet_input.clear();
+        self.macro_name.clear();
+        self.macro_operations.clear();
+        self.patch_name.clear();
+        self.patch_list.clear();
     }
 
+    fn reset_insert_delete_editors(&mut self) {
+        self.loadfile_offset = String::from("0");
+        self.loadfile_length.clear();
+        self.multifile_name.clear();
+        self.multifile_paths.clear();
+        self.multifile_glob.clear();
+        self.softload_name.clear();
+        self.softload_path = None;
+        self.softload_format = SoftFormat::SignedByte;
+        self.softload_combine = false;
+        self.insert_name.clear();
+        self.insert_position = String::from("0");
+        self.insert_count = String::from("1");
+        self.insert_fill = String::from("0");
+        self.delete_name.clear();
+        self.delete_unstuff = false;
+        self.delete_position = String::from("0");
+        self.delete_count = String::from("1");
+        self.delete_run_length = String::from("5");
+        self.range_name.clear();
+        self.range_action = RangeAction::Invert;
+        self.range_start = String::from("0");
+        self.range_end.clear();
+        self.editing_group_child = None;
+        self.xor_name.clear();
+        self.xor_key.clear();
+        self.line_code_name.clear();
+        self.line_code = LineCode::EightBTenB;
+        self.symbol_code_name.clear();
+        self.symbol_code = SymbolCode::GrayToBinary;
+        self.symbol_code_bits = "8".to_string();
+        self.remap_name.clear();
+        self.remap_input_bits = "4".to_string();
+        self.remap_output_bits = "4".to_string();
+        self.remap_table.clear();
+        self.majority_name.clear();
+        self.majority_frames = false;
+        self.majority_repeats = "3".to_string();
+        self.majority_frame_length = self.viewer.frame_length.to_string();
+        self.majority_block_frames = "1".to_string();
+        self.majority_tie_break = TieBreak::First;
+        self.nand_name.clear();
+        self.nand_page_size = "2048".to_string();
+        self.nand_spare_size = "64".to_string();
+        self.nand_interleaved = false;
+        self.nand_steps = "4".to_string();
+        self.nand_keep = NandArea::Data;
+        self.nand_ecc = NandEcc::None;
+        self.nand_ecc_offset = "0".to_string();
+        self.nand_correct = true;
+        self.nand_report = None;
+        self.group_name.clear();
+        self.group_operations.clear();
+        self.group_collapsed = false;
+        self.conditional_name.clear();
+        self.conditional_operation = None;
+        self.conditional_by_ranges = false;
+        self.conditional_frame_length = String::from("64");
+        self.conditional_every = String::from("2");
+        self.conditional_offset = String::from("0");
+        self.conditional_ranges.clear();
+        self.variants_name.clear();
+        self.variants_a = None;
+        self.variants_b = None;
+        self.variants_use_b = false;
+        self.variant_comparison = None;
+    }
+    
     pub fn open_operation_editor(&mut self, index: usize) {
-        if let Some(op) = self.operations.get(index) {
-            match op {
-                BitOperation::LoadFile { name, file_path, .. } => {
-                    self.show_operation_menu = Some(OperationType::LoadFile);
-                    self.editing_operation_index = Some(index);
-                    self.loadfile_name = name.clone();
-                    self.loadfile_path = Some(file_path.clone());
-                }
-                BitOperation::TakeSkipSequence { name, sequence, .. } => {
-                    self.show_operation_menu = Some(OperationType::TakeSkipSequence);
-                    self.editing_operation_index = Some(index);
-                    self.takeskip_name = name.clone();
-                    self.takeskip_input = sequence.to_string();
-                }
-                BitOperation::InvertBits { name, .. } => {
-                    self.show_operation_menu = Some(OperationType::InvertBits);
-                    self.editing_operation_index = Some(index);
-                    self.invert_name = name.clone();
-                }
-                BitOperation::TruncateBits { name, start, end, .. } => {
-                    self.show_operation_menu = Some(OperationType::TruncateBits);
-                    self.editing_operation_index = Some(index);
-                    self.truncate_name = name.clone();
-                    self.truncate_start = start.to_string();
-                    self.truncate_end = end.to_string();
+        if let Some(op) = self.operations.get(index).cloned() {
+            self.editing_group_child = None;
+            self.open_editor_for(&op, index);
+        }
+    }
+    
+    /// Edit child `child` of the group operation at `group`
+    pub fn open_group_child_editor(&mut self, group: usize, child: usize) {
+        if let Some(BitOperation::Group { operations, .. }) = self.operations.get(group)
+            && let Some(op) = operations.get(child).cloned()
+        {
+            self.open_editor_for(&op, group);
+            self.editing_group_child = Some(child);
+        }
+    }
+    
+    /// Edit version A (`slot` 0) or B (1) of the Variants operation at `index`
+    pub fn open_variant_editor(&mut self, index: usize, slot: usize) {
+        if let Some(BitOperation::Variants { a, b, .. }) = self.operations.get(index).cloned() {
+            let op = if slot == 0 { a } else { b };
+            self.open_editor_for(&op, index);
+            self.editing_group_child = Some(slot);
+        }
+    }
+    
+    fn open_editor_for(&mut self, op: &BitOperation, index: usize) {
+        match op {
+            BitOperation::Variants { name, a, b, use_b, .. } => {
+                self.show_operation_menu = Some(OperationType::Variants);
+                self.editing_operation_index = Some(index);
+                self.variants_name = name.clone();
+                self.variants_a = Some((**a).clone());
+                self.variants_b = Some((**b).clone());
+                self.variants_use_b = *use_b;
+                self.variant_comparison = None;
+            }
+            BitOperation::LoadFile { name, file_path, offset, length, .. } => {
+                self.show_operation_menu = Some(OperationType::LoadFile);
+                self.editing_operation_index = Some(index);
+                self.loadfile_name = name.clone();
+                self.loadfile_path = Some(file_path.clone());
+                self.loadfile_offset = offset.to_string();
+                self.loadfile_length = length.map(|length| length.to_string()).unwrap_or_default();
+            }
+            BitOperation::MultiFileLoad { name, file_paths, .. } => {
+                self.show_operation_menu = Some(OperationType::MultiFileLoad);
+                self.editing_operation_index = Some(index);
+                self.multifile_name = name.clone();
+                self.multifile_paths = file_paths.clone();
+            }
+            BitOperation::LoadSoftBits { name, file_path, format, combine, .. } => {
+                self.show_operation_menu = Some(OperationType::LoadSoftBits);
+                self.editing_operation_index = Some(index);
+                self.softload_name = name.clone();
+                self.softload_path = Some(file_path.clone());
+                self.softload_format = *format;
+                self.softload_combine = combine.is_some();
+                if let Some(vote) = combine {
+                    self.set_majority_fields(vote);
                 }
-                BitOperation::InterleaveBits { name, interleaver_type, block_config, convolutional_config, symbol_config, .. } => {
-                    self.show_operation_menu = Some(OperationType::InterleaveBits);
-                    self.editing_operation_index = Some(index);
-                    self.interleave_name = name.clone();
-                    self.interleave_type = *interleaver_type;
-                    
-                    match interleaver_type {
-                        crate::processing::InterleaverType::Block => {
-                            if let Some(cfg) = block_config {
-                                self.interleave_direction = cfg.direction;
-                                self.interleave_block_size = cfg.block_size.to_string();
-                                self.interleave_depth = cfg.depth.to_string();
-                            }
+            }
+            BitOperation::TakeSkipSequence { name, sequence, .. } => {
+                self.show_operation_menu = Some(OperationType::TakeSkipSequence);
+                self.editing_operation_index = Some(index);
+                self.takeskip_name = name.clone();
+                self.takeskip_input = sequence.to_string();
+            }
+            BitOperation::InvertBits { name, .. } => {
+                self.show_operation_menu = Some(OperationType::InvertBits);
+                self.editing_operation_index = Some(index);
+                self.invert_name = name.clone();
+            }
+            BitOperation::TruncateBits { name, start, end, exprs, .. } => {
+                self.show_operation_menu = Some(OperationType::TruncateBits);
+                self.editing_operation_index = Some(index);
+                self.truncate_name = name.clone();
+                self.truncate_start = exprs.display("start", *start);
+                self.truncate_end = exprs.display("end", *end);
+            }
+            BitOperation::InterleaveBits { name, interleaver_type, block_config, convolutional_config, symbol_config, .. } => {
+                self.show_operation_menu = Some(OperationType::InterleaveBits);
+                self.editing_operation_index = Some(index);
+                self.interleave_name = name.clone();
+                self.interleave_type = *interleaver_type;
+                
+                match interleaver_type {
+                    crate::processing::InterleaverType::Block => {
+                        if let Some(cfg) = block_config {
+                            self.interleave_direction = cfg.direction;
+                            self.interleave_block_size = cfg.block_size.to_string();
+                            self.interleave_depth = cfg.depth.to_string();
                         }
-                        crate::processing::InterleaverType::Convolutional => {
-                            if let Some(cfg) = convolutional_config {
-                                self.interleave_direction = cfg.direction;
-                                self.interleave_branches = cfg.branches.to_string();
-                                self.interleave_delay_increment = cfg.delay_increment.to_string();
-                            }
+                    }
+                    crate::processing::InterleaverType::Convolutional => {
+                        if let Some(cfg) = convolutional_config {
+                            self.interleave_direction = cfg.direction;
+                            self.interleave_branches = cfg.branches.to_string();
+                            self.interleave_delay_increment = cfg.delay_increment.to_string();
                         }
-                        crate::processing::InterleaverType::Symbol => {
-                            if let Some(cfg) = symbol_config {
-                                self.interleave_direction = cfg.direction;
-                                self.interleave_symbol_size = cfg.symbol_size.to_string();
-                                self.interleave_block_size = cfg.block_size.to_string();
-                                self.interleave_depth = cfg.depth.to_string();
-                            }
+                    }
+                    crate::processing::InterleaverType::Symbol => {
+                        if let Some(cfg) = symbol_config {
+                            self.interleave_direction = cfg.direction;
+                            self.interleave_symbol_size = cfg.symbol_size.to_string();
+                            self.interleave_block_size = cfg.block_size.to_string();
+                            self.interleave_depth = cfg.depth.to_string();
                         }
                     }
                 }
-                BitOperation::MultiWorksheetLoad { name, worksheet_operations, .. } => {
-                    self.show_operation_menu = Some(OperationType::MultiWorksheetLoad);
-                    self.editing_operation_index = Some(index);
-                    self.multiworksheet_name = name.clone();
-                    self.multiworksheet_ops = worksheet_operations
-                        .iter()
-                        .map(|wo| (wo.worksheet_index, wo.sequence.to_string()))
-                        .collect();
+            }
+            BitOperation::InsertBits { name, position, count, fill, exprs, .. } => {
+                self.show_operation_menu = Some(OperationType::InsertBits);
+                self.editing_operation_index = Some(index);
+                self.insert_name = name.clone();
+                self.insert_position = exprs.display("position", *position);
+                self.insert_count = exprs.display("count", *count);
+                self.insert_fill = fill.clone();
+            }
+            BitOperation::DeleteBits { name, mode, exprs, .. } => {
+                self.show_operation_menu = Some(OperationType::DeleteBits);
+                self.editing_operation_index = Some(index);
+                self.delete_name = name.clone();
+                match *mode {
+                    DeleteMode::Range { position, count } => {
+                        self.delete_unstuff = false;
+                        self.delete_position = exprs.display("position", position);
+                        self.delete_count = exprs.display("count", count);
+                    }
+                    DeleteMode::Unstuff { run_length } => {
+                        self.delete_unstuff = true;
+                        self.delete_run_length = run_length.to_string();
+                    }
+                }
+            }
+            BitOperation::RangeBits { name, action, start, end, exprs, .. } => {
+                self.show_operation_menu = Some(OperationType::RangeBits);
+                self.editing_operation_index = Some(index);
+                self.range_name = name.clone();
+                self.range_action = *action;
+                self.range_start = exprs.display("start", *start);
+                self.range_end = if *end == usize::MAX && !exprs.exprs.contains_key("end") { String::new() } else { exprs.display("end", *end) };
+            }
+            BitOperation::XorKey { name, key, .. } => {
+                self.show_operation_menu = Some(OperationType::XorKey);
+                self.editing_operation_index = Some(index);
+                self.xor_name = name.clone();
+                self.xor_key = key.iter().map(|byte| format!("{:02X}", byte)).collect();
+            }
+            BitOperation::LineDecode { name, code, .. } => {
+                self.show_operation_menu = Some(OperationType::LineDecode);
+                self.editing_operation_index = Some(index);
+                self.line_code_name = name.clone();
+                self.line_code = *code;
+            }
+            BitOperation::SymbolConvert { name, code, symbol_bits, .. } => {
+                self.show_operation_menu = Some(OperationType::SymbolConvert);
+                self.editing_operation_index = Some(index);
+                self.symbol_code_name = name.clone();
+                self.symbol_code = *code;
+                self.symbol_code_bits = symbol_bits.to_string();
+            }
+            BitOperation::SymbolRemap { name, map, .. } => {
+                self.show_operation_menu = Some(OperationType::SymbolRemap);
+                self.editing_operation_index = Some(index);
+                self.remap_name = name.clone();
+                self.remap_input_bits = map.input_bits.to_string();
+                self.remap_output_bits = map.output_bits.to_string();
+                self.remap_table = map.table_text();
+            }
+            BitOperation::MajorityVote { name, vote, .. } => {
+                self.show_operation_menu = Some(OperationType::MajorityVote);
+                self.editing_operation_index = Some(index);
+                self.majority_name = name.clone();
+                self.set_majority_fields(vote);
+            }
+            BitOperation::NandLayout { name, layout, .. } => {
+                self.show_operation_menu = Some(OperationType::NandLayout);
+                self.editing_operation_index = Some(index);
+                self.nand_name = name.clone();
+                self.nand_page_size = layout.page_size.to_string();
+                self.nand_spare_size = layout.spare_size.to_string();
+                if let SpareLayout::Interleaved { steps } = layout.spare_layout {
+                    self.nand_interleaved = true;
+                    self.nand_steps = steps.to_string();
+                } else {
+                    self.nand_interleaved = false;
+                }
+                self.nand_keep = layout.keep;
+                self.nand_ecc = layout.ecc;
+                self.nand_ecc_offset = layout.ecc_offset.to_string();
+                self.nand_correct = layout.correct;
+                self.nand_report = None;
+            }
+            BitOperation::Conditional { name, operation, condition, .. } => {
+                self.show_operation_menu = Some(OperationType::Conditional);
+                self.editing_operation_index = Some(index);
+                self.conditional_name = name.clone();
+                self.conditional_operation = Some((**operation).clone());
+                match condition {
+                    BitCondition::EveryNthFrame { frame_length, every, offset } => {
+                        self.conditional_by_ranges = false;
+                        self.conditional_frame_length = frame_length.to_string();
+                        self.conditional_every = every.to_string();
+                        self.conditional_offset = offset.to_string();
+                    }
+                    BitCondition::Ranges { ranges } => {
+                        self.conditional_by_ranges = true;
+                        self.conditional_ranges = ranges
+                            .iter()
+                            .map(|(start, end)| format!("{},{}\n", start, end))
+                            .collect();
+                    }
                 }
             }
+            BitOperation::Script { name, source, time_limit_ms, .. } => {
+                self.show_operation_menu = Some(OperationType::Script);
+                self.editing_operation_index = Some(index);
+                self.script_name = name.clone();
+                self.script_source = source.clone();
+                self.script_time_limit_ms = *time_limit_ms;
+            }
+            BitOperation::PatchBits { name, patches, .. } => {
+                self.show_operation_menu = Some(OperationType::PatchBits);
+                self.editing_operation_index = Some(index);
+                self.patch_name = name.clone();
+                self.patch_list = patches.clone();
+            }
+            BitOperation::Macro { name, operations, .. } => {
+                self.show_operation_menu = Some(OperationType::Macro);
+                self.editing_operation_index = Some(index);
+                self.macro_name = name.clone();
+                self.macro_operations = operations.clone();
+            }
+            BitOperation::Group { name, operations, collapsed, .. } => {
+                self.show_operation_menu = Some(OperationType::Group);
+                self.editing_operation_index = Some(index);
+                self.group_name = name.clone();
+                self.group_operations = operations.clone();
+                self.group_collapsed = *collapsed;
+            }
+            BitOperation::MultiWorksheetLoad { name, worksheet_operations, .. } => {
+                self.show_operation_menu = Some(OperationType::MultiWorksheetLoad);
+                self.editing_operation_index = Some(index);
+                self.multiworksheet_name = name.clone();
+                self.multiworksheet_ops = worksheet_operations
+                    .iter()
+                    .map(|wo| {
+                        let id = find_worksheet(&self.worksheets, wo)
+                            .map_or(wo.worksheet_id, |idx| self.worksheets[idx].id);
+                        (id, wo.sequence.to_string())
+                    })
+                    .collect();
+            }
         }
     }
 
-    pub fn save_current_operation(&mut self) {
-        if let Some(op_type) = self.show_operation_menu {
-            let new_operation = match op_type {
-                OperationType::LoadFile => {
-                    if self.loadfile_path.is_none() {
-                        self.error_message = Some("Please select a file to load".to_string());
-                        return;
-                    }
-                    
-                    let file_path = self.loadfile_path.clone().unwrap();
-                    let name = if self.loadfile_name.trim().is_empty() {
-                        format!("Load: {}", file_path.file_name().unwrap_or_default().to_string_lossy())
-                    } else {
-                        self.loadfile_name.clone()
-                    };
-                    
-                    BitOperation::LoadFile {
-                        name,
-                        file_path,
-                        enabled: true,
+    /// Append the files matching the Multi-File Load editor's glob, in natural order,
+    /// skipping files already listed
+    pub fn add_multifile_glob(&mut self) {
+        let pattern = self.multifile_glob.trim();
+        if pattern.is_empty() {
+            return;
+        }
+        match expand_glob(std::path::Path::new(pattern)) {
+            Ok(files) if files.is_empty() => {
+                self.notifications.error(format!("No files match {}", pattern));
+            }
+            Ok(files) => {
+                for file in files {
+                    if !self.multifile_paths.contains(&file) {
+                        self.multifile_paths.push(file);
                     }
                 }
-                OperationType::TakeSkipSequence => {
-                    if self.takeskip_input.is_empty() {
-                        self.error_message = Some("Operation sequence cannot be empty".to_string());
-                        return;
-                    }
-                    
-                    match OperationSequence::from_string(&self.takeskip_input) {
-                        Ok(seq) => {
-                            let name = if self.takeskip_name.trim().is_empty() {
-                                format!("Sequence: {}", self.takeskip_input)
-                            } else {
-                                self.takeskip_name.clone()
-                            };
-                            
-                            BitOperation::TakeSkipSequence {
-                                name,
-                                sequence: seq,
-                                enabled: true,
-                            }
-                        }
-                        Err(e) => {
-                            self.error_message = Some(format!("Invalid operation: {}", e));
-                            return;
-                        }
-                    }
+            }
+            Err(e) => self.notifications.error(e),
+        }
+    }
+    
+    fn set_majority_fields(&mut self, vote: &MajorityVote) {
+        self.majority_tie_break = vote.tie_break;
+        match vote.grouping {
+            VoteGrouping::Bits { repeats } => {
+                self.majority_frames = false;
+                self.majority_repeats = repeats.to_string();
+            }
+            VoteGrouping::Frames { frame_length, repeats, block_frames } => {
+                self.majority_frames = true;
+                self.majority_repeats = repeats.to_string();
+                self.majority_frame_length = frame_length.to_string();
+                self.majority_block_frames = block_frames.to_string();
+            }
+        }
+    }
+    
+    /// The vote described by the Majority Vote fields
+    fn majority_from_fields(&self) -> Result<MajorityVote, String> {
+        use crate::utils::eval_expression;
+        
+        let repeats = eval_expression(&self.majority_repeats).map_err(|e| format!("Invalid repeats: {}", e))?;
+        let grouping = if self.majority_frames {
+            let frame_length = eval_expression(&self.majority_frame_length).map_err(|e| format!("Invalid frame length: {}", e))?;
+            let block_frames = eval_expression(&self.majority_block_frames).map_err(|e| format!("Invalid frames per block: {}", e))?;
+            VoteGrouping::Frames { frame_length, repeats, block_frames }
+        } else {
+            VoteGrouping::Bits { repeats }
+        };
+        let vote = MajorityVote { grouping, tie_break: self.majority_tie_break };
+        vote.validate()?;
+        Ok(vote)
+    }
+    
+    /// The layout described by the NAND Page Layout fields
+    fn nand_layout_from_fields(&self) -> Result<NandLayout, String> {
+        use crate::utils::eval_expression;
+        
+        let page_size = eval_expression(&self.nand_page_size).map_err(|e| format!("Invalid page size: {}", e))?;
+        let spare_size = eval_expression(&self.nand_spare_size).map_err(|e| format!("Invalid spare size: {}", e))?;
+        let spare_layout = if self.nand_interleaved {
+            let steps = eval_expression(&self.nand_steps).map_err(|e| format!("Invalid steps: {}", e))?;
+            SpareLayout::Interleaved { steps }
+        } else {
+            SpareLayout::Adjacent
+        };
+        let ecc_offset = eval_expression(&self.nand_ecc_offset).map_err(|e| format!("Invalid ECC offset: {}", e))?;
+        let layout = NandLayout {
+            page_size,
+            spare_size,
+            spare_layout,
+            keep: self.nand_keep,
+            ecc: self.nand_ecc,
+            ecc_offset,
+            correct: self.nand_correct,
+        };
+        layout.validate()?;
+        Ok(layout)
+    }
+    
+    /// Check the ECC of the data the NAND layout being edited would receive, keeping the
+    /// result in `nand_report`
+    pub fn check_nand_ecc(&mut self) {
+        let result = self.nand_layout_from_fields().and_then(|layout| {
+            let mut input = self.original_view().to_bitvec();
+            for op in self.preceding_operations()?.iter().filter(|op| op.is_enabled()) {
+                op.apply_in_place(&mut input);
+            }
+            layout.check(&input)
+        });
+        match result {
+            Ok(report) => self.nand_report = Some(report),
+            Err(e) => {
+                self.nand_report = None;
+                self.notifications.error(e);
+            }
+        }
+    }
+    
+    /// Expressions for an operation's parameters, using the bit view's frame length for `frame_len`
+    fn new_param_expressions(&self) -> ParamExpressions {
+        ParamExpressions { frame_len: self.viewer.frame_length, ..Default::default() }
+    }
+    
+    /// The value of an editor field that may use `len`, `frames` and `frame_len`, for the data
+    /// shown now. Unless it's a plain number it's kept in `exprs`, to be evaluated again for
+    /// each input.
+    fn eval_param(&self, input: &str, param: &str, exprs: &mut ParamExpressions) -> Result<usize, String> {
+        use crate::utils::eval_expression_with;
+        
+        let vars = ParamExpressions::variables(self.processed_view().len(), exprs.frame_len);
+        let value = eval_expression_with(input, &vars)?;
+        if input.trim().parse::<usize>().is_err() {
+            exprs.exprs.insert(param.to_string(), input.trim().to_string());
+        }
+        Ok(value)
+    }
+    
+    /// Build the operation described by the open editor's fields
+    fn build_current_operation(&self, op_type: OperationType) -> Result<BitOperation, String> {
+        use crate::utils::eval_expression;
+        
+        let operation = match op_type {
+            OperationType::LoadFile => {
+                if self.loadfile_path.is_none() {
+                    return Err("Please select a file to load".to_string());
                 }
-                OperationType::InvertBits => {
-                    let name = if self.invert_name.trim().is_empty() {
-                        "Invert All Bits".to_string()
-                    } else {
-                        self.invert_name.clone()
-                    };
-                    
-                    BitOperation::InvertBits { name, enabled: true }
+                
+                let file_path = self.loadfile_path.clone().unwrap();
+                let name = if self.loadfile_name.trim().is_empty() {
+                    format!("Load: {}", file_path.file_name().unwrap_or_default().to_string_lossy())
+                } else {
+                    self.loadfile_name.clone()
+                };
+                
+                let offset = eval_expression(&self.loadfile_offset).map_err(|e| format!("Invalid offset: {}", e))? as u64;
+                let length = match self.loadfile_length.trim() {
+                    "" => None,
+                    length => Some(eval_expression(length).map_err(|e| format!("Invalid length: {}", e))? as u64),
+                };
+                
+                BitOperation::LoadFile {
+                    name,
+                    file_path,
+                    offset,
+                    length,
+                    enabled: true,
                 }
-                OperationType::TruncateBits => {
-                    // Parse start and end
-                    let start = self.truncate_start.trim().parse::<usize>().unwrap_or(0);
-                    let end = if self.truncate_end.trim().is_empty() {
-                        // If no end specified, use a very large number (essentially to the end)
-                        usize::MAX
-                    } else {
-                        match self.truncate_end.trim().parse::<usize>() {
-                            Ok(val) => val,
-                            Err(_) => {
-                                self.error_message = Some("Invalid end value".to_string());
-                                return;
-                            }
+            }
+            OperationType::MultiFileLoad => {
+                if self.multifile_paths.is_empty() {
+                    return Err("Please add at least one file to load".to_string());
+                }
+                
+                let name = if self.multifile_name.trim().is_empty() {
+                    format!("Load {} files", self.multifile_paths.len())
+                } else {
+                    self.multifile_name.clone()
+                };
+                
+                BitOperation::MultiFileLoad {
+                    name,
+                    file_paths: self.multifile_paths.clone(),
+                    enabled: true,
+                }
+            }
+            OperationType::LoadSoftBits => {
+                let file_path = self.softload_path.clone().ok_or("Please select a file to load")?;
+                let combine = if self.softload_combine { Some(self.majority_from_fields()?) } else { None };
+                
+                let name = if self.softload_name.trim().is_empty() {
+                    format!("Soft bits: {}", file_path.file_name().unwrap_or_default().to_string_lossy())
+                } else {
+                    self.softload_name.clone()
+                };
+                
+                BitOperation::LoadSoftBits {
+                    name,
+                    file_path,
+                    format: self.softload_format,
+                    combine,
+                    enabled: true,
+                }
+            }
+            OperationType::TakeSkipSequence => {
+                if self.takeskip_input.is_empty() {
+                    return Err("Operation sequence cannot be empty".to_string());
+                }
+                
+                match OperationSequence::from_string(&self.takeskip_input) {
+                    Ok(seq) => {
+                        let name = if self.takeskip_name.trim().is_empty() {
+                            format!("Sequence: {}", self.takeskip_input)
+                        } else {
+                            self.takeskip_name.clone()
+                        };
+                        
+                        BitOperation::TakeSkipSequence {
+                            name,
+                            sequence: seq,
+                            enabled: true,
                         }
-                    };
-                    
-                    if start >= end {
-                        self.error_message = Some("Start must be less than end".to_string());
-                        return;
                     }
-                    
-                    let name = if self.truncate_name.trim().is_empty() {
-                        format!("Truncate: {}-{}", start, if end == usize::MAX { "end".to_string() } else { end.to_string() })
-                    } else {
-                        self.truncate_name.clone()
-                    };
-                    
-                    BitOperation::TruncateBits { name, start, end, enabled: true }
+                    Err(e) => {
+                        return Err(format!("Invalid operation: {}", e));
+                    }
                 }
-                OperationType::InterleaveBits => {
-                    use crate::processing::{BlockInterleaverConfig, ConvolutionalInterleaverConfig, InterleaverType};
-                    use crate::processing::interleaver::SymbolInterleaverConfig;
-                    
-                    let name = if self.interleave_name.trim().is_empty() {
-                        match self.interleave_type {
-                            InterleaverType::Block => "Block Interleaver".to_string(),
-                            InterleaverType::Convolutional => "Convolutional Interleaver".to_string(),
-                            InterleaverType::Symbol => "Symbol Interleaver".to_string(),
-                        }
-                    } else {
-                        self.interleave_name.clone()
-                    };
-                    
-                    let (block_config, convolutional_config, symbol_config) = match self.interleave_type {
-                        InterleaverType::Block => {
-                            let block_size = match self.interleave_block_size.trim().parse::<usize>() {
-                                Ok(val) if val > 0 => val,
-                                _ => {
-                                    self.error_message = Some("Block size must be a positive number".to_string());
-                                    return;
-                                }
-                            };
-                            
-                            let depth = match self.interleave_depth.trim().parse::<usize>() {
-                                Ok(val) if val > 0 => val,
-                                _ => {
-                                    self.error_message = Some("Depth must be a positive number".to_string());
-                                    return;
-                                }
-                            };
-                            
-                            (Some(BlockInterleaverConfig::new(block_size, depth, self.interleave_direction)), None, None)
-                        }
-                        InterleaverType::Convolutional => {
-                            let branches = match self.interleave_branches.trim().parse::<usize>() {
-                                Ok(val) if val > 0 => val,
-                                _ => {
-                                    self.error_message = Some("Branches must be a positive number".to_string());
-                                    return;
-                                }
-                            };
-                            
-                            let delay_increment = match self.interleave_delay_increment.trim().parse::<usize>() {
-                                Ok(val) => val,
-                                _ => {
-                                    self.error_message = Some("Delay increment must be a valid number".to_string());
-                                    return;
-                                }
-                            };
-                            
-                            (None, Some(ConvolutionalInterleaverConfig::new(branches, delay_increment, self.interleave_direction)), None)
-                        }
-                        InterleaverType::Symbol => {
-                            let symbol_size = match self.interleave_symbol_size.trim().parse::<usize>() {
-                                Ok(val) if val > 0 => val,
-                                _ => {
-                                    self.error_message = Some("Symbol size must be a positive number".to_string());
-                                    return;
-                                }
-                            };
-                            
-                            let block_size = match self.interleave_block_size.trim().parse::<usize>() {
-                                Ok(val) if val > 0 => val,
-                                _ => {
-                                    self.error_message = Some("Block size must be a positive number".to_string());
-                                    return;
-                                }
-                            };
-                            
-                            let depth = match self.interleave_depth.trim().parse::<usize>() {
-                                Ok(val) if val > 0 => val,
-                                _ => {
-                                    self.error_message = Some("Depth must be a positive number".to_string());
-                                    return;
-                                }
-                            };
-                            
-                            (None, None, Some(SymbolInterleaverConfig::new(symbol_size, block_size, depth, self.interleave_direction)))
-                        }
+            }
+            OperationType::InsertBits => {
+                let mut exprs = self.new_param_expressions();
+                let position = self.eval_param(&self.insert_position, "position", &mut exprs).map_err(|e| format!("Invalid position: {}", e))?;
+                let count = self.eval_param(&self.insert_count, "count", &mut exprs).map_err(|e| format!("Invalid count: {}", e))?;
+                if count == 0 {
+                    return Err("Count must be at least 1".to_string());
+                }
+                parse_bit_pattern(&self.insert_fill)?;
+                let fill: String = self.insert_fill.chars().filter(|c| !c.is_whitespace()).collect();
+                
+                let name = if self.insert_name.trim().is_empty() {
+                    format!("Insert {} at {}", count, position)
+                } else {
+                    self.insert_name.clone()
+                };
+                
+                BitOperation::InsertBits { name, position, count, fill, exprs, enabled: true }
+            }
+            OperationType::DeleteBits => {
+                let mut exprs = self.new_param_expressions();
+                let (mode, default_name) = if self.delete_unstuff {
+                    let run_length = eval_expression(&self.delete_run_length).map_err(|e| format!("Invalid run length: {}", e))?;
+                    if run_length == 0 {
+                        return Err("Run length must be at least 1".to_string());
+                    }
+                    (DeleteMode::Unstuff { run_length }, "Remove Bit Stuffing".to_string())
+                } else {
+                    let position = self.eval_param(&self.delete_position, "position", &mut exprs).map_err(|e| format!("Invalid position: {}", e))?;
+                    let count = self.eval_param(&self.delete_count, "count", &mut exprs).map_err(|e| format!("Invalid count: {}", e))?;
+                    if count == 0 {
+                        return Err("Count must be at least 1".to_string());
+                    }
+                    (DeleteMode::Range { position, count }, format!("Delete {} at {}", count, position))
+                };
+                
+                let name = if self.delete_name.trim().is_empty() {
+                    default_name
+                } else {
+                    self.delete_name.clone()
+                };
+                
+                BitOperation::DeleteBits { name, mode, exprs, enabled: true }
+            }
+            OperationType::RangeBits => {
+                let mut exprs = self.new_param_expressions();
+                let start = self.eval_param(&self.range_start, "start", &mut exprs).map_err(|e| format!("Invalid start: {}", e))?;
+                let end = if self.range_end.trim().is_empty() {
+                    usize::MAX
+                } else {
+                    self.eval_param(&self.range_end, "end", &mut exprs).map_err(|e| format!("Invalid end: {}", e))?
+                };
+                if start >= end {
+                    return Err("Start must be less than end".to_string());
+                }
+                
+                let name = if self.range_name.trim().is_empty() {
+                    let verb = match self.range_action {
+                        RangeAction::Invert => "Invert",
+                        RangeAction::Reverse => "Reverse",
                     };
-                    
-                    BitOperation::InterleaveBits {
-                        name,
-                        interleaver_type: self.interleave_type,
-                        block_config,
-                        convolutional_config,
-                        symbol_config,
-                        enabled: true,
+                    format!("{} {}-{}", verb, start, if end == usize::MAX { "end".to_string() } else { end.to_string() })
+                } else {
+                    self.range_name.clone()
+                };
+                
+                BitOperation::RangeBits { name, action: self.range_action, start, end, exprs, enabled: true }
+            }
+            OperationType::XorKey => {
+                let key = parse_hex_key(&self.xor_key).map_err(|e| format!("Invalid key: {}", e))?;
+                
+                let name = if self.xor_name.trim().is_empty() {
+                    format!("XOR {} byte key", key.len())
+                } else {
+                    self.xor_name.clone()
+                };
+                
+                BitOperation::XorKey { name, key, enabled: true }
+            }
+            OperationType::LineDecode => {
+                let name = if self.line_code_name.trim().is_empty() {
+                    format!("{} Decode", self.line_code.name())
+                } else {
+                    self.line_code_name.clone()
+                };
+                
+                BitOperation::LineDecode { name, code: self.line_code, enabled: true }
+            }
+            OperationType::SymbolConvert => {
+                let symbol_bits = eval_expression(&self.symbol_code_bits).map_err(|e| format!("Invalid symbol width: {}", e))?;
+                self.symbol_code.check_width(symbol_bits)?;
+                
+                let name = if self.symbol_code_name.trim().is_empty() {
+                    format!("{} ({} bits)", self.symbol_code.name(), symbol_bits)
+                } else {
+                    self.symbol_code_name.clone()
+                };
+                
+                BitOperation::SymbolConvert { name, code: self.symbol_code, symbol_bits, enabled: true }
+            }
+            OperationType::SymbolRemap => {
+                let input_bits = eval_expression(&self.remap_input_bits).map_err(|e| format!("Invalid input width: {}", e))?;
+                let output_bits = eval_expression(&self.remap_output_bits).map_err(|e| format!("Invalid output width: {}", e))?;
+                let table = SymbolMap::parse_table(&self.remap_table)?;
+                if table.is_empty() {
+                    return Err("The table has no entries".to_string());
+                }
+                let map = SymbolMap { input_bits, output_bits, table };
+                map.validate()?;
+                
+                let name = if self.remap_name.trim().is_empty() {
+                    format!("Remap {} symbols", map.table.len())
+                } else {
+                    self.remap_name.clone()
+                };
+                
+                BitOperation::SymbolRemap { name, map, enabled: true }
+            }
+            OperationType::MajorityVote => {
+                let vote = self.majority_from_fields()?;
+                
+                let name = if self.majority_name.trim().is_empty() {
+                    format!("Majority of {}", self.majority_repeats.trim())
+                } else {
+                    self.majority_name.clone()
+                };
+                
+                BitOperation::MajorityVote { name, vote, enabled: true }
+            }
+            OperationType::NandLayout => {
+                let layout = self.nand_layout_from_fields()?;
+                
+                let name = if self.nand_name.trim().is_empty() {
+                    match layout.keep {
+                        NandArea::Data => format!("Strip spare ({}+{})", layout.page_size, layout.spare_size),
+                        NandArea::Spare => format!("Spare only ({}+{})", layout.page_size, layout.spare_size),
                     }
+                } else {
+                    self.nand_name.clone()
+                };
+                
+                BitOperation::NandLayout { name, layout, enabled: true }
+            }
+            OperationType::Conditional => {
+                let operation = self.conditional_operation.clone().ok_or("No operation to apply")?;
+                if operation.is_source() {
+                    return Err("File and worksheet loads can't be applied conditionally".to_string());
                 }
-                OperationType::MultiWorksheetLoad => {
-                    if self.multiworksheet_ops.is_empty() {
-                        self.error_message = Some("Must add at least one worksheet operation".to_string());
-                        return;
+                
+                let condition = if self.conditional_by_ranges {
+                    let ranges = parse_ranges_csv(&self.conditional_ranges).map_err(|e| format!("Invalid ranges: {}", e))?;
+                    if ranges.is_empty() {
+                        return Err("Enter at least one start,end range".to_string());
                     }
-                    
-                    let mut worksheet_operations = Vec::new();
-                    for (ws_idx, seq_str) in &self.multiworksheet_ops {
-                        match OperationSequence::from_string(seq_str) {
-                            Ok(seq) => {
-                                worksheet_operations.push(WorksheetOperation {
-                                    worksheet_index: *ws_idx,
-                                    sequence: seq,
-                                });
+                    BitCondition::Ranges { ranges }
+                } else {
+                    let frame_length = eval_expression(&self.conditional_frame_length).map_err(|e| format!("Invalid frame length: {}", e))?;
+                    let every = eval_expression(&self.conditional_every).map_err(|e| format!("Invalid interval: {}", e))?;
+                    let offset = eval_expression(&self.conditional_offset).map_err(|e| format!("Invalid first frame: {}", e))?;
+                    if frame_length == 0 || every == 0 {
+                        return Err("Frame length and interval must be at least 1".to_string());
+                    }
+                    BitCondition::EveryNthFrame { frame_length, every, offset }
+                };
+                
+                let name = if self.conditional_name.trim().is_empty() {
+                    format!("{} (conditional)", operation.name())
+                } else {
+                    self.conditional_name.clone()
+                };
+                
+                BitOperation::Conditional { name, operation: Box::new(operation), condition, enabled: true }
+            }
+            OperationType::Script => {
+                BitScript::parse(&self.script_source).map_err(|e| format!("Invalid script: {}", e))?;
+                
+                let name = if self.script_name.trim().is_empty() {
+                    "Script".to_string()
+                } else {
+                    self.script_name.clone()
+                };
+                
+                BitOperation::Script {
+                    name,
+                    source: self.script_source.clone(),
+                    time_limit_ms: self.script_time_limit_ms,
+                    enabled: true,
+                }
+            }
+            OperationType::PatchBits => {
+                if self.patch_list.is_empty() {
+                    return Err("No bit edits left; remove the operation instead".to_string());
+                }
+                
+                let name = if self.patch_name.trim().is_empty() {
+                    BIT_EDITS_NAME.to_string()
+                } else {
+                    self.patch_name.clone()
+                };
+                
+                BitOperation::PatchBits { name, patches: self.patch_list.clone(), enabled: true }
+            }
+            OperationType::Macro => {
+                if self.macro_operations.is_empty() {
+                    return Err("Macro has no operations".to_string());
+                }
+                
+                let name = if self.macro_name.trim().is_empty() {
+                    "Macro".to_string()
+                } else {
+                    self.macro_name.clone()
+                };
+                
+                BitOperation::Macro { name, operations: self.macro_operations.clone(), enabled: true }
+            }
+            OperationType::Variants => {
+                let (Some(a), Some(b)) = (self.variants_a.clone(), self.variants_b.clone()) else {
+                    return Err("Both variants need an operation".to_string());
+                };
+                let name = if self.variants_name.trim().is_empty() {
+                    format!("{} (A/B)", a.name())
+                } else {
+                    self.variants_name.clone()
+                };
+                
+                BitOperation::Variants { name, a: Box::new(a), b: Box::new(b), use_b: self.variants_use_b, enabled: true }
+            }
+            OperationType::Group => {
+                let name = if self.group_name.trim().is_empty() {
+                    "Group".to_string()
+                } else {
+                    self.group_name.clone()
+                };
+                
+                BitOperation::Group {
+                    name,
+                    operations: self.group_operations.clone(),
+                    collapsed: self.group_collapsed,
+                    enabled: true,
+                }
+            }
+            OperationType::InvertBits => {
+                let name = if self.invert_name.trim().is_empty() {
+                    "Invert All Bits".to_string()
+                } else {
+                    self.invert_name.clone()
+                };
+                
+                BitOperation::InvertBits { name, enabled: true }
+            }
+            OperationType::TruncateBits => {
+                // Parse start and end
+                let mut exprs = self.new_param_expressions();
+                let start = if self.truncate_start.trim().is_empty() {
+                    0
+                } else {
+                    self.eval_param(&self.truncate_start, "start", &mut exprs).map_err(|e| format!("Invalid start: {}", e))?
+                };
+                let end = if self.truncate_end.trim().is_empty() {
+                    // If no end specified, use a very large number (essentially to the end)
+                    usize::MAX
+                } else {
+                    self.eval_param(&self.truncate_end, "end", &mut exprs).map_err(|e| format!("Invalid end value: {}", e))?
+                };
+                
+                if start >= end {
+                    return Err("Start must be less than end".to_string());
+                }
+                
+                let name = if self.truncate_name.trim().is_empty() {
+                    format!("Truncate: {}-{}", start, if end == usize::MAX { "end".to_string() } else { end.to_string() })
+                } else {
+                    self.truncate_name.clone()
+                };
+                
+                BitOperation::TruncateBits { name, start, end, exprs, enabled: true }
+            }
+            OperationType::InterleaveBits => {
+                use crate::processing::{BlockInterleaverConfig, ConvolutionalInterleaverConfig, InterleaverType};
+                use crate::processing::interleaver::SymbolInterleaverConfig;
+                
+                let name = if self.interleave_name.trim().is_empty() {
+                    match self.interleave_type {
+                        InterleaverType::Block => "Block Interleaver".to_string(),
+                        InterleaverType::Convolutional => "Convolutional Interleaver".to_string(),
+                        InterleaverType::Symbol => "Symbol Interleaver".to_string(),
+                    }
+                } else {
+                    self.interleave_name.clone()
+                };
+                
+                let (block_config, convolutional_config, symbol_config) = match self.interleave_type {
+                    InterleaverType::Block => {
+                        let block_size = match self.interleave_block_size.trim().parse::<usize>() {
+                            Ok(val) if val > 0 => val,
+                            _ => {
+                                return Err("Block size must be a positive number".to_string());
                             }
-                            Err(e) => {
-                                self.error_message = Some(format!("Invalid sequence for worksheet {}: {}", ws_idx + 1, e));
-                                return;
+                        };
+                        
+                        let depth = match self.interleave_depth.trim().parse::<usize>() {
+                            Ok(val) if val > 0 => val,
+                            _ => {
+                                return Err("Depth must be a positive number".to_string());
                             }
-                        }
+                        };
+                        
+                        (Some(BlockInterleaverConfig::new(block_size, depth, self.interleave_direction)), None, None)
                     }
-                    
-                    let name = if self.multiworksheet_name.trim().is_empty() {
-                        format!("Load from {} worksheets", worksheet_operations.len())
-                    } else {
-                        self.multiworksheet_name.clone()
+                    InterleaverType::Convolutional => {
+                        let branches = match self.interleave_branches.trim().parse::<usize>() {
+                            Ok(val) if val > 0 => val,
+                            _ => {
+                                return Err("Branches must be a positive number".to_string());
+                            }
+                        };
+                        
+                        let delay_increment = match self.interleave_delay_increment.trim().parse::<usize>() {
+                            Ok(val) => val,
+                            _ => {
+                                return Err("Delay increment must be a valid number".to_string());
+                            }
+                        };
+                        
+                        (None, Some(ConvolutionalInterleaverConfig::new(branches, delay_increment, self.interleave_direction)), None)
+                    }
+                    InterleaverType::Symbol => {
+                        let symbol_size = match self.interleave_symbol_size.trim().parse::<usize>() {
+                            Ok(val) if val > 0 => val,
+                            _ => {
+                                return Err("Symbol size must be a positive number".to_string());
+                            }
+                        };
+                        
+                        let block_size = match self.interleave_block_size.trim().parse::<usize>() {
+                            Ok(val) if val > 0 => val,
+                            _ => {
+                                return Err("Block size must be a positive number".to_string());
+                            }
+                        };
+                        
+                        let depth = match self.interleave_depth.trim().parse::<usize>() {
+                            Ok(val) if val > 0 => val,
+                            _ => {
+                                return Err("Depth must be a positive number".to_string());
+                            }
+                        };
+                        
+                        (None, None, Some(SymbolInterleaverConfig::new(symbol_size, block_size, depth, self.interleave_direction)))
+                    }
+                };
+                
+                BitOperation::InterleaveBits {
+                    name,
+                    interleaver_type: self.interleave_type,
+                    block_config,
+                    convolutional_config,
+                    symbol_config,
+                    enabled: true,
+                }
+            }
+            OperationType::MultiWorksheetLoad => {
+                if self.multiworksheet_ops.is_empty() {
+                    return Err("Must add at least one worksheet operation".to_string());
+                }
+                
+                let mut worksheet_operations = Vec::new();
+                for (ws_id, seq_str) in &self.multiworksheet_ops {
+                    let Some(worksheet) = self.worksheets.iter().find(|ws| ws.id == *ws_id) else {
+                        return Err("A selected worksheet no longer exists; remove it from the list".to_string());
                     };
-                    
-                    BitOperation::MultiWorksheetLoad {
-                        name,
-                        worksheet_operations,
-                        enabled: true,
+                    match OperationSequence::from_string(seq_str) {
+                        Ok(seq) => {
+                            worksheet_operations.push(WorksheetOperation {
+                                worksheet_id: worksheet.id,
+                                worksheet_name: worksheet.name.clone(),
+                                sequence: seq,
+                            });
+                        }
+                        Err(e) => {
+                            return Err(format!("Invalid sequence for worksheet {}: {}", worksheet.name, e));
+                        }
                     }
                 }
+                
+                let name = if self.multiworksheet_name.trim().is_empty() {
+                    format!("Load from {} worksheets", worksheet_operations.len())
+                } else {
+                    self.multiworksheet_name.clone()
+                };
+                
+                BitOperation::MultiWorksheetLoad {
+                    name,
+                    worksheet_operations,
+                    enabled: true,
+                }
+            }
+        };
+        Ok(operation)
+    }
+    
+    pub fn save_current_operation(&mut self) {
+        if let Some(op_type) = self.show_operation_menu {
+            let new_operation = match self.build_current_operation(op_type) {
+                Ok(operation) => operation,
+                Err(e) => {
+                    self.notifications.error(e);
+                    return;
+                }
             };
 
             if let Some(index) = self.editing_operation_index {
                 // Editing existing operation - data will change
-                self.operations[index] = new_operation;
+                match (self.editing_group_child, &mut self.operations[index]) {
+                    (Some(child), BitOperation::Group { operations, .. }) if child < operations.len() => {
+                        operations[child] = new_operation;
+                    }
+                    (Some(0), BitOperation::Variants { a, .. }) => **a = new_operation,
+                    (Some(_), BitOperation::Variants { b, .. }) => **b = new_operation,
+                    _ => self.operations[index] = new_operation,
+                }
                 self.clear_pattern_matches();
             } else {
                 // Adding new operation - data will change
@@ -1147,159 +3334,616 @@ impl BitApp {
             self.truncate_name.clear();
             self.truncate_start = String::from("0");
             self.truncate_end.clear();
+        self.reset_insert_delete_editors();
+        self.script_name.clear();
+        self.script_source = String::from(DEFAULT_SCRIPT);
+        self.script_time_limit_ms = DEFAULT_SCRIPT_TIME_LIMIT_MS;
             self.multiworksheet_name.clear();
             self.multiworksheet_ops.clear();
             self.multiworksheet_input.clear();
-            self.error_message = None;
-            self.apply_operations();
+        self.macro_name.clear();
+        self.macro_operations.clear();
+        self.patch_name.clear();
+        self.patch_list.clear();
+            self.operations_changed();
         }
     }
 
-    pub fn cancel_operation_edit(&mut self) {
-        self.show_operation_menu = None;
-        self.editing_operation_index = None;
-        self.takeskip_name.clear();
-        self.takeskip_input.clear();
-        self.loadfile_name.clear();
-        self.loadfile_path = None;
-        self.invert_name.clear();
-        self.truncate_name.clear();
-        self.truncate_start = String::from("0");
-        self.truncate_end.clear();
-        self.interleave_name.clear();
-        self.interleave_type = crate::processing::InterleaverType::Block;
-        self.interleave_direction = crate::processing::InterleaverDirection::Interleave;
-        self.interleave_block_size = String::from("8");
-        self.interleave_depth = String::from("4");
-        self.interleave_branches = String::from("4");
-        self.interleave_delay_increment = String::from("1");
-        self.interleave_symbol_size = String::from("8");
-        self.multiworksheet_name.clear();
-        self.multiworksheet_ops.clear();
-        self.multiworksheet_input.clear();
+    /// Bits before and after the operation being edited, computed over the first
+    /// `PREVIEW_INPUT_BITS` of the original data with the preceding operations applied
+    pub fn operation_preview(&self) -> Result<OperationPreview, String> {
+        let op_type = self.show_operation_menu.ok_or("No operation is being edited")?;
+        let operation = self.build_current_operation(op_type)?;
+        let preceding = self.preceding_operations()?;
+        
+        let original = self.original_view();
+        let mut before = original[..original.len().min(PREVIEW_INPUT_BITS)].to_bitvec();
+        for op in preceding.iter().filter(|op| op.is_enabled()) {
+            op.apply_in_place(&mut before);
+        }
+        
+        let mut after = before.clone();
+        match &operation {
+            // Show why a script fails instead of an unchanged preview
+            BitOperation::Script { source, time_limit_ms, .. } => run_script(source, &mut after, *time_limit_ms)?,
+            _ => operation.apply_in_place(&mut after),
+        }
+        Ok(OperationPreview { before, after })
     }
     
-    pub fn render_ascii_view(&self, ui: &mut eframe::egui::Ui, bits: &BitVec<u8, Msb0>) {
-        use eframe::egui;
+    /// The operations before the one being edited, whose output it receives
+    fn preceding_operations(&self) -> Result<Vec<&BitOperation>, String> {
+        let mut preceding: Vec<&BitOperation> = match self.editing_operation_index {
+            Some(index) => self.operations[..index.min(self.operations.len())].iter().collect(),
+            None => self.operations.iter().collect(),
+        };
+        // A group child sees its earlier siblings' output
+        if let (Some(index), Some(child)) = (self.editing_operation_index, self.editing_group_child)
+            && let Some(BitOperation::Group { operations, enabled: true, .. }) = self.operations.get(index)
+        {
+            preceding.extend(&operations[..child.min(operations.len())]);
+        }
+        if preceding.iter().any(|op| op.is_enabled() && op.is_source()) {
+            return Err("No preview after Load File or Multi-Worksheet Load operations".to_string());
+        }
+        Ok(preceding)
+    }
+    
+    /// Save the enabled transforming operations as a macro named `new_macro_name`,
+    /// replacing any macro with the same name
+    pub fn save_operations_as_macro(&mut self) {
+        let name = self.new_macro_name.trim().to_string();
+        if name.is_empty() {
+            self.notifications.error("Enter a name for the macro".to_string());
+            return;
+        }
         
-        if bits.is_empty() {
-            ui.label("No data to display");
+        let operations: Vec<BitOperation> = self.operations
+            .iter()
+            .filter(|op| op.is_enabled() && !op.is_source())
+            .cloned()
+            .collect();
+        if operations.is_empty() {
+            self.notifications.error("No enabled operations to save (file and worksheet loads aren't included)".to_string());
             return;
         }
-
-        // Predefined colors for different patterns (same as byte viewer)
-        let pattern_colors = [
-            egui::Color32::from_rgb(255, 100, 100),  // Red
-            egui::Color32::from_rgb(100, 255, 100),  // Green
-            egui::Color32::from_rgb(100, 100, 255),  // Blue
-            egui::Color32::from_rgb(255, 255, 100),  // Yellow
-            egui::Color32::from_rgb(255, 100, 255),  // Magenta
-            egui::Color32::from_rgb(100, 255, 255),  // Cyan
-            egui::Color32::from_rgb(255, 150, 100),  // Orange
-            egui::Color32::from_rgb(150, 100, 255),  // Purple
-        ];
-
-        // Calculate total size WITHOUT converting all bits
-        let total_bits = bits.len();
-        let total_bytes = (total_bits + 7) / 8;
         
-        // Use frame_length (in bits) to determine characters per row
-        // Each character represents 8 bits (1 byte)
-        let chars_per_row = self.viewer.frame_length / 8;
-        let total_rows = (total_bytes + chars_per_row - 1) / chars_per_row;
+        let saved = OperationMacro { name: name.clone(), operations };
+        match self.settings.macros.iter_mut().find(|m| m.name == name) {
+            Some(existing) => *existing = saved,
+            None => self.settings.macros.push(saved),
+        }
+        self.settings.auto_save();
+        self.new_macro_name.clear();
+    }
+    
+    /// Save the operation list and patterns to a standalone file, without the worksheet's file
+    pub fn export_operation_chain(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Operations", &["json"])
+            .set_file_name(format!("operations.{}", OperationChain::FILE_EXTENSION))
+            .save_file()
+        else {
+            return;
+        };
         
-        let char_width = 12.0;
-        let char_height = 20.0;
-        let offset_width = if self.byte_viewer.config.show_hex_offset { 90.0 } else { 0.0 };
+        let chain = OperationChain::new(self.operations.clone(), self.patterns.clone());
+        if let Err(e) = chain.save_to_file(&path) {
+            self.notifications.error(e);
+        }
+    }
+    
+    /// Append the operations of a saved chain to the list and add the patterns not already
+    /// here; files the chain loads that don't exist on this machine are reported
+    pub fn import_operation_chain(&mut self) {
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("Operations", &["json"])
+            .pick_file()
+        else {
+            return;
+        };
         
-        egui::ScrollArea::vertical()
-            .id_salt("ascii_viewer_scroll")
-            .auto_shrink([false, false])
-            .show_rows(
-                ui,
-                char_height,
-                total_rows,
-                |ui, row_range| {
-                    egui::ScrollArea::horizontal()
-                        .id_salt("ascii_viewer_horizontal")
-                        .show(ui, |ui| {
-                            // Set a light background for better text visibility
-                            ui.painter().rect_filled(
-                                ui.max_rect(),
-                                0.0,
-                                egui::Color32::from_rgb(245, 245, 245)
-                            );
-                            
-                            // Only render visible rows
-                            for row in row_range {
-                                ui.horizontal(|ui| {
-                                    // Show offset if enabled
-                                    if self.byte_viewer.config.show_hex_offset {
-                                        let offset = row * chars_per_row;
-                                        ui.add_sized(
-                                            [offset_width, char_height],
-                                            egui::Label::new(
-                                                egui::RichText::new(format!("{:08X}  ", offset))
-                                                    .monospace()
-                                                    .color(egui::Color32::GRAY)
-                                            )
-                                        );
-                                    }
-                                    
-                                    // Draw ASCII characters - only convert bytes we need for this row
-                                    let row_start = row * chars_per_row;
-                                    let row_end = (row_start + chars_per_row).min(total_bytes);
-                                    
-                                    for byte_idx in row_start..row_end {
-                                        // Convert only this single byte from bits
-                                        let bit_start = byte_idx * 8;
-                                        let bit_end = (bit_start + 8).min(total_bits);
-                                        let byte_bits = &bits[bit_start..bit_end];
-                                        
-                                        let mut byte = 0u8;
-                                        for (i, bit) in byte_bits.iter().enumerate() {
-                                            if *bit {
-                                                byte |= 1 << (7 - i);
-                                            }
-                                        }
-                                        
-                                        // Check if this byte is part of any pattern match
-                                        let mut pattern_match: Option<(egui::Color32, String)> = None;
-                                        for (pattern_idx, pattern) in self.patterns.iter().enumerate() {
-                                            for match_info in &pattern.matches {
-                                                let match_start = match_info.position;
-                                                let match_end = match_info.position + pattern.bits.len();
-                                                
-                                                // Check if this byte overlaps with the pattern match
-                                                if bit_start < match_end && bit_end > match_start {
-                                                    let color = pattern_colors[pattern_idx % pattern_colors.len()];
-                                                    pattern_match = Some((color, pattern.name.clone()));
-                                                    break;
-                                                }
-                                            }
-                                            if pattern_match.is_some() {
-                                                break;
-                                            }
-                                        }
-                                        
-                                        let ch = if byte >= 32 && byte <= 126 {
-                                            byte as char
-                                        } else {
-                                            '.'
-                                        };
-                                        
-                                        let (rect, response) = ui.allocate_exact_size(
-                                            egui::Vec2::new(char_width, char_height),
-                                            egui::Sense::hover(),
-                                        );
-                                        
-                                        // Draw background highlight for pattern matches
-                                        if let Some((pattern_color, _)) = pattern_match {
-                                            ui.painter().rect_filled(
-                                                rect,
-                                                2.0,
-                                                egui::Color32::from_rgba_unmultiplied(
-                                                    pattern_color.r(),
+        let chain = match OperationChain::load_from_file(&path) {
+            Ok(chain) => chain,
+            Err(e) => {
+                self.notifications.error(e);
+                return;
+            }
+        };
+        let missing: Vec<String> = chain.missing_files().iter().map(|path| path.display().to_string()).collect();
+        
+        for pattern in chain.patterns {
+            if !self.patterns.iter().any(|p| p.format == pattern.format && p.input == pattern.input) {
+                self.patterns.push(pattern);
+            }
+        }
+        self.operations.extend(chain.operations);
+        if !missing.is_empty() {
+            self.notifications.warning(format!("Imported operations load files that don't exist here: {}", missing.join(", ")));
+        }
+        self.clear_pattern_matches();
+        self.operations_changed();
+    }
+    
+    /// Save the sequence in the Take/Skip editor as a preset under its name, replacing any
+    /// preset with the same name
+    pub fn save_takeskip_preset(&mut self) {
+        let sequence = self.takeskip_input.trim().to_string();
+        if let Err(e) = OperationSequence::from_string(&sequence) {
+            self.notifications.error(format!("Invalid operation: {}", e));
+            return;
+        }
+        let name = match self.takeskip_name.trim() {
+            "" => sequence.clone(),
+            name => name.to_string(),
+        };
+        
+        let saved = TakeSkipPreset::new(name.clone(), sequence, String::new());
+        match self.settings.takeskip_presets.iter_mut().find(|preset| preset.name == name) {
+            Some(existing) => *existing = saved,
+            None => self.settings.takeskip_presets.push(saved),
+        }
+        self.settings.auto_save();
+    }
+    
+    /// Fill the Take/Skip editor from `preset`
+    pub fn use_takeskip_preset(&mut self, preset: &TakeSkipPreset) {
+        self.takeskip_name = preset.name.clone();
+        self.takeskip_input = preset.sequence.clone();
+    }
+    
+    pub fn delete_takeskip_preset(&mut self, index: usize) {
+        if index < self.settings.takeskip_presets.len() {
+            self.settings.takeskip_presets.remove(index);
+            self.settings.auto_save();
+        }
+    }
+    
+    /// Append the library macro at `index` to the operation list
+    pub fn add_macro_operation(&mut self, index: usize) {
+        if let Some(saved) = self.settings.macros.get(index) {
+            self.operations.push(saved.to_operation());
+            self.clear_pattern_matches();
+            self.operations_changed();
+        }
+    }
+    
+    pub fn delete_macro(&mut self, index: usize) {
+        if index < self.settings.macros.len() {
+            self.settings.macros.remove(index);
+            self.settings.auto_save();
+        }
+    }
+    
+    /// Replace the macro operation at `index` with its individual steps
+    pub fn expand_macro_operation(&mut self, index: usize) {
+        if let Some(BitOperation::Macro { operations, .. }) = self.operations.get(index).cloned() {
+            self.operations.splice(index..=index, operations);
+            self.clear_pattern_matches();
+            self.operations_changed();
+        }
+    }
+    
+    /// Open the Conditional editor to restrict the operation at `index` to some frames or ranges;
+    /// saving replaces the operation with the wrapped one
+    pub fn wrap_operation_in_condition(&mut self, index: usize) {
+        let Some(op) = self.operations.get(index).cloned() else {
+            return;
+        };
+        self.cancel_operation_edit();
+        self.show_operation_menu = Some(OperationType::Conditional);
+        self.editing_operation_index = Some(index);
+        self.conditional_operation = Some(op);
+    }
+    
+    /// Replace the conditional operation at `index` with its child, applied everywhere
+    pub fn unwrap_conditional_operation(&mut self, index: usize) {
+        if let Some(BitOperation::Conditional { operation, .. }) = self.operations.get(index).cloned() {
+            self.operations[index] = *operation;
+            self.clear_pattern_matches();
+            self.operations_changed();
+        }
+    }
+    
+    /// Put the operation at `index` into a new group of its own
+    pub fn group_operation(&mut self, index: usize) {
+        let Some(op) = self.operations.get(index) else {
+            return;
+        };
+        if op.is_source() || matches!(op, BitOperation::Group { .. }) {
+            self.notifications.error("File loads, worksheet loads and groups can't be grouped".to_string());
+            return;
+        }
+        let count = self.operations.iter().filter(|op| matches!(op, BitOperation::Group { .. })).count();
+        let op = self.operations.remove(index);
+        self.operations.insert(index, BitOperation::Group {
+            name: format!("Group {}", count + 1),
+            operations: vec![op],
+            collapsed: false,
+            enabled: true,
+        });
+        // Grouping an operation doesn't change the output
+        self.operations_changed();
+    }
+    
+    /// Move the operation at `index` to the end of the group at `group`
+    pub fn add_operation_to_group(&mut self, index: usize, group: usize) {
+        let movable = self.operations
+            .get(index)
+            .is_some_and(|op| !op.is_source() && !matches!(op, BitOperation::Group { .. }));
+        if !movable || !matches!(self.operations.get(group), Some(BitOperation::Group { .. })) {
+            return;
+        }
+        let op = self.operations.remove(index);
+        let group = if group > index { group - 1 } else { group };
+        if let BitOperation::Group { operations, .. } = &mut self.operations[group] {
+            operations.push(op);
+        }
+        self.clear_pattern_matches();
+        self.operations_changed();
+    }
+    
+    /// Move child `child` of the group at `group` out to just after the group; a group left
+    /// empty is removed
+    pub fn move_out_of_group(&mut self, group: usize, child: usize) {
+        let Some(BitOperation::Group { operations, .. }) = self.operations.get_mut(group) else {
+            return;
+        };
+        if child >= operations.len() {
+            return;
+        }
+        let op = operations.remove(child);
+        if operations.is_empty() {
+            self.operations[group] = op;
+        } else {
+            self.operations.insert(group + 1, op);
+        }
+        self.clear_pattern_matches();
+        self.operations_changed();
+    }
+    
+    /// Turn the operation at `index` into A/B variants, both starting as copies of it, and
+    /// open variant B for editing
+    pub fn make_operation_variants(&mut self, index: usize) {
+        let Some(op) = self.operations.get(index).cloned() else {
+            return;
+        };
+        if op.is_source() || matches!(op, BitOperation::Group { .. } | BitOperation::Variants { .. }) {
+            self.notifications.error("File loads, worksheet loads, groups and variants can't have A/B variants".to_string());
+            return;
+        }
+        self.operations[index] = BitOperation::Variants {
+            name: format!("{} (A/B)", op.name()),
+            a: Box::new(op.clone()),
+            b: Box::new(op),
+            use_b: false,
+            enabled: true,
+        };
+        self.cancel_operation_edit();
+        self.open_variant_editor(index, 1);
+    }
+    
+    /// Switch the Variants operation at `index` between A and B
+    pub fn toggle_operation_variant(&mut self, index: usize) {
+        if let Some(BitOperation::Variants { use_b, enabled, .. }) = self.operations.get_mut(index) {
+            *use_b = !*use_b;
+            if *enabled {
+                self.clear_pattern_matches();
+                self.operations_changed();
+            }
+        }
+    }
+    
+    /// Replace the Variants operation at `index` with its selected version
+    pub fn keep_active_variant(&mut self, index: usize) {
+        if let Some(active) = self.operations.get(index).and_then(|op| op.active_variant()).cloned() {
+            self.operations[index] = active;
+            self.operations_changed();
+        }
+    }
+    
+    /// Compare the outputs of the edited variants' A and B, each applied to the data as the
+    /// operations before them leave it
+    pub fn compare_variants(&mut self) {
+        let (Some(index), Some(a), Some(b)) = (self.editing_operation_index, &self.variants_a, &self.variants_b) else {
+            return;
+        };
+        let preceding = &self.operations[..index.min(self.operations.len())];
+        if preceding.iter().any(|op| op.is_enabled() && op.is_source()) {
+            self.notifications.error("Variants after Load File or Multi-Worksheet Load operations can't be compared".to_string());
+            return;
+        }
+        
+        let mut input = self.original_view().to_bitvec();
+        for op in preceding.iter().filter(|op| op.is_enabled()) {
+            op.apply_in_place(&mut input);
+        }
+        let (output_a, output_b) = (a.apply(&input), b.apply(&input));
+        self.variant_comparison = Some(bit_diff(&output_a, &output_b));
+    }
+    
+    /// Replace the group at `index` with its children
+    pub fn ungroup_operation(&mut self, index: usize) {
+        if let Some(BitOperation::Group { operations, enabled, .. }) = self.operations.get(index).cloned() {
+            self.operations.splice(index..=index, operations);
+            // Children of a disabled group were skipped; the output only changes if it was disabled
+            if !enabled {
+                self.clear_pattern_matches();
+            }
+            self.operations_changed();
+        }
+    }
+    
+    pub fn toggle_group_collapsed(&mut self, index: usize) {
+        if let Some(BitOperation::Group { collapsed, .. }) = self.operations.get_mut(index) {
+            *collapsed = !*collapsed;
+        }
+    }
+    
+    pub fn toggle_group_child(&mut self, group: usize, child: usize) {
+        if let Some(BitOperation::Group { operations, .. }) = self.operations.get_mut(group)
+            && let Some(op) = operations.get_mut(child)
+        {
+            op.set_enabled(!op.is_enabled());
+            self.operations_changed();
+        }
+    }
+    
+    pub fn remove_group_child(&mut self, group: usize, child: usize) {
+        if let Some(BitOperation::Group { operations, .. }) = self.operations.get_mut(group)
+            && child < operations.len()
+        {
+            operations.remove(child);
+            self.clear_pattern_matches();
+            self.operations_changed();
+        }
+    }
+    
+    /// Turn edit mode on or off; edits always apply to the processed view
+    pub fn set_edit_mode(&mut self, enabled: bool) {
+        self.edit_mode = enabled;
+        self.viewer.edit_mode = enabled;
+        self.byte_viewer.edit_mode = enabled;
+        if enabled && self.show_original {
+            self.show_original = false;
+            self.update_viewer();
+        }
+    }
+    
+    /// Record `patch` in the trailing Bit Edits operation, adding one if needed, and reapply
+    pub fn patch_bits(&mut self, patch: BitPatch) {
+        if self.show_original {
+            self.notifications.error("Bit edits apply to the processed view".to_string());
+            return;
+        }
+        
+        match self.operations.last_mut() {
+            Some(BitOperation::PatchBits { patches, enabled: true, .. }) => patches.push(patch),
+            _ => self.operations.push(BitOperation::PatchBits {
+                name: BIT_EDITS_NAME.to_string(),
+                patches: vec![patch],
+                enabled: true,
+            }),
+        }
+        self.clear_pattern_matches();
+        self.apply_operations();
+    }
+    
+    /// Flip the processed bit at `position`
+    pub fn toggle_bit(&mut self, position: usize) {
+        let Some(current) = self.processed_view().get(position).map(|bit| *bit) else {
+            return;
+        };
+        self.patch_bits(BitPatch::bit(position, !current));
+    }
+    
+    /// Record a byte view layout change since the last call as an undo step. Edits made while
+    /// the pointer is held down, like dragging a slider, become one step when it's let go.
+    pub fn track_byte_view_changes(&mut self, pointer_down: bool) {
+        if pointer_down || self.byte_viewer.config == self.byte_view_snapshot {
+            return;
+        }
+        let previous = std::mem::replace(&mut self.byte_view_snapshot, self.byte_viewer.config.clone());
+        self.byte_view_undo.push(previous);
+        if self.byte_view_undo.len() > MAX_BYTE_VIEW_UNDO {
+            self.byte_view_undo.remove(0);
+        }
+        self.byte_view_redo.clear();
+    }
+    
+    /// Forget the undo steps, as when another worksheet's layout is loaded
+    fn reset_byte_view_history(&mut self) {
+        self.byte_view_undo.clear();
+        self.byte_view_redo.clear();
+        self.byte_view_snapshot = self.byte_viewer.config.clone();
+    }
+    
+    pub fn can_undo_byte_view(&self) -> bool {
+        !self.byte_view_undo.is_empty()
+    }
+    
+    pub fn can_redo_byte_view(&self) -> bool {
+        !self.byte_view_redo.is_empty()
+    }
+    
+    /// Go back to the byte view layout before the last change
+    pub fn undo_byte_view(&mut self) {
+        if let Some(previous) = self.byte_view_undo.pop() {
+            self.byte_view_redo.push(std::mem::replace(&mut self.byte_viewer.config, previous));
+            self.byte_view_snapshot = self.byte_viewer.config.clone();
+        }
+    }
+    
+    /// Reapply the last byte view layout change that was undone
+    pub fn redo_byte_view(&mut self) {
+        if let Some(next) = self.byte_view_redo.pop() {
+            self.byte_view_undo.push(std::mem::replace(&mut self.byte_viewer.config, next));
+            self.byte_view_snapshot = self.byte_viewer.config.clone();
+        }
+    }
+    
+    /// Whether the last operation holds edits that `undo_bit_edit` can take back
+    pub fn can_undo_bit_edit(&self) -> bool {
+        matches!(self.operations.last(), Some(BitOperation::PatchBits { enabled: true, .. }))
+    }
+    
+    /// Remove the most recent bit edit, dropping the Bit Edits operation once it's empty
+    pub fn undo_bit_edit(&mut self) {
+        let Some(BitOperation::PatchBits { patches, enabled: true, .. }) = self.operations.last_mut() else {
+            return;
+        };
+        patches.pop();
+        if patches.is_empty() {
+            self.operations.pop();
+        }
+        self.clear_pattern_matches();
+        self.apply_operations();
+    }
+    
+    pub fn cancel_operation_edit(&mut self) {
+        self.show_operation_menu = None;
+        self.editing_operation_index = None;
+        self.takeskip_name.clear();
+        self.takeskip_input.clear();
+        self.loadfile_name.clear();
+        self.loadfile_path = None;
+        self.invert_name.clear();
+        self.truncate_name.clear();
+        self.truncate_start = String::from("0");
+        self.truncate_end.clear();
+        self.reset_insert_delete_editors();
+        self.script_name.clear();
+        self.script_source = String::from(DEFAULT_SCRIPT);
+        self.script_time_limit_ms = DEFAULT_SCRIPT_TIME_LIMIT_MS;
+        self.interleave_name.clear();
+        self.interleave_type = crate::processing::InterleaverType::Block;
+        self.interleave_direction = crate::processing::InterleaverDirection::Interleave;
+        self.interleave_block_size = String::from("8");
+        self.interleave_depth = String::from("4");
+        self.interleave_branches = String::from("4");
+        self.interleave_delay_increment = String::from("1");
+        self.interleave_symbol_size = String::from("8");
+        self.multiworksheet_name.clear();
+        self.multiworksheet_ops.clear();
+        self.multiworksheet_input.clear();
+        self.macro_name.clear();
+        self.macro_operations.clear();
+        self.patch_name.clear();
+        self.patch_list.clear();
+    }
+    
+    pub fn render_ascii_view(&self, ui: &mut eframe::egui::Ui, bits: &BitSlice<u8, Msb0>, jump_to_bit: Option<usize>) {
+        use eframe::egui;
+        
+        if bits.is_empty() {
+            ui.label(tr("No data to display"));
+            return;
+        }
+
+        // Pattern colors are shared with the byte viewer
+        let colors = self.settings.bit_colors.clone().unwrap_or_else(|| BitColors::for_dark_mode(ui.visuals().dark_mode));
+
+        let encoding = self.ascii_encoding;
+        let bits_per_char = self.ascii_bits_per_char.max(1);
+        
+        // Calculate total size WITHOUT converting all bits
+        let total_bits = bits.len();
+        let total_chars = total_bits.div_ceil(bits_per_char);
+        
+        // Use frame_length (in bits) to determine characters per row
+        // Each character represents bits_per_char bits
+        let chars_per_row = (self.viewer.frame_length / bits_per_char).max(1);
+        let total_rows = total_chars.div_ceil(chars_per_row);
+        
+        let char_width = 12.0;
+        let char_height = 20.0;
+        let offset_width = if self.byte_viewer.config.show_hex_offset { 90.0 } else { 0.0 };
+        
+        // The "Go to" flash is shared with the bit viewer
+        let flash = self.viewer.flash;
+        let flash_color = flash.and_then(|flash| flash.color(ui.input(|i| i.time)));
+        if flash_color.is_some() {
+            ui.ctx().request_repaint();
+        }
+        
+        let mut scroll_area = egui::ScrollArea::vertical()
+            .id_salt("ascii_viewer_scroll")
+            .auto_shrink([false, false]);
+        
+        if let Some(bit_pos) = jump_to_bit {
+            let row = bit_pos / bits_per_char / chars_per_row;
+            let row_height = char_height + ui.spacing().item_spacing.y;
+            scroll_area = scroll_area.vertical_scroll_offset(row as f32 * row_height);
+        }
+        
+        scroll_area
+            .show_rows(
+                ui,
+                char_height,
+                total_rows,
+                |ui, row_range| {
+                    egui::ScrollArea::horizontal()
+                        .id_salt("ascii_viewer_horizontal")
+                        .show(ui, |ui| {
+                            // Set a theme-aware background for better text visibility
+                            ui.painter().rect_filled(
+                                ui.max_rect(),
+                                0.0,
+                                ui.visuals().extreme_bg_color
+                            );
+                            
+                            // Baudot shift state carries over from the rows above
+                            let mut decoder = TextDecoder::starting_at(encoding, bits, bits_per_char, row_range.start * chars_per_row);
+                            
+                            // Only render visible rows
+                            for row in row_range {
+                                ui.horizontal(|ui| {
+                                    // Show offset if enabled
+                                    if self.byte_viewer.config.show_hex_offset {
+                                        let offset = row * chars_per_row;
+                                        ui.add_sized(
+                                            [offset_width, char_height],
+                                            egui::Label::new(
+                                                egui::RichText::new(format!("{:08X}  ", offset))
+                                                    .monospace()
+                                                    .color(egui::Color32::GRAY)
+                                            )
+                                        );
+                                    }
+                                    
+                                    // Draw characters - only convert codes we need for this row
+                                    let row_start = row * chars_per_row;
+                                    let row_end = (row_start + chars_per_row).min(total_chars);
+                                    
+                                    for char_idx in row_start..row_end {
+                                        // Convert only this single character from bits
+                                        let bit_start = char_idx * bits_per_char;
+                                        let bit_end = (bit_start + bits_per_char).min(total_bits);
+                                        let code = char_code(&bits[bit_start..bit_end], encoding);
+                                        
+                                        // Check if this byte is part of any pattern match
+                                        let pattern_match: Option<(egui::Color32, &str)> = self.match_index
+                                            .first_pattern(bit_start, bit_end)
+                                            .and_then(|pattern_idx| {
+                                                let pattern = self.patterns.get(pattern_idx)?;
+                                                Some((rgb(colors.color_of(pattern, pattern_idx)), pattern.name.as_str()))
+                                            });
+                                        
+                                        let decoded = decoder.decode(code);
+                                        let ch = decoded.unwrap_or('.');
+                                        
+                                        let (rect, response) = ui.allocate_exact_size(
+                                            egui::Vec2::new(char_width, char_height),
+                                            egui::Sense::hover(),
+                                        );
+                                        
+                                        // Draw background highlight for pattern matches
+                                        if let Some((pattern_color, _)) = pattern_match {
+                                            ui.painter().rect_filled(
+                                                rect,
+                                                2.0,
+                                                egui::Color32::from_rgba_unmultiplied(
+                                                    pattern_color.r(),
                                                     pattern_color.g(),
                                                     pattern_color.b(),
                                                     120
@@ -1307,13 +3951,25 @@ impl BitApp {
                                             );
                                         }
                                         
+                                        // Flash overlay after a "Go to"
+                                        if let (Some(flash), Some(color)) = (flash, flash_color)
+                                            && flash.overlaps(bit_start, bit_end) {
+                                            ui.painter().rect_filled(rect, 2.0, color.gamma_multiply(0.6));
+                                        }
+                                        
+                                        // Burst gaps are shared with the bit viewer too
+                                        let char_gaps = self.viewer.gaps.within(bit_start, bit_end);
+                                        if let Some(&(_, gap_secs)) = char_gaps.first() {
+                                            self.viewer.gaps.paint(ui.painter(), rect.min.x, rect.y_range(), ui.max_rect().x_range(), gap_secs);
+                                        }
+                                        
                                         // Choose color based on character type and pattern match
                                         let text_color = if pattern_match.is_some() {
                                             egui::Color32::BLACK
-                                        } else if byte >= 32 && byte <= 126 {
-                                            egui::Color32::BLACK
+                                        } else if decoded.is_some() {
+                                            ui.visuals().strong_text_color()
                                         } else {
-                                            egui::Color32::DARK_GRAY
+                                            ui.visuals().weak_text_color()
                                         };
                                         
                                         // Draw character
@@ -1328,14 +3984,17 @@ impl BitApp {
                                         // Show tooltip
                                         if response.hovered() {
                                             response.on_hover_ui(|ui| {
-                                                ui.label(format!("Byte: {}", byte_idx));
-                                                ui.label(format!("Value: 0x{:02X} ({})", byte, byte));
-                                                ui.label(format!("ASCII: '{}'", ch));
-                                                ui.label(format!("Binary: {:08b}", byte));
+                                                ui.label(format!("{}: {}", tr("Character"), char_idx));
+                                                ui.label(format!("{}: 0x{:02X} ({})", tr("Value"), code, code));
+                                                ui.label(format!("{}: '{}'", encoding.name(), ch));
+                                                ui.label(format!("{}: {:0width$b}", tr("Binary"), code, width = bits_per_char.min(encoding.code_bits())));
                                                 
                                                 if let Some((_, pattern_name)) = pattern_match {
                                                     ui.separator();
-                                                    ui.label(format!("🎯 Pattern: {}", pattern_name));
+                                                    ui.label(format!("🎯 {}: {}", tr("Pattern"), pattern_name));
+                                                }
+                                                for &(bit, gap_secs) in char_gaps {
+                                                    ui.label(crate::viewers::GapMarkers::hover_text(bit, gap_secs));
                                                 }
                                             });
                                         }
@@ -1349,27 +4008,1071 @@ impl BitApp {
     
     /// Run frame width analysis on the current bits
     pub fn run_frame_width_analysis(&mut self) {
-        use crate::analysis::find_best_width;
+        use crate::analysis::{find_best_width_among, parse_width_list};
         
-        let bits_to_analyze = if self.show_original {
-            &self.original_bits
-        } else {
-            &self.processed_bits
+        let (_, bits_to_analyze) = match self.analysis_bits() {
+            Ok(scoped) => scoped,
+            Err(e) => {
+                self.notifications.error(e);
+                return;
+            }
         };
         
         if bits_to_analyze.is_empty() {
-            self.error_message = Some("No data to analyze".to_string());
+            self.notifications.error("No data to analyze".to_string());
             return;
         }
         
-        // Run analysis
-        let analysis = find_best_width(
-            bits_to_analyze,
-            self.frame_width_min,
-            self.frame_width_max,
-            self.frame_width_delta,
-        );
+        let spec = if self.frame_width_use_list {
+            self.frame_width_list.trim().to_string()
+        } else {
+            format!("{}..{}", self.frame_width_min, self.frame_width_max)
+        };
+        let widths = match parse_width_list(&spec) {
+            Ok(widths) => widths,
+            Err(e) => {
+                self.notifications.error(format!("Invalid widths: {}", e));
+                return;
+            }
+        };
+        let delta = self.frame_width_delta;
+        let scan = (self.current_worksheet().id, spec, delta);
         
-        self.frame_width_analysis = Some(analysis);
+        // Large inputs are analyzed on a background thread so the UI stays responsive
+        if bits_to_analyze.len() as u64 > ASYNC_THRESHOLD_BYTES * 8 {
+            let input = match self.analysis_input() {
+                Ok((_, input)) => input,
+                Err(e) => {
+                    self.notifications.error(e);
+                    return;
+                }
+            };
+            self.frame_width_pending_scan = Some(scan);
+            self.analysis_task = Some(BackgroundTask::spawn("Finding Frame Width", move |progress| {
+                progress.report(0.0, format!("Scoring {} widths", widths.len()));
+                find_best_width_among(input.bits(), &widths, delta, progress)
+                    .map(AnalysisResult::FrameWidth)
+            }));
+            return;
+        }
+        
+        // Run analysis
+        let analysis = find_best_width_among(bits_to_analyze, &widths, delta, &ProgressReporter::new());
+        self.frame_width_pending_scan = Some(scan);
+        if let Some(analysis) = analysis {
+            self.finish_frame_width_analysis(analysis);
+        }
+    }
+    
+    /// Show a finished frame width analysis and remember the scan on the worksheet it ran on
+    fn finish_frame_width_analysis(&mut self, analysis: FrameWidthAnalysis) {
+        if let Some((id, widths, delta)) = self.frame_width_pending_scan.take()
+            && let Some(worksheet) = self.worksheets.iter_mut().find(|ws| ws.id == id)
+        {
+            let scans = &mut worksheet.frame_width_scans;
+            // Running the same scan again replaces its old entry
+            scans.retain(|scan| scan.widths != widths || scan.delta != delta);
+            scans.push(FrameWidthScan { widths, delta, best_width: analysis.best_width, best_score: analysis.best_score });
+            let excess = scans.len().saturating_sub(MAX_FRAME_WIDTH_SCANS);
+            scans.drain(..excess);
+        }
+        self.frame_width_analysis = Some(analysis);
+    }
+    
+    /// Put a previous scan's widths and delta back into the Frame Width Finder
+    pub fn restore_frame_width_scan(&mut self, scan: &FrameWidthScan) {
+        self.frame_width_use_list = true;
+        self.frame_width_list = scan.widths.clone();
+        self.frame_width_delta = scan.delta;
+    }
+    
+    /// Compute the autocorrelation spectrum of the current bits
+    pub fn run_periodicity_analysis(&mut self) {
+        use crate::analysis::periodicity_spectrum;
+        
+        let (_, bits_to_analyze) = match self.analysis_bits() {
+            Ok(scoped) => scoped,
+            Err(e) => {
+                self.notifications.error(e);
+                return;
+            }
+        };
+        
+        if bits_to_analyze.is_empty() {
+            self.notifications.error("No data to analyze".to_string());
+            return;
+        }
+        
+        let spectrum = periodicity_spectrum(bits_to_analyze, self.periodicity_min, self.periodicity_max);
+        self.periodicity_spectrum = Some(spectrum);
+    }
+    
+    /// Decode HDLC frames from the current bits
+    pub fn run_hdlc_decode(&mut self) {
+        use crate::analysis::decode_hdlc;
+        
+        let (offset, bits_to_decode) = match self.analysis_bits() {
+            Ok(scoped) => scoped,
+            Err(e) => {
+                self.notifications.error(e);
+                return;
+            }
+        };
+        
+        if bits_to_decode.is_empty() {
+            self.notifications.error("No data to analyze".to_string());
+            return;
+        }
+        
+        let mut frames = decode_hdlc(bits_to_decode, self.hdlc_options);
+        for frame in &mut frames {
+            frame.start_bit += offset;
+        }
+        self.hdlc_frames = Some(frames);
+        self.hdlc_selected = None;
+    }
+    
+    /// Split the current bits into frames at each match of the framer's sync word, or between
+    /// the sync word and the end marker when one is given
+    pub fn run_framer(&mut self) {
+        use crate::analysis::{split_frames, split_frames_between};
+        
+        let mut sync = match Pattern::new(
+            "Sync".to_string(),
+            self.framer_sync_format,
+            self.framer_sync_input.clone(),
+            self.framer_sync_garbles,
+        ) {
+            Ok(sync) => sync,
+            Err(e) => {
+                self.notifications.error(format!("Invalid sync word: {}", e));
+                return;
+            }
+        };
+        
+        let mut end_marker = None;
+        if !self.framer_end_input.trim().is_empty() {
+            match Pattern::new(
+                "End".to_string(),
+                self.framer_sync_format,
+                self.framer_end_input.clone(),
+                self.framer_sync_garbles,
+            ) {
+                Ok(end) => end_marker = Some(end),
+                Err(e) => {
+                    self.notifications.error(format!("Invalid end marker: {}", e));
+                    return;
+                }
+            }
+        }
+        
+        let (offset, bits_to_split) = match self.analysis_bits() {
+            Ok(scoped) => scoped,
+            Err(e) => {
+                self.notifications.error(e);
+                return;
+            }
+        };
+        if bits_to_split.is_empty() {
+            self.notifications.error("No data to analyze".to_string());
+            return;
+        }
+        
+        let mut frames = match &mut end_marker {
+            Some(end) => split_frames_between(bits_to_split, &mut sync, end, self.framer_crc),
+            None => split_frames(bits_to_split, &mut sync, self.framer_frame_length, self.framer_crc),
+        };
+        for frame in &mut frames {
+            frame.position += offset;
+        }
+        self.framer_frames = Some(frames);
+        self.framer_selected = None;
+    }
+    
+    /// Restrict the operation at `index` to the framer's frames by wrapping it in a
+    /// Conditional over their bit ranges
+    pub fn restrict_operation_to_frames(&mut self, index: usize) {
+        let Some(frames) = self.framer_frames.as_ref().filter(|frames| !frames.is_empty()) else {
+            self.notifications.error("No frames; split the stream first".to_string());
+            return;
+        };
+        let Some(op) = self.operations.get(index) else {
+            return;
+        };
+        if op.is_source() {
+            self.notifications.error("File and worksheet loads can't be restricted to frames".to_string());
+            return;
+        }
+        let ranges = frames.iter().map(|frame| (frame.position, frame.position + frame.length)).collect();
+        let op = self.operations.remove(index);
+        self.operations.insert(index, BitOperation::Conditional {
+            name: format!("{} in frames", op.name()),
+            operation: Box::new(op),
+            condition: BitCondition::Ranges { ranges },
+            enabled: true,
+        });
+        self.clear_pattern_matches();
+        self.operations_changed();
+    }
+    
+    /// Write the bits of every frame, one after another, to a file
+    pub fn export_frame_bits(&mut self) {
+        use crate::storage::write_bits_to_file;
+        
+        let Some(frames) = self.framer_frames.as_ref().filter(|frames| !frames.is_empty()) else {
+            self.notifications.error("No frames to export".to_string());
+            return;
+        };
+        let bits = self.display_bits();
+        let mut joined: BitVec<u8, Msb0> = BitVec::new();
+        for frame in frames {
+            let end = (frame.position + frame.length).min(bits.len());
+            joined.extend_from_bitslice(&bits[frame.position.min(end)..end]);
+        }
+        
+        let Some(path) = rfd::FileDialog::new().set_file_name("frames.bin").save_file() else {
+            return;
+        };
+        if let Err(e) = write_bits_to_file(&path, &joined) {
+            self.notifications.error(format!("Failed to write {}: {}", path.display(), e));
+        }
+    }
+    
+    /// Write each round-robin channel of the displayed bits to its own file in a chosen folder,
+    /// named after the current file with `_ch0`, `_ch1`, ... appended
+    pub fn export_channels(&mut self) {
+        use crate::processing::split_channels;
+        use crate::storage::write_bits_to_file;
+        
+        if self.display_bits().is_empty() {
+            self.notifications.error("No data to export".to_string());
+            return;
+        }
+        let Some(dir) = rfd::FileDialog::new().pick_folder() else {
+            return;
+        };
+        let stem = self.current_file_path.as_ref()
+            .and_then(|path| path.file_stem())
+            .map_or_else(|| "channel".to_string(), |stem| stem.to_string_lossy().to_string());
+        
+        let channels = split_channels(self.display_bits(), self.channel_count, self.channel_unit_bits);
+        for (index, channel) in channels.iter().enumerate() {
+            let path = dir.join(format!("{}_ch{}.bin", stem, index));
+            if let Err(e) = write_bits_to_file(&path, channel) {
+                self.notifications.error(format!("Failed to write {}: {}", path.display(), e));
+                return;
+            }
+        }
+    }
+    
+    /// Select frame `index` in the framer and jump to it in the viewers
+    pub fn select_frame(&mut self, index: usize, now: f64) {
+        let Some(frame) = self.framer_frames.as_ref().and_then(|frames| frames.get(index)) else {
+            return;
+        };
+        let (position, length) = (frame.position, frame.length);
+        self.framer_selected = Some(index);
+        self.jump_to_range(position, length, now);
+    }
+    
+    /// Start listening for bytes to append to the original bits
+    pub fn start_network_source(&mut self) {
+        if self.mapped_original.is_some() {
+            self.notifications.error("A memory-mapped file can't be appended to; open a smaller file or a new worksheet".to_string());
+            return;
+        }
+        
+        match NetworkListener::start(self.source_protocol, "0.0.0.0", self.source_port) {
+            Ok(listener) => {
+                self.network_listener = Some(listener);
+                self.received_segments.clear();
+            }
+            Err(e) => self.notifications.error(e),
+        }
+    }
+    
+    pub fn stop_network_source(&mut self) {
+        self.network_listener = None;
+    }
+    
+    /// Start or stop reloading the current file when it changes on disk. A watched file is
+    /// read into memory, so one that is mapped now is loaded again.
+    pub fn set_watch_file(&mut self, watch: bool) {
+        self.file_watcher = match (&self.current_file_path, watch) {
+            (Some(path), true) => Some(FileWatcher::new(path.clone())),
+            _ => None,
+        };
+        if self.file_watcher.is_some() && (self.mapped_original.is_some() || self.mapped_processed.is_some()) {
+            self.sync_to_worksheet();
+            self.load_from_worksheet();
+        }
+    }
+    
+    /// Reload the current file and reapply the operations if it was rewritten since the last
+    /// poll. The views keep their scroll position, since only their data is replaced.
+    pub fn poll_file_watch(&mut self) {
+        // Picked up on a later poll, since the change stays pending
+        if self.is_loading() || self.is_processing_operations() {
+            return;
+        }
+        let Some(watcher) = &mut self.file_watcher else {
+            return;
+        };
+        // Follow the current file when the worksheet or file changes
+        match &self.current_file_path {
+            Some(path) if path.as_path() == watcher.path() => {}
+            Some(path) => {
+                *watcher = FileWatcher::new(path.clone());
+                return;
+            }
+            None => return,
+        }
+        if !watcher.poll() {
+            return;
+        }
+        
+        self.sync_to_worksheet();
+        self.load_from_worksheet();
+    }
+    
+    /// Append whatever the network source received since the last frame and reapply the operations
+    pub fn poll_network_source(&mut self) {
+        // Leave data queued until the current run finishes, so runs don't pile up
+        if self.is_processing_operations() {
+            return;
+        }
+        let Some(listener) = &self.network_listener else {
+            return;
+        };
+        
+        let chunks = listener.drain();
+        let stopped = !listener.is_running();
+        
+        if !chunks.is_empty() {
+            for chunk in chunks {
+                let bit_offset = self.original_bits.len();
+                self.original_bits.extend_from_bitslice(chunk.bytes.view_bits::<Msb0>());
+                self.received_segments.push(ReceivedSegment {
+                    received_at: chunk.received_at,
+                    peer: chunk.peer,
+                    bit_offset,
+                    bit_len: chunk.bytes.len() * 8,
+                });
+            }
+            self.apply_operations();
+        }
+        
+        if stopped {
+            self.network_listener = None;
+            self.notifications.error("Network source stopped after a socket error".to_string());
+        }
+    }
+    
+    /// Find sequences that repeat unusually often in the current bits
+    pub fn run_repeat_search(&mut self) {
+        use crate::analysis::find_repeats;
+        
+        let (offset, bits_to_analyze) = match self.analysis_bits() {
+            Ok(scoped) => scoped,
+            Err(e) => {
+                self.notifications.error(e);
+                return;
+            }
+        };
+        
+        if bits_to_analyze.is_empty() {
+            self.notifications.error("No data to analyze".to_string());
+            return;
+        }
+        
+        let mut results = find_repeats(bits_to_analyze, self.repeat_min_len, self.repeat_max_len, 50);
+        for candidate in &mut results {
+            for position in &mut candidate.positions {
+                *position += offset;
+            }
+        }
+        self.repeat_results = Some(results);
+    }
+    
+    /// Column statistics of the frames in the analysis scope at the current frame width, or of
+    /// only those at one position in each super-frame
+    pub fn run_frame_stats(&mut self) {
+        use crate::analysis::{frame_stats, super_frame_stats};
+        
+        let frame_length = self.viewer.frame_length;
+        let super_frame = self.viewer.super_frame_length;
+        let position = self.frame_stats_super_position.filter(|&position| super_frame > 1 && position < super_frame);
+        let bits_to_analyze = match self.analysis_bits() {
+            Ok((_, bits)) => bits,
+            Err(e) => {
+                self.notifications.error(e);
+                return;
+            }
+        };
+        if bits_to_analyze.len() < frame_length * 2 {
+            self.notifications.error(format!("Need at least two {}-bit frames to compare", frame_length));
+            return;
+        }
+        
+        let column_bits = self.frame_stats_column_bits;
+        let stats = match position {
+            Some(position) => super_frame_stats(bits_to_analyze, frame_length, column_bits, super_frame, position),
+            None => frame_stats(bits_to_analyze, frame_length, column_bits),
+        };
+        if stats.is_empty() {
+            self.notifications.error("No frames at that position in the super-frame");
+            return;
+        }
+        self.frame_stats = Some((frame_length, stats));
+        self.frame_stats_super_frame = position.map(|position| (position, super_frame));
+    }
+    
+    /// Hamming distances between consecutive frames in the analysis scope at the current frame width
+    pub fn run_frame_distances(&mut self) {
+        use crate::analysis::frame_distances;
+        
+        let frame_length = self.viewer.frame_length;
+        let (offset, bits_to_analyze) = match self.analysis_bits() {
+            Ok(scoped) => scoped,
+            Err(e) => {
+                self.notifications.error(e);
+                return;
+            }
+        };
+        if bits_to_analyze.len() < frame_length * 2 {
+            self.notifications.error(format!("Need at least two {}-bit frames to compare", frame_length));
+            return;
+        }
+        
+        let distances = frame_distances(bits_to_analyze, frame_length);
+        self.frame_distances = Some((offset, distances));
+    }
+    
+    /// Field specs for the frames CSV from the byte view columns, which must be relative to
+    /// frames rather than display rows to line up
+    pub fn frames_csv_spec_from_columns(&mut self) {
+        let columns = &self.byte_viewer.config.columns;
+        if columns.is_empty() {
+            self.notifications.error("No columns to copy; add some in the byte view first");
+            return;
+        }
+        self.frames_csv_spec = columns
+            .iter()
+            .map(|column| {
+                let length = column.bit_end + 1 - column.bit_start;
+                let format = if length > 64 { "hex" } else { "int" };
+                format!("{} {}..{} {}\n", column.label, column.bit_start, column.bit_end + 1, format)
+            })
+            .collect();
+    }
+    
+    /// Write the frames in the analysis scope at the current frame width as CSV, one row per frame
+    pub fn export_frames_csv(&mut self) {
+        use crate::analysis::{frames_csv, parse_frame_fields};
+        
+        let frame_length = self.viewer.frame_length;
+        let csv = parse_frame_fields(&self.frames_csv_spec).and_then(|fields| {
+            let (offset, bits) = self.analysis_bits()?;
+            frames_csv(bits, frame_length, &fields, offset)
+        });
+        let csv = match csv {
+            Ok(csv) => csv,
+            Err(e) => {
+                self.notifications.error(e);
+                return;
+            }
+        };
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter("CSV", &["csv"])
+            .set_file_name("frames.csv")
+            .save_file()
+            && let Err(e) = std::fs::write(&path, csv)
+        {
+            self.notifications.error(format!("Failed to write {}: {}", path.display(), e));
+        }
+    }
+    
+    /// Look for timestamp fields in the frames in the analysis scope at the current frame width
+    pub fn run_timestamp_detection(&mut self) {
+        use crate::analysis::detect_timestamps;
+        
+        let frame_length = self.viewer.frame_length;
+        match self.analysis_bits() {
+            Ok((_, bits)) => self.timestamp_candidates = Some((frame_length, detect_timestamps(bits, frame_length))),
+            Err(e) => self.notifications.error(e),
+        }
+    }
+    
+    /// Add timestamp candidate `index` as a frame-relative byte view column decoded as a time
+    pub fn add_timestamp_column(&mut self, index: usize) {
+        use crate::viewers::ByteColumn;
+        
+        let Some((frame_length, candidate)) = self.timestamp_candidates.as_ref()
+            .and_then(|(frame_length, candidates)| Some((*frame_length, candidates.get(index)?.clone()))) else {
+            return;
+        };
+        let config = &mut self.byte_viewer.config;
+        if config.column_frame_length != Some(frame_length) {
+            if !config.columns.is_empty() {
+                self.notifications.error(format!(
+                    "The byte view columns aren't relative to {}-bit frames; clear them or make them frame-relative first",
+                    frame_length
+                ));
+                return;
+            }
+            config.column_frame_length = Some(frame_length);
+        }
+        let mut column = ByteColumn::new(
+            "Timestamp".to_string(),
+            candidate.offset,
+            candidate.offset + candidate.format.bits() - 1,
+            [120, 200, 120],
+        );
+        column.timestamp = Some(candidate.format);
+        config.columns.push(column);
+    }
+    
+    /// Add repeat candidate `index` to the Pattern Locator
+    pub fn add_repeat_as_pattern(&mut self, index: usize) {
+        let Some(candidate) = self.repeat_results.as_ref().and_then(|results| results.get(index)) else {
+            return;
+        };
+        
+        let name = format!("Repeat {}", self.patterns.len() + 1);
+        match Pattern::new(name, PatternFormat::Bits, candidate.bits_string(), 0) {
+            Ok(pattern) => {
+                self.patterns.push(pattern);
+                self.selected_pattern = Some(self.patterns.len() - 1);
+                self.show_pattern_locator = true;
+            }
+            Err(e) => self.notifications.error(format!("Invalid pattern: {}", e)),
+        }
+    }
+    
+    /// Add the longest run of near-constant columns of the `frame_length`-bit frames in the
+    /// analysis scope to the Pattern Locator as a sync pattern
+    pub fn suggest_sync_pattern(&mut self, frame_length: usize) {
+        use crate::analysis::{suggest_sync_pattern, MIN_SYNC_BITS};
+        
+        let suggestion = match self.analysis_bits() {
+            Ok((_, bits)) => suggest_sync_pattern(bits, frame_length),
+            Err(e) => {
+                self.notifications.error(e);
+                return;
+            }
+        };
+        let Some(suggestion) = suggestion else {
+            self.notifications.warning(format!(
+                "No run of {} or more constant bits in {}-bit frames",
+                MIN_SYNC_BITS, frame_length
+            ));
+            return;
+        };
+        
+        let name = format!("Sync @{} ({}-bit frames)", suggestion.offset, frame_length);
+        match Pattern::new(name, PatternFormat::Bits, suggestion.bits_string(), 0) {
+            Ok(pattern) => {
+                self.patterns.push(pattern);
+                self.selected_pattern = Some(self.patterns.len() - 1);
+                self.show_pattern_locator = true;
+            }
+            Err(e) => self.notifications.error(format!("Invalid pattern: {}", e)),
+        }
+    }
+    
+    /// Compute every checksum over the bits in the analysis scope
+    pub fn run_checksums(&mut self) {
+        use crate::analysis::checksum_report;
+        
+        match self.analysis_bits() {
+            Ok((offset, bits)) => self.checksum_report = Some(checksum_report(bits, offset)),
+            Err(e) => self.notifications.error(e),
+        }
+    }
+    
+    /// Decode the structure template at the offset it names
+    pub fn run_structure_decode(&mut self) {
+        use crate::analysis::{decode_structure, parse_template};
+        
+        let result = parse_template(&self.structure_template).and_then(|template| {
+            let offset = crate::utils::eval_expression(&self.structure_offset)
+                .map_err(|e| format!("Invalid offset: {}", e))?;
+            decode_structure(&template, self.display_bits(), offset)
+        });
+        match result {
+            Ok(root) => self.structure_result = Some(root),
+            Err(e) => self.notifications.error(e),
+        }
+    }
+    
+    /// Replace the byte view columns with the decoded structure's leaf fields, repeating every
+    /// record length so that each record starting at the decode offset lines up
+    pub fn structure_to_byte_columns(&mut self) {
+        use crate::viewers::ByteColumn;
+        
+        let Some(root) = &self.structure_result else {
+            return;
+        };
+        let period = root.bit_len;
+        if period == 0 {
+            self.notifications.error("The decoded structure is empty".to_string());
+            return;
+        }
+        let shift = root.bit_start % period;
+        let mut columns = Vec::new();
+        let mut wrapped = 0;
+        for leaf in root.leaves().into_iter().filter(|leaf| leaf.bit_len > 0) {
+            let start = (leaf.bit_start - root.bit_start + shift) % period;
+            if start + leaf.bit_len > period {
+                wrapped += 1;
+                continue;
+            }
+            columns.push(ByteColumn::new(leaf.name.clone(), start, start + leaf.bit_len - 1, leaf.color));
+        }
+        self.byte_viewer.config.columns = columns;
+        self.byte_viewer.config.column_frame_length = Some(period);
+        if wrapped > 0 {
+            self.notifications.error(format!(
+                "{} field(s) cross a {}-bit record boundary from bit 0 and were left out; use an offset that is a multiple of the record length",
+                wrapped, period
+            ));
+        }
+    }
+    
+    /// Replace the byte view columns with a sync field for the chosen pattern and equal fields
+    /// after it, frame-relative at the current frame width and anchored on the first match
+    pub fn auto_layout_columns(&mut self) {
+        let Some(pattern) = self.patterns.get(self.auto_layout_pattern) else {
+            self.notifications.error("Add a sync pattern first".to_string());
+            return;
+        };
+        let Some(first) = pattern.matches.first() else {
+            self.notifications.error(format!("Pattern '{}' has no matches; search for it first", pattern.name));
+            return;
+        };
+        let frame_bits = self.viewer.frame_length;
+        let palette = self.settings.bit_colors.clone().unwrap_or_default().pattern_palette;
+        match crate::analysis::anchored_columns(
+            &pattern.name,
+            first.position,
+            pattern.bits.len(),
+            frame_bits,
+            self.auto_layout_field_bytes.max(1) * 8,
+            &palette,
+        ) {
+            Ok(columns) => {
+                self.byte_viewer.config.columns = columns;
+                self.byte_viewer.config.column_frame_length = Some(frame_bits);
+            }
+            Err(e) => self.notifications.error(e),
+        }
+    }
+    
+    /// Suggest repeating XOR keys that turn the current data into ASCII text
+    pub fn run_xor_key_search(&mut self) {
+        use crate::analysis::recover_xor_keys;
+        
+        let (offset, bits_to_analyze) = match self.analysis_bits() {
+            Ok(scoped) => scoped,
+            Err(e) => {
+                self.notifications.error(e);
+                return;
+            }
+        };
+        
+        if bits_to_analyze.len() < 16 {
+            self.notifications.error("Need at least two bytes to analyze".to_string());
+            return;
+        }
+        
+        let mut results = recover_xor_keys(bits_to_analyze, self.xor_min_key_len, self.xor_max_key_len, 20);
+        // Keys found in a scoped range are re-phased so they line up when applied from bit 0
+        for candidate in &mut results {
+            let len = candidate.key.len();
+            candidate.key.rotate_right((offset / 8) % len);
+        }
+        self.xor_results = Some(results);
+    }
+    
+    /// Append an XOR With Key operation for key candidate `index`
+    pub fn add_xor_key_operation(&mut self, index: usize) {
+        let Some(candidate) = self.xor_results.as_ref().and_then(|results| results.get(index)) else {
+            return;
+        };
+        
+        let name = match candidate.key_ascii() {
+            Some(text) => format!("XOR \"{}\"", text),
+            None => format!("XOR {}", candidate.key_hex()),
+        };
+        self.operations.push(BitOperation::XorKey { name, key: candidate.key.clone(), enabled: true });
+        self.clear_pattern_matches();
+        self.operations_changed();
+    }
+    
+    /// Ask for a folder and analyze every file in it in the background, using the current
+    /// operations, patterns, frame width range and framer settings
+    pub fn start_batch_report(&mut self) {
+        use crate::analysis::{list_capture_files, run_batch, BatchFramer, BatchSettings};
+        
+        let Some(folder) = rfd::FileDialog::new().pick_folder() else {
+            return;
+        };
+        let files = match list_capture_files(&folder) {
+            Ok(files) => files,
+            Err(e) => {
+                self.notifications.error(e);
+                return;
+            }
+        };
+        if files.is_empty() {
+            self.notifications.error(format!("No files in {}", folder.display()));
+            return;
+        }
+        
+        let framer = Pattern::new(
+            "Sync".to_string(),
+            self.framer_sync_format,
+            self.framer_sync_input.clone(),
+            self.framer_sync_garbles,
+        )
+        .ok()
+        .map(|sync| BatchFramer { sync, frame_length: self.framer_frame_length, crc: self.framer_crc });
+        
+        let settings = BatchSettings {
+            operations: self.operations.clone(),
+            patterns: self.patterns.clone(),
+            min_width: self.frame_width_min,
+            max_width: self.frame_width_max,
+            framer,
+        };
+        
+        let (tx, rx) = channel();
+        thread::spawn(move || {
+            let _ = tx.send(run_batch(&files, &settings));
+        });
+        
+        self.batch_pattern_names = self.patterns.iter().map(|pattern| pattern.name.clone()).collect();
+        self.batch_folder = Some(folder);
+        self.batch_rows = None;
+        self.batch_receiver = Some(rx);
+    }
+    
+    /// Pick up the batch results once the background run has finished
+    pub fn poll_batch_report(&mut self) {
+        let Some(receiver) = &self.batch_receiver else {
+            return;
+        };
+        match receiver.try_recv() {
+            Ok(rows) => {
+                self.batch_rows = Some(rows);
+                self.batch_receiver = None;
+            }
+            Err(std::sync::mpsc::TryRecvError::Empty) => {}
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                self.batch_receiver = None;
+                self.notifications.error("Batch report failed".to_string());
+            }
+        }
+    }
+    
+    pub fn export_batch_csv(&mut self) {
+        use crate::analysis::batch_csv;
+        
+        let Some(rows) = &self.batch_rows else {
+            return;
+        };
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter("CSV", &["csv"])
+            .set_file_name("batch_report.csv")
+            .save_file()
+        {
+            let csv = batch_csv(rows, &self.batch_pattern_names);
+            if let Err(e) = std::fs::write(&path, csv) {
+                self.notifications.error(format!("Failed to write {}: {}", path.display(), e));
+            }
+        }
+    }
+    
+    /// Write the byte view columns as a Kaitai Struct or Wireshark dissector skeleton, one
+    /// record per column period
+    pub fn export_protocol_definition(&mut self, format: crate::analysis::ProtocolFormat) {
+        use crate::analysis::export_protocol;
+        
+        let config = &self.byte_viewer.config;
+        if config.columns.is_empty() {
+            self.notifications.error("No columns to export; add some in the byte view first".to_string());
+            return;
+        }
+        
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter(format.name(), &[format.extension()])
+            .set_file_name(format!("protocol.{}", format.extension()))
+            .save_file()
+        else {
+            return;
+        };
+        
+        let name = path.file_stem().map_or_else(|| "protocol".to_string(), |stem| stem.to_string_lossy().into_owned());
+        let contents = export_protocol(format, &name, &config.columns, config.column_period());
+        if let Err(e) = std::fs::write(&path, contents) {
+            self.notifications.error(format!("Failed to write {}: {}", path.display(), e));
+        }
+    }
+    
+    /// Write the column documentation, operation chain, region snapshots and pattern matches
+    /// to one HTML file
+    pub fn export_html_report(&mut self) {
+        use crate::analysis::HtmlReport;
+        
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("HTML", &["html"])
+            .set_file_name("protocol_report.html")
+            .save_file()
+        else {
+            return;
+        };
+        
+        let html = HtmlReport {
+            title: &self.report_title,
+            config: &self.byte_viewer.config,
+            bits: self.display_bits(),
+            regions: &self.report_regions,
+            patterns: &self.patterns,
+            operations: &self.operations,
+        }
+        .render();
+        if let Err(e) = std::fs::write(&path, html) {
+            self.notifications.error(format!("Failed to write {}: {}", path.display(), e));
+        }
+    }
+    
+    /// Write every pattern's matches, with surrounding bytes, to a CSV or JSON file
+    pub fn export_pattern_matches(&mut self) {
+        use crate::analysis::{match_records, matches_csv, matches_json};
+        
+        let records = match_records(&self.patterns, self.display_bits(), self.match_export_context_bytes);
+        if records.is_empty() {
+            self.notifications.error("No matches to export; search for a pattern first".to_string());
+            return;
+        }
+        
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("CSV", &["csv"])
+            .add_filter("JSON", &["json"])
+            .set_file_name("matches.csv")
+            .save_file()
+        else {
+            return;
+        };
+        
+        let is_json = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        let contents = if is_json { matches_json(&records) } else { Ok(matches_csv(&records)) };
+        let result = contents.and_then(|contents| {
+            std::fs::write(&path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+        });
+        if let Err(e) = result {
+            self.notifications.error(e);
+        }
+    }
+    
+    /// Search pattern `index` within the analysis scope
+    pub fn search_pattern(&mut self, index: usize) {
+        let range = match self.analysis_range() {
+            Ok(range) => range,
+            Err(e) => {
+                self.notifications.error(e);
+                return;
+            }
+        };
+        let mut pattern = self.patterns[index].clone();
+        
+        // Large inputs are searched on a background thread so the UI stays responsive
+        if range.len() as u64 > ASYNC_THRESHOLD_BYTES * 8 {
+            let (offset, input) = match self.analysis_input() {
+                Ok(scoped) => scoped,
+                Err(e) => {
+                    self.notifications.error(e);
+                    return;
+                }
+            };
+            self.analysis_task = Some(BackgroundTask::spawn("Searching", move |progress| {
+                progress.report(0.0, format!("Searching for {}", pattern.name));
+                let bits = input.bits();
+                pattern.search_range(bits, 0..bits.len(), progress).then(|| {
+                    for m in &mut pattern.matches {
+                        m.position += offset;
+                    }
+                    AnalysisResult::PatternMatches { index, pattern }
+                })
+            }));
+            return;
+        }
+        
+        pattern.search_range(self.display_bits(), range, &ProgressReporter::new());
+        self.patterns[index] = pattern;
+        self.selected_pattern = Some(index);
+        self.current_match = None;
+    }
+    
+    /// Position of the current match among the selected pattern's matches, counting from 1,
+    /// and how many matches it has
+    pub fn match_position(&self) -> Option<(usize, usize)> {
+        let index = self.selected_pattern?;
+        let total = self.patterns.get(index)?.matches.len();
+        let current = self.current_match.filter(|&(pattern, m)| pattern == index && m < total);
+        Some((current.map_or(0, |(_, m)| m + 1), total))
+    }
+    
+    /// Scroll the views to match `match_index` of the selected pattern and highlight it
+    pub fn go_to_match(&mut self, match_index: usize, now: f64) {
+        let Some(index) = self.selected_pattern else {
+            return;
+        };
+        let Some(m) = self.patterns.get(index).and_then(|pattern| pattern.matches.get(match_index)) else {
+            return;
+        };
+        let (position, len) = (m.position, m.actual_bits.len().max(1));
+        self.viewer.clear_highlights();
+        self.viewer.add_highlight_range(position, len);
+        self.jump_to_range(position, len, now);
+        self.current_match = Some((index, match_index));
+    }
+    
+    /// Step to the selected pattern's next match, or the previous one when `forward` is false,
+    /// wrapping around at either end
+    pub fn step_match(&mut self, forward: bool, now: f64) {
+        let Some(pattern) = self.selected_pattern.and_then(|index| self.patterns.get(index)) else {
+            self.notifications.warning("Select a pattern in the Pattern Locator to step through its matches");
+            return;
+        };
+        if pattern.matches.is_empty() {
+            self.notifications.warning(format!("'{}' has no matches; search for it first", pattern.name));
+            return;
+        }
+        let target = match self.match_position() {
+            Some((current, total)) if current > 0 && forward => current % total,
+            Some((current, total)) if current > 0 => (current + total - 2) % total,
+            Some((_, total)) if !forward => total - 1,
+            _ => 0,
+        };
+        self.go_to_match(target, now);
+    }
+    
+    /// Search every pattern within the analysis scope, for the combined timeline
+    pub fn search_all_patterns(&mut self) {
+        use rayon::prelude::*;
+        
+        if self.patterns.is_empty() {
+            return;
+        }
+        let range = match self.analysis_range() {
+            Ok(range) => range,
+            Err(e) => {
+                self.notifications.error(e);
+                return;
+            }
+        };
+        let mut patterns = self.patterns.clone();
+        
+        if range.len() as u64 > ASYNC_THRESHOLD_BYTES * 8 {
+            let (offset, input) = match self.analysis_input() {
+                Ok(scoped) => scoped,
+                Err(e) => {
+                    self.notifications.error(e);
+                    return;
+                }
+            };
+            self.analysis_task = Some(BackgroundTask::spawn("Searching", move |progress| {
+                let total = patterns.len();
+                let bits = input.bits();
+                for (i, pattern) in patterns.iter_mut().enumerate() {
+                    progress.report(i as f32 / total as f32, format!("Searching for {} ({}/{})", pattern.name, i + 1, total));
+                    if !pattern.search_range(bits, 0..bits.len(), &ProgressReporter::new()) || progress.is_cancelled() {
+                        return None;
+                    }
+                    for m in &mut pattern.matches {
+                        m.position += offset;
+                    }
+                }
+                Some(AnalysisResult::AllPatternMatches(patterns))
+            }));
+            return;
+        }
+        
+        let bits = self.display_bits();
+        patterns.par_iter_mut().for_each(|pattern| {
+            pattern.search_range(bits, range.clone(), &ProgressReporter::new());
+        });
+        self.patterns = patterns;
+    }
+    
+    /// Search the pattern at `index` through the processed bits of every worksheet in the background
+    pub fn search_session(&mut self, index: usize) {
+        let Some(pattern) = self.patterns.get(index).cloned() else {
+            return;
+        };
+        self.sync_to_worksheet();
+        let worksheets = self.worksheets.clone();
+        let current = self.current_worksheet_index;
+        let current_bits = self.processed_view().to_bitvec();
+        self.analysis_task = Some(BackgroundTask::spawn("Searching worksheets", move |progress| {
+            crate::analysis::search_session(&pattern, &worksheets, current, &current_bits, progress)
+                .map(|results| AnalysisResult::SessionMatches { pattern: pattern.name.clone(), results })
+        }));
+    }
+    
+    /// Open the worksheet with `worksheet_id` if it isn't already and jump to a match in it
+    pub fn jump_to_worksheet_match(&mut self, worksheet_id: u64, bit_pos: usize, len: usize, now: f64) {
+        let Some(index) = self.worksheets.iter().position(|ws| ws.id == worksheet_id) else {
+            self.notifications.error("That worksheet no longer exists".to_string());
+            return;
+        };
+        if index != self.current_worksheet_index {
+            self.switch_worksheet(index);
+            self.show_original = false;
+        }
+        self.jump_to_range(bit_pos, len, now);
+    }
+    
+    /// Apply the background analysis result once its task has finished
+    pub fn poll_analysis_task(&mut self) {
+        let Some(result) = self.analysis_task.as_ref().and_then(|task| task.try_finish()) else {
+            return;
+        };
+        self.analysis_task = None;
+        match result {
+            Ok(Some(AnalysisResult::FrameWidth(analysis))) => {
+                self.finish_frame_width_analysis(analysis);
+            }
+            Ok(Some(AnalysisResult::PatternMatches { index, pattern })) => {
+                // The pattern may have been removed or edited while the search ran
+                if let Some(current) = self.patterns.get_mut(index)
+                    && current.bits == pattern.bits
+                {
+                    current.matches = pattern.matches;
+                    self.selected_pattern = Some(index);
+                    self.current_match = None;
+                }
+            }
+            Ok(Some(AnalysisResult::SessionMatches { pattern, results })) => {
+                self.session_matches = Some((pattern, results));
+            }
+            Ok(Some(AnalysisResult::AllPatternMatches(patterns))) => {
+                for (current, pattern) in self.patterns.iter_mut().zip(patterns) {
+                    if current.bits == pattern.bits {
+                        current.matches = pattern.matches;
+                    }
+                }
+            }
+            Ok(None) => {}
+            Err(e) => self.notifications.error(e),
+        }
+    }
+    
+    /// Stop the background analysis; its partial result is discarded
+    pub fn cancel_analysis(&mut self) {
+        if let Some(task) = self.analysis_task.take() {
+            task.cancel();
+        }
     }
 }
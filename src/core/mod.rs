@@ -1,3 +0,0 @@
-pub mod types;
-
-pub use types::{ViewMode, OperationType};
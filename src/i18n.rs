@@ -0,0 +1,173 @@
+// Localization - UI strings looked up in a per-language catalog keyed by their English text
+
+pub use crate::storage::Language;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Catalog text for `language`; English is the key language and needs none
+fn catalog_source(language: Language) -> Option<&'static str> {
+    match language {
+        Language::English => None,
+        Language::German => Some(include_str!("locales/de.txt")),
+    }
+}
+
+fn catalog(language: Language) -> Option<&'static Catalog> {
+    static GERMAN: OnceLock<Catalog> = OnceLock::new();
+    let cell = match language {
+        Language::English => return None,
+        Language::German => &GERMAN,
+    };
+    // The catalogs are checked by the tests, so a broken line only drops that line
+    Some(cell.get_or_init(|| parse_catalog(catalog_source(language).unwrap_or_default()).unwrap_or_default()))
+}
+
+type Catalog = HashMap<String, String>;
+
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+/// Switches every later `tr` call to `language`
+pub fn set_language(language: Language) {
+    CURRENT.store(language as u8, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    Language::ALL.get(CURRENT.load(Ordering::Relaxed) as usize).copied().unwrap_or_default()
+}
+
+/// `text` in the current language, or `text` itself if the catalog has no translation
+pub fn tr(text: &'static str) -> &'static str {
+    translate(language(), text)
+}
+
+fn translate(language: Language, text: &'static str) -> &'static str {
+    catalog(language)
+        .and_then(|catalog| catalog.get(text))
+        .map(String::as_str)
+        .unwrap_or(text)
+}
+
+/// Lines of `English = Translation`; blank lines and lines starting with `#` are skipped and
+/// `\n` stands for a line break
+fn parse_catalog(source: &str) -> Result<Catalog, String> {
+    let mut catalog = Catalog::new();
+    for (number, line) in source.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (key, value) = line
+            .split_once(" = ")
+            .ok_or_else(|| format!("Line {}: expected `English = Translation`", number + 1))?;
+        let (key, value) = (key.trim().replace("\\n", "\n"), value.trim().replace("\\n", "\n"));
+        if value.is_empty() {
+            return Err(format!("Line {}: '{}' has no translation", number + 1, key));
+        }
+        if catalog.insert(key.clone(), value).is_some() {
+            return Err(format!("Line {}: '{}' is translated twice", number + 1, key));
+        }
+    }
+    Ok(catalog)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalogs_parse() {
+        for language in Language::ALL {
+            if let Some(source) = catalog_source(language) {
+                let catalog = parse_catalog(source).unwrap_or_else(|e| panic!("{}: {}", language.name(), e));
+                assert!(!catalog.is_empty());
+            }
+        }
+        assert_eq!(translate(Language::German, "⚙ Settings"), "⚙ Einstellungen");
+        assert!(translate(Language::German, "Undo the last bit edit (Ctrl+Z)").starts_with("Letzte"));
+        assert!(translate(Language::German, "Click bits to flip them, or select a byte in the byte view and type hex.\nEdits are recorded as a Bit Edits operation.").contains('\n'));
+        assert_eq!(translate(Language::German, "Not in the catalog"), "Not in the catalog");
+        assert_eq!(translate(Language::English, "⚙ Settings"), "⚙ Settings");
+    }
+
+    #[test]
+    fn test_german_covers_every_key() {
+        let catalog = parse_catalog(catalog_source(Language::German).unwrap()).unwrap();
+        let mut keys = Vec::new();
+        literals_after(&std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src"), &["tr(\""], &mut keys);
+        assert!(keys.len() > 500);
+        let mut missing: Vec<&String> = keys.iter().filter(|key| !catalog.contains_key(*key)).collect();
+        missing.sort();
+        missing.dedup();
+        assert!(missing.is_empty(), "not in the German catalog: {:#?}", missing);
+    }
+
+    #[test]
+    fn test_ui_text_goes_through_tr() {
+        let src = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let calls = [
+            "ui.heading(\"", "ui.label(\"", "ui.button(\"", "ui.small_button(\"", "ui.small(\"", "ui.weak(\"",
+            "ui.strong(\"", ".on_hover_text(\"", "Window::new(\"",
+        ];
+        let mut texts = Vec::new();
+        for part in ["main.rs", "app.rs", "ui", "viewers"] {
+            literals_after(&src.join(part), &calls, &mut texts);
+        }
+        // The tool's name and the script syntax summary read the same in every language
+        let untranslated: Vec<&String> = texts
+            .iter()
+            .filter(|text| text.as_bytes().windows(2).any(|pair| pair.iter().all(u8::is_ascii_alphabetic)))
+            .filter(|text| !text.contains("B.I.T.") && !text.starts_with("• let"))
+            .collect();
+        assert!(untranslated.is_empty(), "shown without tr: {:#?}", untranslated);
+    }
+
+    /// String literals right after any of `prefixes` in the sources at `path`, a file or a directory
+    fn literals_after(path: &std::path::Path, prefixes: &[&str], found: &mut Vec<String>) {
+        if path.is_dir() {
+            for entry in std::fs::read_dir(path).unwrap() {
+                literals_after(&entry.unwrap().path(), prefixes, found);
+            }
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            let source = std::fs::read_to_string(path).unwrap();
+            for prefix in prefixes {
+                let mut rest = source.as_str();
+                while let Some(at) = rest.find(prefix) {
+                    let is_call = !rest[..at].ends_with(|c: char| c.is_alphanumeric() || c == '_');
+                    rest = &rest[at + prefix.len()..];
+                    if is_call {
+                        found.push(string_literal(rest));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Value of the string literal that `source` starts inside of, just after the opening quote
+    fn string_literal(source: &str) -> String {
+        let mut value = String::new();
+        let mut chars = source.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => break,
+                '\\' => match chars.next() {
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    // A line continuation skips the next line's indentation
+                    Some('\n') => while chars.next_if(|c| c.is_whitespace()).is_some() {},
+                    Some(escaped) => value.push(escaped),
+                    None => break,
+                },
+                c => value.push(c),
+            }
+        }
+        value
+    }
+
+    #[test]
+    fn test_catalog_errors() {
+        assert!(parse_catalog("# comment\n\nSave = Speichern").is_ok());
+        assert!(parse_catalog("Save Speichern").is_err());
+        assert!(parse_catalog("Save = Speichern\nSave = Sichern").is_err());
+    }
+}
@@ -1,13 +1,12 @@
 // Library exports for testing and external use
-pub mod analysis;
 pub mod app;
-pub mod core;
-pub mod processing;
-pub mod storage;
+pub mod i18n;
 pub mod ui;
-pub mod utils;
 pub mod viewers;
 
+// The GUI-free core, under the paths it had before it moved to its own crate
+pub use bit_core::{analysis, core, processing, storage, utils};
+
 // Re-export commonly used items for convenience
 pub use analysis::{Pattern, PatternFormat};
 pub use app::BitApp;
@@ -1,15 +1,18 @@
-mod analysis;
 mod app;
-mod core;
-mod processing;
-mod storage;
+mod i18n;
 mod ui;
-mod utils;
 mod viewers;
 
+use bit_core::{analysis, core, processing, storage, utils};
+
 use crate::app::BitApp;
 use crate::core::{ViewMode, OperationType};
-use crate::storage::AppSession;
+use crate::i18n::{tr, Language};
+use crate::processing::{BitOperation, ProgressState};
+use crate::storage::{AppSession, AppTheme, MAX_UI_SCALE, MIN_UI_SCALE};
+use crate::utils::TextEncoding;
+use crate::viewers::{rgb, Endianness, WordKind};
+use bitvec::prelude::*;
 use eframe::egui;
 
 fn main() -> Result<(), eframe::Error> {
@@ -31,11 +34,12 @@ impl eframe::App for BitApp {
     fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
         // Auto-save session when closing
         self.save_session();
+        crate::storage::autosave::mark_clean_exit();
     }
     
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         // Disable text selection while dragging
-        if self.dragging_operation.is_some() {
+        if self.dragging_operation.is_some() || self.dragging_worksheet.is_some() {
             ctx.output_mut(|o| o.cursor_icon = egui::CursorIcon::Grabbing);
         }
         
@@ -58,8 +62,50 @@ impl eframe::App for BitApp {
         if font_size_changed {
             ctx.request_repaint();
         }
+        
+        // Ctrl+Z takes back the last bit edit while in edit mode
+        if self.edit_mode
+            && self.can_undo_bit_edit()
+            && ctx.memory(|m| m.focused().is_none())
+            && ctx.input(|i| i.modifiers.command && i.key_pressed(egui::Key::Z))
+        {
+            self.undo_bit_edit();
+        }
+        
+        // Otherwise Ctrl+Z and Ctrl+Shift+Z step through byte view layout changes
+        let pointer_down = ctx.input(|i| i.pointer.any_down());
+        self.track_byte_view_changes(pointer_down);
+        if self.view_mode == ViewMode::Byte
+            && !self.edit_mode
+            && ctx.memory(|m| m.focused().is_none())
+        {
+            let (undo, redo) = ctx.input(|i| {
+                let z = i.modifiers.command && i.key_pressed(egui::Key::Z);
+                (z && !i.modifiers.shift, z && i.modifiers.shift)
+            });
+            if undo {
+                self.undo_byte_view();
+            } else if redo {
+                self.redo_byte_view();
+            }
+        }
 
-        // Apply font size to the context
+        // F3 and Shift+F3 step through the selected pattern's matches
+        if let Some(forward) = ctx.input(|i| i.key_pressed(egui::Key::F3).then_some(!i.modifiers.shift)) {
+            self.step_match(forward, ctx.input(|i| i.time));
+        }
+        
+        i18n::set_language(self.settings.language);
+        
+        // The UI scale setting owns the zoom factor, so egui's own Ctrl+Plus/Minus zoom is off
+        ctx.options_mut(|options| options.zoom_with_keyboard = false);
+        let ui_scale = self.settings.ui_scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE);
+        if (ctx.zoom_factor() - ui_scale).abs() > f32::EPSILON {
+            ctx.set_zoom_factor(ui_scale);
+        }
+        
+        // Apply the theme, then the font size on top of it
+        ctx.set_visuals(theme_visuals(self.settings.theme, ctx.system_theme()));
         let mut style = (*ctx.style()).clone();
         style.text_styles.insert(
             egui::TextStyle::Body,
@@ -79,27 +125,132 @@ impl eframe::App for BitApp {
         );
         ctx.set_style(style);
 
+        // Live network data
+        if self.network_listener.is_some() {
+            self.poll_network_source();
+            ctx.request_repaint_after(std::time::Duration::from_millis(100));
+        }
+        
+        // Source file rewritten by another program
+        if self.file_watcher.is_some() {
+            self.poll_file_watch();
+            ctx.request_repaint_after(std::time::Duration::from_millis(500));
+        }
+        
+        // Pattern search or frame width analysis running in the background
+        if self.analysis_task.is_some() {
+            self.poll_analysis_task();
+            ctx.request_repaint_after(std::time::Duration::from_millis(50));
+        }
+        
+        // Large export being written in the background
+        if self.save_task.is_some() {
+            self.poll_save_task();
+            ctx.request_repaint_after(std::time::Duration::from_millis(50));
+        }
+        
+        // Batch report running in the background
+        if self.batch_receiver.is_some() {
+            self.poll_batch_report();
+            ctx.request_repaint_after(std::time::Duration::from_millis(100));
+        }
+        
+        // Periodic crash recovery snapshot
+        if self.autosave_receiver.is_some() {
+            self.poll_autosave();
+            ctx.request_repaint_after(std::time::Duration::from_millis(100));
+        }
+        if self.settings.autosave_enabled {
+            self.autosave_if_due(ctx.input(|i| i.time));
+            ctx.request_repaint_after(std::time::Duration::from_secs(self.settings.autosave_interval_secs));
+        }
+        
+        // Offer autosave snapshots if the last run didn't exit cleanly
+        if !self.recovery_snapshots.is_empty() {
+            let mut restore = None;
+            let mut dismiss = false;
+            egui::Window::new(tr("Recover Unsaved Work?"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(tr("B.I.T. didn't exit cleanly last time. Restore one of these autosave snapshots?"));
+                    ui.add_space(10.0);
+                    
+                    for (idx, snapshot) in self.recovery_snapshots.iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            if ui.button(tr("🔄 Restore")).clicked() {
+                                restore = Some(idx);
+                            }
+                            let names: Vec<&str> = snapshot.session.worksheets.iter().map(|w| w.name.as_str()).collect();
+                            ui.label(format!("🕘 {} - {}", snapshot.age_label(), names.join(", ")))
+                                .on_hover_text(snapshot.path.display().to_string());
+                        });
+                    }
+                    
+                    ui.add_space(10.0);
+                    if ui.button(tr("🆕 Start Fresh")).clicked() {
+                        dismiss = true;
+                    }
+                });
+            
+            if let Some(idx) = restore {
+                let session = self.recovery_snapshots[idx].session.clone();
+                self.restore_session(session);
+                dismiss = true;
+            }
+            if dismiss {
+                self.recovery_snapshots.clear();
+                self.pending_session = None;
+                self.show_restore_dialog = false;
+            }
+        }
+        
+        // Explain why the previous session couldn't be restored instead of silently dropping it
+        if let Some(error) = self.session_load_error.clone() {
+            egui::Window::new(tr("Couldn't Restore Previous Session"))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.vertical_centered(|ui| {
+                        ui.label(tr("The previous session file could not be loaded:"));
+                        ui.colored_label(egui::Color32::RED, &error);
+                        ui.add_space(10.0);
+                        ui.label(tr("It will be moved aside so it isn't overwritten, and a fresh session started."));
+                        ui.add_space(10.0);
+                        
+                        if ui.button(tr("📦 Back Up and Start Fresh")).clicked() {
+                            if let Err(e) = AppSession::back_up_unreadable() {
+                                self.notifications.error(e);
+                            }
+                            self.session_load_error = None;
+                        }
+                    });
+                });
+        }
+        
         // Show restore session dialog
         if self.show_restore_dialog {
-            egui::Window::new("Restore Previous Session?")
+            egui::Window::new(tr("Restore Previous Session?"))
                 .collapsible(false)
                 .resizable(false)
                 .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
                 .show(ctx, |ui| {
                     ui.vertical_centered(|ui| {
-                        ui.label("A previous session was found.");
-                        ui.label("Would you like to restore it or start fresh?");
+                        ui.label(tr("A previous session was found."));
+                        ui.label(tr("Would you like to restore it or start fresh?"));
                         ui.add_space(10.0);
                         
                         ui.horizontal(|ui| {
-                            if ui.button("🔄 Restore Session").clicked() {
+                            if ui.button(tr("🔄 Restore Session")).clicked() {
                                 if let Some(session) = self.pending_session.take() {
                                     self.restore_session(session);
                                 }
                                 self.show_restore_dialog = false;
                             }
                             
-                            if ui.button("🆕 Start Fresh").clicked() {
+                            if ui.button(tr("🆕 Start Fresh")).clicked() {
                                 let _ = AppSession::delete();
                                 self.pending_session = None;
                                 self.show_restore_dialog = false;
@@ -117,14 +268,15 @@ impl eframe::App for BitApp {
         
         // Show loading dialog
         if self.is_loading() {
-            egui::Window::new("Loading File...")
+            let mut cancel_requested = false;
+            egui::Window::new(tr("Loading File..."))
                 .collapsible(false)
                 .resizable(false)
                 .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
                 .show(ctx, |ui| {
                     ui.vertical_centered(|ui| {
                         if let Some(path) = &self.loading_file_path {
-                            ui.label(format!("Loading: {}", path.file_name().unwrap_or_default().to_string_lossy()));
+                            ui.label(format!("{}: {}", tr("Loading"), path.file_name().unwrap_or_default().to_string_lossy()));
                         }
                         ui.add_space(10.0);
                         
@@ -140,16 +292,27 @@ impl eframe::App for BitApp {
                         let loaded_mb = (self.loading_total as f64 * self.loading_progress as f64) / (1024.0 * 1024.0);
                         let total_mb = self.loading_total as f64 / (1024.0 * 1024.0);
                         ui.label(format!("{:.2} MB / {:.2} MB", loaded_mb, total_mb));
+                        
+                        ui.add_space(5.0);
+                        
+                        if ui.button(tr("✖ Cancel")).clicked() {
+                            cancel_requested = true;
+                        }
                     });
                 });
             
+            if cancel_requested {
+                self.cancel_loading();
+            }
+            
             // Request continuous repaints while loading
             ctx.request_repaint();
         }
         
         // Show operation processing dialog
         if self.is_processing_operations() {
-            egui::Window::new("Processing Operations...")
+            let mut cancel_requested = false;
+            egui::Window::new(tr("Processing Operations..."))
                 .collapsible(false)
                 .resizable(false)
                 .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
@@ -163,16 +326,38 @@ impl eframe::App for BitApp {
                             .show_percentage()
                             .desired_width(300.0);
                         ui.add(progress_bar);
+                        
+                        ui.add_space(5.0);
+                        
+                        if ui.button(tr("✖ Cancel")).clicked() {
+                            cancel_requested = true;
+                        }
                     });
                 });
             
+            if cancel_requested {
+                self.cancel_async_operations();
+            }
+            
             // Request continuous repaints while processing
             ctx.request_repaint();
         }
         
+        // Show analysis and save progress dialogs
+        if let Some(task) = &self.analysis_task
+            && render_task_dialog(ctx, &task.title, &task.progress())
+        {
+            self.cancel_analysis();
+        }
+        if let Some(task) = &self.save_task
+            && render_task_dialog(ctx, &task.title, &task.progress())
+        {
+            self.cancel_save();
+        }
+        
         // Show rendering preparation dialog and defer render if needed
         if self.defer_first_render {
-            egui::Window::new("Preparing View...")
+            egui::Window::new(tr("Preparing View..."))
                 .collapsible(false)
                 .resizable(false)
                 .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
@@ -185,7 +370,7 @@ impl eframe::App for BitApp {
                         ui.spinner();
                         
                         ui.add_space(5.0);
-                        ui.label("This may take a moment for large files...");
+                        ui.label(tr("This may take a moment for large files..."));
                     });
                 });
             
@@ -209,21 +394,67 @@ impl eframe::App for BitApp {
         render_settings_window(self, ctx);
         render_pattern_locator_window(self, ctx);
         render_frame_width_finder_window(self, ctx);
+        render_periodicity_window(self, ctx);
+        render_hdlc_decoder_window(self, ctx);
+        render_framer_window(self, ctx);
+        render_sources_window(self, ctx);
+        render_repeats_window(self, ctx);
+        render_channel_export_window(self, ctx);
+        render_frame_stats_window(self, ctx);
+        render_frame_distance_window(self, ctx);
+        render_frames_csv_window(self, ctx);
+        render_xor_key_window(self, ctx);
+        render_converter_window(self, ctx);
+        render_checksum_window(self, ctx);
+        render_structure_window(self, ctx);
+        render_report_window(self, ctx);
+        render_missing_files_window(self, ctx);
+        render_batch_window(self, ctx);
         render_operation_windows(self, ctx);
         render_column_editor_window(self, ctx);
+        render_notifications(self, ctx);
         
         // Render central panel
         render_central_panel(self, ctx);
     }
 }
 
+/// Progress dialog for a background task; returns true when Cancel is clicked
+fn render_task_dialog(ctx: &egui::Context, title: &str, progress: &ProgressState) -> bool {
+    let mut cancel_requested = false;
+    egui::Window::new(format!("{}...", title))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ctx, |ui| {
+            ui.vertical_centered(|ui| {
+                if !progress.message.is_empty() {
+                    ui.label(&progress.message);
+                    ui.add_space(10.0);
+                }
+                
+                let progress_bar = egui::ProgressBar::new(progress.fraction)
+                    .show_percentage()
+                    .desired_width(300.0);
+                ui.add(progress_bar);
+                
+                ui.add_space(5.0);
+                
+                if ui.button(tr("✖ Cancel")).clicked() {
+                    cancel_requested = true;
+                }
+            });
+        });
+    cancel_requested
+}
+
 fn render_left_panels(app: &mut BitApp, ctx: &egui::Context) {
     // Leftmost panel: Available Operations
     egui::SidePanel::left("available_operations_panel")
         .default_width(200.0)
         .resizable(true)
         .show(ctx, |ui| {
-            ui.heading("Available Operations");
+            ui.heading(tr("Available Operations"));
             ui.separator();
             
             egui::ScrollArea::vertical()
@@ -231,10 +462,22 @@ fn render_left_panels(app: &mut BitApp, ctx: &egui::Context) {
                 .show(ui, |ui| {
                     let operations = [
                         OperationType::LoadFile,
+                        OperationType::MultiFileLoad,
+                        OperationType::LoadSoftBits,
                         OperationType::TakeSkipSequence,
                         OperationType::InvertBits,
                         OperationType::TruncateBits,
                         OperationType::InterleaveBits,
+                        OperationType::InsertBits,
+                        OperationType::DeleteBits,
+                        OperationType::RangeBits,
+                        OperationType::XorKey,
+                        OperationType::LineDecode,
+                        OperationType::SymbolConvert,
+                        OperationType::SymbolRemap,
+                        OperationType::MajorityVote,
+                        OperationType::NandLayout,
+                        OperationType::Script,
                         OperationType::MultiWorksheetLoad,
                     ];
                     
@@ -277,9 +520,36 @@ fn render_left_panels(app: &mut BitApp, ctx: &egui::Context) {
                         ui.add_space(4.0);
                     }
                     
+                    if !app.settings.macros.is_empty() {
+                        ui.separator();
+                        ui.label(tr("📦 Macros"));
+                        ui.add_space(4.0);
+                        
+                        let mut to_add = None;
+                        let mut to_delete = None;
+                        for (i, saved) in app.settings.macros.iter().enumerate() {
+                            ui.horizontal(|ui| {
+                                let steps: Vec<&str> = saved.operations.iter().map(|op| op.name()).collect();
+                                if ui.button(format!("📦 {}", saved.name)).on_hover_text(steps.join(" → ")).clicked() {
+                                    to_add = Some(i);
+                                }
+                                if ui.small_button("🗑").on_hover_text(tr("Delete macro")).clicked() {
+                                    to_delete = Some(i);
+                                }
+                            });
+                        }
+                        
+                        if let Some(i) = to_add {
+                            app.add_macro_operation(i);
+                        }
+                        if let Some(i) = to_delete {
+                            app.delete_macro(i);
+                        }
+                    }
+                    
                     ui.separator();
-                    ui.label("💡 Click an operation type");
-                    ui.label("to add it to the list");
+                    ui.label(tr("💡 Click an operation type"));
+                    ui.label(tr("to add it to the list"));
                 });
         });
 
@@ -292,12 +562,14 @@ fn render_left_panels(app: &mut BitApp, ctx: &egui::Context) {
             ui.separator();
             render_active_operations_section(app, ui);
             render_byte_view_config_section(app, ui);
+            render_ascii_view_config_section(app, ui);
+            render_symbol_view_config_section(app, ui);
             render_info_section(app, ui);
         });
 }
 
 fn render_worksheets_section(app: &mut BitApp, ui: &mut egui::Ui) {
-    ui.heading("Worksheets");
+    ui.heading(tr("Worksheets"));
     ui.separator();
     
     ui.horizontal(|ui| {
@@ -309,15 +581,19 @@ fn render_worksheets_section(app: &mut BitApp, ui: &mut egui::Ui) {
             app.load_from_worksheet();
         }
         
-        if ui.button("💾 Save").clicked() {
+        if ui.button(tr("💾 Save")).clicked() {
             app.save_worksheet_to_file();
         }
         
-        if ui.button("📂 Load").clicked() {
+        if ui.button(tr("📂 Load")).clicked() {
             app.load_worksheet_from_file();
         }
     });
     
+    if app.worksheets.len() > 1 {
+        render_list_filter(ui, &mut app.worksheet_filter, tr("🔍 Filter worksheets"));
+    }
+    
     ui.add_space(4.0);
     
     egui::ScrollArea::vertical()
@@ -326,13 +602,33 @@ fn render_worksheets_section(app: &mut BitApp, ui: &mut egui::Ui) {
         .show(ui, |ui| {
             let mut to_switch = None;
             let mut to_delete = None;
+            let mut drop_target_idx = None;
             let num_worksheets = app.worksheets.len();
+            let filter = app.worksheet_filter.trim().to_lowercase();
             
             for i in 0..num_worksheets {
                 let is_current = i == app.current_worksheet_index;
                 let worksheet_name = app.worksheets[i].name.clone();
+                let file_name = app.worksheets[i].file_path.as_ref()
+                    .and_then(|path| path.file_name())
+                    .map(|name| name.to_string_lossy().to_lowercase())
+                    .unwrap_or_default();
+                if !is_current
+                    && !filter.is_empty()
+                    && !worksheet_name.to_lowercase().contains(&filter)
+                    && !file_name.contains(&filter)
+                {
+                    continue;
+                }
+                // The current worksheet's operations live in the app until the next sync
+                let operation_count = if is_current { app.operations.len() } else { app.worksheets[i].operations.len() };
                 
-                ui.horizontal(|ui| {
+                let row = ui.horizontal(|ui| {
+                    let drag_handle = ui.label("☰").interact(egui::Sense::click_and_drag());
+                    if drag_handle.dragged() {
+                        app.dragging_worksheet = Some(i);
+                    }
+                    
                     let mut response = ui.selectable_label(is_current, "");
                     if response.clicked() && !is_current {
                         to_switch = Some(i);
@@ -355,10 +651,46 @@ fn render_worksheets_section(app: &mut BitApp, ui: &mut egui::Ui) {
                         }
                     }
                     
+                    if operation_count > 0 {
+                        ui.weak(format!("({})", operation_count))
+                            .on_hover_text(format!("{} {}", operation_count, tr("operations")));
+                    }
+                    
                     if num_worksheets > 1 && ui.small_button("🗑").clicked() {
                         to_delete = Some(i);
                     }
-                });
+                }).response;
+                
+                if let Some(dragged_idx) = app.dragging_worksheet {
+                    if dragged_idx != i {
+                        if let Some(pointer_pos) = ui.ctx().pointer_hover_pos() {
+                            let rect = row.rect;
+                            if rect.contains(pointer_pos) {
+                                let (target, y) = if pointer_pos.y < rect.center().y {
+                                    (i, rect.min.y)
+                                } else {
+                                    (i + 1, rect.max.y)
+                                };
+                                drop_target_idx = Some(target);
+                                let line_rect = egui::Rect::from_min_size(
+                                    egui::pos2(rect.min.x, y - 1.5),
+                                    egui::vec2(rect.width(), 3.0)
+                                );
+                                ui.painter().rect_filled(line_rect, 1.5, egui::Color32::from_rgb(70, 130, 255));
+                            }
+                        }
+                    } else {
+                        ui.ctx().set_cursor_icon(egui::CursorIcon::Grabbing);
+                    }
+                }
+            }
+            
+            if ui.input(|i| i.pointer.primary_released()) {
+                if let (Some(from), Some(to)) = (app.dragging_worksheet, drop_target_idx) {
+                    let insert_pos = if to > from { to - 1 } else { to };
+                    app.move_worksheet(from, insert_pos);
+                }
+                app.dragging_worksheet = None;
             }
             
             if let Some(idx) = to_switch {
@@ -377,13 +709,57 @@ fn render_worksheets_section(app: &mut BitApp, ui: &mut egui::Ui) {
         });
 }
 
+/// A one-line filter box with a button to clear it
+fn render_list_filter(ui: &mut egui::Ui, filter: &mut String, hint: &str) {
+    ui.horizontal(|ui| {
+        ui.add(egui::TextEdit::singleline(filter).hint_text(hint).desired_width(ui.available_width() - 30.0));
+        if !filter.is_empty() && ui.small_button("✖").on_hover_text(tr("Clear the filter")).clicked() {
+            filter.clear();
+        }
+    });
+}
+
 fn render_active_operations_section(app: &mut BitApp, ui: &mut egui::Ui) {
-    ui.heading("Active Operations");
+    let enabled_count = app.operations.iter().filter(|op| op.is_enabled()).count();
+    ui.horizontal(|ui| {
+        ui.heading(tr("Active Operations"));
+        if !app.operations.is_empty() {
+            ui.weak(format!("{}/{}", enabled_count, app.operations.len()))
+                .on_hover_text(format!("{} {} {} {}", enabled_count, tr("of"), app.operations.len(), tr("operations enabled")));
+        }
+    });
     ui.separator();
 
+    if app.operations.len() > 1 {
+        render_list_filter(ui, &mut app.operation_filter, tr("🔍 Filter by name or type"));
+    }
+    let filter = app.operation_filter.clone();
+    let shown = app.operations.iter().filter(|op| op.matches_filter(&filter)).count();
+    if shown < app.operations.len() {
+        ui.weak(format!("{} {} {} {}", tr("Showing"), shown, tr("of"), app.operations.len()));
+    }
+    
     let mut to_remove: Option<usize> = None;
     let mut to_edit: Option<usize> = None;
+    let mut to_wrap: Option<usize> = None;
+    let mut to_make_variants: Option<usize> = None;
+    let mut to_toggle_variant: Option<usize> = None;
     let mut toggled_operation: Option<usize> = None;
+    let mut to_group: Option<usize> = None;
+    let mut to_add_to_group: Option<(usize, usize)> = None;
+    let mut to_ungroup: Option<usize> = None;
+    let mut toggled_collapse: Option<usize> = None;
+    // Group members as (group index, child index)
+    let mut child_toggled: Option<(usize, usize)> = None;
+    let mut child_to_edit: Option<(usize, usize)> = None;
+    let mut child_to_remove: Option<(usize, usize)> = None;
+    let mut child_to_move_out: Option<(usize, usize)> = None;
+    let groups: Vec<(usize, String)> = app.operations
+        .iter()
+        .enumerate()
+        .filter(|(_, op)| matches!(op, BitOperation::Group { .. }))
+        .map(|(i, op)| (i, op.name().to_string()))
+        .collect();
 
     egui::ScrollArea::vertical()
         .id_salt("active_ops")
@@ -392,12 +768,15 @@ fn render_active_operations_section(app: &mut BitApp, ui: &mut egui::Ui) {
 
             if app.operations.is_empty() {
                 ui.centered_and_justified(|ui| {
-                    ui.label("No operations added");
+                    ui.label(tr("No operations added"));
                 });
             } else {
                 let mut drop_target_idx = None;
                 
                 for (i, op) in app.operations.iter().enumerate() {
+                    if !op.matches_filter(&filter) {
+                        continue;
+                    }
                     let is_being_dragged = app.dragging_operation == Some(i);
                     let is_enabled = op.is_enabled();
                     let alpha = if is_being_dragged { 0.3 } else if !is_enabled { 0.5 } else { 1.0 };
@@ -428,10 +807,31 @@ fn render_active_operations_section(app: &mut BitApp, ui: &mut egui::Ui) {
                                     }
                                     
                                     ui.label(format!("{}.", i + 1));
+                                    if let BitOperation::Group { collapsed, .. } = op
+                                        && ui.small_button(if *collapsed { "▶" } else { "▼" }).clicked()
+                                    {
+                                        toggled_collapse = Some(i);
+                                    }
                                     ui.vertical(|ui| {
                                         let name_color = if is_enabled { ui.style().visuals.text_color() } else { ui.style().visuals.weak_text_color() };
                                         ui.colored_label(name_color, op.name());
                                         ui.small(op.description());
+                                        if let Some(Some(timing)) = app.operation_timings.get(i) {
+                                            ui.horizontal(|ui| {
+                                                ui.small(timing.summary());
+                                                if is_enabled && let Some(warning) = timing.warning(app.settings.max_drop_percent) {
+                                                    ui.colored_label(ui.visuals().warn_fg_color, "⚠").on_hover_text(warning);
+                                                }
+                                                // Expressions such as len-1024 that don't fit this input leave it unchanged
+                                                if is_enabled
+                                                    && let Some((input_bits, _)) = timing.sizes()
+                                                    && let Some(error) = op.expression_error(input_bits)
+                                                {
+                                                    ui.colored_label(ui.visuals().error_fg_color, "⚠")
+                                                        .on_hover_text(format!("{}\n{}", error, tr("The operation passes its input through unchanged")));
+                                                }
+                                            });
+                                        }
                                     });
                                     
                                     if drag_handle.dragged() {
@@ -440,13 +840,87 @@ fn render_active_operations_section(app: &mut BitApp, ui: &mut egui::Ui) {
                                 });
                                 
                                 ui.horizontal(|ui| {
-                                    if ui.button("✏ Edit").clicked() {
+                                    if ui.button(tr("✏ Edit")).clicked() {
                                         to_edit = Some(i);
                                     }
+                                    if !op.is_source()
+                                        && !matches!(op, BitOperation::Conditional { .. })
+                                        && ui.button("🎯").on_hover_text(tr("Apply only to every Nth frame or listed ranges")).clicked()
+                                    {
+                                        to_wrap = Some(i);
+                                    }
+                                    if let BitOperation::Variants { use_b, .. } = op {
+                                        if ui.selectable_label(!*use_b, "A").on_hover_text(tr("Use variant A")).clicked() && *use_b {
+                                            to_toggle_variant = Some(i);
+                                        }
+                                        if ui.selectable_label(*use_b, "B").on_hover_text(tr("Use variant B")).clicked() && !*use_b {
+                                            to_toggle_variant = Some(i);
+                                        }
+                                    } else if !op.is_source()
+                                        && !matches!(op, BitOperation::Group { .. })
+                                        && ui.button("🆎").on_hover_text(tr("Keep two versions of this operation and switch between them")).clicked()
+                                    {
+                                        to_make_variants = Some(i);
+                                    }
+                                    if matches!(op, BitOperation::Group { .. }) {
+                                        if ui.button("⤵").on_hover_text(tr("Ungroup")).clicked() {
+                                            to_ungroup = Some(i);
+                                        }
+                                    } else if !op.is_source() {
+                                        ui.menu_button("📁", |ui| {
+                                            if ui.button(tr("New Group")).clicked() {
+                                                to_group = Some(i);
+                                                ui.close();
+                                            }
+                                            for (group, name) in &groups {
+                                                if ui.button(format!("{} {}", tr("Add to"), name)).clicked() {
+                                                    to_add_to_group = Some((i, *group));
+                                                    ui.close();
+                                                }
+                                            }
+                                        }).response.on_hover_text(tr("Group operations"));
+                                    }
                                     if ui.button("🗑").clicked() {
                                         to_remove = Some(i);
                                     }
                                 });
+                                
+                                if let BitOperation::Group { operations, collapsed: false, .. } = op {
+                                    ui.indent(("group_members", i), |ui| {
+                                        for (c, child) in operations.iter().enumerate() {
+                                            ui.horizontal(|ui| {
+                                                let mut child_enabled = child.is_enabled();
+                                                if ui.checkbox(&mut child_enabled, "").changed() {
+                                                    child_toggled = Some((i, c));
+                                                }
+                                                ui.label(format!("{}.{}", i + 1, c + 1));
+                                                ui.vertical(|ui| {
+                                                    let name_color = if is_enabled && child.is_enabled() {
+                                                        ui.style().visuals.text_color()
+                                                    } else {
+                                                        ui.style().visuals.weak_text_color()
+                                                    };
+                                                    ui.colored_label(name_color, child.name());
+                                                    ui.small(child.description());
+                                                });
+                                            });
+                                            ui.horizontal(|ui| {
+                                                if ui.small_button(tr("✏ Edit")).clicked() {
+                                                    child_to_edit = Some((i, c));
+                                                }
+                                                if ui.small_button("⤴").on_hover_text(tr("Move out of the group")).clicked() {
+                                                    child_to_move_out = Some((i, c));
+                                                }
+                                                if ui.small_button("🗑").clicked() {
+                                                    child_to_remove = Some((i, c));
+                                                }
+                                            });
+                                        }
+                                        if operations.is_empty() {
+                                            ui.weak(tr("Empty group"));
+                                        }
+                                    });
+                                }
                             });
                         }).response;
                         
@@ -517,7 +991,7 @@ fn render_active_operations_section(app: &mut BitApp, ui: &mut egui::Ui) {
                                 let insert_pos = if to > from { to - 1 } else { to };
                                 app.operations.insert(insert_pos, op);
                                 app.clear_pattern_matches(); // Operation order changed, clear patterns
-                                app.apply_operations();
+                                app.operations_changed();
                             }
                         }
                     }
@@ -529,58 +1003,287 @@ fn render_active_operations_section(app: &mut BitApp, ui: &mut egui::Ui) {
     if let Some(idx) = to_remove {
         app.operations.remove(idx);
         app.clear_pattern_matches(); // Operation removed, clear patterns
-        app.apply_operations();
+        app.operations_changed();
     }
     
     if let Some(idx) = to_edit {
         app.open_operation_editor(idx);
     }
     
+    if let Some(idx) = to_wrap {
+        app.wrap_operation_in_condition(idx);
+    }
+    
+    if let Some(idx) = to_make_variants {
+        app.make_operation_variants(idx);
+    }
+    
+    if let Some(idx) = to_toggle_variant {
+        app.toggle_operation_variant(idx);
+    }
+    
+    if let Some(idx) = to_group {
+        app.group_operation(idx);
+    }
+    
+    if let Some((idx, group)) = to_add_to_group {
+        app.add_operation_to_group(idx, group);
+    }
+    
+    if let Some(idx) = to_ungroup {
+        app.ungroup_operation(idx);
+    }
+    
+    if let Some(idx) = toggled_collapse {
+        app.toggle_group_collapsed(idx);
+    }
+    
+    if let Some((group, child)) = child_toggled {
+        app.toggle_group_child(group, child);
+    }
+    
+    if let Some((group, child)) = child_to_edit {
+        app.open_group_child_editor(group, child);
+    }
+    
+    if let Some((group, child)) = child_to_move_out {
+        app.move_out_of_group(group, child);
+    }
+    
+    if let Some((group, child)) = child_to_remove {
+        app.remove_group_child(group, child);
+    }
+    
     if let Some(idx) = toggled_operation {
         if let Some(op) = app.operations.get_mut(idx) {
             let new_enabled = !op.is_enabled();
             op.set_enabled(new_enabled);
-            app.apply_operations();
+            app.operations_changed();
         }
     }
 
-    if !app.operations.is_empty() {
+    if !app.operations.is_empty() || app.pipeline_dirty {
         ui.separator();
-        if ui.button("🔄 Reapply All").clicked() {
-            app.apply_operations();
+        if app.pipeline_dirty {
+            ui.colored_label(ui.visuals().warn_fg_color, tr("⚠ Operations changed; the view is out of date"));
+        }
+        ui.horizontal(|ui| {
+            if ui.add(egui::Button::new(tr("🔄 Reapply All")).selected(app.pipeline_dirty)).clicked() {
+                app.apply_operations();
+            }
+            if let Some(summary) = app.operation_timing_summary() {
+                ui.small(summary);
+            }
+        });
+        if ui.checkbox(&mut app.settings.auto_apply_operations, tr("Apply on change"))
+            .on_hover_text(tr("Recompute after every edit; turn off on large files and apply with Reapply All"))
+            .changed()
+        {
+            app.settings.auto_save();
+            if app.settings.auto_apply_operations && app.pipeline_dirty {
+                app.apply_operations();
+            }
         }
-        if ui.button("🗑 Clear All").clicked() {
+    }
+    
+    ui.horizontal(|ui| {
+        if ui.add_enabled(!app.operations.is_empty(), egui::Button::new(tr("📤 Export...")))
+            .on_hover_text(tr("Save the operations and patterns as a standalone file to share or version"))
+            .clicked()
+        {
+            app.export_operation_chain();
+        }
+        if ui.button(tr("📥 Import..."))
+            .on_hover_text(tr("Append the operations from an exported file and add its patterns"))
+            .clicked()
+        {
+            app.import_operation_chain();
+        }
+    });
+    
+    if !app.operations.is_empty() {
+        if ui.button(tr("🗑 Clear All")).clicked() {
             app.operations.clear();
             app.clear_pattern_matches(); // Operations cleared, clear patterns
             app.processed_bits = app.original_bits.clone();
+            app.mapped_processed = app.mapped_original.clone();
+            app.pipeline_dirty = false;
             app.update_viewer();
         }
+        
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut app.new_macro_name).hint_text(tr("Macro name")).desired_width(120.0));
+            if ui.button(tr("📦 Save as Macro"))
+                .on_hover_text(tr("Save the enabled operations as a reusable macro"))
+                .clicked()
+            {
+                app.save_operations_as_macro();
+            }
+        });
+    }
+}
+
+fn render_ascii_view_config_section(app: &mut BitApp, ui: &mut egui::Ui) {
+    if app.view_mode == ViewMode::Ascii {
+        ui.separator();
+        ui.heading(tr("🔤 ASCII View Config"));
+        
+        ui.horizontal(|ui| {
+            ui.label(tr("Encoding:"));
+            egui::ComboBox::from_id_salt("ascii_encoding")
+                .selected_text(app.ascii_encoding.name())
+                .show_ui(ui, |ui| {
+                    for encoding in TextEncoding::ALL {
+                        if ui.selectable_value(&mut app.ascii_encoding, encoding, encoding.name()).clicked() {
+                            app.ascii_bits_per_char = encoding.code_bits();
+                        }
+                    }
+                });
+        });
+        
+        ui.horizontal(|ui| {
+            ui.label(tr("Bits per character:"));
+            ui.add(egui::Slider::new(&mut app.ascii_bits_per_char, 5..=8));
+        });
+        
+        if app.ascii_bits_per_char > app.ascii_encoding.code_bits() {
+            ui.small(format!("{}: {}", tr("Leading bits of each character that are ignored (e.g. parity)"),
+                app.ascii_bits_per_char - app.ascii_encoding.code_bits()));
+        } else if app.ascii_bits_per_char < app.ascii_encoding.code_bits() {
+            ui.small(tr("Characters are shorter than the encoding; missing high bits read as 0"));
+        }
+    }
+}
+
+fn render_symbol_view_config_section(app: &mut BitApp, ui: &mut egui::Ui) {
+    use crate::viewers::SymbolFormat;
+    
+    if app.view_mode == ViewMode::Symbol {
+        ui.separator();
+        ui.heading(tr("🔢 Symbol View Config"));
+        
+        let viewer = &mut app.symbol_viewer;
+        ui.horizontal(|ui| {
+            ui.label(tr("Bits per symbol:"));
+            ui.add(egui::DragValue::new(&mut viewer.symbol_bits).range(1..=64));
+        });
+        ui.horizontal_wrapped(|ui| {
+            for bits in [6, 7, 10, 12, 14, 16] {
+                if ui.selectable_label(viewer.symbol_bits == bits, bits.to_string()).clicked() {
+                    viewer.symbol_bits = bits;
+                }
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr("Symbols per row:"));
+            ui.add(egui::Slider::new(&mut viewer.symbols_per_row, 1..=64));
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr("First symbol at bit:"));
+            ui.add(egui::DragValue::new(&mut viewer.offset).range(0..=usize::MAX));
+        }).response.on_hover_text(tr("Skip bits at the start so symbols line up with the data"));
+        ui.horizontal(|ui| {
+            ui.label(tr("Show as:"));
+            egui::ComboBox::from_id_salt("symbol_format")
+                .selected_text(viewer.format.name())
+                .show_ui(ui, |ui| {
+                    for format in SymbolFormat::ALL {
+                        ui.selectable_value(&mut viewer.format, format, format.name());
+                    }
+                });
+        });
     }
 }
 
 fn render_byte_view_config_section(app: &mut BitApp, ui: &mut egui::Ui) {
     if app.view_mode == ViewMode::Byte {
         ui.separator();
-        ui.heading("📊 Byte View Config");
+        ui.heading(tr("📊 Byte View Config"));
         
         ui.horizontal(|ui| {
-            ui.label("Bytes per row:");
+            if ui.add_enabled(app.can_undo_byte_view(), egui::Button::new(tr("↶ Undo")))
+                .on_hover_text(tr("Undo the last column or layout change (Ctrl+Z)"))
+                .clicked()
+            {
+                app.undo_byte_view();
+            }
+            if ui.add_enabled(app.can_redo_byte_view(), egui::Button::new(tr("↷ Redo")))
+                .on_hover_text(tr("Redo the last undone change (Ctrl+Shift+Z)"))
+                .clicked()
+            {
+                app.redo_byte_view();
+            }
+        });
+        
+        ui.horizontal(|ui| {
+            ui.label(tr("Bytes per row:"));
             let mut bytes_per_row = app.byte_viewer.config.bytes_per_row;
             if ui.add(egui::Slider::new(&mut bytes_per_row, 1..=64)).changed() {
                 app.byte_viewer.set_bytes_per_row(bytes_per_row);
             }
         });
         
-        ui.checkbox(&mut app.byte_viewer.config.show_hex_offset, "Show hex offset");
+        ui.checkbox(&mut app.byte_viewer.config.show_hex_offset, tr("Show hex offset"));
+        
+        ui.horizontal(|ui| {
+            ui.label(tr("Cells:"));
+            let config = &mut app.byte_viewer.config;
+            egui::ComboBox::from_id_salt("byte_word_kind")
+                .selected_text(config.word_kind.name())
+                .show_ui(ui, |ui| {
+                    for kind in WordKind::ALL {
+                        ui.selectable_value(&mut config.word_kind, kind, kind.name());
+                    }
+                });
+            if !config.word_kind.sizes().contains(&config.word_size) {
+                config.word_size = config.word_kind.sizes()[0];
+            }
+            egui::ComboBox::from_id_salt("byte_word_size")
+                .selected_text(format!("{}-bit", config.word_size * 8))
+                .show_ui(ui, |ui| {
+                    for &size in config.word_kind.sizes() {
+                        ui.selectable_value(&mut config.word_size, size, format!("{}-bit", size * 8));
+                    }
+                });
+            ui.add_enabled_ui(config.word_size > 1, |ui| {
+                ui.selectable_value(&mut config.endianness, Endianness::Big, tr("BE"))
+                    .on_hover_text(tr("Big endian: first byte is most significant"));
+                ui.selectable_value(&mut config.endianness, Endianness::Little, tr("LE"))
+                    .on_hover_text(tr("Little endian: first byte is least significant"));
+            });
+        });
+        if app.byte_viewer.config.word_kind == WordKind::Float {
+            ui.small(tr("Implausible floats (NaN, infinities, extreme magnitudes) are dimmed"));
+        }
         
         ui.add_space(8.0);
-        ui.strong("Protocol Columns");
+        ui.strong(tr("Protocol Columns"));
+        
+        let mut frame_relative = app.byte_viewer.config.column_frame_length.is_some();
+        if ui.checkbox(&mut frame_relative, tr("Frame-relative columns"))
+            .on_hover_text(tr("Define column bit ranges against a frame length instead of the display row, so they survive changing bytes per row"))
+            .changed() {
+            app.byte_viewer.config.column_frame_length = frame_relative.then_some(app.viewer.frame_length);
+        }
+        if let Some(frame_length) = app.byte_viewer.config.column_frame_length.as_mut() {
+            ui.horizontal(|ui| {
+                ui.label(tr("Frame length (bits):"));
+                ui.add(egui::DragValue::new(frame_length).range(1..=usize::MAX));
+                if ui.small_button(tr("Use bit view")).on_hover_text(tr("Use the bit view's frame length")).clicked() {
+                    *frame_length = app.viewer.frame_length;
+                }
+            });
+        }
         
         egui::ScrollArea::vertical()
             .id_salt("byte_columns")
             .max_height(200.0)
             .show(ui, |ui| {
                 let mut to_remove = None;
+                let mut to_edit = None;
+                let mut to_move = None;
+                let mut to_toggle = None;
+                let column_count = app.byte_viewer.config.columns.len();
                 
                 for (idx, column) in app.byte_viewer.config.columns.iter().enumerate() {
                     ui.group(|ui| {
@@ -589,13 +1292,30 @@ fn render_byte_view_config_section(app: &mut BitApp, ui: &mut egui::Ui) {
                                 egui::Rect::from_min_size(ui.cursor().min, egui::vec2(20.0, 20.0)),
                                 egui::Sense::hover()
                             );
-                            ui.painter().rect_filled(color_rect.rect, 3.0, column.color32());
+                            ui.painter().rect_filled(color_rect.rect, 3.0, rgb(column.color));
                             
                             ui.vertical(|ui| {
-                                ui.label(&column.label);
-                                ui.small(format!("Bits {}..{}", column.bit_start, column.bit_end));
+                                if column.visible {
+                                    ui.label(&column.label);
+                                } else {
+                                    ui.weak(&column.label);
+                                }
+                                ui.small(format!("{} {}..{}", tr("Bits"), column.bit_start, column.bit_end));
                             });
                             
+                            let visibility_icon = if column.visible { "👁" } else { "🚫" };
+                            if ui.small_button(visibility_icon).on_hover_text(tr("Show/hide column")).clicked() {
+                                to_toggle = Some(idx);
+                            }
+                            if ui.add_enabled(idx > 0, egui::Button::new("⬆").small()).clicked() {
+                                to_move = Some((idx, idx - 1));
+                            }
+                            if ui.add_enabled(idx + 1 < column_count, egui::Button::new("⬇").small()).clicked() {
+                                to_move = Some((idx, idx + 1));
+                            }
+                            if ui.small_button("✏").on_hover_text(tr("Edit column")).clicked() {
+                                to_edit = Some(idx);
+                            }
                             if ui.button("🗑").clicked() {
                                 to_remove = Some(idx);
                             }
@@ -606,14 +1326,23 @@ fn render_byte_view_config_section(app: &mut BitApp, ui: &mut egui::Ui) {
                 if let Some(idx) = to_remove {
                     app.byte_viewer.remove_column(idx);
                 }
+                if let Some((from, to)) = to_move {
+                    app.byte_viewer.move_column(from, to);
+                }
+                if let Some(idx) = to_toggle {
+                    app.byte_viewer.config.columns[idx].visible = !app.byte_viewer.config.columns[idx].visible;
+                }
+                if let Some(idx) = to_edit {
+                    app.open_column_editor(Some(idx));
+                }
             });
         
         ui.horizontal(|ui| {
-            if ui.button("➕ Add Column").clicked() {
-                app.show_column_editor = true;
+            if ui.button(tr("➕ Add Column")).clicked() {
+                app.open_column_editor(None);
             }
             
-            if ui.button("💾 Save Config").clicked() {
+            if ui.button(tr("💾 Save Config")).clicked() {
                 if let Some(file_path) = rfd::FileDialog::new()
                     .add_filter("JSON", &["json"])
                     .set_file_name("protocol_config.json")
@@ -621,17 +1350,17 @@ fn render_byte_view_config_section(app: &mut BitApp, ui: &mut egui::Ui) {
                     match serde_json::to_string_pretty(&app.byte_viewer.config) {
                         Ok(json) => {
                             if let Err(e) = std::fs::write(&file_path, json) {
-                                app.error_message = Some(format!("Failed to save config: {}", e));
+                                app.notifications.error(format!("Failed to save config: {}", e));
                             }
                         }
                         Err(e) => {
-                            app.error_message = Some(format!("Failed to serialize config: {}", e));
+                            app.notifications.error(format!("Failed to serialize config: {}", e));
                         }
                     }
                 }
             }
             
-            if ui.button("📂 Load Config").clicked() {
+            if ui.button(tr("📂 Load Config")).clicked() {
                 if let Some(file_path) = rfd::FileDialog::new()
                     .add_filter("JSON", &["json"])
                     .pick_file() {
@@ -642,124 +1371,321 @@ fn render_byte_view_config_section(app: &mut BitApp, ui: &mut egui::Ui) {
                                     app.byte_viewer.config = config;
                                 }
                                 Err(e) => {
-                                    app.error_message = Some(format!("Failed to parse config: {}", e));
+                                    app.notifications.error(format!("Failed to parse config: {}", e));
                                 }
                             }
                         }
                         Err(e) => {
-                            app.error_message = Some(format!("Failed to read config file: {}", e));
+                            app.notifications.error(format!("Failed to read config file: {}", e));
                         }
                     }
                 }
             }
         });
         
-        if ui.button("📄 Export Documentation").clicked() {
+        ui.collapsing(tr("🧩 Auto Layout"), |ui| {
+            ui.label(tr("Columns for a sync field and equal fields after it, to refine by hand"));
+            if app.patterns.is_empty() {
+                ui.weak(tr("Add the frame's sync pattern first"));
+                return;
+            }
+            app.auto_layout_pattern = app.auto_layout_pattern.min(app.patterns.len() - 1);
+            ui.horizontal(|ui| {
+                ui.label(tr("Sync:"));
+                egui::ComboBox::from_id_salt("auto_layout_pattern")
+                    .selected_text(app.patterns[app.auto_layout_pattern].name.as_str())
+                    .show_ui(ui, |ui| {
+                        for (index, pattern) in app.patterns.iter().enumerate() {
+                            ui.selectable_value(&mut app.auto_layout_pattern, index, pattern.name.as_str());
+                        }
+                    });
+            });
+            ui.horizontal(|ui| {
+                ui.label(tr("Field size (bytes):"));
+                ui.add(egui::DragValue::new(&mut app.auto_layout_field_bytes).range(1..=8));
+            });
+            ui.small(format!("{}: {} ({})", tr("Frame width in bits"), app.viewer.frame_length, tr("set it from Frame Width Analysis")));
+            if ui.button(tr("🧩 Create Columns"))
+                .on_hover_text(tr("Replace the columns with the generated layout"))
+                .clicked()
+            {
+                app.auto_layout_columns();
+            }
+        });
+        
+        if ui.button(tr("📄 Export Documentation")).clicked() {
             if let Some(file_path) = rfd::FileDialog::new()
                 .add_filter("Text", &["txt"])
                 .set_file_name("protocol_documentation.txt")
                 .save_file() {
-                let mut doc = String::new();
-                doc.push_str("Protocol Documentation\n");
-                doc.push_str("=====================\n\n");
-                doc.push_str(&format!("Bytes per row: {}\n\n", app.byte_viewer.config.bytes_per_row));
-                doc.push_str("Field Definitions:\n");
-                doc.push_str("------------------\n\n");
-                
-                for (idx, column) in app.byte_viewer.config.columns.iter().enumerate() {
-                    doc.push_str(&format!("{}. {}\n", idx + 1, column.label));
-                    doc.push_str(&format!("   Bit Range: {} - {}\n", column.bit_start, column.bit_end));
-                    let (start_byte, end_byte) = column.byte_range(app.byte_viewer.config.bytes_per_row);
-                    doc.push_str(&format!("   Byte Range: {} - {}\n", start_byte, end_byte));
-                    doc.push_str(&format!("   Color: RGB({}, {}, {})\n\n", 
-                        column.color[0], column.color[1], column.color[2]));
-                }
-                
+                let doc = crate::analysis::protocol_documentation(&app.byte_viewer.config);
                 if let Err(e) = std::fs::write(&file_path, doc) {
-                    app.error_message = Some(format!("Failed to export documentation: {}", e));
+                    app.notifications.error(format!("Failed to export documentation: {}", e));
                 }
             }
         }
+        
+        ui.horizontal(|ui| {
+            if ui.button(tr("📤 Kaitai (.ksy)"))
+                .on_hover_text(tr("Export the columns as a Kaitai Struct skeleton"))
+                .clicked() {
+                app.export_protocol_definition(crate::analysis::ProtocolFormat::Kaitai);
+            }
+            if ui.button(tr("📤 Wireshark (.lua)"))
+                .on_hover_text(tr("Export the columns as a Lua Wireshark dissector stub"))
+                .clicked() {
+                app.export_protocol_definition(crate::analysis::ProtocolFormat::WiresharkLua);
+            }
+            if ui.button(tr("📊 Frames CSV"))
+                .on_hover_text(tr("Export one row per frame with chosen bit ranges decoded"))
+                .clicked() {
+                app.show_frames_csv = true;
+            }
+            if ui.button(tr("📰 HTML Report"))
+                .on_hover_text(tr("Publish the documentation, snapshots and matches as one HTML page"))
+                .clicked() {
+                app.show_report = true;
+            }
+        });
     }
 }
 
-fn render_info_section(app: &BitApp, ui: &mut egui::Ui) {
+fn render_info_section(app: &mut BitApp, ui: &mut egui::Ui) {
+    use crate::storage::BitOrder;
+    
     ui.separator();
     if let Some(path) = &app.current_file_path {
         ui.label(format!("📄 {}", path.file_name().unwrap_or_default().to_string_lossy()));
     }
-    ui.label(format!("Original: {} bits", app.original_bits.len()));
-    ui.label(format!("Processed: {} bits", app.processed_bits.len()));
-    ui.label(format!("Bit size: {:.1}px", app.viewer.bit_size));
+    let current_order = app.current_worksheet().bit_order;
+    let mut order = current_order;
+    ui.horizontal(|ui| {
+        ui.label(tr("Bit order:"));
+        egui::ComboBox::from_id_salt("bit_order")
+            .selected_text(order.name())
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut order, BitOrder::MsbFirst, BitOrder::MsbFirst.name());
+                ui.selectable_value(&mut order, BitOrder::LsbFirst, BitOrder::LsbFirst.name());
+            });
+    }).response.on_hover_text(tr("How each byte's bits are read from this worksheet's files; LSB first suits UART-style captures"));
+    if order != current_order {
+        app.set_bit_order(order);
+    }
+    if app.current_file_path.is_some() {
+        let mut watch = app.file_watcher.is_some();
+        if ui.checkbox(&mut watch, tr("👁 Reload on change"))
+            .on_hover_text(tr("Reload the file and reapply the operations whenever another program rewrites it; a watched file is read into memory rather than memory-mapped"))
+            .changed()
+        {
+            app.set_watch_file(watch);
+        }
+    }
+    ui.label(format!("{}: {} {}", tr("Original"), app.original_view().len(), tr("bits")));
+    ui.label(format!("{}: {} {}", tr("Processed"), app.processed_view().len(), tr("bits")));
+    if app.mapped_original.is_some() || app.mapped_processed.is_some() {
+        ui.label(tr("🗺 Memory-mapped (read-only)"));
+    }
+    ui.label(format!("{}: {:.1}px", tr("Bit size"), app.viewer.bit_size));
+    
+    let stats = app.stream_stats();
+    if stats.len > 0 {
+        ui.add_space(4.0);
+        ui.label(format!("{}: {:.2}% ({} {} {})", tr("Ones"), stats.ones_percent(), stats.ones, tr("of"), stats.len))
+            .on_hover_text(tr("Far from 50% suggests unscrambled or sparse data; near 50% suggests scrambled, compressed or encrypted data"));
+        let partial = if stats.run_bits < stats.len { format!(" ({})", tr("first 64 Mbit")) } else { String::new() };
+        ui.label(format!("{}: {} {}, {} {}{}", tr("Longest run"), stats.longest_zeros, tr("zeros"), stats.longest_ones, tr("ones"), partial))
+            .on_hover_text(format!("{}: {}\n{}", tr("Runs of equal bits"), stats.runs, tr("Very long runs hint at fill or idle patterns")));
+        if stats.byte_remainder() == 0 {
+            ui.label(tr("Byte aligned"));
+        } else {
+            ui.label(format!("{}: {}", tr("Bits past the last whole byte"), stats.byte_remainder()));
+        }
+    }
+}
+
+/// Visuals for `theme`; `system` is the OS theme if known (dark otherwise)
+fn theme_visuals(theme: AppTheme, system: Option<egui::Theme>) -> egui::Visuals {
+    match theme {
+        AppTheme::Dark => egui::Visuals::dark(),
+        AppTheme::Light => egui::Visuals::light(),
+        AppTheme::System => match system {
+            Some(egui::Theme::Light) => egui::Visuals::light(),
+            _ => egui::Visuals::dark(),
+        },
+    }
 }
 
 fn render_bottom_panel(app: &mut BitApp, ctx: &egui::Context) {
     egui::TopBottomPanel::bottom("bottom_panel").show(ctx, |ui| {
         ui.horizontal(|ui| {
-            ui.label("Frame Length (bits per row):");
+            ui.label(tr("Frame Length (bits per row):"));
             if ui.add(egui::Slider::new(&mut app.viewer.frame_length, 8..=512).logarithmic(true)).changed() {
                 app.settings.frame_length = app.viewer.frame_length;
                 app.settings.auto_save();
             }
-            ui.label(format!("({} chars in ASCII view)", app.viewer.frame_length / 8));
+            ui.label(format!("({} {})", app.viewer.frame_length / 8, tr("chars in ASCII view")));
+            ui.separator();
+            ui.checkbox(&mut app.viewer.transposed, tr("⤡ Transpose Frames"))
+                .on_hover_text(tr("Bit view only: draw each frame as a column, so every bit position becomes a row.\nSlowly changing fields such as counters line up horizontally."));
+            ui.separator();
+            ui.label(tr("Super-frame:"));
+            let mut super_frame = app.viewer.super_frame_length;
+            if ui.add(egui::DragValue::new(&mut super_frame).range(0..=65536).suffix(format!(" {}", tr("frames"))))
+                .on_hover_text(tr("Frames per super-frame, marked by an orange line before each one; 0 for none"))
+                .changed()
+            {
+                app.set_super_frame_length(super_frame);
+            }
+            ui.separator();
+            ui.label(format!("🔍 {:.0}%", app.viewer.zoom_percent()))
+                .on_hover_text(format!("{}: {:.2}px\n{}", tr("Bit size"), app.viewer.bit_size, tr("Ctrl+wheel to zoom")));
+            if let Some((current, total)) = app.match_position().filter(|&(_, total)| total > 0) {
+                ui.separator();
+                let shown = if current > 0 { current.to_string() } else { "-".to_string() };
+                ui.label(format!("{} {}/{}", tr("Match"), shown, total))
+                    .on_hover_text(tr("F3: next match, Shift+F3: previous match"));
+            }
         });
     });
 }
 
 fn render_central_panel(app: &mut BitApp, ctx: &egui::Context) {
     egui::CentralPanel::default().show(ctx, |ui| {
-        if let Some(error) = &app.error_message {
-            ui.colored_label(egui::Color32::RED, error);
+        if app.settings.show_worksheet_tabs {
+            render_worksheet_tabs(app, ui);
+            ui.separator();
         }
-
-        if app.original_bits.is_empty() && app.processed_bits.is_empty() {
+        
+        if app.original_view().is_empty() && app.processed_view().is_empty() {
             ui.centered_and_justified(|ui| {
-                ui.heading("Open a file to view its bits");
+                ui.heading(tr("Open a file to view its bits"));
             });
         } else {
-            let bits_to_display = if app.show_original {
-                &app.original_bits
-            } else {
-                &app.processed_bits
+            let bits_to_display: &BitSlice<u8, Msb0> = match (app.show_original, &app.mapped_original, &app.mapped_processed) {
+                (true, Some(mapped), _) | (false, _, Some(mapped)) => mapped.bits(),
+                (true, None, _) => &app.original_bits,
+                (false, _, None) => &app.processed_bits,
             };
             
+            app.match_index.refresh(&app.patterns);
             match app.view_mode {
                 ViewMode::Bit => {
-                    app.viewer.show(ui);
+                    app.viewer.show(ui, &app.patterns, &app.match_index);
+                    if let Some(bit) = app.viewer.take_clicked_bit() {
+                        app.toggle_bit(bit);
+                    }
                 }
                 ViewMode::Byte => {
-                    app.byte_viewer.render_with_patterns(ui, bits_to_display, &app.patterns);
+                    app.byte_viewer.render_with_patterns(ui, bits_to_display, &app.patterns, &app.match_index);
+                    if let Some((byte_idx, value)) = app.byte_viewer.take_edited_byte() {
+                        app.patch_bits(crate::processing::BitPatch::byte(byte_idx, value));
+                    }
                 }
                 ViewMode::Ascii => {
-                    app.render_ascii_view(ui, bits_to_display);
+                    let jump_to_bit = app.ascii_jump_to_bit.take();
+                    app.render_ascii_view(ui, bits_to_display, jump_to_bit);
+                }
+                ViewMode::Symbol => {
+                    app.symbol_viewer.render(ui, bits_to_display);
                 }
             }
         }
     });
 }
 
+/// Worksheets as a tab strip; tabs can be dragged sideways to reorder them
+fn render_worksheet_tabs(app: &mut BitApp, ui: &mut egui::Ui) {
+    let mut to_switch = None;
+    let mut drop_target_idx = None;
+    
+    egui::ScrollArea::horizontal()
+        .id_salt("worksheet_tabs")
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                for i in 0..app.worksheets.len() {
+                    let is_current = i == app.current_worksheet_index;
+                    let tab = ui.selectable_label(is_current, &app.worksheets[i].name)
+                        .interact(egui::Sense::click_and_drag());
+                    
+                    if tab.clicked() && !is_current {
+                        to_switch = Some(i);
+                    }
+                    if tab.dragged() {
+                        app.dragging_worksheet = Some(i);
+                    }
+                    
+                    if let Some(dragged_idx) = app.dragging_worksheet {
+                        if dragged_idx != i {
+                            if let Some(pointer_pos) = ui.ctx().pointer_hover_pos() {
+                                let rect = tab.rect;
+                                if rect.contains(pointer_pos) {
+                                    let (target, x) = if pointer_pos.x < rect.center().x {
+                                        (i, rect.min.x)
+                                    } else {
+                                        (i + 1, rect.max.x)
+                                    };
+                                    drop_target_idx = Some(target);
+                                    let line_rect = egui::Rect::from_min_size(
+                                        egui::pos2(x - 1.5, rect.min.y),
+                                        egui::vec2(3.0, rect.height())
+                                    );
+                                    ui.painter().rect_filled(line_rect, 1.5, egui::Color32::from_rgb(70, 130, 255));
+                                }
+                            }
+                        } else {
+                            ui.ctx().set_cursor_icon(egui::CursorIcon::Grabbing);
+                        }
+                    }
+                }
+                
+                if ui.small_button("➕").on_hover_text(tr("New worksheet")).clicked() {
+                    let new_name = format!("Worksheet {}", app.worksheets.len() + 1);
+                    app.sync_to_worksheet();
+                    app.worksheets.push(crate::storage::Worksheet::new(new_name));
+                    app.current_worksheet_index = app.worksheets.len() - 1;
+                    app.load_from_worksheet();
+                }
+            });
+        });
+    
+    if ui.input(|i| i.pointer.primary_released()) {
+        if let (Some(from), Some(to)) = (app.dragging_worksheet, drop_target_idx) {
+            let insert_pos = if to > from { to - 1 } else { to };
+            app.move_worksheet(from, insert_pos);
+        }
+        app.dragging_worksheet = None;
+    }
+    
+    if let Some(idx) = to_switch {
+        app.switch_worksheet(idx);
+    }
+}
+
 fn render_settings_window(app: &mut BitApp, ctx: &egui::Context) {
     if app.show_settings {
-        egui::Window::new("⚙ Settings")
+        // Applied after the window closes over `app.show_settings`
+        let mut new_bit_colors = None;
+        
+        egui::Window::new(tr("⚙ Settings"))
             .open(&mut app.show_settings)
             .resizable(false)
             .show(ctx, |ui| {
-                ui.heading("Display Settings");
+                ui.heading(tr("Display Settings"));
                 ui.separator();
 
-                ui.label("Bit Shape:");
+                ui.label(tr("Bit Shape:"));
                 ui.horizontal(|ui| {
-                    if ui.selectable_label(app.viewer.shape == crate::viewers::BitShape::Square, "⬛ Square").clicked() {
+                    if ui.selectable_label(app.viewer.shape == crate::viewers::BitShape::Square, tr("⬛ Square")).clicked() {
                         app.viewer.shape = crate::viewers::BitShape::Square;
                         app.settings.bit_shape = crate::viewers::BitShape::Square;
                         app.settings.auto_save();
                     }
-                    if ui.selectable_label(app.viewer.shape == crate::viewers::BitShape::Circle, "⚫ Circle").clicked() {
+                    if ui.selectable_label(app.viewer.shape == crate::viewers::BitShape::Circle, tr("⚫ Circle")).clicked() {
                         app.viewer.shape = crate::viewers::BitShape::Circle;
                         app.settings.bit_shape = crate::viewers::BitShape::Circle;
                         app.settings.auto_save();
                     }
-                    if ui.selectable_label(app.viewer.shape == crate::viewers::BitShape::Octagon, "⬢ Octagon").clicked() {
+                    if ui.selectable_label(app.viewer.shape == crate::viewers::BitShape::Octagon, tr("⬢ Octagon")).clicked() {
                         app.viewer.shape = crate::viewers::BitShape::Octagon;
                         app.settings.bit_shape = crate::viewers::BitShape::Octagon;
                         app.settings.auto_save();
@@ -768,14 +1694,50 @@ fn render_settings_window(app: &mut BitApp, ctx: &egui::Context) {
 
                 ui.separator();
 
-                if ui.checkbox(&mut app.viewer.show_grid, "Show Grid Lines").changed() {
+                if ui.checkbox(&mut app.viewer.show_grid, tr("Show Grid Lines")).changed() {
                     app.settings.show_grid = app.viewer.show_grid;
                     app.settings.auto_save();
                 }
 
+                if ui.checkbox(&mut app.viewer.show_minimap, tr("Show Minimap")).changed() {
+                    app.settings.show_minimap = app.viewer.show_minimap;
+                    app.settings.auto_save();
+                }
+                
+                if ui.checkbox(&mut app.viewer.show_column_stats, tr("Show Column Statistics"))
+                    .on_hover_text(tr("Ones density per bit column of the current frame length; constant columns are highlighted"))
+                    .changed()
+                {
+                    app.settings.show_column_stats = app.viewer.show_column_stats;
+                    app.settings.auto_save();
+                }
+                
+                if ui.checkbox(&mut app.viewer.show_offsets, tr("Show Row Offsets and Ruler"))
+                    .on_hover_text(tr("Label each bit view row with its starting bit and byte offset, and each column with its index"))
+                    .changed()
+                {
+                    app.settings.show_bit_offsets = app.viewer.show_offsets;
+                    app.settings.auto_save();
+                }
+                
+                if ui.checkbox(&mut app.viewer.show_row_density, tr("Show Row Density"))
+                    .on_hover_text(tr("A bar beside each bit view row showing its fraction of ones, so constant header rows stand out from payload"))
+                    .changed()
+                {
+                    app.settings.show_row_density = app.viewer.show_row_density;
+                    app.settings.auto_save();
+                }
+                
+                if ui.checkbox(&mut app.settings.show_worksheet_tabs, tr("Show Worksheet Tabs"))
+                    .on_hover_text(tr("Show worksheets as tabs above the viewer; drag a tab to reorder"))
+                    .changed()
+                {
+                    app.settings.auto_save();
+                }
+                
                 ui.add_space(8.0);
 
-                ui.label("Thick Grid Interval (Horizontal):");
+                ui.label(tr("Thick Grid Interval (Horizontal):"));
                 if ui.add(egui::Slider::new(&mut app.viewer.thick_grid_interval_horizontal, 0..=64)).changed() {
                     app.settings.thick_grid_interval_horizontal = app.viewer.thick_grid_interval_horizontal;
                     app.settings.auto_save();
@@ -783,7 +1745,7 @@ fn render_settings_window(app: &mut BitApp, ctx: &egui::Context) {
 
                 ui.add_space(4.0);
 
-                ui.label("Thick Grid Interval (Vertical):");
+                ui.label(tr("Thick Grid Interval (Vertical):"));
                 if ui.add(egui::Slider::new(&mut app.viewer.thick_grid_interval_vertical, 0..=64)).changed() {
                     app.settings.thick_grid_interval_vertical = app.viewer.thick_grid_interval_vertical;
                     app.settings.auto_save();
@@ -791,7 +1753,7 @@ fn render_settings_window(app: &mut BitApp, ctx: &egui::Context) {
 
                 ui.add_space(4.0);
 
-                ui.label("Thick Grid Spacing (Horizontal):");
+                ui.label(tr("Thick Grid Spacing (Horizontal):"));
                 if ui.add(egui::Slider::new(&mut app.viewer.thick_grid_spacing_horizontal, 0.0..=10.0)).changed() {
                     app.settings.thick_grid_spacing_horizontal = app.viewer.thick_grid_spacing_horizontal;
                     app.settings.auto_save();
@@ -799,7 +1761,7 @@ fn render_settings_window(app: &mut BitApp, ctx: &egui::Context) {
 
                 ui.add_space(4.0);
 
-                ui.label("Thick Grid Spacing (Vertical):");
+                ui.label(tr("Thick Grid Spacing (Vertical):"));
                 if ui.add(egui::Slider::new(&mut app.viewer.thick_grid_spacing_vertical, 0.0..=10.0)).changed() {
                     app.settings.thick_grid_spacing_vertical = app.viewer.thick_grid_spacing_vertical;
                     app.settings.auto_save();
@@ -807,7 +1769,56 @@ fn render_settings_window(app: &mut BitApp, ctx: &egui::Context) {
 
                 ui.separator();
 
-                ui.label("GUI Font Size:");
+                ui.horizontal(|ui| {
+                    ui.label(tr("Theme:"));
+                    for theme in [AppTheme::System, AppTheme::Dark, AppTheme::Light] {
+                        if ui.selectable_label(app.settings.theme == theme, tr(theme.label())).clicked() {
+                            app.settings.theme = theme;
+                            app.settings.auto_save();
+                        }
+                    }
+                });
+                
+                ui.add_space(4.0);
+                
+                ui.horizontal(|ui| {
+                    ui.label(tr("Language:"));
+                    egui::ComboBox::from_id_salt("ui_language")
+                        .selected_text(app.settings.language.name())
+                        .show_ui(ui, |ui| {
+                            for language in Language::ALL {
+                                if ui.selectable_value(&mut app.settings.language, language, language.name()).changed() {
+                                    app.settings.auto_save();
+                                }
+                            }
+                        });
+                });
+                
+                ui.add_space(4.0);
+                
+                ui.horizontal(|ui| {
+                    ui.label(tr("UI Scale:"));
+                    // Applied when the drag ends so the slider doesn't move under the pointer
+                    let response = ui.add(egui::Slider::new(&mut app.ui_scale_input, MIN_UI_SCALE..=MAX_UI_SCALE)
+                        .step_by(0.05)
+                        .custom_formatter(|scale, _| format!("{:.0}%", scale * 100.0)))
+                        .on_hover_text(tr("Scales bits, byte cells, panels and text for high-DPI screens"));
+                    if response.drag_stopped() || (response.changed() && !response.dragged()) {
+                        app.settings.ui_scale = app.ui_scale_input;
+                        app.settings.auto_save();
+                    }
+                    for scale in [1.0, 1.5, 2.0] {
+                        if ui.selectable_label(app.settings.ui_scale == scale, format!("{:.0}%", scale * 100.0)).clicked() {
+                            app.settings.ui_scale = scale;
+                            app.ui_scale_input = scale;
+                            app.settings.auto_save();
+                        }
+                    }
+                });
+                
+                ui.add_space(4.0);
+                
+                ui.label(tr("GUI Font Size:"));
                 if ui.add(egui::Slider::new(&mut app.font_size, 8.0..=24.0)).changed() {
                     app.settings.font_size = app.font_size;
                     app.settings.auto_save();
@@ -815,18 +1826,136 @@ fn render_settings_window(app: &mut BitApp, ctx: &egui::Context) {
 
                 ui.separator();
                 
+                ui.heading(tr("Colors"));
+                ui.separator();
+                
+                let mut custom_colors = app.settings.bit_colors.is_some();
+                if ui.checkbox(&mut custom_colors, tr("Custom colors"))
+                    .on_hover_text(tr("When off, colors follow the light or dark theme"))
+                    .changed() {
+                    new_bit_colors = Some(custom_colors.then(|| crate::viewers::BitColors::for_dark_mode(ui.visuals().dark_mode)));
+                }
+                
+                if let Some(mut colors) = app.settings.bit_colors.clone() {
+                    let mut changed = false;
+                    egui::Grid::new("bit_colors_grid").num_columns(2).show(ui, |ui| {
+                        ui.label(tr("0 bits:"));
+                        changed |= ui.color_edit_button_srgb(&mut colors.zero).changed();
+                        ui.end_row();
+                        ui.label(tr("1 bits:"));
+                        changed |= ui.color_edit_button_srgb(&mut colors.one).changed();
+                        ui.end_row();
+                        ui.label(tr("Background:"));
+                        changed |= ui.color_edit_button_srgb(&mut colors.background).changed();
+                        ui.end_row();
+                        ui.label(tr("Grid lines:"));
+                        changed |= ui.color_edit_button_srgb(&mut colors.grid).changed();
+                        ui.end_row();
+                        ui.label(tr("Highlight:"));
+                        changed |= ui.color_edit_button_srgba_unmultiplied(&mut colors.highlight).changed();
+                        ui.end_row();
+                    });
+                    
+                    ui.label(tr("Pattern colors:"));
+                    ui.horizontal_wrapped(|ui| {
+                        for color in &mut colors.pattern_palette {
+                            changed |= ui.color_edit_button_srgb(color).changed();
+                        }
+                    });
+                    
+                    ui.horizontal(|ui| {
+                        ui.label(tr("Presets:"));
+                        if ui.button(tr("Light")).clicked() {
+                            colors = crate::viewers::BitColors::light();
+                            changed = true;
+                        }
+                        if ui.button(tr("Dark")).clicked() {
+                            colors = crate::viewers::BitColors::dark();
+                            changed = true;
+                        }
+                        if ui.button(tr("Colorblind Safe")).on_hover_text(tr("Okabe-Ito palette")).clicked() {
+                            colors = crate::viewers::BitColors::colorblind_safe();
+                            changed = true;
+                        }
+                    });
+                    
+                    if changed {
+                        new_bit_colors = Some(Some(colors));
+                    }
+                }
+                
+                ui.separator();
+                
+                ui.heading(tr("Autosave"));
+                ui.separator();
+                
+                if ui.checkbox(&mut app.settings.autosave_enabled, tr("Autosave recovery snapshots"))
+                    .on_hover_text(tr("Periodically snapshot the session so work can be recovered after a crash"))
+                    .changed() {
+                    app.settings.auto_save();
+                }
+                
+                ui.add_enabled_ui(app.settings.autosave_enabled, |ui| {
+                    ui.label(tr("Snapshot interval (seconds):"));
+                    if ui.add(egui::Slider::new(&mut app.settings.autosave_interval_secs, 15..=1800).logarithmic(true)).changed() {
+                        app.settings.auto_save();
+                    }
+                });
+                
+                ui.separator();
+                
+                ui.heading(tr("Operations"));
+                ui.separator();
+                
+                ui.label(tr("Warn when an operation drops more than (%):"));
+                if ui.add(egui::Slider::new(&mut app.settings.max_drop_percent, 0.0..=100.0))
+                    .on_hover_text(tr("Operations that produce no bits are always flagged"))
+                    .changed()
+                {
+                    app.settings.auto_save();
+                }
+                
+                ui.separator();
+                
+                ui.heading(tr("Large Files"));
+                ui.separator();
+                
+                if ui.checkbox(&mut app.settings.memory_map_large_files, tr("Memory-map large files"))
+                    .on_hover_text(tr("Large files are viewed straight from disk instead of being loaded into memory"))
+                    .changed() {
+                    app.settings.auto_save();
+                }
+                
+                ui.add_enabled_ui(app.settings.memory_map_large_files, |ui| {
+                    ui.label(tr("Memory-map files larger than (MB):"));
+                    if ui.add(egui::Slider::new(&mut app.settings.memory_map_threshold_mb, 16..=4096).logarithmic(true)).changed() {
+                        app.settings.auto_save();
+                    }
+                });
+                
+                ui.label(tr("Operation cache (MB):"))
+                    .on_hover_text(tr("Copies of intermediate results kept so editing a late operation doesn't rerun the whole chain; 0 turns the cache off"));
+                if ui.add(egui::Slider::new(&mut app.settings.operation_cache_mb, 0..=4096)).changed() {
+                    if let Ok(mut cache) = app.operation_cache.try_lock() {
+                        cache.set_budget(app.settings.operation_cache_budget());
+                    }
+                    app.settings.auto_save();
+                }
+                
+                ui.separator();
+                
                 ui.horizontal(|ui| {
-                    if ui.button("💾 Save Settings").clicked() {
+                    if ui.button(tr("💾 Save Settings")).clicked() {
                         if let Some(file_path) = rfd::FileDialog::new()
                             .add_filter("JSON", &["json"])
                             .set_file_name("settings.json")
                             .save_file() {
                             if let Err(e) = app.settings.save_to_file(&file_path) {
-                                app.error_message = Some(format!("Failed to save settings: {}", e));
+                                app.notifications.error(format!("Failed to save settings: {}", e));
                             }
                         }
                     }
-                    if ui.button("📂 Load Settings").clicked() {
+                    if ui.button(tr("📂 Load Settings")).clicked() {
                         if let Some(file_path) = rfd::FileDialog::new()
                             .add_filter("JSON", &["json"])
                             .pick_file() {
@@ -840,15 +1969,22 @@ fn render_settings_window(app: &mut BitApp, ctx: &egui::Context) {
                                     app.viewer.thick_grid_spacing_horizontal = app.settings.thick_grid_spacing_horizontal;
                                     app.viewer.thick_grid_spacing_vertical = app.settings.thick_grid_spacing_vertical;
                                     app.viewer.frame_length = app.settings.frame_length;
+                                    app.viewer.show_minimap = app.settings.show_minimap;
+                                    app.viewer.show_column_stats = app.settings.show_column_stats;
+                                    app.viewer.show_offsets = app.settings.show_bit_offsets;
+                                    app.viewer.show_row_density = app.settings.show_row_density;
+                                    app.viewer.colors = app.settings.bit_colors.clone();
+                                    app.byte_viewer.colors = app.settings.bit_colors.clone();
                                     app.font_size = app.settings.font_size;
+                                    app.ui_scale_input = app.settings.ui_scale;
                                 }
                                 Err(e) => {
-                                    app.error_message = Some(format!("Failed to load settings: {}", e));
+                                    app.notifications.error(format!("Failed to load settings: {}", e));
                                 }
                             }
                         }
                     }
-                    if ui.button("🔄 Reset to Defaults").clicked() {
+                    if ui.button(tr("🔄 Reset to Defaults")).clicked() {
                         app.settings = crate::storage::AppSettings::default();
                         app.viewer.shape = app.settings.bit_shape;
                         app.viewer.show_grid = app.settings.show_grid;
@@ -857,11 +1993,22 @@ fn render_settings_window(app: &mut BitApp, ctx: &egui::Context) {
                         app.viewer.thick_grid_spacing_horizontal = app.settings.thick_grid_spacing_horizontal;
                         app.viewer.thick_grid_spacing_vertical = app.settings.thick_grid_spacing_vertical;
                         app.viewer.frame_length = app.settings.frame_length;
+                        app.viewer.show_minimap = app.settings.show_minimap;
+                        app.viewer.show_column_stats = app.settings.show_column_stats;
+                        app.viewer.show_offsets = app.settings.show_bit_offsets;
+                        app.viewer.show_row_density = app.settings.show_row_density;
+                        app.viewer.colors = app.settings.bit_colors.clone();
+                        app.byte_viewer.colors = app.settings.bit_colors.clone();
                         app.font_size = app.settings.font_size;
+                        app.ui_scale_input = app.settings.ui_scale;
                         app.settings.auto_save();
                     }
                 });
             });
+        
+        if let Some(colors) = new_bit_colors {
+            app.set_bit_colors(colors);
+        }
     }
 }
 
@@ -875,6 +2022,75 @@ fn render_frame_width_finder_window(app: &mut BitApp, ctx: &egui::Context) {
     crate::ui::windows::render_frame_width_finder_window(app, ctx);
 }
 
+fn render_hdlc_decoder_window(app: &mut BitApp, ctx: &egui::Context) {
+    crate::ui::windows::render_hdlc_decoder_window(app, ctx);
+}
+
+fn render_framer_window(app: &mut BitApp, ctx: &egui::Context) {
+    crate::ui::windows::render_framer_window(app, ctx);
+}
+
+fn render_sources_window(app: &mut BitApp, ctx: &egui::Context) {
+    crate::ui::windows::render_sources_window(app, ctx);
+}
+
+fn render_periodicity_window(app: &mut BitApp, ctx: &egui::Context) {
+    crate::ui::windows::render_periodicity_window(app, ctx);
+}
+
+fn render_batch_window(app: &mut BitApp, ctx: &egui::Context) {
+    crate::ui::windows::render_batch_window(app, ctx);
+}
+
+fn render_repeats_window(app: &mut BitApp, ctx: &egui::Context) {
+    crate::ui::windows::render_repeats_window(app, ctx);
+}
+
+fn render_xor_key_window(app: &mut BitApp, ctx: &egui::Context) {
+    crate::ui::windows::render_xor_key_window(app, ctx);
+}
+
+fn render_channel_export_window(app: &mut BitApp, ctx: &egui::Context) {
+    crate::ui::windows::render_channel_export_window(app, ctx);
+}
+
+fn render_frame_stats_window(app: &mut BitApp, ctx: &egui::Context) {
+    crate::ui::windows::render_frame_stats_window(app, ctx);
+}
+
+fn render_frame_distance_window(app: &mut BitApp, ctx: &egui::Context) {
+    crate::ui::windows::render_frame_distance_window(app, ctx);
+}
+
+fn render_frames_csv_window(app: &mut BitApp, ctx: &egui::Context) {
+    crate::ui::windows::render_frames_csv_window(app, ctx);
+}
+
+fn render_notifications(app: &mut BitApp, ctx: &egui::Context) {
+    crate::ui::windows::render_notification_toasts(app, ctx);
+    crate::ui::windows::render_notifications_window(app, ctx);
+}
+
+fn render_converter_window(app: &mut BitApp, ctx: &egui::Context) {
+    crate::ui::windows::render_converter_window(app, ctx);
+}
+
+fn render_checksum_window(app: &mut BitApp, ctx: &egui::Context) {
+    crate::ui::windows::render_checksum_window(app, ctx);
+}
+
+fn render_structure_window(app: &mut BitApp, ctx: &egui::Context) {
+    crate::ui::windows::render_structure_window(app, ctx);
+}
+
+fn render_missing_files_window(app: &mut BitApp, ctx: &egui::Context) {
+    crate::ui::windows::render_missing_files_window(app, ctx);
+}
+
+fn render_report_window(app: &mut BitApp, ctx: &egui::Context) {
+    crate::ui::windows::render_report_window(app, ctx);
+}
+
 fn render_operation_windows(app: &mut BitApp, ctx: &egui::Context) {
     crate::ui::windows::render_operation_windows(app, ctx);
 }
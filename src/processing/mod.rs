@@ -1,11 +0,0 @@
-// Processing module - bit manipulation operations
-
-pub mod interleaver;
-pub mod operations;
-
-#[allow(unused_imports)]
-pub use operations::{BitOperation, Operation, OperationSequence, WorksheetOperation};
-pub use interleaver::{
-    BlockInterleaverConfig, ConvolutionalInterleaverConfig,
-    InterleaverDirection, InterleaverType,
-};
@@ -1,11 +0,0 @@
-// Storage module - file I/O, sessions, settings, and worksheets
-
-pub mod file_io;
-pub mod session;
-pub mod settings;
-pub mod worksheet;
-
-pub use file_io::{read_file_as_bits, read_file_as_bits_with_progress, write_bits_to_file, LoadProgress};
-pub use session::AppSession;
-pub use settings::AppSettings;
-pub use worksheet::Worksheet;
@@ -2,6 +2,7 @@
 
 use crate::app::BitApp;
 use crate::core::ViewMode;
+use crate::i18n::tr;
 use eframe::egui;
 
 pub fn render(app: &mut BitApp, ctx: &egui::Context) {
@@ -11,41 +12,116 @@ pub fn render(app: &mut BitApp, ctx: &egui::Context) {
             
             ui.separator();
 
-            if ui.button("💾 Save File").clicked() {
+            if ui.button(tr("💾 Save File")).clicked() {
                 app.save_file();
             }
 
+            if ui.button(tr("🔀 Export Channels")).on_hover_text(tr("Write each round-robin sub-stream to its own file")).clicked() {
+                app.show_channel_export = !app.show_channel_export;
+            }
+
+            ui.menu_button(tr("📁 Project"), |ui| {
+                if ui.button(tr("📂 Open Project...")).clicked() {
+                    app.open_project();
+                    ui.close();
+                }
+                if ui.button(tr("💾 Save Project")).clicked() {
+                    app.save_project();
+                    ui.close();
+                }
+                if ui.button(tr("💾 Save Project As...")).clicked() {
+                    app.save_project_as();
+                    ui.close();
+                }
+            });
+            if let Some(name) = app.project_name() {
+                ui.label(format!("📁 {}", name));
+            }
+
             ui.separator();
 
-            if ui.button("⚙ Settings").clicked() {
+            if ui.button(tr("⚙ Settings")).clicked() {
                 app.show_settings = !app.show_settings;
             }
 
-            if ui.button("🔍 Pattern Locator").clicked() {
+            if ui.button(tr("🔍 Pattern Locator")).clicked() {
                 app.show_pattern_locator = !app.show_pattern_locator;
             }
 
-            if ui.button("📏 Frame Width Finder").clicked() {
+            if ui.button(tr("📏 Frame Width Finder")).clicked() {
                 app.show_frame_width_finder = !app.show_frame_width_finder;
             }
 
+            if ui.button(tr("〰 Periodicity")).clicked() {
+                app.show_periodicity = !app.show_periodicity;
+            }
+
+            if ui.button(tr("🔁 Repeats")).clicked() {
+                app.show_repeats = !app.show_repeats;
+            }
+
+            if ui.button(tr("📋 Frame Stats")).clicked() {
+                app.show_frame_stats = !app.show_frame_stats;
+            }
+
+            if ui.button(tr("📉 Frame Distance")).clicked() {
+                app.show_frame_distance = !app.show_frame_distance;
+            }
+
+            if ui.button(tr("🧮 Convert")).clicked() {
+                app.show_converter = !app.show_converter;
+            }
+
+            if ui.button(tr("Σ Checksums")).clicked() {
+                app.show_checksums = !app.show_checksums;
+            }
+
+            if ui.button(tr("🧱 Structures")).clicked() {
+                app.show_structures = !app.show_structures;
+            }
+
+            if ui.button(tr("🔑 XOR Key")).clicked() {
+                app.show_xor_key = !app.show_xor_key;
+            }
+
+            if ui.button(tr("🗂 Batch")).clicked() {
+                app.show_batch = !app.show_batch;
+            }
+
+            if ui.button(tr("📡 HDLC Decoder")).clicked() {
+                app.show_hdlc_decoder = !app.show_hdlc_decoder;
+            }
+
+            if ui.button(tr("🧱 Framer")).clicked() {
+                app.show_framer = !app.show_framer;
+            }
+
+            if ui.button(tr("🌐 Sources")).clicked() {
+                app.show_sources = !app.show_sources;
+            }
+
             ui.separator();
 
             // View mode toggle
-            ui.label("View:");
-            if ui.selectable_label(app.view_mode == ViewMode::Bit, "⬛ Bit").clicked() {
+            ui.label(tr("View:"));
+            if ui.selectable_label(app.view_mode == ViewMode::Bit, tr("⬛ Bit")).clicked() {
                 app.view_mode = ViewMode::Bit;
             }
-            if ui.selectable_label(app.view_mode == ViewMode::Byte, "📊 Byte").clicked() {
+            if ui.selectable_label(app.view_mode == ViewMode::Byte, tr("📊 Byte")).clicked() {
                 app.view_mode = ViewMode::Byte;
             }
-            if ui.selectable_label(app.view_mode == ViewMode::Ascii, "🔤 ASCII").clicked() {
+            if ui.selectable_label(app.view_mode == ViewMode::Ascii, tr("🔤 ASCII")).clicked() {
                 app.view_mode = ViewMode::Ascii;
             }
+            if ui.selectable_label(app.view_mode == ViewMode::Symbol, tr("🔢 Symbol"))
+                .on_hover_text(tr("Fixed-width N-bit symbols, for data that doesn't line up with bytes"))
+                .clicked() {
+                app.view_mode = ViewMode::Symbol;
+            }
 
             ui.separator();
 
-            ui.label("Zoom:");
+            ui.label(tr("Zoom:"));
             if ui.button("➕").clicked() {
                 app.viewer.zoom_in();
             }
@@ -55,21 +131,76 @@ pub fn render(app: &mut BitApp, ctx: &egui::Context) {
             if ui.button("🔄").clicked() {
                 app.viewer.reset_zoom();
             }
+            if ui.button("↔").on_hover_text(tr("Fit one frame to the window width (Ctrl+wheel zooms the bit view)")).clicked() {
+                app.viewer.fit_to_width();
+            }
+
+            ui.separator();
+
+            ui.label(tr("Go to:"));
+            let goto_response = ui.add(
+                egui::TextEdit::singleline(&mut app.goto_input)
+                    .desired_width(90.0)
+                    .hint_text(tr("offset"))
+            ).on_hover_text(tr("Bit or byte offset; math expressions like 128*8+3 are allowed"));
+            if ui.selectable_label(!app.goto_in_bytes, "bit").clicked() {
+                app.goto_in_bytes = false;
+            }
+            if ui.selectable_label(app.goto_in_bytes, "byte").clicked() {
+                app.goto_in_bytes = true;
+            }
+            let submitted = goto_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if ui.button("➡").on_hover_text(tr("Go to offset")).clicked() || submitted {
+                let now = ui.input(|i| i.time);
+                app.go_to_offset(now);
+            }
 
             ui.separator();
 
-            if ui.selectable_label(app.show_original, "Original").clicked() {
+            if ui.selectable_label(app.show_original, tr("Original")).clicked() {
                 app.show_original = true;
                 if app.view_mode == ViewMode::Bit {
                     app.update_viewer();
                 }
             }
-            if ui.selectable_label(!app.show_original, "Processed").clicked() {
+            if ui.selectable_label(!app.show_original, tr("Processed")).clicked() {
                 app.show_original = false;
                 if app.view_mode == ViewMode::Bit {
                     app.update_viewer();
                 }
             }
+
+            ui.separator();
+
+            if ui.selectable_label(app.edit_mode, tr("✏ Edit"))
+                .on_hover_text(tr("Click bits to flip them, or select a byte in the byte view and type hex.\nEdits are recorded as a Bit Edits operation."))
+                .clicked()
+            {
+                app.set_edit_mode(!app.edit_mode);
+            }
+            if app.edit_mode
+                && ui.add_enabled(app.can_undo_bit_edit(), egui::Button::new(tr("↶ Undo Edit")))
+                    .on_hover_text(tr("Undo the last bit edit (Ctrl+Z)"))
+                    .clicked()
+            {
+                app.undo_bit_edit();
+            }
+
+            ui.separator();
+
+            let bell = if app.notifications.is_empty() {
+                "🔔".to_string()
+            } else {
+                format!("🔔 {}", app.notifications.len())
+            };
+            let bell_text = if app.notifications.error_count() > 0 {
+                egui::RichText::new(bell).color(egui::Color32::from_rgb(220, 80, 80))
+            } else {
+                egui::RichText::new(bell)
+            };
+            if ui.selectable_label(app.show_notifications, bell_text).on_hover_text(tr("Errors and warnings")).clicked() {
+                app.show_notifications = !app.show_notifications;
+            }
         });
     });
 }
@@ -1,15 +1,27 @@
 // Window rendering functions for pattern locator and operation editors
 
 use crate::analysis::{Pattern, PatternFormat};
-use crate::app::BitApp;
-use crate::core::OperationType;
+use crate::app::{AnalysisScope, BitApp, OperationPreview};
+use crate::i18n::tr;
+use crate::core::{Notification, NotificationLevel, OperationType};
+use crate::processing::{builtin_takeskip_presets, LineCode, NandArea, NandEcc, RangeAction, SoftFormat, SymbolCode, TieBreak};
+use crate::storage::natural_cmp;
 use crate::utils::eval_expression;
+use crate::viewers::{rgb, BitColors};
+use bitvec::prelude::*;
 use eframe::egui;
 
 pub fn render_pattern_locator_window(app: &mut BitApp, ctx: &egui::Context) {
     if app.show_pattern_locator {
-        egui::Window::new("🔍 Pattern Locator")
-            .open(&mut app.show_pattern_locator)
+        let mut export_matches = false;
+        let mut search_all = false;
+        let mut timeline_jump = None;
+        let mut session_jump = None;
+        let mut step_match = None;
+        let mut go_to_match = None;
+        let mut keep_open = true;
+        egui::Window::new(tr("🔍 Pattern Locator"))
+            .open(&mut keep_open)
             .default_width(450.0)
             .default_height(700.0)
             .resizable(true)
@@ -18,36 +30,42 @@ pub fn render_pattern_locator_window(app: &mut BitApp, ctx: &egui::Context) {
                 egui::ScrollArea::vertical()
                     .auto_shrink([false, false])
                     .show(ui, |ui| {
-                        ui.heading("Search for Bit Patterns");
+                        ui.heading(tr("Search for Bit Patterns"));
                         ui.separator();
                         
                         ui.group(|ui| {
-                            ui.heading("Add Pattern");
+                            ui.heading(tr("Add Pattern"));
                             
                             ui.horizontal(|ui| {
-                                ui.label("Name:");
+                                ui.label(tr("Name:"));
                                 ui.text_edit_singleline(&mut app.pattern_name_input);
                             });
                             
                             ui.horizontal(|ui| {
-                                ui.label("Format:");
-                                ui.selectable_value(&mut app.pattern_format, PatternFormat::Bits, "Bits");
-                                ui.selectable_value(&mut app.pattern_format, PatternFormat::Hex, "Hex");
-                                ui.selectable_value(&mut app.pattern_format, PatternFormat::Ascii, "ASCII");
+                                ui.label(tr("Format:"));
+                                ui.selectable_value(&mut app.pattern_format, PatternFormat::Bits, tr("Bits"));
+                                ui.selectable_value(&mut app.pattern_format, PatternFormat::Hex, tr("Hex"));
+                                ui.selectable_value(&mut app.pattern_format, PatternFormat::Ascii, tr("ASCII"));
                             });
                             
                             ui.horizontal(|ui| {
-                                ui.label("Pattern:");
+                                ui.label(tr("Pattern:"));
                                 ui.text_edit_singleline(&mut app.pattern_input);
                             });
                             
                             ui.horizontal(|ui| {
-                                ui.label("Garbles:");
+                                ui.label(tr("Garbles:"));
                                 ui.add(egui::Slider::new(&mut app.pattern_garbles, 0..=16));
                             });
                             
                             ui.horizontal(|ui| {
-                                if ui.button("➕ Add Pattern").clicked() {
+                                ui.label(tr("Slips:"));
+                                ui.add(egui::Slider::new(&mut app.pattern_slips, 0..=8))
+                                    .on_hover_text(tr("Inserted or dropped bits allowed in a match, for clock-slipped captures"));
+                            });
+                            
+                            ui.horizontal(|ui| {
+                                if ui.button(tr("➕ Add Pattern")).clicked() {
                                     let name = if app.pattern_name_input.is_empty() {
                                         format!("Pattern {}", app.patterns.len() + 1)
                                     } else {
@@ -55,54 +73,100 @@ pub fn render_pattern_locator_window(app: &mut BitApp, ctx: &egui::Context) {
                                     };
                                     
                                     match Pattern::new(name, app.pattern_format, app.pattern_input.clone(), app.pattern_garbles) {
-                                        Ok(pattern) => {
+                                        Ok(mut pattern) => {
+                                            pattern.slips = app.pattern_slips;
                                             app.patterns.push(pattern);
                                             app.pattern_name_input.clear();
                                             app.pattern_input.clear();
-                                            app.error_message = None;
                                         }
                                         Err(e) => {
-                                            app.error_message = Some(format!("Invalid pattern: {}", e));
+                                            app.notifications.error(format!("Invalid pattern: {}", e));
                                         }
                                     }
                                 }
                                 
-                                if ui.button("🔄 Clear").clicked() {
+                                if ui.button(tr("🔄 Clear")).clicked() {
                                     app.pattern_name_input.clear();
                                     app.pattern_input.clear();
                                     app.pattern_garbles = 0;
+                                    app.pattern_slips = 0;
                                 }
                             });
                         });
                         
                         ui.separator();
-                        ui.heading("Patterns");
+                        render_analysis_scope(app, ui);
+                        
+                        ui.separator();
+                        ui.heading(tr("Patterns"));
                         
+                        if !app.patterns.is_empty()
+                            && ui.button(tr("🔍 Search All"))
+                                .on_hover_text(tr("Search every pattern and show the matches together on a timeline"))
+                                .clicked()
+                        {
+                            search_all = true;
+                        }
+                        if app.patterns.iter().any(|pattern| !pattern.matches.is_empty()) {
+                            ui.horizontal(|ui| {
+                                if ui.button(tr("💾 Export Matches...")).on_hover_text(tr("Write all matches to CSV or JSON")).clicked() {
+                                    export_matches = true;
+                                }
+                                ui.label(tr("Context bytes:"));
+                                ui.add(egui::DragValue::new(&mut app.match_export_context_bytes).range(0..=64));
+                            });
+                        }
                         if app.patterns.is_empty() {
-                            ui.label("No patterns added yet");
+                            ui.label(tr("No patterns added yet"));
                         } else {
                             let mut to_remove = None;
                             let mut to_search = None;
+                            let mut to_search_session = None;
+                            let colors = app.viewer.colors.clone().unwrap_or_else(|| BitColors::for_dark_mode(ui.visuals().dark_mode));
                             
-                            for (idx, pattern) in app.patterns.iter().enumerate() {
+                            for (idx, pattern) in app.patterns.iter_mut().enumerate() {
                                 ui.group(|ui| {
                                     ui.horizontal(|ui| {
+                                        ui.checkbox(&mut pattern.visible, "")
+                                            .on_hover_text(tr("Highlight this pattern's matches in the views"));
+                                        let color = rgb(colors.color_of(pattern, idx));
+                                        let mut rgb = [color.r(), color.g(), color.b()];
+                                        if ui.color_edit_button_srgb(&mut rgb).on_hover_text(tr("Match highlight color")).changed() {
+                                            pattern.color = Some(rgb);
+                                        }
+                                        if pattern.color.is_some()
+                                            && ui.small_button("↺").on_hover_text(tr("Use the palette color again")).clicked()
+                                        {
+                                            pattern.color = None;
+                                        }
+                                        
                                         let selected = app.selected_pattern == Some(idx);
                                         if ui.selectable_label(selected, &pattern.name).clicked() {
                                             app.selected_pattern = Some(idx);
                                         }
                                         
-                                        if ui.button("🔍 Search").clicked() {
+                                        if ui.button(tr("🔍 Search")).clicked() {
                                             to_search = Some(idx);
                                         }
                                         
+                                        if ui.button("🌐")
+                                            .on_hover_text(tr("Search the processed bits of every worksheet"))
+                                            .clicked()
+                                        {
+                                            to_search_session = Some(idx);
+                                        }
+                                        
                                         if ui.button("❌").clicked() {
                                             to_remove = Some(idx);
                                         }
                                     });
                                     
                                     ui.label(format!("Pattern: {}", pattern.input));
-                                    ui.label(format!("Garbles: {} | Matches: {}", pattern.garbles, pattern.matches.len()));
+                                    if pattern.slips > 0 {
+                                        ui.label(format!("Garbles: {} | Slips: {} | Matches: {}", pattern.garbles, pattern.slips, pattern.matches.len()));
+                                    } else {
+                                        ui.label(format!("Garbles: {} | Matches: {}", pattern.garbles, pattern.matches.len()));
+                                    }
                                 });
                             }
                             
@@ -114,16 +178,55 @@ pub fn render_pattern_locator_window(app: &mut BitApp, ctx: &egui::Context) {
                             }
                             
                             if let Some(idx) = to_search {
-                                let bits_to_search = if app.show_original {
-                                    &app.original_bits
-                                } else {
-                                    &app.processed_bits
-                                };
-                                app.patterns[idx].search(bits_to_search);
-                                app.selected_pattern = Some(idx);
+                                app.search_pattern(idx);
+                            }
+                            
+                            if let Some(idx) = to_search_session {
+                                app.search_session(idx);
                             }
                         }
                         
+                        if app.patterns.iter().filter(|pattern| !pattern.matches.is_empty()).count() > 1 {
+                            ui.separator();
+                            ui.collapsing(tr("Combined Timeline"), |ui| {
+                                timeline_jump = render_match_timeline(app, ui);
+                            });
+                        }
+                        
+                        if let Some((pattern_name, results)) = &app.session_matches {
+                            ui.separator();
+                            let total: usize = results.iter().map(|ws| ws.matches.len()).sum();
+                            ui.collapsing(format!("All Worksheets: '{}' ({} matches)", pattern_name, total), |ui| {
+                                for ws in results {
+                                    let title = match &ws.error {
+                                        Some(_) => format!("{} (not searched)", ws.worksheet_name),
+                                        None => format!("{} ({} matches)", ws.worksheet_name, ws.matches.len()),
+                                    };
+                                    egui::CollapsingHeader::new(title)
+                                        .id_salt(("session_matches", ws.worksheet_id))
+                                        .show(ui, |ui| {
+                                            if let Some(e) = &ws.error {
+                                                ui.colored_label(egui::Color32::from_rgb(220, 120, 60), e);
+                                            }
+                                            egui::ScrollArea::vertical()
+                                                .id_salt(("session_matches_scroll", ws.worksheet_id))
+                                                .max_height(200.0)
+                                                .show(ui, |ui| {
+                                                    for m in &ws.matches {
+                                                        let mut text = format!("@{}", m.position);
+                                                        if m.mismatches > 0 {
+                                                            text.push_str(&format!(" ~{}", m.mismatches));
+                                                        }
+                                                        if ui.link(text).on_hover_text(tr("Open the worksheet and jump here")).clicked() {
+                                                            session_jump = Some((ws.worksheet_id, m.position, m.actual_bits.len()));
+                                                        }
+                                                    }
+                                                });
+                                        });
+                                }
+                            });
+                        }
+                        
                         ui.separator();
                         
                         if let Some(pattern_idx) = app.selected_pattern {
@@ -133,16 +236,29 @@ pub fn render_pattern_locator_window(app: &mut BitApp, ctx: &egui::Context) {
                                 ui.heading(format!("Results for '{}'", pattern.name));
                                 ui.label(format!("Found {} matches", pattern.matches.len()));
                                 
+                                if let Some((current, total)) = app.match_position().filter(|&(_, total)| total > 0) {
+                                    ui.horizontal(|ui| {
+                                        if ui.button("◀").on_hover_text(tr("Previous match (Shift+F3)")).clicked() {
+                                            step_match = Some(false);
+                                        }
+                                        let shown = if current > 0 { current.to_string() } else { "-".to_string() };
+                                        ui.label(format!("Match {}/{}", shown, total));
+                                        if ui.button("▶").on_hover_text(tr("Next match (F3)")).clicked() {
+                                            step_match = Some(true);
+                                        }
+                                    });
+                                }
+                                
                                 if !pattern.matches.is_empty() {
                                     ui.horizontal(|ui| {
-                                        if ui.button("🎯 Highlight All").clicked() {
+                                        if ui.button(tr("🎯 Highlight All")).clicked() {
                                             app.viewer.clear_highlights();
                                             for m in &pattern.matches {
-                                                app.viewer.add_highlight_range(m.position, pattern.bits.len());
+                                                app.viewer.add_highlight_range(m.position, m.actual_bits.len());
                                             }
                                         }
                                         
-                                        if ui.button("🔲 Clear Highlights").clicked() {
+                                        if ui.button(tr("🔲 Clear Highlights")).clicked() {
                                             app.viewer.clear_highlights();
                                         }
                                     });
@@ -155,9 +271,7 @@ pub fn render_pattern_locator_window(app: &mut BitApp, ctx: &egui::Context) {
                                             for (idx, m) in pattern.matches.iter().enumerate() {
                                                 ui.horizontal(|ui| {
                                                     if ui.button(format!("#{}", idx + 1)).clicked() {
-                                                        app.viewer.clear_highlights();
-                                                        app.viewer.add_highlight_range(m.position, pattern.bits.len());
-                                                        app.viewer.jump_to_position(m.position);
+                                                        go_to_match = Some(idx);
                                                     }
                                                     
                                                     ui.label(format!("@{}", m.position));
@@ -169,6 +283,11 @@ pub fn render_pattern_locator_window(app: &mut BitApp, ctx: &egui::Context) {
                                                     if m.mismatches > 0 {
                                                         ui.label(format!("~{}", m.mismatches));
                                                     }
+                                                    
+                                                    if m.slips > 0 {
+                                                        ui.label(format!("±{}", m.slips))
+                                                            .on_hover_text(tr("Inserted or dropped bits"));
+                                                    }
                                                 });
                                             }
                                         });
@@ -177,7 +296,140 @@ pub fn render_pattern_locator_window(app: &mut BitApp, ctx: &egui::Context) {
                         }
                     });
             });
+        
+        app.show_pattern_locator = keep_open;
+        
+        if export_matches {
+            app.export_pattern_matches();
+        }
+        if search_all {
+            app.search_all_patterns();
+        }
+        if let Some((start, len)) = timeline_jump {
+            app.jump_to_range(start, len, ctx.input(|i| i.time));
+        }
+        if let Some((worksheet_id, start, len)) = session_jump {
+            app.jump_to_worksheet_match(worksheet_id, start, len, ctx.input(|i| i.time));
+        }
+        if let Some(forward) = step_match {
+            app.step_match(forward, ctx.input(|i| i.time));
+        }
+        if let Some(index) = go_to_match {
+            app.go_to_match(index, ctx.input(|i| i.time));
+        }
+    }
+}
+
+/// One lane per pattern with a tick at each match across the whole stream, followed by the
+/// most common pattern-to-pattern transitions. Returns the match clicked on, if any.
+fn render_match_timeline(app: &BitApp, ui: &mut egui::Ui) -> Option<(usize, usize)> {
+    use crate::analysis::{match_timeline, pattern_transitions};
+    use crate::viewers::BitColors;
+    
+    const LANE_HEIGHT: f32 = 14.0;
+    const LABEL_WIDTH: f32 = 90.0;
+    
+    let events = match_timeline(&app.patterns);
+    let lanes: Vec<usize> = (0..app.patterns.len()).filter(|i| !app.patterns[*i].matches.is_empty()).collect();
+    let colors = app.viewer.colors.clone().unwrap_or_else(|| BitColors::for_dark_mode(ui.visuals().dark_mode));
+    let total_bits = app.display_bits().len().max(1);
+    
+    let width = ui.available_width().max(LABEL_WIDTH + 100.0);
+    let (rect, response) = ui.allocate_exact_size(
+        egui::vec2(width, LANE_HEIGHT * lanes.len() as f32),
+        egui::Sense::click(),
+    );
+    let painter = ui.painter_at(rect);
+    let strip_left = rect.left() + LABEL_WIDTH;
+    let strip_width = rect.width() - LABEL_WIDTH;
+    let x_of = |position: usize| strip_left + position as f32 / total_bits as f32 * strip_width;
+    
+    painter.rect_filled(egui::Rect::from_min_max(egui::pos2(strip_left, rect.top()), rect.max), 2.0, ui.visuals().extreme_bg_color);
+    for (lane, &pattern) in lanes.iter().enumerate() {
+        let top = rect.top() + lane as f32 * LANE_HEIGHT;
+        painter.text(
+            egui::pos2(rect.left(), top + LANE_HEIGHT / 2.0),
+            egui::Align2::LEFT_CENTER,
+            &app.patterns[pattern].name,
+            egui::FontId::proportional(11.0),
+            ui.visuals().text_color(),
+        );
+        let color = rgb(colors.color_of(&app.patterns[pattern], pattern));
+        for event in events.iter().filter(|event| event.pattern == pattern) {
+            let x = x_of(event.position);
+            painter.line_segment(
+                [egui::pos2(x, top + 2.0), egui::pos2(x, top + LANE_HEIGHT - 2.0)],
+                egui::Stroke::new(1.5, color),
+            );
+        }
     }
+    
+    // The match nearest the pointer in the lane it is over
+    let nearest = response.hover_pos().and_then(|pos| {
+        let lane = ((pos.y - rect.top()) / LANE_HEIGHT) as usize;
+        let pattern = *lanes.get(lane)?;
+        events
+            .iter()
+            .filter(|event| event.pattern == pattern)
+            .min_by(|a, b| (x_of(a.position) - pos.x).abs().total_cmp(&(x_of(b.position) - pos.x).abs()))
+            .copied()
+    });
+    let mut clicked = None;
+    if let Some(event) = nearest {
+        if response.clicked() {
+            clicked = Some((event.position, event.length.max(1)));
+        }
+        response.on_hover_text(format!("{} @{}", app.patterns[event.pattern].name, event.position));
+    }
+    
+    ui.label(format!("{} matches from {} patterns", events.len(), lanes.len()));
+    let transitions = pattern_transitions(&events);
+    if !transitions.is_empty() {
+        ui.label(tr("Most common sequences:"));
+        for ((from, to), count) in transitions.iter().take(8) {
+            ui.label(format!("  {} → {}  ×{}", app.patterns[*from].name, app.patterns[*to].name, count));
+        }
+    }
+    clicked
+}
+
+/// Scope selector shown in the analysis windows: whole stream, viewer selection or an offset range
+fn render_analysis_scope(app: &mut BitApp, ui: &mut egui::Ui) {
+    ui.horizontal_wrapped(|ui| {
+        ui.label(tr("Scope:"));
+        ui.selectable_value(&mut app.analysis_scope, AnalysisScope::Whole, tr("Whole Stream"));
+        ui.selectable_value(&mut app.analysis_scope, AnalysisScope::Selection, tr("Selection"));
+        if ui.selectable_value(&mut app.analysis_scope, AnalysisScope::Range, tr("Range")).clicked()
+            && app.analysis_range_end == 0
+        {
+            (app.analysis_range_start, app.analysis_range_end) = app.viewer.selection
+                .unwrap_or((0, app.display_bits().len()));
+        }
+        
+        match app.analysis_scope {
+            AnalysisScope::Whole => {}
+            AnalysisScope::Selection => match app.viewer.selection {
+                Some((start, end)) => {
+                    ui.label(format!("bits {}..{} ({} bits)", start, end, end - start));
+                    if ui.small_button(tr("Clear")).clicked() {
+                        app.viewer.selection = None;
+                    }
+                }
+                None => {
+                    ui.weak(tr("Shift-drag in the bit viewer to select"));
+                }
+            },
+            AnalysisScope::Range => {
+                ui.add(egui::DragValue::new(&mut app.analysis_range_start).prefix(format!("{} ", tr("from"))));
+                ui.add(egui::DragValue::new(&mut app.analysis_range_end).prefix("to "));
+                if let Some((start, end)) = app.viewer.selection
+                    && ui.small_button(tr("Use Selection")).clicked()
+                {
+                    (app.analysis_range_start, app.analysis_range_end) = (start, end);
+                }
+            }
+        }
+    });
 }
 
 pub fn render_operation_windows(app: &mut BitApp, ctx: &egui::Context) {
@@ -195,11 +447,28 @@ pub fn render_operation_windows(app: &mut BitApp, ctx: &egui::Context) {
             .show(ctx, |ui| {
                 match op_type {
                     OperationType::LoadFile => render_loadfile_editor(app, ui),
+                    OperationType::MultiFileLoad => render_multifile_editor(app, ui),
                     OperationType::TakeSkipSequence => render_takeskip_editor(app, ui),
                     OperationType::InvertBits => render_invert_editor(app, ui),
                     OperationType::MultiWorksheetLoad => render_multiworksheet_editor(app, ui),
                     OperationType::TruncateBits => render_truncate_editor(app, ui),
                     OperationType::InterleaveBits => render_interleave_editor(app, ui),
+                    OperationType::InsertBits => render_insert_editor(app, ui),
+                    OperationType::DeleteBits => render_delete_editor(app, ui),
+                    OperationType::RangeBits => render_range_editor(app, ui),
+                    OperationType::XorKey => render_xor_editor(app, ui),
+                    OperationType::LineDecode => render_line_code_editor(app, ui),
+                    OperationType::SymbolConvert => render_symbol_code_editor(app, ui),
+                    OperationType::SymbolRemap => render_remap_editor(app, ui),
+                    OperationType::MajorityVote => render_majority_editor(app, ui),
+                    OperationType::NandLayout => render_nand_editor(app, ui),
+                    OperationType::LoadSoftBits => render_softload_editor(app, ui),
+                    OperationType::Conditional => render_conditional_editor(app, ui),
+                    OperationType::Variants => render_variants_editor(app, ui),
+                    OperationType::Macro => render_macro_editor(app, ui),
+                    OperationType::Group => render_group_editor(app, ui),
+                    OperationType::PatchBits => render_patch_editor(app, ui),
+                    OperationType::Script => render_script_editor(app, ui),
                 }
             });
         
@@ -210,11 +479,11 @@ pub fn render_operation_windows(app: &mut BitApp, ctx: &egui::Context) {
 }
 
 fn render_loadfile_editor(app: &mut BitApp, ui: &mut egui::Ui) {
-    ui.heading("Load File");
+    ui.heading(tr("Load File"));
     ui.separator();
     
     ui.horizontal(|ui| {
-        ui.label("Name:");
+        ui.label(tr("Name:"));
         ui.text_edit_singleline(&mut app.loadfile_name);
     });
     
@@ -223,10 +492,10 @@ fn render_loadfile_editor(app: &mut BitApp, ui: &mut egui::Ui) {
     if let Some(path) = &app.loadfile_path {
         ui.label(format!("📄 Selected: {}", path.display()));
     } else {
-        ui.label("No file selected");
+        ui.label(tr("No file selected"));
     }
     
-    if ui.button("📂 Browse...").clicked() {
+    if ui.button(tr("📂 Browse...")).clicked() {
         if let Some(path) = rfd::FileDialog::new().pick_file() {
             app.loadfile_path = Some(path);
         }
@@ -235,994 +504,4048 @@ fn render_loadfile_editor(app: &mut BitApp, ui: &mut egui::Ui) {
     ui.add_space(8.0);
     
     ui.horizontal(|ui| {
-        if ui.button("✓ Save").clicked() {
+        ui.label(tr("From byte:"));
+        ui.add(egui::TextEdit::singleline(&mut app.loadfile_offset).desired_width(100.0));
+        ui.label(tr("Length:"));
+        ui.add(egui::TextEdit::singleline(&mut app.loadfile_length).desired_width(100.0).hint_text(tr("to end")))
+            .on_hover_text(tr("Bytes to load; empty loads the rest of the file"));
+    });
+    ui.label(tr("Only this part of the file is read, so part of a huge capture can be loaded"));
+    
+    ui.add_space(8.0);
+    
+    ui.horizontal(|ui| {
+        if ui.button(tr("✓ Save")).clicked() {
             app.save_current_operation();
         }
         
-        if ui.button("✗ Cancel").clicked() {
+        if ui.button(tr("✗ Cancel")).clicked() {
             app.cancel_operation_edit();
         }
     });
 }
 
-fn render_takeskip_editor(app: &mut BitApp, ui: &mut egui::Ui) {
-    ui.heading("Take/Skip Sequence");
+fn render_softload_editor(app: &mut BitApp, ui: &mut egui::Ui) {
+    ui.heading(tr("Load Soft Bits"));
     ui.separator();
     
     ui.horizontal(|ui| {
-        ui.label("Name:");
-        ui.text_edit_singleline(&mut app.takeskip_name);
+        ui.label(tr("Name:"));
+        ui.text_edit_singleline(&mut app.softload_name);
     });
     
     ui.add_space(8.0);
     
-    ui.label("Enter a sequence of operations:");
-    ui.label("• t = take N bits");
-    ui.label("• r = reverse N bits");
-    ui.label("• i = invert N bits");
-    ui.label("• s = skip N bits");
+    if let Some(path) = &app.softload_path {
+        ui.label(format!("📄 Selected: {}", path.display()));
+    } else {
+        ui.label(tr("No file selected"));
+    }
+    
+    if ui.button(tr("📂 Browse...")).clicked()
+        && let Some(path) = rfd::FileDialog::new().pick_file()
+    {
+        app.softload_path = Some(path);
+    }
     
     ui.add_space(8.0);
     
     ui.horizontal(|ui| {
-        ui.label("Sequence:");
-        let response = ui.text_edit_singleline(&mut app.takeskip_input);
-        
-        if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-            app.save_current_operation();
-        }
+        ui.label(tr("Samples:"));
+        egui::ComboBox::from_id_salt("softload_format")
+            .selected_text(app.softload_format.name())
+            .show_ui(ui, |ui| {
+                for format in SoftFormat::ALL {
+                    ui.selectable_value(&mut app.softload_format, format, format.name());
+                }
+            });
     });
     
-    ui.label("Example: t4r3i8s1");
+    ui.checkbox(&mut app.softload_combine, tr("Combine repeated copies before slicing"))
+        .on_hover_text(tr("Add up the samples of each repeated bit or frame, so confident copies outvote doubtful ones"));
+    if app.softload_combine {
+        ui.indent("softload_vote", |ui| render_vote_fields(app, ui));
+    }
+    
+    ui.add_space(4.0);
+    ui.label(tr("💡 Tips:"));
+    ui.label(tr("• One sample per bit; positive samples are 1s"));
+    ui.label(tr("• Each bit's confidence shows as its brightness while this is the only operation"));
     
     ui.add_space(8.0);
     
     ui.horizontal(|ui| {
-        if ui.button("✓ Save").clicked() {
+        if ui.button(tr("✓ Save")).clicked() {
             app.save_current_operation();
         }
         
-        if ui.button("✗ Cancel").clicked() {
+        if ui.button(tr("✗ Cancel")).clicked() {
             app.cancel_operation_edit();
         }
     });
 }
 
-fn render_invert_editor(app: &mut BitApp, ui: &mut egui::Ui) {
-    ui.heading("Invert All Bits");
+fn render_multifile_editor(app: &mut BitApp, ui: &mut egui::Ui) {
+    ui.heading(tr("Multi-File Load"));
     ui.separator();
     
     ui.horizontal(|ui| {
-        ui.label("Name:");
-        ui.text_edit_singleline(&mut app.invert_name);
+        ui.label(tr("Name:"));
+        ui.text_edit_singleline(&mut app.multifile_name);
     });
     
     ui.add_space(8.0);
+    ui.label(tr("Files are appended in this order:"));
     
-    ui.label("This operation will invert all bits:");
-    ui.label("• 0 → 1");
-    ui.label("• 1 → 0");
+    let mut move_up = None;
+    let mut remove = None;
+    egui::ScrollArea::vertical()
+        .id_salt("multifile_list")
+        .max_height(200.0)
+        .show(ui, |ui| {
+            for (index, path) in app.multifile_paths.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(index > 0, egui::Button::new("⬆").small()).clicked() {
+                        move_up = Some(index);
+                    }
+                    if ui.small_button("🗑").clicked() {
+                        remove = Some(index);
+                    }
+                    ui.label(format!("{}. {}", index + 1, path.file_name().unwrap_or_default().to_string_lossy()))
+                        .on_hover_text(path.display().to_string());
+                });
+            }
+        });
+    if let Some(index) = move_up {
+        app.multifile_paths.swap(index - 1, index);
+    }
+    if let Some(index) = remove {
+        app.multifile_paths.remove(index);
+    }
+    if app.multifile_paths.is_empty() {
+        ui.label(tr("No files added"));
+    }
+    
+    ui.horizontal(|ui| {
+        if ui.button(tr("📂 Add Files...")).clicked()
+            && let Some(mut paths) = rfd::FileDialog::new().pick_files()
+        {
+            paths.sort_by(|a, b| natural_cmp(&a.to_string_lossy(), &b.to_string_lossy()));
+            app.multifile_paths.extend(paths);
+        }
+        if ui.add_enabled(app.multifile_paths.len() > 1, egui::Button::new(tr("🔢 Sort by Name"))).clicked() {
+            app.multifile_paths.sort_by(|a, b| natural_cmp(&a.to_string_lossy(), &b.to_string_lossy()));
+        }
+        if ui.add_enabled(!app.multifile_paths.is_empty(), egui::Button::new(tr("Clear"))).clicked() {
+            app.multifile_paths.clear();
+        }
+    });
+    
+    ui.horizontal(|ui| {
+        ui.label(tr("Glob:"));
+        ui.add(egui::TextEdit::singleline(&mut app.multifile_glob).hint_text(tr("/captures/capture_*.bin")));
+        if ui.button(tr("➕ Add Matching")).on_hover_text(tr("Add the files matching * and ? in the file name, numbered chunks in numeric order")).clicked() {
+            app.add_multifile_glob();
+        }
+    });
     
     ui.add_space(8.0);
     
     ui.horizontal(|ui| {
-        if ui.button("✓ Save").clicked() {
+        if ui.button(tr("✓ Save")).clicked() {
             app.save_current_operation();
         }
         
-        if ui.button("✗ Cancel").clicked() {
+        if ui.button(tr("✗ Cancel")).clicked() {
             app.cancel_operation_edit();
         }
     });
 }
 
-fn render_truncate_editor(app: &mut BitApp, ui: &mut egui::Ui) {
-    ui.heading("Truncate Bits");
+fn render_takeskip_editor(app: &mut BitApp, ui: &mut egui::Ui) {
+    ui.heading(tr("Take/Skip Sequence"));
     ui.separator();
     
     ui.horizontal(|ui| {
-        ui.label("Name:");
-        ui.text_edit_singleline(&mut app.truncate_name);
+        ui.label(tr("Name:"));
+        ui.text_edit_singleline(&mut app.takeskip_name);
     });
     
     ui.add_space(8.0);
     
-    ui.label("Specify the range of bits to keep:");
-    ui.add_space(4.0);
+    ui.label(tr("Enter a sequence of operations:"));
+    ui.label(tr("• t: take N bits"));
+    ui.label(tr("• r: reverse N bits"));
+    ui.label(tr("• i: invert N bits"));
+    ui.label(tr("• s: skip N bits"));
+    
+    ui.add_space(8.0);
     
     ui.horizontal(|ui| {
-        ui.label("Start (inclusive):");
-        let start_response = ui.text_edit_singleline(&mut app.truncate_start);
+        ui.label(tr("Sequence:"));
+        let response = ui.text_edit_singleline(&mut app.takeskip_input);
         
-        // Evaluate math expression on Enter key
-        if start_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-            if let Ok(result) = eval_expression(&app.truncate_start) {
-                app.truncate_start = result.to_string();
-            }
+        if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+            app.save_current_operation();
         }
     });
     
+    ui.label(tr("Example: t4r3i8s1"));
+    
+    ui.add_space(4.0);
+    
+    let mut chosen = None;
+    let mut delete = None;
+    let mut save_preset = false;
     ui.horizontal(|ui| {
-        ui.label("End (exclusive):  ");
-        let end_response = ui.text_edit_singleline(&mut app.truncate_end);
-        
-        // Evaluate math expression on Enter key
-        if end_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-            if !app.truncate_end.is_empty() {
-                if let Ok(result) = eval_expression(&app.truncate_end) {
-                    app.truncate_end = result.to_string();
+        ui.menu_button(tr("📚 Presets"), |ui| {
+            for preset in builtin_takeskip_presets() {
+                if ui.button(format!("{}  ({})", preset.name, preset.sequence)).on_hover_text(&preset.description).clicked() {
+                    chosen = Some(preset);
+                    ui.close();
+                }
+            }
+            if !app.settings.takeskip_presets.is_empty() {
+                ui.separator();
+                ui.label(tr("My presets:"));
+                for (index, preset) in app.settings.takeskip_presets.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        if ui.button(format!("{}  ({})", preset.name, preset.sequence)).clicked() {
+                            chosen = Some(preset.clone());
+                            ui.close();
+                        }
+                        if ui.small_button("🗑").on_hover_text(tr("Delete preset")).clicked() {
+                            delete = Some(index);
+                        }
+                    });
                 }
             }
+        });
+        if ui.add_enabled(!app.takeskip_input.trim().is_empty(), egui::Button::new(tr("💾 Save as Preset")))
+            .on_hover_text(tr("Keep this sequence under its name in the Presets menu"))
+            .clicked()
+        {
+            save_preset = true;
         }
     });
+    if let Some(preset) = chosen {
+        app.use_takeskip_preset(&preset);
+    }
+    if let Some(index) = delete {
+        app.delete_takeskip_preset(index);
+    }
+    if save_preset {
+        app.save_takeskip_preset();
+    }
     
-    ui.add_space(4.0);
-    ui.label("💡 Tips:");
-    ui.label("• Leave end empty to keep until the end");
-    ui.label("• You can use math: 8*8, 100+50, 200-10, 64/2");
-    ui.label("• Example: Start=0, End=250 keeps bits 0-249");
-    ui.label("• Example: Start=100, End=250 keeps bits 100-249");
-    ui.label("• Example: Start=0, End=empty keeps all bits from 0");
+    ui.add_space(8.0);
+    
+    render_operation_preview(app, ui);
     
     ui.add_space(8.0);
     
     ui.horizontal(|ui| {
-        if ui.button("✓ Save").clicked() {
+        if ui.button(tr("✓ Save")).clicked() {
             app.save_current_operation();
         }
         
-        if ui.button("✗ Cancel").clicked() {
+        if ui.button(tr("✗ Cancel")).clicked() {
             app.cancel_operation_edit();
         }
     });
 }
 
-fn render_multiworksheet_editor(app: &mut BitApp, ui: &mut egui::Ui) {
-    ui.heading("Multi-Worksheet Load");
+/// Bits shown per row and in total in an editor's live preview
+const PREVIEW_ROW_BITS: usize = 64;
+const PREVIEW_BITS: usize = 256;
+
+/// Before/after rows for the operation being edited, updated as its fields change
+fn render_operation_preview(app: &BitApp, ui: &mut egui::Ui) {
+    ui.group(|ui| {
+        ui.label(tr("Preview (start of data):"));
+        
+        if app.original_view().is_empty() {
+            ui.weak(tr("Open a file to preview"));
+            return;
+        }
+        
+        match app.operation_preview() {
+            Ok(OperationPreview { before, after }) => {
+                let colors = app.viewer.colors.clone().unwrap_or_else(|| BitColors::for_dark_mode(ui.visuals().dark_mode));
+                ui.small(format!("Before ({} bits shown)", before.len().min(PREVIEW_BITS)));
+                paint_preview_bits(ui, &before, &colors);
+                ui.small(format!("After ({} bits shown)", after.len().min(PREVIEW_BITS)));
+                paint_preview_bits(ui, &after, &colors);
+            }
+            Err(e) => {
+                ui.weak(e);
+            }
+        }
+    });
+}
+
+fn paint_preview_bits(ui: &mut egui::Ui, bits: &BitSlice<u8, Msb0>, colors: &BitColors) {
+    let cell = 5.0;
+    let shown = bits.len().min(PREVIEW_BITS);
+    let rows = shown.div_ceil(PREVIEW_ROW_BITS).max(1);
+    let (rect, _) = ui.allocate_exact_size(
+        egui::vec2(PREVIEW_ROW_BITS as f32 * cell, rows as f32 * cell),
+        egui::Sense::hover()
+    );
+    
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 0.0, rgb(colors.background));
+    for (i, bit) in bits[..shown].iter().enumerate() {
+        let pos = rect.min + egui::vec2((i % PREVIEW_ROW_BITS) as f32 * cell, (i / PREVIEW_ROW_BITS) as f32 * cell);
+        let color = if *bit { rgb(colors.one) } else { rgb(colors.zero) };
+        painter.rect_filled(egui::Rect::from_min_size(pos, egui::vec2(cell - 1.0, cell - 1.0)), 0.0, color);
+    }
+}
+
+fn render_insert_editor(app: &mut BitApp, ui: &mut egui::Ui) {
+    ui.heading(tr("Insert Bits"));
     ui.separator();
     
     ui.horizontal(|ui| {
-        ui.label("Name:");
-        ui.text_edit_singleline(&mut app.multiworksheet_name);
+        ui.label(tr("Name:"));
+        ui.text_edit_singleline(&mut app.insert_name);
     });
     
     ui.add_space(8.0);
     
-    ui.group(|ui| {
-        ui.label("Add Worksheet Operation:");
-        
-        ui.horizontal(|ui| {
-            ui.label("Worksheet:");
-            egui::ComboBox::from_id_salt("worksheet_selector")
-                .selected_text(if app.multiworksheet_selected_worksheet < app.worksheets.len() {
-                    &app.worksheets[app.multiworksheet_selected_worksheet].name
-                } else {
-                    "Select..."
-                })
-                .show_ui(ui, |ui| {
-                    for (idx, worksheet) in app.worksheets.iter().enumerate() {
-                        if idx != app.current_worksheet_index {
-                            ui.selectable_value(&mut app.multiworksheet_selected_worksheet, idx, &worksheet.name);
-                        }
-                    }
-                });
-        });
-        
-        ui.horizontal(|ui| {
-            ui.label("Sequence:");
-            ui.text_edit_singleline(&mut app.multiworksheet_input);
-        });
-        
-        if ui.button("➕ Add").clicked() {
-            if !app.multiworksheet_input.is_empty() {
-                app.multiworksheet_ops.push((
-                    app.multiworksheet_selected_worksheet,
-                    app.multiworksheet_input.clone()
-                ));
-                app.multiworksheet_input.clear();
-            }
-        }
+    ui.horizontal(|ui| {
+        ui.label(tr("Position:"));
+        ui.text_edit_singleline(&mut app.insert_position);
+    });
+    
+    ui.horizontal(|ui| {
+        ui.label(tr("Count:"));
+        ui.text_edit_singleline(&mut app.insert_count);
+    });
+    
+    ui.horizontal(|ui| {
+        ui.label(tr("Fill:"));
+        ui.text_edit_singleline(&mut app.insert_fill);
     });
     
+    ui.add_space(4.0);
+    ui.label(tr("💡 Tips:"));
+    ui.label(tr("• Bits are inserted before the bit at Position"));
+    ui.label(tr("• Fill is a 0/1 pattern repeated to Count bits, e.g. 0, 1 or 0110"));
+    ui.label(tr("• Position and Count accept math: 64*8+3"));
+    ui.label(tr("• len, frame_len and frames follow the input: len-64, frames/2*frame_len"));
+    
     ui.add_space(8.0);
     
-    if app.multiworksheet_ops.is_empty() {
-        ui.label("No worksheets added yet");
-    } else {
-        let mut to_remove = None;
-        for (idx, (ws_idx, seq)) in app.multiworksheet_ops.iter().enumerate() {
-            ui.horizontal(|ui| {
-                let ws_name = if *ws_idx < app.worksheets.len() {
-                    &app.worksheets[*ws_idx].name
-                } else {
-                    "Unknown"
-                };
-                ui.label(format!("{}. {} → {}", idx + 1, ws_name, seq));
-                if ui.button("❌").clicked() {
-                    to_remove = Some(idx);
-                }
-            });
-        }
-        
-        if let Some(idx) = to_remove {
-            app.multiworksheet_ops.remove(idx);
-        }
-    }
+    render_operation_preview(app, ui);
     
     ui.add_space(8.0);
     
     ui.horizontal(|ui| {
-        if ui.button("✓ Save").clicked() {
+        if ui.button(tr("✓ Save")).clicked() {
             app.save_current_operation();
         }
         
-        if ui.button("✗ Cancel").clicked() {
+        if ui.button(tr("✗ Cancel")).clicked() {
             app.cancel_operation_edit();
         }
     });
 }
 
-pub fn render_column_editor_window(app: &mut BitApp, ctx: &egui::Context) {
-    if app.show_column_editor {
-        let mut open = true;
-        egui::Window::new("➕ Add Protocol Column")
-            .open(&mut open)
-            .resizable(false)
-            .show(ctx, |ui| {
-                ui.heading("Define Protocol Column");
-                ui.separator();
-                
-                ui.horizontal(|ui| {
-                    ui.label("Label:");
-                    ui.text_edit_singleline(&mut app.column_editor_label);
-                });
-                
-                ui.add_space(8.0);
-                
-                ui.horizontal(|ui| {
-                    ui.label("Start bit:");
-                    ui.text_edit_singleline(&mut app.column_editor_bit_start);
-                });
-                
-                ui.horizontal(|ui| {
-                    ui.label("End bit:");
-                    ui.text_edit_singleline(&mut app.column_editor_bit_end);
-                });
-                
-                ui.add_space(8.0);
-                
-                ui.label("Color:");
-                ui.horizontal(|ui| {
-                    ui.label("R:");
-                    ui.add(egui::Slider::new(&mut app.column_editor_color[0], 0..=255));
-                });
-                ui.horizontal(|ui| {
-                    ui.label("G:");
-                    ui.add(egui::Slider::new(&mut app.column_editor_color[1], 0..=255));
-                });
-                ui.horizontal(|ui| {
-                    ui.label("B:");
-                    ui.add(egui::Slider::new(&mut app.column_editor_color[2], 0..=255));
-                });
-                
-                let color = egui::Color32::from_rgb(
-                    app.column_editor_color[0],
-                    app.column_editor_color[1],
-                    app.column_editor_color[2]
-                );
-                ui.horizontal(|ui| {
-                    ui.label("Preview:");
-                    let (rect, _) = ui.allocate_exact_size(
-                        egui::vec2(100.0, 30.0),
-                        egui::Sense::hover()
-                    );
-                    ui.painter().rect_filled(rect, 3.0, color);
-                });
-                
-                ui.add_space(8.0);
-                
-                ui.horizontal(|ui| {
-                    if ui.button("✓ Add Column").clicked() {
-                        if let (Ok(start), Ok(end)) = (
-                            app.column_editor_bit_start.parse::<usize>(),
-                            app.column_editor_bit_end.parse::<usize>()
-                        ) {
-                            if start <= end {
-                                let label = if app.column_editor_label.is_empty() {
-                                    format!("Column {}", app.byte_viewer.config.columns.len() + 1)
-                                } else {
-                                    app.column_editor_label.clone()
-                                };
-                                
-                                app.byte_viewer.add_column(
-                                    crate::viewers::ByteColumn::new(
-                                        label,
-                                        start,
-                                        end,
-                                        app.column_editor_color
-                                    )
-                                );
-                                
-                                app.column_editor_label.clear();
-                                app.column_editor_bit_start = format!("{}", end + 1);
-                                app.column_editor_bit_end = format!("{}", end + 8);
-                                app.show_column_editor = false;
-                            } else {
-                                app.error_message = Some("Start bit must be <= end bit".to_string());
-                            }
-                        } else {
-                            app.error_message = Some("Invalid bit range values".to_string());
-                        }
-                    }
-                    
-                    if ui.button("✗ Cancel").clicked() {
-                        app.show_column_editor = false;
-                    }
-                });
-            });
-        
-        if !open {
-            app.show_column_editor = false;
-        }
-    }
-}
-
-fn render_interleave_editor(app: &mut BitApp, ui: &mut egui::Ui) {
-    ui.heading("Bit Interleaving / De-interleaving");
+fn render_range_editor(app: &mut BitApp, ui: &mut egui::Ui) {
+    ui.heading(tr("Invert/Reverse Range"));
     ui.separator();
     
     ui.horizontal(|ui| {
-        ui.label("Name:");
-        ui.text_edit_singleline(&mut app.interleave_name);
+        ui.label(tr("Name:"));
+        ui.text_edit_singleline(&mut app.range_name);
     });
     
     ui.add_space(8.0);
     
-    // Type selection
     ui.horizontal(|ui| {
-        ui.label("Interleaver Type:");
-        ui.radio_value(&mut app.interleave_type, crate::processing::InterleaverType::Block, "Block");
-        ui.radio_value(&mut app.interleave_type, crate::processing::InterleaverType::Convolutional, "Convolutional");
-        ui.radio_value(&mut app.interleave_type, crate::processing::InterleaverType::Symbol, "Symbol");
+        ui.radio_value(&mut app.range_action, RangeAction::Invert, tr("Invert"));
+        ui.radio_value(&mut app.range_action, RangeAction::Reverse, tr("Reverse"));
     });
     
     ui.add_space(4.0);
     
-    // Direction selection
     ui.horizontal(|ui| {
-        ui.label("Direction:");
-        ui.radio_value(&mut app.interleave_direction, crate::processing::InterleaverDirection::Interleave, "Interleave");
-        ui.radio_value(&mut app.interleave_direction, crate::processing::InterleaverDirection::Deinterleave, "De-interleave");
+        ui.label(tr("Start:"));
+        ui.text_edit_singleline(&mut app.range_start);
     });
     
+    ui.horizontal(|ui| {
+        ui.label(tr("End:"));
+        ui.text_edit_singleline(&mut app.range_end);
+    });
+    
+    ui.add_space(4.0);
+    ui.label(tr("💡 Tips:"));
+    ui.label(tr("• Bits from Start up to (not including) End are changed; positions don't move"));
+    ui.label(tr("• Leave End empty to go to the end of the data"));
+    ui.label(tr("• Start and End accept math: 64*8+3"));
+    ui.label(tr("• len, frame_len and frames follow the input: len-frame_len, frames/2*frame_len"));
+    
     ui.add_space(8.0);
-    ui.separator();
     
-    // Parameters based on type
-    match app.interleave_type {
-        crate::processing::InterleaverType::Block => {
-            ui.label("📦 Block Interleaver Parameters:");
-            ui.add_space(4.0);
-            
-            ui.horizontal(|ui| {
-                ui.label("Block Size (columns):");
-                let block_response = ui.text_edit_singleline(&mut app.interleave_block_size);
-                
-                if block_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-                    if let Ok(result) = eval_expression(&app.interleave_block_size) {
-                        app.interleave_block_size = result.to_string();
-                    }
-                }
-            });
-            
-            ui.horizontal(|ui| {
-                ui.label("Depth (rows):      ");
-                let depth_response = ui.text_edit_singleline(&mut app.interleave_depth);
-                
-                if depth_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-                    if let Ok(result) = eval_expression(&app.interleave_depth) {
-                        app.interleave_depth = result.to_string();
-                    }
-                }
-            });
-            
-            ui.add_space(8.0);
-            
-            // Visual preview
-            ui.group(|ui| {
-                ui.label("📊 Visual Preview:");
-                ui.add_space(4.0);
-                
-                if let (Ok(block_size), Ok(depth)) = (
-                    app.interleave_block_size.parse::<usize>(),
-                    app.interleave_depth.parse::<usize>()
-                ) {
-                    if block_size > 0 && depth > 0 && block_size <= 16 && depth <= 16 {
-                        ui.label(format!("Matrix: {}×{} = {} bits per block", block_size, depth, block_size * depth));
-                        ui.add_space(4.0);
-                        
-                        match app.interleave_direction {
-                            crate::processing::InterleaverDirection::Interleave => {
-                                ui.label("Write row-wise → Read column-wise:");
-                                ui.add_space(2.0);
-                                render_block_matrix_preview(ui, block_size, depth);
-                            }
-                            crate::processing::InterleaverDirection::Deinterleave => {
-                                ui.label("Write column-wise → Read row-wise:");
-                                ui.add_space(2.0);
-                                render_block_matrix_preview(ui, block_size, depth);
-                            }
-                        }
-                    } else {
-                        ui.label("⚠ Invalid dimensions (max 16×16)");
-                    }
-                } else {
-                    ui.label("⚠ Enter valid numbers for preview");
-                }
-            });
-            
-            ui.add_space(4.0);
-            ui.label("💡 Tips:");
-            ui.label("• Block interleaver rearranges data in matrix blocks");
-            ui.label("• Interleave: Write row-wise, read column-wise");
-            ui.label("• De-interleave: Reverses the process");
-            ui.label("• Example: 8×4 matrix handles 32 bits at a time");
-            ui.label("• Math supported: 2*4, 16/2, 8+4, etc.");
-        }
-        
-        crate::processing::InterleaverType::Convolutional => {
-            ui.label("🔄 Convolutional Interleaver Parameters:");
-            ui.add_space(4.0);
-            
-            ui.horizontal(|ui| {
-                ui.label("Branches (B):       ");
-                let branches_response = ui.text_edit_singleline(&mut app.interleave_branches);
-                
-                if branches_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-                    if let Ok(result) = eval_expression(&app.interleave_branches) {
-                        app.interleave_branches = result.to_string();
-                    }
-                }
-            });
-            
-            ui.horizontal(|ui| {
-                ui.label("Delay Increment (M):");
-                let delay_response = ui.text_edit_singleline(&mut app.interleave_delay_increment);
-                
-                if delay_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-                    if let Ok(result) = eval_expression(&app.interleave_delay_increment) {
-                        app.interleave_delay_increment = result.to_string();
-                    }
-                }
-            });
-            
-            ui.add_space(8.0);
-            
-            // Visual preview
-            ui.group(|ui| {
-                ui.label("📊 Visual Preview:");
-                ui.add_space(4.0);
-                
-                if let (Ok(branches), Ok(delay_inc)) = (
-                    app.interleave_branches.parse::<usize>(),
-                    app.interleave_delay_increment.parse::<usize>()
-                ) {
-                    if branches > 0 && branches <= 16 && delay_inc > 0 && delay_inc <= 16 {
-                        ui.label(format!("Branches: {}, Delay Increment: {}", branches, delay_inc));
-                        ui.add_space(4.0);
-                        
-                        match app.interleave_direction {
-                            crate::processing::InterleaverDirection::Interleave => {
-                                ui.label("Round-robin distribution with delay:");
-                                ui.add_space(2.0);
-                                render_convolutional_preview(ui, branches, delay_inc);
-                            }
-                            crate::processing::InterleaverDirection::Deinterleave => {
-                                ui.label("Reverse round-robin with delay:");
-                                ui.add_space(2.0);
-                                render_convolutional_preview(ui, branches, delay_inc);
-                            }
-                        }
-                    } else {
-                        ui.label("⚠ Invalid parameters (max B=16, M=16)");
-                    }
-                } else {
-                    ui.label("⚠ Enter valid numbers for preview");
-                }
-            });
-            
-            ui.add_space(4.0);
-            ui.label("💡 Tips:");
-            ui.label("• Convolutional uses delay lines for each branch");
-            ui.label("• Branch i has delay = i × M symbols");
-            ui.label("• Distributes bits round-robin across branches");
-            ui.label("• Example: B=4, M=1 → delays [0,1,2,3]");
-            ui.label("• Provides time-diversity for burst errors");
+    render_operation_preview(app, ui);
+    
+    ui.add_space(8.0);
+    
+    ui.horizontal(|ui| {
+        if ui.button(tr("✓ Save")).clicked() {
+            app.save_current_operation();
         }
         
-        crate::processing::InterleaverType::Symbol => {
-            ui.label("🔤 Symbol Interleaver Parameters:");
-            ui.add_space(4.0);
-            
-            ui.horizontal(|ui| {
-                ui.label("Symbol Size (bits):");
-                let symbol_response = ui.text_edit_singleline(&mut app.interleave_symbol_size);
-                
-                if symbol_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-                    if let Ok(result) = eval_expression(&app.interleave_symbol_size) {
-                        app.interleave_symbol_size = result.to_string();
-                    }
-                }
-            });
-            
-            ui.horizontal(|ui| {
-                ui.label("Block Size (columns):");
-                let block_response = ui.text_edit_singleline(&mut app.interleave_block_size);
-                
-                if block_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-                    if let Ok(result) = eval_expression(&app.interleave_block_size) {
-                        app.interleave_block_size = result.to_string();
-                    }
-                }
-            });
-            
-            ui.horizontal(|ui| {
-                ui.label("Depth (rows):       ");
-                let depth_response = ui.text_edit_singleline(&mut app.interleave_depth);
-                
-                if depth_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-                    if let Ok(result) = eval_expression(&app.interleave_depth) {
-                        app.interleave_depth = result.to_string();
-                    }
-                }
-            });
-            
-            ui.add_space(8.0);
-            
-            ui.label("💡 Tips:");
-            ui.label("• Symbol interleaver treats multi-bit symbols as atomic units");
-            ui.label("• Common symbol sizes: 8 (bytes), 4 (nibbles), 16 (words)");
-            ui.label("• Use for AABBCCDD → ABCDABCD transformations");
-            ui.label("• Matrix: Write symbols row-wise, read column-wise");
-            ui.label("• Example: Symbol=8, Block=2, Depth=4 → AABBCCDD → ABCDABCD");
+        if ui.button(tr("✗ Cancel")).clicked() {
+            app.cancel_operation_edit();
         }
-    }
+    });
+}
+
+fn render_xor_editor(app: &mut BitApp, ui: &mut egui::Ui) {
+    ui.heading(tr("XOR With Key"));
+    ui.separator();
+    
+    ui.horizontal(|ui| {
+        ui.label(tr("Name:"));
+        ui.text_edit_singleline(&mut app.xor_name);
+    });
+    
+    ui.add_space(8.0);
+    
+    ui.horizontal(|ui| {
+        ui.label(tr("Key (hex):"));
+        ui.text_edit_singleline(&mut app.xor_key);
+    });
+    
+    ui.add_space(4.0);
+    ui.label(tr("💡 Tips:"));
+    ui.label(tr("• The key repeats over the data, byte by byte from the first bit"));
+    ui.label(tr("• Hex bytes with optional 0x and spaces: 0x4B 45 59"));
+    ui.label(tr("• Use 🔑 XOR Key to guess the key of XORed text"));
+    
+    ui.add_space(8.0);
+    
+    render_operation_preview(app, ui);
     
     ui.add_space(8.0);
     
     ui.horizontal(|ui| {
-        if ui.button("✓ Save").clicked() {
+        if ui.button(tr("✓ Save")).clicked() {
             app.save_current_operation();
         }
         
-        if ui.button("✗ Cancel").clicked() {
+        if ui.button(tr("✗ Cancel")).clicked() {
             app.cancel_operation_edit();
         }
     });
 }
 
-fn render_block_matrix_preview(ui: &mut egui::Ui, cols: usize, rows: usize) {
-    use egui::{Color32, Rect, Stroke};
+fn render_line_code_editor(app: &mut BitApp, ui: &mut egui::Ui) {
+    ui.heading(tr("Line Code Decode"));
+    ui.separator();
     
-    let cell_size = 24.0;
-    let spacing = 2.0;
-    let total_width = cols as f32 * (cell_size + spacing);
-    let total_height = rows as f32 * (cell_size + spacing);
+    ui.horizontal(|ui| {
+        ui.label(tr("Name:"));
+        ui.text_edit_singleline(&mut app.line_code_name);
+    });
     
-    let (response, painter) = ui.allocate_painter(
-        egui::vec2(total_width, total_height),
-        egui::Sense::hover()
-    );
+    ui.add_space(8.0);
     
-    let base_pos = response.rect.min;
+    ui.horizontal(|ui| {
+        ui.label(tr("Code:"));
+        egui::ComboBox::from_id_salt("line_code")
+            .selected_text(app.line_code.name())
+            .show_ui(ui, |ui| {
+                for code in LineCode::ALL {
+                    ui.selectable_value(&mut app.line_code, code, code.name());
+                }
+            });
+    });
     
-    for row in 0..rows.min(8) {
-        for col in 0..cols.min(8) {
-            let x = base_pos.x + col as f32 * (cell_size + spacing);
-            let y = base_pos.y + row as f32 * (cell_size + spacing);
-            
-            let rect = Rect::from_min_size(
-                egui::pos2(x, y),
-                egui::vec2(cell_size, cell_size)
-            );
-            
-            let bit_index = row * cols + col;
-            let color = Color32::from_rgb(
-                (100 + bit_index * 15).min(255) as u8,
-                (150 - bit_index * 5).max(50) as u8,
-                200
-            );
-            
-            painter.rect_filled(rect, 2.0, color);
-            painter.rect_stroke(
-                rect,
-                2.0,
-                Stroke::new(1.0, Color32::from_gray(80)),
-                egui::epaint::StrokeKind::Outside
-            );
-            
-            let text = format!("{}", bit_index);
-            painter.text(
-                rect.center(),
-                egui::Align2::CENTER_CENTER,
-                text,
-                egui::FontId::monospace(10.0),
-                Color32::WHITE
-            );
+    ui.add_space(4.0);
+    ui.label(tr("💡 Tips:"));
+    match app.line_code {
+        LineCode::EightBTenB => {
+            ui.label(tr("• Symbols are read 10 bits at a time from the first bit; trim to the first symbol with Truncate"));
+            ui.label(tr("• Control symbols such as K28.5 decode to their byte value (0xBC)"));
+            ui.label(tr("• Invalid code groups decode to 0x00"));
+        }
+        LineCode::SixtyFourBSixtySixB => {
+            ui.label(tr("• Blocks are found at the offset with the most valid 01/10 sync headers"));
+            ui.label(tr("• The 2-bit headers are dropped and the payload is descrambled"));
+            ui.label(tr("• The first 58 bits come out wrong until the descrambler has synchronised"));
         }
     }
+    ui.label(tr("• When this is the last operation, code violations are marked red in the bit view"));
     
-    if rows > 8 || cols > 8 {
-        ui.label("  (showing first 8×8)");
-    }
+    ui.add_space(8.0);
+    
+    render_operation_preview(app, ui);
+    
+    ui.add_space(8.0);
+    
+    ui.horizontal(|ui| {
+        if ui.button(tr("✓ Save")).clicked() {
+            app.save_current_operation();
+        }
+        
+        if ui.button(tr("✗ Cancel")).clicked() {
+            app.cancel_operation_edit();
+        }
+    });
 }
 
-fn render_convolutional_preview(ui: &mut egui::Ui, branches: usize, delay_inc: usize) {
-    use egui::{Color32, Rect, Stroke};
+fn render_symbol_code_editor(app: &mut BitApp, ui: &mut egui::Ui) {
+    ui.heading(tr("Gray/BCD Convert"));
+    ui.separator();
     
-    let branch_width = 80.0;
-    let branch_height = 30.0;
-    let spacing = 10.0;
+    ui.horizontal(|ui| {
+        ui.label(tr("Name:"));
+        ui.text_edit_singleline(&mut app.symbol_code_name);
+    });
     
-    let total_width = branch_width + 150.0;
-    let total_height = branches.min(8) as f32 * (branch_height + spacing);
+    ui.add_space(8.0);
     
-    let (response, painter) = ui.allocate_painter(
-        egui::vec2(total_width, total_height),
-        egui::Sense::hover()
-    );
+    ui.horizontal(|ui| {
+        ui.label(tr("Conversion:"));
+        egui::ComboBox::from_id_salt("symbol_code")
+            .selected_text(app.symbol_code.name())
+            .show_ui(ui, |ui| {
+                for code in SymbolCode::ALL {
+                    ui.selectable_value(&mut app.symbol_code, code, code.name());
+                }
+            });
+    });
     
-    let base_pos = response.rect.min;
+    ui.horizontal(|ui| {
+        ui.label(tr("Symbol width (bits):"));
+        ui.text_edit_singleline(&mut app.symbol_code_bits);
+    });
     
-    for i in 0..branches.min(8) {
-        let y = base_pos.y + i as f32 * (branch_height + spacing);
-        let delay = i * delay_inc;
-        
-        // Draw branch box
-        let rect = Rect::from_min_size(
-            egui::pos2(base_pos.x, y),
-            egui::vec2(branch_width, branch_height)
-        );
-        
-        let color = Color32::from_rgb(
-            (100 + i * 20).min(255) as u8,
-            (150 - i * 10).max(50) as u8,
-            200
-        );
-        
-        painter.rect_filled(rect, 3.0, color);
-        painter.rect_stroke(
-            rect,
-            3.0,
-            Stroke::new(1.5, Color32::from_gray(80)),
-            egui::epaint::StrokeKind::Outside
-        );
+    ui.add_space(4.0);
+    ui.label(tr("💡 Tips:"));
+    ui.label(tr("• Symbols are read from the first bit, most significant bit first, and keep their width"));
+    ui.label(tr("• A trailing partial symbol is left unchanged; trim to the first symbol with Truncate"));
+    if app.symbol_code == SymbolCode::BcdToBinary {
+        ui.label(tr("• Each 4 bits are one decimal digit, so the width must be a multiple of 4"));
+        ui.label(tr("• Symbols with a digit above 9 are left unchanged"));
+    } else {
+        ui.label(tr("• Rotary encoders usually give 8 to 16-bit Gray code positions"));
+    }
+    
+    ui.add_space(8.0);
+    
+    render_operation_preview(app, ui);
+    
+    ui.add_space(8.0);
+    
+    ui.horizontal(|ui| {
+        if ui.button(tr("✓ Save")).clicked() {
+            app.save_current_operation();
+        }
         
-        let text = format!("Branch {}", i);
-        painter.text(
-            rect.center(),
-            egui::Align2::CENTER_CENTER,
-            text,
-            egui::FontId::proportional(11.0),
-            Color32::WHITE
-        );
+        if ui.button(tr("✗ Cancel")).clicked() {
+            app.cancel_operation_edit();
+        }
+    });
+}
+
+fn render_remap_editor(app: &mut BitApp, ui: &mut egui::Ui) {
+    ui.heading(tr("Symbol Remap"));
+    ui.separator();
+    
+    ui.horizontal(|ui| {
+        ui.label(tr("Name:"));
+        ui.text_edit_singleline(&mut app.remap_name);
+    });
+    
+    ui.add_space(8.0);
+    
+    ui.horizontal(|ui| {
+        ui.label(tr("Input bits:"));
+        ui.add(egui::TextEdit::singleline(&mut app.remap_input_bits).desired_width(50.0));
+        ui.label(tr("Output bits:"));
+        ui.add(egui::TextEdit::singleline(&mut app.remap_output_bits).desired_width(50.0));
+    });
+    
+    ui.horizontal(|ui| {
+        ui.label(tr("Table (input,output per line):"));
+        if ui.button(tr("📂 Load CSV...")).clicked()
+            && let Some(path) = rfd::FileDialog::new().add_filter("CSV", &["csv", "txt"]).pick_file()
+        {
+            match std::fs::read_to_string(&path) {
+                Ok(text) => app.remap_table = text,
+                Err(e) => app.notifications.error(format!("Failed to read {}: {}", path.display(), e)),
+            }
+        }
+        if ui.button(tr("Identity"))
+            .on_hover_text(tr("Fill the table with every input value mapped to itself, to edit from there"))
+            .clicked()
+            && let Ok(bits) = eval_expression(&app.remap_input_bits)
+            && (1..=12).contains(&bits)
+        {
+            app.remap_table = (0..1u64 << bits).map(|value| format!("0x{:X},0x{:X}\n", value, value)).collect();
+        }
+    });
+    egui::ScrollArea::vertical()
+        .id_salt("remap_table")
+        .max_height(160.0)
+        .show(ui, |ui| {
+            ui.add(egui::TextEdit::multiline(&mut app.remap_table)
+                .font(egui::TextStyle::Monospace)
+                .hint_text("0x0,0x3\n0x1,0x2")
+                .desired_rows(8));
+        });
+    
+    ui.add_space(4.0);
+    ui.label(tr("💡 Tips:"));
+    ui.label(tr("• Values are decimal, 0x hex or 0b binary; a header line and # comments are skipped"));
+    ui.label(tr("• Values not in the table pass through, cut to the output width"));
+    ui.label(tr("• A trailing partial symbol is dropped"));
+    
+    ui.add_space(8.0);
+    
+    render_operation_preview(app, ui);
+    
+    ui.add_space(8.0);
+    
+    ui.horizontal(|ui| {
+        if ui.button(tr("✓ Save")).clicked() {
+            app.save_current_operation();
+        }
         
-        // Draw delay indicator
-        let delay_text = format!("Delay: {} symbols", delay);
-        painter.text(
-            egui::pos2(base_pos.x + branch_width + 15.0, rect.center().y),
-            egui::Align2::LEFT_CENTER,
-            delay_text,
-            egui::FontId::monospace(10.0),
-            ui.style().visuals.text_color()
-        );
-    }
+        if ui.button(tr("✗ Cancel")).clicked() {
+            app.cancel_operation_edit();
+        }
+    });
+}
+
+fn render_majority_editor(app: &mut BitApp, ui: &mut egui::Ui) {
+    ui.heading(tr("Majority Vote"));
+    ui.separator();
     
-    if branches > 8 {
-        ui.label("  (showing first 8 branches)");
+    ui.horizontal(|ui| {
+        ui.label(tr("Name:"));
+        ui.text_edit_singleline(&mut app.majority_name);
+    });
+    
+    ui.add_space(4.0);
+    
+    render_vote_fields(app, ui);
+    
+    ui.add_space(4.0);
+    ui.label(tr("💡 Tips:"));
+    if app.majority_frames {
+        ui.label(tr("• Frames per block 1: each frame is sent Repeats times in a row (AAA BBB)"));
+        ui.label(tr("• Frames per block N: N frames are sent, then sent again (AB AB AB)"));
+    } else {
+        ui.label(tr("• Every Repeats bits are copies of one bit, e.g. 3 for 111 000 111"));
     }
+    ui.label(tr("• Ties only happen with an even number of repeats"));
+    ui.label(tr("• A trailing incomplete group is dropped"));
+    
+    ui.add_space(8.0);
+    
+    render_operation_preview(app, ui);
+    
+    ui.add_space(8.0);
+    
+    ui.horizontal(|ui| {
+        if ui.button(tr("✓ Save")).clicked() {
+            app.save_current_operation();
+        }
+        
+        if ui.button(tr("✗ Cancel")).clicked() {
+            app.cancel_operation_edit();
+        }
+    });
 }
 
-pub fn render_frame_width_finder_window(app: &mut BitApp, ctx: &egui::Context) {
-    use egui_plot::{Line, Plot, PlotPoints, Bar, BarChart, Points};
+fn render_nand_editor(app: &mut BitApp, ui: &mut egui::Ui) {
+    ui.heading(tr("NAND Page Layout"));
+    ui.separator();
     
-    if !app.show_frame_width_finder {
-        return;
+    ui.horizontal(|ui| {
+        ui.label(tr("Name:"));
+        ui.text_edit_singleline(&mut app.nand_name);
+    });
+    
+    ui.add_space(4.0);
+    
+    ui.horizontal(|ui| {
+        ui.label(tr("Page size:"));
+        ui.text_edit_singleline(&mut app.nand_page_size);
+    });
+    ui.horizontal(|ui| {
+        ui.label(tr("Spare size:"));
+        ui.text_edit_singleline(&mut app.nand_spare_size);
+    });
+    ui.horizontal(|ui| {
+        ui.radio_value(&mut app.nand_interleaved, false, tr("Spare after the data"));
+        ui.radio_value(&mut app.nand_interleaved, true, tr("Spare interleaved"));
+    });
+    if app.nand_interleaved {
+        ui.horizontal(|ui| {
+            ui.label(tr("Steps:"));
+            ui.text_edit_singleline(&mut app.nand_steps);
+        });
     }
+    ui.horizontal(|ui| {
+        ui.label(tr("Keep:"));
+        ui.radio_value(&mut app.nand_keep, NandArea::Data, tr("Data"));
+        ui.radio_value(&mut app.nand_keep, NandArea::Spare, tr("Spare (OOB)"));
+    });
     
-    // Clone values before entering the window to avoid borrow checker issues
-    let analysis = app.frame_width_analysis.clone();
-    let mut frame_width_min = app.frame_width_min;
-    let mut frame_width_max = app.frame_width_max;
-    let mut frame_width_delta = app.frame_width_delta;
-    let mut sort_by_score = app.frame_width_sort_by_score;
-    let mut selected_width = app.frame_width_selected;
-    let mut run_analysis = false;
-    let mut apply_width: Option<usize> = None;
-    let mut keep_open = true;
+    ui.add_space(4.0);
     
-    egui::Window::new("🔍 Find Frame Width")
-        .open(&mut keep_open)
-        .default_width(800.0)
-        .default_height(600.0)
-        .resizable(true)
-        .show(ctx, |ui| {
-            ui.heading("Automatic Frame Width Detection");
-            ui.label("Analyzes bit patterns to detect the most probable frame width");
-            ui.separator();
-            
-            // Controls
+    ui.horizontal(|ui| {
+        ui.label(tr("ECC:"));
+        if ui.radio(app.nand_ecc == NandEcc::None, tr("None")).clicked() {
+            app.nand_ecc = NandEcc::None;
+        }
+        if ui.radio(matches!(app.nand_ecc, NandEcc::Hamming { .. }), "Hamming").clicked() {
+            app.nand_ecc = NandEcc::Hamming { sector: 256 };
+        }
+        if ui.radio(matches!(app.nand_ecc, NandEcc::Bch { .. }), "BCH").clicked() {
+            app.nand_ecc = NandEcc::Bch { sector: 512, strength: 4 };
+        }
+    });
+    match &mut app.nand_ecc {
+        NandEcc::None => {}
+        NandEcc::Hamming { sector } => {
             ui.horizontal(|ui| {
-                ui.label("Min Width:");
-                ui.add(egui::DragValue::new(&mut frame_width_min)
-                    .range(1..=1024)
-                    .speed(1.0));
-                
-                ui.add_space(10.0);
-                
-                ui.label("Max Width:");
-                ui.add(egui::DragValue::new(&mut frame_width_max)
-                    .range(1..=1024)
-                    .speed(1.0));
-                
-                ui.add_space(10.0);
-                
-                ui.label("Delta:")
-                    .on_hover_text("⚠️ Delta measures REPETITION PERIOD, not frame width!\n\n\
-                        • Delta = 0: Find frame width by bit position consistency (recommended)\n\
-                        • Delta > 0: Find repetition period (e.g., delta=5 finds patterns every 5 frames)\n\
-                        • For ASCII/binary frame detection, keep Delta = 0");
-                ui.add(egui::DragValue::new(&mut frame_width_delta)
-                    .range(0..=100)
-                    .speed(1.0));
+                ui.label(tr("Sector:"));
+                ui.radio_value(sector, 256, format!("256 {}", tr("bytes")));
+                ui.radio_value(sector, 512, format!("512 {}", tr("bytes")));
             });
-            
-            ui.add_space(5.0);
-            
-            if ui.button("🔍 Analyze").clicked() {
-                run_analysis = true;
-            }
-            
-            ui.separator();
-            
-            // Show results if analysis has been run
+        }
+        NandEcc::Bch { sector, strength } => {
+            ui.horizontal(|ui| {
+                ui.label(tr("Sector:"));
+                ui.radio_value(sector, 512, format!("512 {}", tr("bytes")));
+                ui.radio_value(sector, 1024, format!("1024 {}", tr("bytes")));
+            });
+            ui.horizontal(|ui| {
+                ui.label(tr("Strength:"));
+                ui.add(egui::DragValue::new(strength).range(1..=64).suffix(" bits"));
+            });
+        }
+    }
+    if app.nand_ecc != NandEcc::None {
+        ui.horizontal(|ui| {
+            ui.label(tr("ECC offset:"));
+            ui.text_edit_singleline(&mut app.nand_ecc_offset);
+        });
+        ui.checkbox(&mut app.nand_correct, tr("Correct the data"));
+        
+        ui.horizontal(|ui| {
+            if ui.button(tr("🔍 Check ECC")).clicked() {
+                app.check_nand_ecc();
+            }
+            if let Some(report) = &app.nand_report {
+                ui.label(format!(
+                    "{} pages, {} sectors: {} erased, {} corrected ({} bits), {} uncorrectable",
+                    report.pages,
+                    report.sectors,
+                    report.erased,
+                    report.corrected.len(),
+                    report.corrected_bits(),
+                    report.uncorrectable.len()
+                ));
+            }
+        });
+        if let Some(report) = &app.nand_report
+            && !report.uncorrectable.is_empty()
+        {
+            let listed: Vec<String> = report.uncorrectable.iter().take(10).map(|(page, sector)| format!("{}.{}", page, sector)).collect();
+            let more = if report.uncorrectable.len() > 10 { ", ..." } else { "" };
+            ui.colored_label(egui::Color32::from_rgb(220, 80, 80), format!("Uncorrectable (page.sector): {}{}", listed.join(", "), more));
+        }
+    }
+    
+    ui.add_space(4.0);
+    ui.label(tr("💡 Tips:"));
+    ui.label(tr("• Sizes are in bytes, e.g. 2048 + 64 or 4096 + 224"));
+    ui.label(tr("• Interleaved: each of the steps' data is followed by its share of the spare"));
+    ui.label(tr("• ECC offset is where the codes start in the spare, or in each step's share"));
+    ui.label(tr("• Hamming needs 3 bytes per sector; BCH needs 13 × strength bits per 512 bytes, 14 × per 1024, rounded up to bytes"));
+    ui.label(tr("• Erased (all FF) sectors are skipped; a trailing partial page is dropped"));
+    
+    ui.add_space(8.0);
+    
+    render_operation_preview(app, ui);
+    
+    ui.add_space(8.0);
+    
+    ui.horizontal(|ui| {
+        if ui.button(tr("✓ Save")).clicked() {
+            app.save_current_operation();
+        }
+        
+        if ui.button(tr("✗ Cancel")).clicked() {
+            app.cancel_operation_edit();
+        }
+    });
+}
+
+/// Grouping and tie-break fields of a majority vote, shared by the editors that vote
+fn render_vote_fields(app: &mut BitApp, ui: &mut egui::Ui) {
+    ui.horizontal(|ui| {
+        ui.radio_value(&mut app.majority_frames, false, tr("Repeated bits"));
+        ui.radio_value(&mut app.majority_frames, true, tr("Repeated frames"));
+    });
+    
+    ui.add_space(4.0);
+    
+    ui.horizontal(|ui| {
+        ui.label(tr("Repeats:"));
+        ui.text_edit_singleline(&mut app.majority_repeats);
+    });
+    if app.majority_frames {
+        ui.horizontal(|ui| {
+            ui.label(tr("Frame length:"));
+            ui.text_edit_singleline(&mut app.majority_frame_length);
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr("Frames per block:"));
+            ui.text_edit_singleline(&mut app.majority_block_frames);
+        });
+    }
+    
+    ui.horizontal(|ui| {
+        ui.label(tr("Ties give:"));
+        egui::ComboBox::from_id_salt("majority_tie_break")
+            .selected_text(app.majority_tie_break.name())
+            .show_ui(ui, |ui| {
+                for tie_break in TieBreak::ALL {
+                    ui.selectable_value(&mut app.majority_tie_break, tie_break, tie_break.name());
+                }
+            });
+    });
+}
+
+fn render_conditional_editor(app: &mut BitApp, ui: &mut egui::Ui) {
+    ui.heading(tr("Conditional"));
+    ui.separator();
+    
+    ui.horizontal(|ui| {
+        ui.label(tr("Name:"));
+        ui.text_edit_singleline(&mut app.conditional_name);
+    });
+    
+    ui.add_space(8.0);
+    
+    match &app.conditional_operation {
+        Some(op) => ui.label(format!("Applies: {} ({})", op.name(), op.description())),
+        None => ui.weak(tr("No operation to apply")),
+    };
+    
+    ui.add_space(4.0);
+    
+    ui.horizontal(|ui| {
+        ui.radio_value(&mut app.conditional_by_ranges, false, tr("Every Nth frame"));
+        ui.radio_value(&mut app.conditional_by_ranges, true, tr("Bit ranges"));
+    });
+    
+    ui.add_space(4.0);
+    
+    if app.conditional_by_ranges {
+        ui.horizontal(|ui| {
+            ui.label(tr("Ranges (start,end per line):"));
+            if ui.button(tr("📂 Load CSV...")).clicked()
+                && let Some(path) = rfd::FileDialog::new().add_filter("CSV", &["csv", "txt"]).pick_file()
+            {
+                match std::fs::read_to_string(&path) {
+                    Ok(text) => app.conditional_ranges = text,
+                    Err(e) => app.notifications.error(format!("Failed to read {}: {}", path.display(), e)),
+                }
+            }
+        });
+        egui::ScrollArea::vertical()
+            .id_salt("conditional_ranges")
+            .max_height(120.0)
+            .show(ui, |ui| {
+                ui.add(egui::TextEdit::multiline(&mut app.conditional_ranges)
+                    .font(egui::TextStyle::Monospace)
+                    .desired_rows(5));
+            });
+    } else {
+        ui.horizontal(|ui| {
+            ui.label(tr("Frame length:"));
+            ui.text_edit_singleline(&mut app.conditional_frame_length);
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr("Every:"));
+            ui.text_edit_singleline(&mut app.conditional_every);
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr("First frame:"));
+            ui.text_edit_singleline(&mut app.conditional_offset);
+        });
+    }
+    
+    ui.add_space(4.0);
+    ui.label(tr("💡 Tips:"));
+    ui.label(tr("• The operation runs on each selected frame or range separately"));
+    ui.label(tr("• Every 2 from frame 1 picks frames 1, 3, 5, ... (counting from 0)"));
+    ui.label(tr("• Range ends are exclusive; a header line and # comments are skipped"));
+    ui.label(tr("• Values accept math: 64*8+3"));
+    
+    ui.add_space(8.0);
+    
+    render_operation_preview(app, ui);
+    
+    ui.add_space(8.0);
+    
+    ui.horizontal(|ui| {
+        if ui.button(tr("✓ Save")).clicked() {
+            app.save_current_operation();
+        }
+        
+        if let Some(index) = app.editing_operation_index
+            && matches!(app.operations.get(index), Some(crate::processing::BitOperation::Conditional { .. }))
+            && ui.button(tr("⤴ Apply Everywhere")).on_hover_text(tr("Replace this with the wrapped operation, applied to all bits")).clicked()
+        {
+            app.cancel_operation_edit();
+            app.unwrap_conditional_operation(index);
+        }
+        
+        if ui.button(tr("✗ Cancel")).clicked() {
+            app.cancel_operation_edit();
+        }
+    });
+}
+
+fn render_variants_editor(app: &mut BitApp, ui: &mut egui::Ui) {
+    ui.heading(tr("A/B Variants"));
+    ui.separator();
+    
+    ui.horizontal(|ui| {
+        ui.label(tr("Name:"));
+        ui.text_edit_singleline(&mut app.variants_name);
+    });
+    
+    ui.add_space(8.0);
+    
+    let mut edit_slot = None;
+    for (slot, label) in [(0, "A"), (1, "B")] {
+        let variant = if slot == 0 { &app.variants_a } else { &app.variants_b };
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut app.variants_use_b, slot == 1, label);
+            match variant {
+                Some(op) => ui.label(format!("{} ({})", op.name(), op.description())),
+                None => ui.weak(tr("No operation")),
+            };
+            if app.editing_operation_index.is_some() && ui.small_button("✏").on_hover_text(format!("Edit variant {}", label)).clicked() {
+                edit_slot = Some(slot);
+            }
+        });
+    }
+    
+    ui.add_space(8.0);
+    
+    ui.horizontal(|ui| {
+        if ui.button(tr("📊 Compare Outputs")).on_hover_text(tr("Run both variants on the data and count the bits their outputs differ in")).clicked() {
+            app.compare_variants();
+        }
+        if let Some(comparison) = &app.variant_comparison {
+            if comparison.is_identical() {
+                ui.colored_label(ui.visuals().warn_fg_color, tr("A and B give the same output"));
+            } else {
+                ui.label(format!("{} bits differ", comparison.differing_bits));
+            }
+        }
+    });
+    if let Some(comparison) = &app.variant_comparison {
+        ui.small(comparison.summary());
+    }
+    
+    ui.add_space(8.0);
+    
+    render_operation_preview(app, ui);
+    
+    ui.add_space(8.0);
+    
+    ui.horizontal(|ui| {
+        if ui.button(tr("✓ Save")).clicked() {
+            app.save_current_operation();
+        }
+        
+        if let Some(index) = app.editing_operation_index
+            && ui.button(tr("⤴ Keep Selected")).on_hover_text(tr("Replace the variants with the selected one")).clicked()
+        {
+            app.save_current_operation();
+            app.keep_active_variant(index);
+        }
+        
+        if ui.button(tr("✗ Cancel")).clicked() {
+            app.cancel_operation_edit();
+        }
+    });
+    
+    // Saving first keeps the name and selection when switching to a variant's own editor
+    if let (Some(index), Some(slot)) = (app.editing_operation_index, edit_slot) {
+        app.save_current_operation();
+        app.open_variant_editor(index, slot);
+    }
+}
+
+fn render_delete_editor(app: &mut BitApp, ui: &mut egui::Ui) {
+    ui.heading(tr("Delete Bits"));
+    ui.separator();
+    
+    ui.horizontal(|ui| {
+        ui.label(tr("Name:"));
+        ui.text_edit_singleline(&mut app.delete_name);
+    });
+    
+    ui.add_space(8.0);
+    
+    ui.horizontal(|ui| {
+        ui.radio_value(&mut app.delete_unstuff, false, tr("Bit range"));
+        ui.radio_value(&mut app.delete_unstuff, true, tr("Bit stuffing"));
+    });
+    
+    ui.add_space(4.0);
+    
+    if app.delete_unstuff {
+        ui.horizontal(|ui| {
+            ui.label(tr("Ones before a stuffed 0:"));
+            ui.text_edit_singleline(&mut app.delete_run_length);
+        });
+        ui.label(tr("• Removes the 0 a transmitter inserts after that many consecutive 1s"));
+        ui.label(tr("• HDLC and AX.25 use 5; flags (six 1s) pass through unchanged"));
+    } else {
+        ui.horizontal(|ui| {
+            ui.label(tr("Position:"));
+            ui.text_edit_singleline(&mut app.delete_position);
+        });
+        
+        ui.horizontal(|ui| {
+            ui.label(tr("Count:"));
+            ui.text_edit_singleline(&mut app.delete_count);
+        });
+        ui.label(tr("• Position and Count accept math: 64*8+3"));
+        ui.label(tr("• len, frame_len and frames follow the input: len-64, frames/2*frame_len"));
+    }
+    
+    ui.add_space(8.0);
+    
+    render_operation_preview(app, ui);
+    
+    ui.add_space(8.0);
+    
+    ui.horizontal(|ui| {
+        if ui.button(tr("✓ Save")).clicked() {
+            app.save_current_operation();
+        }
+        
+        if ui.button(tr("✗ Cancel")).clicked() {
+            app.cancel_operation_edit();
+        }
+    });
+}
+
+fn render_script_editor(app: &mut BitApp, ui: &mut egui::Ui) {
+    ui.heading(tr("Script"));
+    ui.separator();
+    
+    ui.horizontal(|ui| {
+        ui.label(tr("Name:"));
+        ui.text_edit_singleline(&mut app.script_name);
+    });
+    
+    ui.add_space(8.0);
+    
+    egui::ScrollArea::vertical()
+        .id_salt("script_source")
+        .max_height(240.0)
+        .show(ui, |ui| {
+            ui.add(
+                egui::TextEdit::multiline(&mut app.script_source)
+                    .code_editor()
+                    .desired_rows(10)
+                    .desired_width(420.0),
+            );
+        });
+    
+    ui.horizontal(|ui| {
+        ui.label(tr("Time limit:"));
+        ui.add(egui::DragValue::new(&mut app.script_time_limit_ms).range(10..=600_000).suffix(" ms"));
+    });
+    
+    ui.collapsing(tr("Script reference"), |ui| {
+        ui.label(tr("• len(), get(i), set(i, v)"));
+        ui.label(tr("• get_bits(i, n), set_bits(i, n, v): up to 64 bits, MSB first"));
+        ui.label(tr("• push(v), push_bits(n, v), truncate(n)"));
+        ui.label(tr("• copy(from, to, n), reverse(i, n), invert(i, n)"));
+        ui.label("• let x = 1;  x = x + 1;  if c { } else { }  while c { }  for i in 0..n { }");
+        ui.label(tr("• Operators: + - * / % << >> & | ^ == != < <= > >= && || !"));
+        ui.label(tr("• A script that fails leaves the data unchanged"));
+    });
+    
+    ui.add_space(8.0);
+    
+    render_operation_preview(app, ui);
+    
+    ui.add_space(8.0);
+    
+    ui.horizontal(|ui| {
+        if ui.button(tr("✓ Save")).clicked() {
+            app.save_current_operation();
+        }
+        
+        if ui.button(tr("✗ Cancel")).clicked() {
+            app.cancel_operation_edit();
+        }
+    });
+}
+
+fn render_patch_editor(app: &mut BitApp, ui: &mut egui::Ui) {
+    ui.heading(tr("Bit Edits"));
+    ui.separator();
+    
+    ui.horizontal(|ui| {
+        ui.label(tr("Name:"));
+        ui.text_edit_singleline(&mut app.patch_name);
+    });
+    
+    ui.add_space(8.0);
+    
+    ui.label(tr("Edits, applied in order:"));
+    let mut to_remove = None;
+    egui::ScrollArea::vertical()
+        .id_salt("patch_list")
+        .max_height(200.0)
+        .show(ui, |ui| {
+            for (i, patch) in app.patch_list.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    let value = format!("{:0width$b}", patch.value, width = patch.len);
+                    if patch.len == 8 && patch.position % 8 == 0 {
+                        ui.label(format!("{}. Byte {} ← 0x{:02X} ({})", i + 1, patch.position / 8, patch.value, value));
+                    } else {
+                        ui.label(format!("{}. Bit {} ← {}", i + 1, patch.position, value));
+                    }
+                    if ui.small_button("❌").clicked() {
+                        to_remove = Some(i);
+                    }
+                });
+            }
+        });
+    if let Some(i) = to_remove {
+        app.patch_list.remove(i);
+    }
+    
+    ui.add_space(8.0);
+    
+    ui.horizontal(|ui| {
+        if ui.button(tr("✓ Save")).clicked() {
+            app.save_current_operation();
+        }
+        
+        if ui.button(tr("✗ Cancel")).clicked() {
+            app.cancel_operation_edit();
+        }
+    });
+}
+
+fn render_macro_editor(app: &mut BitApp, ui: &mut egui::Ui) {
+    ui.heading(tr("Macro"));
+    ui.separator();
+    
+    ui.horizontal(|ui| {
+        ui.label(tr("Name:"));
+        ui.text_edit_singleline(&mut app.macro_name);
+    });
+    
+    ui.add_space(8.0);
+    
+    ui.label(tr("Steps:"));
+    for (i, op) in app.macro_operations.iter().enumerate() {
+        let text = format!("{}. {} ({})", i + 1, op.name(), op.description());
+        if op.is_enabled() {
+            ui.label(text);
+        } else {
+            ui.weak(format!("{} - disabled", text));
+        }
+    }
+    
+    ui.add_space(8.0);
+    
+    render_operation_preview(app, ui);
+    
+    ui.add_space(8.0);
+    
+    ui.horizontal(|ui| {
+        if ui.button(tr("✓ Save")).clicked() {
+            app.save_current_operation();
+        }
+        
+        if let Some(index) = app.editing_operation_index
+            && app.editing_group_child.is_none()
+            && ui.button(tr("⤵ Expand into Steps")).on_hover_text(tr("Replace the macro with its individual operations")).clicked()
+        {
+            app.cancel_operation_edit();
+            app.expand_macro_operation(index);
+        }
+        
+        if ui.button(tr("✗ Cancel")).clicked() {
+            app.cancel_operation_edit();
+        }
+    });
+}
+
+fn render_group_editor(app: &mut BitApp, ui: &mut egui::Ui) {
+    ui.heading(tr("Group"));
+    ui.separator();
+    
+    ui.horizontal(|ui| {
+        ui.label(tr("Name:"));
+        ui.text_edit_singleline(&mut app.group_name);
+    });
+    
+    ui.add_space(8.0);
+    
+    ui.label(tr("Operations:"));
+    for (i, op) in app.group_operations.iter().enumerate() {
+        let text = format!("{}. {} ({})", i + 1, op.name(), op.description());
+        if op.is_enabled() {
+            ui.label(text);
+        } else {
+            ui.weak(format!("{} - disabled", text));
+        }
+    }
+    ui.small(tr("Edit, move out or remove members from the Active Operations panel"));
+    
+    ui.add_space(8.0);
+    
+    ui.horizontal(|ui| {
+        if ui.button(tr("✓ Save")).clicked() {
+            app.save_current_operation();
+        }
+        
+        if let Some(index) = app.editing_operation_index
+            && ui.button(tr("⤵ Ungroup")).on_hover_text(tr("Replace the group with its operations")).clicked()
+        {
+            app.cancel_operation_edit();
+            app.ungroup_operation(index);
+        }
+        
+        if ui.button(tr("✗ Cancel")).clicked() {
+            app.cancel_operation_edit();
+        }
+    });
+}
+
+fn render_invert_editor(app: &mut BitApp, ui: &mut egui::Ui) {
+    ui.heading(tr("Invert All Bits"));
+    ui.separator();
+    
+    ui.horizontal(|ui| {
+        ui.label(tr("Name:"));
+        ui.text_edit_singleline(&mut app.invert_name);
+    });
+    
+    ui.add_space(8.0);
+    
+    ui.label(tr("This operation will invert all bits:"));
+    ui.label("• 0 → 1");
+    ui.label("• 1 → 0");
+    
+    ui.add_space(8.0);
+    
+    ui.horizontal(|ui| {
+        if ui.button(tr("✓ Save")).clicked() {
+            app.save_current_operation();
+        }
+        
+        if ui.button(tr("✗ Cancel")).clicked() {
+            app.cancel_operation_edit();
+        }
+    });
+}
+
+fn render_truncate_editor(app: &mut BitApp, ui: &mut egui::Ui) {
+    ui.heading(tr("Truncate Bits"));
+    ui.separator();
+    
+    ui.horizontal(|ui| {
+        ui.label(tr("Name:"));
+        ui.text_edit_singleline(&mut app.truncate_name);
+    });
+    
+    ui.add_space(8.0);
+    
+    ui.label(tr("Specify the range of bits to keep:"));
+    ui.add_space(4.0);
+    
+    ui.horizontal(|ui| {
+        ui.label(tr("Start (inclusive):"));
+        let start_response = ui.text_edit_singleline(&mut app.truncate_start);
+        
+        // Evaluate math expression on Enter key
+        if start_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+            if let Ok(result) = eval_expression(&app.truncate_start) {
+                app.truncate_start = result.to_string();
+            }
+        }
+    });
+    
+    ui.horizontal(|ui| {
+        ui.label(tr("End (exclusive):"));
+        let end_response = ui.text_edit_singleline(&mut app.truncate_end);
+        
+        // Evaluate math expression on Enter key
+        if end_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+            if !app.truncate_end.is_empty() {
+                if let Ok(result) = eval_expression(&app.truncate_end) {
+                    app.truncate_end = result.to_string();
+                }
+            }
+        }
+    });
+    
+    ui.add_space(4.0);
+    ui.label(tr("💡 Tips:"));
+    ui.label(tr("• Leave end empty to keep until the end"));
+    ui.label(tr("• You can use math: 8*8, 100+50, 200-10, 64/2"));
+    ui.label(tr("• len, frame_len and frames follow the input: End=len-1024 drops a 1024-bit trailer"));
+    ui.label(tr("• Example: Start=0, End=250 keeps bits 0-249"));
+    ui.label(tr("• Example: Start=100, End=250 keeps bits 100-249"));
+    ui.label(tr("• Example: Start=0, End=empty keeps all bits from 0"));
+    
+    ui.add_space(8.0);
+    
+    render_operation_preview(app, ui);
+    
+    ui.add_space(8.0);
+    
+    ui.horizontal(|ui| {
+        if ui.button(tr("✓ Save")).clicked() {
+            app.save_current_operation();
+        }
+        
+        if ui.button(tr("✗ Cancel")).clicked() {
+            app.cancel_operation_edit();
+        }
+    });
+}
+
+fn render_multiworksheet_editor(app: &mut BitApp, ui: &mut egui::Ui) {
+    ui.heading(tr("Multi-Worksheet Load"));
+    ui.separator();
+    
+    ui.horizontal(|ui| {
+        ui.label(tr("Name:"));
+        ui.text_edit_singleline(&mut app.multiworksheet_name);
+    });
+    
+    ui.add_space(8.0);
+    
+    ui.group(|ui| {
+        ui.label(tr("Add Worksheet Operation:"));
+        
+        ui.horizontal(|ui| {
+            ui.label(tr("Worksheet:"));
+            let selected_name = app.worksheets.iter()
+                .find(|ws| ws.id == app.multiworksheet_selected_worksheet)
+                .map_or(tr("Select..."), |ws| ws.name.as_str());
+            egui::ComboBox::from_id_salt("worksheet_selector")
+                .selected_text(selected_name)
+                .show_ui(ui, |ui| {
+                    for (idx, worksheet) in app.worksheets.iter().enumerate() {
+                        if idx != app.current_worksheet_index {
+                            ui.selectable_value(&mut app.multiworksheet_selected_worksheet, worksheet.id, &worksheet.name);
+                        }
+                    }
+                });
+        });
+        
+        ui.horizontal(|ui| {
+            ui.label(tr("Sequence:"));
+            ui.text_edit_singleline(&mut app.multiworksheet_input);
+        });
+        
+        if ui.button(tr("➕ Add")).clicked() {
+            let selected_exists = app.worksheets.iter().any(|ws| ws.id == app.multiworksheet_selected_worksheet);
+            if !app.multiworksheet_input.is_empty() && selected_exists {
+                app.multiworksheet_ops.push((
+                    app.multiworksheet_selected_worksheet,
+                    app.multiworksheet_input.clone()
+                ));
+                app.multiworksheet_input.clear();
+            }
+        }
+    });
+    
+    ui.add_space(8.0);
+    
+    if app.multiworksheet_ops.is_empty() {
+        ui.label(tr("No worksheets added yet"));
+    } else {
+        let mut to_remove = None;
+        for (idx, (ws_id, seq)) in app.multiworksheet_ops.iter().enumerate() {
+            ui.horizontal(|ui| {
+                let ws_name = app.worksheets.iter()
+                    .find(|ws| ws.id == *ws_id)
+                    .map_or(tr("Missing worksheet"), |ws| ws.name.as_str());
+                ui.label(format!("{}. {} → {}", idx + 1, ws_name, seq));
+                if ui.button("❌").clicked() {
+                    to_remove = Some(idx);
+                }
+            });
+        }
+        
+        if let Some(idx) = to_remove {
+            app.multiworksheet_ops.remove(idx);
+        }
+    }
+    
+    ui.add_space(8.0);
+    
+    ui.horizontal(|ui| {
+        if ui.button(tr("✓ Save")).clicked() {
+            app.save_current_operation();
+        }
+        
+        if ui.button(tr("✗ Cancel")).clicked() {
+            app.cancel_operation_edit();
+        }
+    });
+}
+
+pub fn render_column_editor_window(app: &mut BitApp, ctx: &egui::Context) {
+    if app.show_column_editor {
+        let mut open = true;
+        let title = if app.column_editor_index.is_some() { tr("✏ Edit Protocol Column") } else { tr("➕ Add Protocol Column") };
+        egui::Window::new(title)
+            .id(egui::Id::new("column_editor_window"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.heading(tr("Define Protocol Column"));
+                ui.separator();
+                
+                ui.horizontal(|ui| {
+                    ui.label(tr("Label:"));
+                    ui.text_edit_singleline(&mut app.column_editor_label);
+                });
+                
+                ui.add_space(8.0);
+                
+                ui.horizontal(|ui| {
+                    ui.label(tr("Start bit:"));
+                    ui.text_edit_singleline(&mut app.column_editor_bit_start);
+                });
+                
+                ui.horizontal(|ui| {
+                    ui.label(tr("End bit:"));
+                    ui.text_edit_singleline(&mut app.column_editor_bit_end);
+                });
+                
+                ui.add_space(8.0);
+                
+                ui.label(tr("Color:"));
+                ui.horizontal(|ui| {
+                    ui.label("R:");
+                    ui.add(egui::Slider::new(&mut app.column_editor_color[0], 0..=255));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("G:");
+                    ui.add(egui::Slider::new(&mut app.column_editor_color[1], 0..=255));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("B:");
+                    ui.add(egui::Slider::new(&mut app.column_editor_color[2], 0..=255));
+                });
+                
+                let color = egui::Color32::from_rgb(
+                    app.column_editor_color[0],
+                    app.column_editor_color[1],
+                    app.column_editor_color[2]
+                );
+                ui.horizontal(|ui| {
+                    ui.label(tr("Preview:"));
+                    let (rect, _) = ui.allocate_exact_size(
+                        egui::vec2(100.0, 30.0),
+                        egui::Sense::hover()
+                    );
+                    ui.painter().rect_filled(rect, 3.0, color);
+                });
+                
+                ui.horizontal(|ui| {
+                    ui.label(tr("Decode as time:"));
+                    let selected = app.column_editor_timestamp.map_or("No", |format| format.name());
+                    egui::ComboBox::from_id_salt("column_editor_timestamp")
+                        .selected_text(selected)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut app.column_editor_timestamp, None, tr("No"));
+                            for format in crate::analysis::TimestampFormat::ALL {
+                                ui.selectable_value(&mut app.column_editor_timestamp, Some(format), format.name());
+                            }
+                        });
+                });
+                
+                ui.add_space(8.0);
+                
+                ui.horizontal(|ui| {
+                    let confirm = if app.column_editor_index.is_some() { tr("✓ Save Column") } else { tr("✓ Add Column") };
+                    if ui.button(confirm).clicked() {
+                        if let (Ok(start), Ok(end)) = (
+                            app.column_editor_bit_start.parse::<usize>(),
+                            app.column_editor_bit_end.parse::<usize>()
+                        ) {
+                            if start <= end {
+                                let label = if app.column_editor_label.is_empty() {
+                                    format!("Column {}", app.byte_viewer.config.columns.len() + 1)
+                                } else {
+                                    app.column_editor_label.clone()
+                                };
+                                
+                                let mut column = crate::viewers::ByteColumn::new(
+                                    label,
+                                    start,
+                                    end,
+                                    app.column_editor_color
+                                );
+                                column.timestamp = app.column_editor_timestamp;
+                                
+                                match app.column_editor_index.take() {
+                                    Some(index) => {
+                                        // Editing keeps the column's visibility
+                                        if let Some(existing) = app.byte_viewer.config.columns.get(index) {
+                                            column.visible = existing.visible;
+                                        }
+                                        app.byte_viewer.update_column(index, column);
+                                    }
+                                    None => app.byte_viewer.add_column(column),
+                                }
+                                
+                                app.column_editor_label.clear();
+                                app.show_column_editor = false;
+                            } else {
+                                app.notifications.error("Start bit must be <= end bit".to_string());
+                            }
+                        } else {
+                            app.notifications.error("Invalid bit range values".to_string());
+                        }
+                    }
+                    
+                    if ui.button(tr("✗ Cancel")).clicked() {
+                        app.show_column_editor = false;
+                        app.column_editor_index = None;
+                    }
+                });
+            });
+        
+        if !open {
+            app.show_column_editor = false;
+            app.column_editor_index = None;
+        }
+    }
+}
+
+fn render_interleave_editor(app: &mut BitApp, ui: &mut egui::Ui) {
+    ui.heading(tr("Bit Interleaving / De-interleaving"));
+    ui.separator();
+    
+    ui.horizontal(|ui| {
+        ui.label(tr("Name:"));
+        ui.text_edit_singleline(&mut app.interleave_name);
+    });
+    
+    ui.add_space(8.0);
+    
+    // Type selection
+    ui.horizontal(|ui| {
+        ui.label(tr("Interleaver Type:"));
+        ui.radio_value(&mut app.interleave_type, crate::processing::InterleaverType::Block, tr("Block"));
+        ui.radio_value(&mut app.interleave_type, crate::processing::InterleaverType::Convolutional, tr("Convolutional"));
+        ui.radio_value(&mut app.interleave_type, crate::processing::InterleaverType::Symbol, tr("Symbol"));
+    });
+    
+    ui.add_space(4.0);
+    
+    // Direction selection
+    ui.horizontal(|ui| {
+        ui.label(tr("Direction:"));
+        ui.radio_value(&mut app.interleave_direction, crate::processing::InterleaverDirection::Interleave, tr("Interleave"));
+        ui.radio_value(&mut app.interleave_direction, crate::processing::InterleaverDirection::Deinterleave, tr("De-interleave"));
+    });
+    
+    ui.add_space(8.0);
+    ui.separator();
+    
+    // Parameters based on type
+    match app.interleave_type {
+        crate::processing::InterleaverType::Block => {
+            ui.label(tr("📦 Block Interleaver Parameters:"));
+            ui.add_space(4.0);
+            
+            ui.horizontal(|ui| {
+                ui.label(tr("Block Size (columns):"));
+                let block_response = ui.text_edit_singleline(&mut app.interleave_block_size);
+                
+                if block_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    if let Ok(result) = eval_expression(&app.interleave_block_size) {
+                        app.interleave_block_size = result.to_string();
+                    }
+                }
+            });
+            
+            ui.horizontal(|ui| {
+                ui.label(tr("Depth (rows):"));
+                let depth_response = ui.text_edit_singleline(&mut app.interleave_depth);
+                
+                if depth_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    if let Ok(result) = eval_expression(&app.interleave_depth) {
+                        app.interleave_depth = result.to_string();
+                    }
+                }
+            });
+            
+            ui.add_space(8.0);
+            
+            // Visual preview
+            ui.group(|ui| {
+                ui.label(tr("📊 Visual Preview:"));
+                ui.add_space(4.0);
+                
+                if let (Ok(block_size), Ok(depth)) = (
+                    app.interleave_block_size.parse::<usize>(),
+                    app.interleave_depth.parse::<usize>()
+                ) {
+                    if block_size > 0 && depth > 0 && block_size <= 16 && depth <= 16 {
+                        ui.label(format!("Matrix: {}×{} = {} bits per block", block_size, depth, block_size * depth));
+                        ui.add_space(4.0);
+                        
+                        match app.interleave_direction {
+                            crate::processing::InterleaverDirection::Interleave => {
+                                ui.label(tr("Write row-wise → Read column-wise:"));
+                                ui.add_space(2.0);
+                                render_block_matrix_preview(ui, block_size, depth);
+                            }
+                            crate::processing::InterleaverDirection::Deinterleave => {
+                                ui.label(tr("Write column-wise → Read row-wise:"));
+                                ui.add_space(2.0);
+                                render_block_matrix_preview(ui, block_size, depth);
+                            }
+                        }
+                    } else {
+                        ui.label(tr("⚠ Invalid dimensions (max 16×16)"));
+                    }
+                } else {
+                    ui.label(tr("⚠ Enter valid numbers for preview"));
+                }
+            });
+            
+            ui.add_space(4.0);
+            ui.label(tr("💡 Tips:"));
+            ui.label(tr("• Block interleaver rearranges data in matrix blocks"));
+            ui.label(tr("• Interleave: Write row-wise, read column-wise"));
+            ui.label(tr("• De-interleave: Reverses the process"));
+            ui.label(tr("• Example: 8×4 matrix handles 32 bits at a time"));
+            ui.label(tr("• Math supported: 2*4, 16/2, 8+4, etc."));
+        }
+        
+        crate::processing::InterleaverType::Convolutional => {
+            ui.label(tr("🔄 Convolutional Interleaver Parameters:"));
+            ui.add_space(4.0);
+            
+            ui.horizontal(|ui| {
+                ui.label(tr("Branches (B):"));
+                let branches_response = ui.text_edit_singleline(&mut app.interleave_branches);
+                
+                if branches_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    if let Ok(result) = eval_expression(&app.interleave_branches) {
+                        app.interleave_branches = result.to_string();
+                    }
+                }
+            });
+            
+            ui.horizontal(|ui| {
+                ui.label(tr("Delay Increment (M):"));
+                let delay_response = ui.text_edit_singleline(&mut app.interleave_delay_increment);
+                
+                if delay_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    if let Ok(result) = eval_expression(&app.interleave_delay_increment) {
+                        app.interleave_delay_increment = result.to_string();
+                    }
+                }
+            });
+            
+            ui.add_space(8.0);
+            
+            // Visual preview
+            ui.group(|ui| {
+                ui.label(tr("📊 Visual Preview:"));
+                ui.add_space(4.0);
+                
+                if let (Ok(branches), Ok(delay_inc)) = (
+                    app.interleave_branches.parse::<usize>(),
+                    app.interleave_delay_increment.parse::<usize>()
+                ) {
+                    if branches > 0 && branches <= 16 && delay_inc > 0 && delay_inc <= 16 {
+                        ui.label(format!("Branches: {}, Delay Increment: {}", branches, delay_inc));
+                        ui.add_space(4.0);
+                        
+                        match app.interleave_direction {
+                            crate::processing::InterleaverDirection::Interleave => {
+                                ui.label(tr("Round-robin distribution with delay:"));
+                                ui.add_space(2.0);
+                                render_convolutional_preview(ui, branches, delay_inc);
+                            }
+                            crate::processing::InterleaverDirection::Deinterleave => {
+                                ui.label(tr("Reverse round-robin with delay:"));
+                                ui.add_space(2.0);
+                                render_convolutional_preview(ui, branches, delay_inc);
+                            }
+                        }
+                    } else {
+                        ui.label(tr("⚠ Invalid parameters (max B=16, M=16)"));
+                    }
+                } else {
+                    ui.label(tr("⚠ Enter valid numbers for preview"));
+                }
+            });
+            
+            ui.add_space(4.0);
+            ui.label(tr("💡 Tips:"));
+            ui.label(tr("• Convolutional uses delay lines for each branch"));
+            ui.label(tr("• Branch i is delayed by i × M symbols"));
+            ui.label(tr("• Distributes bits round-robin across branches"));
+            ui.label(tr("• Example: B=4, M=1 → delays [0,1,2,3]"));
+            ui.label(tr("• Provides time-diversity for burst errors"));
+        }
+        
+        crate::processing::InterleaverType::Symbol => {
+            ui.label(tr("🔤 Symbol Interleaver Parameters:"));
+            ui.add_space(4.0);
+            
+            ui.horizontal(|ui| {
+                ui.label(tr("Symbol Size (bits):"));
+                let symbol_response = ui.text_edit_singleline(&mut app.interleave_symbol_size);
+                
+                if symbol_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    if let Ok(result) = eval_expression(&app.interleave_symbol_size) {
+                        app.interleave_symbol_size = result.to_string();
+                    }
+                }
+            });
+            
+            ui.horizontal(|ui| {
+                ui.label(tr("Block Size (columns):"));
+                let block_response = ui.text_edit_singleline(&mut app.interleave_block_size);
+                
+                if block_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    if let Ok(result) = eval_expression(&app.interleave_block_size) {
+                        app.interleave_block_size = result.to_string();
+                    }
+                }
+            });
+            
+            ui.horizontal(|ui| {
+                ui.label(tr("Depth (rows):"));
+                let depth_response = ui.text_edit_singleline(&mut app.interleave_depth);
+                
+                if depth_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    if let Ok(result) = eval_expression(&app.interleave_depth) {
+                        app.interleave_depth = result.to_string();
+                    }
+                }
+            });
+            
+            ui.add_space(8.0);
+            
+            ui.label(tr("💡 Tips:"));
+            ui.label(tr("• Symbol interleaver treats multi-bit symbols as atomic units"));
+            ui.label(tr("• Common symbol sizes: 8 (bytes), 4 (nibbles), 16 (words)"));
+            ui.label(tr("• Use for AABBCCDD → ABCDABCD transformations"));
+            ui.label(tr("• Matrix: Write symbols row-wise, read column-wise"));
+            ui.label(tr("• Example: Symbol=8, Block=2, Depth=4 → AABBCCDD → ABCDABCD"));
+        }
+    }
+    
+    ui.add_space(8.0);
+    
+    render_operation_preview(app, ui);
+    
+    ui.add_space(8.0);
+    
+    ui.horizontal(|ui| {
+        if ui.button(tr("✓ Save")).clicked() {
+            app.save_current_operation();
+        }
+        
+        if ui.button(tr("✗ Cancel")).clicked() {
+            app.cancel_operation_edit();
+        }
+    });
+}
+
+fn render_block_matrix_preview(ui: &mut egui::Ui, cols: usize, rows: usize) {
+    use egui::{Color32, Rect, Stroke};
+    
+    let cell_size = 24.0;
+    let spacing = 2.0;
+    let total_width = cols as f32 * (cell_size + spacing);
+    let total_height = rows as f32 * (cell_size + spacing);
+    
+    let (response, painter) = ui.allocate_painter(
+        egui::vec2(total_width, total_height),
+        egui::Sense::hover()
+    );
+    
+    let base_pos = response.rect.min;
+    
+    for row in 0..rows.min(8) {
+        for col in 0..cols.min(8) {
+            let x = base_pos.x + col as f32 * (cell_size + spacing);
+            let y = base_pos.y + row as f32 * (cell_size + spacing);
+            
+            let rect = Rect::from_min_size(
+                egui::pos2(x, y),
+                egui::vec2(cell_size, cell_size)
+            );
+            
+            let bit_index = row * cols + col;
+            let color = Color32::from_rgb(
+                (100 + bit_index * 15).min(255) as u8,
+                (150 - bit_index * 5).max(50) as u8,
+                200
+            );
+            
+            painter.rect_filled(rect, 2.0, color);
+            painter.rect_stroke(
+                rect,
+                2.0,
+                Stroke::new(1.0, Color32::from_gray(80)),
+                egui::epaint::StrokeKind::Outside
+            );
+            
+            let text = format!("{}", bit_index);
+            painter.text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                text,
+                egui::FontId::monospace(10.0),
+                Color32::WHITE
+            );
+        }
+    }
+    
+    if rows > 8 || cols > 8 {
+        ui.label(tr("(showing first 8×8)"));
+    }
+}
+
+fn render_convolutional_preview(ui: &mut egui::Ui, branches: usize, delay_inc: usize) {
+    use egui::{Color32, Rect, Stroke};
+    
+    let branch_width = 80.0;
+    let branch_height = 30.0;
+    let spacing = 10.0;
+    
+    let total_width = branch_width + 150.0;
+    let total_height = branches.min(8) as f32 * (branch_height + spacing);
+    
+    let (response, painter) = ui.allocate_painter(
+        egui::vec2(total_width, total_height),
+        egui::Sense::hover()
+    );
+    
+    let base_pos = response.rect.min;
+    
+    for i in 0..branches.min(8) {
+        let y = base_pos.y + i as f32 * (branch_height + spacing);
+        let delay = i * delay_inc;
+        
+        // Draw branch box
+        let rect = Rect::from_min_size(
+            egui::pos2(base_pos.x, y),
+            egui::vec2(branch_width, branch_height)
+        );
+        
+        let color = Color32::from_rgb(
+            (100 + i * 20).min(255) as u8,
+            (150 - i * 10).max(50) as u8,
+            200
+        );
+        
+        painter.rect_filled(rect, 3.0, color);
+        painter.rect_stroke(
+            rect,
+            3.0,
+            Stroke::new(1.5, Color32::from_gray(80)),
+            egui::epaint::StrokeKind::Outside
+        );
+        
+        let text = format!("Branch {}", i);
+        painter.text(
+            rect.center(),
+            egui::Align2::CENTER_CENTER,
+            text,
+            egui::FontId::proportional(11.0),
+            Color32::WHITE
+        );
+        
+        // Draw delay indicator
+        let delay_text = format!("Delay: {} symbols", delay);
+        painter.text(
+            egui::pos2(base_pos.x + branch_width + 15.0, rect.center().y),
+            egui::Align2::LEFT_CENTER,
+            delay_text,
+            egui::FontId::monospace(10.0),
+            ui.style().visuals.text_color()
+        );
+    }
+    
+    if branches > 8 {
+        ui.label(tr("(showing first 8 branches)"));
+    }
+}
+
+pub fn render_frame_width_finder_window(app: &mut BitApp, ctx: &egui::Context) {
+    use egui_plot::{Line, Plot, PlotPoints, Bar, BarChart, Points};
+    
+    if !app.show_frame_width_finder {
+        return;
+    }
+    
+    // Clone values before entering the window to avoid borrow checker issues
+    let analysis = app.frame_width_analysis.clone();
+    let mut frame_width_min = app.frame_width_min;
+    let mut frame_width_max = app.frame_width_max;
+    let mut frame_width_delta = app.frame_width_delta;
+    let mut use_list = app.frame_width_use_list;
+    let mut width_list = app.frame_width_list.clone();
+    let scans = app.current_worksheet().frame_width_scans.clone();
+    let mut restore_scan: Option<usize> = None;
+    let mut sort_by_score = app.frame_width_sort_by_score;
+    let mut selected_width = app.frame_width_selected;
+    let mut run_analysis = false;
+    let mut apply_width: Option<usize> = None;
+    let mut preview_width: Option<usize> = None;
+    let mut suggest_sync: Option<usize> = None;
+    let mut keep_open = true;
+    
+    egui::Window::new(tr("🔍 Find Frame Width"))
+        .open(&mut keep_open)
+        .default_width(800.0)
+        .default_height(600.0)
+        .resizable(true)
+        .show(ctx, |ui| {
+            ui.heading(tr("Automatic Frame Width Detection"));
+            ui.label(tr("Analyzes bit patterns to detect the most probable frame width"));
+            ui.separator();
+            
+            // Controls
+            ui.horizontal(|ui| {
+                ui.label(tr("Widths:"));
+                ui.radio_value(&mut use_list, false, tr("Range"));
+                ui.radio_value(&mut use_list, true, tr("List"))
+                    .on_hover_text(tr("Comma-separated widths and ranges, like '96..1200 step 8' or '100,150,204,255'"));
+            });
+            ui.horizontal(|ui| {
+                if use_list {
+                    ui.add(egui::TextEdit::singleline(&mut width_list)
+                        .hint_text(tr("96..1200 step 8, 100, 150"))
+                        .desired_width(260.0));
+                    match crate::analysis::parse_width_list(&width_list) {
+                        Ok(widths) => ui.label(format!("{} widths", widths.len())),
+                        Err(e) => ui.colored_label(egui::Color32::RED, e),
+                    };
+                    ui.menu_button(tr("Presets"), |ui| {
+                        for preset in ["1..1024", "8..4096 step 8", "96..1200 step 8", "16..2048 step 16", "100,150,204,255"] {
+                            if ui.button(preset).clicked() {
+                                width_list = preset.to_string();
+                                ui.close();
+                            }
+                        }
+                    });
+                } else {
+                    ui.label(tr("Min Width:"));
+                    ui.add(egui::DragValue::new(&mut frame_width_min)
+                        .range(1..=1024)
+                        .speed(1.0));
+                    
+                    ui.add_space(10.0);
+                    
+                    ui.label(tr("Max Width:"));
+                    ui.add(egui::DragValue::new(&mut frame_width_max)
+                        .range(1..=1024)
+                        .speed(1.0));
+                }
+                
+                ui.add_space(10.0);
+                
+                ui.label(tr("Delta:"))
+                    .on_hover_text(tr("⚠️ Delta measures REPETITION PERIOD, not frame width!\n\n\
+                        • Delta 0: Find frame width by bit position consistency (recommended)\n\
+                        • Delta above 0: Find repetition period (e.g., delta 5 finds patterns every 5 frames)\n\
+                        • For ASCII/binary frame detection, keep Delta at 0"));
+                ui.add(egui::DragValue::new(&mut frame_width_delta)
+                    .range(0..=100)
+                    .speed(1.0));
+            });
+            
+            ui.add_space(5.0);
+            render_analysis_scope(app, ui);
+            ui.add_space(5.0);
+            
+            if ui.button(tr("🔍 Analyze")).clicked() {
+                run_analysis = true;
+            }
+            
+            if !scans.is_empty() {
+                ui.collapsing(format!("🕘 Previous Scans ({})", scans.len()), |ui| {
+                    egui::Grid::new("frame_width_scans").striped(true).show(ui, |ui| {
+                        ui.strong(tr("Widths"));
+                        ui.strong(tr("Delta"));
+                        ui.strong(tr("Best"));
+                        ui.strong(tr("Score"));
+                        ui.label("");
+                        ui.end_row();
+                        for (index, scan) in scans.iter().enumerate().rev() {
+                            ui.monospace(&scan.widths);
+                            ui.label(scan.delta.to_string());
+                            ui.label(scan.best_width.to_string());
+                            ui.label(format!("{:.4}", scan.best_score));
+                            ui.horizontal(|ui| {
+                                if ui.small_button("↺").on_hover_text(tr("Use these widths and delta for the next scan")).clicked() {
+                                    restore_scan = Some(index);
+                                }
+                                if ui.small_button("✓").on_hover_text(tr("Apply the best width to the viewer")).clicked() {
+                                    apply_width = Some(scan.best_width);
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+                });
+            }
+            
+            ui.separator();
+            
+            // Show results if analysis has been run
             if let Some(ref analysis) = analysis {
                 ui.horizontal(|ui| {
-                    ui.heading(format!("Best Width: {}", analysis.best_width));
-                    ui.label(format!("(score: {:.4})", analysis.best_score));
-                    
-                    ui.add_space(20.0);
-                    
-                    if ui.button("✓ Apply Width to Viewer").clicked() {
-                        apply_width = Some(analysis.best_width);
+                    ui.heading(format!("Best Width: {}", analysis.best_width));
+                    ui.label(format!("(score: {:.4})", analysis.best_score));
+                    
+                    ui.add_space(20.0);
+                    
+                    if ui.button(tr("✓ Apply Width to Viewer"))
+                        .on_hover_text(tr("Use this width for the bit, byte and text views, and remember it for this worksheet"))
+                        .clicked()
+                    {
+                        apply_width = Some(analysis.best_width);
+                    }
+                    
+                    let sync_width = selected_width.unwrap_or(analysis.best_width);
+                    if ui.button(format!("🎯 Suggest Sync Pattern ({} bits)", sync_width))
+                        .on_hover_text(tr("Add the longest run of bits that stay the same in every frame to the Pattern Locator"))
+                        .clicked()
+                    {
+                        suggest_sync = Some(sync_width);
+                    }
+                });
+                
+                ui.separator();
+                    
+                // Width scores line chart
+                ui.heading(tr("Width Scores"));
+                ui.label(tr("Higher scores indicate more consistent bit patterns at that width"));
+                ui.label(tr("💡 Click on the graph to select a width"));
+                
+                let plot_response = Plot::new("width_scores_plot")
+                    .view_aspect(2.5)
+                    .legend(egui_plot::Legend::default())
+                    .allow_drag(false)  // Disable dragging so clicks are easier
+                    .label_formatter(|name, value| {
+                        if !name.is_empty() {
+                            format!("{}\nWidth: {}\nScore: {:.4}", name, value.x as usize, value.y)
+                        } else {
+                            format!("Width: {}\nScore: {:.4}", value.x as usize, value.y)
+                        }
+                    })
+                    .show(ui, |plot_ui| {
+                        // Convert scores to plot points
+                        let points: PlotPoints = analysis.width_scores
+                            .iter()
+                            .map(|(width, score)| [*width as f64, *score])
+                            .collect();
+                        
+                        plot_ui.line(
+                            Line::new("consistency", points)
+                                .width(2.0)
+                        );
+                        
+                        // Highlight the best width
+                        let best_point = PlotPoints::new(vec![
+                            [analysis.best_width as f64, analysis.best_score]
+                        ]);
+                        
+                        plot_ui.points(
+                            Points::new("best", best_point)
+                                .radius(6.0)
+                        );
+                    });
+                
+                // Handle clicks on the plot to select a width
+                let mut hover_width: Option<usize> = None;
+                if let Some(pointer_pos) = plot_response.response.hover_pos() {
+                    let plot_pos = plot_response.transform.value_from_position(pointer_pos);
+                    
+                    // Find the nearest width to the hover position
+                    let hovered_width = plot_pos.x.round() as usize;
+                    
+                    // Find the closest actual width that was tested
+                    if let Some((nearest_width, _)) = analysis.width_scores
+                        .iter()
+                        .min_by_key(|(w, _)| (*w as i32 - hovered_width as i32).abs())
+                    {
+                        hover_width = Some(*nearest_width);
+                        
+                        if plot_response.response.clicked() {
+                            apply_width = Some(*nearest_width);
+                        }
+                    }
+                }
+                
+                // Show hover feedback
+                if let Some(width) = hover_width {
+                    let score = analysis.width_scores
+                        .iter()
+                        .find(|(w, _)| *w == width)
+                        .map(|(_, s)| *s)
+                        .unwrap_or(0.0);
+                    ui.label(format!("🖱️ Hovering: Width {} (score: {:.6}) - Click to apply", width, score));
+                }
+                
+                ui.add_space(10.0);
+                ui.separator();
+                
+                ui.heading(tr("Consistency Heatmap"));
+                ui.label(tr("One row per tested width, bit positions left to right; brighter cells are more consistent"));
+                ui.label(tr("💡 Click a row to preview that width in the viewer"));
+                if let Some(width) = render_consistency_heatmap(ui, analysis) {
+                    preview_width = Some(width);
+                    selected_width = Some(width);
+                }
+                
+                ui.add_space(10.0);
+                    ui.separator();
+                    
+                    // Bit position consistency for best width
+                    ui.heading(format!("Bit Position Consistency (Width {})", analysis.best_width));
+                    ui.label(tr("Shows which bit positions have consistent patterns"));
+                    
+                    let best_width_idx = analysis.width_scores
+                        .iter()
+                        .position(|(w, _)| *w == analysis.best_width)
+                        .unwrap_or(0);
+                    
+                    if best_width_idx < analysis.bit_position_patterns.len() {
+                        let bit_patterns = &analysis.bit_position_patterns[best_width_idx];
+                        
+                        Plot::new("bit_position_heatmap")
+                            .view_aspect(3.0)
+                            .legend(egui_plot::Legend::default())
+                            .label_formatter(|name, value| {
+                                if !name.is_empty() {
+                                    format!("{}\nBit Position: {}\nConsistency: {:.4}", 
+                                        name, value.x as usize, value.y)
+                                } else {
+                                    format!("Bit Position: {}\nConsistency: {:.4}", 
+                                        value.x as usize, value.y)
+                                }
+                            })
+                            .show(ui, |plot_ui| {
+                                // Create bars for each bit position
+                                let bars: Vec<_> = bit_patterns
+                                    .iter()
+                                    .enumerate()
+                                    .map(|(pos, &score)| {
+                                        Bar::new(pos as f64, score)
+                                            .width(0.8)
+                                    })
+                                    .collect();
+                                
+                                plot_ui.bar_chart(BarChart::new("bit_positions", bars));
+                            });
+                    }
+                    
+                    ui.add_space(10.0);
+                    ui.separator();
+                    
+                    // Top 100 candidate widths in a scrollable, sortable table
+                    ui.heading(tr("Candidate Widths"));
+                    
+                    let mut sorted_widths = analysis.width_scores.clone();
+                    
+                    // Apply current sort
+                    if sort_by_score {
+                        sorted_widths.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+                    } else {
+                        sorted_widths.sort_by_key(|(w, _)| *w);
+                    }
+                    
+                    use egui_extras::{TableBuilder, Column};
+                    
+                    TableBuilder::new(ui)
+                        .striped(true)
+                        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+                        .column(Column::auto().at_least(80.0))  // Width
+                        .column(Column::remainder())             // Score
+                        .header(25.0, |mut header| {
+                            header.col(|ui| {
+                                if ui.selectable_label(!sort_by_score, tr("Width")).clicked() {
+                                    sort_by_score = false;
+                                }
+                            });
+                            header.col(|ui| {
+                                if ui.selectable_label(sort_by_score, tr("Score")).clicked() {
+                                    sort_by_score = true;
+                                }
+                            });
+                        })
+                        .body(|body| {
+                            body.rows(20.0, sorted_widths.len().min(100), |mut row| {
+                                let row_idx = row.index();
+                                let (width, score) = sorted_widths[row_idx];
+                                let is_selected = selected_width == Some(width);
+                                
+                                row.col(|ui| {
+                                    let (rect, response) = ui.allocate_exact_size(
+                                        ui.available_size(),
+                                        egui::Sense::click()
+                                    );
+                                    
+                                    if is_selected {
+                                        ui.painter().rect_filled(rect, 0.0, egui::Color32::from_rgb(60, 100, 180));
+                                    }
+                                    
+                                    ui.painter().text(
+                                        rect.left_center() + egui::vec2(5.0, 0.0),
+                                        egui::Align2::LEFT_CENTER,
+                                        format!("{}", width),
+                                        egui::FontId::default(),
+                                        if is_selected { egui::Color32::WHITE } else { ui.visuals().text_color() }
+                                    );
+                                    
+                                    if response.clicked() {
+                                        apply_width = Some(width);
+                                        selected_width = Some(width);
+                                    }
+                                });
+                                
+                                row.col(|ui| {
+                                    let (rect, response) = ui.allocate_exact_size(
+                                        ui.available_size(),
+                                        egui::Sense::click()
+                                    );
+                                    
+                                    if is_selected {
+                                        ui.painter().rect_filled(rect, 0.0, egui::Color32::from_rgb(60, 100, 180));
+                                    }
+                                    
+                                    ui.painter().text(
+                                        rect.left_center() + egui::vec2(5.0, 0.0),
+                                        egui::Align2::LEFT_CENTER,
+                                        format!("{:.6}", score),
+                                        egui::FontId::default(),
+                                        if is_selected { egui::Color32::WHITE } else { ui.visuals().text_color() }
+                                    );
+                                    
+                                    if response.clicked() {
+                                        apply_width = Some(width);
+                                        selected_width = Some(width);
+                                    }
+                                });
+                            });
+                        });
+                } else {
+                    ui.label(tr("Click 'Analyze' to detect frame width"));
+                }
+            });
+    
+    // Update app state from window
+    app.show_frame_width_finder = keep_open;
+    app.frame_width_min = frame_width_min;
+    app.frame_width_max = frame_width_max;
+    app.frame_width_delta = frame_width_delta;
+    app.frame_width_use_list = use_list;
+    app.frame_width_list = width_list;
+    app.frame_width_sort_by_score = sort_by_score;
+    app.frame_width_selected = selected_width;
+    
+    if let Some(scan) = restore_scan.and_then(|index| scans.get(index)) {
+        app.restore_frame_width_scan(scan);
+    }
+    
+    // Run analysis if requested
+    if run_analysis {
+        app.run_frame_width_analysis();
+    }
+    
+    // Apply width after window to avoid borrow issues
+    if let Some(width) = apply_width {
+        app.lock_frame_width(width);
+    } else if let Some(width) = preview_width {
+        app.preview_frame_width(width);
+    }
+    
+    if let Some(width) = suggest_sync {
+        app.suggest_sync_pattern(width);
+    }
+}
+
+/// Most rows and columns of the Frame Width Finder heatmap
+const HEATMAP_ROWS: usize = 256;
+const HEATMAP_COLUMNS: usize = 512;
+
+/// Draws the analysis' consistency grid as an image and returns the width of a clicked row.
+/// The grid and its texture are kept in egui memory until the analysis changes.
+fn render_consistency_heatmap(ui: &mut egui::Ui, analysis: &crate::analysis::FrameWidthAnalysis) -> Option<usize> {
+    use std::hash::{Hash, Hasher};
+    
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    for (width, score) in &analysis.width_scores {
+        (width, score.to_bits()).hash(&mut hasher);
+    }
+    let key = hasher.finish();
+    let id = egui::Id::new("frame_width_heatmap");
+    let cached = ui.ctx().data(|data| data.get_temp::<(u64, crate::analysis::ConsistencyGrid, egui::TextureHandle)>(id));
+    let (grid, texture) = match cached {
+        Some((cached_key, grid, texture)) if cached_key == key => (grid, texture),
+        _ => {
+            let grid = analysis.consistency_grid(HEATMAP_ROWS, HEATMAP_COLUMNS);
+            let columns = grid.cells.first().map_or(0, Vec::len).max(1);
+            let cold = egui::Color32::from_rgb(20, 30, 90);
+            let hot = egui::Color32::from_rgb(250, 220, 40);
+            let pixels = grid.cells
+                .iter()
+                .flat_map(|row| (0..columns).map(move |column| row.get(column).copied().flatten()))
+                .map(|value| match value {
+                    Some(value) => cold.lerp_to_gamma(hot, value.clamp(0.0, 1.0) as f32),
+                    None => egui::Color32::TRANSPARENT,
+                })
+                .collect();
+            let image = egui::ColorImage::new([columns, grid.widths.len().max(1)], pixels);
+            let texture = ui.ctx().load_texture("frame_width_heatmap", image, egui::TextureOptions::NEAREST);
+            ui.ctx().data_mut(|data| data.insert_temp(id, (key, grid.clone(), texture.clone())));
+            (grid, texture)
+        }
+    };
+    if grid.widths.is_empty() {
+        ui.weak(tr("No widths were scored"));
+        return None;
+    }
+    
+    let height = (grid.widths.len() as f32 * 3.0).clamp(120.0, 360.0);
+    let response = ui.add(
+        egui::Image::new((texture.id(), egui::vec2(ui.available_width(), height))).sense(egui::Sense::click()),
+    );
+    let pos = response.hover_pos()?;
+    let fraction = |value: f32, low: f32, size: f32| ((value - low) / size).clamp(0.0, 0.999);
+    let row = (fraction(pos.y, response.rect.top(), response.rect.height()) * grid.widths.len() as f32) as usize;
+    let columns = grid.cells[row].len();
+    let column = (fraction(pos.x, response.rect.left(), response.rect.width()) * columns as f32) as usize;
+    let width = grid.widths[row];
+    let first_bit = column * grid.bits_per_column;
+    let consistency = match grid.cells[row].get(column).copied().flatten() {
+        Some(value) => format!("{:.3}", value),
+        None => "-".to_string(),
+    };
+    let bits = if grid.bits_per_column > 1 {
+        format!("bits {}-{}", first_bit, first_bit + grid.bits_per_column - 1)
+    } else {
+        format!("bit {}", first_bit)
+    };
+    let clicked = response.clicked();
+    response.on_hover_text(format!("Width {}, {}\nConsistency: {}\nClick to preview", width, bits, consistency));
+    clicked.then_some(width)
+}
+
+pub fn render_periodicity_window(app: &mut BitApp, ctx: &egui::Context) {
+    use egui_plot::{Line, Plot, PlotPoints, Points};
+    
+    if !app.show_periodicity {
+        return;
+    }
+    
+    let mut run_analysis = false;
+    let mut apply_width: Option<usize> = None;
+    let mut keep_open = true;
+    
+    egui::Window::new(tr("〰 Periodicity Spectrum"))
+        .open(&mut keep_open)
+        .default_width(800.0)
+        .default_height(450.0)
+        .resizable(true)
+        .show(ctx, |ui| {
+            ui.label(tr("Autocorrelation of the bits against each candidate period; harmonics and interleaved periods show up as extra peaks"));
+            ui.separator();
+            
+            ui.horizontal(|ui| {
+                ui.label(tr("Min Period:"));
+                ui.add(egui::DragValue::new(&mut app.periodicity_min).range(1..=65536));
+                ui.add_space(10.0);
+                ui.label(tr("Max Period:"));
+                ui.add(egui::DragValue::new(&mut app.periodicity_max).range(1..=65536));
+                app.periodicity_max = app.periodicity_max.max(app.periodicity_min);
+                ui.add_space(10.0);
+                if ui.button(tr("🔍 Analyze")).clicked() {
+                    run_analysis = true;
+                }
+            });
+            render_analysis_scope(app, ui);
+            
+            ui.separator();
+            
+            let Some(spectrum) = &app.periodicity_spectrum else {
+                ui.label(tr("Click 'Analyze' to compute the spectrum"));
+                return;
+            };
+            if spectrum.scores.is_empty() {
+                ui.label(tr("Not enough data for the requested periods"));
+                return;
+            }
+            
+            ui.horizontal_wrapped(|ui| {
+                ui.label(tr("Peaks:"));
+                if spectrum.peaks.is_empty() {
+                    ui.label(tr("none"));
+                }
+                for &period in &spectrum.peaks {
+                    if ui.button(period.to_string()).on_hover_text(tr("Set as frame width")).clicked() {
+                        apply_width = Some(period);
+                    }
+                }
+            });
+            ui.label(tr("💡 Click on the graph to use a period as the frame width"));
+            
+            let plot_response = Plot::new("periodicity_plot")
+                .view_aspect(2.5)
+                .allow_drag(false)
+                .label_formatter(|_, value| format!("Period: {}\nScore: {:.4}", value.x.round() as usize, value.y))
+                .show(ui, |plot_ui| {
+                    let points: PlotPoints = spectrum.scores
+                        .iter()
+                        .map(|(period, score)| [*period as f64, *score])
+                        .collect();
+                    plot_ui.line(Line::new("autocorrelation", points).width(1.5));
+                    
+                    let peak_points: PlotPoints = spectrum.peaks
+                        .iter()
+                        .filter_map(|peak| spectrum.scores.iter().find(|(period, _)| period == peak))
+                        .map(|(period, score)| [*period as f64, *score])
+                        .collect();
+                    plot_ui.points(Points::new("peaks", peak_points).radius(5.0));
+                });
+            
+            if let Some(pointer_pos) = plot_response.response.hover_pos() {
+                let hovered = plot_response.transform.value_from_position(pointer_pos).x;
+                // Snap to the highest score near the pointer so peaks are easy to hit
+                let snap = (spectrum.scores.len() / 200).max(1) as f64;
+                let nearest = spectrum.scores
+                    .iter()
+                    .filter(|(period, _)| (*period as f64 - hovered).abs() <= snap)
+                    .max_by(|a, b| a.1.total_cmp(&b.1));
+                if let Some(&(period, score)) = nearest {
+                    ui.label(format!("🖱️ Hovering: Period {} (score: {:.4}) - Click to apply", period, score));
+                    if plot_response.response.clicked() {
+                        apply_width = Some(period);
+                    }
+                }
+            }
+        });
+    
+    app.show_periodicity = keep_open;
+    
+    if run_analysis {
+        app.run_periodicity_analysis();
+    }
+    if let Some(width) = apply_width {
+        app.lock_frame_width(width);
+    }
+}
+
+pub fn render_hdlc_decoder_window(app: &mut BitApp, ctx: &egui::Context) {
+    use crate::analysis::ax25_summary;
+    
+    if !app.show_hdlc_decoder {
+        return;
+    }
+    
+    let mut options = app.hdlc_options;
+    let mut only_valid = app.hdlc_only_valid;
+    let mut selected = app.hdlc_selected;
+    let mut run_decode = false;
+    let mut jump_to: Option<(usize, usize)> = None;
+    let mut keep_open = true;
+    
+    egui::Window::new(tr("📡 HDLC Decoder"))
+        .open(&mut keep_open)
+        .default_width(600.0)
+        .default_height(500.0)
+        .resizable(true)
+        .show(ctx, |ui| {
+            ui.label(tr("Splits the stream on 0x7E flags, removes stuffed bits and checks the CRC-16 FCS"));
+            ui.separator();
+            
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut options.nrzi, tr("NRZI decode"))
+                    .on_hover_text(tr("Decode NRZI first: a 0 is a level change, a 1 is no change (AX.25 on air)"));
+                ui.checkbox(&mut options.lsb_first, tr("LSB first"))
+                    .on_hover_text(tr("HDLC sends each byte least significant bit first"));
+            });
+            render_analysis_scope(app, ui);
+            
+            if ui.button(tr("🔍 Decode")).clicked() {
+                run_decode = true;
+            }
+            
+            ui.separator();
+            
+            let Some(frames) = &app.hdlc_frames else {
+                ui.label(tr("Click 'Decode' to search the current data for frames"));
+                return;
+            };
+            
+            let valid = frames.iter().filter(|frame| frame.fcs_ok).count();
+            ui.horizontal(|ui| {
+                ui.label(format!("{} frames, {} with a valid FCS", frames.len(), valid));
+                ui.add_space(20.0);
+                ui.checkbox(&mut only_valid, tr("Only valid frames"));
+            });
+            ui.label(tr("💡 Click a frame to jump to it"));
+            ui.add_space(5.0);
+            
+            egui::ScrollArea::vertical()
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    for (index, frame) in frames.iter().enumerate() {
+                        if only_valid && !frame.fcs_ok {
+                            continue;
+                        }
+                        
+                        let payload = frame.payload();
+                        let summary = ax25_summary(payload).unwrap_or_else(|| {
+                            let preview: Vec<String> = payload.iter().take(16).map(|b| format!("{:02X}", b)).collect();
+                            let more = if payload.len() > 16 { " …" } else { "" };
+                            format!("{}{}", preview.join(" "), more)
+                        });
+                        let label = format!(
+                            "#{:<4} bit {:<10} {:>4} bytes  {}  {}",
+                            index,
+                            frame.start_bit,
+                            payload.len(),
+                            if frame.fcs_ok { "✓" } else { "✗" },
+                            summary,
+                        );
+                        
+                        let text = egui::RichText::new(label).monospace();
+                        let text = if frame.fcs_ok { text } else { text.color(egui::Color32::from_rgb(220, 80, 80)) };
+                        if ui.selectable_label(selected == Some(index), text).clicked() {
+                            selected = Some(index);
+                            jump_to = Some((frame.start_bit, frame.bit_len));
+                        }
+                    }
+                });
+        });
+    
+    app.show_hdlc_decoder = keep_open;
+    app.hdlc_options = options;
+    app.hdlc_only_valid = only_valid;
+    app.hdlc_selected = selected;
+    
+    if run_decode {
+        app.run_hdlc_decode();
+    }
+    
+    if let Some((start, len)) = jump_to {
+        app.jump_to_range(start, len, ctx.input(|i| i.time));
+    }
+}
+
+pub fn render_framer_window(app: &mut BitApp, ctx: &egui::Context) {
+    use crate::analysis::FrameCrc;
+    
+    if !app.show_framer {
+        return;
+    }
+    
+    let mut run_framer = false;
+    let mut select: Option<usize> = None;
+    let mut apply_width: Option<usize> = None;
+    let mut restrict: Option<usize> = None;
+    let mut export_bits = false;
+    let mut keep_open = true;
+    
+    egui::Window::new(tr("🧱 Framer"))
+        .open(&mut keep_open)
+        .default_width(550.0)
+        .default_height(500.0)
+        .resizable(true)
+        .show(ctx, |ui| {
+            ui.label(tr("Splits the stream into frames that start with a sync word, optionally ending at an end marker"));
+            ui.separator();
+            
+            ui.horizontal(|ui| {
+                ui.label(tr("Format:"));
+                ui.selectable_value(&mut app.framer_sync_format, PatternFormat::Bits, tr("Bits"));
+                ui.selectable_value(&mut app.framer_sync_format, PatternFormat::Hex, tr("Hex"));
+                ui.selectable_value(&mut app.framer_sync_format, PatternFormat::Ascii, tr("ASCII"));
+            });
+            
+            ui.horizontal(|ui| {
+                ui.label(tr("Sync word:"));
+                ui.text_edit_singleline(&mut app.framer_sync_input);
+            });
+            
+            ui.horizontal(|ui| {
+                ui.label(tr("End marker:"));
+                ui.text_edit_singleline(&mut app.framer_end_input);
+            }).response.on_hover_text(tr("Optional; frames then run from the sync word through the next end marker"));
+            
+            ui.horizontal(|ui| {
+                ui.label(tr("Garbles:"));
+                ui.add(egui::Slider::new(&mut app.framer_sync_garbles, 0..=16));
+            });
+            
+            let has_end_marker = !app.framer_end_input.trim().is_empty();
+            ui.add_enabled_ui(!has_end_marker, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr("Frame length (bits):"));
+                    ui.add(egui::DragValue::new(&mut app.framer_frame_length).range(0..=1_000_000));
+                    ui.label(tr("(0: up to the next sync word)"));
+                });
+            });
+            
+            ui.horizontal(|ui| {
+                ui.label(tr("Checksum:"));
+                egui::ComboBox::from_id_salt("framer_crc")
+                    .selected_text(app.framer_crc.name())
+                    .show_ui(ui, |ui| {
+                        for crc in FrameCrc::all() {
+                            ui.selectable_value(&mut app.framer_crc, crc, crc.name());
+                        }
+                    });
+            }).response.on_hover_text(tr("Stored in the last bits of each frame, computed over the bytes between the sync word and the checksum"));
+            render_analysis_scope(app, ui);
+            
+            ui.horizontal(|ui| {
+                if ui.button(tr("🔍 Split Frames")).clicked() {
+                    run_framer = true;
+                }
+                if app.framer_frame_length > 0 && ui.button(tr("✓ Use Length as Row Width")).clicked() {
+                    apply_width = Some(app.framer_frame_length);
+                }
+            });
+            
+            ui.separator();
+            
+            let Some(frames) = &app.framer_frames else {
+                ui.label(tr("Click 'Split Frames' to find frames in the current data"));
+                return;
+            };
+            
+            ui.horizontal(|ui| {
+                ui.add_enabled_ui(!frames.is_empty(), |ui| {
+                    ui.menu_button(tr("🎯 Restrict Operation"), |ui| {
+                        ui.label(tr("Apply an operation only inside these frames:"));
+                        if app.operations.is_empty() {
+                            ui.weak(tr("No operations yet"));
+                        }
+                        for (index, op) in app.operations.iter().enumerate() {
+                            if ui.add_enabled(!op.is_source(), egui::Button::new(op.name())).clicked() {
+                                restrict = Some(index);
+                                ui.close();
+                            }
+                        }
+                    }).response.on_hover_text(tr("Wraps the operation in a Conditional over the frames' bit ranges"));
+                    if ui.button(tr("💾 Export Frame Bits...")).on_hover_text(tr("Write the frames one after another to a binary file")).clicked() {
+                        export_bits = true;
+                    }
+                });
+            });
+            
+            ui.horizontal(|ui| {
+                ui.label(format!("{} frames", frames.len()));
+                if app.framer_crc != FrameCrc::None {
+                    let good = frames.iter().filter(|frame| frame.crc_ok == Some(true)).count();
+                    ui.label(format!("({} pass the checksum)", good));
+                }
+                
+                ui.add_space(20.0);
+                
+                let current = app.framer_selected;
+                if ui.add_enabled(current.is_some_and(|i| i > 0), egui::Button::new(tr("◀ Prev"))).clicked() {
+                    select = current.map(|i| i - 1);
+                }
+                if ui.add_enabled(current.is_none_or(|i| i + 1 < frames.len()), egui::Button::new(tr("Next ▶"))).clicked() {
+                    select = Some(current.map_or(0, |i| i + 1));
+                }
+            });
+            ui.add_space(5.0);
+            
+            egui::ScrollArea::vertical()
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    for (index, frame) in frames.iter().enumerate() {
+                        let crc = match frame.crc_ok {
+                            Some(true) => "✓",
+                            Some(false) => "✗",
+                            None => "—",
+                        };
+                        let label = format!(
+                            "#{:<5} bit {:<10} byte {:<9} {:>6} bits  sync errors {}  CRC {}",
+                            index,
+                            frame.position,
+                            format!("{}.{}", frame.position / 8, frame.position % 8),
+                            frame.length,
+                            frame.sync_errors,
+                            crc,
+                        );
+                        
+                        let text = egui::RichText::new(label).monospace();
+                        let text = if frame.crc_ok == Some(false) { text.color(egui::Color32::from_rgb(220, 80, 80)) } else { text };
+                        let response = ui.selectable_label(app.framer_selected == Some(index), text);
+                        if response.clicked() {
+                            select = Some(index);
+                        }
+                        if select == Some(index) {
+                            response.scroll_to_me(None);
+                        }
+                    }
+                });
+        });
+    
+    app.show_framer = keep_open;
+    
+    if run_framer {
+        app.run_framer();
+    }
+    
+    if let Some(index) = select {
+        app.select_frame(index, ctx.input(|i| i.time));
+    }
+    
+    if let Some(width) = apply_width {
+        app.lock_frame_width(width);
+    }
+    
+    if let Some(index) = restrict {
+        app.restrict_operation_to_frames(index);
+    }
+    
+    if export_bits {
+        app.export_frame_bits();
+    }
+}
+
+pub fn render_sources_window(app: &mut BitApp, ctx: &egui::Context) {
+    use crate::storage::{burst_starts, NetworkProtocol};
+    
+    if !app.show_sources {
+        return;
+    }
+    
+    let mut start = false;
+    let mut stop = false;
+    let mut jump_to: Option<(usize, usize)> = None;
+    let mut keep_open = true;
+    
+    egui::Window::new(tr("🌐 Sources"))
+        .open(&mut keep_open)
+        .default_width(500.0)
+        .default_height(400.0)
+        .resizable(true)
+        .show(ctx, |ui| {
+            ui.heading(tr("Network Source"));
+            ui.label(tr("Appends bytes received over the network to the original bits"));
+            ui.separator();
+            
+            let listening = app.network_listener.is_some();
+            ui.add_enabled_ui(!listening, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr("Protocol:"));
+                    ui.selectable_value(&mut app.source_protocol, NetworkProtocol::Udp, tr("UDP"));
+                    ui.selectable_value(&mut app.source_protocol, NetworkProtocol::Tcp, tr("TCP"));
+                    ui.add_space(10.0);
+                    ui.label(tr("Port:"));
+                    ui.add(egui::DragValue::new(&mut app.source_port).range(1..=65535));
+                });
+            });
+            
+            ui.horizontal(|ui| {
+                if let Some(listener) = &app.network_listener {
+                    if ui.button(tr("⏹ Stop")).clicked() {
+                        stop = true;
+                    }
+                    ui.label(format!("Listening on {} {}", listener.protocol.name(), listener.local_addr));
+                } else if ui.button(tr("▶ Start Listening")).clicked() {
+                    start = true;
+                }
+            });
+            
+            ui.separator();
+            
+            let total_bytes: usize = app.received_segments.iter().map(|segment| segment.bit_len / 8).sum();
+            ui.label(format!("Received {} chunk(s), {} bytes", app.received_segments.len(), total_bytes));
+            
+            ui.horizontal(|ui| {
+                let mut changed = ui.checkbox(&mut app.viewer.gaps.show, tr("Mark burst gaps"))
+                    .on_hover_text(tr("Draw a marker in the bit, byte and ASCII views where data resumes after a pause"))
+                    .changed();
+                ui.add_enabled_ui(app.viewer.gaps.show, |ui| {
+                    changed |= ui.checkbox(&mut app.viewer.gaps.collapsed, tr("Collapse gaps"))
+                        .on_hover_text(tr("Collapsed: a tick before each burst\nExpanded: a line across the view with the length of the pause"))
+                        .changed();
+                });
+                if changed {
+                    app.update_gap_markers();
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label(tr("Merge chunks closer than:"));
+                if ui.add(egui::DragValue::new(&mut app.gap_threshold_ms).range(0..=60_000).suffix(" ms")).changed() {
+                    app.update_gap_markers();
+                }
+            });
+            if let Some(note) = &app.gap_markers_note && app.viewer.gaps.show {
+                ui.colored_label(ui.visuals().warn_fg_color, format!("⚠ {}", note));
+            }
+            if !app.received_segments.is_empty() {
+                let gaps = burst_starts(&app.received_segments, std::time::Duration::from_millis(app.gap_threshold_ms));
+                ui.label(format!("{} burst(s)", gaps.len() + 1));
+            }
+            ui.label(tr("💡 Click a chunk to jump to it"));
+            ui.add_space(5.0);
+            
+            // Times are relative to the first chunk, so they stay meaningful after stopping
+            let started_at = app.received_segments.first().map(|segment| segment.received_at);
+            egui::ScrollArea::vertical()
+                .auto_shrink([false, false])
+                .stick_to_bottom(true)
+                .show(ui, |ui| {
+                    for segment in &app.received_segments {
+                        let elapsed = started_at
+                            .and_then(|started| segment.received_at.duration_since(started).ok())
+                            .map_or_else(String::new, |elapsed| format!("+{:.3}s", elapsed.as_secs_f64()));
+                        let label = format!(
+                            "{:<10} {:<22} bit {:<10} {:>6} bytes",
+                            elapsed,
+                            segment.peer,
+                            segment.bit_offset,
+                            segment.bit_len / 8,
+                        );
+                        if ui.selectable_label(false, egui::RichText::new(label).monospace()).clicked() {
+                            jump_to = Some((segment.bit_offset, segment.bit_len));
+                        }
+                    }
+                });
+        });
+    
+    app.show_sources = keep_open;
+    
+    if start {
+        app.start_network_source();
+    }
+    if stop {
+        app.stop_network_source();
+    }
+    if let Some((start, len)) = jump_to {
+        app.jump_to_range(start, len, ctx.input(|i| i.time));
+    }
+}
+
+pub fn render_repeats_window(app: &mut BitApp, ctx: &egui::Context) {
+    use crate::analysis::MAX_REPEAT_LENGTH;
+    
+    if !app.show_repeats {
+        return;
+    }
+    
+    let mut run_search = false;
+    let mut add_pattern: Option<usize> = None;
+    let mut jump_to: Option<(usize, usize)> = None;
+    let mut keep_open = true;
+    
+    egui::Window::new(tr("🔁 Repeating Sequences"))
+        .open(&mut keep_open)
+        .default_width(600.0)
+        .default_height(500.0)
+        .resizable(true)
+        .show(ctx, |ui| {
+            ui.label(tr("Finds bit sequences that occur far more often than in random data, e.g. sync words"));
+            ui.separator();
+            
+            ui.horizontal(|ui| {
+                ui.label(tr("Min Length:"));
+                ui.add(egui::DragValue::new(&mut app.repeat_min_len).range(1..=MAX_REPEAT_LENGTH));
+                ui.add_space(10.0);
+                ui.label(tr("Max Length:"));
+                ui.add(egui::DragValue::new(&mut app.repeat_max_len).range(1..=MAX_REPEAT_LENGTH));
+                app.repeat_max_len = app.repeat_max_len.max(app.repeat_min_len);
+            });
+            
+            render_analysis_scope(app, ui);
+            
+            if ui.button(tr("🔍 Find Repeats")).clicked() {
+                run_search = true;
+            }
+            
+            ui.separator();
+            
+            let Some(results) = &app.repeat_results else {
+                ui.label(tr("Click 'Find Repeats' to search the current data"));
+                return;
+            };
+            if results.is_empty() {
+                ui.label(tr("No repeating sequences found"));
+                return;
+            }
+            
+            ui.label(tr("💡 Click a sequence to jump to its first occurrence"));
+            ui.add_space(5.0);
+            
+            egui::ScrollArea::vertical()
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    for (index, candidate) in results.iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            if ui.button(tr("➕ Pattern")).on_hover_text(tr("Add to the Pattern Locator")).clicked() {
+                                add_pattern = Some(index);
+                            }
+                            
+                            let value = candidate.hex_string().unwrap_or_else(|| candidate.bits_string());
+                            let label = format!(
+                                "{:>2} bits  ×{:<6} {}",
+                                candidate.bits.len(),
+                                candidate.count,
+                                value,
+                            );
+                            let positions: Vec<String> = candidate.positions.iter().take(8).map(|p| p.to_string()).collect();
+                            let response = ui
+                                .selectable_label(false, egui::RichText::new(label).monospace())
+                                .on_hover_text(format!(
+                                    "{}\n{:.0}× more often than chance\nAt bits {}{}",
+                                    candidate.bits_string(),
+                                    candidate.surprise.exp2(),
+                                    positions.join(", "),
+                                    if candidate.count > positions.len() { ", …" } else { "" },
+                                ));
+                            if response.clicked() {
+                                jump_to = Some((candidate.positions[0], candidate.bits.len()));
+                            }
+                        });
+                    }
+                });
+        });
+    
+    app.show_repeats = keep_open;
+    
+    if run_search {
+        app.run_repeat_search();
+    }
+    if let Some(index) = add_pattern {
+        app.add_repeat_as_pattern(index);
+    }
+    if let Some((start, len)) = jump_to {
+        app.jump_to_range(start, len, ctx.input(|i| i.time));
+    }
+}
+
+pub fn render_channel_export_window(app: &mut BitApp, ctx: &egui::Context) {
+    if !app.show_channel_export {
+        return;
+    }
+    
+    let mut export = false;
+    let mut keep_open = true;
+    
+    egui::Window::new(tr("🔀 Export Channels"))
+        .open(&mut keep_open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.label(tr("Deals the displayed bits out to channels in turn and writes each channel to its own file"));
+            ui.separator();
+            
+            ui.horizontal(|ui| {
+                ui.label(tr("Channels:"));
+                ui.add(egui::DragValue::new(&mut app.channel_count).range(2..=256));
+            });
+            ui.horizontal(|ui| {
+                ui.label(tr("Bits per turn:"));
+                ui.add(egui::DragValue::new(&mut app.channel_unit_bits).range(1..=4096));
+                if ui.small_button(tr("Bit")).clicked() {
+                    app.channel_unit_bits = 1;
+                }
+                if ui.small_button(tr("Byte")).clicked() {
+                    app.channel_unit_bits = 8;
+                }
+                if ui.small_button(tr("Frame")).on_hover_text(tr("One frame of the current frame width per turn")).clicked() {
+                    app.channel_unit_bits = app.viewer.frame_length;
+                }
+            });
+            
+            let total = app.display_bits().len();
+            let units = total.div_ceil(app.channel_unit_bits.max(1));
+            ui.label(format!(
+                "About {} bits per channel, written as {{file}}_ch0.bin to _ch{}.bin",
+                units.div_ceil(app.channel_count.max(1)) * app.channel_unit_bits,
+                app.channel_count.saturating_sub(1),
+            ));
+            
+            ui.separator();
+            if ui.button(tr("💾 Export to Folder...")).clicked() {
+                export = true;
+            }
+        });
+    
+    app.show_channel_export = keep_open;
+    
+    if export {
+        app.export_channels();
+    }
+}
+
+pub fn render_frame_stats_window(app: &mut BitApp, ctx: &egui::Context) {
+    use crate::analysis::ColumnKind;
+    
+    if !app.show_frame_stats {
+        return;
+    }
+    
+    let mut compute = false;
+    let mut find_timestamps = false;
+    let mut add_timestamp: Option<usize> = None;
+    let mut jump_to: Option<(usize, usize)> = None;
+    let mut keep_open = true;
+    
+    egui::Window::new(tr("📋 Frame Statistics"))
+        .open(&mut keep_open)
+        .default_width(560.0)
+        .default_height(500.0)
+        .resizable(true)
+        .show(ctx, |ui| {
+            ui.label(tr("How each column of the frames behaves from frame to frame, like reading the transposed view"));
+            ui.separator();
+            
+            ui.horizontal(|ui| {
+                ui.label(format!("Frame width: {} bits", app.viewer.frame_length));
+                ui.add_space(10.0);
+                ui.label(tr("Column:"));
+                ui.selectable_value(&mut app.frame_stats_column_bits, 1, tr("Bits"));
+                ui.selectable_value(&mut app.frame_stats_column_bits, 8, tr("Bytes"));
+            });
+            
+            let super_frame = app.viewer.super_frame_length;
+            if super_frame > 1 {
+                ui.horizontal(|ui| {
+                    let mut per_position = app.frame_stats_super_position.is_some();
+                    if ui.checkbox(&mut per_position, format!("Only position in {}-frame super-frame:", super_frame)).changed() {
+                        app.frame_stats_super_position = per_position.then_some(0);
+                    }
+                    if let Some(position) = &mut app.frame_stats_super_position {
+                        ui.add(egui::DragValue::new(position).range(0..=super_frame - 1));
+                    }
+                });
+            }
+            
+            render_analysis_scope(app, ui);
+            
+            ui.horizontal(|ui| {
+                if ui.button(tr("📋 Compute")).clicked() {
+                    compute = true;
+                }
+                if ui.button(tr("🕒 Find Timestamps"))
+                    .on_hover_text(tr("Look for Unix, GPS and CCSDS times that increase from frame to frame"))
+                    .clicked()
+                {
+                    find_timestamps = true;
+                }
+            });
+            
+            if let Some((frame_length, candidates)) = &app.timestamp_candidates {
+                ui.separator();
+                if candidates.is_empty() {
+                    ui.label(format!("No timestamp fields found in {}-bit frames", frame_length));
+                }
+                for (index, candidate) in candidates.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("➕ Column")).on_hover_text(tr("Add as a byte view column decoded in tooltips")).clicked() {
+                            add_timestamp = Some(index);
+                        }
+                        let range = format!("{}..{}", candidate.offset, candidate.offset + candidate.format.bits());
+                        if ui.selectable_label(false, egui::RichText::new(range).monospace()).clicked() {
+                            jump_to = Some((candidate.offset, candidate.format.bits()));
+                        }
+                        ui.label(candidate.format.name()).on_hover_text(format!(
+                            "First frame: {}\nLast frame: {}\n{:.0}% of frames don't go back in time",
+                            crate::analysis::format_utc_millis(candidate.first_millis),
+                            crate::analysis::format_utc_millis(candidate.last_millis),
+                            candidate.increasing_percent,
+                        ));
+                        ui.weak(format!("~{} ms per frame", candidate.step_millis));
+                    });
+                }
+            }
+            
+            ui.separator();
+            
+            let Some((frame_length, stats)) = &app.frame_stats else {
+                ui.label(tr("Click 'Compute' to analyze the frames in the current scope"));
+                return;
+            };
+            if *frame_length != app.viewer.frame_length {
+                ui.colored_label(ui.visuals().warn_fg_color, format!("⚠ Computed for {}-bit frames; compute again for the current width", frame_length));
+            }
+            if let Some((position, super_frame)) = app.frame_stats_super_frame {
+                ui.label(format!("Frames at position {} of every {}", position, super_frame));
+            }
+            ui.label(tr("💡 Click an offset to jump to that column in the first frame"));
+            
+            egui::ScrollArea::vertical()
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    egui::Grid::new("frame_stats_table").num_columns(6).striped(true).show(ui, |ui| {
+                        ui.strong(tr("Bits"));
+                        ui.strong(tr("Most common"));
+                        ui.strong(tr("Constant"));
+                        ui.strong(tr("Distinct"));
+                        ui.strong(tr("Changes"));
+                        ui.strong(tr("Looks like"));
+                        ui.end_row();
+                        
+                        for column in stats {
+                            let range = format!("{}..{}", column.offset, column.offset + column.width);
+                            if ui.selectable_label(false, egui::RichText::new(range).monospace()).clicked() {
+                                jump_to = Some((column.offset, column.width));
+                            }
+                            let common = if column.width == 1 {
+                                column.common_value.to_string()
+                            } else {
+                                format!("0x{:0width$X}", column.common_value, width = column.width.div_ceil(4))
+                            };
+                            ui.label(egui::RichText::new(common).monospace());
+                            ui.label(format!("{:.1}%", column.constant_percent));
+                            ui.label(column.distinct.to_string());
+                            ui.label(format!("{:.1}%", column.transition_rate * 100.0));
+                            let color = match column.kind {
+                                ColumnKind::Constant => ui.visuals().weak_text_color(),
+                                ColumnKind::Counter => egui::Color32::from_rgb(100, 180, 255),
+                                ColumnKind::Flags => egui::Color32::from_rgb(230, 180, 60),
+                                ColumnKind::Payload => ui.visuals().text_color(),
+                            };
+                            ui.colored_label(color, column.kind.name());
+                            ui.end_row();
+                        }
+                    });
+                });
+        });
+    
+    app.show_frame_stats = keep_open;
+    
+    if compute {
+        app.run_frame_stats();
+    }
+    if find_timestamps {
+        app.run_timestamp_detection();
+    }
+    if let Some(index) = add_timestamp {
+        app.add_timestamp_column(index);
+    }
+    if let Some((start, len)) = jump_to {
+        let position = app.frame_stats_super_frame.map_or(0, |(position, _)| position);
+        let first_frame = app.analysis_range().map_or(0, |range| range.start) + position * app.viewer.frame_length;
+        app.jump_to_range(first_frame + start, len, ctx.input(|i| i.time));
+    }
+}
+
+/// Most points the frame distance plot draws; longer captures show the largest distance of
+/// each run of frames so spikes stay visible
+const MAX_DISTANCE_POINTS: usize = 4000;
+
+pub fn render_frame_distance_window(app: &mut BitApp, ctx: &egui::Context) {
+    use egui_plot::{Line, Plot, PlotPoints};
+    
+    if !app.show_frame_distance {
+        return;
+    }
+    
+    let mut compute = false;
+    let mut jump_to_frame: Option<usize> = None;
+    let mut use_period: Option<usize> = None;
+    let mut keep_open = true;
+    
+    egui::Window::new(tr("📉 Frame Distance"))
+        .open(&mut keep_open)
+        .default_width(640.0)
+        .default_height(420.0)
+        .resizable(true)
+        .show(ctx, |ui| {
+            ui.label(tr("Bits that change from each frame to the next; super-frames and mode changes show as repeating spikes"));
+            ui.separator();
+            
+            ui.label(format!("Frame width: {} bits", app.viewer.frame_length));
+            render_analysis_scope(app, ui);
+            if ui.button(tr("📉 Compute")).clicked() {
+                compute = true;
+            }
+            
+            ui.separator();
+            
+            let Some((_, result)) = &app.frame_distances else {
+                ui.label(tr("Click 'Compute' to compare the frames in the current scope"));
+                return;
+            };
+            ui.horizontal(|ui| {
+                ui.label(format!("{} frames of {} bits", result.distances.len() + 1, result.frame_length));
+                ui.separator();
+                ui.label(format!("Mean distance: {:.1}", result.mean()));
+                ui.label(format!("Max: {}", result.max()));
+                ui.separator();
+                match result.period {
+                    Some(period) => {
+                        ui.strong(format!("Repeats every {} frames", period));
+                        if ui.button(tr("Use as super-frame"))
+                            .on_hover_text(tr("Mark super-frames of this many frames in the views"))
+                            .clicked()
+                        {
+                            use_period = Some(period);
+                        }
+                    }
+                    None => {
+                        ui.weak(tr("No repeating pattern"));
+                    }
+                }
+            });
+            ui.label(tr("💡 Click on the graph to go to that frame"));
+            
+            let bucket = result.distances.len().div_ceil(MAX_DISTANCE_POINTS).max(1);
+            let plot_response = Plot::new("frame_distance_plot")
+                .view_aspect(2.5)
+                .allow_drag(false)
+                .label_formatter(|_, value| format!("Frame: {}\nDistance: {:.0}", value.x.round() as usize, value.y))
+                .show(ui, |plot_ui| {
+                    let points: PlotPoints = result.distances
+                        .chunks(bucket)
+                        .enumerate()
+                        .map(|(index, chunk)| [(index * bucket + 1) as f64, chunk.iter().copied().max().unwrap_or(0) as f64])
+                        .collect();
+                    plot_ui.line(Line::new("distance", points).width(1.5));
+                });
+            if plot_response.response.clicked()
+                && let Some(pointer_pos) = plot_response.response.hover_pos()
+            {
+                let frame = plot_response.transform.value_from_position(pointer_pos).x.round().max(0.0) as usize;
+                jump_to_frame = Some(frame.min(result.distances.len()));
+            }
+        });
+    
+    app.show_frame_distance = keep_open;
+    
+    if compute {
+        app.run_frame_distances();
+    }
+    if let Some(period) = use_period {
+        app.set_super_frame_length(period);
+    }
+    if let Some(frame) = jump_to_frame
+        && let Some((offset, result)) = &app.frame_distances
+    {
+        let (start, len) = (offset + frame * result.frame_length, result.frame_length);
+        app.jump_to_range(start, len, ctx.input(|i| i.time));
+    }
+}
+
+pub fn render_frames_csv_window(app: &mut BitApp, ctx: &egui::Context) {
+    use crate::analysis::parse_frame_fields;
+    
+    if !app.show_frames_csv {
+        return;
+    }
+    
+    let mut from_columns = false;
+    let mut export = false;
+    let mut keep_open = true;
+    
+    egui::Window::new(tr("📊 Frames to CSV"))
+        .open(&mut keep_open)
+        .default_width(420.0)
+        .resizable(true)
+        .show(ctx, |ui| {
+            ui.label(tr("One row per frame with the frame number, its first bit and each field below"));
+            ui.separator();
+            
+            ui.label(format!("Frame width: {} bits", app.viewer.frame_length));
+            render_analysis_scope(app, ui);
+            
+            ui.horizontal(|ui| {
+                ui.label(tr("Fields:"));
+                if ui.button(tr("📋 From Columns"))
+                    .on_hover_text(tr("Replace the fields with the byte view columns"))
+                    .clicked()
+                {
+                    from_columns = true;
+                }
+            });
+            ui.add(
+                egui::TextEdit::multiline(&mut app.frames_csv_spec)
+                    .font(egui::TextStyle::Monospace)
+                    .desired_rows(6)
+                    .desired_width(f32::INFINITY)
+                    .hint_text(tr("sync 0..16 hex\ncounter 16..24\nflags 24..28 bits")),
+            );
+            ui.small(tr("One field per line: name start..end [int|hex|bits], end exclusive, int if left out"));
+            
+            let fields = parse_frame_fields(&app.frames_csv_spec);
+            match &fields {
+                Ok(fields) if fields.is_empty() => {
+                    ui.weak(tr("No fields; only frame numbers and offsets will be written"));
+                }
+                Ok(fields) => {
+                    ui.label(format!("{} fields", fields.len()));
+                }
+                Err(e) => {
+                    ui.colored_label(ui.visuals().error_fg_color, e);
+                }
+            }
+            
+            if ui.add_enabled(fields.is_ok(), egui::Button::new(tr("💾 Export CSV..."))).clicked() {
+                export = true;
+            }
+        });
+    
+    app.show_frames_csv = keep_open;
+    
+    if from_columns {
+        app.frames_csv_spec_from_columns();
+    }
+    if export {
+        app.export_frames_csv();
+    }
+}
+
+pub fn render_converter_window(app: &mut BitApp, ctx: &egui::Context) {
+    use crate::utils::{format_number, parse_number, to_signed, NumberBase};
+    
+    if !app.show_converter {
+        return;
+    }
+    
+    let mut keep_open = true;
+    
+    egui::Window::new(tr("🧮 Base Converter"))
+        .open(&mut keep_open)
+        .default_width(380.0)
+        .resizable(false)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_id_salt("converter_base")
+                    .selected_text(app.converter_base.name())
+                    .show_ui(ui, |ui| {
+                        for base in NumberBase::ALL {
+                            ui.selectable_value(&mut app.converter_base, base, base.name());
+                        }
+                    });
+                ui.add(egui::TextEdit::singleline(&mut app.converter_input)
+                    .font(egui::TextStyle::Monospace)
+                    .hint_text(tr("Value")));
+            });
+            
+            ui.horizontal(|ui| {
+                ui.label(tr("Width:"));
+                for width in [8, 16, 32, 64] {
+                    ui.selectable_value(&mut app.converter_width, width, format!("{}", width));
+                }
+                ui.add(egui::DragValue::new(&mut app.converter_width).range(1..=64).suffix(" bits"));
+            });
+            
+            ui.separator();
+            
+            if app.converter_input.trim().is_empty() {
+                ui.weak(tr("Decimal input accepts math: 64*8+3"));
+                return;
+            }
+            let value = match parse_number(&app.converter_input, app.converter_base) {
+                Ok(value) => value,
+                Err(e) => {
+                    ui.colored_label(egui::Color32::from_rgb(255, 100, 100), e);
+                    return;
+                }
+            };
+            
+            let width = app.converter_width;
+            if width < 64 && value >> width != 0 {
+                ui.colored_label(ui.visuals().warn_fg_color, format!("⚠ Value doesn't fit; showing the low {} bits", width));
+            }
+            
+            let mut rows: Vec<(String, String)> = NumberBase::ALL
+                .iter()
+                .map(|base| (base.name().to_string(), format_number(value, *base, width)))
+                .collect();
+            rows.insert(3, ("Signed".to_string(), to_signed(value, width).to_string()));
+            
+            egui::Grid::new("converter_results").num_columns(3).striped(true).show(ui, |ui| {
+                for (label, text) in rows {
+                    ui.label(label);
+                    ui.label(egui::RichText::new(&text).monospace());
+                    if ui.small_button("📋").on_hover_text(tr("Copy")).clicked() {
+                        ui.ctx().copy_text(text);
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+    
+    app.show_converter = keep_open;
+}
+
+pub fn render_checksum_window(app: &mut BitApp, ctx: &egui::Context) {
+    use crate::utils::{format_number, NumberBase};
+    
+    if !app.show_checksums {
+        return;
+    }
+    
+    let mut compute = false;
+    let mut keep_open = true;
+    
+    egui::Window::new(tr("Σ Checksums"))
+        .open(&mut keep_open)
+        .default_width(520.0)
+        .resizable(true)
+        .show(ctx, |ui| {
+            ui.label(tr("Common checksums over a bit range, read as bytes from its first bit"));
+            ui.separator();
+            
+            render_analysis_scope(app, ui);
+            
+            if ui.button(tr("Σ Compute")).clicked() {
+                compute = true;
+            }
+            
+            ui.separator();
+            
+            let Some(report) = &app.checksum_report else {
+                ui.label(tr("Click 'Compute' to checksum the current scope"));
+                return;
+            };
+            
+            ui.label(format!("Bits {}..{} ({} bytes)", report.start, report.start + report.bits, report.bits.div_ceil(8)));
+            if report.padding_bits > 0 {
+                ui.colored_label(ui.visuals().warn_fg_color, format!("⚠ Last byte padded with {} zero bit(s)", report.padding_bits));
+            }
+            
+            egui::Grid::new("checksum_results").num_columns(3).striped(true).show(ui, |ui| {
+                for (kind, value) in &report.results {
+                    let hex: String = value.iter().map(|byte| format!("{:02X}", byte)).collect();
+                    ui.label(kind.name());
+                    let response = ui.label(egui::RichText::new(format!("0x{}", hex)).monospace());
+                    // Short values are shown in the other bases too
+                    if value.len() <= 8 {
+                        let number = value.iter().fold(0u64, |number, byte| (number << 8) | *byte as u64);
+                        let width = value.len() as u32 * 8;
+                        response.on_hover_text(format!(
+                            "Decimal: {}\nOctal: {}\nBinary: {}",
+                            number,
+                            format_number(number, NumberBase::Octal, width),
+                            format_number(number, NumberBase::Binary, width),
+                        ));
+                        ui.label(egui::RichText::new(number.to_string()).monospace());
+                    } else {
+                        ui.label("");
+                    }
+                    if ui.small_button("📋").on_hover_text(tr("Copy hex")).clicked() {
+                        ui.ctx().copy_text(hex);
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+    
+    app.show_checksums = keep_open;
+    
+    if compute {
+        app.run_checksums();
+    }
+}
+
+pub fn render_structure_window(app: &mut BitApp, ctx: &egui::Context) {
+    if !app.show_structures {
+        return;
+    }
+    
+    let mut decode = false;
+    let mut to_columns = false;
+    let mut jump: Option<(usize, usize)> = None;
+    let mut keep_open = true;
+    
+    egui::Window::new(tr("🧱 Structures"))
+        .open(&mut keep_open)
+        .default_width(560.0)
+        .resizable(true)
+        .show(ctx, |ui| {
+            ui.label(tr("Decode a JSON record template at a bit offset"));
+            ui.separator();
+            
+            ui.horizontal(|ui| {
+                ui.label(tr("Template:"));
+                if ui.button(tr("📂 Load...")).clicked()
+                    && let Some(path) = rfd::FileDialog::new().add_filter("JSON", &["json"]).pick_file()
+                {
+                    match std::fs::read_to_string(&path) {
+                        Ok(text) => app.structure_template = text,
+                        Err(e) => app.notifications.error(format!("Failed to read {}: {}", path.display(), e)),
                     }
+                }
+                if ui.button(tr("💾 Save...")).clicked()
+                    && let Some(path) = rfd::FileDialog::new().add_filter("JSON", &["json"]).save_file()
+                    && let Err(e) = std::fs::write(&path, &app.structure_template)
+                {
+                    app.notifications.error(format!("Failed to write {}: {}", path.display(), e));
+                }
+            });
+            egui::ScrollArea::vertical()
+                .id_salt("structure_template")
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    ui.add(egui::TextEdit::multiline(&mut app.structure_template)
+                        .font(egui::TextStyle::Monospace)
+                        .code_editor()
+                        .desired_width(f32::INFINITY)
+                        .desired_rows(10));
                 });
-                
-                ui.separator();
-                    
-                // Width scores line chart
-                ui.heading("Width Scores");
-                ui.label("Higher scores indicate more consistent bit patterns at that width");
-                ui.label("💡 Click on the graph to select a width");
-                
-                let plot_response = Plot::new("width_scores_plot")
-                    .view_aspect(2.5)
-                    .legend(egui_plot::Legend::default())
-                    .allow_drag(false)  // Disable dragging so clicks are easier
-                    .label_formatter(|name, value| {
-                        if !name.is_empty() {
-                            format!("{}\nWidth: {}\nScore: {:.4}", name, value.x as usize, value.y)
-                        } else {
-                            format!("Width: {}\nScore: {:.4}", value.x as usize, value.y)
+            ui.collapsing(tr("Template format"), |ui| {
+                ui.label(tr("• type: u8/u16/u32/u64, i8..i64, bits (with \"bits\"), bytes or ascii (with \"length\"), struct (with \"fields\")"));
+                ui.label(tr("• \"count\" repeats a field as an array; lengths and counts may name an earlier integer field"));
+                ui.label(tr("• \"if\": {\"field\", \"mask\", \"equals\"} skips a field unless an earlier value matches"));
+                ui.label(tr("• \"little_endian\": true and \"color\": [r, g, b] are optional"));
+            });
+            
+            ui.horizontal(|ui| {
+                ui.label(tr("Offset (bits):"));
+                ui.add(egui::TextEdit::singleline(&mut app.structure_offset).desired_width(100.0));
+                if ui.button(tr("▶ Decode")).clicked() {
+                    decode = true;
+                }
+            });
+            
+            ui.separator();
+            
+            let Some(root) = &app.structure_result else {
+                ui.label(tr("Click 'Decode' to apply the template"));
+                return;
+            };
+            
+            ui.horizontal(|ui| {
+                ui.label(format!("{} bits from bit {}", root.bit_len, root.bit_start));
+                if ui.button(tr("📊 Show as Byte Columns"))
+                    .on_hover_text(tr("Replace the byte view columns with the decoded fields, repeating every record length"))
+                    .clicked()
+                {
+                    to_columns = true;
+                }
+            });
+            egui::ScrollArea::vertical()
+                .id_salt("structure_tree")
+                .max_height(300.0)
+                .show(ui, |ui| {
+                    render_decoded_field(ui, root, "structure", &mut jump);
+                });
+        });
+    
+    app.show_structures = keep_open;
+    
+    if decode {
+        app.run_structure_decode();
+    }
+    if to_columns {
+        app.structure_to_byte_columns();
+    }
+    if let Some((start, len)) = jump {
+        app.jump_to_range(start, len, ctx.input(|i| i.time));
+    }
+}
+
+/// One node of the decoded structure tree; clicking a field's name jumps to its bits
+fn render_decoded_field(ui: &mut egui::Ui, field: &crate::analysis::DecodedField, path: &str, jump: &mut Option<(usize, usize)>) {
+    let path = format!("{}/{}", path, field.name);
+    let children = field.children();
+    if children.is_empty() {
+        ui.horizontal(|ui| {
+            let (rect, _) = ui.allocate_exact_size(egui::vec2(12.0, 12.0), egui::Sense::hover());
+            let [r, g, b] = field.color;
+            ui.painter().rect_filled(rect, 2.0, egui::Color32::from_rgb(r, g, b));
+            if ui.link(&field.name).on_hover_text(format!("Bits {}..{}", field.bit_start, field.bit_start + field.bit_len)).clicked() {
+                *jump = Some((field.bit_start, field.bit_len.max(1)));
+            }
+            ui.label(egui::RichText::new(field.value_text()).monospace());
+        });
+        return;
+    }
+    
+    let response = egui::CollapsingHeader::new(format!("{}  {}", field.name, field.value_text()))
+        .id_salt(&path)
+        .default_open(true)
+        .show(ui, |ui| {
+            for child in children {
+                render_decoded_field(ui, child, &path, jump);
+            }
+        });
+    if response.header_response.secondary_clicked() {
+        *jump = Some((field.bit_start, field.bit_len.max(1)));
+    }
+    response.header_response.on_hover_text(format!(
+        "Bits {}..{}, right-click to jump",
+        field.bit_start,
+        field.bit_start + field.bit_len
+    ));
+}
+
+pub fn render_missing_files_window(app: &mut BitApp, ctx: &egui::Context) {
+    if app.missing_files.is_empty() {
+        return;
+    }
+    
+    let mut locate = None;
+    let mut remap_folder = false;
+    let mut keep_open = true;
+    
+    egui::Window::new(tr("📂 Missing Files"))
+        .open(&mut keep_open)
+        .default_width(480.0)
+        .resizable(true)
+        .show(ctx, |ui| {
+            ui.label(tr("These files weren't found, perhaps because the session came from another machine or the captures were moved"));
+            ui.separator();
+            
+            egui::ScrollArea::vertical().max_height(240.0).show(ui, |ui| {
+                for (index, path) in app.missing_files.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("📂 Locate...")).clicked() {
+                            locate = Some(index);
                         }
-                    })
-                    .show(ui, |plot_ui| {
-                        // Convert scores to plot points
-                        let points: PlotPoints = analysis.width_scores
-                            .iter()
-                            .map(|(width, score)| [*width as f64, *score])
-                            .collect();
-                        
-                        plot_ui.line(
-                            Line::new("consistency", points)
-                                .width(2.0)
-                        );
-                        
-                        // Highlight the best width
-                        let best_point = PlotPoints::new(vec![
-                            [analysis.best_width as f64, analysis.best_score]
-                        ]);
-                        
-                        plot_ui.points(
-                            Points::new("best", best_point)
-                                .radius(6.0)
-                        );
+                        ui.label(path.display().to_string());
                     });
-                
-                // Handle clicks on the plot to select a width
-                let mut hover_width: Option<usize> = None;
-                if let Some(pointer_pos) = plot_response.response.hover_pos() {
-                    let plot_pos = plot_response.transform.value_from_position(pointer_pos);
-                    
-                    // Find the nearest width to the hover position
-                    let hovered_width = plot_pos.x.round() as usize;
-                    
-                    // Find the closest actual width that was tested
-                    if let Some((nearest_width, _)) = analysis.width_scores
-                        .iter()
-                        .min_by_key(|(w, _)| (*w as i32 - hovered_width as i32).abs())
-                    {
-                        hover_width = Some(*nearest_width);
-                        
-                        if plot_response.response.clicked() {
-                            apply_width = Some(*nearest_width);
-                        }
+                }
+            });
+            
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button(tr("📁 Remap Folder..."))
+                    .on_hover_text(tr("Pick the folder the files are in now; they are matched by name"))
+                    .clicked()
+                {
+                    remap_folder = true;
+                }
+                if ui.button(tr("Ignore")).clicked() {
+                    app.missing_files.clear();
+                }
+            });
+        });
+    
+    if !keep_open {
+        app.missing_files.clear();
+    }
+    if let Some(index) = locate {
+        app.locate_missing_file(index);
+    }
+    if remap_folder {
+        app.remap_missing_folder();
+    }
+}
+
+pub fn render_report_window(app: &mut BitApp, ctx: &egui::Context) {
+    if !app.show_report {
+        return;
+    }
+    
+    let mut export = false;
+    let mut keep_open = true;
+    let mut jump = None;
+    
+    egui::Window::new(tr("📰 HTML Report"))
+        .open(&mut keep_open)
+        .default_width(420.0)
+        .resizable(true)
+        .show(ctx, |ui| {
+            ui.label(tr("A self-contained page with the column documentation, the operation chain, byte view snapshots and pattern matches"));
+            ui.separator();
+            
+            ui.horizontal(|ui| {
+                ui.label(tr("Title:"));
+                ui.text_edit_singleline(&mut app.report_title);
+            });
+            
+            ui.add_space(4.0);
+            ui.label(format!("Snapshots ({})", app.report_regions.len()));
+            let selection = app.viewer.selection;
+            if ui.add_enabled(selection.is_some(), egui::Button::new(tr("➕ Add Selection")))
+                .on_disabled_hover_text(tr("Shift-drag in the bit viewer to select a region"))
+                .clicked()
+                && let Some(region) = selection
+            {
+                app.report_regions.push(region);
+            }
+            
+            let mut to_remove = None;
+            for (index, &(start, end)) in app.report_regions.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    if ui.link(format!("Bits {}..{} ({} bytes)", start, end, (end - start).div_ceil(8))).clicked() {
+                        jump = Some((start, end - start));
+                    }
+                    if ui.small_button("❌").clicked() {
+                        to_remove = Some(index);
+                    }
+                });
+            }
+            if let Some(index) = to_remove {
+                app.report_regions.remove(index);
+            }
+            
+            ui.add_space(4.0);
+            let searched = app.patterns.iter().filter(|pattern| !pattern.matches.is_empty()).count();
+            ui.label(format!("{} column(s), {} operation(s), {} searched pattern(s)",
+                app.byte_viewer.config.columns.len(), app.operations.len(), searched));
+            
+            ui.separator();
+            if ui.button(tr("💾 Export HTML...")).clicked() {
+                export = true;
+            }
+        });
+    
+    app.show_report = keep_open;
+    
+    if let Some((start, len)) = jump {
+        app.jump_to_range(start, len, ctx.input(|i| i.time));
+    }
+    if export {
+        app.export_html_report();
+    }
+}
+
+pub fn render_xor_key_window(app: &mut BitApp, ctx: &egui::Context) {
+    use crate::analysis::MAX_XOR_KEY_LENGTH;
+    
+    if !app.show_xor_key {
+        return;
+    }
+    
+    let mut run_search = false;
+    let mut add_operation: Option<usize> = None;
+    let mut keep_open = true;
+    
+    egui::Window::new(tr("🔑 XOR Key Recovery"))
+        .open(&mut keep_open)
+        .default_width(600.0)
+        .default_height(450.0)
+        .resizable(true)
+        .show(ctx, |ui| {
+            ui.label(tr("Guesses repeating XOR keys by frequency analysis, assuming the plaintext is ASCII text"));
+            ui.separator();
+            
+            ui.horizontal(|ui| {
+                ui.label(tr("Key Length:"));
+                ui.add(egui::DragValue::new(&mut app.xor_min_key_len).range(1..=MAX_XOR_KEY_LENGTH));
+                ui.label(tr("to"));
+                ui.add(egui::DragValue::new(&mut app.xor_max_key_len).range(1..=MAX_XOR_KEY_LENGTH));
+                ui.label(tr("bytes"));
+                app.xor_max_key_len = app.xor_max_key_len.max(app.xor_min_key_len);
+            });
+            
+            render_analysis_scope(app, ui);
+            
+            if ui.button(tr("🔍 Find Keys")).clicked() {
+                run_search = true;
+            }
+            
+            ui.separator();
+            
+            let Some(results) = &app.xor_results else {
+                ui.label(tr("Click 'Find Keys' to analyze the current data"));
+                return;
+            };
+            if results.is_empty() {
+                ui.label(tr("Not enough data for these key lengths"));
+                return;
+            }
+            
+            egui::ScrollArea::vertical()
+                .auto_shrink([false, false])
+                .show(ui, |ui| {
+                    for (index, candidate) in results.iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            if ui.button(tr("➕ XOR")).on_hover_text(tr("Add an XOR With Key operation")).clicked() {
+                                add_operation = Some(index);
+                            }
+                            
+                            let key = match candidate.key_ascii() {
+                                Some(text) => format!("{} \"{}\"", candidate.key_hex(), text),
+                                None => candidate.key_hex(),
+                            };
+                            ui.label(egui::RichText::new(format!("{:>2} B  {:>5.2}  {}", candidate.key.len(), candidate.score, key)).monospace())
+                                .on_hover_text(format!("Decrypted: {}", candidate.preview));
+                        });
+                        ui.weak(egui::RichText::new(&candidate.preview).monospace());
                     }
+                });
+        });
+    
+    app.show_xor_key = keep_open;
+    
+    if run_search {
+        app.run_xor_key_search();
+    }
+    if let Some(index) = add_operation {
+        app.add_xor_key_operation(index);
+    }
+}
+
+pub fn render_batch_window(app: &mut BitApp, ctx: &egui::Context) {
+    use egui_extras::{Column, TableBuilder};
+    
+    if !app.show_batch {
+        return;
+    }
+    
+    let mut start_batch = false;
+    let mut export_csv = false;
+    let mut keep_open = true;
+    
+    egui::Window::new(tr("🗂 Batch Report"))
+        .open(&mut keep_open)
+        .default_width(800.0)
+        .default_height(450.0)
+        .resizable(true)
+        .show(ctx, |ui| {
+            ui.label(tr("Applies this worksheet's operations, patterns, frame width range and framer sync word to every file in a folder"));
+            ui.separator();
+            
+            ui.horizontal(|ui| {
+                let running = app.batch_receiver.is_some();
+                if ui.add_enabled(!running, egui::Button::new(tr("📁 Choose Folder..."))).clicked() {
+                    start_batch = true;
                 }
-                
-                // Show hover feedback
-                if let Some(width) = hover_width {
-                    let score = analysis.width_scores
-                        .iter()
-                        .find(|(w, _)| *w == width)
-                        .map(|(_, s)| *s)
-                        .unwrap_or(0.0);
-                    ui.label(format!("🖱️ Hovering: Width {} (score: {:.6}) - Click to apply", width, score));
+                if running {
+                    ui.spinner();
+                    ui.label(tr("Analyzing..."));
                 }
-                
-                ui.add_space(10.0);
-                    ui.separator();
-                    
-                    // Bit position consistency for best width
-                    ui.heading(format!("Bit Position Consistency (Width {})", analysis.best_width));
-                    ui.label("Shows which bit positions have consistent patterns");
-                    
-                    let best_width_idx = analysis.width_scores
-                        .iter()
-                        .position(|(w, _)| *w == analysis.best_width)
-                        .unwrap_or(0);
-                    
-                    if best_width_idx < analysis.bit_position_patterns.len() {
-                        let bit_patterns = &analysis.bit_position_patterns[best_width_idx];
-                        
-                        Plot::new("bit_position_heatmap")
-                            .view_aspect(3.0)
-                            .legend(egui_plot::Legend::default())
-                            .label_formatter(|name, value| {
-                                if !name.is_empty() {
-                                    format!("{}\nBit Position: {}\nConsistency: {:.4}", 
-                                        name, value.x as usize, value.y)
-                                } else {
-                                    format!("Bit Position: {}\nConsistency: {:.4}", 
-                                        value.x as usize, value.y)
-                                }
-                            })
-                            .show(ui, |plot_ui| {
-                                // Create bars for each bit position
-                                let bars: Vec<_> = bit_patterns
-                                    .iter()
-                                    .enumerate()
-                                    .map(|(pos, &score)| {
-                                        Bar::new(pos as f64, score)
-                                            .width(0.8)
-                                    })
-                                    .collect();
-                                
-                                plot_ui.bar_chart(BarChart::new("bit_positions", bars));
-                            });
-                    }
-                    
-                    ui.add_space(10.0);
-                    ui.separator();
-                    
-                    // Top 100 candidate widths in a scrollable, sortable table
-                    ui.heading("Candidate Widths");
-                    
-                    let mut sorted_widths = analysis.width_scores.clone();
-                    
-                    // Apply current sort
-                    if sort_by_score {
-                        sorted_widths.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-                    } else {
-                        sorted_widths.sort_by_key(|(w, _)| *w);
+                if app.batch_rows.is_some() && ui.button(tr("💾 Export CSV...")).clicked() {
+                    export_csv = true;
+                }
+            });
+            if let Some(folder) = &app.batch_folder {
+                ui.label(format!("Folder: {}", folder.display()));
+            }
+            
+            let Some(rows) = &app.batch_rows else {
+                return;
+            };
+            ui.separator();
+            
+            let pattern_names = &app.batch_pattern_names;
+            let mut table = TableBuilder::new(ui)
+                .striped(true)
+                .resizable(true)
+                .column(Column::auto().at_least(160.0))
+                .column(Column::auto());
+            for _ in pattern_names {
+                table = table.column(Column::auto());
+            }
+            table
+                .column(Column::auto())
+                .column(Column::auto())
+                .column(Column::remainder())
+                .header(20.0, |mut header| {
+                    header.col(|ui| { ui.strong(tr("File")); });
+                    header.col(|ui| { ui.strong(tr("Bytes")); });
+                    for name in pattern_names {
+                        header.col(|ui| { ui.strong(name); });
                     }
-                    
-                    use egui_extras::{TableBuilder, Column};
-                    
-                    TableBuilder::new(ui)
-                        .striped(true)
-                        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
-                        .column(Column::auto().at_least(80.0))  // Width
-                        .column(Column::remainder())             // Score
-                        .header(25.0, |mut header| {
-                            header.col(|ui| {
-                                if ui.selectable_label(!sort_by_score, "Width").clicked() {
-                                    sort_by_score = false;
-                                }
-                            });
-                            header.col(|ui| {
-                                if ui.selectable_label(sort_by_score, "Score").clicked() {
-                                    sort_by_score = true;
-                                }
-                            });
-                        })
-                        .body(|body| {
-                            body.rows(20.0, sorted_widths.len().min(100), |mut row| {
-                                let row_idx = row.index();
-                                let (width, score) = sorted_widths[row_idx];
-                                let is_selected = selected_width == Some(width);
-                                
-                                row.col(|ui| {
-                                    let (rect, response) = ui.allocate_exact_size(
-                                        ui.available_size(),
-                                        egui::Sense::click()
-                                    );
-                                    
-                                    if is_selected {
-                                        ui.painter().rect_filled(rect, 0.0, egui::Color32::from_rgb(60, 100, 180));
-                                    }
-                                    
-                                    ui.painter().text(
-                                        rect.left_center() + egui::vec2(5.0, 0.0),
-                                        egui::Align2::LEFT_CENTER,
-                                        format!("{}", width),
-                                        egui::FontId::default(),
-                                        if is_selected { egui::Color32::WHITE } else { ui.visuals().text_color() }
-                                    );
-                                    
-                                    if response.clicked() {
-                                        apply_width = Some(width);
-                                        selected_width = Some(width);
-                                    }
-                                });
-                                
-                                row.col(|ui| {
-                                    let (rect, response) = ui.allocate_exact_size(
-                                        ui.available_size(),
-                                        egui::Sense::click()
-                                    );
-                                    
-                                    if is_selected {
-                                        ui.painter().rect_filled(rect, 0.0, egui::Color32::from_rgb(60, 100, 180));
-                                    }
-                                    
-                                    ui.painter().text(
-                                        rect.left_center() + egui::vec2(5.0, 0.0),
-                                        egui::Align2::LEFT_CENTER,
-                                        format!("{:.6}", score),
-                                        egui::FontId::default(),
-                                        if is_selected { egui::Color32::WHITE } else { ui.visuals().text_color() }
-                                    );
-                                    
-                                    if response.clicked() {
-                                        apply_width = Some(width);
-                                        selected_width = Some(width);
-                                    }
-                                });
+                    header.col(|ui| { ui.strong(tr("Best Width")); });
+                    header.col(|ui| { ui.strong(tr("Frames")); });
+                    header.col(|ui| { ui.strong(tr("CRC Pass")); });
+                })
+                .body(|body| {
+                    body.rows(18.0, rows.len(), |mut table_row| {
+                        let row = &rows[table_row.index()];
+                        let file_name = row.path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+                        table_row.col(|ui| {
+                            let label = ui.label(&file_name);
+                            if let Some(error) = &row.error {
+                                label.on_hover_text(error);
+                            }
+                        });
+                        table_row.col(|ui| { ui.label(row.size_bytes.to_string()); });
+                        for i in 0..pattern_names.len() {
+                            table_row.col(|ui| {
+                                ui.label(row.match_counts.get(i).map(|count| count.to_string()).unwrap_or_default());
                             });
+                        }
+                        table_row.col(|ui| {
+                            ui.label(row.best_width.map(|width| width.to_string()).unwrap_or_else(|| "-".to_string()));
                         });
-                } else {
-                    ui.label("Click 'Analyze' to detect frame width");
-                }
-            });
+                        table_row.col(|ui| { ui.label(row.frames.to_string()); });
+                        table_row.col(|ui| {
+                            match (&row.error, row.crc_pass_rate) {
+                                (Some(_), _) => ui.colored_label(egui::Color32::from_rgb(220, 80, 80), tr("error")),
+                                (None, Some(rate)) => ui.label(format!("{:.1}%", rate * 100.0)),
+                                (None, None) => ui.label("-"),
+                            };
+                        });
+                    });
+                });
+        });
     
-    // Update app state from window
-    app.show_frame_width_finder = keep_open;
-    app.frame_width_min = frame_width_min;
-    app.frame_width_max = frame_width_max;
-    app.frame_width_delta = frame_width_delta;
-    app.frame_width_sort_by_score = sort_by_score;
-    app.frame_width_selected = selected_width;
+    app.show_batch = keep_open;
     
-    // Run analysis if requested
-    if run_analysis {
-        app.run_frame_width_analysis();
+    if start_batch {
+        app.start_batch_report();
+    }
+    if export_csv {
+        app.export_batch_csv();
+    }
+}
+
+/// How long a new error or warning stays in the corner of the window
+const TOAST_SECONDS: f32 = 6.0;
+
+fn notification_color(level: NotificationLevel) -> egui::Color32 {
+    match level {
+        NotificationLevel::Error => egui::Color32::from_rgb(220, 80, 80),
+        NotificationLevel::Warning => egui::Color32::from_rgb(220, 170, 60),
+    }
+}
+
+/// "12 s ago", "3 min ago" and so on
+fn notification_age(notification: &Notification) -> String {
+    let seconds = notification.time.elapsed().as_secs();
+    if seconds < 60 {
+        format!("{} s ago", seconds)
+    } else if seconds < 3600 {
+        format!("{} min ago", seconds / 60)
+    } else {
+        format!("{} h ago", seconds / 3600)
+    }
+}
+
+fn notification_text(notification: &Notification) -> String {
+    if notification.count > 1 {
+        format!("{} {} (×{})", notification.level.icon(), notification.message, notification.count)
+    } else {
+        format!("{} {}", notification.level.icon(), notification.message)
+    }
+}
+
+pub fn render_notification_toasts(app: &mut BitApp, ctx: &egui::Context) {
+    if app.show_notifications {
+        return;
+    }
+    let within = std::time::Duration::from_secs_f32(TOAST_SECONDS);
+    let toasts: Vec<Notification> = app.notifications.recent(within).take(3).cloned().collect();
+    if toasts.is_empty() {
+        return;
     }
     
-    // Apply width after window to avoid borrow issues
-    if let Some(width) = apply_width {
-        app.viewer.frame_length = width;
-        app.update_viewer();
+    let mut dismiss = None;
+    let mut open_center = false;
+    egui::Area::new(egui::Id::new("notification_toasts"))
+        .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-12.0, -12.0))
+        .order(egui::Order::Foreground)
+        .show(ctx, |ui| {
+            for notification in &toasts {
+                egui::Frame::popup(ui.style())
+                    .stroke(egui::Stroke::new(1.0, notification_color(notification.level)))
+                    .show(ui, |ui| {
+                        ui.set_max_width(360.0);
+                        ui.horizontal(|ui| {
+                            let text = egui::RichText::new(notification_text(notification))
+                                .color(notification_color(notification.level));
+                            if ui.add(egui::Label::new(text).wrap().sense(egui::Sense::click()))
+                                .on_hover_text(tr("Open the notification center"))
+                                .clicked()
+                            {
+                                open_center = true;
+                            }
+                            if ui.small_button("✖").on_hover_text(tr("Dismiss")).clicked() {
+                                dismiss = Some(notification.id);
+                            }
+                        });
+                    });
+            }
+        });
+    
+    if let Some(id) = dismiss {
+        app.notifications.dismiss(id);
+    }
+    if open_center {
+        app.show_notifications = true;
+    }
+    // Repaint when the oldest toast is due to disappear
+    if let Some(oldest) = toasts.last() {
+        let remaining = within.saturating_sub(oldest.time.elapsed());
+        ctx.request_repaint_after(remaining);
     }
 }
 
+pub fn render_notifications_window(app: &mut BitApp, ctx: &egui::Context) {
+    if !app.show_notifications {
+        return;
+    }
+    
+    let mut dismiss = None;
+    let mut clear = false;
+    let mut keep_open = true;
+    egui::Window::new(tr("🔔 Notifications"))
+        .open(&mut keep_open)
+        .default_width(460.0)
+        .default_height(320.0)
+        .resizable(true)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(format!("{} {}, {} {}", app.notifications.error_count(), tr("errors"),
+                    app.notifications.len() - app.notifications.error_count(), tr("warnings")));
+                if ui.add_enabled(!app.notifications.is_empty(), egui::Button::new(tr("🗑 Clear All"))).clicked() {
+                    clear = true;
+                }
+            });
+            ui.separator();
+            
+            if app.notifications.is_empty() {
+                ui.label(tr("No errors or warnings"));
+                return;
+            }
+            egui::ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+                for notification in app.notifications.items().iter().rev() {
+                    ui.horizontal(|ui| {
+                        if ui.small_button("✖").on_hover_text(tr("Dismiss")).clicked() {
+                            dismiss = Some(notification.id);
+                        }
+                        ui.weak(notification_age(notification));
+                        ui.add(egui::Label::new(
+                            egui::RichText::new(notification_text(notification)).color(notification_color(notification.level)),
+                        ).wrap());
+                    });
+                }
+            });
+        });
+    
+    if let Some(id) = dismiss {
+        app.notifications.dismiss(id);
+    }
+    if clear {
+        app.notifications.clear();
+    }
+    if !keep_open {
+        app.show_notifications = false;
+    }
+}
@@ -1,3 +0,0 @@
-pub mod math_eval;
-
-pub use math_eval::eval_expression;
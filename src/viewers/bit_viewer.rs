@@ -1,18 +1,57 @@
+use crate::analysis::{score_width, MatchIndex, Pattern};
+use crate::i18n::tr;
+use crate::storage::MappedBits;
+use super::{rgb, rgba, BitColors, GapMarkers};
+pub use bit_core::view::BitShape;
 use bitvec::prelude::*;
 use egui::{Color32, Pos2, Rect, Sense, Stroke, Vec2};
-use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::sync::Arc;
 
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
-pub enum BitShape {
-    Square,
-    Circle,
-    Octagon,
-}
+/// Width of the minimap strip beside the bit grid, in points
+const MINIMAP_WIDTH: f32 = 48.0;
+/// Gap between the bit grid and the minimap
+const MINIMAP_GAP: f32 = 4.0;
+/// Maximum number of frame columns the minimap resolves
+const MINIMAP_COLUMNS: usize = 24;
+/// Height of one minimap cell, in points
+const MINIMAP_CELL_HEIGHT: f32 = 2.0;
+/// Rows sampled per minimap cell, so huge streams stay cheap to summarize
+const MINIMAP_SAMPLE_ROWS: usize = 32;
+/// Height of the column statistics bar drawn over the top of the grid
+const COLUMN_STATS_HEIGHT: f32 = 32.0;
+/// Maximum number of bits sampled for column statistics
+const COLUMN_STATS_MAX_BITS: usize = 64 * 1024 * 1024;
+/// Consistency above which a column is drawn as constant (flag/sync candidate)
+const COLUMN_STATS_CONSTANT: f64 = 0.9;
+/// Bit size below which the grid is drawn as a texture instead of one shape per bit
+const TEXTURE_BIT_SIZE: f32 = 4.0;
+/// Smallest bit size zooming out reaches; below one point each texel samples several bits
+const MIN_BIT_SIZE: f32 = 0.05;
+/// Largest bit size zooming in reaches
+const MAX_BIT_SIZE: f32 = 100.0;
+/// Bit size shown as 100% zoom
+const DEFAULT_BIT_SIZE: f32 = 10.0;
+/// Space kept free past the grid so the scrollbars don't cover it
+const CONTENT_PADDING: f32 = 20.0;
+/// Height of the column ruler above the grid
+const RULER_HEIGHT: f32 = 16.0;
+/// Minimum distance between ruler labels, in points
+const RULER_LABEL_SPACING: f32 = 28.0;
+/// Font size of row offsets and ruler labels
+const OFFSET_FONT_SIZE: f32 = 10.0;
+/// Width of the row density gutter right of the grid
+const DENSITY_WIDTH: f32 = 24.0;
+/// Bits counted per density bar, so bars over long frames stay cheap
+const DENSITY_MAX_BITS: usize = 64 * 1024;
 
 pub struct BitViewer {
     pub bits: BitVec<u8, Msb0>,
+    /// Memory-mapped file shown instead of `bits` when set
+    pub mapped: Option<Arc<MappedBits>>,
     pub frame_length: usize,
+    /// Frames per super-frame, marked by a line before each one's first frame; 0 for none
+    pub super_frame_length: usize,
     pub bit_size: f32,
     pub bit_spacing: f32,
     pub shape: BitShape,
@@ -23,13 +62,123 @@ pub struct BitViewer {
     pub thick_grid_spacing_vertical: f32,
     pub highlighted_bits: HashSet<usize>,
     pub jump_to_bit: Option<usize>,
+    pub flash: Option<FlashHighlight>,
+    /// Custom colors; `None` follows the current egui theme
+    pub colors: Option<BitColors>,
+    pub show_minimap: bool,
+    minimap: Option<MinimapCache>,
+    pub show_column_stats: bool,
+    column_stats: Option<ColumnStats>,
+    /// Label each row with its starting offset and each column with its index
+    pub show_offsets: bool,
+    /// Draw a bar beside each row showing its fraction of ones
+    pub show_row_density: bool,
+    /// Clicking a bit reports it through `take_clicked_bit` instead of doing nothing
+    pub edit_mode: bool,
+    clicked_bit: Option<usize>,
+    /// Draw each frame as a column instead of a row, so bit positions of successive frames line up horizontally
+    pub transposed: bool,
+    /// Where capture bursts start in the shown data
+    pub gaps: GapMarkers,
+    /// Line code violations in the shown data as (bit, what went wrong); sorted by bit
+    pub violation_markers: Vec<(usize, &'static str)>,
+    /// How sure each shown bit is, 0 to 255, drawn as brightness; see `set_confidence`
+    confidence: Option<Arc<Vec<u8>>>,
+    /// Bits selected by shift-dragging, as start..end (end exclusive)
+    pub selection: Option<(usize, usize)>,
+    selection_anchor: Option<usize>,
+    /// Visible part of the grid when zoomed out far enough to draw it as a texture
+    texture: Option<GridTexture>,
+    /// Bumped whenever the displayed bits change, so the texture knows to redraw
+    data_generation: u64,
+    // Scroll position and viewport size from the last frame, used to decide horizontal jumps
+    last_scroll_offset: Vec2,
+    last_view_size: Vec2,
+    /// Scroll offset to restore the next time the grid is shown
+    restore_scroll: Option<Vec2>,
+}
+
+/// Per-column statistics of the stream at one frame length
+struct ColumnStats {
+    frame_length: usize,
+    /// Fraction of ones in each column
+    ones_density: Vec<f32>,
+    /// Per-column consistency from `score_width` (1.0 = constant)
+    consistency: Vec<f64>,
+}
+
+/// Rows and columns of the grid drawn in the last frame
+type VisibleCells = (std::ops::Range<usize>, std::ops::Range<usize>);
+
+/// Uploaded texture of the visible grid, with what it was drawn from
+struct GridTexture {
+    handle: egui::TextureHandle,
+    key: GridTextureKey,
+}
+
+/// Everything a grid texture depends on; the texture is redrawn when any of it changes
+#[derive(PartialEq)]
+struct GridTextureKey {
+    data_generation: u64,
+    frame_length: usize,
+    transposed: bool,
+    rows: std::ops::Range<usize>,
+    cols: std::ops::Range<usize>,
+    size: [usize; 2],
+    colors: [Color32; 4],
+    highlights: usize,
+    matches: u64,
+    selection: Option<(usize, usize)>,
+}
+
+/// A short-lived highlight over a bit range, e.g. to show where a "Go to" landed
+#[derive(Debug, Clone, Copy)]
+pub struct FlashHighlight {
+    pub start: usize,
+    pub len: usize,
+    pub started_at: f64,
+}
+
+impl FlashHighlight {
+    /// How long the highlight takes to fade out, in seconds
+    pub const DURATION: f64 = 1.5;
+
+    pub fn new(start: usize, len: usize, now: f64) -> Self {
+        Self { start, len, started_at: now }
+    }
+
+    /// Whether the bit range [start, end) overlaps the highlight
+    pub fn overlaps(&self, start: usize, end: usize) -> bool {
+        start < self.start + self.len && end > self.start
+    }
+
+    /// Overlay color at time `now`, fading out; None once the flash is over
+    pub fn color(&self, now: f64) -> Option<Color32> {
+        let elapsed = now - self.started_at;
+        if !(0.0..Self::DURATION).contains(&elapsed) {
+            return None;
+        }
+        let alpha = (1.0 - elapsed / Self::DURATION) * 200.0;
+        Some(Color32::from_rgba_unmultiplied(255, 140, 0, alpha as u8))
+    }
+}
+
+/// Downsampled ones-density of the whole stream, rebuilt when the data or layout changes
+struct MinimapCache {
+    frame_length: usize,
+    bucket_rows: usize,
+    columns: usize,
+    /// Row-major `bucket_rows` x `columns` densities; None where the stream has no bits
+    densities: Vec<Option<f32>>,
 }
 
 impl BitViewer {
     pub fn new() -> Self {
         Self {
             bits: BitVec::new(),
+            mapped: None,
             frame_length: 64,
+            super_frame_length: 0,
             bit_size: 10.0,
             bit_spacing: 0.0,
             shape: BitShape::Square,
@@ -40,11 +189,75 @@ impl BitViewer {
             thick_grid_spacing_vertical: 3.0,
             highlighted_bits: HashSet::new(),
             jump_to_bit: None,
+            flash: None,
+            colors: None,
+            show_minimap: true,
+            minimap: None,
+            show_column_stats: false,
+            column_stats: None,
+            show_offsets: false,
+            show_row_density: false,
+            edit_mode: false,
+            clicked_bit: None,
+            transposed: false,
+            gaps: GapMarkers::default(),
+            violation_markers: Vec::new(),
+            confidence: None,
+            selection: None,
+            selection_anchor: None,
+            texture: None,
+            data_generation: 0,
+            last_scroll_offset: Vec2::ZERO,
+            last_view_size: Vec2::ZERO,
+            restore_scroll: None,
         }
     }
 
     pub fn set_bits(&mut self, bits: BitVec<u8, Msb0>) {
         self.bits = bits;
+        self.mapped = None;
+        self.minimap = None;
+        self.column_stats = None;
+        self.data_generation += 1;
+    }
+
+    /// Draw each bit's confidence from soft decisions as its brightness, doubtful bits fading
+    /// into the background. `confidence` must line up with the shown bits.
+    pub fn set_confidence(&mut self, confidence: Option<Arc<Vec<u8>>>) {
+        let unchanged = match (&self.confidence, &confidence) {
+            (Some(old), Some(new)) => Arc::ptr_eq(old, new),
+            (None, None) => true,
+            _ => false,
+        };
+        if !unchanged {
+            self.confidence = confidence;
+            self.data_generation += 1;
+        }
+    }
+
+    /// `color` of bit `bit_index`, dimmed towards `background` by how doubtful the bit is
+    fn shade(&self, color: Color32, bit_index: usize, background: Color32) -> Color32 {
+        match self.confidence.as_ref().and_then(|confidence| confidence.get(bit_index)) {
+            Some(&confidence) => background.lerp_to_gamma(color, 0.25 + 0.75 * confidence as f32 / 255.0),
+            None => color,
+        }
+    }
+
+    /// Show a memory-mapped file; rows are read from the mapping as they scroll into view
+    pub fn set_mapped(&mut self, mapped: Arc<MappedBits>) {
+        self.bits = BitVec::new();
+        self.mapped = Some(mapped);
+        self.minimap = None;
+        self.column_stats = None;
+        self.data_generation += 1;
+    }
+
+    /// The bits being displayed, whichever storage backs them
+    pub fn source_bits(&self) -> &BitSlice<u8, Msb0> {
+        match &self.mapped {
+            Some(mapped) => mapped.bits(),
+            None => &self.bits,
+        }
     }
     
     pub fn clear_highlights(&mut self) {
@@ -62,31 +275,71 @@ impl BitViewer {
         }
     }
     
+    /// Where the grid is scrolled to, or will be once it's next shown
+    pub fn scroll_offset(&self) -> Vec2 {
+        self.restore_scroll.unwrap_or(self.last_scroll_offset)
+    }
+
+    /// Scroll the grid to `offset` the next time it's shown
+    pub fn set_scroll_offset(&mut self, offset: Vec2) {
+        self.restore_scroll = Some(offset);
+    }
+
     pub fn jump_to_position(&mut self, bit_position: usize) {
         self.jump_to_bit = Some(bit_position);
     }
 
-    pub fn show(&mut self, ui: &mut egui::Ui) {
+    /// The bit clicked in edit mode since the last call, if any
+    pub fn take_clicked_bit(&mut self) -> Option<usize> {
+        self.clicked_bit.take()
+    }
+
+    /// Briefly highlight `len` bits starting at `start`
+    pub fn flash_range(&mut self, start: usize, len: usize, now: f64) {
+        self.flash = Some(FlashHighlight::new(start, len, now));
+    }
+
+    /// Rows and columns of the bit grid; transposed, there is one row per bit position in a frame
+    fn grid_shape(&self) -> (usize, usize) {
+        let frames = self.source_bits().len().div_ceil(self.frame_length);
+        if self.transposed { (self.frame_length, frames) } else { (frames, self.frame_length) }
+    }
+
+    /// Index of the bit drawn at grid cell (row, col)
+    fn bit_at(&self, row: usize, col: usize) -> usize {
+        if self.transposed { col * self.frame_length + row } else { row * self.frame_length + col }
+    }
+
+    /// Grid cell (row, col) where `bit_index` is drawn
+    fn grid_cell(&self, bit_index: usize) -> (usize, usize) {
+        let (frame, offset) = (bit_index / self.frame_length, bit_index % self.frame_length);
+        if self.transposed { (offset, frame) } else { (frame, offset) }
+    }
+
+    /// Shows the grid, tinting bits inside the matches of `patterns`, looked up in `matches`
+    pub fn show(&mut self, ui: &mut egui::Ui, patterns: &[Pattern], matches: &MatchIndex) {
         // Calculate total content size
-        let total_rows = (self.bits.len() + self.frame_length - 1) / self.frame_length;
-        let cell_size = self.bit_size + self.bit_spacing;
+        let (total_rows, grid_columns) = self.grid_shape();
+        // Zoomed far out, the grid is one texture: no gaps between bits, no grid lines or shapes
+        let texture_mode = self.bit_size < TEXTURE_BIT_SIZE;
+        let (bit_spacing, thick_interval_horizontal, thick_interval_vertical) = if texture_mode {
+            (0.0, 0, 0)
+        } else {
+            (self.bit_spacing, self.thick_grid_interval_horizontal, self.thick_grid_interval_vertical)
+        };
+        let (thick_spacing_horizontal, thick_spacing_vertical) = (self.thick_grid_spacing_horizontal, self.thick_grid_spacing_vertical);
+        let cell_size = self.bit_size + bit_spacing;
         // Add padding to prevent scrollbar from covering content
-        let padding = 20.0;
+        let padding = CONTENT_PADDING;
         
         // Calculate extra spacing from thick grid intervals
-        let extra_width_spacing = if self.thick_grid_interval_horizontal > 0 {
-            ((self.frame_length / self.thick_grid_interval_horizontal) as f32) * self.thick_grid_spacing_horizontal
-        } else {
-            0.0
-        };
+        let extra_width_spacing = grid_columns.checked_div(thick_interval_horizontal)
+            .map_or(0.0, |groups| groups as f32 * thick_spacing_horizontal);
         
-        let extra_height_spacing = if self.thick_grid_interval_vertical > 0 {
-            ((total_rows / self.thick_grid_interval_vertical) as f32) * self.thick_grid_spacing_vertical
-        } else {
-            0.0
-        };
+        let extra_height_spacing = total_rows.checked_div(thick_interval_vertical)
+            .map_or(0.0, |groups| groups as f32 * thick_spacing_vertical);
         
-        let content_width = (self.frame_length as f32) * cell_size + padding + extra_width_spacing;
+        let content_width = (grid_columns as f32) * cell_size + padding + extra_width_spacing;
         let content_height = (total_rows as f32) * cell_size + padding + extra_height_spacing;
 
         // Set scrollbar to always be expanded (no hover animation)
@@ -100,31 +353,133 @@ impl BitViewer {
             .auto_shrink([false, false])
             .scroll_bar_visibility(egui::scroll_area::ScrollBarVisibility::AlwaysVisible);
         
+        // Ctrl+wheel (or pinch) zooms, keeping the bit under the pointer in place
+        let zoom = ui.input(|i| i.zoom_delta());
+        if zoom != 1.0
+            && self.jump_to_bit.is_none()
+            && ui.rect_contains_pointer(ui.max_rect())
+            && let Some(pointer) = ui.input(|i| i.pointer.hover_pos())
+        {
+            let old_size = self.bit_size;
+            self.zoom_by(zoom);
+            let factor = self.bit_size / old_size;
+            let anchor = pointer - ui.max_rect().min;
+            let offset = (self.last_scroll_offset + anchor) * factor - anchor;
+            scroll_area = scroll_area.scroll_offset(offset.max(Vec2::ZERO));
+            ui.ctx().request_repaint();
+        }
+
+        if let Some(offset) = self.restore_scroll.take() {
+            scroll_area = scroll_area.scroll_offset(offset);
+        }
+
         // Handle jump to bit position
         if let Some(bit_pos) = self.jump_to_bit.take() {
-            let row = bit_pos / self.frame_length;
+            let (row, col) = self.grid_cell(bit_pos);
             let y_offset = (row as f32) * cell_size;
             scroll_area = scroll_area.vertical_scroll_offset(y_offset);
+
+            // Also scroll sideways if the target column is out of view
+            let x = (col as f32) * cell_size;
+            if x < self.last_scroll_offset.x || x + cell_size > self.last_scroll_offset.x + self.last_view_size.x {
+                scroll_area = scroll_area.horizontal_scroll_offset((x - self.last_view_size.x / 2.0).max(0.0));
+            }
+        }
+
+        let now = ui.input(|i| i.time);
+        let flash_color = self.flash.and_then(|flash| flash.color(now));
+        if flash_color.is_some() {
+            ui.ctx().request_repaint();
+        } else {
+            self.flash = None;
         }
 
-        scroll_area.show_viewport(ui, |ui, viewport| {
+        // Reserve a strip on the right for the minimap
+        let full_rect = ui.available_rect_before_wrap();
+        // The minimap summarizes rows of frames, which a transposed grid doesn't have
+        let minimap_rect = if self.show_minimap && !self.transposed && total_rows > 0 && full_rect.width() > MINIMAP_WIDTH * 4.0 {
+            Some(Rect::from_min_max(Pos2::new(full_rect.max.x - MINIMAP_WIDTH, full_rect.min.y), full_rect.max))
+        } else {
+            None
+        };
+        let view_rect = match minimap_rect {
+            Some(rect) => Rect::from_min_max(full_rect.min, Pos2::new(rect.min.x - MINIMAP_GAP, full_rect.max.y)),
+            None => full_rect,
+        };
+        // Row densities go in a gutter between the grid and the minimap
+        let (view_rect, density_rect) = if self.show_row_density && !self.transposed && total_rows > 0 && view_rect.width() > DENSITY_WIDTH * 4.0 {
+            let split = view_rect.max.x - DENSITY_WIDTH;
+            (
+                Rect::from_min_max(view_rect.min, Pos2::new(split - MINIMAP_GAP, view_rect.max.y)),
+                Some(Rect::from_min_max(Pos2::new(split, view_rect.min.y), view_rect.max)),
+            )
+        } else {
+            (view_rect, None)
+        };
+
+        // Row offsets go in a gutter on the left and column indices in a ruler on top
+        let offset_font = egui::FontId::monospace(OFFSET_FONT_SIZE);
+        let label_color = ui.visuals().weak_text_color();
+        let (grid_rect, gutter_rect, ruler_rect) = if self.show_offsets && total_rows > 0 {
+            let widest = self.row_label(total_rows - 1);
+            let gutter_width = ui.painter().layout_no_wrap(widest, offset_font.clone(), label_color).size().x + 8.0;
+            let grid_rect = Rect::from_min_max(view_rect.min + Vec2::new(gutter_width, RULER_HEIGHT), view_rect.max);
+            let gutter_rect = Rect::from_min_max(Pos2::new(view_rect.min.x, grid_rect.min.y), Pos2::new(grid_rect.min.x, view_rect.max.y));
+            let ruler_rect = Rect::from_min_max(Pos2::new(grid_rect.min.x, view_rect.min.y), Pos2::new(view_rect.max.x, grid_rect.min.y));
+            (grid_rect, Some(gutter_rect), Some(ruler_rect))
+        } else {
+            (view_rect, None, None)
+        };
+        let mut view_ui = ui.new_child(egui::UiBuilder::new().max_rect(grid_rect));
+
+        let show_column_stats = self.show_column_stats && !self.transposed;
+        if show_column_stats {
+            self.update_column_stats();
+        }
+        let column_stats = self.column_stats.as_ref().filter(|_| show_column_stats);
+
+        let colors = self.colors.clone().unwrap_or_else(|| BitColors::for_dark_mode(ui.visuals().dark_mode));
+        let (zero_color, one_color) = (rgb(colors.zero), rgb(colors.one));
+        let grid_color = rgb(colors.grid);
+        let highlight_color = rgba(colors.highlight);
+
+        let selection_color = ui.visuals().selection.bg_fill.gamma_multiply(0.6);
+
+        // Shift-dragging selects a range; otherwise drags are left to the scroll area
+        let selecting = !self.edit_mode && (self.selection_anchor.is_some() || ui.input(|i| i.modifiers.shift));
+        let sense = if self.edit_mode {
+            Sense::click()
+        } else if selecting {
+            Sense::drag()
+        } else {
+            Sense::hover()
+        };
+
+        let mut texture = self.texture.take();
+        let bits = self.source_bits();
+        let mut clicked_bit = None;
+        let mut selection = self.selection;
+        let mut selection_anchor = self.selection_anchor;
+
+        // Helper function to calculate position with spacing
+        let calc_position = |index: usize, interval: usize, spacing: f32| -> f32 {
+            if interval > 0 && index > 0 {
+                (index as f32) * cell_size + (index / interval) as f32 * spacing
+            } else {
+                (index as f32) * cell_size
+            }
+        };
+
+        let output = scroll_area.show_viewport(&mut view_ui, |ui, viewport| {
                 // Set the content size
                 ui.set_width(content_width);
                 ui.set_height(content_height);
 
                 let (response, painter) = ui.allocate_painter(
                     Vec2::new(content_width, content_height),
-                    Sense::hover(),
+                    sense,
                 );
-
-                // Helper function to calculate position with spacing
-                let calc_position = |index: usize, interval: usize, spacing: f32| -> f32 {
-                    if interval > 0 && index > 0 {
-                        (index as f32) * cell_size + (index / interval) as f32 * spacing
-                    } else {
-                        (index as f32) * cell_size
-                    }
-                };
+                painter.rect_filled(viewport.translate(response.rect.min.to_vec2()), 0.0, rgb(colors.background));
 
                 // Binary search for start row
                 let start_row = if total_rows == 0 {
@@ -134,7 +489,7 @@ impl BitViewer {
                     let mut high = total_rows;
                     while low < high {
                         let mid = (low + high) / 2;
-                        let pos = calc_position(mid, self.thick_grid_interval_vertical, self.thick_grid_spacing_vertical);
+                        let pos = calc_position(mid, thick_interval_vertical, thick_spacing_vertical);
                         if pos < viewport.min.y - cell_size {
                             low = mid + 1;
                         } else {
@@ -150,7 +505,7 @@ impl BitViewer {
                 } else {
                     let mut row = start_row;
                     while row < total_rows {
-                        let pos = calc_position(row, self.thick_grid_interval_vertical, self.thick_grid_spacing_vertical);
+                        let pos = calc_position(row, thick_interval_vertical, thick_spacing_vertical);
                         if pos > viewport.max.y + cell_size {
                             break;
                         }
@@ -160,14 +515,14 @@ impl BitViewer {
                 };
 
                 // Binary search for start col
-                let start_col = if self.frame_length == 0 {
+                let start_col = if grid_columns == 0 {
                     0
                 } else {
                     let mut low = 0;
-                    let mut high = self.frame_length;
+                    let mut high = grid_columns;
                     while low < high {
                         let mid = (low + high) / 2;
-                        let pos = calc_position(mid, self.thick_grid_interval_horizontal, self.thick_grid_spacing_horizontal);
+                        let pos = calc_position(mid, thick_interval_horizontal, thick_spacing_horizontal);
                         if pos < viewport.min.x - cell_size {
                             low = mid + 1;
                         } else {
@@ -178,40 +533,86 @@ impl BitViewer {
                 };
 
                 // Find end col
-                let end_col = if self.frame_length == 0 {
+                let end_col = if grid_columns == 0 {
                     0
                 } else {
                     let mut col = start_col;
-                    while col < self.frame_length {
-                        let pos = calc_position(col, self.thick_grid_interval_horizontal, self.thick_grid_spacing_horizontal);
+                    while col < grid_columns {
+                        let pos = calc_position(col, thick_interval_horizontal, thick_spacing_horizontal);
                         if pos > viewport.max.x + cell_size {
                             break;
                         }
                         col += 1;
                     }
-                    col.min(self.frame_length)
+                    col.min(grid_columns)
                 };
 
+                if texture_mode && start_row < end_row && start_col < end_col {
+                    let key = GridTextureKey {
+                        data_generation: self.data_generation,
+                        frame_length: self.frame_length,
+                        transposed: self.transposed,
+                        rows: start_row..end_row,
+                        cols: start_col..end_col,
+                        // One texel per bit, or per point once bits are smaller than that
+                        size: [
+                            ((end_col - start_col) as f32 * self.bit_size.min(1.0)).ceil().max(1.0) as usize,
+                            ((end_row - start_row) as f32 * self.bit_size.min(1.0)).ceil().max(1.0) as usize,
+                        ],
+                        colors: [zero_color, one_color, highlight_color, rgb(colors.background)],
+                        highlights: self.highlighted_bits.len(),
+                        matches: matches.fingerprint(),
+                        selection,
+                    };
+                    if texture.as_ref().is_none_or(|texture| texture.key != key) {
+                        let image = self.grid_image(bits, &key, selection_color, patterns, matches, &colors);
+                        match &mut texture {
+                            Some(texture) => {
+                                texture.handle.set(image, egui::TextureOptions::NEAREST);
+                                texture.key = key;
+                            }
+                            None => {
+                                let handle = ui.ctx().load_texture("bit_viewer_grid", image, egui::TextureOptions::NEAREST);
+                                texture = Some(GridTexture { handle, key });
+                            }
+                        }
+                    }
+                    if let Some(texture) = &texture {
+                        let min = response.rect.min + Vec2::new(start_col as f32, start_row as f32) * cell_size;
+                        let max = response.rect.min + Vec2::new(end_col as f32, end_row as f32) * cell_size;
+                        painter.image(
+                            texture.handle.id(),
+                            Rect::from_min_max(min, max),
+                            Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0)),
+                            Color32::WHITE,
+                        );
+                    }
+                }
+
                 // Only render visible bits
                 for row in start_row..end_row {
+                    if texture_mode {
+                        break;
+                    }
                     for col in start_col..end_col {
-                        let bit_index = row * self.frame_length + col;
-                        if bit_index >= self.bits.len() {
+                        let bit_index = self.bit_at(row, col);
+                        if bit_index >= bits.len() {
                             break;
                         }
 
-                        let bit = self.bits[bit_index];
-                        let color = if bit { Color32::BLACK } else { Color32::WHITE };
+                        let bit = bits[bit_index];
+                        let color = self.shade(if bit { one_color } else { zero_color }, bit_index, rgb(colors.background));
+                        let color = tint_match(color, bit_index, patterns, matches, &colors);
 
                         // Calculate accumulated extra spacing for thick grid boundaries
-                        let accumulated_x_spacing = if self.thick_grid_interval_horizontal > 0 && col > 0 {
-                            (col / self.thick_grid_interval_horizontal) as f32 * self.thick_grid_spacing_horizontal
+                        let accumulated_x_spacing = if thick_interval_horizontal > 0 && col > 0 {
+                            (col / thick_interval_horizontal) as f32 * thick_spacing_horizontal
                         } else {
                             0.0
                         };
                         
-                        let accumulated_y_spacing = if self.thick_grid_interval_vertical > 0 && row > 0 {
-                            (row / self.thick_grid_interval_vertical) as f32 * self.thick_grid_spacing_vertical
+                        let accumulated_y_spacing = if thick_interval_vertical > 0 && row > 0 {
+                            (row / thick_interval_vertical) as f32 * thick_spacing_vertical
                         } else {
                             0.0
                         };
@@ -220,10 +621,10 @@ impl BitViewer {
                         let y = response.rect.min.y + (row as f32) * cell_size + accumulated_y_spacing;
 
                         // Determine if this bit is on a thick grid boundary
-                        let is_thick_horizontal = self.thick_grid_interval_horizontal > 0 
-                            && col % self.thick_grid_interval_horizontal == 0;
-                        let is_thick_vertical = self.thick_grid_interval_vertical > 0 
-                            && row % self.thick_grid_interval_vertical == 0;
+                        let is_thick_horizontal = thick_interval_horizontal > 0 
+                            && col % thick_interval_horizontal == 0;
+                        let is_thick_vertical = thick_interval_vertical > 0 
+                            && row % thick_interval_vertical == 0;
 
                         match self.shape {
                             BitShape::Square => {
@@ -235,7 +636,7 @@ impl BitViewer {
                                 
                                 // Draw highlight overlay if this bit is highlighted
                                 if self.highlighted_bits.contains(&bit_index) {
-                                    painter.rect_filled(rect, 0.0, Color32::from_rgba_unmultiplied(255, 255, 0, 150));
+                                    painter.rect_filled(rect, 0.0, highlight_color);
                                 }
                                 
                                 if self.show_grid {
@@ -248,22 +649,22 @@ impl BitViewer {
                                     // Left edge
                                     painter.line_segment(
                                         [rect.left_top(), rect.left_bottom()],
-                                        Stroke::new(left_width, Color32::GRAY),
+                                        Stroke::new(left_width, grid_color),
                                     );
                                     // Top edge
                                     painter.line_segment(
                                         [rect.left_top(), rect.right_top()],
-                                        Stroke::new(top_width, Color32::GRAY),
+                                        Stroke::new(top_width, grid_color),
                                     );
                                     // Right edge
                                     painter.line_segment(
                                         [rect.right_top(), rect.right_bottom()],
-                                        Stroke::new(right_width, Color32::GRAY),
+                                        Stroke::new(right_width, grid_color),
                                     );
                                     // Bottom edge
                                     painter.line_segment(
                                         [rect.left_bottom(), rect.right_bottom()],
-                                        Stroke::new(bottom_width, Color32::GRAY),
+                                        Stroke::new(bottom_width, grid_color),
                                     );
                                 }
                             }
@@ -276,7 +677,7 @@ impl BitViewer {
                                 
                                 // Draw highlight overlay if this bit is highlighted
                                 if self.highlighted_bits.contains(&bit_index) {
-                                    painter.circle_filled(center, self.bit_size / 2.0, Color32::from_rgba_unmultiplied(255, 255, 0, 150));
+                                    painter.circle_filled(center, self.bit_size / 2.0, highlight_color);
                                 }
                                 
                                 if self.show_grid {
@@ -284,7 +685,7 @@ impl BitViewer {
                                     painter.circle_stroke(
                                         center,
                                         self.bit_size / 2.0,
-                                        Stroke::new(1.0, Color32::GRAY),
+                                        Stroke::new(1.0, grid_color),
                                     );
                                 }
                             }
@@ -317,7 +718,7 @@ impl BitViewer {
                                 if self.highlighted_bits.contains(&bit_index) {
                                     painter.add(egui::Shape::convex_polygon(
                                         points.clone(),
-                                        Color32::from_rgba_unmultiplied(255, 255, 0, 150),
+                                        highlight_color,
                                         Stroke::NONE,
                                     ));
                                 }
@@ -327,25 +728,598 @@ impl BitViewer {
                                     painter.add(egui::Shape::convex_polygon(
                                         points,
                                         Color32::TRANSPARENT,
-                                        Stroke::new(1.0, Color32::GRAY),
+                                        Stroke::new(1.0, grid_color),
                                     ));
                                 }
                             }
                         }
+
+                        if selection.is_some_and(|(start, end)| bit_index >= start && bit_index < end) {
+                            let rect = Rect::from_min_size(Pos2::new(x, y), Vec2::splat(self.bit_size));
+                            painter.rect_filled(rect, 0.0, selection_color);
+                        }
+
+                        // A flashed range is scattered across a transposed grid, so mark its cells one by one
+                        if self.transposed
+                            && let (Some(flash), Some(color)) = (self.flash, flash_color)
+                            && flash.overlaps(bit_index, bit_index + 1)
+                        {
+                            let rect = Rect::from_min_size(Pos2::new(x, y), Vec2::splat(self.bit_size));
+                            painter.rect_filled(rect, 0.0, color.gamma_multiply(0.5));
+                        }
+                    }
+                }
+
+                // The visible bit nearest to a pointer position
+                let bit_at_pointer = |pos: Pos2| -> Option<usize> {
+                    let local = pos - response.rect.min;
+                    let col = (start_col..end_col)
+                        .find(|&col| local.x < calc_position(col, thick_interval_horizontal, thick_spacing_horizontal) + self.bit_size)
+                        .unwrap_or(end_col.checked_sub(1)?);
+                    let row = (start_row..end_row)
+                        .find(|&row| local.y < calc_position(row, thick_interval_vertical, thick_spacing_vertical) + self.bit_size)
+                        .unwrap_or(end_row.checked_sub(1)?);
+                    Some(self.bit_at(row, col).min(bits.len().checked_sub(1)?))
+                };
+
+                // In edit mode, find the bit under a click
+                if self.edit_mode {
+                    if response.hovered() {
+                        ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+                    }
+                    if response.clicked()
+                        && let Some(pos) = response.interact_pointer_pos()
+                    {
+                        let local = pos - response.rect.min;
+                        let col = (start_col..end_col).find(|&col| {
+                            let x = calc_position(col, thick_interval_horizontal, thick_spacing_horizontal);
+                            local.x >= x && local.x < x + self.bit_size
+                        });
+                        let row = (start_row..end_row).find(|&row| {
+                            let y = calc_position(row, thick_interval_vertical, thick_spacing_vertical);
+                            local.y >= y && local.y < y + self.bit_size
+                        });
+                        if let (Some(row), Some(col)) = (row, col) {
+                            clicked_bit = Some(self.bit_at(row, col)).filter(|&index| index < bits.len());
+                        }
+                    }
+                } else if selecting {
+                    ui.ctx().set_cursor_icon(egui::CursorIcon::Text);
+                    if let Some(bit) = response.interact_pointer_pos().and_then(bit_at_pointer) {
+                        if response.drag_started() {
+                            selection_anchor = Some(bit);
+                        }
+                        if let Some(anchor) = selection_anchor {
+                            selection = Some((anchor.min(bit), anchor.max(bit) + 1));
+                        }
+                    }
+                    if response.drag_stopped() {
+                        selection_anchor = None;
+                    }
+                }
+
+                // Outline the flashed range on each visible row it covers
+                if !self.transposed
+                    && let (Some(flash), Some(color)) = (self.flash, flash_color)
+                {
+                    let first_row = (flash.start / self.frame_length).max(start_row);
+                    let last_row = ((flash.start + flash.len).saturating_sub(1) / self.frame_length).min(end_row.saturating_sub(1));
+                    for row in first_row..=last_row {
+                        let row_start = row * self.frame_length;
+                        let first_col = flash.start.saturating_sub(row_start).min(self.frame_length - 1);
+                        let last_col = (flash.start + flash.len).saturating_sub(row_start + 1).min(self.frame_length - 1);
+                        let min = Pos2::new(
+                            response.rect.min.x + calc_position(first_col, thick_interval_horizontal, thick_spacing_horizontal),
+                            response.rect.min.y + calc_position(row, thick_interval_vertical, thick_spacing_vertical),
+                        );
+                        let max = Pos2::new(
+                            response.rect.min.x + calc_position(last_col, thick_interval_horizontal, thick_spacing_horizontal) + self.bit_size,
+                            min.y + self.bit_size,
+                        );
+                        let flash_rect = Rect::from_min_max(min, max).expand(1.0);
+                        painter.rect_filled(flash_rect, 0.0, color.gamma_multiply(0.5));
+                        painter.rect_stroke(flash_rect, 0.0, Stroke::new(2.0, color), egui::StrokeKind::Outside);
+                    }
+                }
+
+                // A line before the first frame of each super-frame: across the grid, or down it
+                // when transposed
+                if self.super_frame_length > 1 && start_row < end_row && start_col < end_col {
+                    let stroke = Stroke::new(2.0, Color32::from_rgb(255, 140, 0));
+                    let x_of = |col: usize| response.rect.min.x + calc_position(col, thick_interval_horizontal, thick_spacing_horizontal);
+                    let y_of = |row: usize| response.rect.min.y + calc_position(row, thick_interval_vertical, thick_spacing_vertical);
+                    let (left, right) = (x_of(start_col), x_of(end_col - 1) + self.bit_size);
+                    let (top, bottom) = (y_of(start_row), y_of(end_row - 1) + self.bit_size);
+                    let frames = if self.transposed { start_col..end_col } else { start_row..end_row };
+                    for frame in frames.filter(|&frame| frame > 0 && frame % self.super_frame_length == 0) {
+                        if self.transposed {
+                            let x = x_of(frame) - bit_spacing / 2.0;
+                            painter.line_segment([Pos2::new(x, top), Pos2::new(x, bottom)], stroke);
+                        } else {
+                            let y = y_of(frame) - bit_spacing / 2.0;
+                            painter.line_segment([Pos2::new(left, y), Pos2::new(right, y)], stroke);
+                        }
+                    }
+                }
+
+                // Set once a marker or the statistics bar has shown its own tooltip
+                let mut tooltip_shown = false;
+
+                // Mark where bursts start, with the silence before them on hover
+                if !self.gaps.starts.is_empty() {
+                    let visible = if self.transposed {
+                        self.gaps.within(0, usize::MAX)
+                    } else {
+                        self.gaps.within(self.bit_at(start_row, 0), self.bit_at(end_row, 0))
+                    };
+                    for &(bit, gap_secs) in visible {
+                        let (row, col) = self.grid_cell(bit);
+                        if !(start_row..end_row).contains(&row) || !(start_col..end_col).contains(&col) {
+                            continue;
+                        }
+                        let x = response.rect.min.x + calc_position(col, thick_interval_horizontal, thick_spacing_horizontal);
+                        let y = response.rect.min.y + calc_position(row, thick_interval_vertical, thick_spacing_vertical);
+                        let line = egui::Rangef::new(y, y + self.bit_size);
+                        let marker = self.gaps.paint(&painter, x, line, response.rect.x_range(), gap_secs);
+                        if response.hover_pos().is_some_and(|pos| marker.expand(2.0).contains(pos)) {
+                            response.clone().on_hover_text_at_pointer(GapMarkers::hover_text(bit, gap_secs));
+                            tooltip_shown = true;
+                        }
+                    }
+                }
+
+                // Mark the symbols a line code decode couldn't make sense of
+                if !self.violation_markers.is_empty() {
+                    let violation_color = Color32::from_rgb(230, 40, 40);
+                    let visible = if self.transposed {
+                        &self.violation_markers[..]
+                    } else {
+                        let first = self.bit_at(start_row, 0);
+                        let last = self.bit_at(end_row, 0);
+                        let from = self.violation_markers.partition_point(|&(bit, _)| bit < first);
+                        let to = self.violation_markers.partition_point(|&(bit, _)| bit < last);
+                        &self.violation_markers[from..to]
+                    };
+                    for &(bit, kind) in visible {
+                        let (row, col) = self.grid_cell(bit);
+                        if !(start_row..end_row).contains(&row) || !(start_col..end_col).contains(&col) {
+                            continue;
+                        }
+                        let x = response.rect.min.x + calc_position(col, thick_interval_horizontal, thick_spacing_horizontal);
+                        let y = response.rect.min.y + calc_position(row, thick_interval_vertical, thick_spacing_vertical);
+                        let marker = Rect::from_min_size(Pos2::new(x, y), Vec2::splat(self.bit_size)).expand(1.0);
+                        painter.rect_stroke(marker, 0.0, Stroke::new(2.0, violation_color), egui::StrokeKind::Outside);
+                        if !tooltip_shown && response.hover_pos().is_some_and(|pos| marker.expand(2.0).contains(pos)) {
+                            response.clone().on_hover_text_at_pointer(format!("{} {}: {}", tr("Code violation at bit"), bit, kind));
+                            tooltip_shown = true;
+                        }
+                    }
+                }
+
+                // Column statistics stick to the top of the view, over the first rows
+                if let Some(stats) = column_stats {
+                    let band = Rect::from_min_max(
+                        Pos2::new(response.rect.min.x + viewport.min.x, response.rect.min.y + viewport.min.y),
+                        Pos2::new(response.rect.min.x + viewport.max.x, response.rect.min.y + viewport.min.y + COLUMN_STATS_HEIGHT),
+                    );
+                    painter.rect_filled(band, 0.0, Color32::from_rgba_unmultiplied(30, 30, 30, 200));
+
+                    for col in start_col..end_col.min(stats.ones_density.len()) {
+                        let x = response.rect.min.x + calc_position(col, thick_interval_horizontal, thick_spacing_horizontal);
+                        let bar_height = stats.ones_density[col] * (COLUMN_STATS_HEIGHT - 4.0);
+                        let bar = Rect::from_min_max(
+                            Pos2::new(x, band.max.y - 2.0 - bar_height),
+                            Pos2::new(x + self.bit_size, band.max.y - 2.0),
+                        );
+                        // Constant columns stand out: likely flags, sync or padding
+                        let color = if stats.consistency.get(col).is_some_and(|&c| c > COLUMN_STATS_CONSTANT) {
+                            Color32::from_rgb(255, 150, 50)
+                        } else {
+                            Color32::from_rgb(100, 160, 255)
+                        };
+                        painter.rect_filled(bar, 0.0, color);
+                    }
+
+                    if let Some(pos) = response.hover_pos()
+                        && band.contains(pos)
+                    {
+                        tooltip_shown = true;
+                        let col = (start_col..end_col).find(|&col| {
+                            let x = response.rect.min.x + calc_position(col, thick_interval_horizontal, thick_spacing_horizontal);
+                            pos.x >= x && pos.x < x + cell_size
+                        });
+                        if let Some(col) = col.filter(|&col| col < stats.ones_density.len()) {
+                            let consistency = stats.consistency.get(col).copied().unwrap_or(0.0);
+                            response.clone().on_hover_text_at_pointer(format!(
+                                "{} {}\n{}: {:.1}%\n{}: {:.3}",
+                                tr("Column"),
+                                col,
+                                tr("Ones"),
+                                stats.ones_density[col] * 100.0,
+                                tr("Consistency"),
+                                consistency,
+                            ));
+                        }
                     }
                 }
+
+                // Describe the bit under the pointer
+                if !tooltip_shown
+                    && selection_anchor.is_none()
+                    && let Some(bit_index) = response.hover_pos().and_then(bit_at_pointer)
+                {
+                    let (row, col) = self.grid_cell(bit_index);
+                    let byte_index = bit_index / 8;
+                    let byte_bits = &bits[byte_index * 8..(byte_index * 8 + 8).min(bits.len())];
+                    let byte = byte_bits.load_be::<u8>() << (8 - byte_bits.len());
+                    let ascii = if byte.is_ascii_graphic() || byte == b' ' { format!(" '{}'", byte as char) } else { String::new() };
+                    let confidence = match self.confidence.as_ref().and_then(|confidence| confidence.get(bit_index)) {
+                        Some(&confidence) => format!("\n{}: {:.0}%", tr("Confidence"), confidence as f32 / 2.55),
+                        None => String::new(),
+                    };
+                    response.clone().on_hover_text_at_pointer(format!(
+                        "{} {} = {}\n{} {}, {} {}\n{} {} (0x{:X}), {} {}\n{}: 0x{:02X} = {} = {:08b}{}{}",
+                        tr("Bit"),
+                        bit_index,
+                        bits[bit_index] as u8,
+                        tr("Row"),
+                        row,
+                        tr("column"),
+                        col,
+                        tr("Byte"),
+                        byte_index,
+                        byte_index,
+                        tr("bit"),
+                        bit_index % 8,
+                        tr("Byte value"),
+                        byte,
+                        byte,
+                        byte,
+                        ascii,
+                        confidence,
+                    ));
+                }
+
+                (start_row..end_row, start_col..end_col)
+            });
+
+        if clicked_bit.is_some() {
+            self.clicked_bit = clicked_bit;
+        }
+        self.selection = selection;
+        self.selection_anchor = selection_anchor;
+        self.texture = texture;
+        self.last_scroll_offset = output.state.offset;
+        self.last_view_size = output.inner_rect.size();
+
+        let (visible_rows, visible_cols) = output.inner.clone();
+        if let Some(rect) = gutter_rect {
+            let painter = ui.painter_at(rect);
+            for row in visible_rows {
+                let y = rect.min.y + calc_position(row, thick_interval_vertical, thick_spacing_vertical) - output.state.offset.y;
+                painter.text(
+                    Pos2::new(rect.max.x - 4.0, y + self.bit_size / 2.0),
+                    egui::Align2::RIGHT_CENTER,
+                    self.row_label(row),
+                    offset_font.clone(),
+                    label_color,
+                );
+            }
+        }
+        if let Some(rect) = ruler_rect {
+            let painter = ui.painter_at(rect);
+            // Label every column that fits, preferring the thick grid intervals once they don't all fit
+            let unit = if thick_interval_horizontal > 0 && cell_size < RULER_LABEL_SPACING { thick_interval_horizontal } else { 1 };
+            let mut step = unit;
+            while (step as f32) * cell_size < RULER_LABEL_SPACING {
+                step *= 2;
+            }
+            for col in visible_cols.filter(|col| col.is_multiple_of(step)) {
+                let x = rect.min.x + calc_position(col, thick_interval_horizontal, thick_spacing_horizontal) - output.state.offset.x;
+                painter.line_segment([Pos2::new(x, rect.max.y - 4.0), Pos2::new(x, rect.max.y)], Stroke::new(1.0, label_color));
+                painter.text(Pos2::new(x, rect.max.y - 4.0), egui::Align2::LEFT_BOTTOM, col.to_string(), offset_font.clone(), label_color);
+            }
+        }
+
+        if let Some(rect) = density_rect {
+            // The gutter starts level with the grid, below the ruler
+            let rect = Rect::from_min_max(Pos2::new(rect.min.x, grid_rect.min.y), rect.max);
+            self.show_density_gutter(ui, rect, output.inner.0.clone(), output.state.offset.y, cell_size, one_color);
+        }
+
+        ui.advance_cursor_after_rect(view_rect);
+
+        if let Some(rect) = minimap_rect {
+            ui.advance_cursor_after_rect(rect);
+            self.show_minimap_strip(ui, rect, total_rows, cell_size, &output);
+        }
+    }
+
+    /// Fraction of ones in each visible row as a bar, merging rows once they're less than a
+    /// point tall
+    fn show_density_gutter(&self, ui: &mut egui::Ui, rect: Rect, rows: std::ops::Range<usize>, scroll_y: f32, cell_size: f32, color: Color32) {
+        let bits = self.source_bits();
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 0.0, ui.visuals().extreme_bg_color);
+        let thick_interval = if self.bit_size < TEXTURE_BIT_SIZE { 0 } else { self.thick_grid_interval_vertical };
+        let row_y = |row: usize| {
+            let spacing = row.checked_div(thick_interval).map_or(0.0, |groups| groups as f32 * self.thick_grid_spacing_vertical);
+            rect.min.y + row as f32 * cell_size + spacing - scroll_y
+        };
+        let density = |first_row: usize, end_row: usize| {
+            let start = first_row * self.frame_length;
+            let end = (end_row * self.frame_length).min(bits.len()).min(start + DENSITY_MAX_BITS);
+            (end > start).then(|| bits[start..end].count_ones() as f32 / (end - start) as f32)
+        };
+
+        let rows_per_bar = (1.0 / cell_size).ceil().max(1.0) as usize;
+        let first = rows.start / rows_per_bar * rows_per_bar;
+        for row in (first..rows.end).step_by(rows_per_bar) {
+            let Some(ratio) = density(row, row + rows_per_bar) else {
+                continue;
+            };
+            let height = (rows_per_bar as f32 * cell_size).min(self.bit_size.max(1.0));
+            let bar = Rect::from_min_size(Pos2::new(rect.min.x + 2.0, row_y(row)), Vec2::new((rect.width() - 4.0) * ratio, height));
+            painter.rect_filled(bar, 0.0, color);
+        }
+
+        let response = ui.interact(rect, ui.id().with("row_density"), Sense::hover());
+        if let Some(pos) = response.hover_pos() {
+            let row = rows.clone().find(|&row| pos.y < row_y(row) + cell_size);
+            if let Some((row, ratio)) = row.and_then(|row| Some((row, density(row, row + 1)?))) {
+                response.on_hover_text_at_pointer(format!("{} {}: {:.1}% {}", tr("Row"), row, ratio * 100.0, tr("ones")));
+            }
+        }
+    }
+
+    /// Colors of the cells described by `key`, sampling one bit per texel
+    fn grid_image(
+        &self,
+        bits: &BitSlice<u8, Msb0>,
+        key: &GridTextureKey,
+        selection_color: Color32,
+        patterns: &[Pattern],
+        matches: &MatchIndex,
+        colors: &BitColors,
+    ) -> egui::ColorImage {
+        let [zero_color, one_color, highlight_color, background_color] = key.colors;
+        let [width, height] = key.size;
+        let (rows, cols) = (key.rows.len(), key.cols.len());
+        let mut pixels = Vec::with_capacity(width * height);
+
+        for y in 0..height {
+            let row = key.rows.start + y * rows / height;
+            for x in 0..width {
+                let col = key.cols.start + x * cols / width;
+                let bit_index = self.bit_at(row, col);
+                if bit_index >= bits.len() {
+                    pixels.push(background_color);
+                    continue;
+                }
+                let mut color = self.shade(if bits[bit_index] { one_color } else { zero_color }, bit_index, background_color);
+                color = tint_match(color, bit_index, patterns, matches, colors);
+                if !self.highlighted_bits.is_empty() && self.highlighted_bits.contains(&bit_index) {
+                    color = color.blend(highlight_color);
+                }
+                if key.selection.is_some_and(|(start, end)| bit_index >= start && bit_index < end) {
+                    color = color.blend(selection_color);
+                }
+                pixels.push(color);
+            }
+        }
+
+        egui::ColorImage::new(key.size, pixels)
+    }
+
+    /// Gutter label of a grid row: its first bit and byte offset, or its position in the frame when transposed
+    fn row_label(&self, row: usize) -> String {
+        if self.transposed {
+            return format!("+{}", row);
+        }
+        let bit = self.bit_at(row, 0);
+        if bit.is_multiple_of(8) {
+            format!("{} 0x{:X}", bit, bit / 8)
+        } else {
+            format!("{} 0x{:X}.{}", bit, bit / 8, bit % 8)
+        }
+    }
+
+    /// Recompute column statistics if the data or frame length changed
+    fn update_column_stats(&mut self) {
+        if self.frame_length == 0 || self.column_stats.as_ref().is_some_and(|stats| stats.frame_length == self.frame_length) {
+            return;
+        }
+
+        let frame_length = self.frame_length;
+        let bits = self.source_bits();
+        // Whole frames only, capped so huge streams stay responsive
+        let sample_len = bits.len().min(COLUMN_STATS_MAX_BITS) / frame_length * frame_length;
+        let sample = &bits[..sample_len];
+
+        let mut ones = vec![0usize; frame_length];
+        for frame in sample.chunks_exact(frame_length) {
+            for bit_pos in frame.iter_ones() {
+                ones[bit_pos] += 1;
+            }
+        }
+        let frames = (sample_len / frame_length).max(1);
+        let ones_density = ones.iter().map(|&count| count as f32 / frames as f32).collect();
+        // score_width reports per-column entropy; invert it so a constant column is 1.0
+        let (_, entropies) = score_width(sample, frame_length);
+        let consistency = entropies.iter().map(|entropy| 1.0 - entropy).collect();
+
+        self.column_stats = Some(ColumnStats {
+            frame_length,
+            ones_density,
+            consistency,
+        });
+    }
+
+    /// Draw the minimap with the visible region marked, and jump to wherever it is clicked or dragged
+    fn show_minimap_strip(
+        &mut self,
+        ui: &mut egui::Ui,
+        rect: Rect,
+        total_rows: usize,
+        cell_size: f32,
+        output: &egui::scroll_area::ScrollAreaOutput<VisibleCells>,
+    ) {
+        let bucket_rows = ((rect.height() / MINIMAP_CELL_HEIGHT) as usize).clamp(1, total_rows);
+        let columns = self.frame_length.clamp(1, MINIMAP_COLUMNS);
+
+        let stale = match &self.minimap {
+            Some(cache) => cache.frame_length != self.frame_length || cache.bucket_rows != bucket_rows || cache.columns != columns,
+            None => true,
+        };
+        if stale {
+            let densities = minimap_densities(self.source_bits(), self.frame_length, total_rows, bucket_rows, columns);
+            self.minimap = Some(MinimapCache {
+                frame_length: self.frame_length,
+                bucket_rows,
+                columns,
+                densities,
             });
+        }
+
+        let painter = ui.painter_at(rect);
+        painter.rect_filled(rect, 0.0, Color32::from_gray(200));
+
+        if let Some(cache) = &self.minimap {
+            let cell_width = rect.width() / cache.columns as f32;
+            let cell_height = rect.height() / cache.bucket_rows as f32;
+            for (i, density) in cache.densities.iter().enumerate() {
+                let Some(density) = density else { continue };
+                let (row, col) = (i / cache.columns, i % cache.columns);
+                let cell = Rect::from_min_size(
+                    Pos2::new(rect.min.x + col as f32 * cell_width, rect.min.y + row as f32 * cell_height),
+                    Vec2::new(cell_width, cell_height),
+                );
+                // Same convention as the grid: ones are dark, zeros are light
+                painter.rect_filled(cell, 0.0, Color32::from_gray(((1.0 - density) * 255.0) as u8));
+            }
+        }
+
+        // Mark the part of the stream currently on screen
+        let content_height = output.content_size.y;
+        if content_height > 0.0 {
+            let top = (output.state.offset.y / content_height).clamp(0.0, 1.0);
+            let height = (output.inner_rect.height() / content_height).clamp(0.0, 1.0);
+            let indicator = Rect::from_min_size(
+                Pos2::new(rect.min.x, rect.min.y + top * rect.height()),
+                Vec2::new(rect.width(), (height * rect.height()).max(2.0)),
+            );
+            painter.rect_filled(indicator, 0.0, Color32::from_rgba_unmultiplied(70, 130, 255, 60));
+            painter.rect_stroke(indicator, 0.0, Stroke::new(1.5, Color32::from_rgb(70, 130, 255)), egui::StrokeKind::Inside);
+        }
+        painter.rect_stroke(rect, 0.0, Stroke::new(1.0, Color32::GRAY), egui::StrokeKind::Inside);
+
+        let response = ui
+            .interact(rect, ui.id().with("bit_viewer_minimap"), Sense::click_and_drag())
+            .on_hover_cursor(egui::CursorIcon::PointingHand);
+        let row_at = |y: f32| (((y - rect.min.y) / rect.height()).clamp(0.0, 1.0) * total_rows as f32) as usize;
+
+        if response.clicked() || response.dragged() {
+            if let Some(pos) = response.interact_pointer_pos() {
+                // Center the clicked row in the view
+                let visible_rows = (output.inner_rect.height() / cell_size) as usize;
+                let row = row_at(pos.y).min(total_rows - 1).saturating_sub(visible_rows / 2);
+                self.jump_to_bit = Some(row * self.frame_length);
+            }
+        } else if let Some(pos) = response.hover_pos() {
+            response.on_hover_text(format!("{} {}", tr("Row"), row_at(pos.y).min(total_rows - 1)));
+        }
     }
 
     pub fn zoom_in(&mut self) {
-        self.bit_size = (self.bit_size * 1.2).min(100.0);
+        self.zoom_by(1.2);
     }
 
     pub fn zoom_out(&mut self) {
-        self.bit_size = (self.bit_size / 1.2).max(2.0);
+        self.zoom_by(1.0 / 1.2);
     }
 
     pub fn reset_zoom(&mut self) {
-        self.bit_size = 10.0;
+        self.bit_size = DEFAULT_BIT_SIZE;
+    }
+
+    /// Scale the bit size by `factor`, within the zoom limits
+    pub fn zoom_by(&mut self, factor: f32) {
+        self.bit_size = (self.bit_size * factor).clamp(MIN_BIT_SIZE, MAX_BIT_SIZE);
     }
+
+    /// Current bit size relative to the default, in percent
+    pub fn zoom_percent(&self) -> f32 {
+        self.bit_size / DEFAULT_BIT_SIZE * 100.0
+    }
+
+    /// Pick the bit size at which one row of the grid fills the width of the view
+    pub fn fit_to_width(&mut self) {
+        let columns = self.grid_shape().1;
+        let available = self.last_view_size.x - CONTENT_PADDING;
+        if columns == 0 || available <= 0.0 {
+            return;
+        }
+
+        let thick_spacing = columns.checked_div(self.thick_grid_interval_horizontal)
+            .map_or(0.0, |groups| groups as f32 * self.thick_grid_spacing_horizontal);
+        let size = (available - thick_spacing) / columns as f32 - self.bit_spacing;
+        // Zoomed out that far the grid is a texture, which has no gaps to leave room for
+        self.bit_size = if size < TEXTURE_BIT_SIZE { available / columns as f32 } else { size }
+            .clamp(MIN_BIT_SIZE, MAX_BIT_SIZE);
+    }
+}
+
+/// `color` tinted with the color of the first pattern with a match covering `bit_index`
+fn tint_match(color: Color32, bit_index: usize, patterns: &[Pattern], matches: &MatchIndex, colors: &BitColors) -> Color32 {
+    let tint = matches
+        .first_pattern(bit_index, bit_index + 1)
+        .and_then(|index| Some(rgb(colors.color_of(patterns.get(index)?, index))));
+    match tint {
+        Some(tint) => color.lerp_to_gamma(tint, 0.5),
+        None => color,
+    }
+}
+
+/// Ones-density per (row bucket, column bucket) of the stream laid out `frame_length` bits per row
+fn minimap_densities(
+    bits: &BitSlice<u8, Msb0>,
+    frame_length: usize,
+    total_rows: usize,
+    bucket_rows: usize,
+    columns: usize,
+) -> Vec<Option<f32>> {
+    let mut densities = Vec::with_capacity(bucket_rows * columns);
+    let mut ones = vec![0usize; columns];
+    let mut counts = vec![0usize; columns];
+
+    for bucket in 0..bucket_rows {
+        let row_start = bucket * total_rows / bucket_rows;
+        let row_end = ((bucket + 1) * total_rows / bucket_rows).clamp(row_start + 1, total_rows);
+        let step = ((row_end - row_start) / MINIMAP_SAMPLE_ROWS).max(1);
+
+        ones.fill(0);
+        counts.fill(0);
+
+        for row in (row_start..row_end).step_by(step) {
+            let start = row * frame_length;
+            let end = (start + frame_length).min(bits.len());
+
+            for col in 0..columns {
+                let seg_start = start + col * frame_length / columns;
+                let seg_end = (start + (col + 1) * frame_length / columns).min(end);
+                if seg_start >= seg_end {
+                    break;
+                }
+                let segment = &bits[seg_start..seg_end];
+                ones[col] += segment.count_ones();
+                counts[col] += segment.len();
+            }
+        }
+
+        densities.extend(ones.iter().zip(&counts).map(|(&one, &count)| {
+            if count > 0 { Some(one as f32 / count as f32) } else { None }
+        }));
+    }
+
+    densities
 }
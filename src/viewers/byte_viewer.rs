@@ -1,60 +1,33 @@
 use bitvec::prelude::*;
 use egui::{Color32, Pos2, Rect, Sense, Stroke, Vec2};
-use serde::{Deserialize, Serialize};
-use crate::analysis::Pattern;
-
-/// Represents a labeled column in the byte view
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ByteColumn {
-    pub label: String,
-    pub bit_start: usize,  // Start bit within the byte row (0-based)
-    pub bit_end: usize,    // End bit within the byte row (inclusive)
-    pub color: [u8; 3],    // RGB color
-}
-
-impl ByteColumn {
-    pub fn new(label: String, bit_start: usize, bit_end: usize, color: [u8; 3]) -> Self {
-        Self {
-            label,
-            bit_start,
-            bit_end,
-            color,
-        }
-    }
-
-    pub fn color32(&self) -> Color32 {
-        Color32::from_rgb(self.color[0], self.color[1], self.color[2])
-    }
+use crate::analysis::{MatchIndex, Pattern};
+use crate::i18n::tr;
+use crate::utils::{hex_dump, raw_hex};
+use super::{rgb, BitColors, FlashHighlight, GapMarkers};
 
-    pub fn byte_range(&self, bytes_per_row: usize) -> (usize, usize) {
-        let start_byte = self.bit_start / 8;
-        let end_byte = self.bit_end / 8;
-        (start_byte.min(bytes_per_row - 1), end_byte.min(bytes_per_row - 1))
-    }
-}
-
-/// Configuration for the byte viewer
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ByteViewConfig {
-    pub bytes_per_row: usize,
-    pub columns: Vec<ByteColumn>,
-    pub show_hex_offset: bool,
-}
-
-impl Default for ByteViewConfig {
-    fn default() -> Self {
-        Self {
-            bytes_per_row: 16,
-            columns: Vec::new(),
-            show_hex_offset: true,
-        }
-    }
-}
+pub use bit_core::view::{ByteColumn, ByteViewConfig, Endianness, WordKind};
 
 /// The byte viewer component
 pub struct ByteViewer {
     pub config: ByteViewConfig,
     pub byte_size: f32,
+    pub jump_to_bit: Option<usize>,
+    pub flash: Option<FlashHighlight>,
+    /// Custom colors; `None` follows the current egui theme
+    pub colors: Option<BitColors>,
+    /// Clicking a byte selects it and typed hex digits overwrite it
+    pub edit_mode: bool,
+    /// Selected byte range (start, end exclusive), dragged out over the cells
+    pub selection: Option<(usize, usize)>,
+    selection_anchor: Option<usize>,
+    editing_byte: Option<usize>,
+    hex_input: String,
+    edited_byte: Option<(usize, u8)>,
+    /// Rows per super-frame when each row is a frame, marked by a line before each one's first
+    /// row; 0 for none
+    pub super_frame_length: usize,
+    /// Where capture bursts start in the shown data
+    pub gaps: GapMarkers,
 }
 
 impl Default for ByteViewer {
@@ -62,6 +35,17 @@ impl Default for ByteViewer {
         Self {
             config: ByteViewConfig::default(),
             byte_size: 20.0,
+            jump_to_bit: None,
+            flash: None,
+            colors: None,
+            edit_mode: false,
+            selection: None,
+            selection_anchor: None,
+            editing_byte: None,
+            hex_input: String::new(),
+            edited_byte: None,
+            super_frame_length: 0,
+            gaps: GapMarkers::default(),
         }
     }
 }
@@ -71,6 +55,55 @@ impl ByteViewer {
         Self::default()
     }
 
+    /// The byte index and value typed in edit mode since the last call, if any
+    pub fn take_edited_byte(&mut self) -> Option<(usize, u8)> {
+        self.edited_byte.take()
+    }
+
+    /// Collect hex digits typed for the selected byte; two digits complete it and
+    /// move the selection to the next byte
+    fn handle_edit_input(&mut self, ui: &egui::Ui, total_bytes: usize) {
+        let Some(byte_idx) = self.editing_byte else {
+            return;
+        };
+        if !self.edit_mode || byte_idx >= total_bytes {
+            self.editing_byte = None;
+            return;
+        }
+        // Leave keys alone while a text field has focus
+        if ui.memory(|m| m.focused().is_some()) {
+            return;
+        }
+
+        let (typed, backspace, escape) = ui.input(|i| {
+            let typed: String = i.events.iter().filter_map(|event| match event {
+                egui::Event::Text(text) => Some(text.as_str()),
+                _ => None,
+            }).collect();
+            (typed, i.key_pressed(egui::Key::Backspace), i.key_pressed(egui::Key::Escape))
+        });
+
+        if escape {
+            self.editing_byte = None;
+            self.hex_input.clear();
+            return;
+        }
+        if backspace {
+            self.hex_input.pop();
+        }
+        for c in typed.chars().filter(|c| c.is_ascii_hexdigit()) {
+            self.hex_input.push(c);
+            if self.hex_input.len() == 2 {
+                if let Ok(value) = u8::from_str_radix(&self.hex_input, 16) {
+                    self.edited_byte = Some((byte_idx, value));
+                }
+                self.hex_input.clear();
+                self.editing_byte = Some(byte_idx + 1).filter(|&next| next < total_bytes);
+                break;
+            }
+        }
+    }
+
     pub fn set_bytes_per_row(&mut self, bytes_per_row: usize) {
         self.config.bytes_per_row = bytes_per_row.max(1).min(64);
     }
@@ -85,16 +118,22 @@ impl ByteViewer {
         }
     }
 
-    #[allow(dead_code)]
     pub fn update_column(&mut self, index: usize, column: ByteColumn) {
         if index < self.config.columns.len() {
             self.config.columns[index] = column;
         }
     }
 
+    /// Move the column at `from` so it ends up at index `to`
+    pub fn move_column(&mut self, from: usize, to: usize) {
+        if from < self.config.columns.len() && to < self.config.columns.len() {
+            let column = self.config.columns.remove(from);
+            self.config.columns.insert(to, column);
+        }
+    }
+
     /// Convert bits to bytes for display
-    #[allow(dead_code)]
-    fn bits_to_bytes(bits: &BitVec<u8, Msb0>) -> Vec<u8> {
+    fn bits_to_bytes(bits: &BitSlice<u8, Msb0>) -> Vec<u8> {
         let mut bytes = Vec::new();
         for chunk in bits.chunks(8) {
             let mut byte = 0u8;
@@ -108,16 +147,40 @@ impl ByteViewer {
         bytes
     }
 
+    /// Copy actions for `selection`, shown in a cell's context menu; a partial last byte is
+    /// padded with zero bits. Returns true if the selection should be cleared.
+    fn selection_menu(ui: &mut egui::Ui, selection: Option<(usize, usize)>, bytes_per_row: usize, bits: &BitSlice<u8, Msb0>) -> bool {
+        let Some((start, end)) = selection else {
+            ui.label(tr("Drag over bytes to select them"));
+            return false;
+        };
+        let bytes = Self::bits_to_bytes(&bits[start * 8..(end * 8).min(bits.len())]);
+        ui.label(format!("{} {} 0x{:X}", bytes.len(), tr("byte(s) from"), start));
+        if ui.button(tr("📋 Copy as hex dump")).clicked() {
+            ui.ctx().copy_text(hex_dump(&bytes, start, bytes_per_row));
+            ui.close();
+        }
+        if ui.button(tr("📋 Copy as raw hex")).clicked() {
+            ui.ctx().copy_text(raw_hex(&bytes));
+            ui.close();
+        }
+        let clear = ui.button(tr("Clear selection")).clicked();
+        if clear {
+            ui.close();
+        }
+        clear
+    }
+
     /// Render the byte view with virtualization for large files
     #[allow(dead_code)]
-    pub fn render(&mut self, ui: &mut egui::Ui, bits: &BitVec<u8, Msb0>) {
-        self.render_with_patterns(ui, bits, &[]);
+    pub fn render(&mut self, ui: &mut egui::Ui, bits: &BitSlice<u8, Msb0>) {
+        self.render_with_patterns(ui, bits, &[], &MatchIndex::default());
     }
 
-    /// Render the byte view with pattern highlighting
-    pub fn render_with_patterns(&mut self, ui: &mut egui::Ui, bits: &BitVec<u8, Msb0>, patterns: &[Pattern]) {
+    /// Render the byte view with the matches of `patterns`, looked up in `matches`, highlighted
+    pub fn render_with_patterns(&mut self, ui: &mut egui::Ui, bits: &BitSlice<u8, Msb0>, patterns: &[Pattern], matches: &MatchIndex) {
         if bits.is_empty() {
-            ui.label("No data to display");
+            ui.label(tr("No data to display"));
             return;
         }
 
@@ -125,6 +188,7 @@ impl ByteViewer {
         let total_bits = bits.len();
         let total_bytes = (total_bits + 7) / 8;
         let bytes_per_row = self.config.bytes_per_row;
+        let word_size = self.config.word_size.max(1);
         let total_rows = (total_bytes + bytes_per_row - 1) / bytes_per_row;
 
         // Calculate layout dimensions
@@ -136,10 +200,44 @@ impl ByteViewer {
         // Draw column headers (outside scroll area)
         self.render_column_headers(ui, bytes_per_row, byte_width, offset_width, header_height);
 
+        // Bytes can only be typed over when each cell is one byte
+        let editable = self.edit_mode && word_size == 1;
+        if editable {
+            self.handle_edit_input(ui, total_bytes);
+        } else {
+            self.editing_byte = None;
+        }
+        let mut clicked_byte = None;
+        let mut clear_selection = false;
+        if self.selection.is_some_and(|(start, _)| start >= total_bytes) {
+            self.selection = None;
+        }
+        let mut selection = self.selection;
+        let mut selection_anchor = self.selection_anchor;
+        let selection_color = ui.visuals().selection.bg_fill.gamma_multiply(0.6);
+
+        let colors = self.colors.clone().unwrap_or_else(|| BitColors::for_dark_mode(ui.visuals().dark_mode));
+
+        let now = ui.input(|i| i.time);
+        let flash_color = self.flash.and_then(|flash| flash.color(now));
+        if flash_color.is_some() {
+            ui.ctx().request_repaint();
+        } else {
+            self.flash = None;
+        }
+
         // Use ScrollArea with virtualization
-        egui::ScrollArea::vertical()
+        let mut scroll_area = egui::ScrollArea::vertical()
             .id_salt("byte_viewer_scroll")
-            .auto_shrink([false, false])
+            .auto_shrink([false, false]);
+
+        if let Some(bit_pos) = self.jump_to_bit.take() {
+            let row = bit_pos / 8 / bytes_per_row;
+            let row_height = byte_height + ui.spacing().item_spacing.y;
+            scroll_area = scroll_area.vertical_scroll_offset(row as f32 * row_height);
+        }
+
+        scroll_area
             .show_rows(
                 ui,
                 byte_height,
@@ -147,6 +245,10 @@ impl ByteViewer {
                 |ui, row_range| {
                     // Only render visible rows
                     for row in row_range {
+                        if self.super_frame_length > 1 && row > 0 && row % self.super_frame_length == 0 {
+                            let y = ui.cursor().top() - ui.spacing().item_spacing.y / 2.0;
+                            ui.painter().hline(ui.max_rect().x_range(), y, Stroke::new(2.0, Color32::from_rgb(255, 140, 0)));
+                        }
                         ui.horizontal(|ui| {
                             // Show offset
                             if self.config.show_hex_offset {
@@ -161,40 +263,68 @@ impl ByteViewer {
                                 );
                             }
 
-                            // Draw bytes - only convert the bytes we need for this row
+                            // Draw words - only convert the bytes we need for this row
                             let row_start = row * bytes_per_row;
                             let row_end = (row_start + bytes_per_row).min(total_bytes);
                             
-                            for byte_idx in row_start..row_end {
-                                // Convert only this single byte from bits
-                                let bit_start = byte_idx * 8;
-                                let bit_end = (bit_start + 8).min(total_bits);
-                                let byte_bits = &bits[bit_start..bit_end];
+                            for byte_idx in (row_start..row_end).step_by(word_size) {
+                                let word_end = (byte_idx + word_size).min(row_end);
                                 
-                                let mut byte = 0u8;
-                                for (i, bit) in byte_bits.iter().enumerate() {
-                                    if *bit {
-                                        byte |= 1 << (7 - i);
-                                    }
-                                }
+                                // Convert only this word's bytes from bits
+                                let bit_start = byte_idx * 8;
+                                let bit_end = (word_end * 8).min(total_bits);
+                                let word_bytes: Vec<u8> = bits[bit_start..bit_end]
+                                    .chunks(8)
+                                    .map(|byte_bits| {
+                                        let mut byte = 0u8;
+                                        for (i, bit) in byte_bits.iter().enumerate() {
+                                            if *bit {
+                                                byte |= 1 << (7 - i);
+                                            }
+                                        }
+                                        byte
+                                    })
+                                    .collect();
+                                let (word_text, plausible) = self.config.format_word(&word_bytes);
                                 
-                                let local_byte_idx = byte_idx - row_start;
-                                let bit_offset = local_byte_idx * 8;
-
                                 // Check for pattern matches first (higher priority)
-                                let pattern_match = self.find_pattern_match(bit_start, bit_end, patterns);
+                                let pattern_match = Self::find_pattern_match(bit_start, bit_end, patterns, matches, &colors);
                                 
-                                // Find which column this byte belongs to (lower priority)
+                                // Find which column each bit of this cell belongs to (lower priority)
+                                let bit_columns: Vec<Option<usize>> = (bit_start..bit_end)
+                                    .map(|bit_pos| self.find_column(self.config.column_bit_offset(bit_pos)))
+                                    .collect();
                                 let column_color = if pattern_match.is_none() {
-                                    self.find_column_color(bit_offset)
+                                    bit_columns.iter().flatten().next().map(|&idx| rgb(self.config.columns[idx].color))
                                 } else {
                                     None
                                 };
 
+                                // Words span their bytes' cells so columns line up across word sizes
+                                let cells = word_bytes.len() as f32;
                                 let (rect, response) = ui.allocate_exact_size(
-                                    Vec2::new(byte_width, byte_height),
-                                    Sense::hover(),
+                                    Vec2::new(cells * byte_width + (cells - 1.0) * ui.spacing().item_spacing.x, byte_height),
+                                    if editable { Sense::click() } else { Sense::click_and_drag() },
                                 );
+                                if editable {
+                                    if response.clicked() {
+                                        clicked_byte = Some(byte_idx);
+                                    }
+                                } else {
+                                    // Dragging from a cell selects through whichever cell is under the pointer
+                                    if response.drag_started() {
+                                        selection_anchor = Some(byte_idx);
+                                    } else if response.clicked() {
+                                        selection = None;
+                                    }
+                                    if let Some(anchor) = selection_anchor
+                                        && ui.rect_contains_pointer(rect)
+                                    {
+                                        selection = Some((anchor.min(byte_idx), (anchor.max(byte_idx) + word_size).min(total_bytes)));
+                                    }
+                                }
+                                let selected = selection.is_some_and(|(start, end)| byte_idx < end && word_end > start);
+                                let is_editing = self.editing_byte == Some(byte_idx);
 
                                 // Draw background color - pattern match takes priority over column
                                 if let Some((pattern_color, _)) = pattern_match {
@@ -209,34 +339,68 @@ impl ByteViewer {
                                             120
                                         )
                                     );
-                                } else if let Some(color) = column_color {
-                                    // Column color - subtle highlight
-                                    ui.painter().rect_filled(
-                                        rect,
-                                        2.0,
-                                        Color32::from_rgba_unmultiplied(color.r(), color.g(), color.b(), 40)
-                                    );
+                                } else if column_color.is_some() {
+                                    // Column colors - subtle highlight over just the bits each field covers,
+                                    // with a solid bar along the bottom so narrow fields stay visible
+                                    let bit_width = rect.width() / bit_columns.len() as f32;
+                                    let mut run_start = 0;
+                                    while run_start < bit_columns.len() {
+                                        let run_end = (run_start..bit_columns.len())
+                                            .find(|&i| bit_columns[i] != bit_columns[run_start])
+                                            .unwrap_or(bit_columns.len());
+                                        if let Some(idx) = bit_columns[run_start] {
+                                            let color = rgb(self.config.columns[idx].color);
+                                            let slice = Rect::from_min_max(
+                                                Pos2::new(rect.min.x + run_start as f32 * bit_width, rect.min.y),
+                                                Pos2::new(rect.min.x + run_end as f32 * bit_width, rect.max.y),
+                                            );
+                                            ui.painter().rect_filled(
+                                                slice,
+                                                0.0,
+                                                Color32::from_rgba_unmultiplied(color.r(), color.g(), color.b(), 40)
+                                            );
+                                            ui.painter().rect_filled(
+                                                Rect::from_min_max(Pos2::new(slice.min.x, slice.max.y - 3.0), slice.max),
+                                                0.0,
+                                                color
+                                            );
+                                        }
+                                        run_start = run_end;
+                                    }
+                                }
+                                if selected {
+                                    ui.painter().rect_filled(rect, 2.0, selection_color);
                                 }
 
-                                // Draw byte value
+                                // Draw word value
                                 let text_color = if pattern_match.is_some() {
                                     Color32::BLACK
+                                } else if !plausible {
+                                    ui.visuals().weak_text_color()
                                 } else if column_color.is_some() {
-                                    Color32::BLACK
+                                    ui.visuals().strong_text_color()
                                 } else {
-                                    Color32::DARK_GRAY
+                                    ui.visuals().text_color()
                                 };
                                 
+                                // The byte being typed shows the digits entered so far
+                                let cell_text = if is_editing {
+                                    format!("{:_<2}", self.hex_input)
+                                } else {
+                                    word_text.clone()
+                                };
                                 ui.painter().text(
                                     rect.center(),
                                     egui::Align2::CENTER_CENTER,
-                                    format!("{:02X}", byte),
+                                    cell_text.as_str(),
                                     egui::FontId::monospace(self.byte_size),
                                     text_color
                                 );
 
-                                // Draw border - thicker for pattern matches
-                                let border_stroke = if let Some((pattern_color, _)) = pattern_match {
+                                // Draw border - thicker for pattern matches and the byte being edited
+                                let border_stroke = if is_editing {
+                                    Stroke::new(2.0, ui.visuals().selection.stroke.color)
+                                } else if let Some((pattern_color, _)) = pattern_match {
                                     Stroke::new(2.0, pattern_color)
                                 } else {
                                     Stroke::new(1.0, Color32::from_gray(100))
@@ -249,16 +413,88 @@ impl ByteViewer {
                                     egui::epaint::StrokeKind::Middle
                                 );
 
+                                // Mark where a new frame starts when columns are frame-relative
+                                if !self.config.columns_follow_rows() {
+                                    let period = self.config.column_period();
+                                    if bit_start.div_ceil(period) * period < bit_end {
+                                        ui.painter().line_segment(
+                                            [rect.left_top(), rect.left_bottom()],
+                                            Stroke::new(3.0, ui.visuals().strong_text_color()),
+                                        );
+                                    }
+                                }
+
+                                // Flash overlay after a "Go to"
+                                if let (Some(flash), Some(color)) = (self.flash, flash_color)
+                                    && flash.overlaps(bit_start, bit_end) {
+                                    ui.painter().rect_filled(rect, 2.0, color.gamma_multiply(0.6));
+                                    ui.painter().rect_stroke(rect, 2.0, Stroke::new(2.0, color), egui::epaint::StrokeKind::Outside);
+                                }
+
+                                // Mark bursts starting in this cell, at the byte they start in
+                                let word_gaps = self.gaps.within(bit_start, bit_end);
+                                for &(bit, gap_secs) in word_gaps {
+                                    let x = rect.min.x + ((bit - bit_start) / 8 * 8) as f32 / (bit_end - bit_start) as f32 * rect.width();
+                                    self.gaps.paint(ui.painter(), x, rect.y_range(), ui.max_rect().x_range(), gap_secs);
+                                }
+
+                                if !editable {
+                                    response.context_menu(|ui| {
+                                        clear_selection |= Self::selection_menu(ui, selection, bytes_per_row, bits);
+                                    });
+                                }
+
                                 // Show tooltip with bit offset and pattern info
-                                if response.hovered() {
+                                if response.hovered() && selection_anchor.is_none() {
                                     response.on_hover_ui(|ui| {
-                                        ui.label(format!("Byte: {}\nBit offset: {}", byte_idx, byte_idx * 8));
-                                        ui.label(format!("Value: 0x{:02X} ({})", byte, byte));
-                                        ui.label(format!("Binary: {:08b}", byte));
+                                        ui.label(format!("{}: {}\n{}: {}", tr("Byte"), byte_idx, tr("Bit offset"), byte_idx * 8));
+                                        if word_bytes.len() == 1 {
+                                            let byte = word_bytes[0];
+                                            ui.label(format!("{}: 0x{:02X} ({})", tr("Value"), byte, byte));
+                                            ui.label(format!("{}: {:08b}", tr("Binary"), byte));
+                                        } else {
+                                            let hex: Vec<String> = word_bytes.iter().map(|byte| format!("{:02X}", byte)).collect();
+                                            ui.label(format!("{}: {}", tr("Bytes"), hex.join(" ")));
+                                            ui.label(format!("{}: {}", tr("Value"), word_text));
+                                        }
+                                        
+                                        // Decode every field touching this cell, including bits outside it
+                                        let mut fields: Vec<usize> = bit_columns.iter().flatten().copied().collect();
+                                        fields.dedup();
+                                        if !fields.is_empty() {
+                                            ui.separator();
+                                        }
+                                        for idx in fields {
+                                            let column = &self.config.columns[idx];
+                                            let first_bit = (bit_start..bit_end)
+                                                .find(|&bit_pos| bit_columns[bit_pos - bit_start] == Some(idx))
+                                                .unwrap_or(bit_start);
+                                            let field_start = first_bit - (self.config.column_bit_offset(first_bit) - column.bit_start);
+                                            match field_value(bits, field_start, column.width()) {
+                                                Some(value) => {
+                                                    let time = column.timestamp
+                                                        .and_then(|format| format.decode(value))
+                                                        .map(|time| format!("\n    🕒 {}", time))
+                                                        .unwrap_or_default();
+                                                    ui.label(format!(
+                                                        "{} ({} {}..{}): {} (0x{:X}, 0b{:0width$b}){}",
+                                                        column.label, tr("bits"), column.bit_start, column.bit_end, value, value, value, time,
+                                                        width = column.width()
+                                                    ))
+                                                }
+                                                None => ui.label(format!(
+                                                    "{} ({} {}..{}): {}-{}",
+                                                    column.label, tr("bits"), column.bit_start, column.bit_end, column.width(), tr("bit field")
+                                                )),
+                                            };
+                                        }
                                         
                                         if let Some((_, pattern_name)) = pattern_match {
                                             ui.separator();
-                                            ui.label(format!("🎯 Pattern: {}", pattern_name));
+                                            ui.label(format!("🎯 {}: {}", tr("Pattern"), pattern_name));
+                                        }
+                                        for &(bit, gap_secs) in word_gaps {
+                                            ui.label(GapMarkers::hover_text(bit, gap_secs));
                                         }
                                     });
                                 }
@@ -267,10 +503,34 @@ impl ByteViewer {
                     }
                 },
             );
+
+        if let Some(byte_idx) = clicked_byte {
+            self.editing_byte = Some(byte_idx);
+            self.hex_input.clear();
+        }
+        if selection_anchor.is_some() && !ui.input(|i| i.pointer.primary_down()) {
+            selection_anchor = None;
+        }
+        self.selection = selection.filter(|_| !clear_selection);
+        self.selection_anchor = selection_anchor;
     }
 
     fn render_column_headers(&self, ui: &mut egui::Ui, bytes_per_row: usize, byte_width: f32, offset_width: f32, header_height: f32) {
-        if self.config.columns.is_empty() {
+        if !self.config.columns.iter().any(|column| column.visible) {
+            return;
+        }
+
+        // Frame-relative columns wrap across rows, so a fixed header would be misleading
+        if !self.config.columns_follow_rows() {
+            ui.horizontal(|ui| {
+                ui.add_space(offset_width);
+                ui.weak(format!(
+                    "{} ({} {})",
+                    tr("Columns are relative to frames; frame starts are marked with a bar"),
+                    self.config.column_period(),
+                    tr("bits")
+                ));
+            });
             return;
         }
 
@@ -289,12 +549,12 @@ impl ByteViewer {
             );
 
             // Draw each column header
-            for column in &self.config.columns {
-                let (start_byte, end_byte) = column.byte_range(bytes_per_row);
-                
-                if start_byte < bytes_per_row {
-                    let x_start = rect.min.x + start_byte as f32 * byte_width;
-                    let x_end = rect.min.x + (end_byte + 1) as f32 * byte_width;
+            for column in self.config.columns.iter().filter(|column| column.visible) {
+                if column.bit_start < bytes_per_row * 8 {
+                    // Headers are bit-precise so sub-byte fields get a partial-width header
+                    let bit_end = (column.bit_end + 1).min(bytes_per_row * 8);
+                    let x_start = rect.min.x + column.bit_start as f32 / 8.0 * byte_width;
+                    let x_end = rect.min.x + bit_end as f32 / 8.0 * byte_width;
                     
                     let header_rect = Rect::from_min_max(
                         Pos2::new(x_start, rect.min.y),
@@ -326,7 +586,7 @@ impl ByteViewer {
                     ui.painter().rect_stroke(
                         header_rect,
                         2.0,
-                        Stroke::new(2.0, column.color32()),
+                        Stroke::new(2.0, rgb(column.color)),
                         egui::epaint::StrokeKind::Middle
                     );
                 }
@@ -336,43 +596,17 @@ impl ByteViewer {
         ui.add_space(5.0);
     }
 
-    fn find_column_color(&self, bit_offset: usize) -> Option<Color32> {
-        for column in &self.config.columns {
-            if bit_offset >= column.bit_start && bit_offset < column.bit_end {
-                return Some(column.color32());
-            }
-        }
-        None
+    /// Index of the first visible column covering `bit_offset` (end bit inclusive)
+    fn find_column(&self, bit_offset: usize) -> Option<usize> {
+        self.config.column_at(bit_offset)
     }
 
     /// Check if a byte range overlaps with any pattern matches
-    /// Returns (is_match, pattern_color, pattern_name) if there's a match
-    fn find_pattern_match(&self, bit_start: usize, bit_end: usize, patterns: &[Pattern]) -> Option<(Color32, String)> {
-        // Predefined colors for different patterns
-        let pattern_colors = [
-            Color32::from_rgb(255, 100, 100),  // Red
-            Color32::from_rgb(100, 255, 100),  // Green
-            Color32::from_rgb(100, 100, 255),  // Blue
-            Color32::from_rgb(255, 255, 100),  // Yellow
-            Color32::from_rgb(255, 100, 255),  // Magenta
-            Color32::from_rgb(100, 255, 255),  // Cyan
-            Color32::from_rgb(255, 150, 100),  // Orange
-            Color32::from_rgb(150, 100, 255),  // Purple
-        ];
-
-        for (pattern_idx, pattern) in patterns.iter().enumerate() {
-            for match_info in &pattern.matches {
-                let match_start = match_info.position;
-                let match_end = match_info.position + pattern.bits.len();
-                
-                // Check if this byte overlaps with the pattern match
-                if bit_start < match_end && bit_end > match_start {
-                    let color = pattern_colors[pattern_idx % pattern_colors.len()];
-                    return Some((color, pattern.name.clone()));
-                }
-            }
-        }
-        None
+    /// Returns (pattern_color, pattern_name) of the first pattern matching there
+    fn find_pattern_match(bit_start: usize, bit_end: usize, patterns: &[Pattern], matches: &MatchIndex, colors: &BitColors) -> Option<(Color32, String)> {
+        let pattern_idx = matches.first_pattern(bit_start, bit_end)?;
+        let pattern = patterns.get(pattern_idx)?;
+        Some((rgb(colors.color_of(pattern, pattern_idx)), pattern.name.clone()))
     }
 
     #[allow(dead_code)]
@@ -385,3 +619,12 @@ impl ByteViewer {
         self.config = config;
     }
 }
+
+/// Value of the `len`-bit field at `start`, MSB first; `None` if it is wider than
+/// 64 bits or runs past the end of the data
+fn field_value(bits: &BitSlice<u8, Msb0>, start: usize, len: usize) -> Option<u64> {
+    if len == 0 || len > 64 || start + len > bits.len() {
+        return None;
+    }
+    Some(bits[start..start + len].iter().fold(0u64, |value, bit| (value << 1) | *bit as u64))
+}
@@ -0,0 +1,12 @@
+// Colors - the RGB arrays stored in settings and layouts, as egui colors for painting
+
+use egui::Color32;
+
+pub fn rgb([r, g, b]: [u8; 3]) -> Color32 {
+    Color32::from_rgb(r, g, b)
+}
+
+/// An RGBA overlay color, alpha not premultiplied
+pub fn rgba([r, g, b, a]: [u8; 4]) -> Color32 {
+    Color32::from_rgba_unmultiplied(r, g, b, a)
+}
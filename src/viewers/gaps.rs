@@ -0,0 +1,58 @@
+// Burst gaps - where received data picked up again after a pause, marked alike in every viewer
+
+use crate::i18n::tr;
+use egui::{Align2, Color32, FontId, Painter, Pos2, Rangef, Rect, Stroke, Vec2};
+
+pub const GAP_COLOR: Color32 = Color32::from_rgb(230, 60, 160);
+
+/// Burst starts in the displayed data and how they are drawn
+#[derive(Debug, Clone)]
+pub struct GapMarkers {
+    /// First bit of each burst after the first, with the silence before it in seconds, sorted by bit
+    pub starts: Vec<(usize, f64)>,
+    pub show: bool,
+    /// Collapsed gaps are a tick before the burst; expanded ones break the view with the pause length
+    pub collapsed: bool,
+}
+
+impl Default for GapMarkers {
+    fn default() -> Self {
+        Self { starts: Vec::new(), show: true, collapsed: true }
+    }
+}
+
+impl GapMarkers {
+    /// Bursts starting in `start..end`, or none while hidden
+    pub fn within(&self, start: usize, end: usize) -> &[(usize, f64)] {
+        if !self.show {
+            return &[];
+        }
+        let from = self.starts.partition_point(|&(bit, _)| bit < start);
+        let to = self.starts.partition_point(|&(bit, _)| bit < end);
+        &self.starts[from..to]
+    }
+
+    pub fn hover_text(bit: usize, gap_secs: f64) -> String {
+        format!("{} {}\n{}: {:.1} ms", tr("Burst at bit"), bit, tr("Pause before it"), gap_secs * 1000.0)
+    }
+
+    /// Draw the marker for a burst whose first bit is at `x` on a line spanning `line` vertically,
+    /// in a view spanning `across` horizontally; returns the area that shows the tooltip
+    pub fn paint(&self, painter: &Painter, x: f32, line: Rangef, across: Rangef, gap_secs: f64) -> Rect {
+        let tick = Rect::from_min_size(Pos2::new(x - 2.0, line.min - 2.0), Vec2::new(3.0, line.span() + 4.0));
+        painter.rect_filled(tick, 0.0, GAP_COLOR);
+        if self.collapsed {
+            return tick;
+        }
+        let y = line.min - 2.0;
+        painter.hline(across, y, Stroke::new(1.5, GAP_COLOR));
+        let label = painter.text(
+            Pos2::new(across.max - 2.0, y - 1.0),
+            Align2::RIGHT_BOTTOM,
+            format!("{:.1} ms", gap_secs * 1000.0),
+            FontId::proportional(10.0),
+            GAP_COLOR,
+        );
+        tick.union(label)
+    }
+}
@@ -2,6 +2,13 @@
 
 pub mod bit_viewer;
 pub mod byte_viewer;
+pub mod colors;
+pub mod gaps;
+pub mod symbol_viewer;
 
-pub use bit_viewer::{BitShape, BitViewer};
-pub use byte_viewer::{ByteColumn, ByteViewer};
+pub use bit_viewer::{BitShape, BitViewer, FlashHighlight};
+pub use byte_viewer::{ByteColumn, ByteViewer, Endianness, WordKind};
+pub use colors::{rgb, rgba};
+pub use gaps::GapMarkers;
+pub use bit_core::view::BitColors;
+pub use symbol_viewer::{SymbolFormat, SymbolViewer};
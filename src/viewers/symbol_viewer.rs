@@ -0,0 +1,163 @@
+use bitvec::prelude::*;
+use egui::Color32;
+
+use crate::i18n::tr;
+use super::bit_viewer::FlashHighlight;
+
+/// How a symbol's value is written in its cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SymbolFormat {
+    #[default]
+    Hex,
+    Unsigned,
+    Signed,
+    Binary,
+}
+
+impl SymbolFormat {
+    pub const ALL: [SymbolFormat; 4] = [SymbolFormat::Hex, SymbolFormat::Unsigned, SymbolFormat::Signed, SymbolFormat::Binary];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SymbolFormat::Hex => tr("Hex"),
+            SymbolFormat::Unsigned => tr("Unsigned"),
+            SymbolFormat::Signed => tr("Signed"),
+            SymbolFormat::Binary => tr("Binary"),
+        }
+    }
+
+    /// Characters needed for any `bits`-bit value
+    fn width(&self, bits: usize) -> usize {
+        match self {
+            SymbolFormat::Hex => bits.div_ceil(4),
+            SymbolFormat::Unsigned => max_value(bits).to_string().len(),
+            SymbolFormat::Signed => (max_value(bits) / 2 + 1).to_string().len() + 1,
+            SymbolFormat::Binary => bits,
+        }
+    }
+
+    fn format(&self, value: u64, bits: usize) -> String {
+        match self {
+            SymbolFormat::Hex => format!("{:0width$X}", value, width = bits.div_ceil(4)),
+            SymbolFormat::Unsigned => value.to_string(),
+            SymbolFormat::Signed => {
+                let shift = 64 - bits;
+                (((value << shift) as i64) >> shift).to_string()
+            }
+            SymbolFormat::Binary => format!("{:0width$b}", value, width = bits),
+        }
+    }
+}
+
+fn max_value(bits: usize) -> u64 {
+    if bits >= 64 { u64::MAX } else { (1u64 << bits) - 1 }
+}
+
+/// Shows the stream as N-bit symbols, for data that doesn't line up with bytes such as
+/// line-coded streams or ADC samples
+pub struct SymbolViewer {
+    /// Bits per symbol, 1 to 64
+    pub symbol_bits: usize,
+    pub symbols_per_row: usize,
+    /// Bits skipped before the first symbol, to line symbols up with the data
+    pub offset: usize,
+    pub format: SymbolFormat,
+    pub jump_to_bit: Option<usize>,
+    pub flash: Option<FlashHighlight>,
+}
+
+impl Default for SymbolViewer {
+    fn default() -> Self {
+        Self {
+            symbol_bits: 10,
+            symbols_per_row: 16,
+            offset: 0,
+            format: SymbolFormat::Hex,
+            jump_to_bit: None,
+            flash: None,
+        }
+    }
+}
+
+impl SymbolViewer {
+    pub fn render(&mut self, ui: &mut egui::Ui, bits: &BitSlice<u8, Msb0>) {
+        let symbol_bits = self.symbol_bits.clamp(1, 64);
+        let symbols_per_row = self.symbols_per_row.max(1);
+        let offset = self.offset.min(bits.len());
+        let total_symbols = (bits.len() - offset) / symbol_bits;
+        if total_symbols == 0 {
+            ui.label(tr("No data to display"));
+            return;
+        }
+        let total_rows = total_symbols.div_ceil(symbols_per_row);
+
+        let char_width = 8.5;
+        let cell_width = self.format.width(symbol_bits) as f32 * char_width + 10.0;
+        let row_height = 20.0;
+        let offset_width = 90.0;
+
+        let now = ui.input(|i| i.time);
+        let flash_color = self.flash.and_then(|flash| flash.color(now));
+        if flash_color.is_some() {
+            ui.ctx().request_repaint();
+        } else {
+            self.flash = None;
+        }
+
+        let mut scroll_area = egui::ScrollArea::both()
+            .id_salt("symbol_viewer_scroll")
+            .auto_shrink([false, false]);
+
+        if let Some(bit_pos) = self.jump_to_bit.take() {
+            let row = bit_pos.saturating_sub(offset) / symbol_bits / symbols_per_row;
+            scroll_area = scroll_area.vertical_scroll_offset(row as f32 * (row_height + ui.spacing().item_spacing.y));
+        }
+
+        scroll_area.show_rows(ui, row_height, total_rows, |ui, row_range| {
+            for row in row_range {
+                ui.horizontal(|ui| {
+                    let first = row * symbols_per_row;
+                    ui.add_sized(
+                        [offset_width, row_height],
+                        egui::Label::new(
+                            egui::RichText::new(format!("{:>8}", first))
+                                .monospace()
+                                .color(Color32::GRAY),
+                        ),
+                    );
+
+                    for index in first..(first + symbols_per_row).min(total_symbols) {
+                        let start = offset + index * symbol_bits;
+                        let end = start + symbol_bits;
+                        let value = bits[start..end].load_be::<u64>();
+
+                        let (rect, response) = ui.allocate_exact_size(egui::vec2(cell_width, row_height), egui::Sense::hover());
+                        if index % 2 == 1 {
+                            ui.painter().rect_filled(rect, 2.0, ui.visuals().faint_bg_color);
+                        }
+                        if let (Some(flash), Some(color)) = (self.flash, flash_color)
+                            && flash.overlaps(start, end)
+                        {
+                            ui.painter().rect_filled(rect, 2.0, color);
+                        }
+                        ui.painter().text(
+                            rect.center(),
+                            egui::Align2::CENTER_CENTER,
+                            self.format.format(value, symbol_bits),
+                            egui::FontId::monospace(13.0),
+                            ui.visuals().text_color(),
+                        );
+
+                        response.on_hover_ui(|ui| {
+                            ui.label(format!("{} {} ({} {}..{})", tr("Symbol"), index, tr("bits"), start, end));
+                            ui.label(format!("{}: 0x{}", tr("Hex"), SymbolFormat::Hex.format(value, symbol_bits)));
+                            ui.label(format!("{}: {}", tr("Unsigned"), value));
+                            ui.label(format!("{}: {}", tr("Signed"), SymbolFormat::Signed.format(value, symbol_bits)));
+                            ui.label(format!("{}: {}", tr("Binary"), SymbolFormat::Binary.format(value, symbol_bits)));
+                        });
+                    }
+                });
+            }
+        });
+    }
+}
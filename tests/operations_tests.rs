@@ -326,11 +326,25 @@ mod bit_operation_tests {
         let op = BitOperation::LoadFile {
             name: "test".to_string(),
             file_path: PathBuf::from("test.bin"),
+            offset: 0,
+            length: None,
             enabled: true,
         };
         assert!(op.description().contains("test.bin"));
     }
 
+    #[test]
+    fn test_load_file_range_description() {
+        let op = BitOperation::LoadFile {
+            name: "test".to_string(),
+            file_path: PathBuf::from("test.bin"),
+            offset: 16,
+            length: Some(32),
+            enabled: true,
+        };
+        assert_eq!(op.description(), "Load: test.bin bytes 16..48");
+    }
+
     #[test]
     fn test_truncate_bits_basic() {
         let input = bitvec![u8, Msb0; 1, 0, 1, 0, 1, 1, 0, 0, 1, 1];
@@ -338,6 +352,7 @@ mod bit_operation_tests {
             name: "test".to_string(),
             start: 0,
             end: 5,
+            exprs: Default::default(),
             enabled: true,
         };
         let result = op.apply(&input);
@@ -351,6 +366,7 @@ mod bit_operation_tests {
             name: "test".to_string(),
             start: 3,
             end: 7,
+            exprs: Default::default(),
             enabled: true,
         };
         let result = op.apply(&input);
@@ -365,6 +381,7 @@ mod bit_operation_tests {
             name: "test".to_string(),
             start: 5,
             end: usize::MAX,
+            exprs: Default::default(),
             enabled: true,
         };
         let result = op.apply(&input);
@@ -379,6 +396,7 @@ mod bit_operation_tests {
             name: "test".to_string(),
             start: 0,
             end: 100,
+            exprs: Default::default(),
             enabled: true,
         };
         let result = op.apply(&input);
@@ -393,6 +411,7 @@ mod bit_operation_tests {
             name: "test".to_string(),
             start: 10,
             end: 20,
+            exprs: Default::default(),
             enabled: true,
         };
         let result = op.apply(&input);
@@ -407,6 +426,7 @@ mod bit_operation_tests {
             name: "test".to_string(),
             start: 2,
             end: 2,
+            exprs: Default::default(),
             enabled: true,
         };
         let result = op.apply(&input);
@@ -420,6 +440,7 @@ mod bit_operation_tests {
             name: "test".to_string(),
             start: 2,
             end: 3,
+            exprs: Default::default(),
             enabled: true,
         };
         let result = op.apply(&input);
@@ -432,6 +453,7 @@ mod bit_operation_tests {
             name: "test".to_string(),
             start: 100,
             end: 250,
+            exprs: Default::default(),
             enabled: true,
         };
         assert_eq!(op.description(), "Keep bits 100-250");
@@ -477,3 +499,204 @@ mod edge_cases_tests {
         assert_eq!(result, bitvec![u8, Msb0; 1, 1, 1, 1]);
     }
 }
+
+#[cfg(test)]
+mod chunked_apply_tests {
+    use super::*;
+
+    const CHUNK_BITS: usize = 1000;
+
+    fn large_input() -> BitVec<u8, Msb0> {
+        // A few chunks, with a length that isn't a multiple of common periods
+        let bytes: Vec<u8> = (0..(CHUNK_BITS / 2 + 13)).map(|i| (i * 31 % 251) as u8).collect();
+        BitVec::from_vec(bytes)
+    }
+
+    #[test]
+    fn test_chunked_take_skip_matches_apply() {
+        let input = large_input();
+        let op = BitOperation::TakeSkipSequence {
+            name: "test".to_string(),
+            sequence: OperationSequence::from_string("t7s3r5").unwrap(),
+            enabled: true,
+        };
+        let mut chunked = input.clone();
+        assert!(op.apply_in_chunks(&mut chunked, CHUNK_BITS, |_, _| true));
+        assert_eq!(chunked, op.apply(&input));
+    }
+
+    #[test]
+    fn test_chunked_reports_progress() {
+        let input = large_input();
+        let op = BitOperation::InvertBits {
+            name: "test".to_string(),
+            enabled: true,
+        };
+        let mut bits = input.clone();
+        let mut reports = Vec::new();
+        assert!(op.apply_in_chunks(&mut bits, CHUNK_BITS, |processed, total| {
+            reports.push((processed, total));
+            true
+        }));
+
+        assert!(reports.len() > 1);
+        assert_eq!(reports.last(), Some(&(input.len(), input.len())));
+    }
+
+    #[test]
+    fn test_chunked_cancel() {
+        let op = BitOperation::InvertBits {
+            name: "test".to_string(),
+            enabled: true,
+        };
+        let mut bits = large_input();
+        assert!(!op.apply_in_chunks(&mut bits, CHUNK_BITS, |_, _| false));
+    }
+
+    #[test]
+    fn test_chunked_widening_remap_matches_apply() {
+        use bit::processing::SymbolMap;
+
+        let input = large_input();
+        let map = SymbolMap { input_bits: 1, output_bits: 8, table: [(0, 0x0F), (1, 0xF0)].into_iter().collect() };
+        let op = BitOperation::SymbolRemap { name: "test".to_string(), map, enabled: true };
+        assert_eq!(op.chunk_period(), None);
+
+        let mut chunked = input.clone();
+        assert!(op.apply_in_chunks(&mut chunked, 64, |_, _| true));
+        assert_eq!(chunked, op.apply(&input));
+        assert_eq!(chunked.len(), input.len() * 8);
+    }
+
+    #[test]
+    fn test_chunked_nand_layout_matches_apply() {
+        use bit::processing::{NandArea, NandEcc, NandLayout, SpareLayout};
+
+        // 512 + 16 byte pages, several per chunk, the last one partial
+        let input: BitVec<u8, Msb0> = BitVec::from_vec((0..528 * 5 + 100).map(|i| (i * 31 % 251) as u8).collect());
+        let layout = NandLayout {
+            page_size: 512,
+            spare_size: 16,
+            spare_layout: SpareLayout::Interleaved { steps: 2 },
+            keep: NandArea::Spare,
+            ecc: NandEcc::Hamming { sector: 256 },
+            ecc_offset: 0,
+            correct: true,
+        };
+        let op = BitOperation::NandLayout { name: "test".to_string(), layout, enabled: true };
+        assert_eq!(op.chunk_period(), Some(528 * 8));
+
+        let mut chunked = input.clone();
+        assert!(op.apply_in_chunks(&mut chunked, 528 * 8 * 2, |_, _| true));
+        assert_eq!(chunked, op.apply(&input));
+        assert_eq!(chunked.len(), 5 * 16 * 8);
+    }
+
+    #[test]
+    fn test_truncate_not_chunkable() {
+        let op = BitOperation::TruncateBits {
+            name: "test".to_string(),
+            start: 0,
+            end: 10,
+            exprs: Default::default(),
+            enabled: true,
+        };
+        assert_eq!(op.chunk_period(), None);
+    }
+}
+
+#[cfg(test)]
+mod in_place_tests {
+    use super::*;
+    use bit::processing::{BlockInterleaverConfig, ConvolutionalInterleaverConfig, InterleaverDirection, InterleaverType};
+    use bit::processing::interleaver::SymbolInterleaverConfig;
+
+    fn sample_input() -> BitVec<u8, Msb0> {
+        let bytes: Vec<u8> = (0..37).map(|i| (i * 73 % 256) as u8).collect();
+        BitVec::from_vec(bytes)
+    }
+
+    fn interleave_op(
+        interleaver_type: InterleaverType,
+        block_config: Option<BlockInterleaverConfig>,
+        convolutional_config: Option<ConvolutionalInterleaverConfig>,
+        symbol_config: Option<SymbolInterleaverConfig>,
+    ) -> BitOperation {
+        BitOperation::InterleaveBits {
+            name: "test".to_string(),
+            interleaver_type,
+            block_config,
+            convolutional_config,
+            symbol_config,
+            enabled: true,
+        }
+    }
+
+    fn assert_in_place_matches_apply(op: &BitOperation) {
+        let input = sample_input();
+        let mut bits = input.clone();
+        op.apply_in_place(&mut bits);
+        assert_eq!(bits, op.apply(&input), "{}", op.description());
+    }
+
+    #[test]
+    fn test_take_skip_in_place() {
+        let input = bitvec![u8, Msb0; 1, 0, 1, 1, 0, 0, 1, 0, 1, 1, 1, 0];
+        let mut bits = input.clone();
+        OperationSequence::from_string("t4r3i2s1").unwrap().apply_in_place(&mut bits);
+        assert_eq!(bits, bitvec![u8, Msb0; 1, 0, 1, 1, 1, 0, 0, 1, 0, 1, 0]);
+    }
+
+    #[test]
+    fn test_in_place_matches_apply() {
+        let ops = vec![
+            BitOperation::TakeSkipSequence {
+                name: "test".to_string(),
+                sequence: OperationSequence::from_string("s3t5r4i6").unwrap(),
+                enabled: true,
+            },
+            BitOperation::InvertBits {
+                name: "test".to_string(),
+                enabled: true,
+            },
+            BitOperation::TruncateBits {
+                name: "test".to_string(),
+                start: 13,
+                end: 200,
+                exprs: Default::default(),
+                enabled: true,
+            },
+            interleave_op(InterleaverType::Block, Some(BlockInterleaverConfig::new(7, 5, InterleaverDirection::Interleave)), None, None),
+            interleave_op(InterleaverType::Block, Some(BlockInterleaverConfig::new(7, 5, InterleaverDirection::Deinterleave)), None, None),
+            interleave_op(InterleaverType::Convolutional, None, Some(ConvolutionalInterleaverConfig::new(4, 3, InterleaverDirection::Interleave)), None),
+            interleave_op(InterleaverType::Symbol, None, None, Some(SymbolInterleaverConfig::new(5, 3, 4, InterleaverDirection::Interleave))),
+            interleave_op(InterleaverType::Symbol, None, None, Some(SymbolInterleaverConfig::new(5, 3, 4, InterleaverDirection::Deinterleave))),
+        ];
+
+        for op in &ops {
+            assert_in_place_matches_apply(op);
+        }
+    }
+
+    #[test]
+    fn test_truncate_in_place_past_end() {
+        let mut bits = sample_input();
+        let op = BitOperation::TruncateBits {
+            name: "test".to_string(),
+            start: 1000,
+            end: 2000,
+            exprs: Default::default(),
+            enabled: true,
+        };
+        op.apply_in_place(&mut bits);
+        assert!(bits.is_empty());
+    }
+
+    #[test]
+    fn test_symbol_in_place_drops_partial_symbol() {
+        // 37 bytes = 296 bits, 296 / 5 = 59 whole symbols
+        let mut bits = sample_input();
+        SymbolInterleaverConfig::new(5, 3, 4, InterleaverDirection::Interleave).apply_in_place(&mut bits);
+        assert_eq!(bits.len(), 59 * 5);
+    }
+}
@@ -554,6 +554,30 @@ mod pattern_search_tests {
         assert_eq!(pattern.matches.len(), 1);
         assert_eq!(pattern.matches[0].position, 0);
     }
+    
+    #[test]
+    fn test_large_haystack_matches_in_order() {
+        let mut pattern = Pattern::new(
+            "test".to_string(),
+            PatternFormat::Hex,
+            "0xFF".to_string(),
+            0,
+        ).unwrap();
+        
+        // 0xFF every 100 bytes, across enough positions to be split between search tasks
+        let mut bytes = vec![0u8; 10_000];
+        for i in (0..bytes.len()).step_by(100) {
+            bytes[i] = 0xFF;
+        }
+        let haystack = BitVec::<u8, Msb0>::from_vec(bytes);
+        pattern.search(&haystack);
+        
+        assert_eq!(pattern.matches.len(), 100);
+        for (i, m) in pattern.matches.iter().enumerate() {
+            assert_eq!(m.position, i * 800);
+            assert_eq!(m.delta, if i == 0 { None } else { Some(800) });
+        }
+    }
 }
 
 #[cfg(test)]
@@ -640,3 +664,28 @@ mod update_bits_tests {
         assert!(result.is_err());
     }
 }
+
+#[cfg(test)]
+mod display_tests {
+    use super::*;
+    
+    #[test]
+    fn test_color_and_visibility_defaults() {
+        let pattern = Pattern::new("Sync".to_string(), PatternFormat::Hex, "0x1ACF".to_string(), 0).unwrap();
+        assert!(pattern.visible);
+        assert_eq!(pattern.color, None);
+        
+        // Patterns saved before colors and visibility existed are shown in their palette color
+        let saved = r#"{"name":"Sync","format":"Hex","input":"0x1ACF","garbles":0}"#;
+        let loaded: Pattern = serde_json::from_str(saved).unwrap();
+        assert!(loaded.visible);
+        assert_eq!(loaded.color, None);
+        
+        let mut hidden = pattern.clone();
+        hidden.visible = false;
+        hidden.color = Some([10, 200, 30]);
+        let round_trip: Pattern = serde_json::from_str(&serde_json::to_string(&hidden).unwrap()).unwrap();
+        assert!(!round_trip.visible);
+        assert_eq!(round_trip.color, Some([10, 200, 30]));
+    }
+}